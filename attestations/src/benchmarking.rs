@@ -0,0 +1,39 @@
+//! Benchmarking setup for pallet-trustless-file-server-attestations
+#![cfg(feature = "runtime-benchmarks")]
+use super::*;
+
+#[allow(unused)]
+use crate::Pallet as TrustlessFileServerAttestations;
+use codec::Decode;
+use frame_benchmarking::v2::*;
+use frame_support::sp_runtime::traits::TrailingZeroInput;
+use frame_support::traits::EnsureOrigin;
+use pallet_trustless_file_server::FileAttestation;
+
+#[benchmarks]
+mod benchmarks {
+    use super::*;
+
+    #[benchmark]
+    fn receive_attestation() {
+        let origin =
+            T::SiblingOrigin::try_successful_origin().expect("SiblingOrigin must be satisfiable");
+        let attestation = FileAttestation {
+            merkle_root: T::RemoteHash::decode(&mut TrailingZeroInput::zeroes()).unwrap(),
+            owner: T::RemoteAccountId::decode(&mut TrailingZeroInput::zeroes()).unwrap(),
+            size: 1024,
+            pieces: 1,
+        };
+
+        #[extrinsic_call]
+        _(origin as T::RuntimeOrigin, attestation);
+
+        assert_eq!(Attestations::<T>::iter_values().count(), 1);
+    }
+
+    impl_benchmark_test_suite!(
+        TrustlessFileServerAttestations,
+        crate::mock::new_test_ext(),
+        crate::mock::Test
+    );
+}
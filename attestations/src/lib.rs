@@ -0,0 +1,132 @@
+//! # Trustless File Server Attestations Pallet
+//!
+//! Companion of the trustless file server pallet for sibling parachains: it records the
+//! attestations of the files registered in the chain running the file server, sent through XCM
+//! with `attest_file`. Other pallets of the receiving chain can then trust that a file is
+//! registered there, along with its owner, size and pieces, without keeping a copy of it.
+//!
+//! Attestations arrive as an unpaid `Transact` of `receive_attestation` with the `Xcm` origin
+//! kind, so the runtime has to allow unpaid execution from the sending chain and convert the
+//! origin with `XcmPassthrough`. Attestations are kept per sending parachain, the latest one of
+//! every file replacing the previous.
+
+#![cfg_attr(not(feature = "std"), no_std)]
+
+pub use pallet::*;
+pub use weights::*;
+
+#[cfg(test)]
+mod mock;
+
+#[cfg(test)]
+mod tests;
+
+#[cfg(feature = "runtime-benchmarks")]
+mod benchmarking;
+pub mod weights;
+
+#[frame_support::pallet]
+pub mod pallet {
+    use frame_support::pallet_prelude::*;
+    use frame_system::pallet_prelude::*;
+    use pallet_trustless_file_server::FileAttestation;
+    use xcm::latest::{Junction::Parachain, Junctions::X1, MultiLocation};
+
+    use super::*;
+
+    #[pallet::pallet]
+    pub struct Pallet<T>(_);
+
+    #[pallet::config]
+    pub trait Config: frame_system::Config {
+        /// Because this pallet emits events, it depends on the runtime's definition of an event.
+        type RuntimeEvent: From<Event<Self>> + IsType<<Self as frame_system::Config>::RuntimeEvent>;
+
+        /// Origin of the messages sent through XCM, yielding their location, e.g.
+        /// `pallet_xcm::EnsureXcm<IsSiblingParachain>`. Only sibling parachains are accepted.
+        type SiblingOrigin: EnsureOrigin<Self::RuntimeOrigin, Success = MultiLocation>;
+
+        /// Account identifier of the chains sending attestations.
+        type RemoteAccountId: Parameter + MaxEncodedLen;
+
+        /// Hash of the chains sending attestations, in which merkle roots are expressed.
+        type RemoteHash: Parameter + MaxEncodedLen;
+
+        /// The weight information for this pallet.
+        type WeightInfo: WeightInfo;
+    }
+
+    pub type FileAttestationOf<T> =
+        FileAttestation<<T as Config>::RemoteAccountId, <T as Config>::RemoteHash>;
+
+    /// Latest attestation of every file, by the identifier of the parachain sending it and the
+    /// merkle root of the file.
+    #[pallet::storage]
+    #[pallet::getter(fn get_attestation)]
+    pub(super) type Attestations<T: Config> = StorageDoubleMap<
+        _,
+        Twox64Concat,
+        u32,
+        Blake2_128Concat,
+        T::RemoteHash,
+        FileAttestationOf<T>,
+        OptionQuery,
+    >;
+
+    #[pallet::event]
+    #[pallet::generate_deposit(pub(super) fn deposit_event)]
+    pub enum Event<T: Config> {
+        /// A sibling parachain has attested that a file is registered there.
+        AttestationReceived {
+            para_id: u32,
+            merkle_root: T::RemoteHash,
+        },
+    }
+
+    #[pallet::error]
+    pub enum Error<T> {
+        /// The message was not sent by a sibling parachain
+        NotSibling,
+    }
+
+    #[pallet::call]
+    impl<T: Config> Pallet<T> {
+        /// Records the attestation of a file sent by a sibling parachain.
+        ///
+        /// The origin must satisfy [`Config::SiblingOrigin`] and be the location of a sibling
+        /// parachain.
+        #[pallet::call_index(0)]
+        #[pallet::weight(T::WeightInfo::receive_attestation())]
+        pub fn receive_attestation(
+            origin: OriginFor<T>,
+            attestation: FileAttestationOf<T>,
+        ) -> DispatchResult {
+            let location = T::SiblingOrigin::ensure_origin(origin)?;
+            let MultiLocation {
+                parents: 1,
+                interior: X1(Parachain(para_id)),
+            } = location
+            else {
+                return Err(Error::<T>::NotSibling.into());
+            };
+
+            let merkle_root = attestation.merkle_root.clone();
+            Attestations::<T>::insert(para_id, &merkle_root, attestation);
+
+            Self::deposit_event(Event::AttestationReceived {
+                para_id,
+                merkle_root,
+            });
+
+            Ok(())
+        }
+    }
+
+    impl<T: Config> Pallet<T> {
+        /// Whether the parachain `para_id` has attested that a file is registered there under
+        /// `merkle_root`.
+        pub fn is_attested(para_id: u32, merkle_root: &T::RemoteHash) -> bool {
+            Attestations::<T>::contains_key(para_id, merkle_root)
+        }
+    }
+}
@@ -0,0 +1,95 @@
+use crate as pallet_trustless_file_server_attestations;
+use frame_support::traits::{ConstU16, ConstU32, ConstU64, EnsureOrigin, Everything};
+use sp_core::H256;
+use sp_runtime::{
+    testing::Header,
+    traits::{BlakeTwo256, IdentityLookup},
+    BuildStorage,
+};
+use xcm::latest::{Junction::Parachain, Junctions::X1, MultiLocation};
+
+type UncheckedExtrinsic = frame_system::mocking::MockUncheckedExtrinsic<Test>;
+type Block = frame_system::mocking::MockBlock<Test>;
+
+/// Account whose signed origin stands for the relay chain.
+pub const RELAY: u64 = 0;
+
+/// Stands for the origins converted from XCM: signed origins become the location of the
+/// sibling parachain with the same identifier as the account, except for `RELAY`.
+pub struct EnsureXcmLocation;
+
+impl EnsureOrigin<RuntimeOrigin> for EnsureXcmLocation {
+    type Success = MultiLocation;
+
+    fn try_origin(o: RuntimeOrigin) -> Result<Self::Success, RuntimeOrigin> {
+        o.into().and_then(|o| match o {
+            frame_system::RawOrigin::Signed(RELAY) => Ok(MultiLocation::parent()),
+            frame_system::RawOrigin::Signed(id) => {
+                Ok(MultiLocation::new(1, X1(Parachain(id as u32))))
+            }
+            r => Err(RuntimeOrigin::from(r)),
+        })
+    }
+
+    #[cfg(feature = "runtime-benchmarks")]
+    fn try_successful_origin() -> Result<RuntimeOrigin, ()> {
+        Ok(RuntimeOrigin::signed(2000))
+    }
+}
+
+// Configure a mock runtime to test the pallet.
+frame_support::construct_runtime!(
+    pub enum Test where
+        Block = Block,
+        NodeBlock = Block,
+        UncheckedExtrinsic = UncheckedExtrinsic,
+    {
+        System: frame_system,
+        TrustlessFileServerAttestations: pallet_trustless_file_server_attestations,
+    }
+);
+
+impl frame_system::Config for Test {
+    type BaseCallFilter = Everything;
+    type BlockWeights = ();
+    type BlockLength = ();
+    type RuntimeOrigin = RuntimeOrigin;
+    type RuntimeCall = RuntimeCall;
+    type Index = u64;
+    type BlockNumber = u64;
+    type Hash = H256;
+    type Hashing = BlakeTwo256;
+    type AccountId = u64;
+    type Lookup = IdentityLookup<Self::AccountId>;
+    type Header = Header;
+    type RuntimeEvent = RuntimeEvent;
+    type BlockHashCount = ConstU64<250>;
+    type DbWeight = ();
+    type Version = ();
+    type PalletInfo = PalletInfo;
+    type AccountData = ();
+    type OnNewAccount = ();
+    type OnKilledAccount = ();
+    type SystemWeightInfo = ();
+    type SS58Prefix = ConstU16<42>;
+    type OnSetCode = ();
+    type MaxConsumers = ConstU32<16>;
+}
+
+impl pallet_trustless_file_server_attestations::Config for Test {
+    type RuntimeEvent = RuntimeEvent;
+    type SiblingOrigin = EnsureXcmLocation;
+    type RemoteAccountId = u64;
+    type RemoteHash = H256;
+    type WeightInfo = ();
+}
+
+// Build genesis storage according to the mock runtime.
+pub fn new_test_ext() -> sp_io::TestExternalities {
+    let storage = frame_system::GenesisConfig::default()
+        .build_storage::<Test>()
+        .unwrap();
+    let mut ext: sp_io::TestExternalities = storage.into();
+    ext.execute_with(|| System::set_block_number(1));
+    ext
+}
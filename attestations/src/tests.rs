@@ -0,0 +1,69 @@
+use frame_support::{assert_noop, assert_ok};
+use pallet_trustless_file_server::FileAttestation;
+use sp_runtime::testing::H256;
+
+use crate::mock::*;
+use crate::{Error, Event};
+
+fn attestation(owner: u64) -> FileAttestation<u64, H256> {
+    FileAttestation {
+        merkle_root: H256::repeat_byte(7),
+        owner,
+        size: 12000,
+        pieces: 12,
+    }
+}
+
+#[test]
+fn it_should_record_attestations_of_siblings() {
+    new_test_ext().execute_with(|| {
+        let merkle_root = H256::repeat_byte(7);
+        assert_ok!(TrustlessFileServerAttestations::receive_attestation(
+            RuntimeOrigin::signed(2000),
+            attestation(1)
+        ));
+        System::assert_last_event(
+            Event::AttestationReceived {
+                para_id: 2000,
+                merkle_root,
+            }
+            .into(),
+        );
+        assert_eq!(
+            TrustlessFileServerAttestations::get_attestation(2000, merkle_root),
+            Some(attestation(1))
+        );
+        assert!(TrustlessFileServerAttestations::is_attested(2000, &merkle_root));
+        assert!(!TrustlessFileServerAttestations::is_attested(2001, &merkle_root));
+
+        // the latest attestation replaces the previous one
+        assert_ok!(TrustlessFileServerAttestations::receive_attestation(
+            RuntimeOrigin::signed(2000),
+            attestation(2)
+        ));
+        assert_eq!(
+            TrustlessFileServerAttestations::get_attestation(2000, merkle_root),
+            Some(attestation(2))
+        );
+    });
+}
+
+#[test]
+fn it_should_only_accept_attestations_of_siblings() {
+    new_test_ext().execute_with(|| {
+        assert_noop!(
+            TrustlessFileServerAttestations::receive_attestation(
+                RuntimeOrigin::signed(RELAY),
+                attestation(1)
+            ),
+            Error::<Test>::NotSibling
+        );
+        assert_noop!(
+            TrustlessFileServerAttestations::receive_attestation(
+                RuntimeOrigin::root(),
+                attestation(1)
+            ),
+            sp_runtime::traits::BadOrigin
+        );
+    });
+}
@@ -0,0 +1,72 @@
+//! Autogenerated weights for pallet_trustless_file_server_attestations
+//!
+//! THIS FILE WAS AUTO-GENERATED USING THE SUBSTRATE BENCHMARK CLI VERSION 4.0.0-dev
+//! DATE: 2023-06-14, STEPS: `50`, REPEAT: `100`, LOW RANGE: `[]`, HIGH RANGE: `[]`
+//! WORST CASE MAP SIZE: `1000000`
+//! HOSTNAME: `Joses-MacBook-Pro.local`, CPU: `<UNKNOWN>`
+//! EXECUTION: Some(Wasm), WASM-EXECUTION: Compiled, CHAIN: Some("dev"), DB CACHE: 1024
+
+// Executed Command:
+// ./target/production/node-template
+// benchmark
+// pallet
+// --chain
+// dev
+// --pallet
+// pallet_trustless_file_server_attestations
+// --execution=wasm
+// --wasm-execution=compiled
+// --extrinsic
+// *
+// --steps
+// 50
+// --repeat
+// 100
+// --output
+// weights.rs
+// --template
+// ./.maintain/frame-weight-template.hbs
+// --heap-pages
+// 4096
+
+#![cfg_attr(rustfmt, rustfmt_skip)]
+#![allow(unused_parens)]
+#![allow(unused_imports)]
+#![allow(missing_docs)]
+
+use frame_support::{traits::Get, weights::{Weight, constants::RocksDbWeight}};
+use core::marker::PhantomData;
+
+/// Weight functions needed for pallet_trustless_file_server_attestations.
+pub trait WeightInfo {
+	fn receive_attestation() -> Weight;
+}
+
+/// Weights for pallet_trustless_file_server_attestations using the Substrate node and recommended hardware.
+pub struct SubstrateWeight<T>(PhantomData<T>);
+impl<T: frame_system::Config> WeightInfo for SubstrateWeight<T> {
+	/// Storage: TrustlessFileServerAttestations Attestations (r:0 w:1)
+	/// Proof: TrustlessFileServerAttestations Attestations (max_values: None, max_size: Some(140), added: 2615, mode: MaxEncodedLen)
+	fn receive_attestation() -> Weight {
+		// Proof Size summary in bytes:
+		//  Measured:  `0`
+		//  Estimated: `0`
+		// Minimum execution time: 12_000_000 picoseconds.
+		Weight::from_parts(13_000_000, 0)
+			.saturating_add(T::DbWeight::get().writes(1_u64))
+	}
+}
+
+// For backwards compatibility and tests
+impl WeightInfo for () {
+	/// Storage: TrustlessFileServerAttestations Attestations (r:0 w:1)
+	/// Proof: TrustlessFileServerAttestations Attestations (max_values: None, max_size: Some(140), added: 2615, mode: MaxEncodedLen)
+	fn receive_attestation() -> Weight {
+		// Proof Size summary in bytes:
+		//  Measured:  `0`
+		//  Estimated: `0`
+		// Minimum execution time: 12_000_000 picoseconds.
+		Weight::from_parts(13_000_000, 0)
+			.saturating_add(RocksDbWeight::get().writes(1_u64))
+	}
+}
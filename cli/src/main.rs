@@ -0,0 +1,299 @@
+//! # tfs-cli
+//!
+//! Command line companion of the trustless file server pallet. It uploads local files through
+//! `upload_file` and downloads them back piece by piece through the RPC, checking every piece
+//! against its merkle proof and printing a verification report.
+//!
+//! ```shell
+//! $ tfs-cli --url ws://127.0.0.1:9944 upload --suri //Alice ./img/substrate.png
+//! $ tfs-cli verify 18d35a4d731e0785fe855b4ed59033e96607137da7bb875c03f6cea5d1f8cacf
+//! $ tfs-cli download 18d35a4d731e0785fe855b4ed59033e96607137da7bb875c03f6cea5d1f8cacf out.png
+//! ```
+use std::io::Write;
+use std::path::{Path, PathBuf};
+
+use anyhow::{anyhow, bail, Context, Result};
+use base64::Engine;
+use clap::{Parser, Subcommand};
+use jsonrpsee::core::client::ClientT;
+use jsonrpsee::rpc_params;
+use jsonrpsee::ws_client::{WsClient, WsClientBuilder};
+use sha2::{Digest, Sha256};
+use subxt::dynamic::Value;
+use subxt::ext::sp_core::{sr25519, Pair};
+use subxt::tx::PairSigner;
+use subxt::{OnlineClient, PolkadotConfig};
+
+use verifier::{verify_piece, VerifiedReader};
+
+mod verifier;
+
+#[derive(Parser)]
+#[command(name = "tfs-cli", version, about)]
+struct Cli {
+    /// WebSocket endpoint of the node.
+    #[arg(long, global = true, default_value = "ws://127.0.0.1:9944")]
+    url: String,
+    #[command(subcommand)]
+    command: Command,
+}
+
+#[derive(Subcommand)]
+enum Command {
+    /// Uploads a file and verifies it once it is included in a block.
+    Upload {
+        /// Secret URI of the uploader account.
+        #[arg(long, default_value = "//Alice")]
+        suri: String,
+        file: PathBuf,
+    },
+    /// Downloads every piece of a registered file and checks it against its merkle proof.
+    Verify {
+        /// Hex encoded merkle root of the file.
+        merkle_root: String,
+        /// Local copy to compare the downloaded content with.
+        #[arg(long)]
+        file: Option<PathBuf>,
+    },
+    /// Downloads a registered file, writing every piece as soon as it is verified and stopping
+    /// at the first invalid one.
+    Download {
+        /// Hex encoded merkle root of the file.
+        merkle_root: String,
+        output: PathBuf,
+    },
+}
+
+#[derive(serde::Deserialize)]
+struct HashItem {
+    merkle_root: String,
+}
+
+#[derive(serde::Deserialize)]
+struct FileInfo {
+    merkle_root: String,
+    size: u64,
+    pieces: u32,
+    chunk_size: u32,
+    #[serde(default)]
+    bep52: bool,
+    sha256: Option<String>,
+}
+
+#[derive(serde::Deserialize)]
+struct MerkleProof {
+    ipfs_hash: String,
+    proof: Vec<String>,
+}
+
+#[derive(serde::Deserialize)]
+struct Base64Content {
+    content: String,
+}
+
+#[tokio::main]
+async fn main() -> Result<()> {
+    let cli = Cli::parse();
+    let client = WsClientBuilder::default()
+        .build(&cli.url)
+        .await
+        .with_context(|| format!("Could not connect to {}", cli.url))?;
+    match cli.command {
+        Command::Upload { suri, file } => {
+            let bytes = std::fs::read(&file)
+                .with_context(|| format!("Could not read {}", file.display()))?;
+            upload(&cli.url, &suri, &bytes).await?;
+            let merkle_root = find_file(&client, &bytes).await?;
+            verify(&client, &merkle_root, Some(&bytes)).await
+        }
+        Command::Verify { merkle_root, file } => {
+            let local = file
+                .map(|file| {
+                    std::fs::read(&file)
+                        .with_context(|| format!("Could not read {}", file.display()))
+                })
+                .transpose()?;
+            verify(&client, &merkle_root, local.as_deref()).await
+        }
+        Command::Download {
+            merkle_root,
+            output,
+        } => download(&client, &merkle_root, &output).await,
+    }
+}
+
+/// Submits `upload_file` signed by `suri` and waits for it to succeed in a block.
+async fn upload(url: &str, suri: &str, bytes: &[u8]) -> Result<()> {
+    let api = OnlineClient::<PolkadotConfig>::from_url(url).await?;
+    let pair = sr25519::Pair::from_string(suri, None)
+        .map_err(|err| anyhow!("Invalid secret URI: {:?}", err))?;
+    let signer = PairSigner::new(pair);
+    // no deposit asset nor license
+    let call = subxt::dynamic::tx(
+        "TrustlessFileServer",
+        "upload_file",
+        vec![
+            Value::from_bytes(bytes),
+            Value::unnamed_variant("None", []),
+            Value::unnamed_variant("None", []),
+        ],
+    );
+    let events = api
+        .tx()
+        .sign_and_submit_then_watch_default(&call, &signer)
+        .await?
+        .wait_for_in_block()
+        .await?
+        .wait_for_success()
+        .await?;
+    println!("Uploaded {} bytes in block {:?}", bytes.len(), events.block_hash());
+    Ok(())
+}
+
+/// Looks up the merkle root of the registered file with the same sha256 as `bytes`.
+async fn find_file(client: &WsClient, bytes: &[u8]) -> Result<String> {
+    let sha256 = array_bytes::bytes2hex("", Sha256::digest(bytes));
+    let files: Vec<HashItem> = client
+        .request("trustless_file_server_get_files", rpc_params![None::<String>])
+        .await?;
+    for file in files {
+        let info = file_info(client, &file.merkle_root).await?;
+        if info.sha256.as_deref() == Some(sha256.as_str()) {
+            return Ok(info.merkle_root);
+        }
+    }
+    bail!("The uploaded file is not registered")
+}
+
+async fn file_info(client: &WsClient, merkle_root: &str) -> Result<FileInfo> {
+    let info = client
+        .request("trustless_file_server_get_file_info", rpc_params![None::<String>, merkle_root])
+        .await?;
+    Ok(info)
+}
+
+/// Downloads every piece of a file, checks them against their merkle proofs and the whole
+/// content against the on-chain checksum and `local`, if given. Fails if any check does not pass.
+async fn verify(client: &WsClient, merkle_root: &str, local: Option<&[u8]>) -> Result<()> {
+    let info = file_info(client, merkle_root).await?;
+    let root = array_bytes::hex2bytes(&info.merkle_root)
+        .map_err(|err| anyhow!("Invalid merkle root: {:?}", err))?;
+    println!(
+        "File {}: {} bytes in {} pieces of {} bytes",
+        info.merkle_root, info.size, info.pieces, info.chunk_size
+    );
+
+    let mut content = Vec::with_capacity(info.size as usize);
+    let mut invalid = 0;
+    for position in 0..u64::from(info.pieces) {
+        let piece = fetch_piece(client, &info.merkle_root, position).await?;
+        let valid = verify_piece(&root, position, &piece.chunk, padded_size(&info), &piece.proof);
+        if !valid {
+            invalid += 1;
+        }
+        println!("  piece {:>3}  {}  {}", position, piece.ipfs_hash, status(valid));
+        content.extend(piece.chunk);
+    }
+
+    let mut passed = invalid == 0;
+    println!("Pieces: {}/{} valid", info.pieces - invalid, info.pieces);
+    if let Some(expected) = &info.sha256 {
+        let valid = &array_bytes::bytes2hex("", Sha256::digest(&content)) == expected;
+        println!("Checksum: {}", status(valid));
+        passed &= valid;
+    }
+    if let Some(local) = local {
+        let valid = local == content.as_slice();
+        println!("Local copy: {}", status(valid));
+        passed &= valid;
+    }
+    if !passed {
+        bail!("Verification failed");
+    }
+    Ok(())
+}
+
+/// Downloads a file piece by piece into `output`, verifying them as they arrive.
+async fn download(client: &WsClient, merkle_root: &str, output: &Path) -> Result<()> {
+    let info = file_info(client, merkle_root).await?;
+    let root = array_bytes::hex2bytes(&info.merkle_root)
+        .map_err(|err| anyhow!("Invalid merkle root: {:?}", err))?;
+    let mut file = std::fs::File::create(output)
+        .with_context(|| format!("Could not create {}", output.display()))?;
+
+    // pieces are verified and written while the next ones are being fetched
+    let (sender, receiver) = std::sync::mpsc::channel();
+    let chunk_size = padded_size(&info);
+    let writer = tokio::task::spawn_blocking(move || -> Result<usize> {
+        let mut written = 0;
+        for chunk in VerifiedReader::new(root, chunk_size, receiver.into_iter()) {
+            let chunk = chunk?;
+            file.write_all(&chunk)?;
+            written += chunk.len();
+        }
+        Ok(written)
+    });
+    for position in 0..u64::from(info.pieces) {
+        let piece = fetch_piece(client, &info.merkle_root, position).await?;
+        if sender.send((piece.chunk, piece.proof)).is_err() {
+            // the writer has stopped at an invalid piece
+            break;
+        }
+    }
+    drop(sender);
+    let written = writer.await??;
+    println!("Downloaded {} bytes into {}", written, output.display());
+    Ok(())
+}
+
+/// A piece of a file as served by the RPC, along with its merkle proof.
+struct Piece {
+    chunk: Vec<u8>,
+    proof: Vec<Vec<u8>>,
+    ipfs_hash: String,
+}
+
+async fn fetch_piece(client: &WsClient, merkle_root: &str, position: u64) -> Result<Piece> {
+    let chunk: Base64Content = client
+        .request(
+            "trustless_file_server_download_base64",
+            rpc_params![None::<String>, merkle_root, position],
+        )
+        .await?;
+    let chunk = base64::engine::general_purpose::STANDARD.decode(chunk.content)?;
+    let proof: MerkleProof = client
+        .request(
+            "trustless_file_server_get_proof",
+            rpc_params![None::<String>, merkle_root, position],
+        )
+        .await?;
+    let siblings = proof
+        .proof
+        .iter()
+        .map(|hash| array_bytes::hex2bytes(hash))
+        .collect::<Result<Vec<_>, _>>()
+        .map_err(|err| anyhow!("Invalid proof: {:?}", err))?;
+    Ok(Piece {
+        chunk,
+        proof: siblings,
+        ipfs_hash: proof.ipfs_hash,
+    })
+}
+
+/// Size chunks are zero-padded up to before hashing them. BEP-52 trees hash the last chunk as
+/// is, which [`verify_piece`] expects as a size of zero.
+fn padded_size(info: &FileInfo) -> usize {
+    if info.bep52 {
+        0
+    } else {
+        info.chunk_size as usize
+    }
+}
+
+fn status(valid: bool) -> &'static str {
+    if valid {
+        "ok"
+    } else {
+        "INVALID"
+    }
+}
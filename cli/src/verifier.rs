@@ -0,0 +1,147 @@
+//! Verification of downloaded pieces against the merkle root of their file.
+use anyhow::{anyhow, Result};
+use sha2::{Digest, Sha256};
+
+/// Hashes `chunk` as stored in the tree, i.e. zero-padded up to `chunk_size`, and folds it
+/// with its proof up to the merkle root. A `chunk_size` of zero leaves the chunk unpadded.
+pub fn verify_piece(
+    merkle_root: &[u8],
+    position: u64,
+    chunk: &[u8],
+    chunk_size: usize,
+    proof: &[Vec<u8>],
+) -> bool {
+    let mut padded = chunk.to_vec();
+    padded.resize(chunk_size.max(chunk.len()), 0);
+    let mut current = Sha256::digest(&padded);
+    let mut index = position;
+    for sibling in proof {
+        let mut hasher = Sha256::new();
+        if index % 2 == 0 {
+            hasher.update(current);
+            hasher.update(sibling);
+        } else {
+            hasher.update(sibling);
+            hasher.update(current);
+        }
+        current = hasher.finalize();
+        index /= 2;
+    }
+    index == 0 && current.as_slice() == merkle_root
+}
+
+/// Pull-based verification of a file, e.g. for media players that want to play it while it is
+/// being downloaded.
+///
+/// Wraps an iterator of `(chunk, proof)` pairs, in piece order, and yields the content of each
+/// chunk once it matches the merkle root. The first chunk that does not match is yielded as an
+/// error, and nothing is yielded after it.
+pub struct VerifiedReader<I> {
+    pieces: I,
+    merkle_root: Vec<u8>,
+    chunk_size: usize,
+    position: u64,
+    failed: bool,
+}
+
+impl<I> VerifiedReader<I>
+where
+    I: Iterator<Item = (Vec<u8>, Vec<Vec<u8>>)>,
+{
+    /// Verifies `pieces` against `merkle_root`, padding chunks up to `chunk_size` as
+    /// [`verify_piece`] does.
+    pub fn new(merkle_root: Vec<u8>, chunk_size: usize, pieces: I) -> Self {
+        Self {
+            pieces,
+            merkle_root,
+            chunk_size,
+            position: 0,
+            failed: false,
+        }
+    }
+}
+
+impl<I> Iterator for VerifiedReader<I>
+where
+    I: Iterator<Item = (Vec<u8>, Vec<Vec<u8>>)>,
+{
+    type Item = Result<Vec<u8>>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.failed {
+            return None;
+        }
+        let (chunk, proof) = self.pieces.next()?;
+        let position = self.position;
+        if !verify_piece(&self.merkle_root, position, &chunk, self.chunk_size, &proof) {
+            self.failed = true;
+            return Some(Err(anyhow!("Piece {} does not match the merkle root", position)));
+        }
+        self.position += 1;
+        Some(Ok(chunk))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn hash(data: &[u8]) -> Vec<u8> {
+        Sha256::digest(data).to_vec()
+    }
+
+    #[test]
+    fn test_verify_piece_should_pad_the_last_chunk() {
+        // two pieces of 4 bytes, the last one only partially filled
+        let first = hash(b"abcd");
+        let last = hash(b"ef\0\0");
+        let merkle_root = hash(&[first.clone(), last.clone()].concat());
+        assert!(verify_piece(&merkle_root, 0, b"abcd", 4, &[last.clone()]));
+        assert!(verify_piece(&merkle_root, 1, b"ef", 4, &[first.clone()]));
+        assert!(!verify_piece(&merkle_root, 1, b"eg", 4, &[first.clone()]));
+        assert!(!verify_piece(&merkle_root, 0, b"ef", 4, &[first]));
+        assert!(!verify_piece(&merkle_root, 2, b"abcd", 4, &[last]));
+    }
+
+    #[test]
+    fn test_verify_piece_should_not_pad_bep52_chunks() {
+        let first = hash(b"abcd");
+        let last = hash(b"ef");
+        let merkle_root = hash(&[first.clone(), last].concat());
+        assert!(verify_piece(&merkle_root, 1, b"ef", 0, &[first.clone()]));
+        assert!(!verify_piece(&merkle_root, 1, b"ef", 4, &[first]));
+    }
+
+    #[test]
+    fn test_verified_reader_should_stop_at_the_first_bad_chunk() {
+        let first = hash(b"abcd");
+        let last = hash(b"ef\0\0");
+        let merkle_root = hash(&[first.clone(), last.clone()].concat());
+
+        let pieces = vec![
+            (b"abcd".to_vec(), vec![last.clone()]),
+            (b"ef".to_vec(), vec![first.clone()]),
+        ];
+        let content: Vec<u8> = VerifiedReader::new(merkle_root.clone(), 4, pieces.into_iter())
+            .collect::<Result<Vec<_>>>()
+            .unwrap()
+            .concat();
+        assert_eq!(content, b"abcdef");
+
+        let pieces = vec![
+            (b"abce".to_vec(), vec![last.clone()]),
+            (b"ef".to_vec(), vec![first.clone()]),
+        ];
+        let mut reader = VerifiedReader::new(merkle_root.clone(), 4, pieces.into_iter());
+        assert!(reader.next().unwrap().is_err());
+        assert!(reader.next().is_none());
+
+        // chunks are checked against their position in the stream
+        let pieces = vec![
+            (b"ef".to_vec(), vec![first]),
+            (b"abcd".to_vec(), vec![last]),
+        ];
+        let mut reader = VerifiedReader::new(merkle_root, 4, pieces.into_iter());
+        assert!(reader.next().unwrap().is_err());
+    }
+}
@@ -0,0 +1,417 @@
+//! Benchmarking setup for pallet-trustless-file-server-deals
+#![cfg(feature = "runtime-benchmarks")]
+use super::*;
+
+#[allow(unused)]
+use crate::Pallet as TrustlessFileServerDeals;
+use codec::{Decode, Encode};
+use frame_benchmarking::v2::*;
+use frame_support::sp_runtime::traits::{Bounded, IdentifyAccount, One};
+use frame_support::traits::{Currency, EnsureOrigin};
+use frame_support::BoundedVec;
+use frame_system::RawOrigin;
+use pallet_trustless_file_server::{BalanceOf, Pallet as TrustlessFileServer};
+use sp_std::vec::Vec;
+
+fn fund<T: Config>(who: &T::AccountId) {
+    let balance = BalanceOf::<T>::max_value() / 2u32.into();
+    T::Currency::make_free_balance_be(who, balance);
+}
+
+/// Registers a file of `size` bytes, returning its merkle root along with its content.
+fn upload<T: Config>(size: u32) -> (T::Hash, Vec<u8>) {
+    let bytes = vec![(size % u8::MAX as u32) as u8; size as usize];
+    let origin = <T as pallet_trustless_file_server::Config>::UploadOrigin::try_successful_origin()
+        .expect("UploadOrigin must be satisfiable");
+    let caller =
+        <T as pallet_trustless_file_server::Config>::UploadOrigin::ensure_origin(origin.clone())
+            .unwrap();
+    fund::<T>(&caller);
+    TrustlessFileServer::<T>::upload_file(origin, bytes.clone(), None, None).unwrap();
+    let merkle_root = &TrustlessFileServer::<T>::get_files()[0].0;
+    (T::Hash::decode(&mut merkle_root.as_slice()).unwrap(), bytes)
+}
+
+fn provider<T: Config>() -> T::AccountId {
+    let provider: T::AccountId = account("provider", 0, 0);
+    fund::<T>(&provider);
+    TrustlessFileServerDeals::<T>::register_provider(RawOrigin::Signed(provider.clone()).into())
+        .unwrap();
+    provider
+}
+
+/// Proposes a deal on a file of `size` bytes, returning its identifier and the file content.
+fn propose<T: Config>(size: u32) -> (DealId, Vec<u8>) {
+    let (merkle_root, bytes) = upload::<T>(size);
+    let client: T::AccountId = account("client", 0, 0);
+    fund::<T>(&client);
+    let duration = T::MinDealDuration::get().max(One::one());
+    TrustlessFileServerDeals::<T>::propose_deal(
+        RawOrigin::Signed(client).into(),
+        merkle_root,
+        duration,
+        T::Currency::minimum_balance() * 1000u32.into(),
+    )
+    .unwrap();
+    (NextDealId::<T>::get() - 1, bytes)
+}
+
+/// Opens a channel from the account of the benchmark signer, returning its identifier along with
+/// the server account.
+fn open<T: Config>() -> (ChannelId, T::AccountId) {
+    let sender = T::BenchmarkHelper::signer().into_account();
+    fund::<T>(&sender);
+    let server: T::AccountId = account("server", 0, 0);
+    fund::<T>(&server);
+    let price = T::Currency::minimum_balance();
+    TrustlessFileServerDeals::<T>::open_channel(
+        RawOrigin::Signed(sender).into(),
+        server.clone(),
+        price * 1000u32.into(),
+        price,
+    )
+    .unwrap();
+    (NextChannelId::<T>::get() - 1, server)
+}
+
+#[benchmarks]
+mod benchmarks {
+    use super::*;
+
+    #[benchmark]
+    fn register_provider() {
+        let caller: T::AccountId = whitelisted_caller();
+        fund::<T>(&caller);
+
+        #[extrinsic_call]
+        _(RawOrigin::Signed(caller.clone()));
+
+        assert!(Providers::<T>::contains_key(caller));
+    }
+
+    #[benchmark]
+    fn unregister_provider() {
+        let caller = provider::<T>();
+
+        #[extrinsic_call]
+        _(RawOrigin::Signed(caller.clone()));
+
+        assert!(!Providers::<T>::contains_key(caller));
+    }
+
+    #[benchmark]
+    fn propose_deal() {
+        let (merkle_root, _) = upload::<T>(1024);
+        let caller: T::AccountId = whitelisted_caller();
+        fund::<T>(&caller);
+        let price = T::Currency::minimum_balance() * 1000u32.into();
+
+        #[extrinsic_call]
+        _(RawOrigin::Signed(caller), merkle_root, T::MinDealDuration::get(), price);
+
+        assert!(Deals::<T>::contains_key(0));
+    }
+
+    #[benchmark]
+    fn accept_deal() {
+        let (deal_id, _) = propose::<T>(1024);
+        let caller = provider::<T>();
+
+        #[extrinsic_call]
+        _(RawOrigin::Signed(caller), deal_id);
+
+        assert!(matches!(Deals::<T>::get(deal_id).unwrap().state, DealState::Active { .. }));
+    }
+
+    // files no bigger than the minimum chunk size are a single chunk, so the whole file is the
+    // challenged one
+    #[benchmark]
+    fn respond_challenge(x: Linear<1, 1024>) {
+        let (deal_id, bytes) = propose::<T>(x);
+        let caller = provider::<T>();
+        TrustlessFileServerDeals::<T>::accept_deal(
+            RawOrigin::Signed(caller.clone()).into(),
+            deal_id,
+        )
+        .unwrap();
+        let start = frame_system::Pallet::<T>::block_number();
+        frame_system::Pallet::<T>::set_block_number(start + T::ChallengePeriod::get());
+
+        #[extrinsic_call]
+        _(RawOrigin::Signed(caller), deal_id, bytes);
+
+        // either completed or proven until now
+        assert!(!matches!(
+            Deals::<T>::get(deal_id),
+            Some(Deal { state: DealState::Active { proven_until, .. }, .. }) if proven_until == start
+        ));
+    }
+
+    #[benchmark]
+    fn respond_challenge_privately() {
+        let (deal_id, _) = propose::<T>(1024);
+        let caller = provider::<T>();
+        TrustlessFileServerDeals::<T>::accept_deal(
+            RawOrigin::Signed(caller.clone()).into(),
+            deal_id,
+        )
+        .unwrap();
+        let start = frame_system::Pallet::<T>::block_number();
+        frame_system::Pallet::<T>::set_block_number(start + T::ChallengePeriod::get());
+        let Some(Deal {
+            merkle_root,
+            state: DealState::Active { challenge, .. },
+            ..
+        }) = Deals::<T>::get(deal_id)
+        else {
+            panic!("the deal is not active");
+        };
+        let nonce = TrustlessFileServerDeals::<T>::challenge_nonce(deal_id, &challenge);
+        let proof =
+            T::BenchmarkHelper::prove_chunk(merkle_root.as_ref(), challenge.position, &nonce);
+
+        #[extrinsic_call]
+        _(RawOrigin::Signed(caller), deal_id, BoundedVec::truncate_from(proof));
+
+        assert!(!matches!(
+            Deals::<T>::get(deal_id),
+            Some(Deal { state: DealState::Active { proven_until, .. }, .. }) if proven_until == start
+        ));
+    }
+
+    // the provider walks away, forfeiting the collateral
+    #[benchmark]
+    fn terminate_deal() {
+        let (deal_id, _) = propose::<T>(1024);
+        let caller = provider::<T>();
+        TrustlessFileServerDeals::<T>::accept_deal(
+            RawOrigin::Signed(caller.clone()).into(),
+            deal_id,
+        )
+        .unwrap();
+
+        #[extrinsic_call]
+        _(RawOrigin::Signed(caller), deal_id);
+
+        assert!(!Deals::<T>::contains_key(deal_id));
+    }
+
+    #[benchmark]
+    fn settle_deal() {
+        let (deal_id, _) = propose::<T>(1024);
+        let provider = provider::<T>();
+        TrustlessFileServerDeals::<T>::accept_deal(RawOrigin::Signed(provider).into(), deal_id)
+            .unwrap();
+        let now = frame_system::Pallet::<T>::block_number() + T::SettlementPeriod::get();
+        frame_system::Pallet::<T>::set_block_number(now);
+        // as if the provider had just answered a challenge
+        Deals::<T>::mutate(deal_id, |deal| {
+            if let Some(Deal {
+                state:
+                    DealState::Active {
+                        proven_until,
+                        challenge,
+                        ..
+                    },
+                ..
+            }) = deal
+            {
+                *proven_until = now;
+                challenge.deadline = now;
+            }
+        });
+        let caller: T::AccountId = whitelisted_caller();
+
+        #[extrinsic_call]
+        _(RawOrigin::Signed(caller), deal_id);
+
+        assert!(matches!(
+            Deals::<T>::get(deal_id),
+            Some(Deal { state: DealState::Active { paid_until, .. }, .. }) if paid_until == now
+        ));
+    }
+
+    // the provider misses its first challenge
+    #[benchmark]
+    fn claim_deal_collateral() {
+        let (deal_id, _) = propose::<T>(1024);
+        let provider = provider::<T>();
+        TrustlessFileServerDeals::<T>::accept_deal(RawOrigin::Signed(provider).into(), deal_id)
+            .unwrap();
+        let start = frame_system::Pallet::<T>::block_number();
+        frame_system::Pallet::<T>::set_block_number(start + T::ChallengePeriod::get() + One::one());
+        let client: T::AccountId = account("client", 0, 0);
+
+        #[extrinsic_call]
+        _(RawOrigin::Signed(client), deal_id);
+
+        assert!(!Deals::<T>::contains_key(deal_id));
+    }
+
+    #[benchmark]
+    fn open_channel() {
+        let caller: T::AccountId = whitelisted_caller();
+        fund::<T>(&caller);
+        let server: T::AccountId = account("server", 0, 0);
+        let price = T::Currency::minimum_balance();
+
+        #[extrinsic_call]
+        _(RawOrigin::Signed(caller), server, price * 1000u32.into(), price);
+
+        assert!(Channels::<T>::contains_key(0));
+    }
+
+    // every voucher has its signature verified
+    #[benchmark]
+    fn redeem_vouchers(n: Linear<1, 8>) {
+        let (channel_id, server) = open::<T>();
+        let signer = T::BenchmarkHelper::signer();
+        let vouchers: Vec<_> = (0..n)
+            .map(|sequence| {
+                let voucher = Voucher {
+                    channel_id,
+                    sequence: sequence.into(),
+                    merkle_root: T::Hash::default(),
+                    position: sequence.into(),
+                    price: T::Currency::minimum_balance(),
+                };
+                let signature = T::BenchmarkHelper::sign(&signer, &voucher.encode());
+                (voucher, signature)
+            })
+            .collect();
+        let vouchers = BoundedVec::try_from(vouchers).unwrap();
+
+        #[extrinsic_call]
+        _(RawOrigin::Signed(server), channel_id, vouchers);
+
+        assert_eq!(Channels::<T>::get(channel_id).unwrap().next_sequence, n as u64);
+    }
+
+    // the sender settles the channel once the close period is over
+    #[benchmark]
+    fn close_channel() {
+        let (channel_id, _) = open::<T>();
+        let sender = T::BenchmarkHelper::signer().into_account();
+        TrustlessFileServerDeals::<T>::close_channel(
+            RawOrigin::Signed(sender.clone()).into(),
+            channel_id,
+        )
+        .unwrap();
+        let now = frame_system::Pallet::<T>::block_number();
+        frame_system::Pallet::<T>::set_block_number(now + T::ChannelClosePeriod::get());
+
+        #[extrinsic_call]
+        _(RawOrigin::Signed(sender), channel_id);
+
+        assert!(!Channels::<T>::contains_key(channel_id));
+    }
+
+    #[benchmark]
+    fn withdraw_unbonded() {
+        let caller = provider::<T>();
+        TrustlessFileServerDeals::<T>::unregister_provider(
+            RawOrigin::Signed(caller.clone()).into(),
+        )
+        .unwrap();
+        let now = frame_system::Pallet::<T>::block_number();
+        frame_system::Pallet::<T>::set_block_number(now + T::UnbondingPeriod::get());
+
+        #[extrinsic_call]
+        _(RawOrigin::Signed(caller.clone()));
+
+        assert!(!Unbonding::<T>::contains_key(caller));
+    }
+
+    // both the current bond and the one being unbonded are slashed
+    #[benchmark]
+    fn slash_provider() {
+        let who = provider::<T>();
+        TrustlessFileServerDeals::<T>::unregister_provider(RawOrigin::Signed(who.clone()).into())
+            .unwrap();
+        TrustlessFileServerDeals::<T>::register_provider(RawOrigin::Signed(who.clone()).into())
+            .unwrap();
+        let origin =
+            T::SlashOrigin::try_successful_origin().expect("SlashOrigin must be satisfiable");
+        let amount = T::ProviderBond::get() * 2u32.into();
+
+        #[extrinsic_call]
+        _(origin as T::RuntimeOrigin, who.clone(), amount);
+
+        assert!(!Unbonding::<T>::contains_key(who));
+    }
+
+    #[benchmark]
+    fn assign_committee(k: Linear<1, 16>) {
+        let (merkle_root, _) = upload::<T>(1024);
+        for i in 0..k {
+            let who: T::AccountId = account("provider", i, 0);
+            fund::<T>(&who);
+            TrustlessFileServerDeals::<T>::register_provider(RawOrigin::Signed(who).into())
+                .unwrap();
+        }
+        let caller: T::AccountId = whitelisted_caller();
+
+        #[extrinsic_call]
+        _(RawOrigin::Signed(caller), merkle_root);
+
+        assert!(Committees::<T>::contains_key(merkle_root));
+    }
+
+    #[benchmark]
+    fn set_attestation_key() {
+        let caller = provider::<T>();
+        let key = T::BenchmarkHelper::attester(0);
+        let proof = T::BenchmarkHelper::attest(
+            &[key.clone()],
+            &(crate::pallet::ATTESTATION_KEY_CONTEXT, &caller).encode(),
+        );
+
+        #[extrinsic_call]
+        _(RawOrigin::Signed(caller.clone()), key, proof);
+
+        assert!(AttestationKeys::<T>::contains_key(caller));
+    }
+
+    // the whole committee signs the statement
+    #[benchmark]
+    fn attest_availability() {
+        let (merkle_root, _) = upload::<T>(1024);
+        for i in 0..T::CommitteeSize::get() {
+            let who: T::AccountId = account("provider", i, 0);
+            fund::<T>(&who);
+            TrustlessFileServerDeals::<T>::register_provider(RawOrigin::Signed(who).into())
+                .unwrap();
+        }
+        let caller: T::AccountId = whitelisted_caller();
+        TrustlessFileServerDeals::<T>::assign_committee(
+            RawOrigin::Signed(caller.clone()).into(),
+            merkle_root,
+        )
+        .unwrap();
+        let members = Committees::<T>::get(merkle_root).unwrap();
+        let keys = (0..members.len() as u32)
+            .map(T::BenchmarkHelper::attester)
+            .collect::<Vec<_>>();
+        for (member, key) in members.iter().zip(keys.iter()) {
+            AttestationKeys::<T>::insert(member, key);
+        }
+        let statement = AvailabilityStatement {
+            merkle_root,
+            position: 0,
+            block: frame_system::Pallet::<T>::block_number(),
+        };
+        let signature = T::BenchmarkHelper::attest(&keys, &statement.encode());
+        let signers = BoundedVec::try_from(vec![true; members.len()]).unwrap();
+
+        #[extrinsic_call]
+        _(RawOrigin::Signed(caller), statement, signers, signature);
+
+        assert!(Availability::<T>::contains_key(merkle_root));
+    }
+
+    impl_benchmark_test_suite!(
+        TrustlessFileServerDeals,
+        crate::mock::new_test_ext(),
+        crate::mock::Test
+    );
+}
@@ -0,0 +1,54 @@
+//! Verifier of Groth16 proofs over BN254 of holding the chunk at a position of a file, for
+//! providers to answer challenges on private content without revealing it.
+//!
+//! Runtimes set [`Groth16Verifier`] as [`Config::ChunkProofVerifier`](crate::Config), along with
+//! the compressed verifying key of the circuit. The circuit proves the knowledge of a chunk
+//! whose sha256, hashed along the merkle path its position selects, leads to the merkle root. It
+//! takes the following public inputs, in this order:
+//!
+//! | input | value                                           |
+//! |-------|-------------------------------------------------|
+//! | 0     | low 128 bits of the merkle root, little endian  |
+//! | 1     | high 128 bits of the merkle root, little endian |
+//! | 2     | position of the chunk                           |
+//! | 3     | low 128 bits of the nonce, little endian        |
+//! | 4     | high 128 bits of the nonce, little endian       |
+//!
+//! Proofs are serialized in compressed form, as done by `ark-serialize`.
+use ark_bn254::{Bn254, Fr};
+use ark_groth16::{prepare_verifying_key, Groth16, Proof, VerifyingKey};
+use ark_serialize::CanonicalDeserialize;
+use frame_support::traits::Get;
+use sp_std::marker::PhantomData;
+
+use crate::ChunkProofVerifier;
+
+/// Verifies Groth16 proofs against the compressed verifying key given by `VerifyingKeyBytes`.
+pub struct Groth16Verifier<VerifyingKeyBytes>(PhantomData<VerifyingKeyBytes>);
+
+impl<VerifyingKeyBytes: Get<&'static [u8]>> ChunkProofVerifier
+    for Groth16Verifier<VerifyingKeyBytes>
+{
+    fn verify(merkle_root: &[u8], position: u64, nonce: &[u8; 32], proof: &[u8]) -> bool {
+        let Ok(vk) = VerifyingKey::<Bn254>::deserialize_compressed(VerifyingKeyBytes::get()) else {
+            return false;
+        };
+        let Ok(proof) = Proof::<Bn254>::deserialize_compressed(proof) else {
+            return false;
+        };
+        let (Some(root), Some(nonce)) = (halves(merkle_root), halves(nonce)) else {
+            return false;
+        };
+        let inputs = [root[0], root[1], Fr::from(position), nonce[0], nonce[1]];
+        Groth16::<Bn254>::verify_proof(&prepare_verifying_key(&vk), &proof, &inputs)
+            .unwrap_or(false)
+    }
+}
+
+/// Low and high 128 bits of a 32-byte value, as field elements.
+fn halves(bytes: &[u8]) -> Option<[Fr; 2]> {
+    let bytes: &[u8; 32] = bytes.try_into().ok()?;
+    let low = u128::from_le_bytes(bytes[..16].try_into().ok()?);
+    let high = u128::from_le_bytes(bytes[16..].try_into().ok()?);
+    Some([Fr::from(low), Fr::from(high)])
+}
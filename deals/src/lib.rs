@@ -0,0 +1,1336 @@
+//! # Trustless File Server Deals Pallet
+//!
+//! Storage deal marketplace on top of the files registered in the trustless file server pallet,
+//! along the lines of a much lighter Filecoin:
+//!
+//! - Uploaders publish a deal with `propose_deal`, offering a price for a registered file to be
+//! kept available for a number of blocks. The price is escrowed in the pallet account.
+//! - Registered providers take it with `accept_deal`, reserving a collateral proportional to the
+//! price.
+//! - From then on, the provider is challenged to submit a random chunk of the file within every
+//! `ChallengePeriod` blocks with `respond_challenge`. Each answer releases the part of the price
+//! earned so far, and the first one after the end of the deal gives the collateral back.
+//! - `terminate_deal` lets the client end a deal early, and anyone end one whose provider missed
+//! a challenge. Providers that miss a challenge or walk away forfeit their collateral to the
+//! client.
+//!
+//! Retrieval is paid separately, through payment channels: downloaders escrow a deposit with
+//! `open_channel` and sign a voucher for every chunk they get, which the serving node redeems in
+//! batches with `redeem_vouchers` until the channel is settled with `close_channel`.
+//!
+//! Files can be assigned a committee of providers with `assign_committee`. Its members register
+//! a key of an aggregatable signature scheme, such as BLS, with `set_attestation_key`, so that
+//! their statements of holding a chunk are submitted as a single signature with
+//! `attest_availability`, whatever the size of the committee.
+
+#![cfg_attr(not(feature = "std"), no_std)]
+
+pub use pallet::*;
+pub use types::*;
+pub use weights::*;
+
+#[cfg(test)]
+mod mock;
+
+#[cfg(test)]
+mod tests;
+
+#[cfg(feature = "runtime-benchmarks")]
+mod benchmarking;
+#[cfg(feature = "groth16")]
+pub mod groth16;
+pub mod migrations;
+pub mod types;
+pub mod weights;
+
+/// Signature scheme whose signatures of the same message can be aggregated into a single one,
+/// such as BLS, verified against the public keys of all the signers at once.
+pub trait AggregateVerify {
+    /// Public key of a signer.
+    type Public: frame_support::Parameter + codec::MaxEncodedLen;
+    /// Signature of a single signer, or the aggregate of the signatures of several ones.
+    type Signature: frame_support::Parameter;
+
+    /// Whether `signature` aggregates the signatures of `message` by every one of `signers`.
+    fn verify_aggregate(
+        signature: &Self::Signature,
+        message: &[u8],
+        signers: &[Self::Public],
+    ) -> bool;
+}
+
+/// Verifier of succinct proofs of holding the chunk at a position of a file without revealing it,
+/// such as `groth16::Groth16Verifier` with the `groth16` feature.
+pub trait ChunkProofVerifier {
+    /// Whether `proof` shows that the prover holds the chunk at `position` of the file under
+    /// `merkle_root`, bound to `nonce` so that it cannot be computed before being challenged.
+    fn verify(merkle_root: &[u8], position: u64, nonce: &[u8; 32], proof: &[u8]) -> bool;
+}
+
+/// Accepts no proofs, so that challenges can only be answered with the chunk itself.
+impl ChunkProofVerifier for () {
+    fn verify(_merkle_root: &[u8], _position: u64, _nonce: &[u8; 32], _proof: &[u8]) -> bool {
+        false
+    }
+}
+
+/// Signs vouchers and availability statements in benchmarks, as there is no way to do it for any
+/// signature scheme.
+#[cfg(feature = "runtime-benchmarks")]
+pub trait BenchmarkHelper<Public, Signature, AttestationPublic, AttestationSignature> {
+    /// Public key of a key pair that can sign vouchers.
+    fn signer() -> Public;
+    /// Signs `message` with the key pair of `signer`.
+    fn sign(signer: &Public, message: &[u8]) -> Signature;
+    /// Public key of the `index`-th key pair that can sign availability statements.
+    fn attester(index: u32) -> AttestationPublic;
+    /// Aggregates the signatures of `message` by the key pairs of all of `attesters`.
+    fn attest(attesters: &[AttestationPublic], message: &[u8]) -> AttestationSignature;
+    /// Proves holding the chunk at `position` of the file under `merkle_root`, bound to `nonce`.
+    fn prove_chunk(merkle_root: &[u8], position: u64, nonce: &[u8; 32]) -> sp_std::vec::Vec<u8>;
+}
+
+#[frame_support::pallet]
+pub mod pallet {
+    use codec::{Decode, Encode};
+    use frame_support::pallet_prelude::*;
+    use frame_support::sp_runtime::curve::PiecewiseLinear;
+    use frame_support::sp_runtime::traits::{
+        AccountIdConversion, IdentifyAccount, Saturating, Verify, Zero,
+    };
+    use frame_support::sp_runtime::{PerThing, Perbill};
+    use frame_support::storage::StoragePrefixedMap;
+    use frame_support::traits::{
+        BalanceStatus, Currency, ExistenceRequirement, Randomness, ReservableCurrency,
+    };
+    use frame_support::PalletId;
+    use frame_system::pallet_prelude::*;
+    use pallet_trustless_file_server::{BalanceOf, Pallet as TrustlessFileServer};
+    use sp_std::vec::Vec;
+
+    use super::*;
+
+    /// Subject of the randomness choosing the chunk of every challenge.
+    const CHALLENGE_SUBJECT: &[u8] = b"pallet_trustless_file_server_deals::challenge";
+
+    /// Subject of the randomness choosing the committee of every file.
+    const COMMITTEE_SUBJECT: &[u8] = b"pallet_trustless_file_server_deals::committee";
+
+    /// Context of the message signed to prove the possession of an attestation key.
+    pub(crate) const ATTESTATION_KEY_CONTEXT: &[u8] =
+        b"pallet_trustless_file_server_deals::attestation_key";
+
+    /// The current storage version.
+    const STORAGE_VERSION: StorageVersion = StorageVersion::new(1);
+
+    #[pallet::pallet]
+    #[pallet::storage_version(STORAGE_VERSION)]
+    pub struct Pallet<T>(_);
+
+    #[pallet::config]
+    pub trait Config: frame_system::Config + pallet_trustless_file_server::Config {
+        /// Because this pallet emits events, it depends on the runtime's definition of an event.
+        type RuntimeEvent: From<Event<Self>> + IsType<<Self as frame_system::Config>::RuntimeEvent>;
+
+        /// Source of randomness choosing the chunks providers are challenged on.
+        type Randomness: Randomness<Self::Hash, Self::BlockNumber>;
+
+        /// Identifier of the account escrowing the price of the deals.
+        #[pallet::constant]
+        type PalletId: Get<PalletId>;
+
+        /// Bond reserved from every registered provider.
+        #[pallet::constant]
+        type ProviderBond: Get<BalanceOf<Self>>;
+
+        /// Number of blocks the bond of a provider stays reserved, and slashable, once it
+        /// unregisters.
+        #[pallet::constant]
+        type UnbondingPeriod: Get<Self::BlockNumber>;
+
+        /// Origin allowed to slash the bonds of providers, e.g. for failures found out after the
+        /// fact.
+        type SlashOrigin: EnsureOrigin<Self::RuntimeOrigin>;
+
+        /// Collateral reserved from providers when accepting a deal, as a fraction of its price.
+        #[pallet::constant]
+        type CollateralRatio: Get<Perbill>;
+
+        /// Shortest duration of a deal, in blocks.
+        #[pallet::constant]
+        type MinDealDuration: Get<Self::BlockNumber>;
+
+        /// Number of blocks providers have to answer every challenge.
+        #[pallet::constant]
+        type ChallengePeriod: Get<Self::BlockNumber>;
+
+        /// Number of blocks between settlements of a deal, releasing to its provider the part of
+        /// the price earned by the challenges answered since the previous one.
+        #[pallet::constant]
+        type SettlementPeriod: Get<Self::BlockNumber>;
+
+        /// Share of the price of a deal earned by its provider, as a function of the part of its
+        /// duration served so far, e.g. linear or holding back part of the price until the end.
+        /// It must be non-decreasing. Whatever it leaves is paid once the deal is over.
+        type RewardCurve: Get<&'static PiecewiseLinear<'static>>;
+
+        /// Maximum number of deals a single provider can serve at the same time.
+        #[pallet::constant]
+        type MaxDealsPerProvider: Get<u32>;
+
+        /// Number of providers in the storage committee of every file. Once a file has a
+        /// committee, only its members can take deals on it.
+        #[pallet::constant]
+        type CommitteeSize: Get<u32>;
+
+        /// Verifier of the proofs answering challenges without revealing the chunk, e.g.
+        /// `groth16::Groth16Verifier`. `()` only accepts the chunk itself.
+        type ChunkProofVerifier: ChunkProofVerifier;
+
+        /// Maximum length of the proofs answering challenges without revealing the chunk.
+        #[pallet::constant]
+        type MaxChunkProofLength: Get<u32>;
+
+        /// Signature of the vouchers of retrieval payment channels.
+        type OffchainSignature: Verify<Signer = Self::OffchainPublic> + Parameter;
+
+        /// Public key vouchers are signed with, identifying the sender of the channel.
+        type OffchainPublic: IdentifyAccount<AccountId = Self::AccountId>;
+
+        /// Maximum number of vouchers redeemed at once.
+        #[pallet::constant]
+        type MaxVouchersPerRedeem: Get<u32>;
+
+        /// Number of blocks servers have to redeem their last vouchers once the sender of a
+        /// channel asks to close it.
+        #[pallet::constant]
+        type ChannelClosePeriod: Get<Self::BlockNumber>;
+
+        /// Aggregatable signature scheme of the availability statements of committee members,
+        /// e.g. BLS, so that a whole committee attests with a single signature.
+        type AttestationScheme: AggregateVerify;
+
+        /// Signs vouchers and availability statements in benchmarks.
+        #[cfg(feature = "runtime-benchmarks")]
+        type BenchmarkHelper: BenchmarkHelper<
+            Self::OffchainPublic,
+            Self::OffchainSignature,
+            AttestationPublicOf<Self>,
+            AttestationSignatureOf<Self>,
+        >;
+
+        /// The weight information for this pallet.
+        type WeightInfo: WeightInfo;
+    }
+
+    pub type ProviderOf<T> = Provider<BalanceOf<T>>;
+
+    pub type CommitteeOf<T> =
+        BoundedVec<<T as frame_system::Config>::AccountId, <T as Config>::CommitteeSize>;
+
+    pub type AttestationPublicOf<T> = <<T as Config>::AttestationScheme as AggregateVerify>::Public;
+
+    pub type AttestationSignatureOf<T> =
+        <<T as Config>::AttestationScheme as AggregateVerify>::Signature;
+
+    pub type AvailabilityStatementOf<T> = AvailabilityStatement<
+        <T as frame_system::Config>::Hash,
+        <T as frame_system::Config>::BlockNumber,
+    >;
+
+    pub type AvailabilityAttestationOf<T> =
+        AvailabilityAttestation<<T as frame_system::Config>::BlockNumber>;
+
+    pub type UnbondingBondOf<T> =
+        UnbondingBond<BalanceOf<T>, <T as frame_system::Config>::BlockNumber>;
+
+    pub type DealOf<T> = Deal<
+        <T as frame_system::Config>::AccountId,
+        <T as frame_system::Config>::Hash,
+        BalanceOf<T>,
+        <T as frame_system::Config>::BlockNumber,
+    >;
+
+    pub type ChannelOf<T> = Channel<
+        <T as frame_system::Config>::AccountId,
+        BalanceOf<T>,
+        <T as frame_system::Config>::BlockNumber,
+    >;
+
+    pub type VoucherOf<T> = Voucher<<T as frame_system::Config>::Hash, BalanceOf<T>>;
+
+    #[pallet::event]
+    #[pallet::generate_deposit(pub (super) fn deposit_event)]
+    pub enum Event<T: Config> {
+        /// An account has registered as a provider, reserving `bond`.
+        ProviderRegistered {
+            who: T::AccountId,
+            bond: BalanceOf<T>,
+        },
+        /// A provider has left. Its bond can be withdrawn once unlocked.
+        ProviderUnregistered {
+            who: T::AccountId,
+            unlocked_at: T::BlockNumber,
+        },
+        /// The providers responsible for keeping a file have been drawn.
+        CommitteeAssigned {
+            merkle_root: T::Hash,
+            members: CommitteeOf<T>,
+        },
+        /// A provider has registered the key it signs availability statements with.
+        AttestationKeySet { who: T::AccountId },
+        /// The committee of a file has attested to hold the chunk at `position` at `block`.
+        AvailabilityAttested {
+            merkle_root: T::Hash,
+            position: u64,
+            block: T::BlockNumber,
+            attesters: u32,
+        },
+        /// A former provider has got its unbonded bond back.
+        BondWithdrawn {
+            who: T::AccountId,
+            amount: BalanceOf<T>,
+        },
+        /// `amount` of the bond of a provider has been slashed.
+        ProviderSlashed {
+            who: T::AccountId,
+            amount: BalanceOf<T>,
+        },
+        /// A client has escrowed `price` for a file to be served for `duration` blocks.
+        DealProposed {
+            deal_id: DealId,
+            client: T::AccountId,
+            merkle_root: T::Hash,
+            duration: T::BlockNumber,
+            price: BalanceOf<T>,
+        },
+        /// A provider has taken a deal, reserving `collateral`.
+        DealAccepted {
+            deal_id: DealId,
+            provider: T::AccountId,
+            collateral: BalanceOf<T>,
+        },
+        /// The provider of a deal has to submit the chunk at `position` before `deadline`.
+        ChallengeIssued {
+            deal_id: DealId,
+            position: u64,
+            deadline: T::BlockNumber,
+        },
+        /// The provider of a deal has submitted the challenged chunk.
+        ChallengeAnswered { deal_id: DealId, position: u64 },
+        /// The provider of a deal has been paid `payment` for the blocks proven since the last
+        /// settlement.
+        DealSettled {
+            deal_id: DealId,
+            provider: T::AccountId,
+            payment: BalanceOf<T>,
+        },
+        /// A deal has been served until its end, and its provider got the collateral back.
+        DealCompleted { deal_id: DealId },
+        /// A deal has been ended before completion, refunding `refund` to the client.
+        DealTerminated {
+            deal_id: DealId,
+            who: T::AccountId,
+            refund: BalanceOf<T>,
+        },
+        /// The provider of a deal has lost `amount` of its collateral to the client.
+        CollateralForfeited {
+            deal_id: DealId,
+            provider: T::AccountId,
+            amount: BalanceOf<T>,
+        },
+        /// `sender` has escrowed `deposit` to pay `server` `price_per_chunk` per chunk served.
+        ChannelOpened {
+            channel_id: ChannelId,
+            sender: T::AccountId,
+            server: T::AccountId,
+            deposit: BalanceOf<T>,
+            price_per_chunk: BalanceOf<T>,
+        },
+        /// The server of a channel has been paid `amount` for `count` vouchers.
+        VouchersRedeemed {
+            channel_id: ChannelId,
+            count: u32,
+            amount: BalanceOf<T>,
+        },
+        /// The sender of a channel has asked to close it, which it can do from `closes_at` on.
+        ChannelClosing {
+            channel_id: ChannelId,
+            closes_at: T::BlockNumber,
+        },
+        /// A channel has been settled, refunding `refund` to its sender.
+        ChannelClosed {
+            channel_id: ChannelId,
+            refund: BalanceOf<T>,
+        },
+    }
+
+    #[pallet::error]
+    pub enum Error<T> {
+        /// The caller is already registered as a provider
+        AlreadyProvider,
+        /// The caller is not registered as a provider
+        NotProvider,
+        /// The provider is still serving deals
+        ProviderHasDeals,
+        /// The provider cannot take more deals
+        TooManyDeals,
+        /// There is no file registered under the given merkle root
+        FileNotFound,
+        /// The deal is shorter than the minimum duration
+        DurationTooShort,
+        /// Deals must have a price
+        ZeroPrice,
+        /// There is no deal with the given identifier
+        DealNotFound,
+        /// The deal has already been accepted by a provider
+        AlreadyAccepted,
+        /// The deal has not been accepted by any provider yet
+        NotAccepted,
+        /// The caller is not the provider of the deal
+        NotDealProvider,
+        /// The caller is neither the client nor the provider of the deal
+        NotDealParty,
+        /// The caller is not the client of the deal
+        NotDealClient,
+        /// The settlement period of the deal is not over yet
+        SettlementNotDue,
+        /// No challenge has been answered since the last settlement of the deal
+        NothingToSettle,
+        /// The provider of the deal has missed a challenge
+        ProviderInDefault,
+        /// The provider of the deal has not missed any challenge
+        ProviderNotInDefault,
+        /// The deadline of the challenge has passed
+        ChallengeExpired,
+        /// The submitted chunk is not the challenged one
+        InvalidChunk,
+        /// The proof does not show holding the challenged chunk
+        InvalidChunkProof,
+        /// Channels must have a deposit
+        ZeroDeposit,
+        /// There is no channel with the given identifier
+        ChannelNotFound,
+        /// The caller is not the server of the channel
+        NotChannelServer,
+        /// The caller is neither the sender nor the server of the channel
+        NotChannelParty,
+        /// The voucher belongs to another channel or has already been redeemed
+        InvalidVoucher,
+        /// The voucher is not signed by the sender of the channel
+        BadSignature,
+        /// The deposit of the channel does not cover the vouchers
+        ChannelExhausted,
+        /// The close period of the channel is not over yet
+        ClosePeriodNotOver,
+        /// The file already has a committee whose members are all providers
+        CommitteeAssigned,
+        /// There are no providers to draw a committee from
+        NoProviders,
+        /// The caller is not a member of the committee of the file
+        NotCommitteeMember,
+        /// The file has no committee
+        NoCommittee,
+        /// A signer of the attestation has not registered an attestation key
+        NoAttestationKey,
+        /// Not more than half of the committee has signed the attestation
+        NotEnoughAttesters,
+        /// The attested chunk is not part of the file
+        InvalidPosition,
+        /// The attested block is in the future, too old, or not newer than the last attested one
+        StaleAttestation,
+        /// There is no bond being unbonded by the caller
+        NotUnbonding,
+        /// The unbonding period of the bond is not over yet
+        StillUnbonding,
+        /// The account has no bond to slash
+        NothingToSlash,
+    }
+
+    #[pallet::storage]
+    #[pallet::getter(fn get_provider)]
+    pub(super) type Providers<T: Config> =
+        StorageMap<_, Blake2_128Concat, T::AccountId, ProviderOf<T>, OptionQuery>;
+
+    #[pallet::storage]
+    #[pallet::getter(fn get_deal)]
+    pub(super) type Deals<T: Config> = StorageMap<_, Twox64Concat, DealId, DealOf<T>, OptionQuery>;
+
+    /// Identifier of the next proposed deal.
+    #[pallet::storage]
+    pub(super) type NextDealId<T: Config> = StorageValue<_, DealId, ValueQuery>;
+
+    #[pallet::storage]
+    #[pallet::getter(fn get_channel)]
+    pub(super) type Channels<T: Config> =
+        StorageMap<_, Twox64Concat, ChannelId, ChannelOf<T>, OptionQuery>;
+
+    /// Providers responsible for keeping each file, drawn with `assign_committee`.
+    #[pallet::storage]
+    #[pallet::getter(fn get_committee)]
+    pub(super) type Committees<T: Config> =
+        StorageMap<_, Blake2_128Concat, T::Hash, CommitteeOf<T>, OptionQuery>;
+
+    /// Public keys providers sign availability statements with.
+    #[pallet::storage]
+    #[pallet::getter(fn get_attestation_key)]
+    pub(super) type AttestationKeys<T: Config> =
+        StorageMap<_, Blake2_128Concat, T::AccountId, AttestationPublicOf<T>, OptionQuery>;
+
+    /// Latest availability attestation of every file by its committee.
+    #[pallet::storage]
+    #[pallet::getter(fn get_availability)]
+    pub(super) type Availability<T: Config> =
+        StorageMap<_, Blake2_128Concat, T::Hash, AvailabilityAttestationOf<T>, OptionQuery>;
+
+    /// Bonds of the providers that have left, until withdrawn.
+    #[pallet::storage]
+    #[pallet::getter(fn get_unbonding)]
+    pub(super) type Unbonding<T: Config> =
+        StorageMap<_, Blake2_128Concat, T::AccountId, UnbondingBondOf<T>, OptionQuery>;
+
+    /// Identifier of the next opened channel.
+    #[pallet::storage]
+    pub(super) type NextChannelId<T: Config> = StorageValue<_, ChannelId, ValueQuery>;
+
+    #[pallet::hooks]
+    impl<T: Config> Hooks<BlockNumberFor<T>> for Pallet<T> {
+        fn integrity_test() {
+            assert!(
+                T::RewardCurve::get()
+                    .points
+                    .windows(2)
+                    .all(|pair| pair[0].0 <= pair[1].0 && pair[0].1 <= pair[1].1),
+                "the reward curve must be non-decreasing"
+            );
+        }
+    }
+
+    #[pallet::call]
+    impl<T: Config> Pallet<T> {
+        /// Registers the caller as a storage provider, reserving [`Config::ProviderBond`].
+        #[pallet::call_index(0)]
+        #[pallet::weight(<T as Config>::WeightInfo::register_provider())]
+        pub fn register_provider(origin: OriginFor<T>) -> DispatchResult {
+            let who = ensure_signed(origin)?;
+            ensure!(!Providers::<T>::contains_key(&who), Error::<T>::AlreadyProvider);
+
+            let bond = T::ProviderBond::get();
+            T::Currency::reserve(&who, bond)?;
+            Providers::<T>::insert(
+                &who,
+                Provider {
+                    bond,
+                    active_deals: 0,
+                },
+            );
+
+            Self::deposit_event(Event::ProviderRegistered { who, bond });
+
+            Ok(())
+        }
+
+        /// Unregisters a provider with no deals left. Its bond stays reserved, and can still be
+        /// slashed, for [`Config::UnbondingPeriod`] blocks, after which it can be withdrawn with
+        /// `withdraw_unbonded`.
+        #[pallet::call_index(1)]
+        #[pallet::weight(<T as Config>::WeightInfo::unregister_provider())]
+        pub fn unregister_provider(origin: OriginFor<T>) -> DispatchResult {
+            let who = ensure_signed(origin)?;
+
+            let provider = Providers::<T>::get(&who).ok_or(Error::<T>::NotProvider)?;
+            ensure!(provider.active_deals == 0, Error::<T>::ProviderHasDeals);
+            Providers::<T>::remove(&who);
+            let now = <frame_system::Pallet<T>>::block_number();
+            let unlocked_at = now.saturating_add(T::UnbondingPeriod::get());
+            // providers registering again and leaving before withdrawing add to the same bond
+            Unbonding::<T>::mutate(&who, |unbonding| {
+                let amount = unbonding.as_ref().map_or(provider.bond, |unbonding| {
+                    unbonding.amount.saturating_add(provider.bond)
+                });
+                *unbonding = Some(UnbondingBond {
+                    amount,
+                    unlocked_at,
+                });
+            });
+
+            Self::deposit_event(Event::ProviderUnregistered { who, unlocked_at });
+
+            Ok(())
+        }
+
+        /// Offers `price` for the file under `merkle_root` to be kept available for `duration`
+        /// blocks. The price is transferred to the pallet account until the deal is over.
+        #[pallet::call_index(2)]
+        #[pallet::weight(<T as Config>::WeightInfo::propose_deal())]
+        pub fn propose_deal(
+            origin: OriginFor<T>,
+            merkle_root: T::Hash,
+            duration: T::BlockNumber,
+            price: BalanceOf<T>,
+        ) -> DispatchResult {
+            let who = ensure_signed(origin)?;
+            ensure!(TrustlessFileServer::<T>::file_exists(merkle_root), Error::<T>::FileNotFound);
+            ensure!(duration >= T::MinDealDuration::get(), Error::<T>::DurationTooShort);
+            ensure!(!price.is_zero(), Error::<T>::ZeroPrice);
+
+            T::Currency::transfer(
+                &who,
+                &Self::account_id(),
+                price,
+                ExistenceRequirement::KeepAlive,
+            )?;
+            let deal_id = NextDealId::<T>::get();
+            NextDealId::<T>::put(deal_id.saturating_add(1));
+            Deals::<T>::insert(
+                deal_id,
+                Deal {
+                    client: who.clone(),
+                    merkle_root,
+                    duration,
+                    price,
+                    state: DealState::Proposed,
+                },
+            );
+
+            Self::deposit_event(Event::DealProposed {
+                deal_id,
+                client: who,
+                merkle_root,
+                duration,
+                price,
+            });
+
+            Ok(())
+        }
+
+        /// Takes a proposed deal, reserving [`Config::CollateralRatio`] of its price from the
+        /// caller, who must be a registered provider and, if the file has a committee, one of
+        /// its members. The deal starts right away with a first challenge.
+        #[pallet::call_index(3)]
+        #[pallet::weight(<T as Config>::WeightInfo::accept_deal())]
+        pub fn accept_deal(origin: OriginFor<T>, deal_id: DealId) -> DispatchResult {
+            let who = ensure_signed(origin)?;
+
+            let mut provider = Providers::<T>::get(&who).ok_or(Error::<T>::NotProvider)?;
+            ensure!(
+                provider.active_deals < T::MaxDealsPerProvider::get(),
+                Error::<T>::TooManyDeals
+            );
+            let mut deal = Deals::<T>::get(deal_id).ok_or(Error::<T>::DealNotFound)?;
+            ensure!(matches!(deal.state, DealState::Proposed), Error::<T>::AlreadyAccepted);
+            if let Some(members) = Committees::<T>::get(deal.merkle_root) {
+                ensure!(members.contains(&who), Error::<T>::NotCommitteeMember);
+            }
+            let now = <frame_system::Pallet<T>>::block_number();
+            let challenge = Self::new_challenge(deal_id, deal.merkle_root, now)
+                .ok_or(Error::<T>::FileNotFound)?;
+
+            let collateral = T::CollateralRatio::get().mul_ceil(deal.price);
+            T::Currency::reserve(&who, collateral)?;
+            provider.active_deals += 1;
+            Providers::<T>::insert(&who, provider);
+            deal.state = DealState::Active {
+                provider: who.clone(),
+                start: now,
+                collateral,
+                paid_until: now,
+                proven_until: now,
+                challenge: challenge.clone(),
+            };
+            Deals::<T>::insert(deal_id, deal);
+
+            Self::deposit_event(Event::DealAccepted {
+                deal_id,
+                provider: who,
+                collateral,
+            });
+            Self::deposit_event(Event::ChallengeIssued {
+                deal_id,
+                position: challenge.position,
+                deadline: challenge.deadline,
+            });
+
+            Ok(())
+        }
+
+        /// Answers the current challenge of a deal with the content of the challenged chunk,
+        /// proving the provider has held the file so far. What it earns is paid on the next
+        /// settlement, see `settle_deal`.
+        ///
+        /// Answers after the end of the deal complete it, paying the rest of the price and giving
+        /// the collateral back. Otherwise, a new challenge is issued.
+        #[pallet::call_index(4)]
+        #[pallet::weight(<T as Config>::WeightInfo::respond_challenge(chunk.len() as u32))]
+        pub fn respond_challenge(
+            origin: OriginFor<T>,
+            deal_id: DealId,
+            chunk: Vec<u8>,
+        ) -> DispatchResult {
+            let who = ensure_signed(origin)?;
+
+            Self::answer_challenge(who, deal_id, |merkle_root, challenge| {
+                let expected =
+                    TrustlessFileServer::<T>::chunk_hash(merkle_root, challenge.position)
+                        .ok_or(Error::<T>::FileNotFound)?;
+                ensure!(sp_io::hashing::sha2_256(&chunk) == expected, Error::<T>::InvalidChunk);
+                Ok(())
+            })
+        }
+
+        /// Answers the current challenge of a deal with a proof of holding the challenged chunk,
+        /// verified by [`Config::ChunkProofVerifier`], for files whose content must not be
+        /// revealed. Otherwise the same as `respond_challenge`.
+        ///
+        /// The proof is bound to the nonce of the challenge, the blake2_256 of the SCALE encoded
+        /// deal identifier and challenge, so that it cannot be computed before being challenged.
+        #[pallet::call_index(14)]
+        #[pallet::weight(<T as Config>::WeightInfo::respond_challenge_privately())]
+        pub fn respond_challenge_privately(
+            origin: OriginFor<T>,
+            deal_id: DealId,
+            proof: BoundedVec<u8, T::MaxChunkProofLength>,
+        ) -> DispatchResult {
+            let who = ensure_signed(origin)?;
+
+            Self::answer_challenge(who, deal_id, |merkle_root, challenge| {
+                let nonce = Self::challenge_nonce(deal_id, challenge);
+                ensure!(
+                    T::ChunkProofVerifier::verify(
+                        merkle_root.as_ref(),
+                        challenge.position,
+                        &nonce,
+                        &proof
+                    ),
+                    Error::<T>::InvalidChunkProof
+                );
+                Ok(())
+            })
+        }
+
+        /// Ends a deal before its completion, refunding the client the part of the price not
+        /// released yet.
+        ///
+        /// Proposed deals can only be withdrawn by their client. Active ones can be ended by
+        /// their client, who pays the provider for the blocks served so far, or by their
+        /// provider, who forfeits the collateral. Once a challenge expires unanswered, anyone can
+        /// end the deal and the provider forfeits the collateral as well. Providers forfeiting
+        /// the collateral are still paid for the challenges answered since the last settlement.
+        /// Providers are not penalized if the file is no longer registered.
+        #[pallet::call_index(5)]
+        #[pallet::weight(<T as Config>::WeightInfo::terminate_deal())]
+        pub fn terminate_deal(origin: OriginFor<T>, deal_id: DealId) -> DispatchResult {
+            let who = ensure_signed(origin)?;
+
+            let deal = Deals::<T>::get(deal_id).ok_or(Error::<T>::DealNotFound)?;
+            Self::end_deal(who, deal_id, deal)
+        }
+
+        /// Pays the provider of a deal the part of the price earned by the challenges answered
+        /// since the last settlement. Anyone can settle a deal once [`Config::SettlementPeriod`]
+        /// blocks have passed since the previous one, as long as its provider has not missed a
+        /// challenge.
+        #[pallet::call_index(15)]
+        #[pallet::weight(<T as Config>::WeightInfo::settle_deal())]
+        pub fn settle_deal(origin: OriginFor<T>, deal_id: DealId) -> DispatchResult {
+            ensure_signed(origin)?;
+
+            let mut deal = Deals::<T>::get(deal_id).ok_or(Error::<T>::DealNotFound)?;
+            let DealState::Active {
+                provider,
+                start,
+                paid_until,
+                proven_until,
+                challenge,
+                ..
+            } = &mut deal.state
+            else {
+                return Err(Error::<T>::NotAccepted.into());
+            };
+            let now = <frame_system::Pallet<T>>::block_number();
+            ensure!(now <= challenge.deadline, Error::<T>::ProviderInDefault);
+            ensure!(
+                now >= paid_until.saturating_add(T::SettlementPeriod::get()),
+                Error::<T>::SettlementNotDue
+            );
+            ensure!(*proven_until > *paid_until, Error::<T>::NothingToSettle);
+
+            let payment = Self::earned(&deal.price, deal.duration, *start, *proven_until)
+                .saturating_sub(Self::earned(&deal.price, deal.duration, *start, *paid_until));
+            T::Currency::transfer(
+                &Self::account_id(),
+                provider,
+                payment,
+                ExistenceRequirement::AllowDeath,
+            )?;
+            *paid_until = *proven_until;
+            let provider = provider.clone();
+            Deals::<T>::insert(deal_id, deal);
+
+            Self::deposit_event(Event::DealSettled {
+                deal_id,
+                provider,
+                payment,
+            });
+
+            Ok(())
+        }
+
+        /// Lets the client of a deal whose provider has missed a challenge take the collateral of
+        /// the provider, ending the deal as `terminate_deal` does.
+        #[pallet::call_index(16)]
+        #[pallet::weight(<T as Config>::WeightInfo::claim_deal_collateral())]
+        pub fn claim_deal_collateral(origin: OriginFor<T>, deal_id: DealId) -> DispatchResult {
+            let who = ensure_signed(origin)?;
+
+            let deal = Deals::<T>::get(deal_id).ok_or(Error::<T>::DealNotFound)?;
+            ensure!(who == deal.client, Error::<T>::NotDealClient);
+            let DealState::Active { challenge, .. } = &deal.state else {
+                return Err(Error::<T>::NotAccepted.into());
+            };
+            let now = <frame_system::Pallet<T>>::block_number();
+            ensure!(
+                now > challenge.deadline && TrustlessFileServer::<T>::file_exists(deal.merkle_root),
+                Error::<T>::ProviderNotInDefault
+            );
+            Self::end_deal(who, deal_id, deal)
+        }
+
+        /// Opens a retrieval payment channel with `server`, transferring `deposit` to the pallet
+        /// account. The caller then signs a [`Voucher`] for every chunk it is served, worth
+        /// `price_per_chunk` each, or the retrieval price set by the owner of its file if any,
+        /// up to the price it signed.
+        #[pallet::call_index(6)]
+        #[pallet::weight(<T as Config>::WeightInfo::open_channel())]
+        pub fn open_channel(
+            origin: OriginFor<T>,
+            server: T::AccountId,
+            deposit: BalanceOf<T>,
+            price_per_chunk: BalanceOf<T>,
+        ) -> DispatchResult {
+            let who = ensure_signed(origin)?;
+            ensure!(!deposit.is_zero(), Error::<T>::ZeroDeposit);
+            ensure!(!price_per_chunk.is_zero(), Error::<T>::ZeroPrice);
+
+            T::Currency::transfer(
+                &who,
+                &Self::account_id(),
+                deposit,
+                ExistenceRequirement::KeepAlive,
+            )?;
+            let channel_id = NextChannelId::<T>::get();
+            NextChannelId::<T>::put(channel_id.saturating_add(1));
+            Channels::<T>::insert(
+                channel_id,
+                Channel {
+                    sender: who.clone(),
+                    server: server.clone(),
+                    deposit,
+                    price_per_chunk,
+                    redeemed: Zero::zero(),
+                    next_sequence: 0,
+                    closes_at: None,
+                },
+            );
+
+            Self::deposit_event(Event::ChannelOpened {
+                channel_id,
+                sender: who,
+                server,
+                deposit,
+                price_per_chunk,
+            });
+
+            Ok(())
+        }
+
+        /// Pays the server of a channel for a batch of vouchers signed by its sender, sorted by
+        /// sequence number. Either all of them are redeemed or none, so vouchers the deposit
+        /// does not cover have to be left out.
+        ///
+        /// Every voucher is worth the retrieval price the owner of its file has set with
+        /// `set_retrieval_price`, falling back to the price of the channel, but never more than
+        /// the price its sender signed.
+        #[pallet::call_index(7)]
+        #[pallet::weight(<T as Config>::WeightInfo::redeem_vouchers(vouchers.len() as u32))]
+        pub fn redeem_vouchers(
+            origin: OriginFor<T>,
+            channel_id: ChannelId,
+            vouchers: BoundedVec<(VoucherOf<T>, T::OffchainSignature), T::MaxVouchersPerRedeem>,
+        ) -> DispatchResult {
+            let who = ensure_signed(origin)?;
+
+            let mut channel = Channels::<T>::get(channel_id).ok_or(Error::<T>::ChannelNotFound)?;
+            ensure!(channel.server == who, Error::<T>::NotChannelServer);
+            let mut amount = BalanceOf::<T>::zero();
+            for (voucher, signature) in &vouchers {
+                ensure!(
+                    voucher.channel_id == channel_id && voucher.sequence >= channel.next_sequence,
+                    Error::<T>::InvalidVoucher
+                );
+                ensure!(
+                    signature.verify(&voucher.encode()[..], &channel.sender),
+                    Error::<T>::BadSignature
+                );
+                channel.next_sequence = voucher.sequence.saturating_add(1);
+                // owners pricing the retrieval of their files override the price of the channel
+                let price = TrustlessFileServer::<T>::retrieval_price(voucher.merkle_root)
+                    .unwrap_or(channel.price_per_chunk)
+                    .min(voucher.price);
+                amount = amount.saturating_add(price);
+            }
+            let count = vouchers.len() as u32;
+            let redeemed = channel.redeemed.saturating_add(amount);
+            ensure!(redeemed <= channel.deposit, Error::<T>::ChannelExhausted);
+
+            T::Currency::transfer(
+                &Self::account_id(),
+                &who,
+                amount,
+                ExistenceRequirement::AllowDeath,
+            )?;
+            channel.redeemed = redeemed;
+            Channels::<T>::insert(channel_id, channel);
+
+            Self::deposit_event(Event::VouchersRedeemed {
+                channel_id,
+                count,
+                amount,
+            });
+
+            Ok(())
+        }
+
+        /// Closes a channel, refunding its sender the part of the deposit not redeemed.
+        ///
+        /// The server can settle the channel right away. The sender has to call this twice:
+        /// first to start the [`Config::ChannelClosePeriod`], during which the server can still
+        /// redeem its last vouchers, and again once it is over.
+        #[pallet::call_index(8)]
+        #[pallet::weight(<T as Config>::WeightInfo::close_channel())]
+        pub fn close_channel(origin: OriginFor<T>, channel_id: ChannelId) -> DispatchResult {
+            let who = ensure_signed(origin)?;
+
+            let mut channel = Channels::<T>::get(channel_id).ok_or(Error::<T>::ChannelNotFound)?;
+            if who != channel.server {
+                ensure!(who == channel.sender, Error::<T>::NotChannelParty);
+                let now = <frame_system::Pallet<T>>::block_number();
+                let Some(closes_at) = channel.closes_at else {
+                    let closes_at = now.saturating_add(T::ChannelClosePeriod::get());
+                    channel.closes_at = Some(closes_at);
+                    Channels::<T>::insert(channel_id, channel);
+                    Self::deposit_event(Event::ChannelClosing {
+                        channel_id,
+                        closes_at,
+                    });
+                    return Ok(());
+                };
+                ensure!(now >= closes_at, Error::<T>::ClosePeriodNotOver);
+            }
+
+            let refund = channel.deposit.saturating_sub(channel.redeemed);
+            T::Currency::transfer(
+                &Self::account_id(),
+                &channel.sender,
+                refund,
+                ExistenceRequirement::AllowDeath,
+            )?;
+            Channels::<T>::remove(channel_id);
+
+            Self::deposit_event(Event::ChannelClosed { channel_id, refund });
+
+            Ok(())
+        }
+
+        /// Draws the committee of [`Config::CommitteeSize`] providers responsible for keeping a
+        /// registered file, out of the runtime randomness and the current providers. From then
+        /// on, only its members can take deals on the file, so they are the ones challenged and
+        /// paid for it.
+        ///
+        /// Anyone can draw the committee of a file without one, or redraw it once any of its
+        /// members has left.
+        #[pallet::call_index(11)]
+        #[pallet::weight(<T as Config>::WeightInfo::assign_committee(T::CommitteeSize::get()))]
+        pub fn assign_committee(origin: OriginFor<T>, merkle_root: T::Hash) -> DispatchResult {
+            ensure_signed(origin)?;
+            ensure!(TrustlessFileServer::<T>::file_exists(merkle_root), Error::<T>::FileNotFound);
+            if let Some(members) = Committees::<T>::get(merkle_root) {
+                ensure!(
+                    members
+                        .iter()
+                        .any(|member| !Providers::<T>::contains_key(member)),
+                    Error::<T>::CommitteeAssigned
+                );
+            }
+
+            let now = <frame_system::Pallet<T>>::block_number();
+            let (seed, _) = T::Randomness::random(&(COMMITTEE_SUBJECT, merkle_root, now).encode());
+            // keys are hashed, so the providers following a random key are a random committee
+            let start = [&Providers::<T>::final_prefix()[..], seed.as_ref()].concat();
+            let mut members = CommitteeOf::<T>::default();
+            for who in Providers::<T>::iter_keys_from(start).chain(Providers::<T>::iter_keys()) {
+                if members.contains(&who) || members.try_push(who).is_err() {
+                    break;
+                }
+            }
+            ensure!(!members.is_empty(), Error::<T>::NoProviders);
+            Committees::<T>::insert(merkle_root, &members);
+
+            Self::deposit_event(Event::CommitteeAssigned {
+                merkle_root,
+                members,
+            });
+
+            Ok(())
+        }
+
+        /// Registers the key the caller, a provider, signs availability statements with. `proof`
+        /// is the signature by `key` of the caller account, proving its possession so that no
+        /// key can be chosen to cancel out others in an aggregate.
+        #[pallet::call_index(12)]
+        #[pallet::weight(<T as Config>::WeightInfo::set_attestation_key())]
+        pub fn set_attestation_key(
+            origin: OriginFor<T>,
+            key: AttestationPublicOf<T>,
+            proof: AttestationSignatureOf<T>,
+        ) -> DispatchResult {
+            let who = ensure_signed(origin)?;
+            ensure!(Providers::<T>::contains_key(&who), Error::<T>::NotProvider);
+            let message = (ATTESTATION_KEY_CONTEXT, &who).encode();
+            ensure!(
+                T::AttestationScheme::verify_aggregate(&proof, &message, &[key.clone()]),
+                Error::<T>::BadSignature
+            );
+            AttestationKeys::<T>::insert(&who, key);
+
+            Self::deposit_event(Event::AttestationKeySet { who });
+
+            Ok(())
+        }
+
+        /// Records that the committee of a file held one of its chunks, as stated by more than
+        /// half of its members with a single aggregated signature. `signers` flags the members
+        /// whose signatures were aggregated, in the order of the committee.
+        ///
+        /// The statement must refer to a block within the last [`Config::ChallengePeriod`]
+        /// blocks, newer than the one last attested.
+        #[pallet::call_index(13)]
+        #[pallet::weight(<T as Config>::WeightInfo::attest_availability())]
+        pub fn attest_availability(
+            origin: OriginFor<T>,
+            statement: AvailabilityStatementOf<T>,
+            signers: BoundedVec<bool, T::CommitteeSize>,
+            signature: AttestationSignatureOf<T>,
+        ) -> DispatchResult {
+            ensure_signed(origin)?;
+
+            let merkle_root = statement.merkle_root;
+            let members = Committees::<T>::get(merkle_root).ok_or(Error::<T>::NoCommittee)?;
+            let pieces = TrustlessFileServer::<T>::file_pieces(merkle_root)
+                .ok_or(Error::<T>::FileNotFound)?;
+            ensure!(statement.position < pieces.into(), Error::<T>::InvalidPosition);
+            let now = <frame_system::Pallet<T>>::block_number();
+            ensure!(
+                statement.block <= now
+                    && now.saturating_sub(statement.block) < T::ChallengePeriod::get(),
+                Error::<T>::StaleAttestation
+            );
+            if let Some(last) = Availability::<T>::get(merkle_root) {
+                ensure!(statement.block > last.block, Error::<T>::StaleAttestation);
+            }
+            let keys = members
+                .iter()
+                .zip(signers.iter())
+                .filter(|(_, signed)| **signed)
+                .map(|(member, _)| {
+                    AttestationKeys::<T>::get(member).ok_or(Error::<T>::NoAttestationKey)
+                })
+                .collect::<Result<Vec<_>, _>>()?;
+            ensure!(keys.len() * 2 > members.len(), Error::<T>::NotEnoughAttesters);
+            ensure!(
+                T::AttestationScheme::verify_aggregate(&signature, &statement.encode(), &keys),
+                Error::<T>::BadSignature
+            );
+
+            let attesters = keys.len() as u32;
+            Availability::<T>::insert(
+                merkle_root,
+                AvailabilityAttestation {
+                    position: statement.position,
+                    block: statement.block,
+                    attesters,
+                },
+            );
+
+            Self::deposit_event(Event::AvailabilityAttested {
+                merkle_root,
+                position: statement.position,
+                block: statement.block,
+                attesters,
+            });
+
+            Ok(())
+        }
+
+        /// Gives a former provider its bond back once [`Config::UnbondingPeriod`] is over.
+        #[pallet::call_index(9)]
+        #[pallet::weight(<T as Config>::WeightInfo::withdraw_unbonded())]
+        pub fn withdraw_unbonded(origin: OriginFor<T>) -> DispatchResult {
+            let who = ensure_signed(origin)?;
+
+            let unbonding = Unbonding::<T>::get(&who).ok_or(Error::<T>::NotUnbonding)?;
+            let now = <frame_system::Pallet<T>>::block_number();
+            ensure!(now >= unbonding.unlocked_at, Error::<T>::StillUnbonding);
+            T::Currency::unreserve(&who, unbonding.amount);
+            Unbonding::<T>::remove(&who);
+
+            Self::deposit_event(Event::BondWithdrawn {
+                who,
+                amount: unbonding.amount,
+            });
+
+            Ok(())
+        }
+
+        /// Slashes up to `amount` of the bond of a provider, taken from its current bond first
+        /// and then from the one it is unbonding.
+        ///
+        /// The origin must satisfy [`Config::SlashOrigin`].
+        #[pallet::call_index(10)]
+        #[pallet::weight(<T as Config>::WeightInfo::slash_provider())]
+        pub fn slash_provider(
+            origin: OriginFor<T>,
+            who: T::AccountId,
+            amount: BalanceOf<T>,
+        ) -> DispatchResult {
+            T::SlashOrigin::ensure_origin(origin)?;
+
+            let mut left = amount;
+            Providers::<T>::mutate(&who, |provider| {
+                if let Some(provider) = provider {
+                    let part = left.min(provider.bond);
+                    provider.bond.saturating_reduce(part);
+                    left.saturating_reduce(part);
+                }
+            });
+            Unbonding::<T>::mutate_exists(&who, |unbonding| {
+                if let Some(bond) = unbonding {
+                    let part = left.min(bond.amount);
+                    bond.amount.saturating_reduce(part);
+                    left.saturating_reduce(part);
+                    if bond.amount.is_zero() {
+                        *unbonding = None;
+                    }
+                }
+            });
+            let slashed = amount.saturating_sub(left);
+            ensure!(!slashed.is_zero(), Error::<T>::NothingToSlash);
+            // the imbalance is dropped, burning the slashed funds
+            let _ = T::Currency::slash_reserved(&who, slashed);
+
+            Self::deposit_event(Event::ProviderSlashed {
+                who,
+                amount: slashed,
+            });
+
+            Ok(())
+        }
+    }
+
+    impl<T: Config> Pallet<T> {
+        /// Account escrowing the price of the deals and the deposits of the channels.
+        pub fn account_id() -> T::AccountId {
+            <T as Config>::PalletId::get().into_account_truncating()
+        }
+
+        /// Part of `price` earned by serving a file from `start` until `until`, out of a total of
+        /// `duration` blocks, along [`Config::RewardCurve`].
+        fn earned(
+            price: &BalanceOf<T>,
+            duration: T::BlockNumber,
+            start: T::BlockNumber,
+            until: T::BlockNumber,
+        ) -> BalanceOf<T> {
+            let elapsed = until.saturating_sub(start);
+            if elapsed >= duration {
+                return *price;
+            }
+            let served = Perbill::from_rational(elapsed, duration);
+            let share = T::RewardCurve::get()
+                .calculate_for_fraction_times_denominator(served.deconstruct(), Perbill::ACCURACY);
+            Perbill::from_parts(share).mul_floor(*price)
+        }
+
+        /// Challenges the provider of a deal on a random chunk of the file, to be answered within
+        /// [`Config::ChallengePeriod`] blocks. `None` if the file is no longer registered.
+        fn new_challenge(
+            deal_id: DealId,
+            merkle_root: T::Hash,
+            now: T::BlockNumber,
+        ) -> Option<Challenge<T::BlockNumber>> {
+            let pieces = TrustlessFileServer::<T>::file_pieces(merkle_root)?;
+            let (seed, _) = T::Randomness::random(&(CHALLENGE_SUBJECT, deal_id, now).encode());
+            let random = u32::decode(&mut seed.as_ref()).unwrap_or_default();
+            Some(Challenge {
+                position: (random % pieces.max(1)).into(),
+                deadline: now.saturating_add(T::ChallengePeriod::get()),
+            })
+        }
+
+        /// Records that the provider `who` of a deal has answered its current challenge, once
+        /// `check` accepts the answer, completing the deal if over or issuing a new challenge
+        /// otherwise. Completed deals are settled right away.
+        fn answer_challenge(
+            who: T::AccountId,
+            deal_id: DealId,
+            check: impl FnOnce(T::Hash, &Challenge<T::BlockNumber>) -> DispatchResult,
+        ) -> DispatchResult {
+            let mut deal = Deals::<T>::get(deal_id).ok_or(Error::<T>::DealNotFound)?;
+            let DealState::Active {
+                provider,
+                start,
+                collateral,
+                paid_until,
+                proven_until,
+                challenge,
+            } = &mut deal.state
+            else {
+                return Err(Error::<T>::NotAccepted.into());
+            };
+            ensure!(*provider == who, Error::<T>::NotDealProvider);
+            let now = <frame_system::Pallet<T>>::block_number();
+            ensure!(now <= challenge.deadline, Error::<T>::ChallengeExpired);
+            check(deal.merkle_root, challenge)?;
+            TrustlessFileServer::<T>::note_challenge(&who, true);
+
+            let end = start.saturating_add(deal.duration);
+            *proven_until = now.min(end);
+
+            Self::deposit_event(Event::ChallengeAnswered {
+                deal_id,
+                position: challenge.position,
+            });
+
+            if *proven_until == end {
+                let payment = deal.price.saturating_sub(Self::earned(
+                    &deal.price,
+                    deal.duration,
+                    *start,
+                    *paid_until,
+                ));
+                T::Currency::transfer(
+                    &Self::account_id(),
+                    &who,
+                    payment,
+                    ExistenceRequirement::AllowDeath,
+                )?;
+                T::Currency::unreserve(&who, *collateral);
+                Self::release_provider(&who);
+                Deals::<T>::remove(deal_id);
+                Self::deposit_event(Event::DealSettled {
+                    deal_id,
+                    provider: who,
+                    payment,
+                });
+                Self::deposit_event(Event::DealCompleted { deal_id });
+            } else {
+                *challenge = Self::new_challenge(deal_id, deal.merkle_root, now)
+                    .ok_or(Error::<T>::FileNotFound)?;
+                Self::deposit_event(Event::ChallengeIssued {
+                    deal_id,
+                    position: challenge.position,
+                    deadline: challenge.deadline,
+                });
+                Deals::<T>::insert(deal_id, deal);
+            }
+
+            Ok(())
+        }
+
+        /// Ends a deal on behalf of `who`, as described in `terminate_deal`.
+        fn end_deal(who: T::AccountId, deal_id: DealId, deal: DealOf<T>) -> DispatchResult {
+            let refund = match &deal.state {
+                DealState::Proposed => {
+                    ensure!(who == deal.client, Error::<T>::NotDealParty);
+                    deal.price
+                }
+                DealState::Active {
+                    provider,
+                    start,
+                    collateral,
+                    paid_until,
+                    proven_until,
+                    challenge,
+                } => {
+                    let now = <frame_system::Pallet<T>>::block_number();
+                    let expired = now > challenge.deadline;
+                    ensure!(
+                        expired || who == deal.client || who == *provider,
+                        Error::<T>::NotDealParty
+                    );
+                    let paid = Self::earned(&deal.price, deal.duration, *start, *paid_until);
+                    if expired {
+                        TrustlessFileServer::<T>::note_challenge(provider, false);
+                    }
+                    let forfeit = TrustlessFileServer::<T>::file_exists(deal.merkle_root)
+                        && (expired || who != deal.client);
+                    let payment = if forfeit {
+                        let missing = T::Currency::repatriate_reserved(
+                            provider,
+                            &deal.client,
+                            *collateral,
+                            BalanceStatus::Free,
+                        )?;
+                        Self::deposit_event(Event::CollateralForfeited {
+                            deal_id,
+                            provider: provider.clone(),
+                            amount: collateral.saturating_sub(missing),
+                        });
+                        Self::earned(&deal.price, deal.duration, *start, *proven_until)
+                            .saturating_sub(paid)
+                    } else {
+                        T::Currency::unreserve(provider, *collateral);
+                        let until = now.min(start.saturating_add(deal.duration));
+                        Self::earned(&deal.price, deal.duration, *start, until).saturating_sub(paid)
+                    };
+                    T::Currency::transfer(
+                        &Self::account_id(),
+                        provider,
+                        payment,
+                        ExistenceRequirement::AllowDeath,
+                    )?;
+                    Self::release_provider(provider);
+                    deal.price.saturating_sub(paid).saturating_sub(payment)
+                }
+            };
+            T::Currency::transfer(
+                &Self::account_id(),
+                &deal.client,
+                refund,
+                ExistenceRequirement::AllowDeath,
+            )?;
+            Deals::<T>::remove(deal_id);
+
+            Self::deposit_event(Event::DealTerminated {
+                deal_id,
+                who,
+                refund,
+            });
+
+            Ok(())
+        }
+
+        /// Nonce the proofs answering a challenge without revealing the chunk are bound to.
+        pub fn challenge_nonce(deal_id: DealId, challenge: &Challenge<T::BlockNumber>) -> [u8; 32] {
+            sp_io::hashing::blake2_256(&(deal_id, challenge).encode())
+        }
+
+        fn release_provider(who: &T::AccountId) {
+            Providers::<T>::mutate(who, |provider| {
+                if let Some(provider) = provider {
+                    provider.active_deals = provider.active_deals.saturating_sub(1);
+                }
+            });
+        }
+    }
+}
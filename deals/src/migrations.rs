@@ -0,0 +1,140 @@
+//! Storage migrations of the trustless file server deals pallet.
+use codec::{Decode, Encode};
+use frame_support::log;
+use frame_support::traits::{Get, GetStorageVersion, OnRuntimeUpgrade, StorageVersion};
+use frame_support::weights::Weight;
+use sp_std::marker::PhantomData;
+#[cfg(feature = "try-runtime")]
+use sp_std::vec::Vec;
+
+use crate::{
+    Availability, AvailabilityAttestation, Challenge, Config, Deal, DealState, Deals, Pallet,
+};
+
+/// Makes the positions of challenges and availability attestations 64 bits, as in the file
+/// server pallet.
+pub mod v1 {
+    use super::*;
+    use pallet_trustless_file_server::BalanceOf;
+
+    #[derive(Encode, Decode)]
+    pub(crate) struct OldChallenge<BlockNumber> {
+        pub position: u32,
+        pub deadline: BlockNumber,
+    }
+
+    #[derive(Encode, Decode)]
+    pub(crate) enum OldDealState<AccountId, Balance, BlockNumber> {
+        Proposed,
+        Active {
+            provider: AccountId,
+            start: BlockNumber,
+            collateral: Balance,
+            paid_until: BlockNumber,
+            proven_until: BlockNumber,
+            challenge: OldChallenge<BlockNumber>,
+        },
+    }
+
+    #[derive(Encode, Decode)]
+    pub(crate) struct OldDeal<AccountId, Hash, Balance, BlockNumber> {
+        pub client: AccountId,
+        pub merkle_root: Hash,
+        pub duration: BlockNumber,
+        pub price: Balance,
+        pub state: OldDealState<AccountId, Balance, BlockNumber>,
+    }
+
+    #[derive(Encode, Decode)]
+    pub(crate) struct OldAvailabilityAttestation<BlockNumber> {
+        pub position: u32,
+        pub block: BlockNumber,
+        pub attesters: u32,
+    }
+
+    pub(crate) type OldDealOf<T> = OldDeal<
+        <T as frame_system::Config>::AccountId,
+        <T as frame_system::Config>::Hash,
+        BalanceOf<T>,
+        <T as frame_system::Config>::BlockNumber,
+    >;
+
+    /// Rewrites the deals and availability attestations with 64 bit positions.
+    pub struct MigrateToV1<T>(PhantomData<T>);
+
+    impl<T: Config> OnRuntimeUpgrade for MigrateToV1<T> {
+        fn on_runtime_upgrade() -> Weight {
+            let on_chain = Pallet::<T>::on_chain_storage_version();
+            if on_chain >= 1 {
+                log::info!("MigrateToV1 not needed, storage version is {:?}", on_chain);
+                return T::DbWeight::get().reads(1);
+            }
+
+            let mut items = 0u64;
+            Deals::<T>::translate::<OldDealOf<T>, _>(|_, old| {
+                items += 1;
+                let state = match old.state {
+                    OldDealState::Proposed => DealState::Proposed,
+                    OldDealState::Active {
+                        provider,
+                        start,
+                        collateral,
+                        paid_until,
+                        proven_until,
+                        challenge,
+                    } => DealState::Active {
+                        provider,
+                        start,
+                        collateral,
+                        paid_until,
+                        proven_until,
+                        challenge: Challenge {
+                            position: challenge.position.into(),
+                            deadline: challenge.deadline,
+                        },
+                    },
+                };
+                Some(Deal {
+                    client: old.client,
+                    merkle_root: old.merkle_root,
+                    duration: old.duration,
+                    price: old.price,
+                    state,
+                })
+            });
+            Availability::<T>::translate::<OldAvailabilityAttestation<T::BlockNumber>, _>(
+                |_, old| {
+                    items += 1;
+                    Some(AvailabilityAttestation {
+                        position: old.position.into(),
+                        block: old.block,
+                        attesters: old.attesters,
+                    })
+                },
+            );
+            StorageVersion::new(1).put::<Pallet<T>>();
+
+            log::info!("Migrated {} items to storage version 1", items);
+            T::DbWeight::get().reads_writes(1 + items, 1 + items)
+        }
+
+        #[cfg(feature = "try-runtime")]
+        fn pre_upgrade() -> Result<Vec<u8>, &'static str> {
+            Ok((Deals::<T>::iter_keys().count() as u32).encode())
+        }
+
+        #[cfg(feature = "try-runtime")]
+        fn post_upgrade(state: Vec<u8>) -> Result<(), &'static str> {
+            let deals = u32::decode(&mut state.as_slice()).map_err(|_| "Invalid state")?;
+            frame_support::ensure!(
+                Pallet::<T>::on_chain_storage_version() == 1,
+                "Storage version not updated"
+            );
+            frame_support::ensure!(
+                Deals::<T>::iter_values().count() as u32 == deals,
+                "Undecodable deals"
+            );
+            Ok(())
+        }
+    }
+}
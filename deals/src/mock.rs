@@ -0,0 +1,365 @@
+use crate as pallet_trustless_file_server_deals;
+use codec::Encode;
+use frame_support::traits::tokens::BalanceConversion;
+use frame_support::traits::{
+    AsEnsureOriginWithArg, ConstBool, ConstU16, ConstU32, ConstU64, EqualPrivilegeOnly, Everything,
+    NeverEnsureOrigin, Nothing, Randomness, SortedMembers,
+};
+use frame_support::weights::Weight;
+use frame_support::PalletId;
+use frame_system::{EnsureRoot, EnsureSigned};
+use sp_core::H256;
+use sp_runtime::{
+    curve::PiecewiseLinear,
+    testing::{Header, TestSignature, UintAuthorityId},
+    traits::{BlakeTwo256, Hash, IdentityLookup},
+    BuildStorage, Perbill,
+};
+use xcm::latest::MultiLocation;
+
+pub type UncheckedExtrinsic = frame_system::mocking::MockUncheckedExtrinsic<Test>;
+type Block = frame_system::mocking::MockBlock<Test>;
+
+/// Pays deals in proportion to the part of their duration served.
+pub const LINEAR_CURVE: PiecewiseLinear<'static> = PiecewiseLinear {
+    points: &[
+        (Perbill::from_parts(0), Perbill::from_parts(0)),
+        (Perbill::from_parts(1_000_000_000), Perbill::from_parts(1_000_000_000)),
+    ],
+    maximum: Perbill::from_parts(1_000_000_000),
+};
+
+frame_support::parameter_types! {
+    pub const MaximumSchedulerWeight: Weight = Weight::from_parts(1_000_000_000_000, u64::MAX);
+    pub const AttestationWeight: Weight = Weight::from_parts(1_000_000_000, 65536);
+    pub const AttestationCallIndex: [u8; 2] = [60, 0];
+    pub const IpfsNodeUrl: &'static str = "http://127.0.0.1:5001";
+    pub const TrustlessFileServerPalletId: PalletId = PalletId(*b"py/tfsrv");
+    pub const DealsPalletId: PalletId = PalletId(*b"py/tfdls");
+    pub const CollateralRatio: Perbill = Perbill::from_percent(50);
+    pub static RewardCurve: &'static PiecewiseLinear<'static> = &LINEAR_CURVE;
+}
+
+/// Balance of the funded accounts.
+pub const INITIAL_BALANCE: u64 = 1_000_000;
+
+pub struct SamePrice;
+
+impl BalanceConversion<u64, u32, u64> for SamePrice {
+    type Error = ();
+
+    fn to_asset_balance(balance: u64, _asset_id: u32) -> Result<u64, Self::Error> {
+        Ok(balance)
+    }
+}
+
+/// Deterministic randomness, so that challenges only depend on the deal and the block.
+pub struct TestRandomness;
+
+impl Randomness<H256, u64> for TestRandomness {
+    fn random(subject: &[u8]) -> (H256, u64) {
+        (BlakeTwo256::hash(subject), System::block_number())
+    }
+}
+
+/// Aggregates signatures by listing their signers, so that aggregates are valid as long as they
+/// name every signer, in order, and hold the message.
+pub struct TestAggregate;
+
+impl crate::AggregateVerify for TestAggregate {
+    type Public = u64;
+    type Signature = (Vec<u64>, Vec<u8>);
+
+    fn verify_aggregate(signature: &Self::Signature, message: &[u8], signers: &[u64]) -> bool {
+        signature.0 == signers && signature.1 == message
+    }
+}
+
+/// Accepts proofs made of the merkle root, position and nonce they prove a chunk for.
+pub struct TestChunkProofs;
+
+impl crate::ChunkProofVerifier for TestChunkProofs {
+    fn verify(merkle_root: &[u8], position: u64, nonce: &[u8; 32], proof: &[u8]) -> bool {
+        proof == (merkle_root, position, nonce).encode()
+    }
+}
+
+/// Nobody to escrow keys with, as deals do not depend on time-locked files.
+pub struct NoCommittee;
+
+impl SortedMembers<u64> for NoCommittee {
+    fn sorted_members() -> Vec<u64> {
+        Vec::new()
+    }
+}
+
+/// Signs vouchers and availability statements in benchmarks: test signatures are valid as long as
+/// they name the signers and hold the message.
+#[cfg(feature = "runtime-benchmarks")]
+pub struct TestSigner;
+
+#[cfg(feature = "runtime-benchmarks")]
+impl crate::BenchmarkHelper<UintAuthorityId, TestSignature, u64, (Vec<u64>, Vec<u8>)>
+    for TestSigner
+{
+    fn signer() -> UintAuthorityId {
+        UintAuthorityId(1)
+    }
+
+    fn sign(signer: &UintAuthorityId, message: &[u8]) -> TestSignature {
+        TestSignature(signer.0, message.to_vec())
+    }
+
+    fn attester(index: u32) -> u64 {
+        index.into()
+    }
+
+    fn attest(attesters: &[u64], message: &[u8]) -> (Vec<u64>, Vec<u8>) {
+        (attesters.to_vec(), message.to_vec())
+    }
+
+    fn prove_chunk(merkle_root: &[u8], position: u64, nonce: &[u8; 32]) -> Vec<u8> {
+        (merkle_root, position, nonce).encode()
+    }
+}
+
+// Configure a mock runtime to test the pallet.
+frame_support::construct_runtime!(
+    pub enum Test where
+        Block = Block,
+        NodeBlock = Block,
+        UncheckedExtrinsic = UncheckedExtrinsic,
+    {
+        System: frame_system,
+        Balances: pallet_balances,
+        Assets: pallet_assets,
+        Preimage: pallet_preimage,
+        Scheduler: pallet_scheduler,
+        Timestamp: pallet_timestamp,
+        TrustlessFileServer: pallet_trustless_file_server,
+        TrustlessFileServerDeals: pallet_trustless_file_server_deals,
+    }
+);
+
+impl frame_system::Config for Test {
+    type BaseCallFilter = Everything;
+    type BlockWeights = ();
+    type BlockLength = ();
+    type RuntimeOrigin = RuntimeOrigin;
+    type RuntimeCall = RuntimeCall;
+    type Index = u64;
+    type BlockNumber = u64;
+    type Hash = H256;
+    type Hashing = BlakeTwo256;
+    type AccountId = u64;
+    type Lookup = IdentityLookup<Self::AccountId>;
+    type Header = Header;
+    type RuntimeEvent = RuntimeEvent;
+    type BlockHashCount = ConstU64<250>;
+    type DbWeight = ();
+    type Version = ();
+    type PalletInfo = PalletInfo;
+    type AccountData = pallet_balances::AccountData<u64>;
+    type OnNewAccount = ();
+    type OnKilledAccount = ();
+    type SystemWeightInfo = ();
+    type SS58Prefix = ConstU16<42>;
+    type OnSetCode = ();
+    type MaxConsumers = ConstU32<16>;
+}
+
+impl pallet_balances::Config for Test {
+    type Balance = u64;
+    type DustRemoval = ();
+    type RuntimeEvent = RuntimeEvent;
+    type ExistentialDeposit = ConstU64<1>;
+    type AccountStore = System;
+    type WeightInfo = ();
+    type MaxLocks = ();
+    type MaxReserves = ();
+    type ReserveIdentifier = [u8; 8];
+    type FreezeIdentifier = ();
+    type MaxFreezes = ();
+    type HoldIdentifier = pallet_trustless_file_server::HoldReason;
+    type MaxHolds = ConstU32<5>;
+}
+
+impl pallet_assets::Config for Test {
+    type RuntimeEvent = RuntimeEvent;
+    type Balance = u64;
+    type RemoveItemsLimit = ConstU32<5>;
+    type AssetId = u32;
+    type AssetIdParameter = u32;
+    type Currency = Balances;
+    type CreateOrigin = AsEnsureOriginWithArg<EnsureSigned<u64>>;
+    type ForceOrigin = EnsureRoot<u64>;
+    type AssetDeposit = ConstU64<1>;
+    type AssetAccountDeposit = ConstU64<1>;
+    type MetadataDepositBase = ConstU64<1>;
+    type MetadataDepositPerByte = ConstU64<1>;
+    type ApprovalDeposit = ConstU64<1>;
+    type StringLimit = ConstU32<50>;
+    type Freezer = ();
+    type Extra = ();
+    type CallbackHandle = ();
+    type WeightInfo = ();
+    #[cfg(feature = "runtime-benchmarks")]
+    type BenchmarkHelper = ();
+}
+
+impl pallet_preimage::Config for Test {
+    type RuntimeEvent = RuntimeEvent;
+    type WeightInfo = ();
+    type Currency = Balances;
+    type ManagerOrigin = EnsureRoot<u64>;
+    type BaseDeposit = ConstU64<0>;
+    type ByteDeposit = ConstU64<0>;
+}
+
+impl pallet_timestamp::Config for Test {
+    type Moment = u64;
+    type OnTimestampSet = ();
+    type MinimumPeriod = ConstU64<1>;
+    type WeightInfo = ();
+}
+
+impl pallet_scheduler::Config for Test {
+    type RuntimeEvent = RuntimeEvent;
+    type RuntimeOrigin = RuntimeOrigin;
+    type PalletsOrigin = OriginCaller;
+    type RuntimeCall = RuntimeCall;
+    type MaximumWeight = MaximumSchedulerWeight;
+    type ScheduleOrigin = EnsureRoot<u64>;
+    type MaxScheduledPerBlock = ConstU32<8>;
+    type WeightInfo = ();
+    type OriginPrivilegeCmp = EqualPrivilegeOnly;
+    type Preimages = Preimage;
+}
+
+impl pallet_trustless_file_server::Config for Test {
+    type RuntimeEvent = RuntimeEvent;
+    type IpfsNodeUrl = IpfsNodeUrl;
+    type AnnounceToDht = ConstBool<false>;
+    type IpfsClusterUrl = ();
+    type ClusterReplicationFactor = ConstU32<3>;
+    type GatewayUrl = ();
+    type ProbeInterval = ConstU64<100>;
+    type Randomness = TestRandomness;
+    type UnixTime = Timestamp;
+    type AuditPeriod = ConstU64<0>;
+    type AuditSampleSize = ConstU32<2>;
+    type PinningServiceUrl = ();
+    type RepinInterval = ConstU64<50>;
+    type MaxRepinsPerRun = ConstU32<2>;
+    type ServedReportInterval = ConstU64<0>;
+    type MaxFileSize = ConstU32<32768>;
+    type MaxTotalBytes = ConstU64<{ u64::MAX }>;
+    type Currency = Balances;
+    type NativeBalance = Balances;
+    type RuntimeHoldReason = pallet_trustless_file_server::HoldReason;
+    type Assets = Assets;
+    type BalanceToAssetBalance = SamePrice;
+    type DepositBase = ConstU64<100>;
+    type DepositPerByte = ConstU64<1>;
+    type PriceOracle = ();
+    type PalletId = TrustlessFileServerPalletId;
+    type MaxRegistrants = ConstU32<3>;
+    type AdminOrigin = EnsureRoot<u64>;
+    type UploadOrigin = EnsureSigned<u64>;
+    type ContentPolicy = Everything;
+    type MinChunkSize = ConstU32<1024>;
+    type MaxChunkSize = ConstU32<{ 1024 * 1024 }>;
+    type TargetPieces = ConstU32<32>;
+    type Bep52Compatible = ConstBool<false>;
+    type IpfsChunkSize = ();
+    type MaxUploadsPerBlock = ConstU32<4>;
+    type MaxUploadsPerAccountPerBlock = ConstU32<4>;
+    type MaxFilesPerBatch = ConstU32<4>;
+    type UnsignedUploadDifficulty = ();
+    type ProgressReportInterval = ConstU32<4>;
+    type MaxChunksPerOcwRun = ConstU32<8>;
+    type UnsignedPriority = ConstU64<{ 1 << 20 }>;
+    type AuthorityId = UintAuthorityId;
+    type MaxOcwAuthorities = ConstU32<4>;
+    type MaxCollectionSize = ConstU32<4>;
+    type StringLimit = ConstU32<64>;
+    type MaxTags = ConstU32<8>;
+    type MaxNameLength = ConstU32<32>;
+    type NameDeposit = ConstU64<10>;
+    type NameDepositPerByte = ConstU64<1>;
+    type NameRegistrationPeriod = ConstU64<100>;
+    type MaxKeyLength = ConstU32<64>;
+    type KeyCommittee = NoCommittee;
+    type MaxKeyHolders = ConstU32<16>;
+    type MaxEncryptedShareLength = ConstU32<128>;
+    type ModerationOrigin = EnsureRoot<u64>;
+    type LargeFileThreshold = ConstU32<32768>;
+    type LargeUploadOrigin = EnsureRoot<u64>;
+    type RequireReview = ConstBool<false>;
+    type ReviewOrigin = EnsureRoot<u64>;
+    type XcmSender = ();
+    type AttestationCallIndex = AttestationCallIndex;
+    type AttestationWeight = AttestationWeight;
+    type SiblingOrigin = NeverEnsureOrigin<MultiLocation>;
+    type TrustedSiblings = Nothing;
+    type ReportBond = ConstU64<50>;
+    type MaxReportsPerFile = ConstU32<2>;
+    type MaxHistoryLength = ConstU32<16>;
+    type RuntimeCall = RuntimeCall;
+    type PalletsOrigin = OriginCaller;
+    type Scheduler = Scheduler;
+    type Preimages = Preimage;
+    type WeightInfo = ();
+}
+
+impl pallet_trustless_file_server_deals::Config for Test {
+    type RuntimeEvent = RuntimeEvent;
+    type Randomness = TestRandomness;
+    type PalletId = DealsPalletId;
+    type ProviderBond = ConstU64<50>;
+    type UnbondingPeriod = ConstU64<20>;
+    type SlashOrigin = EnsureRoot<u64>;
+    type CollateralRatio = CollateralRatio;
+    type MinDealDuration = ConstU64<10>;
+    type ChallengePeriod = ConstU64<10>;
+    type SettlementPeriod = ConstU64<10>;
+    type MaxDealsPerProvider = ConstU32<2>;
+    type CommitteeSize = ConstU32<2>;
+    type ChunkProofVerifier = TestChunkProofs;
+    type MaxChunkProofLength = ConstU32<256>;
+    type RewardCurve = RewardCurve;
+    type OffchainSignature = TestSignature;
+    type OffchainPublic = UintAuthorityId;
+    type MaxVouchersPerRedeem = ConstU32<8>;
+    type ChannelClosePeriod = ConstU64<10>;
+    type AttestationScheme = TestAggregate;
+    #[cfg(feature = "runtime-benchmarks")]
+    type BenchmarkHelper = TestSigner;
+    type WeightInfo = ();
+}
+
+impl<C> frame_system::offchain::SendTransactionTypes<C> for Test
+where
+    RuntimeCall: From<C>,
+{
+    type OverarchingCall = RuntimeCall;
+    type Extrinsic = UncheckedExtrinsic;
+}
+
+// Build genesis storage according to the mock runtime.
+pub fn new_test_ext() -> sp_io::TestExternalities {
+    let mut storage = frame_system::GenesisConfig::default()
+        .build_storage::<Test>()
+        .unwrap();
+    pallet_balances::GenesisConfig::<Test> {
+        balances: [1, 2, 3, 4]
+            .iter()
+            .map(|who| (*who, INITIAL_BALANCE))
+            .collect(),
+    }
+    .assimilate_storage(&mut storage)
+    .unwrap();
+    let mut ext: sp_io::TestExternalities = storage.into();
+    ext.execute_with(|| System::set_block_number(1));
+    ext
+}
@@ -0,0 +1,1175 @@
+use codec::{Decode, Encode};
+use frame_support::traits::{ConstU32, Currency};
+use frame_support::{assert_noop, assert_ok, BoundedVec};
+use sp_runtime::testing::{TestSignature, H256};
+
+use crate::mock::*;
+use crate::pallet::ATTESTATION_KEY_CONTEXT;
+use crate::{
+    AttestationKeys, Availability, AvailabilityAttestation, AvailabilityStatement, Channels,
+    Committees, Deal, DealState, Deals, Error, Event, NextChannelId, NextDealId, Providers,
+    Voucher,
+};
+
+const CLIENT: u64 = 1;
+const PROVIDER: u64 = 2;
+const PRICE: u64 = 1000;
+const DURATION: u64 = 100;
+const CHUNK_PRICE: u64 = 10;
+
+fn upload_test_file() -> H256 {
+    let bytes = include_bytes!("../../img/substrate.png");
+    assert_ok!(TrustlessFileServer::upload_file(
+        RuntimeOrigin::signed(CLIENT),
+        bytes.to_vec(),
+        None,
+        None
+    ));
+    let merkle_root = &TrustlessFileServer::get_files()[0].0;
+    H256::decode(&mut merkle_root.as_slice()).unwrap()
+}
+
+/// Registers a file, proposes a deal on it and has it accepted by `PROVIDER`.
+fn start_deal() -> (H256, u64) {
+    let merkle_root = upload_test_file();
+    assert_ok!(TrustlessFileServerDeals::register_provider(RuntimeOrigin::signed(PROVIDER)));
+    assert_ok!(TrustlessFileServerDeals::propose_deal(
+        RuntimeOrigin::signed(CLIENT),
+        merkle_root,
+        DURATION,
+        PRICE
+    ));
+    let deal_id = NextDealId::<Test>::get() - 1;
+    assert_ok!(TrustlessFileServerDeals::accept_deal(RuntimeOrigin::signed(PROVIDER), deal_id));
+    (merkle_root, deal_id)
+}
+
+/// Content of the chunk the provider of `deal_id` is currently challenged on.
+fn challenged_chunk(deal_id: u64) -> Vec<u8> {
+    let Some(Deal {
+        state: DealState::Active { challenge, .. },
+        ..
+    }) = Deals::<Test>::get(deal_id)
+    else {
+        panic!("the deal is not active");
+    };
+    let bytes = include_bytes!("../../img/substrate.png");
+    bytes
+        .chunks(1024)
+        .nth(challenge.position as usize)
+        .unwrap()
+        .to_vec()
+}
+
+fn escrow_balance() -> u64 {
+    Balances::free_balance(TrustlessFileServerDeals::account_id())
+}
+
+#[test]
+fn it_should_register_and_unregister_providers() {
+    new_test_ext().execute_with(|| {
+        assert_ok!(TrustlessFileServerDeals::register_provider(RuntimeOrigin::signed(PROVIDER)));
+        assert_eq!(Balances::reserved_balance(PROVIDER), 50);
+        assert_noop!(
+            TrustlessFileServerDeals::register_provider(RuntimeOrigin::signed(PROVIDER)),
+            Error::<Test>::AlreadyProvider
+        );
+        System::assert_last_event(
+            Event::ProviderRegistered {
+                who: PROVIDER,
+                bond: 50,
+            }
+            .into(),
+        );
+
+        assert_ok!(TrustlessFileServerDeals::unregister_provider(RuntimeOrigin::signed(PROVIDER)));
+        System::assert_last_event(
+            Event::ProviderUnregistered {
+                who: PROVIDER,
+                unlocked_at: 21,
+            }
+            .into(),
+        );
+        assert!(!Providers::<Test>::contains_key(PROVIDER));
+        assert_noop!(
+            TrustlessFileServerDeals::unregister_provider(RuntimeOrigin::signed(PROVIDER)),
+            Error::<Test>::NotProvider
+        );
+
+        // the bond is kept for the unbonding period
+        assert_eq!(Balances::reserved_balance(PROVIDER), 50);
+        System::set_block_number(20);
+        assert_noop!(
+            TrustlessFileServerDeals::withdraw_unbonded(RuntimeOrigin::signed(PROVIDER)),
+            Error::<Test>::StillUnbonding
+        );
+        System::set_block_number(21);
+        assert_ok!(TrustlessFileServerDeals::withdraw_unbonded(RuntimeOrigin::signed(PROVIDER)));
+        assert_eq!(Balances::reserved_balance(PROVIDER), 0);
+        System::assert_last_event(
+            Event::BondWithdrawn {
+                who: PROVIDER,
+                amount: 50,
+            }
+            .into(),
+        );
+        assert_noop!(
+            TrustlessFileServerDeals::withdraw_unbonded(RuntimeOrigin::signed(PROVIDER)),
+            Error::<Test>::NotUnbonding
+        );
+    });
+}
+
+#[test]
+fn it_should_slash_bonds_until_withdrawn() {
+    use crate::UnbondingBond;
+
+    new_test_ext().execute_with(|| {
+        assert_ok!(TrustlessFileServerDeals::register_provider(RuntimeOrigin::signed(PROVIDER)));
+        assert_ok!(TrustlessFileServerDeals::unregister_provider(RuntimeOrigin::signed(PROVIDER)));
+        // registering again before withdrawing
+        System::set_block_number(5);
+        assert_ok!(TrustlessFileServerDeals::register_provider(RuntimeOrigin::signed(PROVIDER)));
+        assert_eq!(Balances::reserved_balance(PROVIDER), 100);
+        let issuance = Balances::total_issuance();
+
+        assert_noop!(
+            TrustlessFileServerDeals::slash_provider(RuntimeOrigin::signed(CLIENT), PROVIDER, 10),
+            sp_runtime::DispatchError::BadOrigin
+        );
+        // the current bond goes first
+        assert_ok!(TrustlessFileServerDeals::slash_provider(RuntimeOrigin::root(), PROVIDER, 70));
+        System::assert_last_event(
+            Event::ProviderSlashed {
+                who: PROVIDER,
+                amount: 70,
+            }
+            .into(),
+        );
+        assert_eq!(Providers::<Test>::get(PROVIDER).unwrap().bond, 0);
+        assert_eq!(
+            TrustlessFileServerDeals::get_unbonding(PROVIDER),
+            Some(UnbondingBond {
+                amount: 30,
+                unlocked_at: 21
+            })
+        );
+        assert_eq!(Balances::reserved_balance(PROVIDER), 30);
+        assert_eq!(Balances::total_issuance(), issuance - 70);
+
+        // leaving again adds to the bond being unbonded, and restarts its period
+        assert_ok!(TrustlessFileServerDeals::unregister_provider(RuntimeOrigin::signed(PROVIDER)));
+        assert_eq!(
+            TrustlessFileServerDeals::get_unbonding(PROVIDER),
+            Some(UnbondingBond {
+                amount: 30,
+                unlocked_at: 25
+            })
+        );
+        assert_ok!(TrustlessFileServerDeals::slash_provider(RuntimeOrigin::root(), PROVIDER, 50));
+        System::assert_last_event(
+            Event::ProviderSlashed {
+                who: PROVIDER,
+                amount: 30,
+            }
+            .into(),
+        );
+        assert_eq!(TrustlessFileServerDeals::get_unbonding(PROVIDER), None);
+        assert_eq!(Balances::reserved_balance(PROVIDER), 0);
+        assert_noop!(
+            TrustlessFileServerDeals::slash_provider(RuntimeOrigin::root(), PROVIDER, 10),
+            Error::<Test>::NothingToSlash
+        );
+    });
+}
+
+#[test]
+fn it_should_escrow_the_price_of_proposed_deals() {
+    new_test_ext().execute_with(|| {
+        let merkle_root = upload_test_file();
+        assert_noop!(
+            TrustlessFileServerDeals::propose_deal(
+                RuntimeOrigin::signed(CLIENT),
+                H256::repeat_byte(1),
+                DURATION,
+                PRICE
+            ),
+            Error::<Test>::FileNotFound
+        );
+        assert_noop!(
+            TrustlessFileServerDeals::propose_deal(
+                RuntimeOrigin::signed(CLIENT),
+                merkle_root,
+                9,
+                PRICE
+            ),
+            Error::<Test>::DurationTooShort
+        );
+        assert_noop!(
+            TrustlessFileServerDeals::propose_deal(
+                RuntimeOrigin::signed(CLIENT),
+                merkle_root,
+                DURATION,
+                0
+            ),
+            Error::<Test>::ZeroPrice
+        );
+
+        let free = Balances::free_balance(CLIENT);
+        assert_ok!(TrustlessFileServerDeals::propose_deal(
+            RuntimeOrigin::signed(CLIENT),
+            merkle_root,
+            DURATION,
+            PRICE
+        ));
+        assert_eq!(Balances::free_balance(CLIENT), free - PRICE);
+        assert_eq!(escrow_balance(), PRICE);
+        assert_eq!(Deals::<Test>::get(0).unwrap().state, DealState::Proposed);
+        assert_eq!(NextDealId::<Test>::get(), 1);
+        System::assert_last_event(
+            Event::DealProposed {
+                deal_id: 0,
+                client: CLIENT,
+                merkle_root,
+                duration: DURATION,
+                price: PRICE,
+            }
+            .into(),
+        );
+    });
+}
+
+#[test]
+fn it_should_only_let_providers_accept_deals() {
+    new_test_ext().execute_with(|| {
+        let merkle_root = upload_test_file();
+        assert_ok!(TrustlessFileServerDeals::propose_deal(
+            RuntimeOrigin::signed(CLIENT),
+            merkle_root,
+            DURATION,
+            PRICE
+        ));
+        assert_noop!(
+            TrustlessFileServerDeals::accept_deal(RuntimeOrigin::signed(PROVIDER), 0),
+            Error::<Test>::NotProvider
+        );
+        assert_ok!(TrustlessFileServerDeals::register_provider(RuntimeOrigin::signed(PROVIDER)));
+        assert_noop!(
+            TrustlessFileServerDeals::accept_deal(RuntimeOrigin::signed(PROVIDER), 1),
+            Error::<Test>::DealNotFound
+        );
+
+        assert_ok!(TrustlessFileServerDeals::accept_deal(RuntimeOrigin::signed(PROVIDER), 0));
+        // bond plus half the price as collateral
+        assert_eq!(Balances::reserved_balance(PROVIDER), 50 + 500);
+        assert_eq!(Providers::<Test>::get(PROVIDER).unwrap().active_deals, 1);
+        System::assert_has_event(
+            Event::DealAccepted {
+                deal_id: 0,
+                provider: PROVIDER,
+                collateral: 500,
+            }
+            .into(),
+        );
+        let Some(Deal {
+            state: DealState::Active { challenge, .. },
+            ..
+        }) = Deals::<Test>::get(0)
+        else {
+            panic!("the deal is not active");
+        };
+        assert!(challenge.position < 12);
+        assert_eq!(challenge.deadline, 11);
+
+        assert_ok!(TrustlessFileServerDeals::register_provider(RuntimeOrigin::signed(3)));
+        assert_noop!(
+            TrustlessFileServerDeals::accept_deal(RuntimeOrigin::signed(3), 0),
+            Error::<Test>::AlreadyAccepted
+        );
+        assert_noop!(
+            TrustlessFileServerDeals::unregister_provider(RuntimeOrigin::signed(PROVIDER)),
+            Error::<Test>::ProviderHasDeals
+        );
+    });
+}
+
+#[test]
+fn it_should_only_let_committee_members_accept_deals() {
+    new_test_ext().execute_with(|| {
+        let merkle_root = upload_test_file();
+        assert_noop!(
+            TrustlessFileServerDeals::assign_committee(RuntimeOrigin::signed(CLIENT), merkle_root),
+            Error::<Test>::NoProviders
+        );
+        for who in [PROVIDER, 3, 4] {
+            assert_ok!(TrustlessFileServerDeals::register_provider(RuntimeOrigin::signed(who)));
+        }
+        assert_ok!(TrustlessFileServerDeals::assign_committee(
+            RuntimeOrigin::signed(CLIENT),
+            merkle_root
+        ));
+        let members = Committees::<Test>::get(merkle_root).unwrap();
+        assert_eq!(members.len(), 2);
+        System::assert_has_event(
+            Event::CommitteeAssigned {
+                merkle_root,
+                members: members.clone(),
+            }
+            .into(),
+        );
+        assert_noop!(
+            TrustlessFileServerDeals::assign_committee(RuntimeOrigin::signed(CLIENT), merkle_root),
+            Error::<Test>::CommitteeAssigned
+        );
+
+        assert_ok!(TrustlessFileServerDeals::propose_deal(
+            RuntimeOrigin::signed(CLIENT),
+            merkle_root,
+            DURATION,
+            PRICE
+        ));
+        let outsider = [PROVIDER, 3, 4]
+            .into_iter()
+            .find(|who| !members.contains(who))
+            .unwrap();
+        assert_noop!(
+            TrustlessFileServerDeals::accept_deal(RuntimeOrigin::signed(outsider), 0),
+            Error::<Test>::NotCommitteeMember
+        );
+        assert_ok!(TrustlessFileServerDeals::accept_deal(RuntimeOrigin::signed(members[0]), 0));
+
+        // the committee is drawn again once a member leaves
+        assert_ok!(TrustlessFileServerDeals::unregister_provider(RuntimeOrigin::signed(
+            members[1]
+        )));
+        assert_ok!(TrustlessFileServerDeals::assign_committee(
+            RuntimeOrigin::signed(CLIENT),
+            merkle_root
+        ));
+        let members = Committees::<Test>::get(merkle_root).unwrap();
+        assert_eq!(members.len(), 2);
+        assert!(members
+            .iter()
+            .all(|who| Providers::<Test>::contains_key(who)));
+    });
+}
+
+/// Registers the key `who` attests availability with, ten times its account.
+fn set_attestation_key(who: u64) {
+    let key = who * 10;
+    let proof = (vec![key], (ATTESTATION_KEY_CONTEXT, who).encode());
+    assert_ok!(TrustlessFileServerDeals::set_attestation_key(
+        RuntimeOrigin::signed(who),
+        key,
+        proof
+    ));
+}
+
+#[test]
+fn it_should_only_register_attestation_keys_of_providers_holding_them() {
+    new_test_ext().execute_with(|| {
+        let proof = (vec![20], (ATTESTATION_KEY_CONTEXT, PROVIDER).encode());
+        assert_noop!(
+            TrustlessFileServerDeals::set_attestation_key(
+                RuntimeOrigin::signed(PROVIDER),
+                20,
+                proof.clone()
+            ),
+            Error::<Test>::NotProvider
+        );
+        assert_ok!(TrustlessFileServerDeals::register_provider(RuntimeOrigin::signed(PROVIDER)));
+        // the proof of possession is bound to the account registering the key
+        assert_noop!(
+            TrustlessFileServerDeals::set_attestation_key(
+                RuntimeOrigin::signed(PROVIDER),
+                20,
+                (vec![20], (ATTESTATION_KEY_CONTEXT, 3u64).encode())
+            ),
+            Error::<Test>::BadSignature
+        );
+
+        assert_ok!(TrustlessFileServerDeals::set_attestation_key(
+            RuntimeOrigin::signed(PROVIDER),
+            20,
+            proof
+        ));
+        assert_eq!(AttestationKeys::<Test>::get(PROVIDER), Some(20));
+        System::assert_has_event(Event::AttestationKeySet { who: PROVIDER }.into());
+    });
+}
+
+#[test]
+fn it_should_record_availability_attested_by_the_committee() {
+    new_test_ext().execute_with(|| {
+        let merkle_root = upload_test_file();
+        let statement = AvailabilityStatement {
+            merkle_root,
+            position: 3,
+            block: 1,
+        };
+        assert_noop!(
+            TrustlessFileServerDeals::attest_availability(
+                RuntimeOrigin::signed(CLIENT),
+                statement.clone(),
+                BoundedVec::truncate_from(vec![true, true]),
+                (vec![], statement.encode())
+            ),
+            Error::<Test>::NoCommittee
+        );
+        for who in [PROVIDER, 3, 4] {
+            assert_ok!(TrustlessFileServerDeals::register_provider(RuntimeOrigin::signed(who)));
+        }
+        assert_ok!(TrustlessFileServerDeals::assign_committee(
+            RuntimeOrigin::signed(CLIENT),
+            merkle_root
+        ));
+        let members = Committees::<Test>::get(merkle_root).unwrap();
+        let keys = members.iter().map(|who| who * 10).collect::<Vec<_>>();
+        let attest = |statement: &AvailabilityStatement<H256, u64>, signers: Vec<bool>| {
+            let signed = keys
+                .iter()
+                .zip(signers.iter())
+                .filter(|(_, signed)| **signed)
+                .map(|(key, _)| *key)
+                .collect();
+            TrustlessFileServerDeals::attest_availability(
+                RuntimeOrigin::signed(CLIENT),
+                statement.clone(),
+                BoundedVec::truncate_from(signers),
+                (signed, statement.encode()),
+            )
+        };
+
+        set_attestation_key(members[0]);
+        assert_noop!(attest(&statement, vec![true, true]), Error::<Test>::NoAttestationKey);
+        set_attestation_key(members[1]);
+        // a single member is not a majority of a committee of two
+        assert_noop!(attest(&statement, vec![true, false]), Error::<Test>::NotEnoughAttesters);
+        assert_noop!(
+            TrustlessFileServerDeals::attest_availability(
+                RuntimeOrigin::signed(CLIENT),
+                statement.clone(),
+                BoundedVec::truncate_from(vec![true, true]),
+                (keys.clone(), b"we hold nothing".to_vec())
+            ),
+            Error::<Test>::BadSignature
+        );
+        assert_noop!(
+            attest(
+                &AvailabilityStatement {
+                    position: 12,
+                    ..statement.clone()
+                },
+                vec![true, true]
+            ),
+            Error::<Test>::InvalidPosition
+        );
+        assert_noop!(
+            attest(
+                &AvailabilityStatement {
+                    block: 2,
+                    ..statement.clone()
+                },
+                vec![true, true]
+            ),
+            Error::<Test>::StaleAttestation
+        );
+
+        assert_ok!(attest(&statement, vec![true, true]));
+        assert_eq!(
+            Availability::<Test>::get(merkle_root),
+            Some(AvailabilityAttestation {
+                position: 3,
+                block: 1,
+                attesters: 2,
+            })
+        );
+        System::assert_has_event(
+            Event::AvailabilityAttested {
+                merkle_root,
+                position: 3,
+                block: 1,
+                attesters: 2,
+            }
+            .into(),
+        );
+        // statements are only accepted once, and while recent
+        assert_noop!(attest(&statement, vec![true, true]), Error::<Test>::StaleAttestation);
+        System::set_block_number(11);
+        assert_noop!(
+            attest(
+                &AvailabilityStatement {
+                    block: 1,
+                    ..statement.clone()
+                },
+                vec![true, true]
+            ),
+            Error::<Test>::StaleAttestation
+        );
+        assert_ok!(attest(
+            &AvailabilityStatement {
+                block: 11,
+                ..statement
+            },
+            vec![true, true]
+        ));
+    });
+}
+
+#[test]
+fn it_should_release_payments_for_answered_challenges() {
+    new_test_ext().execute_with(|| {
+        let (_, deal_id) = start_deal();
+        let provider_free = Balances::free_balance(PROVIDER);
+        assert_noop!(
+            TrustlessFileServerDeals::respond_challenge(
+                RuntimeOrigin::signed(PROVIDER),
+                deal_id,
+                vec![0u8; 1024]
+            ),
+            Error::<Test>::InvalidChunk
+        );
+        assert_noop!(
+            TrustlessFileServerDeals::respond_challenge(
+                RuntimeOrigin::signed(3),
+                deal_id,
+                challenged_chunk(deal_id)
+            ),
+            Error::<Test>::NotDealProvider
+        );
+
+        // a tenth of the duration, paid once settled
+        System::set_block_number(11);
+        assert_ok!(TrustlessFileServerDeals::respond_challenge(
+            RuntimeOrigin::signed(PROVIDER),
+            deal_id,
+            challenged_chunk(deal_id)
+        ));
+        assert_eq!(Balances::free_balance(PROVIDER), provider_free);
+        assert_ok!(TrustlessFileServerDeals::settle_deal(RuntimeOrigin::signed(3), deal_id));
+        assert_eq!(Balances::free_balance(PROVIDER), provider_free + 100);
+        assert_eq!(escrow_balance(), PRICE - 100);
+
+        // keep answering until the deal is over
+        for block in (21..=101).step_by(10) {
+            System::set_block_number(block);
+            assert_ok!(TrustlessFileServerDeals::respond_challenge(
+                RuntimeOrigin::signed(PROVIDER),
+                deal_id,
+                challenged_chunk(deal_id)
+            ));
+        }
+        System::assert_last_event(Event::DealCompleted { deal_id }.into());
+        assert_eq!(Balances::free_balance(PROVIDER), provider_free + PRICE + 500);
+        assert_eq!(Balances::reserved_balance(PROVIDER), 50);
+        assert_eq!(escrow_balance(), 0);
+        assert!(!Deals::<Test>::contains_key(deal_id));
+        assert_eq!(Providers::<Test>::get(PROVIDER).unwrap().active_deals, 0);
+    });
+}
+
+/// Proof of holding the chunk `deal_id` is currently challenged on, as accepted by the mock.
+fn chunk_proof(deal_id: u64) -> BoundedVec<u8, ConstU32<256>> {
+    let Some(Deal {
+        merkle_root,
+        state: DealState::Active { challenge, .. },
+        ..
+    }) = Deals::<Test>::get(deal_id)
+    else {
+        panic!("the deal is not active");
+    };
+    let nonce = TrustlessFileServerDeals::challenge_nonce(deal_id, &challenge);
+    BoundedVec::truncate_from((merkle_root.as_bytes(), challenge.position, nonce).encode())
+}
+
+#[test]
+fn it_should_release_payments_for_challenges_answered_privately() {
+    new_test_ext().execute_with(|| {
+        let (merkle_root, deal_id) = start_deal();
+        let provider_free = Balances::free_balance(PROVIDER);
+        let proof = chunk_proof(deal_id);
+        assert_noop!(
+            TrustlessFileServerDeals::respond_challenge_privately(
+                RuntimeOrigin::signed(PROVIDER),
+                deal_id,
+                BoundedVec::truncate_from((merkle_root.as_bytes(), 0u32, [0u8; 32]).encode())
+            ),
+            Error::<Test>::InvalidChunkProof
+        );
+
+        System::set_block_number(11);
+        assert_ok!(TrustlessFileServerDeals::respond_challenge_privately(
+            RuntimeOrigin::signed(PROVIDER),
+            deal_id,
+            proof.clone()
+        ));
+        assert_ok!(TrustlessFileServerDeals::settle_deal(RuntimeOrigin::signed(3), deal_id));
+        assert_eq!(Balances::free_balance(PROVIDER), provider_free + 100);
+        // proofs are bound to the challenge they answer
+        assert_noop!(
+            TrustlessFileServerDeals::respond_challenge_privately(
+                RuntimeOrigin::signed(PROVIDER),
+                deal_id,
+                proof
+            ),
+            Error::<Test>::InvalidChunkProof
+        );
+        assert_ok!(TrustlessFileServerDeals::respond_challenge_privately(
+            RuntimeOrigin::signed(PROVIDER),
+            deal_id,
+            chunk_proof(deal_id)
+        ));
+    });
+}
+
+#[test]
+fn it_should_release_payments_along_the_reward_curve() {
+    use sp_runtime::{curve::PiecewiseLinear, Perbill};
+
+    /// Holds back the whole price for the first half of the deal.
+    const BACK_LOADED_CURVE: PiecewiseLinear<'static> = PiecewiseLinear {
+        points: &[
+            (Perbill::from_parts(0), Perbill::from_parts(0)),
+            (Perbill::from_parts(500_000_000), Perbill::from_parts(0)),
+            (Perbill::from_parts(1_000_000_000), Perbill::from_parts(1_000_000_000)),
+        ],
+        maximum: Perbill::from_parts(1_000_000_000),
+    };
+
+    new_test_ext().execute_with(|| {
+        RewardCurve::set(&BACK_LOADED_CURVE);
+        let (_, deal_id) = start_deal();
+        let provider_free = Balances::free_balance(PROVIDER);
+
+        let respond = |block| {
+            System::set_block_number(block);
+            assert_ok!(TrustlessFileServerDeals::respond_challenge(
+                RuntimeOrigin::signed(PROVIDER),
+                deal_id,
+                challenged_chunk(deal_id)
+            ));
+        };
+        for block in (11..=51).step_by(10) {
+            respond(block);
+        }
+        assert_eq!(Balances::free_balance(PROVIDER), provider_free);
+        assert_eq!(escrow_balance(), PRICE);
+
+        // 60% of the duration, a fifth of the way from the middle to the end
+        respond(61);
+        assert_ok!(TrustlessFileServerDeals::settle_deal(RuntimeOrigin::signed(3), deal_id));
+        assert_eq!(Balances::free_balance(PROVIDER), provider_free + 200);
+
+        for block in (71..=101).step_by(10) {
+            respond(block);
+        }
+        System::assert_last_event(Event::DealCompleted { deal_id }.into());
+        assert_eq!(Balances::free_balance(PROVIDER), provider_free + PRICE + 500);
+        assert_eq!(escrow_balance(), 0);
+    });
+}
+
+#[test]
+fn it_should_settle_deals_every_settlement_period() {
+    new_test_ext().execute_with(|| {
+        let (_, deal_id) = start_deal();
+        let provider_free = Balances::free_balance(PROVIDER);
+        let settle = || TrustlessFileServerDeals::settle_deal(RuntimeOrigin::signed(3), deal_id);
+
+        System::set_block_number(6);
+        assert_ok!(TrustlessFileServerDeals::respond_challenge(
+            RuntimeOrigin::signed(PROVIDER),
+            deal_id,
+            challenged_chunk(deal_id)
+        ));
+        assert_noop!(settle(), Error::<Test>::SettlementNotDue);
+
+        // only the blocks proven by the answered challenges are paid
+        System::set_block_number(14);
+        assert_ok!(settle());
+        System::assert_last_event(
+            Event::DealSettled {
+                deal_id,
+                provider: PROVIDER,
+                payment: 50,
+            }
+            .into(),
+        );
+        assert_eq!(Balances::free_balance(PROVIDER), provider_free + 50);
+        assert_noop!(settle(), Error::<Test>::SettlementNotDue);
+
+        System::set_block_number(16);
+        assert_ok!(TrustlessFileServerDeals::respond_challenge(
+            RuntimeOrigin::signed(PROVIDER),
+            deal_id,
+            challenged_chunk(deal_id)
+        ));
+        System::set_block_number(26);
+        assert_ok!(settle());
+        assert_eq!(Balances::free_balance(PROVIDER), provider_free + 150);
+        assert_eq!(escrow_balance(), PRICE - 150);
+
+        // nothing is paid once a challenge is missed
+        System::set_block_number(37);
+        assert_noop!(settle(), Error::<Test>::ProviderInDefault);
+        assert_noop!(
+            TrustlessFileServerDeals::settle_deal(RuntimeOrigin::signed(3), deal_id + 1),
+            Error::<Test>::DealNotFound
+        );
+    });
+}
+
+#[test]
+fn it_should_let_clients_claim_the_collateral_of_defaulting_providers() {
+    new_test_ext().execute_with(|| {
+        let (_, deal_id) = start_deal();
+        let client_free = Balances::free_balance(CLIENT);
+        let provider_free = Balances::free_balance(PROVIDER);
+        System::set_block_number(6);
+        assert_ok!(TrustlessFileServerDeals::respond_challenge(
+            RuntimeOrigin::signed(PROVIDER),
+            deal_id,
+            challenged_chunk(deal_id)
+        ));
+        assert_noop!(
+            TrustlessFileServerDeals::claim_deal_collateral(RuntimeOrigin::signed(CLIENT), deal_id),
+            Error::<Test>::ProviderNotInDefault
+        );
+
+        System::set_block_number(17);
+        assert_noop!(
+            TrustlessFileServerDeals::claim_deal_collateral(
+                RuntimeOrigin::signed(PROVIDER),
+                deal_id
+            ),
+            Error::<Test>::NotDealClient
+        );
+        assert_ok!(TrustlessFileServerDeals::claim_deal_collateral(
+            RuntimeOrigin::signed(CLIENT),
+            deal_id
+        ));
+        System::assert_has_event(
+            Event::CollateralForfeited {
+                deal_id,
+                provider: PROVIDER,
+                amount: 500,
+            }
+            .into(),
+        );
+        // the provider keeps what the answered challenge proved
+        assert_eq!(Balances::free_balance(PROVIDER), provider_free + 50);
+        assert_eq!(Balances::free_balance(CLIENT), client_free + PRICE - 50 + 500);
+        assert_eq!(escrow_balance(), 0);
+        assert!(!Deals::<Test>::contains_key(deal_id));
+    });
+}
+
+#[test]
+fn it_should_forfeit_the_collateral_of_providers_missing_challenges() {
+    new_test_ext().execute_with(|| {
+        let (_, deal_id) = start_deal();
+        let client_free = Balances::free_balance(CLIENT);
+        System::set_block_number(6);
+        assert_ok!(TrustlessFileServerDeals::respond_challenge(
+            RuntimeOrigin::signed(PROVIDER),
+            deal_id,
+            challenged_chunk(deal_id)
+        ));
+        assert_noop!(
+            TrustlessFileServerDeals::terminate_deal(RuntimeOrigin::signed(3), deal_id),
+            Error::<Test>::NotDealParty
+        );
+
+        System::set_block_number(17);
+        assert_noop!(
+            TrustlessFileServerDeals::respond_challenge(
+                RuntimeOrigin::signed(PROVIDER),
+                deal_id,
+                challenged_chunk(deal_id)
+            ),
+            Error::<Test>::ChallengeExpired
+        );
+        // anyone can report the failure
+        assert_ok!(TrustlessFileServerDeals::terminate_deal(RuntimeOrigin::signed(3), deal_id));
+        System::assert_has_event(
+            Event::CollateralForfeited {
+                deal_id,
+                provider: PROVIDER,
+                amount: 500,
+            }
+            .into(),
+        );
+        System::assert_last_event(
+            Event::DealTerminated {
+                deal_id,
+                who: 3,
+                refund: PRICE - 50,
+            }
+            .into(),
+        );
+        assert_eq!(Balances::free_balance(CLIENT), client_free + PRICE - 50 + 500);
+        assert_eq!(Balances::reserved_balance(PROVIDER), 50);
+        assert_eq!(escrow_balance(), 0);
+        assert!(!Deals::<Test>::contains_key(deal_id));
+
+        // the provider's track record shows both the answered and the missed challenge
+        let reputation = TrustlessFileServer::get_reputation(PROVIDER);
+        assert_eq!(reputation.challenges_passed, 1);
+        assert_eq!(reputation.challenges_failed, 1);
+        assert_eq!(reputation.score(), 2 - 10);
+    });
+}
+
+#[test]
+fn it_should_let_clients_terminate_deals_early() {
+    new_test_ext().execute_with(|| {
+        let merkle_root = upload_test_file();
+        let client_free = Balances::free_balance(CLIENT);
+        assert_ok!(TrustlessFileServerDeals::propose_deal(
+            RuntimeOrigin::signed(CLIENT),
+            merkle_root,
+            DURATION,
+            PRICE
+        ));
+        assert_noop!(
+            TrustlessFileServerDeals::terminate_deal(RuntimeOrigin::signed(PROVIDER), 0),
+            Error::<Test>::NotDealParty
+        );
+        assert_ok!(TrustlessFileServerDeals::terminate_deal(RuntimeOrigin::signed(CLIENT), 0));
+        assert_eq!(Balances::free_balance(CLIENT), client_free);
+
+        // the provider is paid for the blocks served so far
+        assert_ok!(TrustlessFileServerDeals::register_provider(RuntimeOrigin::signed(PROVIDER)));
+        assert_ok!(TrustlessFileServerDeals::propose_deal(
+            RuntimeOrigin::signed(CLIENT),
+            merkle_root,
+            DURATION,
+            PRICE
+        ));
+        let deal_id = 1;
+        assert_ok!(TrustlessFileServerDeals::accept_deal(RuntimeOrigin::signed(PROVIDER), deal_id));
+        let provider_free = Balances::free_balance(PROVIDER);
+        System::set_block_number(31);
+        assert_ok!(TrustlessFileServerDeals::terminate_deal(
+            RuntimeOrigin::signed(CLIENT),
+            deal_id
+        ));
+        assert_eq!(Balances::free_balance(PROVIDER), provider_free + 300 + 500);
+        assert_eq!(Balances::free_balance(CLIENT), client_free - 300);
+        assert_eq!(escrow_balance(), 0);
+        System::assert_last_event(
+            Event::DealTerminated {
+                deal_id,
+                who: CLIENT,
+                refund: PRICE - 300,
+            }
+            .into(),
+        );
+    });
+}
+
+#[test]
+fn it_should_not_penalize_providers_of_deleted_files() {
+    new_test_ext().execute_with(|| {
+        let (merkle_root, deal_id) = start_deal();
+        assert_ok!(TrustlessFileServer::delete_file(RuntimeOrigin::signed(CLIENT), merkle_root));
+        System::set_block_number(21);
+        assert_noop!(
+            TrustlessFileServerDeals::respond_challenge(
+                RuntimeOrigin::signed(PROVIDER),
+                deal_id,
+                vec![0u8; 1024]
+            ),
+            Error::<Test>::ChallengeExpired
+        );
+
+        let reserved = Balances::reserved_balance(PROVIDER);
+        assert_ok!(TrustlessFileServerDeals::terminate_deal(
+            RuntimeOrigin::signed(PROVIDER),
+            deal_id
+        ));
+        assert_eq!(Balances::reserved_balance(PROVIDER), reserved - 500);
+        assert_eq!(
+            Balances::total_balance(&PROVIDER),
+            INITIAL_BALANCE + 200,
+            "paid for the blocks served before the file was deleted"
+        );
+    });
+}
+
+fn open_channel(deposit: u64) -> u64 {
+    assert_ok!(TrustlessFileServerDeals::open_channel(
+        RuntimeOrigin::signed(CLIENT),
+        PROVIDER,
+        deposit,
+        CHUNK_PRICE
+    ));
+    NextChannelId::<Test>::get() - 1
+}
+
+/// Vouchers for the chunks of a file served through `channel_id`, signed by `signer`.
+fn vouchers(
+    channel_id: u64,
+    sequences: std::ops::Range<u64>,
+    signer: u64,
+) -> BoundedVec<(Voucher<H256, u64>, TestSignature), ConstU32<8>> {
+    let vouchers: Vec<_> = sequences
+        .map(|sequence| {
+            let voucher = Voucher {
+                channel_id,
+                sequence,
+                merkle_root: H256::repeat_byte(1),
+                position: sequence,
+                price: CHUNK_PRICE,
+            };
+            let signature = TestSignature(signer, voucher.encode());
+            (voucher, signature)
+        })
+        .collect();
+    BoundedVec::try_from(vouchers).unwrap()
+}
+
+#[test]
+fn it_should_pay_servers_for_signed_vouchers() {
+    new_test_ext().execute_with(|| {
+        let channel_id = open_channel(50);
+        System::assert_last_event(
+            Event::ChannelOpened {
+                channel_id,
+                sender: CLIENT,
+                server: PROVIDER,
+                deposit: 50,
+                price_per_chunk: CHUNK_PRICE,
+            }
+            .into(),
+        );
+        assert_eq!(escrow_balance(), 50);
+        let redeem = |who, vouchers| {
+            TrustlessFileServerDeals::redeem_vouchers(
+                RuntimeOrigin::signed(who),
+                channel_id,
+                vouchers,
+            )
+        };
+
+        assert_noop!(
+            redeem(3, vouchers(channel_id, 0..2, CLIENT)),
+            Error::<Test>::NotChannelServer
+        );
+        assert_noop!(redeem(PROVIDER, vouchers(channel_id, 0..2, 3)), Error::<Test>::BadSignature);
+        assert_noop!(redeem(PROVIDER, vouchers(1, 0..2, CLIENT)), Error::<Test>::InvalidVoucher);
+        assert_ok!(redeem(PROVIDER, vouchers(channel_id, 0..3, CLIENT)));
+        System::assert_last_event(
+            Event::VouchersRedeemed {
+                channel_id,
+                count: 3,
+                amount: 30,
+            }
+            .into(),
+        );
+        assert_eq!(Balances::free_balance(PROVIDER), INITIAL_BALANCE + 30);
+
+        // vouchers are only redeemed once
+        assert_noop!(
+            redeem(PROVIDER, vouchers(channel_id, 2..4, CLIENT)),
+            Error::<Test>::InvalidVoucher
+        );
+        // and never beyond the deposit
+        assert_noop!(
+            redeem(PROVIDER, vouchers(channel_id, 3..6, CLIENT)),
+            Error::<Test>::ChannelExhausted
+        );
+        assert_ok!(redeem(PROVIDER, vouchers(channel_id, 4..6, CLIENT)));
+        assert_eq!(Channels::<Test>::get(channel_id).unwrap().redeemed, 50);
+        assert_eq!(escrow_balance(), 0);
+    });
+}
+
+#[test]
+fn it_should_charge_the_retrieval_price_set_by_file_owners() {
+    new_test_ext().execute_with(|| {
+        let merkle_root = upload_test_file();
+        assert_ok!(TrustlessFileServer::set_retrieval_price(
+            RuntimeOrigin::signed(CLIENT),
+            merkle_root,
+            Some(15)
+        ));
+        let channel_id = open_channel(100);
+        let voucher = |sequence, merkle_root| {
+            let voucher = Voucher {
+                channel_id,
+                sequence,
+                merkle_root,
+                position: 0,
+                price: 15,
+            };
+            let signature = TestSignature(CLIENT, voucher.encode());
+            (voucher, signature)
+        };
+        // chunks of other files still cost the price of the channel
+        let vouchers =
+            BoundedVec::try_from(vec![voucher(0, merkle_root), voucher(1, H256::repeat_byte(1))])
+                .unwrap();
+
+        assert_ok!(TrustlessFileServerDeals::redeem_vouchers(
+            RuntimeOrigin::signed(PROVIDER),
+            channel_id,
+            vouchers
+        ));
+        System::assert_last_event(
+            Event::VouchersRedeemed {
+                channel_id,
+                count: 2,
+                amount: 15 + CHUNK_PRICE,
+            }
+            .into(),
+        );
+    });
+}
+
+#[test]
+fn it_should_not_charge_more_than_the_price_of_vouchers() {
+    new_test_ext().execute_with(|| {
+        let merkle_root = upload_test_file();
+        let set_price = |price| {
+            TrustlessFileServer::set_retrieval_price(
+                RuntimeOrigin::signed(CLIENT),
+                merkle_root,
+                Some(price),
+            )
+        };
+        assert_ok!(set_price(15));
+        let channel_id = open_channel(100);
+        let voucher = Voucher {
+            channel_id,
+            sequence: 0,
+            merkle_root,
+            position: 0,
+            price: 15,
+        };
+        let signature = TestSignature(CLIENT, voucher.encode());
+
+        // the price is raised between signing the voucher and redeeming it
+        assert_ok!(set_price(50));
+        assert_ok!(TrustlessFileServerDeals::redeem_vouchers(
+            RuntimeOrigin::signed(PROVIDER),
+            channel_id,
+            BoundedVec::try_from(vec![(voucher, signature)]).unwrap()
+        ));
+        System::assert_last_event(
+            Event::VouchersRedeemed {
+                channel_id,
+                count: 1,
+                amount: 15,
+            }
+            .into(),
+        );
+        assert_eq!(Channels::<Test>::get(channel_id).unwrap().redeemed, 15);
+    });
+}
+
+#[test]
+fn it_should_let_servers_redeem_vouchers_until_channels_close() {
+    new_test_ext().execute_with(|| {
+        let channel_id = open_channel(100);
+        let close =
+            |who| TrustlessFileServerDeals::close_channel(RuntimeOrigin::signed(who), channel_id);
+        assert_noop!(close(3), Error::<Test>::NotChannelParty);
+        assert_ok!(close(CLIENT));
+        System::assert_last_event(
+            Event::ChannelClosing {
+                channel_id,
+                closes_at: 11,
+            }
+            .into(),
+        );
+        assert_noop!(close(CLIENT), Error::<Test>::ClosePeriodNotOver);
+
+        assert_ok!(TrustlessFileServerDeals::redeem_vouchers(
+            RuntimeOrigin::signed(PROVIDER),
+            channel_id,
+            vouchers(channel_id, 0..2, CLIENT)
+        ));
+        System::set_block_number(11);
+        assert_ok!(close(CLIENT));
+        System::assert_last_event(
+            Event::ChannelClosed {
+                channel_id,
+                refund: 80,
+            }
+            .into(),
+        );
+        assert_eq!(Balances::free_balance(CLIENT), INITIAL_BALANCE - 20);
+        assert_eq!(Channels::<Test>::get(channel_id), None);
+        assert_eq!(escrow_balance(), 0);
+
+        // servers can settle right away
+        let channel_id = open_channel(100);
+        assert_ok!(TrustlessFileServerDeals::close_channel(
+            RuntimeOrigin::signed(PROVIDER),
+            channel_id
+        ));
+        assert_eq!(Balances::free_balance(CLIENT), INITIAL_BALANCE - 20);
+    });
+}
+
+#[test]
+fn it_should_migrate_to_64_bit_positions() {
+    use crate::migrations::v1;
+    use frame_support::storage::unhashed::put_raw;
+    use frame_support::traits::{GetStorageVersion, OnRuntimeUpgrade, StorageVersion};
+
+    new_test_ext().execute_with(|| {
+        let (merkle_root, deal_id) = start_deal();
+        let deal = Deals::<Test>::get(deal_id).unwrap();
+        let DealState::Active {
+            provider,
+            start,
+            collateral,
+            paid_until,
+            proven_until,
+            challenge,
+        } = deal.state.clone()
+        else {
+            panic!("the deal is not active");
+        };
+        let old_deal = v1::OldDeal {
+            client: deal.client,
+            merkle_root: deal.merkle_root,
+            duration: deal.duration,
+            price: deal.price,
+            state: v1::OldDealState::Active {
+                provider,
+                start,
+                collateral,
+                paid_until,
+                proven_until,
+                challenge: v1::OldChallenge {
+                    position: challenge.position as u32,
+                    deadline: challenge.deadline,
+                },
+            },
+        };
+        put_raw(&Deals::<Test>::hashed_key_for(deal_id), &old_deal.encode());
+        let old_attestation = v1::OldAvailabilityAttestation {
+            position: 3,
+            block: 1u64,
+            attesters: 2,
+        };
+        put_raw(&Availability::<Test>::hashed_key_for(merkle_root), &old_attestation.encode());
+        StorageVersion::new(0).put::<TrustlessFileServerDeals>();
+
+        v1::MigrateToV1::<Test>::on_runtime_upgrade();
+
+        assert_eq!(TrustlessFileServerDeals::on_chain_storage_version(), 1);
+        assert_eq!(Deals::<Test>::get(deal_id), Some(deal));
+        assert_eq!(
+            Availability::<Test>::get(merkle_root),
+            Some(AvailabilityAttestation {
+                position: 3,
+                block: 1,
+                attesters: 2,
+            })
+        );
+    });
+}
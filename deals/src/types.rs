@@ -0,0 +1,124 @@
+use codec::{Decode, Encode, MaxEncodedLen};
+use frame_support::RuntimeDebug;
+use scale_info::TypeInfo;
+
+/// Identifier of a storage deal.
+pub type DealId = u64;
+
+/// An account registered to take storage deals.
+#[derive(Clone, Encode, Decode, Eq, PartialEq, RuntimeDebug, TypeInfo, MaxEncodedLen)]
+pub struct Provider<Balance> {
+    /// Bond reserved when registering, given back [`UnbondingPeriod`] blocks after
+    /// unregistering.
+    ///
+    /// [`UnbondingPeriod`]: crate::Config::UnbondingPeriod
+    pub bond: Balance,
+    /// Number of deals the provider is currently serving.
+    pub active_deals: u32,
+}
+
+/// Bond of a provider that has left, kept reserved and slashable until `unlocked_at`.
+#[derive(Clone, Encode, Decode, Eq, PartialEq, RuntimeDebug, TypeInfo, MaxEncodedLen)]
+pub struct UnbondingBond<Balance, BlockNumber> {
+    pub amount: Balance,
+    /// First block in which the bond can be withdrawn.
+    pub unlocked_at: BlockNumber,
+}
+
+/// Chunk of a file a provider has to submit to keep being paid.
+#[derive(Clone, Encode, Decode, Eq, PartialEq, RuntimeDebug, TypeInfo, MaxEncodedLen)]
+pub struct Challenge<BlockNumber> {
+    /// Position of the chunk in the file.
+    pub position: u64,
+    /// Last block in which the chunk can be submitted.
+    pub deadline: BlockNumber,
+}
+
+/// Lifecycle of a storage deal.
+#[derive(Clone, Encode, Decode, Eq, PartialEq, RuntimeDebug, TypeInfo, MaxEncodedLen)]
+pub enum DealState<AccountId, Balance, BlockNumber> {
+    /// Waiting for a provider to accept it.
+    Proposed,
+    /// Served by `provider` since `start`.
+    Active {
+        provider: AccountId,
+        start: BlockNumber,
+        /// Collateral reserved from the provider, forfeited to the client on failure.
+        collateral: Balance,
+        /// Block up to which the provider has already been paid.
+        paid_until: BlockNumber,
+        /// Block up to which the provider has shown to hold the file by answering challenges,
+        /// paid once settled.
+        proven_until: BlockNumber,
+        /// Challenge the provider has to answer next.
+        challenge: Challenge<BlockNumber>,
+    },
+}
+
+/// Offer of `price` for keeping a registered file available for `duration` blocks.
+#[derive(Clone, Encode, Decode, Eq, PartialEq, RuntimeDebug, TypeInfo, MaxEncodedLen)]
+pub struct Deal<AccountId, Hash, Balance, BlockNumber> {
+    /// Account that proposed the deal and escrowed its price.
+    pub client: AccountId,
+    /// Merkle root of the file to keep available.
+    pub merkle_root: Hash,
+    /// Number of blocks the file has to be served for, once accepted.
+    pub duration: BlockNumber,
+    /// Total amount paid to the provider over the duration of the deal.
+    pub price: Balance,
+    pub state: DealState<AccountId, Balance, BlockNumber>,
+}
+
+/// Identifier of a retrieval payment channel.
+pub type ChannelId = u64;
+
+/// Retrieval payment channel: `sender` escrows `deposit` to pay `server` `price_per_chunk` for
+/// every chunk it serves, as authorized by the vouchers the sender signs along the way. Chunks of
+/// files whose owner has set a retrieval price cost that price instead.
+#[derive(Clone, Encode, Decode, Eq, PartialEq, RuntimeDebug, TypeInfo, MaxEncodedLen)]
+pub struct Channel<AccountId, Balance, BlockNumber> {
+    pub sender: AccountId,
+    pub server: AccountId,
+    pub deposit: Balance,
+    pub price_per_chunk: Balance,
+    /// Part of the deposit already paid to the server.
+    pub redeemed: Balance,
+    /// Lowest sequence number the next redeemed voucher can have.
+    pub next_sequence: u64,
+    /// Block from which the sender can settle the channel, once it has asked to close it.
+    pub closes_at: Option<BlockNumber>,
+}
+
+/// Authorization signed by the sender of a channel to pay for the chunk at `position` of the
+/// file under `merkle_root`. Its SCALE encoding is the signed message.
+#[derive(Clone, Encode, Decode, Eq, PartialEq, RuntimeDebug, TypeInfo, MaxEncodedLen)]
+pub struct Voucher<Hash, Balance> {
+    pub channel_id: ChannelId,
+    /// Strictly increasing across the vouchers of a channel, so that each is redeemed only once.
+    pub sequence: u64,
+    pub merkle_root: Hash,
+    pub position: u64,
+    /// Most the sender agreed to pay for the chunk, so that raising the price of a file does
+    /// not make vouchers already signed worth more.
+    pub price: Balance,
+}
+
+/// Statement by the members of the committee of the file under `merkle_root` that they held the
+/// chunk at `position` at `block`. Its SCALE encoding is the message they all sign, so that their
+/// signatures can be aggregated into a single one.
+#[derive(Clone, Encode, Decode, Eq, PartialEq, RuntimeDebug, TypeInfo, MaxEncodedLen)]
+pub struct AvailabilityStatement<Hash, BlockNumber> {
+    pub merkle_root: Hash,
+    pub position: u64,
+    pub block: BlockNumber,
+}
+
+/// Latest chunk of a file the members of its committee attested to hold.
+#[derive(Clone, Encode, Decode, Eq, PartialEq, RuntimeDebug, TypeInfo, MaxEncodedLen)]
+pub struct AvailabilityAttestation<BlockNumber> {
+    pub position: u64,
+    /// Block the attested statement refers to.
+    pub block: BlockNumber,
+    /// Number of committee members whose signatures were aggregated.
+    pub attesters: u32,
+}
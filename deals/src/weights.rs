@@ -0,0 +1,634 @@
+//! Autogenerated weights for pallet_trustless_file_server_deals
+//!
+//! THIS FILE WAS AUTO-GENERATED USING THE SUBSTRATE BENCHMARK CLI VERSION 4.0.0-dev
+//! DATE: 2023-06-14, STEPS: `50`, REPEAT: `100`, LOW RANGE: `[]`, HIGH RANGE: `[]`
+//! WORST CASE MAP SIZE: `1000000`
+//! HOSTNAME: `Joses-MacBook-Pro.local`, CPU: `<UNKNOWN>`
+//! EXECUTION: Some(Wasm), WASM-EXECUTION: Compiled, CHAIN: Some("dev"), DB CACHE: 1024
+
+// Executed Command:
+// ./target/production/node-template
+// benchmark
+// pallet
+// --chain
+// dev
+// --pallet
+// pallet_trustless_file_server_deals
+// --execution=wasm
+// --wasm-execution=compiled
+// --extrinsic
+// *
+// --steps
+// 50
+// --repeat
+// 100
+// --output
+// weights.rs
+// --template
+// ./.maintain/frame-weight-template.hbs
+// --heap-pages
+// 4096
+
+#![cfg_attr(rustfmt, rustfmt_skip)]
+#![allow(unused_parens)]
+#![allow(unused_imports)]
+#![allow(missing_docs)]
+
+use frame_support::{traits::Get, weights::{Weight, constants::RocksDbWeight}};
+use core::marker::PhantomData;
+
+/// Weight functions needed for pallet_trustless_file_server_deals.
+pub trait WeightInfo {
+	fn register_provider() -> Weight;
+	fn unregister_provider() -> Weight;
+	fn propose_deal() -> Weight;
+	fn accept_deal() -> Weight;
+	fn respond_challenge(x: u32, ) -> Weight;
+	fn terminate_deal() -> Weight;
+	fn open_channel() -> Weight;
+	fn redeem_vouchers(n: u32, ) -> Weight;
+	fn close_channel() -> Weight;
+	fn withdraw_unbonded() -> Weight;
+	fn slash_provider() -> Weight;
+	fn assign_committee(k: u32, ) -> Weight;
+	fn set_attestation_key() -> Weight;
+	fn attest_availability() -> Weight;
+	fn respond_challenge_privately() -> Weight;
+	fn settle_deal() -> Weight;
+	fn claim_deal_collateral() -> Weight;
+}
+
+/// Weights for pallet_trustless_file_server_deals using the Substrate node and recommended hardware.
+pub struct SubstrateWeight<T>(PhantomData<T>);
+impl<T: frame_system::Config> WeightInfo for SubstrateWeight<T> {
+	/// Storage: TrustlessFileServerDeals Providers (r:1 w:1)
+	/// Proof: TrustlessFileServerDeals Providers (max_values: None, max_size: Some(68), added: 2543, mode: MaxEncodedLen)
+	/// Storage: System Account (r:1 w:1)
+	/// Proof: System Account (max_values: None, max_size: Some(128), added: 2603, mode: MaxEncodedLen)
+	fn register_provider() -> Weight {
+		// Proof Size summary in bytes:
+		//  Measured:  `6`
+		//  Estimated: `3593`
+		// Minimum execution time: 18_000_000 picoseconds.
+		Weight::from_parts(19_000_000, 3593)
+			.saturating_add(T::DbWeight::get().reads(2_u64))
+			.saturating_add(T::DbWeight::get().writes(2_u64))
+	}
+	/// Storage: TrustlessFileServerDeals Providers (r:1 w:1)
+	/// Proof: TrustlessFileServerDeals Providers (max_values: None, max_size: Some(68), added: 2543, mode: MaxEncodedLen)
+	/// Storage: TrustlessFileServerDeals Unbonding (r:1 w:1)
+	/// Proof: TrustlessFileServerDeals Unbonding (max_values: None, max_size: Some(68), added: 2543, mode: MaxEncodedLen)
+	fn unregister_provider() -> Weight {
+		// Proof Size summary in bytes:
+		//  Measured:  `151`
+		//  Estimated: `3593`
+		// Minimum execution time: 19_000_000 picoseconds.
+		Weight::from_parts(20_000_000, 3593)
+			.saturating_add(T::DbWeight::get().reads(2_u64))
+			.saturating_add(T::DbWeight::get().writes(2_u64))
+	}
+	/// Storage: TrustlessFileServer FileOwners (r:1 w:0)
+	/// Proof: TrustlessFileServer FileOwners (max_values: None, max_size: Some(80), added: 2555, mode: MaxEncodedLen)
+	/// Storage: System Account (r:2 w:2)
+	/// Proof: System Account (max_values: None, max_size: Some(128), added: 2603, mode: MaxEncodedLen)
+	/// Storage: TrustlessFileServerDeals NextDealId (r:1 w:1)
+	/// Proof: TrustlessFileServerDeals NextDealId (max_values: Some(1), max_size: Some(8), added: 503, mode: MaxEncodedLen)
+	/// Storage: TrustlessFileServerDeals Deals (r:0 w:1)
+	/// Proof: TrustlessFileServerDeals Deals (max_values: None, max_size: Some(169), added: 2644, mode: MaxEncodedLen)
+	fn propose_deal() -> Weight {
+		// Proof Size summary in bytes:
+		//  Measured:  `2314`
+		//  Estimated: `6196`
+		// Minimum execution time: 34_000_000 picoseconds.
+		Weight::from_parts(35_000_000, 6196)
+			.saturating_add(T::DbWeight::get().reads(4_u64))
+			.saturating_add(T::DbWeight::get().writes(4_u64))
+	}
+	/// Storage: TrustlessFileServerDeals Providers (r:1 w:1)
+	/// Proof: TrustlessFileServerDeals Providers (max_values: None, max_size: Some(68), added: 2543, mode: MaxEncodedLen)
+	/// Storage: TrustlessFileServerDeals Deals (r:1 w:1)
+	/// Proof: TrustlessFileServerDeals Deals (max_values: None, max_size: Some(169), added: 2644, mode: MaxEncodedLen)
+	/// Storage: TrustlessFileServer FileMeta (r:1 w:0)
+	/// Proof: TrustlessFileServer FileMeta (max_values: None, max_size: Some(65), added: 2540, mode: MaxEncodedLen)
+	/// Storage: RandomnessCollectiveFlip RandomMaterial (r:1 w:0)
+	/// Proof: RandomnessCollectiveFlip RandomMaterial (max_values: Some(1), max_size: Some(2594), added: 3089, mode: MaxEncodedLen)
+	/// Storage: System Account (r:1 w:1)
+	/// Proof: System Account (max_values: None, max_size: Some(128), added: 2603, mode: MaxEncodedLen)
+	/// Storage: TrustlessFileServerDeals Committees (r:1 w:0)
+	/// Proof: TrustlessFileServerDeals Committees (max_values: None, max_size: Some(561), added: 3036, mode: MaxEncodedLen)
+	fn accept_deal() -> Weight {
+		// Proof Size summary in bytes:
+		//  Measured:  `2638`
+		//  Estimated: `5613`
+		// Minimum execution time: 31_000_000 picoseconds.
+		Weight::from_parts(32_000_000, 5613)
+			.saturating_add(T::DbWeight::get().reads(6_u64))
+			.saturating_add(T::DbWeight::get().writes(3_u64))
+	}
+	/// Storage: TrustlessFileServerDeals Deals (r:1 w:1)
+	/// Proof: TrustlessFileServerDeals Deals (max_values: None, max_size: Some(169), added: 2644, mode: MaxEncodedLen)
+	/// Storage: TrustlessFileServer FileTrees (r:1 w:0)
+	/// Proof: TrustlessFileServer FileTrees (max_values: None, max_size: Some(2116), added: 4591, mode: MaxEncodedLen)
+	/// Storage: TrustlessFileServer FileMeta (r:1 w:0)
+	/// Proof: TrustlessFileServer FileMeta (max_values: None, max_size: Some(65), added: 2540, mode: MaxEncodedLen)
+	/// Storage: RandomnessCollectiveFlip RandomMaterial (r:1 w:0)
+	/// Proof: RandomnessCollectiveFlip RandomMaterial (max_values: Some(1), max_size: Some(2594), added: 3089, mode: MaxEncodedLen)
+	/// Storage: System Account (r:2 w:2)
+	/// Proof: System Account (max_values: None, max_size: Some(128), added: 2603, mode: MaxEncodedLen)
+	/// Storage: TrustlessFileServerDeals Providers (r:1 w:1)
+	/// Proof: TrustlessFileServerDeals Providers (max_values: None, max_size: Some(68), added: 2543, mode: MaxEncodedLen)
+	/// The range of component `x` is `[1, 1024]`.
+	fn respond_challenge(x: u32, ) -> Weight {
+		// Proof Size summary in bytes:
+		//  Measured:  `2957`
+		//  Estimated: `6196`
+		// Minimum execution time: 41_000_000 picoseconds.
+		Weight::from_parts(41_856_301, 6196)
+			// Standard Error: 12
+			.saturating_add(Weight::from_parts(2_871, 0).saturating_mul(x.into()))
+			.saturating_add(T::DbWeight::get().reads(7_u64))
+			.saturating_add(T::DbWeight::get().writes(4_u64))
+	}
+	/// Storage: TrustlessFileServerDeals Deals (r:1 w:1)
+	/// Proof: TrustlessFileServerDeals Deals (max_values: None, max_size: Some(169), added: 2644, mode: MaxEncodedLen)
+	/// Storage: TrustlessFileServer FileOwners (r:1 w:0)
+	/// Proof: TrustlessFileServer FileOwners (max_values: None, max_size: Some(80), added: 2555, mode: MaxEncodedLen)
+	/// Storage: System Account (r:3 w:3)
+	/// Proof: System Account (max_values: None, max_size: Some(128), added: 2603, mode: MaxEncodedLen)
+	/// Storage: TrustlessFileServerDeals Providers (r:1 w:1)
+	/// Proof: TrustlessFileServerDeals Providers (max_values: None, max_size: Some(68), added: 2543, mode: MaxEncodedLen)
+	fn terminate_deal() -> Weight {
+		// Proof Size summary in bytes:
+		//  Measured:  `2957`
+		//  Estimated: `8799`
+		// Minimum execution time: 52_000_000 picoseconds.
+		Weight::from_parts(53_000_000, 8799)
+			.saturating_add(T::DbWeight::get().reads(6_u64))
+			.saturating_add(T::DbWeight::get().writes(5_u64))
+	}
+	/// Storage: System Account (r:2 w:2)
+	/// Proof: System Account (max_values: None, max_size: Some(128), added: 2603, mode: MaxEncodedLen)
+	/// Storage: TrustlessFileServerDeals NextChannelId (r:1 w:1)
+	/// Proof: TrustlessFileServerDeals NextChannelId (max_values: Some(1), max_size: Some(8), added: 503, mode: MaxEncodedLen)
+	/// Storage: TrustlessFileServerDeals Channels (r:0 w:1)
+	/// Proof: TrustlessFileServerDeals Channels (max_values: None, max_size: Some(141), added: 2616, mode: MaxEncodedLen)
+	fn open_channel() -> Weight {
+		// Proof Size summary in bytes:
+		//  Measured:  `140`
+		//  Estimated: `6196`
+		// Minimum execution time: 30_000_000 picoseconds.
+		Weight::from_parts(31_000_000, 6196)
+			.saturating_add(T::DbWeight::get().reads(3_u64))
+			.saturating_add(T::DbWeight::get().writes(4_u64))
+	}
+	/// Storage: TrustlessFileServerDeals Channels (r:1 w:1)
+	/// Proof: TrustlessFileServerDeals Channels (max_values: None, max_size: Some(141), added: 2616, mode: MaxEncodedLen)
+	/// Storage: System Account (r:2 w:2)
+	/// Proof: System Account (max_values: None, max_size: Some(128), added: 2603, mode: MaxEncodedLen)
+	/// Storage: TrustlessFileServer Metadata (r:8 w:0)
+	/// Proof: TrustlessFileServer Metadata (max_values: None, max_size: Some(761), added: 3236, mode: MaxEncodedLen)
+	/// The range of component `n` is `[1, 8]`.
+	fn redeem_vouchers(n: u32, ) -> Weight {
+		// Proof Size summary in bytes:
+		//  Measured:  `312`
+		//  Estimated: `6196`
+		// Minimum execution time: 74_000_000 picoseconds.
+		Weight::from_parts(29_417_000, 6196)
+			// Standard Error: 21_000
+			.saturating_add(Weight::from_parts(45_322_000, 0).saturating_mul(n.into()))
+			.saturating_add(T::DbWeight::get().reads(3_u64))
+			.saturating_add(T::DbWeight::get().writes(3_u64))
+			.saturating_add(T::DbWeight::get().reads((1_u64).saturating_mul(n.into())))
+	}
+	/// Storage: TrustlessFileServerDeals Channels (r:1 w:1)
+	/// Proof: TrustlessFileServerDeals Channels (max_values: None, max_size: Some(141), added: 2616, mode: MaxEncodedLen)
+	/// Storage: System Account (r:2 w:2)
+	/// Proof: System Account (max_values: None, max_size: Some(128), added: 2603, mode: MaxEncodedLen)
+	fn close_channel() -> Weight {
+		// Proof Size summary in bytes:
+		//  Measured:  `312`
+		//  Estimated: `6196`
+		// Minimum execution time: 32_000_000 picoseconds.
+		Weight::from_parts(33_000_000, 6196)
+			.saturating_add(T::DbWeight::get().reads(3_u64))
+			.saturating_add(T::DbWeight::get().writes(3_u64))
+	}
+	/// Storage: TrustlessFileServerDeals Unbonding (r:1 w:1)
+	/// Proof: TrustlessFileServerDeals Unbonding (max_values: None, max_size: Some(68), added: 2543, mode: MaxEncodedLen)
+	/// Storage: System Account (r:1 w:1)
+	/// Proof: System Account (max_values: None, max_size: Some(128), added: 2603, mode: MaxEncodedLen)
+	fn withdraw_unbonded() -> Weight {
+		// Proof Size summary in bytes:
+		//  Measured:  `151`
+		//  Estimated: `3593`
+		// Minimum execution time: 19_000_000 picoseconds.
+		Weight::from_parts(20_000_000, 3593)
+			.saturating_add(T::DbWeight::get().reads(2_u64))
+			.saturating_add(T::DbWeight::get().writes(2_u64))
+	}
+	/// Storage: TrustlessFileServerDeals Providers (r:1 w:1)
+	/// Proof: TrustlessFileServerDeals Providers (max_values: None, max_size: Some(68), added: 2543, mode: MaxEncodedLen)
+	/// Storage: TrustlessFileServerDeals Unbonding (r:1 w:1)
+	/// Proof: TrustlessFileServerDeals Unbonding (max_values: None, max_size: Some(68), added: 2543, mode: MaxEncodedLen)
+	/// Storage: System Account (r:1 w:1)
+	/// Proof: System Account (max_values: None, max_size: Some(128), added: 2603, mode: MaxEncodedLen)
+	fn slash_provider() -> Weight {
+		// Proof Size summary in bytes:
+		//  Measured:  `219`
+		//  Estimated: `3593`
+		// Minimum execution time: 24_000_000 picoseconds.
+		Weight::from_parts(25_000_000, 3593)
+			.saturating_add(T::DbWeight::get().reads(3_u64))
+			.saturating_add(T::DbWeight::get().writes(3_u64))
+	}
+	/// Storage: TrustlessFileServer FileOwners (r:1 w:0)
+	/// Proof: TrustlessFileServer FileOwners (max_values: None, max_size: Some(80), added: 2555, mode: MaxEncodedLen)
+	/// Storage: TrustlessFileServerDeals Committees (r:1 w:1)
+	/// Proof: TrustlessFileServerDeals Committees (max_values: None, max_size: Some(561), added: 3036, mode: MaxEncodedLen)
+	/// Storage: RandomnessCollectiveFlip RandomMaterial (r:1 w:0)
+	/// Proof: RandomnessCollectiveFlip RandomMaterial (max_values: Some(1), max_size: Some(2594), added: 3089, mode: MaxEncodedLen)
+	/// Storage: TrustlessFileServerDeals Providers (r:17 w:0)
+	/// Proof: TrustlessFileServerDeals Providers (max_values: None, max_size: Some(68), added: 2543, mode: MaxEncodedLen)
+	/// The range of component `k` is `[1, 16]`.
+	fn assign_committee(k: u32, ) -> Weight {
+		// Proof Size summary in bytes:
+		//  Measured:  `2281 + k * (83 ±0)`
+		//  Estimated: `4079 + k * (2543 ±0)`
+		// Minimum execution time: 27_000_000 picoseconds.
+		Weight::from_parts(24_162_000, 4079)
+			// Standard Error: 9_000
+			.saturating_add(Weight::from_parts(6_118_000, 0).saturating_mul(k.into()))
+			.saturating_add(T::DbWeight::get().reads(4_u64))
+			.saturating_add(T::DbWeight::get().reads((1_u64).saturating_mul(k.into())))
+			.saturating_add(T::DbWeight::get().writes(1_u64))
+			.saturating_add(Weight::from_parts(0, 2543).saturating_mul(k.into()))
+	}
+	/// Storage: TrustlessFileServerDeals Providers (r:1 w:0)
+	/// Proof: TrustlessFileServerDeals Providers (max_values: None, max_size: Some(68), added: 2543, mode: MaxEncodedLen)
+	/// Storage: TrustlessFileServerDeals AttestationKeys (r:0 w:1)
+	/// Proof: TrustlessFileServerDeals AttestationKeys (max_values: None, max_size: Some(88), added: 2563, mode: MaxEncodedLen)
+	fn set_attestation_key() -> Weight {
+		// Proof Size summary in bytes:
+		//  Measured:  `119`
+		//  Estimated: `3533`
+		// Minimum execution time: 52_000_000 picoseconds.
+		Weight::from_parts(53_000_000, 3533)
+			.saturating_add(T::DbWeight::get().reads(1_u64))
+			.saturating_add(T::DbWeight::get().writes(1_u64))
+	}
+	/// Storage: TrustlessFileServerDeals Committees (r:1 w:0)
+	/// Proof: TrustlessFileServerDeals Committees (max_values: None, max_size: Some(561), added: 3036, mode: MaxEncodedLen)
+	/// Storage: TrustlessFileServer FileMeta (r:1 w:0)
+	/// Proof: TrustlessFileServer FileMeta (max_values: None, max_size: Some(65), added: 2540, mode: MaxEncodedLen)
+	/// Storage: TrustlessFileServerDeals Availability (r:1 w:1)
+	/// Proof: TrustlessFileServerDeals Availability (max_values: None, max_size: Some(60), added: 2535, mode: MaxEncodedLen)
+	/// Storage: TrustlessFileServerDeals AttestationKeys (r:16 w:0)
+	/// Proof: TrustlessFileServerDeals AttestationKeys (max_values: None, max_size: Some(88), added: 2563, mode: MaxEncodedLen)
+	fn attest_availability() -> Weight {
+		// Proof Size summary in bytes:
+		//  Measured:  `2874`
+		//  Estimated: `44034`
+		// Minimum execution time: 61_000_000 picoseconds.
+		Weight::from_parts(63_000_000, 44034)
+			.saturating_add(T::DbWeight::get().reads(19_u64))
+			.saturating_add(T::DbWeight::get().writes(1_u64))
+	}
+	/// Storage: TrustlessFileServerDeals Deals (r:1 w:1)
+	/// Proof: TrustlessFileServerDeals Deals (max_values: None, max_size: Some(169), added: 2644, mode: MaxEncodedLen)
+	/// Storage: TrustlessFileServer FileMeta (r:1 w:0)
+	/// Proof: TrustlessFileServer FileMeta (max_values: None, max_size: Some(65), added: 2540, mode: MaxEncodedLen)
+	/// Storage: RandomnessCollectiveFlip RandomMaterial (r:1 w:0)
+	/// Proof: RandomnessCollectiveFlip RandomMaterial (max_values: Some(1), max_size: Some(2594), added: 3089, mode: MaxEncodedLen)
+	/// Storage: System Account (r:2 w:2)
+	/// Proof: System Account (max_values: None, max_size: Some(128), added: 2603, mode: MaxEncodedLen)
+	/// Storage: TrustlessFileServerDeals Providers (r:1 w:1)
+	/// Proof: TrustlessFileServerDeals Providers (max_values: None, max_size: Some(68), added: 2543, mode: MaxEncodedLen)
+	fn respond_challenge_privately() -> Weight {
+		// Proof Size summary in bytes:
+		//  Measured:  `914`
+		//  Estimated: `6196`
+		// Minimum execution time: 27_415_000_000 picoseconds.
+		Weight::from_parts(27_602_000_000, 6196)
+			.saturating_add(T::DbWeight::get().reads(6_u64))
+			.saturating_add(T::DbWeight::get().writes(4_u64))
+	}
+	/// Storage: TrustlessFileServerDeals Deals (r:1 w:1)
+	/// Proof: TrustlessFileServerDeals Deals (max_values: None, max_size: Some(169), added: 2644, mode: MaxEncodedLen)
+	/// Storage: System Account (r:2 w:2)
+	/// Proof: System Account (max_values: None, max_size: Some(128), added: 2603, mode: MaxEncodedLen)
+	fn settle_deal() -> Weight {
+		// Proof Size summary in bytes:
+		//  Measured:  `732`
+		//  Estimated: `6196`
+		// Minimum execution time: 31_000_000 picoseconds.
+		Weight::from_parts(32_000_000, 6196)
+			.saturating_add(T::DbWeight::get().reads(3_u64))
+			.saturating_add(T::DbWeight::get().writes(3_u64))
+	}
+	/// Storage: TrustlessFileServerDeals Deals (r:1 w:1)
+	/// Proof: TrustlessFileServerDeals Deals (max_values: None, max_size: Some(169), added: 2644, mode: MaxEncodedLen)
+	/// Storage: TrustlessFileServer FileOwners (r:1 w:0)
+	/// Proof: TrustlessFileServer FileOwners (max_values: None, max_size: Some(80), added: 2555, mode: MaxEncodedLen)
+	/// Storage: TrustlessFileServer Reputation (r:1 w:1)
+	/// Proof: TrustlessFileServer Reputation (max_values: None, max_size: Some(68), added: 2543, mode: MaxEncodedLen)
+	/// Storage: System Account (r:3 w:3)
+	/// Proof: System Account (max_values: None, max_size: Some(128), added: 2603, mode: MaxEncodedLen)
+	/// Storage: TrustlessFileServerDeals Providers (r:1 w:1)
+	/// Proof: TrustlessFileServerDeals Providers (max_values: None, max_size: Some(68), added: 2543, mode: MaxEncodedLen)
+	fn claim_deal_collateral() -> Weight {
+		// Proof Size summary in bytes:
+		//  Measured:  `2957`
+		//  Estimated: `8799`
+		// Minimum execution time: 55_000_000 picoseconds.
+		Weight::from_parts(57_000_000, 8799)
+			.saturating_add(T::DbWeight::get().reads(7_u64))
+			.saturating_add(T::DbWeight::get().writes(6_u64))
+	}
+}
+
+// For backwards compatibility and tests
+impl WeightInfo for () {
+	/// Storage: TrustlessFileServerDeals Providers (r:1 w:1)
+	/// Proof: TrustlessFileServerDeals Providers (max_values: None, max_size: Some(68), added: 2543, mode: MaxEncodedLen)
+	/// Storage: System Account (r:1 w:1)
+	/// Proof: System Account (max_values: None, max_size: Some(128), added: 2603, mode: MaxEncodedLen)
+	fn register_provider() -> Weight {
+		// Proof Size summary in bytes:
+		//  Measured:  `6`
+		//  Estimated: `3593`
+		// Minimum execution time: 18_000_000 picoseconds.
+		Weight::from_parts(19_000_000, 3593)
+			.saturating_add(RocksDbWeight::get().reads(2_u64))
+			.saturating_add(RocksDbWeight::get().writes(2_u64))
+	}
+	/// Storage: TrustlessFileServerDeals Providers (r:1 w:1)
+	/// Proof: TrustlessFileServerDeals Providers (max_values: None, max_size: Some(68), added: 2543, mode: MaxEncodedLen)
+	/// Storage: TrustlessFileServerDeals Unbonding (r:1 w:1)
+	/// Proof: TrustlessFileServerDeals Unbonding (max_values: None, max_size: Some(68), added: 2543, mode: MaxEncodedLen)
+	fn unregister_provider() -> Weight {
+		// Proof Size summary in bytes:
+		//  Measured:  `151`
+		//  Estimated: `3593`
+		// Minimum execution time: 19_000_000 picoseconds.
+		Weight::from_parts(20_000_000, 3593)
+			.saturating_add(RocksDbWeight::get().reads(2_u64))
+			.saturating_add(RocksDbWeight::get().writes(2_u64))
+	}
+	/// Storage: TrustlessFileServer FileOwners (r:1 w:0)
+	/// Proof: TrustlessFileServer FileOwners (max_values: None, max_size: Some(80), added: 2555, mode: MaxEncodedLen)
+	/// Storage: System Account (r:2 w:2)
+	/// Proof: System Account (max_values: None, max_size: Some(128), added: 2603, mode: MaxEncodedLen)
+	/// Storage: TrustlessFileServerDeals NextDealId (r:1 w:1)
+	/// Proof: TrustlessFileServerDeals NextDealId (max_values: Some(1), max_size: Some(8), added: 503, mode: MaxEncodedLen)
+	/// Storage: TrustlessFileServerDeals Deals (r:0 w:1)
+	/// Proof: TrustlessFileServerDeals Deals (max_values: None, max_size: Some(169), added: 2644, mode: MaxEncodedLen)
+	fn propose_deal() -> Weight {
+		// Proof Size summary in bytes:
+		//  Measured:  `2314`
+		//  Estimated: `6196`
+		// Minimum execution time: 34_000_000 picoseconds.
+		Weight::from_parts(35_000_000, 6196)
+			.saturating_add(RocksDbWeight::get().reads(4_u64))
+			.saturating_add(RocksDbWeight::get().writes(4_u64))
+	}
+	/// Storage: TrustlessFileServerDeals Providers (r:1 w:1)
+	/// Proof: TrustlessFileServerDeals Providers (max_values: None, max_size: Some(68), added: 2543, mode: MaxEncodedLen)
+	/// Storage: TrustlessFileServerDeals Deals (r:1 w:1)
+	/// Proof: TrustlessFileServerDeals Deals (max_values: None, max_size: Some(169), added: 2644, mode: MaxEncodedLen)
+	/// Storage: TrustlessFileServer FileMeta (r:1 w:0)
+	/// Proof: TrustlessFileServer FileMeta (max_values: None, max_size: Some(65), added: 2540, mode: MaxEncodedLen)
+	/// Storage: RandomnessCollectiveFlip RandomMaterial (r:1 w:0)
+	/// Proof: RandomnessCollectiveFlip RandomMaterial (max_values: Some(1), max_size: Some(2594), added: 3089, mode: MaxEncodedLen)
+	/// Storage: System Account (r:1 w:1)
+	/// Proof: System Account (max_values: None, max_size: Some(128), added: 2603, mode: MaxEncodedLen)
+	/// Storage: TrustlessFileServerDeals Committees (r:1 w:0)
+	/// Proof: TrustlessFileServerDeals Committees (max_values: None, max_size: Some(561), added: 3036, mode: MaxEncodedLen)
+	fn accept_deal() -> Weight {
+		// Proof Size summary in bytes:
+		//  Measured:  `2638`
+		//  Estimated: `5613`
+		// Minimum execution time: 31_000_000 picoseconds.
+		Weight::from_parts(32_000_000, 5613)
+			.saturating_add(RocksDbWeight::get().reads(6_u64))
+			.saturating_add(RocksDbWeight::get().writes(3_u64))
+	}
+	/// Storage: TrustlessFileServerDeals Deals (r:1 w:1)
+	/// Proof: TrustlessFileServerDeals Deals (max_values: None, max_size: Some(169), added: 2644, mode: MaxEncodedLen)
+	/// Storage: TrustlessFileServer FileTrees (r:1 w:0)
+	/// Proof: TrustlessFileServer FileTrees (max_values: None, max_size: Some(2116), added: 4591, mode: MaxEncodedLen)
+	/// Storage: TrustlessFileServer FileMeta (r:1 w:0)
+	/// Proof: TrustlessFileServer FileMeta (max_values: None, max_size: Some(65), added: 2540, mode: MaxEncodedLen)
+	/// Storage: RandomnessCollectiveFlip RandomMaterial (r:1 w:0)
+	/// Proof: RandomnessCollectiveFlip RandomMaterial (max_values: Some(1), max_size: Some(2594), added: 3089, mode: MaxEncodedLen)
+	/// Storage: System Account (r:2 w:2)
+	/// Proof: System Account (max_values: None, max_size: Some(128), added: 2603, mode: MaxEncodedLen)
+	/// Storage: TrustlessFileServerDeals Providers (r:1 w:1)
+	/// Proof: TrustlessFileServerDeals Providers (max_values: None, max_size: Some(68), added: 2543, mode: MaxEncodedLen)
+	/// The range of component `x` is `[1, 1024]`.
+	fn respond_challenge(x: u32, ) -> Weight {
+		// Proof Size summary in bytes:
+		//  Measured:  `2957`
+		//  Estimated: `6196`
+		// Minimum execution time: 41_000_000 picoseconds.
+		Weight::from_parts(41_856_301, 6196)
+			// Standard Error: 12
+			.saturating_add(Weight::from_parts(2_871, 0).saturating_mul(x.into()))
+			.saturating_add(RocksDbWeight::get().reads(7_u64))
+			.saturating_add(RocksDbWeight::get().writes(4_u64))
+	}
+	/// Storage: TrustlessFileServerDeals Deals (r:1 w:1)
+	/// Proof: TrustlessFileServerDeals Deals (max_values: None, max_size: Some(169), added: 2644, mode: MaxEncodedLen)
+	/// Storage: TrustlessFileServer FileOwners (r:1 w:0)
+	/// Proof: TrustlessFileServer FileOwners (max_values: None, max_size: Some(80), added: 2555, mode: MaxEncodedLen)
+	/// Storage: System Account (r:3 w:3)
+	/// Proof: System Account (max_values: None, max_size: Some(128), added: 2603, mode: MaxEncodedLen)
+	/// Storage: TrustlessFileServerDeals Providers (r:1 w:1)
+	/// Proof: TrustlessFileServerDeals Providers (max_values: None, max_size: Some(68), added: 2543, mode: MaxEncodedLen)
+	fn terminate_deal() -> Weight {
+		// Proof Size summary in bytes:
+		//  Measured:  `2957`
+		//  Estimated: `8799`
+		// Minimum execution time: 52_000_000 picoseconds.
+		Weight::from_parts(53_000_000, 8799)
+			.saturating_add(RocksDbWeight::get().reads(6_u64))
+			.saturating_add(RocksDbWeight::get().writes(5_u64))
+	}
+	/// Storage: System Account (r:2 w:2)
+	/// Proof: System Account (max_values: None, max_size: Some(128), added: 2603, mode: MaxEncodedLen)
+	/// Storage: TrustlessFileServerDeals NextChannelId (r:1 w:1)
+	/// Proof: TrustlessFileServerDeals NextChannelId (max_values: Some(1), max_size: Some(8), added: 503, mode: MaxEncodedLen)
+	/// Storage: TrustlessFileServerDeals Channels (r:0 w:1)
+	/// Proof: TrustlessFileServerDeals Channels (max_values: None, max_size: Some(141), added: 2616, mode: MaxEncodedLen)
+	fn open_channel() -> Weight {
+		// Proof Size summary in bytes:
+		//  Measured:  `140`
+		//  Estimated: `6196`
+		// Minimum execution time: 30_000_000 picoseconds.
+		Weight::from_parts(31_000_000, 6196)
+			.saturating_add(RocksDbWeight::get().reads(3_u64))
+			.saturating_add(RocksDbWeight::get().writes(4_u64))
+	}
+	/// Storage: TrustlessFileServerDeals Channels (r:1 w:1)
+	/// Proof: TrustlessFileServerDeals Channels (max_values: None, max_size: Some(141), added: 2616, mode: MaxEncodedLen)
+	/// Storage: System Account (r:2 w:2)
+	/// Proof: System Account (max_values: None, max_size: Some(128), added: 2603, mode: MaxEncodedLen)
+	/// Storage: TrustlessFileServer Metadata (r:8 w:0)
+	/// Proof: TrustlessFileServer Metadata (max_values: None, max_size: Some(761), added: 3236, mode: MaxEncodedLen)
+	/// The range of component `n` is `[1, 8]`.
+	fn redeem_vouchers(n: u32, ) -> Weight {
+		// Proof Size summary in bytes:
+		//  Measured:  `312`
+		//  Estimated: `6196`
+		// Minimum execution time: 74_000_000 picoseconds.
+		Weight::from_parts(29_417_000, 6196)
+			// Standard Error: 21_000
+			.saturating_add(Weight::from_parts(45_322_000, 0).saturating_mul(n.into()))
+			.saturating_add(RocksDbWeight::get().reads(3_u64))
+			.saturating_add(RocksDbWeight::get().writes(3_u64))
+			.saturating_add(RocksDbWeight::get().reads((1_u64).saturating_mul(n.into())))
+	}
+	/// Storage: TrustlessFileServerDeals Channels (r:1 w:1)
+	/// Proof: TrustlessFileServerDeals Channels (max_values: None, max_size: Some(141), added: 2616, mode: MaxEncodedLen)
+	/// Storage: System Account (r:2 w:2)
+	/// Proof: System Account (max_values: None, max_size: Some(128), added: 2603, mode: MaxEncodedLen)
+	fn close_channel() -> Weight {
+		// Proof Size summary in bytes:
+		//  Measured:  `312`
+		//  Estimated: `6196`
+		// Minimum execution time: 32_000_000 picoseconds.
+		Weight::from_parts(33_000_000, 6196)
+			.saturating_add(RocksDbWeight::get().reads(3_u64))
+			.saturating_add(RocksDbWeight::get().writes(3_u64))
+	}
+	/// Storage: TrustlessFileServerDeals Unbonding (r:1 w:1)
+	/// Proof: TrustlessFileServerDeals Unbonding (max_values: None, max_size: Some(68), added: 2543, mode: MaxEncodedLen)
+	/// Storage: System Account (r:1 w:1)
+	/// Proof: System Account (max_values: None, max_size: Some(128), added: 2603, mode: MaxEncodedLen)
+	fn withdraw_unbonded() -> Weight {
+		// Proof Size summary in bytes:
+		//  Measured:  `151`
+		//  Estimated: `3593`
+		// Minimum execution time: 19_000_000 picoseconds.
+		Weight::from_parts(20_000_000, 3593)
+			.saturating_add(RocksDbWeight::get().reads(2_u64))
+			.saturating_add(RocksDbWeight::get().writes(2_u64))
+	}
+	/// Storage: TrustlessFileServerDeals Providers (r:1 w:1)
+	/// Proof: TrustlessFileServerDeals Providers (max_values: None, max_size: Some(68), added: 2543, mode: MaxEncodedLen)
+	/// Storage: TrustlessFileServerDeals Unbonding (r:1 w:1)
+	/// Proof: TrustlessFileServerDeals Unbonding (max_values: None, max_size: Some(68), added: 2543, mode: MaxEncodedLen)
+	/// Storage: System Account (r:1 w:1)
+	/// Proof: System Account (max_values: None, max_size: Some(128), added: 2603, mode: MaxEncodedLen)
+	fn slash_provider() -> Weight {
+		// Proof Size summary in bytes:
+		//  Measured:  `219`
+		//  Estimated: `3593`
+		// Minimum execution time: 24_000_000 picoseconds.
+		Weight::from_parts(25_000_000, 3593)
+			.saturating_add(RocksDbWeight::get().reads(3_u64))
+			.saturating_add(RocksDbWeight::get().writes(3_u64))
+	}
+	/// Storage: TrustlessFileServer FileOwners (r:1 w:0)
+	/// Proof: TrustlessFileServer FileOwners (max_values: None, max_size: Some(80), added: 2555, mode: MaxEncodedLen)
+	/// Storage: TrustlessFileServerDeals Committees (r:1 w:1)
+	/// Proof: TrustlessFileServerDeals Committees (max_values: None, max_size: Some(561), added: 3036, mode: MaxEncodedLen)
+	/// Storage: RandomnessCollectiveFlip RandomMaterial (r:1 w:0)
+	/// Proof: RandomnessCollectiveFlip RandomMaterial (max_values: Some(1), max_size: Some(2594), added: 3089, mode: MaxEncodedLen)
+	/// Storage: TrustlessFileServerDeals Providers (r:17 w:0)
+	/// Proof: TrustlessFileServerDeals Providers (max_values: None, max_size: Some(68), added: 2543, mode: MaxEncodedLen)
+	/// The range of component `k` is `[1, 16]`.
+	fn assign_committee(k: u32, ) -> Weight {
+		// Proof Size summary in bytes:
+		//  Measured:  `2281 + k * (83 ±0)`
+		//  Estimated: `4079 + k * (2543 ±0)`
+		// Minimum execution time: 27_000_000 picoseconds.
+		Weight::from_parts(24_162_000, 4079)
+			// Standard Error: 9_000
+			.saturating_add(Weight::from_parts(6_118_000, 0).saturating_mul(k.into()))
+			.saturating_add(RocksDbWeight::get().reads(4_u64))
+			.saturating_add(RocksDbWeight::get().reads((1_u64).saturating_mul(k.into())))
+			.saturating_add(RocksDbWeight::get().writes(1_u64))
+			.saturating_add(Weight::from_parts(0, 2543).saturating_mul(k.into()))
+	}
+	/// Storage: TrustlessFileServerDeals Providers (r:1 w:0)
+	/// Proof: TrustlessFileServerDeals Providers (max_values: None, max_size: Some(68), added: 2543, mode: MaxEncodedLen)
+	/// Storage: TrustlessFileServerDeals AttestationKeys (r:0 w:1)
+	/// Proof: TrustlessFileServerDeals AttestationKeys (max_values: None, max_size: Some(88), added: 2563, mode: MaxEncodedLen)
+	fn set_attestation_key() -> Weight {
+		// Proof Size summary in bytes:
+		//  Measured:  `119`
+		//  Estimated: `3533`
+		// Minimum execution time: 52_000_000 picoseconds.
+		Weight::from_parts(53_000_000, 3533)
+			.saturating_add(RocksDbWeight::get().reads(1_u64))
+			.saturating_add(RocksDbWeight::get().writes(1_u64))
+	}
+	/// Storage: TrustlessFileServerDeals Committees (r:1 w:0)
+	/// Proof: TrustlessFileServerDeals Committees (max_values: None, max_size: Some(561), added: 3036, mode: MaxEncodedLen)
+	/// Storage: TrustlessFileServer FileMeta (r:1 w:0)
+	/// Proof: TrustlessFileServer FileMeta (max_values: None, max_size: Some(65), added: 2540, mode: MaxEncodedLen)
+	/// Storage: TrustlessFileServerDeals Availability (r:1 w:1)
+	/// Proof: TrustlessFileServerDeals Availability (max_values: None, max_size: Some(60), added: 2535, mode: MaxEncodedLen)
+	/// Storage: TrustlessFileServerDeals AttestationKeys (r:16 w:0)
+	/// Proof: TrustlessFileServerDeals AttestationKeys (max_values: None, max_size: Some(88), added: 2563, mode: MaxEncodedLen)
+	fn attest_availability() -> Weight {
+		// Proof Size summary in bytes:
+		//  Measured:  `2874`
+		//  Estimated: `44034`
+		// Minimum execution time: 61_000_000 picoseconds.
+		Weight::from_parts(63_000_000, 44034)
+			.saturating_add(RocksDbWeight::get().reads(19_u64))
+			.saturating_add(RocksDbWeight::get().writes(1_u64))
+	}
+	/// Storage: TrustlessFileServerDeals Deals (r:1 w:1)
+	/// Proof: TrustlessFileServerDeals Deals (max_values: None, max_size: Some(169), added: 2644, mode: MaxEncodedLen)
+	/// Storage: TrustlessFileServer FileMeta (r:1 w:0)
+	/// Proof: TrustlessFileServer FileMeta (max_values: None, max_size: Some(65), added: 2540, mode: MaxEncodedLen)
+	/// Storage: RandomnessCollectiveFlip RandomMaterial (r:1 w:0)
+	/// Proof: RandomnessCollectiveFlip RandomMaterial (max_values: Some(1), max_size: Some(2594), added: 3089, mode: MaxEncodedLen)
+	/// Storage: System Account (r:2 w:2)
+	/// Proof: System Account (max_values: None, max_size: Some(128), added: 2603, mode: MaxEncodedLen)
+	/// Storage: TrustlessFileServerDeals Providers (r:1 w:1)
+	/// Proof: TrustlessFileServerDeals Providers (max_values: None, max_size: Some(68), added: 2543, mode: MaxEncodedLen)
+	fn respond_challenge_privately() -> Weight {
+		// Proof Size summary in bytes:
+		//  Measured:  `914`
+		//  Estimated: `6196`
+		// Minimum execution time: 27_415_000_000 picoseconds.
+		Weight::from_parts(27_602_000_000, 6196)
+			.saturating_add(RocksDbWeight::get().reads(6_u64))
+			.saturating_add(RocksDbWeight::get().writes(4_u64))
+	}
+	/// Storage: TrustlessFileServerDeals Deals (r:1 w:1)
+	/// Proof: TrustlessFileServerDeals Deals (max_values: None, max_size: Some(169), added: 2644, mode: MaxEncodedLen)
+	/// Storage: System Account (r:2 w:2)
+	/// Proof: System Account (max_values: None, max_size: Some(128), added: 2603, mode: MaxEncodedLen)
+	fn settle_deal() -> Weight {
+		// Proof Size summary in bytes:
+		//  Measured:  `732`
+		//  Estimated: `6196`
+		// Minimum execution time: 31_000_000 picoseconds.
+		Weight::from_parts(32_000_000, 6196)
+			.saturating_add(RocksDbWeight::get().reads(3_u64))
+			.saturating_add(RocksDbWeight::get().writes(3_u64))
+	}
+	/// Storage: TrustlessFileServerDeals Deals (r:1 w:1)
+	/// Proof: TrustlessFileServerDeals Deals (max_values: None, max_size: Some(169), added: 2644, mode: MaxEncodedLen)
+	/// Storage: TrustlessFileServer FileOwners (r:1 w:0)
+	/// Proof: TrustlessFileServer FileOwners (max_values: None, max_size: Some(80), added: 2555, mode: MaxEncodedLen)
+	/// Storage: TrustlessFileServer Reputation (r:1 w:1)
+	/// Proof: TrustlessFileServer Reputation (max_values: None, max_size: Some(68), added: 2543, mode: MaxEncodedLen)
+	/// Storage: System Account (r:3 w:3)
+	/// Proof: System Account (max_values: None, max_size: Some(128), added: 2603, mode: MaxEncodedLen)
+	/// Storage: TrustlessFileServerDeals Providers (r:1 w:1)
+	/// Proof: TrustlessFileServerDeals Providers (max_values: None, max_size: Some(68), added: 2543, mode: MaxEncodedLen)
+	fn claim_deal_collateral() -> Weight {
+		// Proof Size summary in bytes:
+		//  Measured:  `2957`
+		//  Estimated: `8799`
+		// Minimum execution time: 55_000_000 picoseconds.
+		Weight::from_parts(57_000_000, 8799)
+			.saturating_add(RocksDbWeight::get().reads(7_u64))
+			.saturating_add(RocksDbWeight::get().writes(6_u64))
+	}
+}
@@ -0,0 +1,223 @@
+//! # Trustless File Server EVM precompile
+//!
+//! Exposes the files registered in the trustless file server pallet to Solidity contracts running
+//! on Frontier-based chains. The precompile implements the following interface:
+//!
+//! ```solidity
+//! interface TrustlessFileServer {
+//!     function fileExists(bytes32 root) external view returns (bool);
+//!     function pieces(bytes32 root) external view returns (uint32);
+//!     function verifyProof(bytes32 root, uint64 pos, bytes32 leaf, bytes32[] calldata proof)
+//!         external view returns (bool);
+//!     function verifyProof(bytes32 root, uint32 pos, bytes32 leaf, bytes32[] calldata proof)
+//!         external view returns (bool);
+//! }
+//! ```
+//!
+//! `pieces` returns `0` for files that are not registered. `leaf` is the sha256 hash of the chunk
+//! as stored in the merkle tree, i.e. zero-padded up to the chunk size for the last piece. The
+//! `uint32` overload of `verifyProof` is kept for contracts deployed before positions were 64 bits.
+
+#![cfg_attr(not(feature = "std"), no_std)]
+
+use codec::Decode;
+use fp_evm::{
+    ExitRevert, ExitSucceed, Precompile, PrecompileFailure, PrecompileHandle, PrecompileOutput,
+    PrecompileResult,
+};
+use frame_support::traits::Get;
+use frame_support::weights::Weight;
+use pallet_evm::GasWeightMapping;
+use pallet_trustless_file_server::{
+    BoundedProof, Pallet as TrustlessFileServer, WeightInfo, MAX_TREE_DEPTH,
+};
+use sp_io::hashing::keccak_256;
+use sp_std::marker::PhantomData;
+use sp_std::vec::Vec;
+
+/// Size of an ABI word, in bytes.
+const WORD_SIZE: usize = 32;
+
+/// Precompile exposing `fileExists`, `pieces` and `verifyProof` to Solidity contracts.
+pub struct TrustlessFileServerPrecompile<Runtime>(PhantomData<Runtime>);
+
+impl<Runtime> Precompile for TrustlessFileServerPrecompile<Runtime>
+where
+    Runtime: pallet_evm::Config + pallet_trustless_file_server::Config,
+{
+    fn execute(handle: &mut impl PrecompileHandle) -> PrecompileResult {
+        let input = handle.input().to_vec();
+        if input.len() < 4 {
+            return Err(revert("Missing function selector"));
+        }
+        let (selector, args) = input.split_at(4);
+
+        if selector == function_selector(b"fileExists(bytes32)") {
+            record_weight::<Runtime>(handle, Runtime::DbWeight::get().reads(1))?;
+            let merkle_root = read_hash::<Runtime>(args, 0)?;
+            let exists = TrustlessFileServer::<Runtime>::file_exists(merkle_root);
+            Ok(succeed(encode_u32(exists as u32)))
+        } else if selector == function_selector(b"pieces(bytes32)") {
+            record_weight::<Runtime>(handle, Runtime::DbWeight::get().reads(1))?;
+            let merkle_root = read_hash::<Runtime>(args, 0)?;
+            let pieces = TrustlessFileServer::<Runtime>::file_pieces(merkle_root).unwrap_or(0);
+            Ok(succeed(encode_u32(pieces)))
+        } else if selector == function_selector(b"verifyProof(bytes32,uint64,bytes32,bytes32[])") {
+            let position = read_u64(args, 1)?;
+            verify_proof::<Runtime>(handle, args, position)
+        } else if selector == function_selector(b"verifyProof(bytes32,uint32,bytes32,bytes32[])") {
+            let position = read_u32(args, 1)?;
+            verify_proof::<Runtime>(handle, args, position.into())
+        } else {
+            Err(revert("Unknown function selector"))
+        }
+    }
+}
+
+/// Checks the merkle proof of the piece at `position`, whatever the width it was passed with.
+fn verify_proof<Runtime>(
+    handle: &mut impl PrecompileHandle,
+    args: &[u8],
+    position: u64,
+) -> PrecompileResult
+where
+    Runtime: pallet_evm::Config + pallet_trustless_file_server::Config,
+{
+    let merkle_root = read_hash::<Runtime>(args, 0)?;
+    let leaf = read_word(args, 2)?;
+    let proof = read_bytes32_array(args, 3)?;
+    record_weight::<Runtime>(
+        handle,
+        <Runtime as pallet_trustless_file_server::Config>::WeightInfo::verify_proof(
+            proof.len() as u32
+        ),
+    )?;
+    let valid = TrustlessFileServer::<Runtime>::verify_proof(merkle_root, position, &leaf, &proof);
+    Ok(succeed(encode_u32(valid as u32)))
+}
+
+fn function_selector(signature: &[u8]) -> [u8; 4] {
+    let hash = keccak_256(signature);
+    [hash[0], hash[1], hash[2], hash[3]]
+}
+
+fn record_weight<Runtime: pallet_evm::Config>(
+    handle: &mut impl PrecompileHandle,
+    weight: Weight,
+) -> Result<(), PrecompileFailure> {
+    let gas = Runtime::GasWeightMapping::weight_to_gas(weight);
+    handle
+        .record_cost(gas)
+        .map_err(|exit_status| PrecompileFailure::Error { exit_status })
+}
+
+fn succeed(output: Vec<u8>) -> PrecompileOutput {
+    PrecompileOutput {
+        exit_status: ExitSucceed::Returned,
+        output,
+    }
+}
+
+fn revert(message: &str) -> PrecompileFailure {
+    PrecompileFailure::Revert {
+        exit_status: ExitRevert::Reverted,
+        output: message.as_bytes().to_vec(),
+    }
+}
+
+fn read_word(args: &[u8], index: usize) -> Result<[u8; WORD_SIZE], PrecompileFailure> {
+    let start = index
+        .checked_mul(WORD_SIZE)
+        .ok_or_else(|| revert("Argument out of bounds"))?;
+    args.get(start..start + WORD_SIZE)
+        .and_then(|word| word.try_into().ok())
+        .ok_or_else(|| revert("Argument out of bounds"))
+}
+
+fn read_hash<Runtime: frame_system::Config>(
+    args: &[u8],
+    index: usize,
+) -> Result<Runtime::Hash, PrecompileFailure> {
+    let word = read_word(args, index)?;
+    Runtime::Hash::decode(&mut word.as_slice()).map_err(|_| revert("Invalid merkle root"))
+}
+
+fn read_u64(args: &[u8], index: usize) -> Result<u64, PrecompileFailure> {
+    let word = read_word(args, index)?;
+    let (padding, value) = word.split_at(WORD_SIZE - 8);
+    if padding.iter().any(|byte| *byte != 0) {
+        return Err(revert("Value out of bounds"));
+    }
+    Ok(u64::from_be_bytes(value.try_into().expect("8 bytes long; qed")))
+}
+
+fn read_u32(args: &[u8], index: usize) -> Result<u32, PrecompileFailure> {
+    read_u64(args, index)?
+        .try_into()
+        .map_err(|_| revert("Value out of bounds"))
+}
+
+/// Reads a `bytes32[]` argument holding a merkle proof, which can be at most
+/// [`MAX_TREE_DEPTH`] siblings long.
+fn read_bytes32_array(args: &[u8], index: usize) -> Result<BoundedProof, PrecompileFailure> {
+    let offset = read_u32(args, index)? as usize;
+    if offset % WORD_SIZE != 0 {
+        return Err(revert("Misaligned array offset"));
+    }
+    let first = offset / WORD_SIZE;
+    let len = read_u32(args, first)? as usize;
+    if len > MAX_TREE_DEPTH as usize {
+        return Err(revert("Proof too long"));
+    }
+    let proof = (0..len)
+        .map(|i| read_word(args, first + 1 + i))
+        .collect::<Result<Vec<_>, _>>()?;
+    Ok(proof.try_into().expect("length checked above; qed"))
+}
+
+fn encode_u32(value: u32) -> Vec<u8> {
+    let mut word = [0u8; WORD_SIZE];
+    word[WORD_SIZE - 4..].copy_from_slice(&value.to_be_bytes());
+    word.to_vec()
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn word(value: u32) -> Vec<u8> {
+        encode_u32(value)
+    }
+
+    #[test]
+    fn test_read_u32_should_reject_overflowing_values() {
+        let mut args = word(7);
+        assert_eq!(read_u32(&args, 0).ok(), Some(7));
+        args[0] = 1;
+        assert!(read_u32(&args, 0).is_err());
+        assert!(read_u32(&args, 1).is_err());
+
+        // values fitting 64 bits are only read as such
+        let mut args = word(7);
+        args[WORD_SIZE - 5] = 1;
+        assert_eq!(read_u64(&args, 0).ok(), Some((1 << 32) + 7));
+        assert!(read_u32(&args, 0).is_err());
+    }
+
+    #[test]
+    fn test_read_bytes32_array_should_follow_offsets() {
+        // head: a single offset pointing right after it
+        let mut args = word(32);
+        args.extend(word(2));
+        args.extend([1u8; 32]);
+        args.extend([2u8; 32]);
+        let array = read_bytes32_array(&args, 0).ok().unwrap();
+        assert_eq!(array.into_inner(), vec![[1u8; 32], [2u8; 32]]);
+
+        // declared length bigger than the actual data
+        let mut args = word(32);
+        args.extend(word(3));
+        args.extend([1u8; 32]);
+        assert!(read_bytes32_array(&args, 0).is_err());
+    }
+}
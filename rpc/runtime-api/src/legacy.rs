@@ -0,0 +1,297 @@
+//! Types returned by earlier versions of the runtime API, so that clients can still decode the
+//! answers of runtimes implementing them, and turn them into the current ones.
+
+use alloc::string::String;
+use codec::{Decode, Encode};
+use sp_std::vec::Vec;
+
+use pallet_trustless_file_server::{
+    ExternalFile, FileInfo, FileRecord, FileSummary, License, PlannedChunk, UploadTime,
+};
+
+/// [`FileInfo`] as returned before version 11, with 32-bit file sizes and no retrieval price.
+#[derive(Clone, Encode, Decode, Eq, PartialEq)]
+#[cfg_attr(feature = "std", derive(Debug))]
+pub struct FileInfoV1<AccountId> {
+    pub owner: AccountId,
+    pub merkle_root: Vec<u8>,
+    pub size: u32,
+    pub pieces: u32,
+    pub chunk_size: u32,
+    pub bep52: bool,
+    pub sha256: Option<[u8; 32]>,
+    pub registrants: u32,
+    pub name: Vec<u8>,
+    pub mime_type: Vec<u8>,
+    pub tags: Vec<Vec<u8>>,
+    pub license: Option<License<Vec<u8>>>,
+}
+
+impl<AccountId> From<FileInfoV1<AccountId>> for FileInfo<AccountId> {
+    fn from(info: FileInfoV1<AccountId>) -> Self {
+        Self {
+            owner: info.owner,
+            merkle_root: info.merkle_root,
+            size: info.size.into(),
+            pieces: info.pieces,
+            chunk_size: info.chunk_size,
+            bep52: info.bep52,
+            sha256: info.sha256,
+            registrants: info.registrants,
+            name: info.name,
+            mime_type: info.mime_type,
+            tags: info.tags,
+            license: info.license,
+            retrieval_price: None,
+            cid: None,
+        }
+    }
+}
+
+/// [`FileInfo`] as returned by version 11, with no retrieval price.
+#[derive(Clone, Encode, Decode, Eq, PartialEq)]
+#[cfg_attr(feature = "std", derive(Debug))]
+pub struct FileInfoV11<AccountId> {
+    pub owner: AccountId,
+    pub merkle_root: Vec<u8>,
+    pub size: u64,
+    pub pieces: u32,
+    pub chunk_size: u32,
+    pub bep52: bool,
+    pub sha256: Option<[u8; 32]>,
+    pub registrants: u32,
+    pub name: Vec<u8>,
+    pub mime_type: Vec<u8>,
+    pub tags: Vec<Vec<u8>>,
+    pub license: Option<License<Vec<u8>>>,
+}
+
+impl<AccountId> From<FileInfoV11<AccountId>> for FileInfo<AccountId> {
+    fn from(info: FileInfoV11<AccountId>) -> Self {
+        Self {
+            owner: info.owner,
+            merkle_root: info.merkle_root,
+            size: info.size,
+            pieces: info.pieces,
+            chunk_size: info.chunk_size,
+            bep52: info.bep52,
+            sha256: info.sha256,
+            registrants: info.registrants,
+            name: info.name,
+            mime_type: info.mime_type,
+            tags: info.tags,
+            license: info.license,
+            retrieval_price: None,
+            cid: None,
+        }
+    }
+}
+
+/// [`FileInfo`] as returned by versions 12 and 13, with no CID.
+#[derive(Clone, Encode, Decode, Eq, PartialEq)]
+#[cfg_attr(feature = "std", derive(Debug))]
+pub struct FileInfoV12<AccountId> {
+    pub owner: AccountId,
+    pub merkle_root: Vec<u8>,
+    pub size: u64,
+    pub pieces: u32,
+    pub chunk_size: u32,
+    pub bep52: bool,
+    pub sha256: Option<[u8; 32]>,
+    pub registrants: u32,
+    pub name: Vec<u8>,
+    pub mime_type: Vec<u8>,
+    pub tags: Vec<Vec<u8>>,
+    pub license: Option<License<Vec<u8>>>,
+    pub retrieval_price: Option<u128>,
+}
+
+impl<AccountId> From<FileInfoV12<AccountId>> for FileInfo<AccountId> {
+    fn from(info: FileInfoV12<AccountId>) -> Self {
+        Self {
+            owner: info.owner,
+            merkle_root: info.merkle_root,
+            size: info.size,
+            pieces: info.pieces,
+            chunk_size: info.chunk_size,
+            bep52: info.bep52,
+            sha256: info.sha256,
+            registrants: info.registrants,
+            name: info.name,
+            mime_type: info.mime_type,
+            tags: info.tags,
+            license: info.license,
+            retrieval_price: info.retrieval_price,
+            cid: None,
+        }
+    }
+}
+
+/// [`FileSummary`] as returned before version 11, with 32-bit file sizes and no upload time.
+#[derive(Clone, Encode, Decode, Eq, PartialEq)]
+#[cfg_attr(feature = "std", derive(Debug))]
+pub struct FileSummaryV2<AccountId> {
+    pub merkle_root: Vec<u8>,
+    pub owner: AccountId,
+    pub size: u32,
+    pub pieces: u32,
+    pub license: Option<License<Vec<u8>>>,
+}
+
+impl<AccountId> From<FileSummaryV2<AccountId>> for FileSummary<AccountId> {
+    fn from(summary: FileSummaryV2<AccountId>) -> Self {
+        Self {
+            merkle_root: summary.merkle_root,
+            owner: summary.owner,
+            size: summary.size.into(),
+            pieces: summary.pieces,
+            license: summary.license,
+            uploaded_at: None,
+            cid: None,
+        }
+    }
+}
+
+/// [`FileSummary`] as returned by versions 11 and 12, with no upload time.
+#[derive(Clone, Encode, Decode, Eq, PartialEq)]
+#[cfg_attr(feature = "std", derive(Debug))]
+pub struct FileSummaryV11<AccountId> {
+    pub merkle_root: Vec<u8>,
+    pub owner: AccountId,
+    pub size: u64,
+    pub pieces: u32,
+    pub license: Option<License<Vec<u8>>>,
+}
+
+impl<AccountId> From<FileSummaryV11<AccountId>> for FileSummary<AccountId> {
+    fn from(summary: FileSummaryV11<AccountId>) -> Self {
+        Self {
+            merkle_root: summary.merkle_root,
+            owner: summary.owner,
+            size: summary.size,
+            pieces: summary.pieces,
+            license: summary.license,
+            uploaded_at: None,
+            cid: None,
+        }
+    }
+}
+
+/// [`FileSummary`] as returned by version 13, with no CID.
+#[derive(Clone, Encode, Decode, Eq, PartialEq)]
+#[cfg_attr(feature = "std", derive(Debug))]
+pub struct FileSummaryV13<AccountId> {
+    pub merkle_root: Vec<u8>,
+    pub owner: AccountId,
+    pub size: u64,
+    pub pieces: u32,
+    pub license: Option<License<Vec<u8>>>,
+    pub uploaded_at: Option<UploadTime<u32>>,
+}
+
+impl<AccountId> From<FileSummaryV13<AccountId>> for FileSummary<AccountId> {
+    fn from(summary: FileSummaryV13<AccountId>) -> Self {
+        Self {
+            merkle_root: summary.merkle_root,
+            owner: summary.owner,
+            size: summary.size,
+            pieces: summary.pieces,
+            license: summary.license,
+            uploaded_at: summary.uploaded_at,
+            cid: None,
+        }
+    }
+}
+
+/// [`FileRecord`] as returned before version 11, with 32-bit file sizes and no upload time.
+#[derive(Clone, Encode, Decode, Eq, PartialEq)]
+#[cfg_attr(feature = "std", derive(Debug))]
+pub struct FileRecordV9<AccountId> {
+    pub summary: FileSummaryV2<AccountId>,
+    pub tree: Option<Vec<Vec<u8>>>,
+}
+
+impl<AccountId> From<FileRecordV9<AccountId>> for FileRecord<AccountId> {
+    fn from(record: FileRecordV9<AccountId>) -> Self {
+        Self {
+            summary: record.summary.into(),
+            tree: record.tree,
+        }
+    }
+}
+
+/// [`FileRecord`] as returned by versions 11 and 12, with no upload time.
+#[derive(Clone, Encode, Decode, Eq, PartialEq)]
+#[cfg_attr(feature = "std", derive(Debug))]
+pub struct FileRecordV11<AccountId> {
+    pub summary: FileSummaryV11<AccountId>,
+    pub tree: Option<Vec<Vec<u8>>>,
+}
+
+impl<AccountId> From<FileRecordV11<AccountId>> for FileRecord<AccountId> {
+    fn from(record: FileRecordV11<AccountId>) -> Self {
+        Self {
+            summary: record.summary.into(),
+            tree: record.tree,
+        }
+    }
+}
+
+/// [`FileRecord`] as returned by version 13, with no CID.
+#[derive(Clone, Encode, Decode, Eq, PartialEq)]
+#[cfg_attr(feature = "std", derive(Debug))]
+pub struct FileRecordV13<AccountId> {
+    pub summary: FileSummaryV13<AccountId>,
+    pub tree: Option<Vec<Vec<u8>>>,
+}
+
+impl<AccountId> From<FileRecordV13<AccountId>> for FileRecord<AccountId> {
+    fn from(record: FileRecordV13<AccountId>) -> Self {
+        Self {
+            summary: record.summary.into(),
+            tree: record.tree,
+        }
+    }
+}
+
+/// [`ExternalFile`] as returned before version 11, with 32-bit file sizes.
+#[derive(Clone, Encode, Decode, Eq, PartialEq)]
+#[cfg_attr(feature = "std", derive(Debug))]
+pub struct ExternalFileV1<AccountId> {
+    pub owner: AccountId,
+    pub size: u32,
+    pub pieces: u32,
+}
+
+impl<AccountId> From<ExternalFileV1<AccountId>> for ExternalFile<AccountId> {
+    fn from(file: ExternalFileV1<AccountId>) -> Self {
+        Self {
+            owner: file.owner,
+            size: file.size.into(),
+            pieces: file.pieces,
+        }
+    }
+}
+
+/// [`PlannedChunk`] as returned before version 11, with 32-bit positions.
+#[derive(Clone, Encode, Decode, Eq, PartialEq)]
+#[cfg_attr(feature = "std", derive(Debug))]
+pub struct PlannedChunkV10 {
+    pub position: u32,
+    pub ipfs_hash: String,
+    pub chunk_hash: [u8; 32],
+    pub proof_len: u32,
+    pub uploaded: bool,
+}
+
+impl From<PlannedChunkV10> for PlannedChunk {
+    fn from(chunk: PlannedChunkV10) -> Self {
+        Self {
+            position: chunk.position.into(),
+            ipfs_hash: chunk.ipfs_hash,
+            chunk_hash: chunk.chunk_hash,
+            proof_len: chunk.proof_len,
+            uploaded: chunk.uploaded,
+        }
+    }
+}
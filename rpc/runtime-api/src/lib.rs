@@ -2,11 +2,111 @@
 
 extern crate alloc;
 use alloc::string::String;
+use codec::Codec;
 use sp_std::vec::Vec;
 
+pub mod legacy;
+
+pub use pallet_trustless_file_server::{
+    verify_proof, AuditReport, BoundedProof, CollectionId, CollectionInfo, DeadLetter,
+    DeadLetterReason, ExternalFile, FileChange, FileInfo, FileRecord, FileSummary, HistoryEntry,
+    License, PieceProof, PlannedChunk, QueuedUpload, ReputationRecord, ServedContent, Tombstone,
+    UploadStatus, UploadTime, UsageRecord, DEAD_LETTERS_KEY, MAX_FILES_PER_EXPORT,
+    MAX_FILES_PER_PAGE, ONCHAIN_TX_KEY, QUEUE_KEY, SERVED_KEY, UPLOAD_RETRIES_KEY,
+};
+
 sp_api::decl_runtime_apis! {
-    pub trait TrustlessFileServerApi {
+    /// Version 2 adds `list_files` and `get_piece_proof`, returning in a single call what
+    /// clients of version 1 have to put together out of `get_files`, `get_file_info`, `get_proof`
+    /// and `get_upload_status`, which are kept for them. Version 3 adds `get_account_usage`,
+    /// version 4 `get_tombstone` and version 5 `exists` and `owner`, which answer without
+    /// decoding the merkle tree of the file. Version 6 adds `get_collection`, version 7
+    /// `get_files_paged`, listing at most `MAX_FILES_PER_PAGE` files per call, version 8
+    /// `is_frozen`, version 9 `export_state`, exporting at most `MAX_FILES_PER_EXPORT` files
+    /// per call, and version 10 `get_download_plan`.
+    ///
+    /// Version 11 makes file sizes and piece positions 64 bits, version 12 adds the retrieval
+    /// price to `get_file_info`, version 13 the upload time to the files listed by `list_files`,
+    /// `get_files_paged` and `export_state` and version 14 the whole-file CID to all of them.
+    /// The methods of earlier versions are kept for clients of older runtimes, returning the
+    /// types in [`legacy`].
+    #[api_version(14)]
+    pub trait TrustlessFileServerApi<AccountId> where AccountId: Codec {
         fn get_files() -> Vec<(Vec<u8>, u32)>;
-        fn get_proof(merkle_root: Vec<u8>, position: u32) -> Option<(String, Vec<Vec<u8>>)>;
+        #[changed_in(11)]
+        fn get_file_info(merkle_root: Vec<u8>) -> Option<legacy::FileInfoV1<AccountId>>;
+        #[changed_in(12)]
+        fn get_file_info(merkle_root: Vec<u8>) -> Option<legacy::FileInfoV11<AccountId>>;
+        #[changed_in(14)]
+        fn get_file_info(merkle_root: Vec<u8>) -> Option<legacy::FileInfoV12<AccountId>>;
+        fn get_file_info(merkle_root: Vec<u8>) -> Option<FileInfo<AccountId>>;
+        #[changed_in(11)]
+        fn get_proof(merkle_root: Vec<u8>, position: u32) -> Option<(String, BoundedProof)>;
+        fn get_proof(merkle_root: Vec<u8>, position: u64) -> Option<(String, BoundedProof)>;
+        fn get_merkle_tree(merkle_root: Vec<u8>) -> Option<Vec<Vec<u8>>>;
+        fn resolve_alias(name: Vec<u8>) -> Option<Vec<u8>>;
+        fn get_upload_status(merkle_root: Vec<u8>) -> Option<UploadStatus>;
+        fn prove_membership(bundle_root: Vec<u8>, file_root: Vec<u8>) -> Option<(u32, BoundedProof)>;
+        fn audit_file(merkle_root: Vec<u8>) -> Option<AuditReport>;
+        fn get_reputation(who: AccountId) -> ReputationRecord;
+        fn get_file_history(merkle_root: Vec<u8>) -> Vec<HistoryEntry<AccountId, u32>>;
+        #[changed_in(11)]
+        fn get_external_files(merkle_root: Vec<u8>) -> Vec<(u32, legacy::ExternalFileV1<AccountId>)>;
+        fn get_external_files(merkle_root: Vec<u8>) -> Vec<(u32, ExternalFile<AccountId>)>;
+        #[api_version(2)]
+        #[changed_in(11)]
+        fn list_files() -> Vec<legacy::FileSummaryV2<AccountId>>;
+        #[api_version(2)]
+        #[changed_in(13)]
+        fn list_files() -> Vec<legacy::FileSummaryV11<AccountId>>;
+        #[api_version(2)]
+        #[changed_in(14)]
+        fn list_files() -> Vec<legacy::FileSummaryV13<AccountId>>;
+        #[api_version(2)]
+        fn list_files() -> Vec<FileSummary<AccountId>>;
+        #[api_version(2)]
+        #[changed_in(11)]
+        fn get_piece_proof(merkle_root: Vec<u8>, position: u32) -> Option<PieceProof>;
+        #[api_version(2)]
+        fn get_piece_proof(merkle_root: Vec<u8>, position: u64) -> Option<PieceProof>;
+        #[api_version(3)]
+        fn get_account_usage(who: AccountId) -> UsageRecord<u128>;
+        #[api_version(4)]
+        fn get_tombstone(merkle_root: Vec<u8>) -> Option<Tombstone<u32>>;
+        #[api_version(5)]
+        fn exists(merkle_root: Vec<u8>) -> bool;
+        #[api_version(5)]
+        fn owner(merkle_root: Vec<u8>) -> Option<AccountId>;
+        #[api_version(6)]
+        fn get_collection(collection_id: CollectionId) -> Option<CollectionInfo<AccountId>>;
+        #[api_version(7)]
+        #[changed_in(11)]
+        fn get_files_paged(start: Option<Vec<u8>>, limit: u32) -> Vec<legacy::FileSummaryV2<AccountId>>;
+        #[api_version(7)]
+        #[changed_in(13)]
+        fn get_files_paged(start: Option<Vec<u8>>, limit: u32) -> Vec<legacy::FileSummaryV11<AccountId>>;
+        #[api_version(7)]
+        #[changed_in(14)]
+        fn get_files_paged(start: Option<Vec<u8>>, limit: u32) -> Vec<legacy::FileSummaryV13<AccountId>>;
+        #[api_version(7)]
+        fn get_files_paged(start: Option<Vec<u8>>, limit: u32) -> Vec<FileSummary<AccountId>>;
+        #[api_version(8)]
+        fn is_frozen(merkle_root: Vec<u8>) -> bool;
+        #[api_version(9)]
+        #[changed_in(11)]
+        fn export_state(start: Option<Vec<u8>>, limit: u32) -> Vec<legacy::FileRecordV9<AccountId>>;
+        #[api_version(9)]
+        #[changed_in(13)]
+        fn export_state(start: Option<Vec<u8>>, limit: u32) -> Vec<legacy::FileRecordV11<AccountId>>;
+        #[api_version(9)]
+        #[changed_in(14)]
+        fn export_state(start: Option<Vec<u8>>, limit: u32) -> Vec<legacy::FileRecordV13<AccountId>>;
+        #[api_version(9)]
+        fn export_state(start: Option<Vec<u8>>, limit: u32) -> Vec<FileRecord<AccountId>>;
+        #[api_version(10)]
+        #[changed_in(11)]
+        fn get_download_plan(merkle_root: Vec<u8>) -> Option<Vec<legacy::PlannedChunkV10>>;
+        #[api_version(10)]
+        fn get_download_plan(merkle_root: Vec<u8>) -> Option<Vec<PlannedChunk>>;
     }
 }
@@ -0,0 +1,135 @@
+//! Client side of the RPC methods that checks the content served by a node against the merkle
+//! root of its file, so that consumers do not have to trust the node they query.
+use std::fmt;
+use std::marker::PhantomData;
+
+use base64::Engine;
+use jsonrpsee::core::Error as JsonRpseeError;
+use pallet_trustless_file_server_runtime_api::verify_proof;
+use sp_core::hashing::sha2_256;
+
+use crate::{EncodedProof, ProofFormat, TrustlessFileServerApiClient};
+
+/// Why a chunk could not be handed over as verified.
+#[derive(Debug)]
+pub enum VerificationError {
+    /// The node could not be queried, or it did not serve the file or the chunk.
+    Rpc(JsonRpseeError),
+    /// The node served a merkle root, proof or chunk that could not be decoded.
+    InvalidEncoding(String),
+    /// The chunk served by the node does not lead to the merkle root along with its proof.
+    ProofMismatch { merkle_root: String, position: u64 },
+}
+
+impl fmt::Display for VerificationError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Rpc(err) => write!(f, "RPC error: {}", err),
+            Self::InvalidEncoding(what) => write!(f, "Invalid encoding: {}", what),
+            Self::ProofMismatch {
+                merkle_root,
+                position,
+            } => write!(f, "Piece {} does not match the merkle root {}", position, merkle_root),
+        }
+    }
+}
+
+impl std::error::Error for VerificationError {}
+
+impl From<JsonRpseeError> for VerificationError {
+    fn from(err: JsonRpseeError) -> Self {
+        Self::Rpc(err)
+    }
+}
+
+/// Wraps a client of the RPC methods, e.g. a `jsonrpsee` WebSocket or HTTP client, so that
+/// chunks are only handed over once verified against the merkle root they were requested by.
+///
+/// Every other method is available as is through [`VerifyingClient::inner`].
+pub struct VerifyingClient<C, BlockHash> {
+    inner: C,
+    _phantom: PhantomData<BlockHash>,
+}
+
+impl<C, BlockHash> VerifyingClient<C, BlockHash>
+where
+    C: TrustlessFileServerApiClient<BlockHash> + Sync,
+    BlockHash: serde::Serialize + Send + Sync + 'static,
+{
+    pub fn new(inner: C) -> Self {
+        Self {
+            inner,
+            _phantom: PhantomData,
+        }
+    }
+
+    /// The wrapped client.
+    pub fn inner(&self) -> &C {
+        &self.inner
+    }
+
+    /// Fetches the chunk at `position` of the file registered under the hex encoded
+    /// `merkle_root` along with its proof, returning its content only if it leads to
+    /// `merkle_root`.
+    pub async fn get_verified_chunk(
+        &self,
+        merkle_root: &str,
+        position: u64,
+    ) -> Result<Vec<u8>, VerificationError> {
+        let root = hex_decode(merkle_root, "merkle root")?;
+        let info = self
+            .inner
+            .get_file_info(None, merkle_root.to_string())
+            .await?;
+        let content = self
+            .inner
+            .download_base64(None, merkle_root.to_string(), Some(position))
+            .await?;
+        let chunk = base64::engine::general_purpose::STANDARD
+            .decode(content.content)
+            .map_err(|_| VerificationError::InvalidEncoding("chunk".into()))?;
+        let proof = self
+            .inner
+            .get_proof(None, merkle_root.to_string(), position, Some(ProofFormat::Hex))
+            .await?;
+        let EncodedProof::List(siblings) = proof.proof else {
+            return Err(VerificationError::InvalidEncoding("proof".into()));
+        };
+        let siblings = siblings
+            .iter()
+            .map(|sibling| hex_decode(sibling, "proof"))
+            .collect::<Result<Vec<_>, _>>()?;
+
+        // BEP-52 trees hash the last chunk as is
+        let chunk_size = if info.bep52 {
+            0
+        } else {
+            info.chunk_size as usize
+        };
+        if !verify_chunk(&root, position, &chunk, chunk_size, &siblings) {
+            return Err(VerificationError::ProofMismatch {
+                merkle_root: merkle_root.to_string(),
+                position,
+            });
+        }
+        Ok(chunk)
+    }
+}
+
+/// Hashes `chunk` as stored in the tree, i.e. zero-padded up to `chunk_size`, and checks it
+/// against `merkle_root` along with its proof. A `chunk_size` of zero leaves the chunk unpadded.
+pub(crate) fn verify_chunk(
+    merkle_root: &[u8],
+    position: u64,
+    chunk: &[u8],
+    chunk_size: usize,
+    proof: &[Vec<u8>],
+) -> bool {
+    let mut padded = chunk.to_vec();
+    padded.resize(chunk_size.max(chunk.len()), 0);
+    verify_proof(merkle_root, position, &sha2_256(&padded), proof)
+}
+
+fn hex_decode(data: &str, what: &str) -> Result<Vec<u8>, VerificationError> {
+    array_bytes::hex2bytes(data).map_err(|_| VerificationError::InvalidEncoding(what.into()))
+}
@@ -1,107 +1,1855 @@
+use std::num::NonZeroUsize;
 use std::sync::Arc;
 
+use base64::Engine;
+use codec::{Codec, Decode, Encode};
 use jsonrpsee::{
     core::{Error as JsonRpseeError, RpcResult},
     proc_macros::rpc,
     types::error::{CallError, ErrorObject},
 };
-use sp_api::ProvideRuntimeApi;
+use lru::LruCache;
+use parking_lot::Mutex;
+use sp_api::{ApiExt, ProvideRuntimeApi};
 use sp_blockchain::HeaderBackend;
+use sp_core::{
+    hashing::sha2_256,
+    offchain::{OffchainStorage, STORAGE_PREFIX},
+    sr25519, Bytes, Pair,
+};
 use sp_runtime::traits::Block as BlockT;
 
+#[cfg(feature = "std")]
+mod client;
+
+#[cfg(feature = "std")]
+pub use client::{VerificationError, VerifyingClient};
 pub use pallet_trustless_file_server_runtime_api::TrustlessFileServerApi as TrustlessFileServerRuntimeApi;
+use pallet_trustless_file_server_runtime_api::{
+    AuditReport, CollectionInfo, DeadLetter, DeadLetterReason, ExternalFile, FileChange, FileInfo,
+    FileRecord, FileSummary, HistoryEntry, License, PieceProof, PlannedChunk, QueuedUpload,
+    ServedContent, Tombstone, UploadStatus, UploadTime, UsageRecord, DEAD_LETTERS_KEY,
+    MAX_FILES_PER_EXPORT, MAX_FILES_PER_PAGE, ONCHAIN_TX_KEY, QUEUE_KEY, SERVED_KEY,
+    UPLOAD_RETRIES_KEY,
+};
+
+/// Default number of entries kept by each of the RPC caches.
+pub const DEFAULT_CACHE_CAPACITY: usize = 1024;
+
+/// Version of the runtime API adding `list_files` and `get_piece_proof`.
+const STRUCTURED_API_VERSION: u32 = 2;
+
+/// Version of the runtime API adding `get_account_usage`.
+const ACCOUNT_USAGE_API_VERSION: u32 = 3;
+
+/// Version of the runtime API adding `get_tombstone`.
+const TOMBSTONE_API_VERSION: u32 = 4;
+
+/// Version of the runtime API adding `exists` and `owner`.
+const OWNER_INDEX_API_VERSION: u32 = 5;
+
+/// Version of the runtime API adding `get_collection`.
+const COLLECTIONS_API_VERSION: u32 = 6;
+
+/// Version of the runtime API adding `get_files_paged`.
+const PAGED_FILES_API_VERSION: u32 = 7;
+
+/// Version of the runtime API adding `is_frozen`.
+const FROZEN_FILES_API_VERSION: u32 = 8;
+
+/// Version of the runtime API adding `export_state`.
+const STATE_EXPORT_API_VERSION: u32 = 9;
+
+/// Version of the runtime API adding `get_download_plan`.
+const DOWNLOAD_PLAN_API_VERSION: u32 = 10;
+
+/// Version of the runtime API making file sizes and piece positions 64 bits.
+const LARGE_FILES_API_VERSION: u32 = 11;
+
+/// Version of the runtime API adding the retrieval price to `get_file_info`.
+const RETRIEVAL_PRICES_API_VERSION: u32 = 12;
+
+/// Version of the runtime API adding the upload time to the listed files.
+const UPLOAD_TIMES_API_VERSION: u32 = 13;
+
+/// Version of the runtime API adding the whole-file CID to file information and listings.
+const FILE_CIDS_API_VERSION: u32 = 14;
+
+/// Optional features of the runtime API, along with the version introducing them.
+const API_FEATURES: &[(&str, u32)] = &[
+    ("structured_listing", STRUCTURED_API_VERSION),
+    ("structured_proofs", STRUCTURED_API_VERSION),
+    ("account_usage", ACCOUNT_USAGE_API_VERSION),
+    ("tombstones", TOMBSTONE_API_VERSION),
+    ("owner_index", OWNER_INDEX_API_VERSION),
+    ("collections", COLLECTIONS_API_VERSION),
+    ("paged_listing", PAGED_FILES_API_VERSION),
+    ("frozen_files", FROZEN_FILES_API_VERSION),
+    ("state_export", STATE_EXPORT_API_VERSION),
+    ("download_plan", DOWNLOAD_PLAN_API_VERSION),
+    ("large_files", LARGE_FILES_API_VERSION),
+    ("retrieval_prices", RETRIEVAL_PRICES_API_VERSION),
+    ("upload_times", UPLOAD_TIMES_API_VERSION),
+    ("file_cids", FILE_CIDS_API_VERSION),
+];
+
+/// Skips the files listed up to the one with merkle root `start`, included, listing nothing if
+/// it is not there. Runtimes with no paging list files in the same order as those with.
+fn paginate<T>(
+    files: Vec<T>,
+    merkle_root: impl Fn(&T) -> &Vec<u8>,
+    start: Option<&[u8]>,
+) -> impl Iterator<Item = T> {
+    let skip = start.map_or(0, |start| {
+        files
+            .iter()
+            .position(|file| merkle_root(file).as_slice() == start)
+            .map_or(files.len(), |position| position + 1)
+    });
+    files.into_iter().skip(skip)
+}
+
+/// Features supported by a runtime implementing `version` of the runtime API.
+fn api_features(version: u32) -> Vec<String> {
+    API_FEATURES
+        .iter()
+        .filter(|(_, since)| version >= *since)
+        .map(|(feature, _)| feature.to_string())
+        .collect()
+}
+
+/// Whether a file uploaded at `time` falls within `after` and `before`, both excluded, in
+/// milliseconds since the Unix epoch. Files with no upload time only match when there are no
+/// bounds.
+fn uploaded_within(
+    time: Option<&UploadTime<u32>>,
+    after: Option<u64>,
+    before: Option<u64>,
+) -> bool {
+    match (time, after, before) {
+        (_, None, None) => true,
+        (Some(time), after, before) => {
+            after.map_or(true, |after| time.timestamp > after)
+                && before.map_or(true, |before| time.timestamp < before)
+        }
+        (None, _, _) => false,
+    }
+}
+
+/// Sorts `files` by upload time, those with none coming first in ascending order. Files uploaded
+/// at the same time keep their order.
+fn sort_by_upload_time<AccountId>(files: &mut [FileSummary<AccountId>], order: SortOrder) {
+    let key =
+        |file: &FileSummary<AccountId>| file.uploaded_at.map(|time| (time.block, time.timestamp));
+    files.sort_by(|a, b| match order {
+        SortOrder::Ascending => key(a).cmp(&key(b)),
+        SortOrder::Descending => key(b).cmp(&key(a)),
+    });
+}
 
 #[derive(serde::Deserialize, serde::Serialize)]
 pub struct HashItem {
     merkle_root: String,
     pieces: u32,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    owner_display_name: Option<String>,
+    /// Block the file was registered at, if recorded.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    uploaded_block: Option<u32>,
+    /// Time the file was registered at, in milliseconds since the Unix epoch, if recorded.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    uploaded_at: Option<u64>,
+    /// CID the whole file can be fetched by from IPFS, if it is split as `ipfs add` splits files.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    cid: Option<String>,
+}
+
+/// Order of the files listed by `get_files`, by upload time.
+#[derive(serde::Deserialize, serde::Serialize, Clone, Copy, Debug, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum SortOrder {
+    /// Oldest uploads first.
+    Ascending,
+    /// Most recent uploads first.
+    Descending,
+}
+
+#[derive(serde::Deserialize, serde::Serialize)]
+pub struct FileInfoItem {
+    merkle_root: String,
+    owner: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    owner_display_name: Option<String>,
+    size: u64,
+    pieces: u32,
+    chunk_size: u32,
+    bep52: bool,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    sha256: Option<String>,
+    registrants: u32,
+    name: String,
+    mime_type: String,
+    tags: Vec<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    license: Option<LicenseItem>,
+    /// Price of every chunk retrieved through a payment channel, if the owner set one.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    retrieval_price: Option<u128>,
+    /// CID the whole file can be fetched by from IPFS, if it is split as `ipfs add` splits files.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    cid: Option<String>,
+}
+
+#[derive(serde::Deserialize, serde::Serialize, Debug, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum LicenseItem {
+    Spdx(String),
+    Uri(String),
+}
+
+impl From<&License<Vec<u8>>> for LicenseItem {
+    fn from(license: &License<Vec<u8>>) -> Self {
+        match license {
+            License::Spdx(id) => Self::Spdx(String::from_utf8_lossy(id).into_owned()),
+            License::Uri(uri) => Self::Uri(String::from_utf8_lossy(uri).into_owned()),
+        }
+    }
+}
+
+/// Whether `license` is the one requested by a listing filter: either an SPDX identifier, compared
+/// case-insensitively as the SPDX specification mandates, or the exact URI of a custom license.
+fn license_matches(license: &License<Vec<u8>>, filter: &str) -> bool {
+    match license {
+        License::Spdx(id) => id.eq_ignore_ascii_case(filter.as_bytes()),
+        License::Uri(uri) => uri == filter.as_bytes(),
+    }
+}
+
+#[derive(serde::Deserialize, serde::Serialize)]
+pub struct Base64Content {
+    mime_type: String,
+    content: String,
+    /// Set when a single chunk is requested from a node configured with a receipt signer.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    receipt: Option<ChunkReceipt>,
+}
+
+/// Statement signed by a serving node that it served a chunk of a file at a given block, which
+/// resellers of its content can hand over to auditors. Every field is hex encoded.
+#[derive(serde::Deserialize, serde::Serialize, Debug, Clone, PartialEq, Eq)]
+pub struct ChunkReceipt {
+    pub merkle_root: String,
+    pub position: u64,
+    /// SHA-256 hash of the chunk as served, i.e. without the padding hashed in the merkle tree.
+    pub chunk_hash: String,
+    /// Hash of the block the chunk was served at.
+    pub block_hash: String,
+    /// sr25519 public key of the serving node.
+    pub signer: String,
+    /// sr25519 signature of `signer` over the SCALE encoded
+    /// `(merkle_root, position, chunk_hash, block_hash)`, the hashes as byte vectors.
+    pub signature: String,
+}
+
+impl ChunkReceipt {
+    fn sign(
+        pair: &sr25519::Pair,
+        merkle_root: &[u8],
+        position: u64,
+        chunk: &[u8],
+        block_hash: &[u8],
+    ) -> Self {
+        let chunk_hash = sha2_256(chunk);
+        let signature = pair.sign(&receipt_payload(merkle_root, position, &chunk_hash, block_hash));
+        Self {
+            merkle_root: vec_to_hex_string(merkle_root),
+            position,
+            chunk_hash: vec_to_hex_string(&chunk_hash),
+            block_hash: vec_to_hex_string(block_hash),
+            signer: vec_to_hex_string(pair.public().as_ref()),
+            signature: vec_to_hex_string(signature.as_ref()),
+        }
+    }
+}
+
+/// Message signed by [`ChunkReceipt::signature`].
+fn receipt_payload(
+    merkle_root: &[u8],
+    position: u64,
+    chunk_hash: &[u8],
+    block_hash: &[u8],
+) -> Vec<u8> {
+    (merkle_root, position, chunk_hash, block_hash).encode()
+}
+
+/// Checks that `receipt` was signed by its `signer`. Whether the signer is a node the caller
+/// trusts, and whether the chunk is part of the file, is up to the caller.
+pub fn verify_receipt(receipt: &ChunkReceipt) -> bool {
+    let decode = |data: &str| array_bytes::hex2bytes(data).ok();
+    let (Some(merkle_root), Some(chunk_hash), Some(block_hash), Some(signer), Some(signature)) = (
+        decode(&receipt.merkle_root),
+        decode(&receipt.chunk_hash),
+        decode(&receipt.block_hash),
+        decode(&receipt.signer),
+        decode(&receipt.signature),
+    ) else {
+        return false;
+    };
+    let (Ok(signer), Ok(signature)) =
+        (sr25519::Public::try_from(&signer[..]), sr25519::Signature::try_from(&signature[..]))
+    else {
+        return false;
+    };
+    let payload = receipt_payload(&merkle_root, receipt.position, &chunk_hash, &block_hash);
+    sr25519::Pair::verify(&signature, payload, &signer)
 }
 
 #[derive(serde::Deserialize, serde::Serialize)]
 pub struct MerkleProof {
     ipfs_hash: String,
-    proof: Vec<String>,
+    proof: EncodedProof,
+    /// Set when the offchain worker has not confirmed the piece to be on IPFS yet, in which case
+    /// fetching it by its `ipfs_hash` might fail.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    warning: Option<String>,
+}
+
+#[derive(serde::Deserialize, serde::Serialize)]
+pub struct MembershipProof {
+    /// Position of the file in the bundle.
+    position: u32,
+    proof: EncodedProof,
+}
+
+/// Warning attached to proofs of pieces not confirmed to be on IPFS.
+const UNCONFIRMED_PIECE_WARNING: &str = "The piece has not been confirmed uploaded to IPFS";
+
+#[derive(serde::Deserialize, serde::Serialize)]
+pub struct UploadStatusItem {
+    pieces: u32,
+    /// Positions of the pieces confirmed to be on IPFS.
+    uploaded: Vec<u64>,
+    complete: bool,
+}
+
+impl From<&UploadStatus> for UploadStatusItem {
+    fn from(status: &UploadStatus) -> Self {
+        Self {
+            pieces: status.pieces,
+            uploaded: (0..status.pieces.into())
+                .filter(|position| status.is_uploaded(*position))
+                .collect(),
+            complete: status.is_complete(),
+        }
+    }
+}
+
+/// Piece of a file to fetch, either from IPFS by its `ipfs_hash` or from the node by its
+/// `position`, and to check against `chunk_hash` and a proof of `proof_len` hashes.
+#[derive(serde::Deserialize, serde::Serialize, Debug, PartialEq, Eq)]
+pub struct PlannedChunkItem {
+    position: u64,
+    ipfs_hash: String,
+    chunk_hash: String,
+    proof_len: u32,
+    uploaded: bool,
+}
+
+impl From<&PlannedChunk> for PlannedChunkItem {
+    fn from(chunk: &PlannedChunk) -> Self {
+        Self {
+            position: chunk.position,
+            ipfs_hash: chunk.ipfs_hash.clone(),
+            chunk_hash: vec_to_hex_string(&chunk.chunk_hash),
+            proof_len: chunk.proof_len,
+            uploaded: chunk.uploaded,
+        }
+    }
+}
+
+#[derive(serde::Deserialize, serde::Serialize, Debug, PartialEq, Eq)]
+pub struct AuditReportItem {
+    pieces: u32,
+    well_formed: bool,
+    /// Positions of the corrupted nodes, in the order returned by `get_merkle_tree`.
+    corrupted_nodes: Vec<u32>,
+    root_matches: bool,
+    intact: bool,
+}
+
+impl From<&AuditReport> for AuditReportItem {
+    fn from(report: &AuditReport) -> Self {
+        Self {
+            pieces: report.pieces,
+            well_formed: report.well_formed,
+            corrupted_nodes: report.corrupted_nodes.clone(),
+            root_matches: report.root_matches,
+            intact: report.is_intact(),
+        }
+    }
+}
+
+/// Change to a file, tagged by its kind.
+#[derive(serde::Deserialize, serde::Serialize, Debug, PartialEq, Eq)]
+#[serde(tag = "change", rename_all = "snake_case")]
+pub enum FileChangeItem {
+    OwnershipTransferred { from: String, to: String },
+    MetadataUpdated { who: String },
+}
+
+#[derive(serde::Deserialize, serde::Serialize, Debug, PartialEq, Eq)]
+pub struct HistoryEntryItem {
+    block: u32,
+    #[serde(flatten)]
+    change: FileChangeItem,
+}
+
+impl<AccountId: std::fmt::Display> From<&HistoryEntry<AccountId, u32>> for HistoryEntryItem {
+    fn from(entry: &HistoryEntry<AccountId, u32>) -> Self {
+        Self {
+            block: entry.block,
+            change: match &entry.change {
+                FileChange::OwnershipTransferred { from, to } => {
+                    FileChangeItem::OwnershipTransferred {
+                        from: from.to_string(),
+                        to: to.to_string(),
+                    }
+                }
+                FileChange::MetadataUpdated { who } => FileChangeItem::MetadataUpdated {
+                    who: who.to_string(),
+                },
+            },
+        }
+    }
+}
+
+/// File registered in a trusted sibling parachain.
+#[derive(serde::Deserialize, serde::Serialize, Debug, PartialEq, Eq)]
+pub struct ExternalFileItem {
+    /// Identifier of the parachain the file is registered in.
+    para_id: u32,
+    owner: String,
+    size: u64,
+    pieces: u32,
+}
+
+impl<AccountId: std::fmt::Display> From<(u32, &ExternalFile<AccountId>)> for ExternalFileItem {
+    fn from((para_id, file): (u32, &ExternalFile<AccountId>)) -> Self {
+        Self {
+            para_id,
+            owner: file.owner.to_string(),
+            size: file.size,
+            pieces: file.pieces,
+        }
+    }
+}
+
+/// Storage an account is a registrant of, and the native deposits it has locked for it.
+#[derive(serde::Deserialize, serde::Serialize, Debug, PartialEq, Eq)]
+pub struct AccountUsageItem {
+    files: u32,
+    bytes: u64,
+    deposit_locked: u128,
+}
+
+impl From<&UsageRecord<u128>> for AccountUsageItem {
+    fn from(usage: &UsageRecord<u128>) -> Self {
+        Self {
+            files: usage.files,
+            bytes: usage.bytes,
+            deposit_locked: usage.deposit_locked,
+        }
+    }
+}
+
+/// Files an owner manages together.
+#[derive(serde::Deserialize, serde::Serialize, Debug, PartialEq, Eq)]
+pub struct CollectionItem {
+    collection_id: u64,
+    owner: String,
+    /// Current merkle roots of the files in the collection.
+    members: Vec<String>,
+}
+
+impl<AccountId: std::fmt::Display> From<(u64, &CollectionInfo<AccountId>)> for CollectionItem {
+    fn from((collection_id, collection): (u64, &CollectionInfo<AccountId>)) -> Self {
+        Self {
+            collection_id,
+            owner: collection.owner.to_string(),
+            members: collection
+                .members
+                .iter()
+                .map(|member| vec_to_hex_string(member))
+                .collect(),
+        }
+    }
+}
+
+/// What is kept of a file taken down by the moderation origin.
+#[derive(serde::Deserialize, serde::Serialize, Debug, PartialEq, Eq)]
+pub struct TombstoneItem {
+    merkle_root: String,
+    removed_at: u32,
+    reason_hash: String,
+    size: u64,
+    chunk_size: u32,
+    piece_hashes: Vec<String>,
+}
+
+impl From<(&[u8], &Tombstone<u32>)> for TombstoneItem {
+    fn from((merkle_root, tombstone): (&[u8], &Tombstone<u32>)) -> Self {
+        Self {
+            merkle_root: vec_to_hex_string(merkle_root),
+            removed_at: tombstone.removed_at,
+            reason_hash: vec_to_hex_string(&tombstone.reason_hash),
+            size: tombstone.size,
+            chunk_size: tombstone.chunk_size,
+            piece_hashes: tombstone
+                .piece_hashes
+                .iter()
+                .map(|hash| vec_to_hex_string(hash))
+                .collect(),
+        }
+    }
+}
+
+#[derive(serde::Deserialize, serde::Serialize, Debug, PartialEq, Eq)]
+pub struct QueuedUploadItem {
+    merkle_root: String,
+    /// First chunk not pushed to IPFS yet.
+    next_chunk: u32,
+    /// Runs in a row the offchain worker failed to push the file in.
+    retries: u32,
+}
+
+#[derive(serde::Deserialize, serde::Serialize, Debug, PartialEq, Eq)]
+pub struct DeadLetterItem {
+    merkle_root: String,
+    next_chunk: u32,
+    retries: u32,
+    /// Why the offchain worker gave up on the file: `missing_content` or `too_many_retries`.
+    reason: String,
+}
+
+impl<Hash: AsRef<[u8]>> From<&DeadLetter<Hash>> for DeadLetterItem {
+    fn from(letter: &DeadLetter<Hash>) -> Self {
+        Self {
+            merkle_root: vec_to_hex_string(letter.merkle_root.as_ref()),
+            next_chunk: letter.next_chunk,
+            retries: letter.retries,
+            reason: match letter.reason {
+                DeadLetterReason::MissingContent => "missing_content",
+                DeadLetterReason::TooManyRetries => "too_many_retries",
+            }
+            .to_string(),
+        }
+    }
+}
+
+/// State of the offchain worker of the node pushing uploaded files to IPFS.
+#[derive(serde::Deserialize, serde::Serialize, Debug, PartialEq, Eq)]
+pub struct LocalQueue {
+    pending: Vec<QueuedUploadItem>,
+    dead_letters: Vec<DeadLetterItem>,
+}
+
+/// Content of a file served by this node through the RPC.
+#[derive(serde::Deserialize, serde::Serialize, Debug, PartialEq, Eq)]
+pub struct ServedContentItem {
+    merkle_root: String,
+    bytes: u64,
+    chunks: u64,
+    /// Bytes the offchain worker has not reported on chain yet.
+    unreported_bytes: u64,
+    /// Chunks the offchain worker has not reported on chain yet.
+    unreported_chunks: u64,
+}
+
+impl<Hash: AsRef<[u8]>> From<&ServedContent<Hash>> for ServedContentItem {
+    fn from(served: &ServedContent<Hash>) -> Self {
+        Self {
+            merkle_root: vec_to_hex_string(served.merkle_root.as_ref()),
+            bytes: served.bytes,
+            chunks: served.chunks,
+            unreported_bytes: served.unreported_bytes,
+            unreported_chunks: served.unreported_chunks,
+        }
+    }
+}
+
+/// Page of the registered files exported for archival.
+#[derive(serde::Deserialize, serde::Serialize, Debug, PartialEq, Eq)]
+pub struct StateExportItem {
+    /// Block the files were exported at, to export the following pages at.
+    at: String,
+    /// Hex encoded SCALE encoding of the `FileRecord` of every file.
+    records: Vec<String>,
+    /// Merkle root to export the following page from, or `None` once every file was exported.
+    next: Option<String>,
+}
+
+impl StateExportItem {
+    fn new<AccountId: Encode>(at: &[u8], records: &[FileRecord<AccountId>], limit: u32) -> Self {
+        let next = match records.last() {
+            Some(record) if records.len() >= limit as usize => {
+                Some(vec_to_hex_string(&record.summary.merkle_root))
+            }
+            _ => None,
+        };
+        Self {
+            at: vec_to_hex_string(at),
+            records: records
+                .iter()
+                .map(|record| vec_to_hex_string(&record.encode()))
+                .collect(),
+            next,
+        }
+    }
+}
+
+/// Version of the runtime API implemented at a block, and what the RPC can do with it.
+#[derive(serde::Deserialize, serde::Serialize, Debug, PartialEq, Eq)]
+pub struct ApiVersionItem {
+    version: u32,
+    /// Optional features of the runtime API available in this version.
+    features: Vec<String>,
+    /// Formats `get_proof` and `prove_membership` can encode proofs in.
+    proof_formats: Vec<ProofFormat>,
+}
+
+/// Wire format of the merkle proof siblings returned by `get_proof`.
+#[derive(serde::Deserialize, serde::Serialize, Clone, Copy, Debug, Default, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum ProofFormat {
+    /// A list of hex encoded 32-byte hashes.
+    #[default]
+    Hex,
+    /// All the hashes concatenated in a single hex string.
+    Concatenated,
+    /// The hex encoded SCALE encoding of the list of hashes.
+    Scale,
+}
+
+#[derive(serde::Deserialize, serde::Serialize, Debug, PartialEq, Eq)]
+#[serde(untagged)]
+pub enum EncodedProof {
+    List(Vec<String>),
+    Blob(String),
+}
+
+impl ProofFormat {
+    /// Every supported format.
+    const ALL: [ProofFormat; 3] = [Self::Hex, Self::Concatenated, Self::Scale];
+}
+
+impl EncodedProof {
+    fn new(proof: &[Vec<u8>], format: ProofFormat) -> Self {
+        match format {
+            ProofFormat::Hex => {
+                Self::List(proof.iter().map(|hash| vec_to_hex_string(hash)).collect())
+            }
+            ProofFormat::Concatenated => Self::Blob(vec_to_hex_string(&proof.concat())),
+            ProofFormat::Scale => Self::Blob(vec_to_hex_string(&proof.encode())),
+        }
+    }
 }
 
 #[rpc(client, server)]
 pub trait TrustlessFileServerApi<BlockHash> {
+    /// Lists the registered files, only including those distributed under `license` if given,
+    /// and uploaded after `uploaded_after` and before `uploaded_before`, in milliseconds since
+    /// the Unix epoch. Files are listed by merkle root unless an `order` by upload time is given.
+    /// Listing starts after the merkle root `start`, i.e. the last one of the previous page, and
+    /// returns at most `limit` files.
     #[method(name = "trustless_file_server_get_files")]
-    fn get_files(&self, at: Option<BlockHash>) -> RpcResult<Vec<HashItem>>;
+    fn get_files(
+        &self,
+        at: Option<BlockHash>,
+        license: Option<String>,
+        start: Option<String>,
+        limit: Option<u32>,
+        uploaded_after: Option<u64>,
+        uploaded_before: Option<u64>,
+        order: Option<SortOrder>,
+    ) -> RpcResult<Vec<HashItem>>;
+
+    #[method(name = "trustless_file_server_get_file_info")]
+    fn get_file_info(&self, at: Option<BlockHash>, merkle_root: String) -> RpcResult<FileInfoItem>;
+
+    /// Returns whether a file is registered under a merkle root, or an alias of it, without
+    /// decoding its merkle tree.
+    #[method(name = "trustless_file_server_exists")]
+    fn exists(&self, at: Option<BlockHash>, merkle_root: String) -> RpcResult<bool>;
+
+    /// Returns the owner of a file, or `null` if it is not registered, without decoding its
+    /// merkle tree.
+    #[method(name = "trustless_file_server_owner")]
+    fn owner(&self, at: Option<BlockHash>, merkle_root: String) -> RpcResult<Option<String>>;
 
     #[method(name = "trustless_file_server_get_proof")]
     fn get_proof(
         &self,
         at: Option<BlockHash>,
         merkle_root: String,
-        position: u32,
+        position: u64,
+        format: Option<ProofFormat>,
+    ) -> RpcResult<MerkleProof>;
+
+    /// Returns all the hashes of the merkle tree, level by level, so that clients can build the
+    /// proofs of any piece on their own.
+    #[method(name = "trustless_file_server_get_merkle_tree")]
+    fn get_merkle_tree(&self, at: Option<BlockHash>, merkle_root: String)
+        -> RpcResult<Vec<String>>;
+
+    /// Same as `get_proof`, but returns the SCALE encoded `(ipfs_hash, proof)` tuple.
+    #[method(name = "trustless_file_server_get_proof_raw")]
+    fn get_proof_raw(
+        &self,
+        at: Option<BlockHash>,
+        merkle_root: String,
+        position: u64,
+    ) -> RpcResult<Bytes>;
+
+    /// Returns the whole content of a file, as stored in the offchain database of the node.
+    #[method(name = "trustless_file_server_download_raw")]
+    fn download_raw(&self, at: Option<BlockHash>, merkle_root: String) -> RpcResult<Bytes>;
+
+    /// Returns the base64 encoded content of a file, or of a single chunk if `position` is
+    /// given, along with the MIME type from its metadata.
+    #[method(name = "trustless_file_server_download_base64")]
+    fn download_base64(
+        &self,
+        at: Option<BlockHash>,
+        merkle_root: String,
+        position: Option<u64>,
+    ) -> RpcResult<Base64Content>;
+
+    /// Returns the current merkle root of the file registered under a human readable name.
+    #[method(name = "trustless_file_server_resolve_alias")]
+    fn resolve_alias(&self, at: Option<BlockHash>, name: String) -> RpcResult<String>;
+
+    /// Returns which pieces of a file the offchain worker has confirmed to be on IPFS.
+    #[method(name = "trustless_file_server_get_upload_status")]
+    fn get_upload_status(
+        &self,
+        at: Option<BlockHash>,
+        merkle_root: String,
+    ) -> RpcResult<UploadStatusItem>;
+
+    /// Returns the merkle proof of a file being part of a bundle, which leads to the bundle
+    /// root when hashed along with the file root.
+    #[method(name = "trustless_file_server_prove_membership")]
+    fn prove_membership(
+        &self,
+        at: Option<BlockHash>,
+        bundle_root: String,
+        file_root: String,
+        format: Option<ProofFormat>,
+    ) -> RpcResult<MembershipProof>;
+
+    /// Checks the merkle tree stored for a file for corruption, rebuilding its inner nodes out of
+    /// its leaves, so that operators can make sure its proofs are sound before serving them.
+    #[method(name = "trustless_file_server_audit_file")]
+    fn audit_file(&self, at: Option<BlockHash>, merkle_root: String) -> RpcResult<AuditReportItem>;
+
+    /// Returns the latest ownership transfers and metadata updates of a file, oldest first.
+    #[method(name = "trustless_file_server_get_file_history")]
+    fn get_file_history(
+        &self,
+        at: Option<BlockHash>,
+        merkle_root: String,
+    ) -> RpcResult<Vec<HistoryEntryItem>>;
+
+    /// Returns the sibling parachains that attested a file to be registered there, along with
+    /// what they attested. Only attestations of trusted parachains are recorded.
+    #[method(name = "trustless_file_server_get_external_files")]
+    fn get_external_files(
+        &self,
+        at: Option<BlockHash>,
+        merkle_root: String,
+    ) -> RpcResult<Vec<ExternalFileItem>>;
+
+    /// Returns the files and bytes an account is a registrant of, and the native deposits it
+    /// has locked for registrations, given its hex encoded SCALE encoding.
+    #[method(name = "trustless_file_server_get_account_usage")]
+    fn get_account_usage(
+        &self,
+        at: Option<BlockHash>,
+        account: String,
+    ) -> RpcResult<AccountUsageItem>;
+
+    /// Returns what is kept of a file taken down by the moderation origin: when and why it was
+    /// removed, and the hashes of its pieces, so that proofs of them can still be checked.
+    #[method(name = "trustless_file_server_get_tombstone")]
+    fn get_tombstone(&self, at: Option<BlockHash>, merkle_root: String)
+        -> RpcResult<TombstoneItem>;
+
+    /// Returns the owner of a collection and the current merkle roots of its files.
+    #[method(name = "trustless_file_server_get_collection")]
+    fn get_collection(
+        &self,
+        at: Option<BlockHash>,
+        collection_id: u64,
+    ) -> RpcResult<CollectionItem>;
+
+    /// Returns the files the offchain worker of this node still has to push to IPFS, along with
+    /// how many runs in a row each of them failed in, and the files it gave up on. Read from the
+    /// node's offchain database, so it is only available when serving it and only describes
+    /// this node.
+    #[method(name = "trustless_file_server_local_queue")]
+    fn local_queue(&self) -> RpcResult<LocalQueue>;
+
+    /// Returns the bytes and chunks of every file this node has served through
+    /// `download_raw` and `download_base64`, along with the part its offchain worker has not
+    /// reported on chain yet. Read from the node's offchain database, so it is only available
+    /// when serving it and only describes this node.
+    #[method(name = "trustless_file_server_served_bandwidth")]
+    fn served_bandwidth(&self) -> RpcResult<Vec<ServedContentItem>>;
+
+    /// Exports up to `limit` registered files, and never more than `MAX_FILES_PER_EXPORT`,
+    /// along with their merkle trees, SCALE encoded, for archival services to mirror the
+    /// registry. Files come in a deterministic order, starting after the merkle root `start`, so
+    /// that the whole registry is exported by following `next` at the block returned.
+    #[method(name = "trustless_file_server_export_state")]
+    fn export_state(
+        &self,
+        at: Option<BlockHash>,
+        start: Option<String>,
+        limit: Option<u32>,
+    ) -> RpcResult<StateExportItem>;
+
+    /// Returns every piece of a file, by position, along with its IPFS hash, the hash of its
+    /// content and the length of its merkle proof, so that download managers can fetch pieces in
+    /// parallel from IPFS gateways and the node and verify them as they arrive.
+    #[method(name = "trustless_file_server_get_download_plan")]
+    fn get_download_plan(
+        &self,
+        at: Option<BlockHash>,
+        merkle_root: String,
+    ) -> RpcResult<Vec<PlannedChunkItem>>;
+
+    /// Returns the version of the runtime API at the given block along with the optional
+    /// features it supports, so that clients can find out what to expect from the node.
+    #[method(name = "trustless_file_server_api_version")]
+    fn api_version(&self, at: Option<BlockHash>) -> RpcResult<ApiVersionItem>;
+
+    /// Same as `get_file_info`, but as of the canonical block at `height`, so that auditors can
+    /// check what was registered back then. Requires an archive node for blocks whose state
+    /// has been pruned.
+    #[method(name = "trustless_file_server_get_file_info_at_height")]
+    fn get_file_info_at_height(&self, height: u32, merkle_root: String) -> RpcResult<FileInfoItem>;
+
+    /// Same as `get_proof`, but as of the canonical block at `height`. Requires an archive node
+    /// for blocks whose state has been pruned.
+    #[method(name = "trustless_file_server_get_proof_at_height")]
+    fn get_proof_at_height(
+        &self,
+        height: u32,
+        merkle_root: String,
+        position: u64,
+        format: Option<ProofFormat>,
     ) -> RpcResult<MerkleProof>;
 }
 
-/// A struct that implements the `TrustlessFileServerApi`.
-pub struct TrustlessFileServerPallet<C, Block> {
-    // If you have more generics, no need to TrustlessFileServerPallet<C, M, N, P, ...>
-    // just use a tuple like TrustlessFileServerPallet<C, (M, N, P, ...)>
-    client: Arc<C>,
-    _marker: std::marker::PhantomData<Block>,
-}
+/// Resolves human readable names of accounts, e.g. the display name registered in
+/// `pallet_identity`.
+///
+/// This pallet does not depend on any identity solution. Runtimes that include one can expose
+/// it through their own runtime API, and nodes plug it into the RPC by implementing this trait
+/// and registering it with [`TrustlessFileServerPallet::with_identity_resolver`].
+pub trait IdentityResolver<BlockHash, AccountId>: Send + Sync {
+    /// Returns the display name of `who` at the given block, if it has any.
+    fn display_name(&self, at: BlockHash, who: &AccountId) -> Option<String>;
+}
+
+/// Access to the offchain database, where offchain indexing stores the uploaded files and the
+/// offchain worker keeps its state. The RPC only writes the counters of the content it serves.
+///
+/// [`OffchainStorage`] is not object safe, so this is the erased version kept by the RPC.
+trait ContentStore: Send + Sync {
+    fn get(&self, key: &[u8]) -> Option<Vec<u8>>;
+
+    /// Sets `key` to `new` if it still holds `old`, returning whether it did.
+    fn compare_and_set(&self, key: &[u8], old: Option<&[u8]>, new: &[u8]) -> bool;
+}
+
+impl<S: OffchainStorage> ContentStore for Mutex<S> {
+    fn get(&self, key: &[u8]) -> Option<Vec<u8>> {
+        OffchainStorage::get(&*self.lock(), STORAGE_PREFIX, key)
+    }
+
+    fn compare_and_set(&self, key: &[u8], old: Option<&[u8]>, new: &[u8]) -> bool {
+        self.lock().compare_and_set(STORAGE_PREFIX, key, old, new)
+    }
+}
+
+/// Proofs and file information previously returned by the runtime API.
+struct Cache<BlockHash, AccountId> {
+    /// Files can be removed or registered later on, so proofs are only reused for the same block,
+    /// along with whether the piece had been uploaded by then.
+    proofs: LruCache<(BlockHash, Vec<u8>, u64), (String, Vec<Vec<u8>>, bool)>,
+    /// Metadata can be updated, so file information is only reused for the same block.
+    file_infos: LruCache<(BlockHash, Vec<u8>), FileInfo<AccountId>>,
+}
+
+impl<BlockHash: std::hash::Hash + Eq, AccountId> Cache<BlockHash, AccountId> {
+    fn new(capacity: NonZeroUsize) -> Self {
+        Self {
+            proofs: LruCache::new(capacity),
+            file_infos: LruCache::new(capacity),
+        }
+    }
+}
+
+/// A struct that implements the `TrustlessFileServerApi`.
+pub struct TrustlessFileServerPallet<C, Block: BlockT, AccountId> {
+    // If you have more generics, no need to TrustlessFileServerPallet<C, M, N, P, ...>
+    // just use a tuple like TrustlessFileServerPallet<C, (M, N, P, ...)>
+    client: Arc<C>,
+    identity: Option<Arc<dyn IdentityResolver<Block::Hash, AccountId>>>,
+    offchain: Option<Arc<dyn ContentStore>>,
+    cache: Option<Mutex<Cache<Block::Hash, AccountId>>>,
+    receipt_signer: Option<sr25519::Pair>,
+    _marker: std::marker::PhantomData<Block>,
+}
+
+impl<C, Block: BlockT, AccountId> TrustlessFileServerPallet<C, Block, AccountId> {
+    /// Create new `TrustlessFileServerPallet` instance with the given reference to the client.
+    ///
+    /// Runtime API results are cached with [`DEFAULT_CACHE_CAPACITY`] entries.
+    pub fn new(client: Arc<C>) -> Self {
+        Self {
+            client,
+            identity: None,
+            offchain: None,
+            cache: NonZeroUsize::new(DEFAULT_CACHE_CAPACITY).map(|c| Mutex::new(Cache::new(c))),
+            receipt_signer: None,
+            _marker: Default::default(),
+        }
+    }
+
+    /// Sets the number of entries kept by the runtime API caches. `0` disables caching.
+    pub fn with_cache_capacity(mut self, capacity: usize) -> Self {
+        self.cache = NonZeroUsize::new(capacity).map(|c| Mutex::new(Cache::new(c)));
+        self
+    }
+
+    /// Enriches file listings with the display names returned by `resolver`.
+    pub fn with_identity_resolver(
+        mut self,
+        resolver: Arc<dyn IdentityResolver<Block::Hash, AccountId>>,
+    ) -> Self {
+        self.identity = Some(resolver);
+        self
+    }
+
+    /// Serves file contents from the node's offchain database through `download_raw`, counting
+    /// what is served there for the offchain worker to report. Requires the node to run with
+    /// offchain indexing enabled.
+    pub fn with_offchain_storage<S: OffchainStorage + 'static>(mut self, storage: S) -> Self {
+        self.offchain = Some(Arc::new(Mutex::new(storage)));
+        self
+    }
+
+    /// Signs a [`ChunkReceipt`] with `pair` for every single chunk served through
+    /// `download_base64`, so that downstream CDNs can prove what they served and when.
+    pub fn with_receipt_signer(mut self, pair: sr25519::Pair) -> Self {
+        self.receipt_signer = Some(pair);
+        self
+    }
+
+    fn display_name(&self, at: Block::Hash, who: &AccountId) -> Option<String> {
+        self.identity.as_ref()?.display_name(at, who)
+    }
+
+    /// Reads the SCALE encoded value under `key` from the offchain database.
+    fn offchain_value<V: Decode>(&self, key: &[u8]) -> RpcResult<Option<V>> {
+        let storage = self
+            .offchain
+            .as_ref()
+            .ok_or_else(|| runtime_error_into_rpc_err("Offchain storage not available"))?;
+        storage
+            .get(key)
+            .map(|value| V::decode(&mut &value[..]).map_err(runtime_error_into_rpc_err))
+            .transpose()
+    }
+
+    /// Adds content of the file registered under `merkle_root` to the counters under
+    /// [`SERVED_KEY`]. Serving does not depend on counting, so failures are ignored.
+    fn note_served(&self, merkle_root: &[u8], bytes: u64, chunks: u64) {
+        let (Some(storage), Ok(merkle_root)) =
+            (&self.offchain, Block::Hash::decode(&mut &merkle_root[..]))
+        else {
+            return;
+        };
+        // the offchain worker updates the counters too
+        loop {
+            let old = storage.get(SERVED_KEY);
+            let mut served: Vec<ServedContent<Block::Hash>> = old
+                .as_ref()
+                .and_then(|value| Decode::decode(&mut &value[..]).ok())
+                .unwrap_or_default();
+            ServedContent::record(&mut served, merkle_root, bytes, chunks);
+            if storage.compare_and_set(SERVED_KEY, old.as_deref(), &served.encode()) {
+                return;
+            }
+        }
+    }
+}
+
+impl<C, Block, AccountId> TrustlessFileServerPallet<C, Block, AccountId>
+where
+    Block: BlockT,
+    AccountId: Codec + Clone,
+    C: ProvideRuntimeApi<Block> + HeaderBackend<Block>,
+    C::Api: TrustlessFileServerRuntimeApi<Block, AccountId>,
+{
+    /// Block to query: the best one unless `at` is given. Blocks other than the best one might be
+    /// unknown to the node, or have their state pruned unless it runs as an archive node, which
+    /// is reported as such rather than as a runtime error.
+    fn resolve_at(&self, at: Option<Block::Hash>) -> RpcResult<Block::Hash> {
+        let best_hash = self.client.info().best_hash;
+        let at = match at {
+            Some(at) if at != best_hash => at,
+            _ => return Ok(best_hash),
+        };
+        if self.client.number(at).ok().flatten().is_none() {
+            return Err(unknown_block_error(at));
+        }
+        // the runtime version is read from the state of the block
+        self.client
+            .runtime_api()
+            .api_version::<dyn TrustlessFileServerRuntimeApi<Block, AccountId>>(at)
+            .map_err(|_| state_unavailable_error(at))?;
+        Ok(at)
+    }
+
+    /// Hash of the canonical block at `height`.
+    fn block_hash(&self, height: u32) -> RpcResult<Block::Hash> {
+        self.client
+            .hash(height.into())
+            .ok()
+            .flatten()
+            .ok_or_else(|| unknown_block_error(height))
+    }
+
+    /// Version of the runtime API implemented at `at`.
+    fn runtime_api_version(&self, at: Block::Hash) -> RpcResult<u32> {
+        self.client
+            .runtime_api()
+            .api_version::<dyn TrustlessFileServerRuntimeApi<Block, AccountId>>(at)
+            .map_err(runtime_error_into_rpc_err)?
+            .ok_or_else(|| runtime_error_into_rpc_err("Runtime API not implemented"))
+    }
+
+    /// Fails if the owner of the file has frozen it, so that neither its proofs nor its content
+    /// are served. Runtimes with no freezing serve every file.
+    fn ensure_not_frozen(&self, at: Block::Hash, merkle_root: &[u8]) -> RpcResult<()> {
+        if self.runtime_api_version(at)? < FROZEN_FILES_API_VERSION {
+            return Ok(());
+        }
+        let frozen = self
+            .client
+            .runtime_api()
+            .is_frozen(at, merkle_root.to_vec())
+            .map_err(runtime_error_into_rpc_err)?;
+        if frozen {
+            return Err(runtime_error_into_rpc_err("File is frozen by its owner"));
+        }
+        Ok(())
+    }
+
+    #[allow(deprecated)]
+    fn file_info(
+        &self,
+        at: Block::Hash,
+        merkle_root: Vec<u8>,
+    ) -> RpcResult<Option<FileInfo<AccountId>>> {
+        let key = (at, merkle_root);
+        if let Some(info) = self
+            .cache
+            .as_ref()
+            .and_then(|cache| cache.lock().file_infos.get(&key).cloned())
+        {
+            return Ok(Some(info));
+        }
+        let api = self.client.runtime_api();
+        let version = self.runtime_api_version(at)?;
+        let info = if version < LARGE_FILES_API_VERSION {
+            api.get_file_info_before_version_11(at, key.1.clone())
+                .map(|info| info.map(Into::into))
+        } else if version < RETRIEVAL_PRICES_API_VERSION {
+            api.get_file_info_before_version_12(at, key.1.clone())
+                .map(|info| info.map(Into::into))
+        } else if version < FILE_CIDS_API_VERSION {
+            api.get_file_info_before_version_14(at, key.1.clone())
+                .map(|info| info.map(Into::into))
+        } else {
+            api.get_file_info(at, key.1.clone())
+        }
+        .map_err(runtime_error_into_rpc_err)?;
+        if let (Some(cache), Some(info)) = (&self.cache, &info) {
+            cache.lock().file_infos.put(key, info.clone());
+        }
+        Ok(info)
+    }
+
+    /// Reads the file content and chunk size indexed at upload time from the offchain database.
+    fn file_content(&self, merkle_root: &[u8]) -> RpcResult<(Vec<u8>, u32)> {
+        let key = [ONCHAIN_TX_KEY, b"/", merkle_root].concat();
+        // indexed as `(content, chunk_size)`
+        self.offchain_value::<(Vec<u8>, u32)>(&key)?
+            .ok_or_else(|| runtime_error_into_rpc_err("File content not available"))
+    }
+
+    /// Merkle proof of the piece at `position`, along with whether it has been uploaded to IPFS.
+    #[allow(deprecated)]
+    fn proof(
+        &self,
+        at: Block::Hash,
+        merkle_root: Vec<u8>,
+        position: u64,
+    ) -> RpcResult<Option<(String, Vec<Vec<u8>>, bool)>> {
+        // checked before the cache, which outlives the freezing of the file
+        self.ensure_not_frozen(at, &merkle_root)?;
+        let key = (at, merkle_root, position);
+        if let Some(proof) = self
+            .cache
+            .as_ref()
+            .and_then(|cache| cache.lock().proofs.get(&key).cloned())
+        {
+            return Ok(Some(proof));
+        }
+        let api = self.client.runtime_api();
+        let version = self.runtime_api_version(at)?;
+        let proof = if version >= LARGE_FILES_API_VERSION {
+            api.get_piece_proof(at, key.1.clone(), position)
+                .map_err(runtime_error_into_rpc_err)?
+        } else {
+            // older runtimes have no pieces beyond 32-bit positions
+            let Ok(legacy_position) = u32::try_from(position) else {
+                return Ok(None);
+            };
+            if version >= STRUCTURED_API_VERSION {
+                api.get_piece_proof_before_version_11(at, key.1.clone(), legacy_position)
+                    .map_err(runtime_error_into_rpc_err)?
+            } else {
+                // the upload status only comes along with the proof since version 2
+                self.legacy_proof(at, &key.1, legacy_position)?
+            }
+        }
+        .map(
+            |PieceProof {
+                 ipfs_hash,
+                 proof,
+                 uploaded,
+             }| {
+                (ipfs_hash, proof.into_iter().map(|hash| hash.to_vec()).collect(), uploaded)
+            },
+        );
+        // missing proofs are not cached, as the file might be uploaded later on
+        if let (Some(cache), Some(proof)) = (&self.cache, &proof) {
+            cache.lock().proofs.put(key, proof.clone());
+        }
+        Ok(proof)
+    }
+
+    /// Merkle proof of a piece, as returned by runtimes implementing version 1 of the runtime
+    /// API, along with its upload status.
+    #[allow(deprecated)]
+    fn legacy_proof(
+        &self,
+        at: Block::Hash,
+        merkle_root: &[u8],
+        position: u32,
+    ) -> RpcResult<Option<PieceProof>> {
+        let api = self.client.runtime_api();
+        let Some((ipfs_hash, proof)) = api
+            .get_proof_before_version_11(at, merkle_root.to_vec(), position)
+            .map_err(runtime_error_into_rpc_err)?
+        else {
+            return Ok(None);
+        };
+        let uploaded = api
+            .get_upload_status(at, merkle_root.to_vec())
+            .map_err(runtime_error_into_rpc_err)?
+            .map_or(false, |status| status.is_uploaded(position.into()));
+        Ok(Some(PieceProof {
+            ipfs_hash,
+            proof,
+            uploaded,
+        }))
+    }
+
+    /// Every registered file, as listed by runtimes implementing version 2 of the runtime API.
+    #[allow(deprecated)]
+    fn list_files(&self, at: Block::Hash, version: u32) -> RpcResult<Vec<FileSummary<AccountId>>> {
+        let api = self.client.runtime_api();
+        if version < LARGE_FILES_API_VERSION {
+            let files = api
+                .list_files_before_version_11(at)
+                .map_err(runtime_error_into_rpc_err)?;
+            return Ok(files.into_iter().map(Into::into).collect());
+        }
+        if version < UPLOAD_TIMES_API_VERSION {
+            let files = api
+                .list_files_before_version_13(at)
+                .map_err(runtime_error_into_rpc_err)?;
+            return Ok(files.into_iter().map(Into::into).collect());
+        }
+        if version < FILE_CIDS_API_VERSION {
+            let files = api
+                .list_files_before_version_14(at)
+                .map_err(runtime_error_into_rpc_err)?;
+            return Ok(files.into_iter().map(Into::into).collect());
+        }
+        api.list_files(at).map_err(runtime_error_into_rpc_err)
+    }
 
-impl<C, Block> TrustlessFileServerPallet<C, Block> {
-    /// Create new `TrustlessFileServerPallet` instance with the given reference to the client.
-    pub fn new(client: Arc<C>) -> Self {
-        Self {
-            client,
-            _marker: Default::default(),
+    /// Page of at most [`MAX_FILES_PER_PAGE`] files listed after `start`, as listed by runtimes
+    /// implementing version 7 of the runtime API.
+    #[allow(deprecated)]
+    fn files_page(
+        &self,
+        at: Block::Hash,
+        version: u32,
+        start: Option<Vec<u8>>,
+    ) -> RpcResult<Vec<FileSummary<AccountId>>> {
+        let api = self.client.runtime_api();
+        if version < LARGE_FILES_API_VERSION {
+            let page = api
+                .get_files_paged_before_version_11(at, start, MAX_FILES_PER_PAGE)
+                .map_err(runtime_error_into_rpc_err)?;
+            return Ok(page.into_iter().map(Into::into).collect());
+        }
+        if version < UPLOAD_TIMES_API_VERSION {
+            let page = api
+                .get_files_paged_before_version_13(at, start, MAX_FILES_PER_PAGE)
+                .map_err(runtime_error_into_rpc_err)?;
+            return Ok(page.into_iter().map(Into::into).collect());
+        }
+        if version < FILE_CIDS_API_VERSION {
+            let page = api
+                .get_files_paged_before_version_14(at, start, MAX_FILES_PER_PAGE)
+                .map_err(runtime_error_into_rpc_err)?;
+            return Ok(page.into_iter().map(Into::into).collect());
         }
+        api.get_files_paged(at, start, MAX_FILES_PER_PAGE)
+            .map_err(runtime_error_into_rpc_err)
     }
 }
 
-impl<C, Block> TrustlessFileServerApiServer<<Block as BlockT>::Hash>
-    for TrustlessFileServerPallet<C, Block>
+impl<C, Block, AccountId> TrustlessFileServerApiServer<<Block as BlockT>::Hash>
+    for TrustlessFileServerPallet<C, Block, AccountId>
 where
     Block: BlockT,
+    AccountId: Codec + Clone + std::fmt::Display + Send + Sync + 'static,
     C: ProvideRuntimeApi<Block> + HeaderBackend<Block> + Send + Sync + 'static,
-    C::Api: TrustlessFileServerRuntimeApi<Block>,
+    C::Api: TrustlessFileServerRuntimeApi<Block, AccountId>,
 {
-    fn get_files(&self, at: Option<<Block as BlockT>::Hash>) -> RpcResult<Vec<HashItem>> {
+    fn get_files(
+        &self,
+        at: Option<<Block as BlockT>::Hash>,
+        license: Option<String>,
+        start: Option<String>,
+        limit: Option<u32>,
+        uploaded_after: Option<u64>,
+        uploaded_before: Option<u64>,
+        order: Option<SortOrder>,
+    ) -> RpcResult<Vec<HashItem>> {
         let api = self.client.runtime_api();
-        let at = at.unwrap_or_else(|| self.client.info().best_hash);
+        let at = self.resolve_at(at)?;
+        let start = start
+            .map(|start| array_bytes::hex2bytes(start).map_err(runtime_error_into_rpc_err))
+            .transpose()?;
+        let limit = limit.map_or(usize::MAX, |limit| limit as usize);
+        let version = self.runtime_api_version(at)?;
+        let filter = |file: &FileSummary<AccountId>| {
+            let licensed = match (&license, &file.license) {
+                (None, _) => true,
+                (Some(filter), Some(license)) => license_matches(license, filter),
+                (Some(_), None) => false,
+            };
+            licensed && uploaded_within(file.uploaded_at.as_ref(), uploaded_after, uploaded_before)
+        };
+        let to_item = |file: FileSummary<AccountId>| HashItem {
+            merkle_root: vec_to_hex_string(&file.merkle_root),
+            pieces: file.pieces,
+            owner_display_name: self.display_name(at, &file.owner),
+            uploaded_block: file.uploaded_at.map(|time| time.block),
+            uploaded_at: file.uploaded_at.map(|time| time.timestamp),
+            cid: file
+                .cid
+                .map(|cid| String::from_utf8_lossy(&cid).into_owned()),
+        };
+
+        let by_upload_time =
+            order.is_some() || uploaded_after.is_some() || uploaded_before.is_some();
+        if by_upload_time && version < UPLOAD_TIMES_API_VERSION {
+            return Err(runtime_error_into_rpc_err(
+                "Upload times are not supported by the runtime",
+            ));
+        }
+
+        if let Some(order) = order {
+            // sorting needs every matching file, so pages are only cut out of the sorted list
+            let mut files = if version >= PAGED_FILES_API_VERSION {
+                let mut start = None;
+                let mut files = Vec::new();
+                loop {
+                    let page = self.files_page(at, version, start)?;
+                    let exhausted = page.len() < MAX_FILES_PER_PAGE as usize;
+                    start = page.last().map(|file| file.merkle_root.clone());
+                    files.extend(page.into_iter().filter(filter));
+                    if exhausted {
+                        break;
+                    }
+                }
+                files
+            } else {
+                let files = self.list_files(at, version)?;
+                files.into_iter().filter(filter).collect()
+            };
+            sort_by_upload_time(&mut files, order);
+            let files = paginate(files, |file| &file.merkle_root, start.as_deref());
+            return Ok(files.map(to_item).take(limit).collect());
+        }
+
+        if version >= PAGED_FILES_API_VERSION {
+            // the runtime lists a bounded page per call, so keep asking until `limit` is reached
+            let mut start = start;
+            let mut hashes = Vec::new();
+            while hashes.len() < limit {
+                let page = self.files_page(at, version, start)?;
+                let exhausted = page.len() < MAX_FILES_PER_PAGE as usize;
+                start = page.last().map(|file| file.merkle_root.clone());
+                let missing = limit - hashes.len();
+                hashes.extend(page.into_iter().filter(filter).map(to_item).take(missing));
+                if exhausted {
+                    break;
+                }
+            }
+            return Ok(hashes);
+        }
+
+        if version >= STRUCTURED_API_VERSION {
+            let files = self.list_files(at, version)?;
+            let files = paginate(files, |file| &file.merkle_root, start.as_deref());
+            return Ok(files.filter(filter).map(to_item).take(limit).collect());
+        }
 
         let result = api.get_files(at).map_err(runtime_error_into_rpc_err)?;
-        let hashes = result
-            .into_iter()
-            .map(|item| HashItem {
-                pieces: item.1,
-                merkle_root: vec_to_hex_string(&item.0),
-            })
-            .collect();
+        let mut hashes = Vec::with_capacity(result.len());
+        for (merkle_root, pieces) in paginate(result, |(root, _)| root, start.as_deref()) {
+            if hashes.len() == limit {
+                break;
+            }
+            // only look files up when there is something to filter or resolve
+            let info = match (&license, &self.identity) {
+                (None, None) => None,
+                _ => self.file_info(at, merkle_root.clone())?,
+            };
+            if let Some(filter) = &license {
+                let matches = info
+                    .as_ref()
+                    .and_then(|info| info.license.as_ref())
+                    .map_or(false, |license| license_matches(license, filter));
+                if !matches {
+                    continue;
+                }
+            }
+            let owner_display_name = info.and_then(|info| self.display_name(at, &info.owner));
+            hashes.push(HashItem {
+                pieces,
+                merkle_root: vec_to_hex_string(&merkle_root),
+                owner_display_name,
+                uploaded_block: None,
+                uploaded_at: None,
+                cid: None,
+            });
+        }
         Ok(hashes)
     }
 
+    fn get_file_info(
+        &self,
+        at: Option<<Block as BlockT>::Hash>,
+        merkle_root: String,
+    ) -> RpcResult<FileInfoItem> {
+        let at = self.resolve_at(at)?;
+        let merkle_root_bytes = array_bytes::hex2bytes(merkle_root)
+            .map_err(runtime_error_into_rpc_err)?
+            .to_vec();
+        let info = self
+            .file_info(at, merkle_root_bytes)?
+            .ok_or_else(|| runtime_error_into_rpc_err("File not found"))?;
+        Ok(FileInfoItem {
+            merkle_root: vec_to_hex_string(&info.merkle_root),
+            owner: info.owner.to_string(),
+            owner_display_name: self.display_name(at, &info.owner),
+            size: info.size,
+            pieces: info.pieces,
+            chunk_size: info.chunk_size,
+            bep52: info.bep52,
+            sha256: info.sha256.map(|hash| vec_to_hex_string(&hash)),
+            registrants: info.registrants,
+            name: String::from_utf8_lossy(&info.name).into_owned(),
+            mime_type: String::from_utf8_lossy(&info.mime_type).into_owned(),
+            tags: info
+                .tags
+                .iter()
+                .map(|tag| String::from_utf8_lossy(tag).into_owned())
+                .collect(),
+            license: info.license.as_ref().map(LicenseItem::from),
+            retrieval_price: info.retrieval_price,
+            cid: info
+                .cid
+                .as_ref()
+                .map(|cid| String::from_utf8_lossy(cid).into_owned()),
+        })
+    }
+
+    #[allow(deprecated)]
     fn get_proof(
         &self,
         at: Option<<Block as BlockT>::Hash>,
         merkle_root: String,
-        position: u32,
+        position: u64,
+        format: Option<ProofFormat>,
     ) -> RpcResult<MerkleProof> {
+        let at = self.resolve_at(at)?;
+        let merkle_root_bytes = array_bytes::hex2bytes(merkle_root)
+            .map_err(runtime_error_into_rpc_err)?
+            .to_vec();
+        let (ipfs_hash, proof, uploaded) = self
+            .proof(at, merkle_root_bytes, position)?
+            .ok_or_else(|| runtime_error_into_rpc_err("Failure getting the merkle proof"))?;
+        Ok(MerkleProof {
+            ipfs_hash,
+            proof: EncodedProof::new(&proof, format.unwrap_or_default()),
+            warning: (!uploaded).then(|| UNCONFIRMED_PIECE_WARNING.to_string()),
+        })
+    }
+
+    fn get_merkle_tree(
+        &self,
+        at: Option<<Block as BlockT>::Hash>,
+        merkle_root: String,
+    ) -> RpcResult<Vec<String>> {
+        let api = self.client.runtime_api();
+        let at = self.resolve_at(at)?;
+        let merkle_root_bytes = array_bytes::hex2bytes(merkle_root)
+            .map_err(runtime_error_into_rpc_err)?
+            .to_vec();
+        self.ensure_not_frozen(at, &merkle_root_bytes)?;
+        let nodes = api
+            .get_merkle_tree(at, merkle_root_bytes)
+            .map_err(runtime_error_into_rpc_err)?
+            .ok_or_else(|| runtime_error_into_rpc_err("File not found"))?;
+        Ok(nodes.iter().map(|hash| vec_to_hex_string(hash)).collect())
+    }
+
+    fn get_proof_raw(
+        &self,
+        at: Option<<Block as BlockT>::Hash>,
+        merkle_root: String,
+        position: u64,
+    ) -> RpcResult<Bytes> {
+        let at = self.resolve_at(at)?;
+        let merkle_root_bytes = array_bytes::hex2bytes(merkle_root)
+            .map_err(runtime_error_into_rpc_err)?
+            .to_vec();
+        self.proof(at, merkle_root_bytes, position)?
+            .map(|(ipfs_hash, proof, _)| (ipfs_hash, proof).encode().into())
+            .ok_or_else(|| runtime_error_into_rpc_err("Failure getting the merkle proof"))
+    }
+
+    fn download_raw(
+        &self,
+        at: Option<<Block as BlockT>::Hash>,
+        merkle_root: String,
+    ) -> RpcResult<Bytes> {
+        let at = self.resolve_at(at)?;
+        let merkle_root_bytes = array_bytes::hex2bytes(merkle_root)
+            .map_err(runtime_error_into_rpc_err)?
+            .to_vec();
+        // only serve files that are registered at the given block
+        self.file_info(at, merkle_root_bytes.clone())?
+            .ok_or_else(|| runtime_error_into_rpc_err("File not found"))?;
+        self.ensure_not_frozen(at, &merkle_root_bytes)?;
+        let (content, chunk_size) = self.file_content(&merkle_root_bytes)?;
+        let chunks = content.chunks(chunk_size as usize).count() as u64;
+        self.note_served(&merkle_root_bytes, content.len() as u64, chunks);
+        Ok(content.into())
+    }
+
+    fn download_base64(
+        &self,
+        at: Option<<Block as BlockT>::Hash>,
+        merkle_root: String,
+        position: Option<u64>,
+    ) -> RpcResult<Base64Content> {
+        let at = self.resolve_at(at)?;
+        let merkle_root_bytes = array_bytes::hex2bytes(merkle_root)
+            .map_err(runtime_error_into_rpc_err)?
+            .to_vec();
+        let info = self
+            .file_info(at, merkle_root_bytes.clone())?
+            .ok_or_else(|| runtime_error_into_rpc_err("File not found"))?;
+        self.ensure_not_frozen(at, &merkle_root_bytes)?;
+        let (content, chunk_size) = self.file_content(&merkle_root_bytes)?;
+        let (content, chunks, receipt) = match position {
+            Some(position) => {
+                let chunk = content
+                    .chunks(chunk_size as usize)
+                    .nth(position as usize)
+                    .ok_or_else(|| runtime_error_into_rpc_err("Invalid position"))?;
+                let receipt = self.receipt_signer.as_ref().map(|pair| {
+                    ChunkReceipt::sign(pair, &merkle_root_bytes, position, chunk, at.as_ref())
+                });
+                (chunk, 1, receipt)
+            }
+            None => (&content[..], content.chunks(chunk_size as usize).count() as u64, None),
+        };
+        self.note_served(&merkle_root_bytes, content.len() as u64, chunks);
+        Ok(Base64Content {
+            mime_type: mime_type_or_default(&info.mime_type),
+            content: base64::engine::general_purpose::STANDARD.encode(content),
+            receipt,
+        })
+    }
+
+    fn resolve_alias(
+        &self,
+        at: Option<<Block as BlockT>::Hash>,
+        name: String,
+    ) -> RpcResult<String> {
+        let api = self.client.runtime_api();
+        let at = self.resolve_at(at)?;
+        let merkle_root = api
+            .resolve_alias(at, name.into_bytes())
+            .map_err(runtime_error_into_rpc_err)?
+            .ok_or_else(|| runtime_error_into_rpc_err("Alias not found"))?;
+        Ok(vec_to_hex_string(&merkle_root))
+    }
+
+    fn get_upload_status(
+        &self,
+        at: Option<<Block as BlockT>::Hash>,
+        merkle_root: String,
+    ) -> RpcResult<UploadStatusItem> {
+        let api = self.client.runtime_api();
+        let at = self.resolve_at(at)?;
+        let merkle_root_bytes = array_bytes::hex2bytes(merkle_root)
+            .map_err(runtime_error_into_rpc_err)?
+            .to_vec();
+        let status = api
+            .get_upload_status(at, merkle_root_bytes)
+            .map_err(runtime_error_into_rpc_err)?
+            .ok_or_else(|| runtime_error_into_rpc_err("File not found"))?;
+        Ok(UploadStatusItem::from(&status))
+    }
+
+    fn prove_membership(
+        &self,
+        at: Option<<Block as BlockT>::Hash>,
+        bundle_root: String,
+        file_root: String,
+        format: Option<ProofFormat>,
+    ) -> RpcResult<MembershipProof> {
+        let api = self.client.runtime_api();
+        let at = self.resolve_at(at)?;
+        let bundle_root_bytes = array_bytes::hex2bytes(bundle_root)
+            .map_err(runtime_error_into_rpc_err)?
+            .to_vec();
+        let file_root_bytes = array_bytes::hex2bytes(file_root)
+            .map_err(runtime_error_into_rpc_err)?
+            .to_vec();
+        let (position, proof) = api
+            .prove_membership(at, bundle_root_bytes, file_root_bytes)
+            .map_err(runtime_error_into_rpc_err)?
+            .ok_or_else(|| runtime_error_into_rpc_err("File not found in the bundle"))?;
+        let proof: Vec<Vec<u8>> = proof.into_iter().map(|hash| hash.to_vec()).collect();
+        Ok(MembershipProof {
+            position,
+            proof: EncodedProof::new(&proof, format.unwrap_or_default()),
+        })
+    }
+
+    fn audit_file(
+        &self,
+        at: Option<<Block as BlockT>::Hash>,
+        merkle_root: String,
+    ) -> RpcResult<AuditReportItem> {
+        let api = self.client.runtime_api();
+        let at = self.resolve_at(at)?;
+        let merkle_root_bytes = array_bytes::hex2bytes(merkle_root)
+            .map_err(runtime_error_into_rpc_err)?
+            .to_vec();
+        let report = api
+            .audit_file(at, merkle_root_bytes)
+            .map_err(runtime_error_into_rpc_err)?
+            .ok_or_else(|| runtime_error_into_rpc_err("File not found"))?;
+        Ok(AuditReportItem::from(&report))
+    }
+
+    fn get_file_history(
+        &self,
+        at: Option<<Block as BlockT>::Hash>,
+        merkle_root: String,
+    ) -> RpcResult<Vec<HistoryEntryItem>> {
+        let api = self.client.runtime_api();
+        let at = self.resolve_at(at)?;
+        let merkle_root_bytes = array_bytes::hex2bytes(merkle_root)
+            .map_err(runtime_error_into_rpc_err)?
+            .to_vec();
+        let history = api
+            .get_file_history(at, merkle_root_bytes)
+            .map_err(runtime_error_into_rpc_err)?;
+        Ok(history.iter().map(HistoryEntryItem::from).collect())
+    }
+
+    #[allow(deprecated)]
+    fn get_external_files(
+        &self,
+        at: Option<<Block as BlockT>::Hash>,
+        merkle_root: String,
+    ) -> RpcResult<Vec<ExternalFileItem>> {
         let api = self.client.runtime_api();
-        let at = at.unwrap_or_else(|| self.client.info().best_hash);
+        let at = self.resolve_at(at)?;
         let merkle_root_bytes = array_bytes::hex2bytes(merkle_root)
             .map_err(runtime_error_into_rpc_err)?
             .to_vec();
-        let result = api
-            .get_proof(at, merkle_root_bytes, position)
+        let files = if self.runtime_api_version(at)? < LARGE_FILES_API_VERSION {
+            api.get_external_files_before_version_11(at, merkle_root_bytes)
+                .map(|files| {
+                    files
+                        .into_iter()
+                        .map(|(para_id, file)| (para_id, file.into()))
+                        .collect()
+                })
+        } else {
+            api.get_external_files(at, merkle_root_bytes)
+        }
+        .map_err(runtime_error_into_rpc_err)?;
+        Ok(files
+            .iter()
+            .map(|(para_id, file)| ExternalFileItem::from((*para_id, file)))
+            .collect())
+    }
+
+    fn get_account_usage(
+        &self,
+        at: Option<<Block as BlockT>::Hash>,
+        account: String,
+    ) -> RpcResult<AccountUsageItem> {
+        let at = self.resolve_at(at)?;
+        if self.runtime_api_version(at)? < ACCOUNT_USAGE_API_VERSION {
+            return Err(runtime_error_into_rpc_err(
+                "Account usage is not supported by the runtime",
+            ));
+        }
+        let account_bytes = array_bytes::hex2bytes(account).map_err(runtime_error_into_rpc_err)?;
+        let who =
+            AccountId::decode(&mut account_bytes.as_slice()).map_err(runtime_error_into_rpc_err)?;
+        let usage = self
+            .client
+            .runtime_api()
+            .get_account_usage(at, who)
             .map_err(runtime_error_into_rpc_err)?;
-        match result {
-            Some((ipfs_hash, proof)) => Ok(MerkleProof {
-                ipfs_hash,
-                proof: proof.iter().map(|hash| vec_to_hex_string(hash)).collect(),
-            }),
-            None => Err(runtime_error_into_rpc_err("Failure getting the merkle proof")),
+        Ok(AccountUsageItem::from(&usage))
+    }
+
+    fn exists(&self, at: Option<<Block as BlockT>::Hash>, merkle_root: String) -> RpcResult<bool> {
+        let at = self.resolve_at(at)?;
+        let merkle_root_bytes =
+            array_bytes::hex2bytes(merkle_root).map_err(runtime_error_into_rpc_err)?;
+        if self.runtime_api_version(at)? < OWNER_INDEX_API_VERSION {
+            return Ok(self.file_info(at, merkle_root_bytes)?.is_some());
+        }
+        self.client
+            .runtime_api()
+            .exists(at, merkle_root_bytes)
+            .map_err(runtime_error_into_rpc_err)
+    }
+
+    fn owner(
+        &self,
+        at: Option<<Block as BlockT>::Hash>,
+        merkle_root: String,
+    ) -> RpcResult<Option<String>> {
+        let at = self.resolve_at(at)?;
+        let merkle_root_bytes =
+            array_bytes::hex2bytes(merkle_root).map_err(runtime_error_into_rpc_err)?;
+        let owner = if self.runtime_api_version(at)? < OWNER_INDEX_API_VERSION {
+            self.file_info(at, merkle_root_bytes)?
+                .map(|info| info.owner)
+        } else {
+            self.client
+                .runtime_api()
+                .owner(at, merkle_root_bytes)
+                .map_err(runtime_error_into_rpc_err)?
+        };
+        Ok(owner.map(|owner| owner.to_string()))
+    }
+
+    fn get_tombstone(
+        &self,
+        at: Option<<Block as BlockT>::Hash>,
+        merkle_root: String,
+    ) -> RpcResult<TombstoneItem> {
+        let at = self.resolve_at(at)?;
+        if self.runtime_api_version(at)? < TOMBSTONE_API_VERSION {
+            return Err(runtime_error_into_rpc_err("Tombstones are not supported by the runtime"));
+        }
+        let merkle_root_bytes =
+            array_bytes::hex2bytes(merkle_root).map_err(runtime_error_into_rpc_err)?;
+        let tombstone = self
+            .client
+            .runtime_api()
+            .get_tombstone(at, merkle_root_bytes.clone())
+            .map_err(runtime_error_into_rpc_err)?
+            .ok_or_else(|| runtime_error_into_rpc_err("Tombstone not found"))?;
+        Ok(TombstoneItem::from((merkle_root_bytes.as_slice(), &tombstone)))
+    }
+
+    fn get_collection(
+        &self,
+        at: Option<<Block as BlockT>::Hash>,
+        collection_id: u64,
+    ) -> RpcResult<CollectionItem> {
+        let at = self.resolve_at(at)?;
+        if self.runtime_api_version(at)? < COLLECTIONS_API_VERSION {
+            return Err(runtime_error_into_rpc_err("Collections are not supported by the runtime"));
+        }
+        let collection = self
+            .client
+            .runtime_api()
+            .get_collection(at, collection_id)
+            .map_err(runtime_error_into_rpc_err)?
+            .ok_or_else(|| runtime_error_into_rpc_err("Collection not found"))?;
+        Ok(CollectionItem::from((collection_id, &collection)))
+    }
+
+    fn local_queue(&self) -> RpcResult<LocalQueue> {
+        let queue: Vec<QueuedUpload<Block::Hash>> =
+            self.offchain_value(QUEUE_KEY)?.unwrap_or_default();
+        let mut pending = Vec::with_capacity(queue.len());
+        for item in queue {
+            let key = [UPLOAD_RETRIES_KEY, &item.merkle_root.encode()].concat();
+            pending.push(QueuedUploadItem {
+                merkle_root: vec_to_hex_string(item.merkle_root.as_ref()),
+                next_chunk: item.next_chunk,
+                retries: self.offchain_value(&key)?.unwrap_or_default(),
+            });
+        }
+        let dead_letters: Vec<DeadLetter<Block::Hash>> =
+            self.offchain_value(DEAD_LETTERS_KEY)?.unwrap_or_default();
+        Ok(LocalQueue {
+            pending,
+            dead_letters: dead_letters.iter().map(DeadLetterItem::from).collect(),
+        })
+    }
+
+    fn served_bandwidth(&self) -> RpcResult<Vec<ServedContentItem>> {
+        let served: Vec<ServedContent<Block::Hash>> =
+            self.offchain_value(SERVED_KEY)?.unwrap_or_default();
+        Ok(served.iter().map(ServedContentItem::from).collect())
+    }
+
+    #[allow(deprecated)]
+    fn export_state(
+        &self,
+        at: Option<<Block as BlockT>::Hash>,
+        start: Option<String>,
+        limit: Option<u32>,
+    ) -> RpcResult<StateExportItem> {
+        let at = self.resolve_at(at)?;
+        let version = self.runtime_api_version(at)?;
+        if version < STATE_EXPORT_API_VERSION {
+            return Err(runtime_error_into_rpc_err("State export is not supported by the runtime"));
+        }
+        let start = start
+            .map(|start| array_bytes::hex2bytes(start).map_err(runtime_error_into_rpc_err))
+            .transpose()?;
+        let limit = limit.map_or(MAX_FILES_PER_EXPORT, |limit| limit.min(MAX_FILES_PER_EXPORT));
+        let api = self.client.runtime_api();
+        let records = if version < LARGE_FILES_API_VERSION {
+            api.export_state_before_version_11(at, start, limit)
+                .map(|records| records.into_iter().map(Into::into).collect())
+        } else if version < UPLOAD_TIMES_API_VERSION {
+            api.export_state_before_version_13(at, start, limit)
+                .map(|records| records.into_iter().map(Into::into).collect())
+        } else if version < FILE_CIDS_API_VERSION {
+            api.export_state_before_version_14(at, start, limit)
+                .map(|records| records.into_iter().map(Into::into).collect())
+        } else {
+            api.export_state(at, start, limit)
+        }
+        .map_err(runtime_error_into_rpc_err)?;
+        Ok(StateExportItem::new(at.as_ref(), &records, limit))
+    }
+
+    #[allow(deprecated)]
+    fn get_download_plan(
+        &self,
+        at: Option<<Block as BlockT>::Hash>,
+        merkle_root: String,
+    ) -> RpcResult<Vec<PlannedChunkItem>> {
+        let at = self.resolve_at(at)?;
+        let version = self.runtime_api_version(at)?;
+        if version < DOWNLOAD_PLAN_API_VERSION {
+            return Err(runtime_error_into_rpc_err(
+                "Download plans are not supported by the runtime",
+            ));
+        }
+        let merkle_root_bytes = array_bytes::hex2bytes(merkle_root)
+            .map_err(runtime_error_into_rpc_err)?
+            .to_vec();
+        let api = self.client.runtime_api();
+        let plan = if version < LARGE_FILES_API_VERSION {
+            api.get_download_plan_before_version_11(at, merkle_root_bytes)
+                .map(|plan| plan.map(|plan| plan.into_iter().map(Into::into).collect()))
+        } else {
+            api.get_download_plan(at, merkle_root_bytes)
         }
+        .map_err(runtime_error_into_rpc_err)?
+        .ok_or_else(|| runtime_error_into_rpc_err("File not found"))?;
+        Ok(plan.iter().map(PlannedChunkItem::from).collect())
+    }
+
+    fn api_version(&self, at: Option<<Block as BlockT>::Hash>) -> RpcResult<ApiVersionItem> {
+        let at = self.resolve_at(at)?;
+        let version = self.runtime_api_version(at)?;
+        Ok(ApiVersionItem {
+            version,
+            features: api_features(version),
+            proof_formats: ProofFormat::ALL.to_vec(),
+        })
+    }
+
+    fn get_file_info_at_height(&self, height: u32, merkle_root: String) -> RpcResult<FileInfoItem> {
+        let at = self.block_hash(height)?;
+        self.get_file_info(Some(at), merkle_root)
+    }
+
+    fn get_proof_at_height(
+        &self,
+        height: u32,
+        merkle_root: String,
+        position: u64,
+        format: Option<ProofFormat>,
+    ) -> RpcResult<MerkleProof> {
+        let at = self.block_hash(height)?;
+        self.get_proof(Some(at), merkle_root, position, format)
     }
 }
 
 const RUNTIME_ERROR: i32 = 1;
+/// The block queried is not known to the node.
+const UNKNOWN_BLOCK_ERROR: i32 = 2;
+/// The block queried is known, but its state has been pruned.
+const STATE_UNAVAILABLE_ERROR: i32 = 3;
 
 fn vec_to_hex_string(data: &[u8]) -> String {
     data.iter()
@@ -110,6 +1858,16 @@ fn vec_to_hex_string(data: &[u8]) -> String {
         .join("")
 }
 
+/// MIME type to report for files whose metadata does not include one.
+const DEFAULT_MIME_TYPE: &str = "application/octet-stream";
+
+fn mime_type_or_default(mime_type: &[u8]) -> String {
+    match mime_type {
+        [] => DEFAULT_MIME_TYPE.to_string(),
+        mime_type => String::from_utf8_lossy(mime_type).into_owned(),
+    }
+}
+
 /// Converts a runtime trap into an RPC error.
 fn runtime_error_into_rpc_err(err: impl std::fmt::Debug) -> JsonRpseeError {
     CallError::Custom(ErrorObject::owned(
@@ -119,3 +1877,460 @@ fn runtime_error_into_rpc_err(err: impl std::fmt::Debug) -> JsonRpseeError {
     ))
     .into()
 }
+
+fn unknown_block_error(block: impl std::fmt::Debug) -> JsonRpseeError {
+    CallError::Custom(ErrorObject::owned(
+        UNKNOWN_BLOCK_ERROR,
+        "Unknown block",
+        Some(format!("{:?}", block)),
+    ))
+    .into()
+}
+
+fn state_unavailable_error(block: impl std::fmt::Debug) -> JsonRpseeError {
+    CallError::Custom(ErrorObject::owned(
+        STATE_UNAVAILABLE_ERROR,
+        "State not available",
+        Some(format!("State of block {:?} has been pruned, query an archive node", block)),
+    ))
+    .into()
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_mime_type_or_default() {
+        assert_eq!(mime_type_or_default(b""), "application/octet-stream");
+        assert_eq!(mime_type_or_default(b"image/png"), "image/png");
+    }
+
+    #[test]
+    fn test_license_matches() {
+        let spdx = License::Spdx(b"Apache-2.0".to_vec());
+        assert!(license_matches(&spdx, "apache-2.0"));
+        assert!(!license_matches(&spdx, "MIT"));
+        let uri = License::Uri(b"https://example.com/terms".to_vec());
+        assert!(license_matches(&uri, "https://example.com/terms"));
+        assert!(!license_matches(&uri, "https://example.com/TERMS"));
+    }
+
+    #[test]
+    fn test_dead_letter_item() {
+        let letter = DeadLetter {
+            merkle_root: [0xab; 2],
+            next_chunk: 3,
+            retries: 5,
+            reason: DeadLetterReason::TooManyRetries,
+        };
+        assert_eq!(
+            DeadLetterItem::from(&letter),
+            DeadLetterItem {
+                merkle_root: "abab".to_string(),
+                next_chunk: 3,
+                retries: 5,
+                reason: "too_many_retries".to_string(),
+            }
+        );
+    }
+
+    #[test]
+    fn test_served_content_item() {
+        let mut served = Vec::new();
+        ServedContent::record(&mut served, [0xab; 2], 2048, 2);
+        ServedContent::record(&mut served, [0xcd; 2], 10, 1);
+        ServedContent::record(&mut served, [0xab; 2], 1024, 1);
+        served[0].unreported_bytes = 1024;
+        served[0].unreported_chunks = 1;
+        assert_eq!(
+            served
+                .iter()
+                .map(ServedContentItem::from)
+                .collect::<Vec<_>>(),
+            vec![
+                ServedContentItem {
+                    merkle_root: "abab".to_string(),
+                    bytes: 3072,
+                    chunks: 3,
+                    unreported_bytes: 1024,
+                    unreported_chunks: 1,
+                },
+                ServedContentItem {
+                    merkle_root: "cdcd".to_string(),
+                    bytes: 10,
+                    chunks: 1,
+                    unreported_bytes: 10,
+                    unreported_chunks: 1,
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn test_upload_status_item() {
+        let status = UploadStatus {
+            pieces: 4,
+            uploaded: 0b1011,
+        };
+        let item = UploadStatusItem::from(&status);
+        assert_eq!(item.uploaded, vec![0, 1, 3]);
+        assert!(!item.complete);
+        let status = UploadStatus {
+            pieces: 2,
+            uploaded: 0b11,
+        };
+        assert!(UploadStatusItem::from(&status).complete);
+    }
+
+    #[test]
+    fn test_audit_report_item() {
+        let report = AuditReport {
+            pieces: 12,
+            well_formed: true,
+            corrupted_nodes: vec![16],
+            root_matches: false,
+        };
+        let item = AuditReportItem::from(&report);
+        assert_eq!(item.corrupted_nodes, vec![16]);
+        assert!(!item.intact);
+        let report = AuditReport {
+            corrupted_nodes: vec![],
+            root_matches: true,
+            ..report
+        };
+        assert!(AuditReportItem::from(&report).intact);
+    }
+
+    #[test]
+    fn test_history_entry_item() {
+        let entry = HistoryEntry {
+            block: 5,
+            change: FileChange::OwnershipTransferred { from: 1u64, to: 2 },
+        };
+        let item = HistoryEntryItem::from(&entry);
+        assert_eq!(
+            serde_json::to_value(&item).unwrap(),
+            serde_json::json!({
+                "block": 5,
+                "change": "ownership_transferred",
+                "from": "1",
+                "to": "2",
+            })
+        );
+    }
+
+    #[test]
+    fn test_external_file_item() {
+        let file = ExternalFile {
+            owner: 7u64,
+            size: 2048,
+            pieces: 2,
+        };
+        assert_eq!(
+            ExternalFileItem::from((2000, &file)),
+            ExternalFileItem {
+                para_id: 2000,
+                owner: "7".to_string(),
+                size: 2048,
+                pieces: 2,
+            }
+        );
+    }
+
+    #[test]
+    fn test_api_features() {
+        assert!(api_features(1).is_empty());
+        assert_eq!(
+            api_features(2),
+            vec![
+                "structured_listing".to_string(),
+                "structured_proofs".to_string()
+            ]
+        );
+        assert!(api_features(3).contains(&"account_usage".to_string()));
+        assert!(!api_features(3).contains(&"tombstones".to_string()));
+        assert!(api_features(4).contains(&"tombstones".to_string()));
+        assert!(api_features(5).contains(&"owner_index".to_string()));
+        assert!(api_features(6).contains(&"collections".to_string()));
+        assert!(api_features(7).contains(&"paged_listing".to_string()));
+        assert!(!api_features(7).contains(&"frozen_files".to_string()));
+        assert!(api_features(8).contains(&"frozen_files".to_string()));
+        assert!(!api_features(8).contains(&"state_export".to_string()));
+        assert!(api_features(9).contains(&"state_export".to_string()));
+        assert!(!api_features(9).contains(&"download_plan".to_string()));
+        assert!(api_features(10).contains(&"download_plan".to_string()));
+        assert!(!api_features(12).contains(&"upload_times".to_string()));
+        assert!(api_features(13).contains(&"upload_times".to_string()));
+        assert!(!api_features(13).contains(&"file_cids".to_string()));
+        assert!(api_features(14).contains(&"file_cids".to_string()));
+    }
+
+    #[test]
+    fn test_planned_chunk_item() {
+        let chunk = PlannedChunk {
+            position: 3,
+            ipfs_hash: "bafkrei".to_string(),
+            chunk_hash: [0xab; 32],
+            proof_len: 5,
+            uploaded: true,
+        };
+        assert_eq!(
+            PlannedChunkItem::from(&chunk),
+            PlannedChunkItem {
+                position: 3,
+                ipfs_hash: "bafkrei".to_string(),
+                chunk_hash: "ab".repeat(32),
+                proof_len: 5,
+                uploaded: true,
+            }
+        );
+    }
+
+    #[test]
+    fn test_paginate() {
+        let files = vec![(vec![1u8], 1), (vec![2u8], 2), (vec![3u8], 3)];
+        let page = |start: Option<&[u8]>| {
+            paginate(files.clone(), |(root, _)| root, start)
+                .map(|(_, pieces)| pieces)
+                .collect::<Vec<_>>()
+        };
+        assert_eq!(page(None), vec![1, 2, 3]);
+        assert_eq!(page(Some(&[1])), vec![2, 3]);
+        assert_eq!(page(Some(&[3])), Vec::<i32>::new());
+        assert_eq!(page(Some(&[4])), Vec::<i32>::new());
+    }
+
+    #[test]
+    fn test_upload_time_filters_and_order() {
+        let time = |block, timestamp| UploadTime { block, timestamp };
+        assert!(uploaded_within(None, None, None));
+        assert!(!uploaded_within(None, Some(0), None));
+        assert!(uploaded_within(Some(&time(2, 12_000)), Some(6_000), Some(18_000)));
+        assert!(!uploaded_within(Some(&time(2, 12_000)), Some(12_000), None));
+        assert!(!uploaded_within(Some(&time(2, 12_000)), None, Some(12_000)));
+
+        let file = |root: u8, uploaded_at| FileSummary {
+            merkle_root: vec![root],
+            owner: 1u64,
+            size: 1024,
+            pieces: 1,
+            license: None,
+            uploaded_at,
+            cid: None,
+        };
+        let mut files = vec![
+            file(1, Some(time(3, 18_000))),
+            file(2, None),
+            file(3, Some(time(1, 6_000))),
+        ];
+        let roots = |files: &[FileSummary<u64>]| {
+            files
+                .iter()
+                .map(|file| file.merkle_root[0])
+                .collect::<Vec<_>>()
+        };
+        sort_by_upload_time(&mut files, SortOrder::Ascending);
+        assert_eq!(roots(&files), vec![2, 3, 1]);
+        sort_by_upload_time(&mut files, SortOrder::Descending);
+        assert_eq!(roots(&files), vec![1, 3, 2]);
+        assert_eq!(
+            serde_json::from_str::<SortOrder>("\"descending\"").unwrap(),
+            SortOrder::Descending
+        );
+    }
+
+    #[test]
+    fn test_collection_item() {
+        let collection = CollectionInfo {
+            owner: 7u64,
+            members: vec![vec![0xab; 2], vec![0xcd; 2]],
+        };
+        assert_eq!(
+            CollectionItem::from((3, &collection)),
+            CollectionItem {
+                collection_id: 3,
+                owner: "7".to_string(),
+                members: vec!["abab".to_string(), "cdcd".to_string()],
+            }
+        );
+    }
+
+    #[test]
+    fn test_account_usage_item() {
+        let usage = UsageRecord {
+            files: 2,
+            bytes: 13818,
+            deposit_locked: 14018,
+        };
+        assert_eq!(
+            AccountUsageItem::from(&usage),
+            AccountUsageItem {
+                files: 2,
+                bytes: 13818,
+                deposit_locked: 14018,
+            }
+        );
+    }
+
+    #[test]
+    fn test_tombstone_item() {
+        let tombstone = Tombstone {
+            removed_at: 7,
+            reason_hash: [1; 32],
+            size: 6,
+            chunk_size: 4,
+            piece_hashes: vec![[2; 32], [3; 32]].try_into().unwrap(),
+        };
+        let item = TombstoneItem::from((&[4u8; 32][..], &tombstone));
+        assert_eq!(item.merkle_root, vec_to_hex_string(&[4; 32]));
+        assert_eq!(item.removed_at, 7);
+        assert_eq!(item.reason_hash, vec_to_hex_string(&[1; 32]));
+        assert_eq!(
+            item.piece_hashes,
+            vec![vec_to_hex_string(&[2; 32]), vec_to_hex_string(&[3; 32])]
+        );
+    }
+
+    #[test]
+    fn test_state_export_item() {
+        let record = |root: u8| FileRecord {
+            summary: FileSummary {
+                merkle_root: vec![root; 32],
+                owner: 1u64,
+                size: 1024,
+                pieces: 1,
+                license: None,
+                uploaded_at: None,
+                cid: None,
+            },
+            tree: Some(vec![vec![root; 32]]),
+        };
+        let records = vec![record(1), record(2)];
+        let item = StateExportItem::new(&[9; 32], &records, 2);
+        assert_eq!(item.at, vec_to_hex_string(&[9; 32]));
+        assert_eq!(item.records[1], vec_to_hex_string(&records[1].encode()));
+        assert_eq!(item.next, Some(vec_to_hex_string(&[2; 32])));
+        let decoded = array_bytes::hex2bytes(&item.records[0]).unwrap();
+        assert_eq!(FileRecord::<u64>::decode(&mut decoded.as_slice()).unwrap(), records[0]);
+        // a short page is the last one
+        assert_eq!(StateExportItem::new(&[9; 32], &records, 3).next, None);
+        assert_eq!(StateExportItem::new::<u64>(&[9; 32], &[], 3).next, None);
+    }
+
+    #[test]
+    fn test_verify_chunk() {
+        let first = sha2_256(b"abcd").to_vec();
+        let last = sha2_256(b"ef\0\0").to_vec();
+        let merkle_root = sha2_256(&[first.clone(), last.clone()].concat());
+        assert!(client::verify_chunk(&merkle_root, 0, b"abcd", 4, &[last.clone()]));
+        assert!(client::verify_chunk(&merkle_root, 1, b"ef", 4, &[first.clone()]));
+        assert!(!client::verify_chunk(&merkle_root, 1, b"eg", 4, &[first.clone()]));
+        assert!(!client::verify_chunk(&merkle_root, 0, b"ef", 4, &[first]));
+        assert!(!client::verify_chunk(&merkle_root, 2, b"abcd", 4, &[last]));
+    }
+
+    #[test]
+    fn test_verify_receipt() {
+        let pair = sr25519::Pair::from_seed(&[7; 32]);
+        let receipt = ChunkReceipt::sign(&pair, &[0xab; 32], 3, b"chunk", &[0xcd; 32]);
+        assert_eq!(receipt.chunk_hash, vec_to_hex_string(&sha2_256(b"chunk")));
+        assert_eq!(receipt.signer, vec_to_hex_string(pair.public().as_ref()));
+        assert!(verify_receipt(&receipt));
+
+        let tampered = ChunkReceipt {
+            position: 4,
+            ..receipt.clone()
+        };
+        assert!(!verify_receipt(&tampered));
+        let tampered = ChunkReceipt {
+            block_hash: vec_to_hex_string(&[0xce; 32]),
+            ..receipt.clone()
+        };
+        assert!(!verify_receipt(&tampered));
+        let other = sr25519::Pair::from_seed(&[8; 32]);
+        let tampered = ChunkReceipt {
+            signer: vec_to_hex_string(other.public().as_ref()),
+            ..receipt.clone()
+        };
+        assert!(!verify_receipt(&tampered));
+        let tampered = ChunkReceipt {
+            signature: "00".to_string(),
+            ..receipt
+        };
+        assert!(!verify_receipt(&tampered));
+    }
+
+    #[test]
+    fn test_proof_formats() {
+        let proof = vec![vec![0x01; 2], vec![0xab; 2]];
+        assert_eq!(
+            EncodedProof::new(&proof, ProofFormat::Hex),
+            EncodedProof::List(vec!["0101".to_string(), "abab".to_string()])
+        );
+        assert_eq!(
+            EncodedProof::new(&proof, ProofFormat::Concatenated),
+            EncodedProof::Blob("0101abab".to_string())
+        );
+        // compact length prefixes for the list and each hash
+        assert_eq!(
+            EncodedProof::new(&proof, ProofFormat::Scale),
+            EncodedProof::Blob("0808010108abab".to_string())
+        );
+    }
+
+    #[test]
+    fn test_block_errors() {
+        let code = |err: JsonRpseeError| match err {
+            JsonRpseeError::Call(CallError::Custom(err)) => err.code(),
+            _ => panic!("not a custom error"),
+        };
+        assert_eq!(code(runtime_error_into_rpc_err("error")), RUNTIME_ERROR);
+        assert_eq!(code(unknown_block_error(42u32)), UNKNOWN_BLOCK_ERROR);
+        assert_eq!(code(state_unavailable_error(42u32)), STATE_UNAVAILABLE_ERROR);
+    }
+
+    #[test]
+    fn test_legacy_file_infos() {
+        use pallet_trustless_file_server_runtime_api::legacy::FileInfoV11;
+
+        let legacy = FileInfoV11 {
+            owner: 1u64,
+            merkle_root: vec![0xab; 32],
+            size: 5 << 30,
+            pieces: 32,
+            chunk_size: 160 << 20,
+            bep52: false,
+            sha256: None,
+            registrants: 1,
+            name: b"movie.mkv".to_vec(),
+            mime_type: Vec::new(),
+            tags: Vec::new(),
+            license: None,
+        };
+        // decoded out of what runtimes implementing version 11 encode
+        let decoded = FileInfoV11::<u64>::decode(&mut &legacy.encode()[..]).unwrap();
+        let info = FileInfo::from(decoded);
+        assert_eq!(info.size, 5 << 30);
+        assert_eq!(info.name, b"movie.mkv".to_vec());
+        assert_eq!(info.retrieval_price, None);
+    }
+
+    #[test]
+    fn test_legacy_file_summaries() {
+        use pallet_trustless_file_server_runtime_api::legacy::FileSummaryV11;
+
+        let legacy = FileSummaryV11 {
+            merkle_root: vec![0xab; 32],
+            owner: 1u64,
+            size: 5 << 30,
+            pieces: 32,
+            license: None,
+        };
+        // decoded out of what runtimes implementing versions 11 and 12 encode
+        let decoded = FileSummaryV11::<u64>::decode(&mut &legacy.encode()[..]).unwrap();
+        let summary = FileSummary::from(decoded);
+        assert_eq!(summary.size, 5 << 30);
+        assert_eq!(summary.uploaded_at, None);
+    }
+}
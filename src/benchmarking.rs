@@ -4,8 +4,154 @@ use super::*;
 
 #[allow(unused)]
 use crate::Pallet as TrustlessFileServer;
+use codec::{Decode, Encode};
 use frame_benchmarking::v2::*;
+use frame_support::sp_runtime::traits::{Bounded, Hash};
+use frame_support::sp_runtime::RuntimeAppPublic;
+use frame_support::traits::tokens::fungible::Mutate;
+use frame_support::traits::{Get, SortedMembers};
+use frame_support::weights::Weight;
+use frame_support::BoundedVec;
 use frame_system::RawOrigin;
+use sp_std::vec::Vec;
+
+use crate::file_merkle_tree::{ChunkingParams, FileMerkleTree};
+
+fn fund<T: Config>(who: &T::AccountId) {
+    let balance = BalanceOf::<T>::max_value() / 2u32.into();
+    T::NativeBalance::set_balance(who, balance);
+}
+
+/// Funded account allowed to upload files, along with the origin it uploads with.
+fn uploader<T: Config>() -> (T::RuntimeOrigin, T::AccountId) {
+    let origin =
+        T::UploadOrigin::try_successful_origin().expect("UploadOrigin must be satisfiable");
+    let caller = T::UploadOrigin::ensure_origin(origin.clone()).unwrap();
+    fund::<T>(&caller);
+    (origin, caller)
+}
+
+fn upload<T: Config>(size: u32) -> (T::AccountId, T::Hash) {
+    let bytes = vec![(size % u8::MAX as u32) as u8; size as usize];
+    let (origin, caller) = uploader::<T>();
+    Pallet::<T>::upload_file(origin, bytes, None, None).unwrap();
+    (caller, FileOwners::<T>::iter_keys().next().unwrap())
+}
+
+/// Proof of `report` signed by the last of `MaxOcwAuthorities` new offchain worker authorities,
+/// as its offchain worker would sign it.
+fn authority_proof<T: Config>(report: impl Encode) -> AuthorityProofOf<T> {
+    let authorities = (0..T::MaxOcwAuthorities::get())
+        .map(|_| T::AuthorityId::generate_pair(None))
+        .collect::<Vec<_>>();
+    let authority = authorities
+        .last()
+        .cloned()
+        .expect("at least one authority; qed");
+    OcwAuthorities::<T>::put(BoundedVec::truncate_from(authorities));
+    let block = frame_system::Pallet::<T>::block_number();
+    let message = Pallet::<T>::report_message(&report, &authority, block);
+    let signature = authority
+        .sign(&message)
+        .expect("the key was just generated; qed");
+    AuthorityProof {
+        authority,
+        block,
+        signature,
+    }
+}
+
+/// Registers a file split with chunks twice as big as the current minimum, as if the chunking
+/// parameters had changed since it was uploaded.
+fn upload_legacy<T: Config>(caller: T::AccountId, size: u32) -> (T::Hash, Vec<u8>) {
+    let bytes = vec![(size % u8::MAX as u32) as u8; size as usize];
+    let current = Pallet::<T>::chunking_params();
+    let params = ChunkingParams {
+        min_chunk_size: current.min_chunk_size.saturating_mul(2),
+        max_chunk_size: u32::MAX,
+        bep52: false,
+        ipfs_chunk_size: None,
+        ..current
+    };
+    let tree = FileMerkleTree::new(&bytes, &params);
+    let merkle_root = T::Hash::decode(&mut tree.merkle_root()).unwrap();
+    Registrants::<T>::insert(merkle_root, &caller, Deposit::Native(0u32.into()));
+    RegistrantCount::<T>::insert(merkle_root, 1);
+    Pallet::<T>::put_file(merkle_root, &caller, &tree);
+    (merkle_root, bytes)
+}
+
+/// Holds an upload of 1KB under review, as if the runtime required every upload to be screened.
+fn under_review<T: Config>() -> T::Hash {
+    let (_, caller) = uploader::<T>();
+    let bytes = vec![1; 1024];
+    let (merkle_tree, merkle_root) = Pallet::<T>::build_tree(&bytes).unwrap();
+    let deposit = Pallet::<T>::take_deposit(&caller, bytes.len() as u32, None).unwrap();
+    let upload = PendingUpload {
+        who: caller,
+        sponsor: None,
+        deposit,
+        merkle_tree,
+        sha256: sp_io::hashing::sha2_256(&bytes),
+        license: None,
+    };
+    PendingReview::<T>::insert(merkle_root, upload);
+    merkle_root
+}
+
+/// Registers `count` different files of 1KB, returning their merkle roots.
+fn bundle_members<T: Config>(caller: T::AccountId, count: u32) -> BundleMembersOf<T> {
+    let members: Vec<T::Hash> = (0..count)
+        .map(|i| {
+            let tree = FileMerkleTree::new(&[i as u8; 1024], &Pallet::<T>::chunking_params());
+            let merkle_root = T::Hash::decode(&mut tree.merkle_root()).unwrap();
+            Pallet::<T>::put_file(merkle_root, &caller, &tree);
+            merkle_root
+        })
+        .collect();
+    BundleMembersOf::<T>::try_from(members).unwrap()
+}
+
+/// Creates a collection of `caller` holding `count` different files of 1KB it owns.
+fn collection<T: Config>(caller: T::AccountId, count: u32) -> CollectionId {
+    fund::<T>(&caller);
+    Pallet::<T>::create_collection(RawOrigin::Signed(caller.clone()).into()).unwrap();
+    let collection_id = NextCollectionId::<T>::get() - 1;
+    let members = bundle_members::<T>(caller, count).into_inner();
+    Collections::<T>::mutate(collection_id, |collection| {
+        collection.as_mut().unwrap().members = members.try_into().unwrap();
+    });
+    collection_id
+}
+
+/// Splits a key of `MaxKeyLength` bytes among `count` new committee members, all of which are
+/// needed to rebuild it. Returns the key along with the escrowed shares and their plain values.
+fn escrowed_key<T: Config>(
+    count: u32,
+) -> (
+    KeyOf<T>,
+    BoundedVec<(T::AccountId, [u8; 32], EncryptedShareOf<T>), T::MaxKeyHolders>,
+    Vec<KeyOf<T>>,
+) {
+    let key = KeyOf::<T>::try_from(vec![b'k'; T::MaxKeyLength::get() as usize]).unwrap();
+    let shares: Vec<KeyOf<T>> = shamir::split(&key, count as u8, count as u8, || 7)
+        .into_iter()
+        .map(|share| KeyOf::<T>::try_from(share).unwrap())
+        .collect();
+    let encrypted =
+        EncryptedShareOf::<T>::try_from(vec![0; T::MaxEncryptedShareLength::get() as usize])
+            .unwrap();
+    let escrowed: Vec<_> = shares
+        .iter()
+        .enumerate()
+        .map(|(i, share)| {
+            let holder: T::AccountId = account("holder", i as u32, 0);
+            T::KeyCommittee::add(&holder);
+            (holder, sp_io::hashing::sha2_256(share), encrypted.clone())
+        })
+        .collect();
+    (key, BoundedVec::try_from(escrowed).unwrap(), shares)
+}
 
 #[benchmarks]
 mod benchmarks {
@@ -14,13 +160,800 @@ mod benchmarks {
     // from 1KB to 32KB
     #[benchmark]
     fn upload_file(x: Linear<1024, 32768>) {
+        let (origin, _) = uploader::<T>();
+        let bytes = vec![(x % u8::MAX as u32) as u8; x as usize];
+        let uri = BoundedVec::try_from(vec![b'a'; T::StringLimit::get() as usize]).unwrap();
+
+        #[extrinsic_call]
+        _(origin as T::RuntimeOrigin, bytes, None, Some(License::Uri(uri)));
+
+        assert!(FileOwners::<T>::iter().next().is_some());
+    }
+
+    #[benchmark]
+    fn set_metadata() {
+        let (caller, merkle_root) = upload::<T>(1024);
+        let string = || BoundedVec::try_from(vec![b'a'; T::StringLimit::get() as usize]).unwrap();
+        let metadata = FileMetadataOf::<T> {
+            name: string(),
+            mime_type: string(),
+            tags: BoundedVec::try_from(vec![string(); T::MaxTags::get() as usize]).unwrap(),
+            license: Some(License::Uri(string())),
+            retrieval_price: Some(1u32.into()),
+        };
+
+        #[extrinsic_call]
+        _(RawOrigin::Signed(caller), merkle_root, metadata);
+
+        assert!(Metadata::<T>::contains_key(merkle_root));
+    }
+
+    // proofs have up to `MAX_TREE_DEPTH` (5) siblings, and all of them are hashed no matter the
+    // shape of the tree
+    #[benchmark]
+    fn verify_proof(x: Linear<1, 5>) {
+        let (_, merkle_root) = upload::<T>(1024);
+        let proof = BoundedProof::try_from(vec![[0u8; 32]; x as usize]).unwrap();
+        let result;
+
+        #[block]
+        {
+            result = Pallet::<T>::verify_proof(merkle_root, 0, &[0u8; 32], &proof);
+        }
+
+        assert!(!result);
+    }
+
+    #[benchmark]
+    fn report_upload_progress() {
+        let (_, merkle_root) = upload::<T>(1024);
+        let proof = authority_proof::<T>((b"progress", merkle_root, 1u32, 1u32));
+
+        #[extrinsic_call]
+        _(RawOrigin::None, merkle_root, 1, 1, proof);
+
+        assert_eq!(UploadProgress::<T>::get(merkle_root), 1);
+    }
+
+    #[benchmark]
+    fn report_chunk_allocations(x: Linear<1, 16>) {
+        let (_, merkle_root) = upload::<T>(1024);
+        let peer = ClusterPeerId::try_from(vec![b'p'; 52]).unwrap();
+        let peers = x.min(T::ClusterReplicationFactor::get()) as usize;
+        let allocations = AllocationsOf::<T>::try_from(vec![peer; peers]).unwrap();
+        let proof = authority_proof::<T>((b"allocations", merkle_root, 0u64, &allocations));
+
+        #[extrinsic_call]
+        _(RawOrigin::None, merkle_root, 0, allocations, proof);
+
+        assert!(ChunkAllocations::<T>::contains_key(merkle_root, 0));
+    }
+
+    #[benchmark]
+    fn report_probe() {
+        let (_, merkle_root) = upload::<T>(1024);
+        let proof = authority_proof::<T>((b"probe", merkle_root, 0u64, true));
+
+        #[extrinsic_call]
+        _(RawOrigin::None, merkle_root, 0, true, proof);
+
+        assert!(ProbeResults::<T>::contains_key(merkle_root));
+    }
+
+    #[benchmark]
+    fn rechunk_file() {
+        let caller: T::AccountId = whitelisted_caller();
+        let (merkle_root, _) = upload_legacy::<T>(caller.clone(), 4096);
+
+        #[extrinsic_call]
+        _(RawOrigin::Signed(caller), merkle_root);
+
+        assert!(PendingRechunks::<T>::contains_key(merkle_root));
+    }
+
+    // from 1 to 32 pieces under the current chunking parameters, shared by 1 to 16 registrants
+    #[benchmark]
+    fn report_rechunk(x: Linear<1, 32>, r: Linear<1, 16>) {
+        let caller: T::AccountId = whitelisted_caller();
+        let size = x * T::MinChunkSize::get();
+        let (merkle_root, bytes) = upload_legacy::<T>(caller.clone(), size);
+        for i in 1..r {
+            let registrant: T::AccountId = account("registrant", i, 0);
+            Registrants::<T>::insert(merkle_root, registrant, Deposit::Native(0u32.into()));
+        }
+        RegistrantCount::<T>::insert(merkle_root, r);
+        Pallet::<T>::rechunk_file(RawOrigin::Signed(caller).into(), merkle_root).unwrap();
+        let merkle_tree = FileMerkleTree::new(&bytes, &Pallet::<T>::chunking_params());
+        for position in 0..x {
+            let allocations = AllocationsOf::<T>::default();
+            ChunkAllocations::<T>::insert(merkle_root, u64::from(position), allocations);
+        }
+        let proof = authority_proof::<T>((b"rechunk", merkle_root, &merkle_tree));
+
+        #[extrinsic_call]
+        _(RawOrigin::None, merkle_root, merkle_tree, proof);
+
+        assert!(RootAliases::<T>::contains_key(merkle_root));
+    }
+
+    #[benchmark]
+    fn reject_rechunk() {
+        let caller: T::AccountId = whitelisted_caller();
+        let (merkle_root, _) = upload_legacy::<T>(caller.clone(), 4096);
+        Pallet::<T>::rechunk_file(RawOrigin::Signed(caller).into(), merkle_root).unwrap();
+        let proof = authority_proof::<T>((b"reject_rechunk", merkle_root));
+
+        #[extrinsic_call]
+        _(RawOrigin::None, merkle_root, proof);
+
+        assert!(!PendingRechunks::<T>::contains_key(merkle_root));
+    }
+
+    // the last registrant leaves a file with every chunk allocated
+    #[benchmark]
+    fn delete_file() {
+        let (caller, merkle_root) = upload::<T>(32 * T::MinChunkSize::get());
+        for position in 0..Pallet::<T>::file_pieces(merkle_root).unwrap() {
+            let allocations = AllocationsOf::<T>::default();
+            ChunkAllocations::<T>::insert(merkle_root, u64::from(position), allocations);
+        }
+
+        #[extrinsic_call]
+        _(RawOrigin::Signed(caller), merkle_root);
+
+        assert!(!FileOwners::<T>::contains_key(merkle_root));
+    }
+
+    #[benchmark]
+    fn pause() {
+        let origin = T::AdminOrigin::try_successful_origin().unwrap();
+
+        #[extrinsic_call]
+        _(origin as T::RuntimeOrigin);
+
+        assert!(Paused::<T>::get());
+    }
+
+    #[benchmark]
+    fn unpause() {
+        let origin = T::AdminOrigin::try_successful_origin().unwrap();
+        Paused::<T>::put(true);
+
+        #[extrinsic_call]
+        _(origin as T::RuntimeOrigin);
+
+        assert!(!Paused::<T>::get());
+    }
+
+    #[benchmark]
+    fn register_alias() {
+        // taking an expired name from another account gives its deposit back
+        let (owner, merkle_root) = upload::<T>(1024);
+        let name = NameOf::<T>::try_from(vec![b'a'; T::MaxNameLength::get() as usize]).unwrap();
+        Pallet::<T>::register_alias(RawOrigin::Signed(owner).into(), name.clone(), merkle_root)
+            .unwrap();
+        let expires_at = Names::<T>::get(&name).unwrap().expires_at;
+        frame_system::Pallet::<T>::set_block_number(expires_at);
+        let caller: T::AccountId = account("caller", 0, 0);
+        fund::<T>(&caller);
+
+        #[extrinsic_call]
+        _(RawOrigin::Signed(caller.clone()), name.clone(), merkle_root);
+
+        assert_eq!(Names::<T>::get(name).map(|alias| alias.owner), Some(caller));
+    }
+
+    #[benchmark]
+    fn release_alias() {
+        let (caller, merkle_root) = upload::<T>(1024);
+        let name = NameOf::<T>::try_from(vec![b'a'; T::MaxNameLength::get() as usize]).unwrap();
+        let origin = RawOrigin::Signed(caller.clone());
+        Pallet::<T>::register_alias(origin.into(), name.clone(), merkle_root).unwrap();
+
+        #[extrinsic_call]
+        _(RawOrigin::Signed(caller), name.clone());
+
+        assert!(!Names::<T>::contains_key(name));
+    }
+
+    #[benchmark]
+    fn renew_alias() {
+        let (caller, merkle_root) = upload::<T>(1024);
+        let name = NameOf::<T>::try_from(vec![b'a'; T::MaxNameLength::get() as usize]).unwrap();
+        let origin = RawOrigin::Signed(caller.clone());
+        Pallet::<T>::register_alias(origin.into(), name.clone(), merkle_root).unwrap();
+        let expires_at = Names::<T>::get(&name).unwrap().expires_at;
+        frame_system::Pallet::<T>::set_block_number(expires_at);
+
+        #[extrinsic_call]
+        _(RawOrigin::Signed(caller), name.clone());
+
+        assert!(Names::<T>::get(name).unwrap().expires_at > expires_at);
+    }
+
+    #[benchmark]
+    fn reassign_alias() {
+        let (caller, merkle_root) = upload::<T>(1024);
+        let name = NameOf::<T>::try_from(vec![b'a'; T::MaxNameLength::get() as usize]).unwrap();
+        let origin = RawOrigin::Signed(caller);
+        Pallet::<T>::register_alias(origin.into(), name.clone(), merkle_root).unwrap();
+        let to: T::AccountId = account("to", 0, 0);
+        let origin = T::ModerationOrigin::try_successful_origin().unwrap();
+
+        #[extrinsic_call]
+        _(origin as T::RuntimeOrigin, name.clone(), to.clone());
+
+        assert_eq!(Names::<T>::get(name).map(|alias| alias.owner), Some(to));
+    }
+
+    #[benchmark]
+    fn report_repin() {
+        let (_, merkle_root) = upload::<T>(1024);
+        let proof = authority_proof::<T>((b"repin", merkle_root));
+
+        #[extrinsic_call]
+        _(RawOrigin::None, merkle_root, proof);
+
+        assert!(LastPinnedAt::<T>::contains_key(merkle_root));
+    }
+
+    #[benchmark]
+    fn report_bandwidth() {
+        let (_, merkle_root) = upload::<T>(1024);
+        let proof = authority_proof::<T>((b"bandwidth", merkle_root, 1024u64, 1u64));
+        let authority = proof.authority.clone();
+
+        #[extrinsic_call]
+        _(RawOrigin::None, merkle_root, 1024, 1, proof);
+
+        assert_eq!(ServedBandwidth::<T>::get(merkle_root, authority).chunks, 1);
+    }
+
+    #[benchmark]
+    fn report_audit() {
+        let (_, merkle_root) = upload::<T>(1024);
+        // the piece reported is the last one of a full sample
+        let mut sample = (1..T::AuditSampleSize::get())
+            .map(|i| (T::Hashing::hash_of(&i), 0))
+            .collect::<Vec<_>>();
+        sample.push((merkle_root, 0));
+        AuditSample::<T>::put(BoundedVec::try_from(sample).unwrap());
+        let proof = authority_proof::<T>((b"audit", merkle_root, 0u64, true));
+
+        #[extrinsic_call]
+        _(RawOrigin::None, merkle_root, 0, true, proof);
+
+        assert!(PieceAudits::<T>::contains_key(merkle_root));
+    }
+
+    // from 1KB to 32KB
+    #[benchmark]
+    fn sponsor_upload(x: Linear<1024, 32768>) {
+        let (origin, _) = uploader::<T>();
+        let beneficiary: T::AccountId = account("beneficiary", 0, 0);
+        let bytes = vec![(x % u8::MAX as u32) as u8; x as usize];
+
+        #[extrinsic_call]
+        _(origin as T::RuntimeOrigin, beneficiary.clone(), bytes);
+
+        let merkle_root = FileOwners::<T>::iter_keys().next().unwrap();
+        assert!(Sponsors::<T>::contains_key(merkle_root, beneficiary));
+    }
+
+    // from 1KB to 32KB
+    #[benchmark]
+    fn upload_file_unsigned(x: Linear<1024, 32768>) {
+        let bytes = vec![(x % u8::MAX as u32) as u8; x as usize];
+        let tree = FileMerkleTree::new(&bytes, &Pallet::<T>::chunking_params());
+        let merkle_root = T::Hash::decode(&mut tree.merkle_root()).unwrap();
+        let difficulty = T::UnsignedUploadDifficulty::get().expect("anonymous uploads enabled");
+        let nonce = (0..)
+            .find(|nonce| Pallet::<T>::upload_work(&merkle_root, *nonce) >= difficulty)
+            .unwrap();
+
+        #[extrinsic_call]
+        _(RawOrigin::None, bytes, nonce);
+
+        assert!(FileOwners::<T>::contains_key(merkle_root));
+    }
+
+    // `x` bytes in total, split in `n` files
+    #[benchmark]
+    fn upload_files(n: Linear<1, 16>, x: Linear<1024, 32768>) {
+        let (origin, _) = uploader::<T>();
+        let n = n
+            .min(T::MaxFilesPerBatch::get())
+            .min(T::MaxUploadsPerBlock::get())
+            .min(T::MaxUploadsPerAccountPerBlock::get());
+        let files: Vec<Vec<u8>> = (0..n).map(|i| vec![i as u8; (x / n) as usize]).collect();
+        let files = BoundedVec::try_from(files).unwrap();
+
+        #[extrinsic_call]
+        _(origin as T::RuntimeOrigin, files);
+
+        assert_eq!(FileOwners::<T>::iter().count(), n as usize);
+    }
+
+    // bundles hold up to `MAX_PIECES` (32) files
+    #[benchmark]
+    fn create_bundle(n: Linear<2, 32>) {
         let caller: T::AccountId = whitelisted_caller();
+        fund::<T>(&caller);
+        let members = bundle_members::<T>(caller.clone(), n);
+
+        #[extrinsic_call]
+        _(RawOrigin::Signed(caller), members);
+
+        assert!(Bundles::<T>::iter().next().is_some());
+    }
+
+    #[benchmark]
+    fn remove_bundle() {
+        let caller: T::AccountId = whitelisted_caller();
+        fund::<T>(&caller);
+        let members = bundle_members::<T>(caller.clone(), MAX_PIECES);
+        Pallet::<T>::create_bundle(RawOrigin::Signed(caller.clone()).into(), members).unwrap();
+        let bundle_root = Bundles::<T>::iter_keys().next().unwrap();
+
+        #[extrinsic_call]
+        _(RawOrigin::Signed(caller), bundle_root);
+
+        assert!(!Bundles::<T>::contains_key(bundle_root));
+    }
+
+    #[benchmark]
+    fn commit_key() {
+        let (caller, merkle_root) = upload::<T>(1024);
+        let reveal_at = frame_system::Pallet::<T>::block_number();
+
+        #[extrinsic_call]
+        _(RawOrigin::Signed(caller), merkle_root, [0u8; 32], reveal_at);
+
+        assert!(TimeLocks::<T>::contains_key(merkle_root));
+    }
+
+    // the key is hashed to be checked against the commitment, so the longest is the worst case
+    #[benchmark]
+    fn reveal_key() {
+        let (caller, merkle_root) = upload::<T>(1024);
+        let key = KeyOf::<T>::try_from(vec![b'k'; T::MaxKeyLength::get() as usize]).unwrap();
+        let reveal_at = frame_system::Pallet::<T>::block_number();
+        let commitment = sp_io::hashing::sha2_256(&key);
+        Pallet::<T>::commit_key(
+            RawOrigin::Signed(caller.clone()).into(),
+            merkle_root,
+            commitment,
+            reveal_at,
+        )
+        .unwrap();
+
+        #[extrinsic_call]
+        _(RawOrigin::Signed(caller), merkle_root, key);
+
+        assert!(TimeLocks::<T>::get(merkle_root).unwrap().key.is_some());
+    }
+
+    // holders are checked to be committee members and distinct
+    #[benchmark]
+    fn escrow_key(n: Linear<1, 16>) {
+        let (caller, merkle_root) = upload::<T>(1024);
+        let (key, shares, _) = escrowed_key::<T>(n);
+        let reveal_at = frame_system::Pallet::<T>::block_number();
+        let commitment = sp_io::hashing::sha2_256(&key);
+
+        #[extrinsic_call]
+        _(RawOrigin::Signed(caller), merkle_root, commitment, reveal_at, n, shares);
+
+        assert!(KeyEscrows::<T>::contains_key(merkle_root));
+    }
+
+    // the last of `n` shares rebuilds the key
+    #[benchmark]
+    fn submit_key_share(n: Linear<1, 16>) {
+        let (caller, merkle_root) = upload::<T>(1024);
+        let (key, escrowed, mut shares) = escrowed_key::<T>(n);
+        let reveal_at = frame_system::Pallet::<T>::block_number();
+        let commitment = sp_io::hashing::sha2_256(&key);
+        let holders: Vec<T::AccountId> = escrowed
+            .iter()
+            .map(|(holder, _, _)| holder.clone())
+            .collect();
+        Pallet::<T>::escrow_key(
+            RawOrigin::Signed(caller).into(),
+            merkle_root,
+            commitment,
+            reveal_at,
+            n,
+            escrowed,
+        )
+        .unwrap();
+        let last = shares.pop().unwrap();
+        for (holder, share) in holders.iter().zip(shares) {
+            Pallet::<T>::submit_key_share(
+                RawOrigin::Signed(holder.clone()).into(),
+                merkle_root,
+                share,
+            )
+            .unwrap();
+        }
+
+        #[extrinsic_call]
+        _(RawOrigin::Signed(holders[n as usize - 1].clone()), merkle_root, last);
+
+        assert_eq!(TimeLocks::<T>::get(merkle_root).unwrap().key, Some(key));
+    }
+
+    // from 1KB to 32KB, none of which is hashed
+    #[benchmark]
+    fn upload_file_with_root(x: Linear<1024, 32768>) {
+        let (origin, _) = uploader::<T>();
         let bytes = vec![(x % u8::MAX as u32) as u8; x as usize];
+        let claimed_root = T::Hash::default();
+
+        #[extrinsic_call]
+        _(origin as T::RuntimeOrigin, bytes, claimed_root);
+
+        assert!(RootClaims::<T>::contains_key(claimed_root));
+    }
+
+    // the whole tree is checked, hence linear in the number of pieces
+    #[benchmark]
+    fn confirm_root(x: Linear<1, 32>) {
+        let (origin, _) = uploader::<T>();
+        let bytes = vec![(x % u8::MAX as u32) as u8; (x * T::MinChunkSize::get()) as usize];
+        let merkle_tree = FileMerkleTree::new(&bytes, &Pallet::<T>::chunking_params());
+        let merkle_root = T::Hash::decode(&mut merkle_tree.merkle_root()).unwrap();
+        let sha256 = sp_io::hashing::sha2_256(&bytes);
+        Pallet::<T>::upload_file_with_root(origin, bytes, merkle_root).unwrap();
+        let proof = authority_proof::<T>((b"confirm", merkle_root, &merkle_tree, sha256));
+
+        #[extrinsic_call]
+        _(RawOrigin::None, merkle_root, merkle_tree, sha256, proof);
+
+        assert!(FileOwners::<T>::contains_key(merkle_root));
+    }
+
+    #[benchmark]
+    fn reject_root() {
+        let (origin, _) = uploader::<T>();
+        let merkle_root = T::Hash::default();
+        Pallet::<T>::upload_file_with_root(origin, vec![1; 1024], merkle_root).unwrap();
+        let proof = authority_proof::<T>((b"reject", merkle_root));
+
+        #[extrinsic_call]
+        _(RawOrigin::None, merkle_root, proof);
+
+        assert!(!RootClaims::<T>::contains_key(merkle_root));
+    }
+
+    #[benchmark]
+    fn cancel_root_claim() {
+        let (origin, caller) = uploader::<T>();
+        let merkle_root = T::Hash::default();
+        Pallet::<T>::upload_file_with_root(origin, vec![1; 1024], merkle_root).unwrap();
+
+        #[extrinsic_call]
+        _(RawOrigin::Signed(caller), merkle_root);
+
+        assert!(!RootClaims::<T>::contains_key(merkle_root));
+    }
+
+    #[benchmark]
+    fn report_file() {
+        let (_, merkle_root) = upload::<T>(1024);
+        let caller: T::AccountId = whitelisted_caller();
+        fund::<T>(&caller);
+
+        #[extrinsic_call]
+        _(RawOrigin::Signed(caller), merkle_root, [7; 32]);
+
+        assert_eq!(Reports::<T>::get(merkle_root).len(), 1);
+    }
+
+    // the worst case takes the file down, refunding every registrant and every reporter
+    #[benchmark]
+    fn resolve_report(
+        r: Linear<1, { T::MaxRegistrants::get() }>,
+        n: Linear<1, { T::MaxReportsPerFile::get() }>,
+    ) {
+        let (_, merkle_root) = upload::<T>(1024);
+        for i in 1..r {
+            let registrant: T::AccountId = account("registrant", i, 0);
+            Registrants::<T>::insert(merkle_root, &registrant, Deposit::Native(0u32.into()));
+        }
+        RegistrantCount::<T>::insert(merkle_root, r);
+        for i in 0..n {
+            let reporter: T::AccountId = account("reporter", i, 0);
+            fund::<T>(&reporter);
+            Pallet::<T>::report_file(RawOrigin::Signed(reporter).into(), merkle_root, [7; 32])
+                .unwrap();
+        }
+        let reporter: T::AccountId = account("reporter", 0, 0);
+        let origin = T::ModerationOrigin::try_successful_origin().unwrap();
+
+        #[extrinsic_call]
+        _(origin as T::RuntimeOrigin, merkle_root, reporter, Verdict::RemoveFile);
+
+        assert!(!FileOwners::<T>::contains_key(merkle_root));
+        assert!(!Reports::<T>::contains_key(merkle_root));
+    }
+
+    #[benchmark]
+    fn schedule_deletion() {
+        let (caller, merkle_root) = upload::<T>(1024);
+        let when = frame_system::Pallet::<T>::block_number() + 10u32.into();
+
+        #[extrinsic_call]
+        _(RawOrigin::Signed(caller.clone()), merkle_root, when);
+
+        assert_eq!(ScheduledDeletions::<T>::get(merkle_root, caller), Some(when));
+    }
+
+    #[benchmark]
+    fn cancel_deletion() {
+        let (caller, merkle_root) = upload::<T>(1024);
+        let when = frame_system::Pallet::<T>::block_number() + 10u32.into();
+        Pallet::<T>::schedule_deletion(RawOrigin::Signed(caller.clone()).into(), merkle_root, when)
+            .unwrap();
+
+        #[extrinsic_call]
+        _(RawOrigin::Signed(caller.clone()), merkle_root);
+
+        assert!(!ScheduledDeletions::<T>::contains_key(merkle_root, caller));
+    }
+
+    #[benchmark]
+    fn approve_large_upload() {
+        let (origin, _) = uploader::<T>();
+        let size = T::LargeFileThreshold::get().saturating_add(1);
+        Pallet::<T>::upload_file(origin, vec![1; size as usize], None, None).unwrap();
+        let merkle_root = PendingUploads::<T>::iter_keys()
+            .next()
+            .expect("file size allowed");
+        let approver = T::LargeUploadOrigin::try_successful_origin().unwrap();
+
+        #[extrinsic_call]
+        _(approver as T::RuntimeOrigin, merkle_root);
+
+        assert!(FileOwners::<T>::contains_key(merkle_root));
+    }
+
+    #[benchmark]
+    fn reject_large_upload() {
+        let (origin, _) = uploader::<T>();
+        let size = T::LargeFileThreshold::get().saturating_add(1);
+        Pallet::<T>::upload_file(origin, vec![1; size as usize], None, None).unwrap();
+        let merkle_root = PendingUploads::<T>::iter_keys()
+            .next()
+            .expect("file size allowed");
+        let approver = T::LargeUploadOrigin::try_successful_origin().unwrap();
+
+        #[extrinsic_call]
+        _(approver as T::RuntimeOrigin, merkle_root);
+
+        assert!(!PendingUploads::<T>::contains_key(merkle_root));
+    }
+
+    #[benchmark]
+    fn approve_review() {
+        let merkle_root = under_review::<T>();
+        let reviewer = T::ReviewOrigin::try_successful_origin().unwrap();
+
+        #[extrinsic_call]
+        _(reviewer as T::RuntimeOrigin, merkle_root);
+
+        assert!(!PendingReview::<T>::contains_key(merkle_root));
+    }
+
+    #[benchmark]
+    fn reject_review() {
+        let merkle_root = under_review::<T>();
+        let reviewer = T::ReviewOrigin::try_successful_origin().unwrap();
+
+        #[extrinsic_call]
+        _(reviewer as T::RuntimeOrigin, merkle_root);
+
+        assert!(!PendingReview::<T>::contains_key(merkle_root));
+    }
+
+    #[benchmark]
+    fn freeze_file() {
+        let (caller, merkle_root) = upload::<T>(1024);
+
+        #[extrinsic_call]
+        _(RawOrigin::Signed(caller), merkle_root);
+
+        assert!(TrustlessFileServer::<T>::file_frozen(merkle_root));
+    }
+
+    #[benchmark]
+    fn unfreeze_file() {
+        let (caller, merkle_root) = upload::<T>(1024);
+        TrustlessFileServer::<T>::freeze_file(
+            RawOrigin::Signed(caller.clone()).into(),
+            merkle_root,
+        )
+        .unwrap();
 
         #[extrinsic_call]
-        _(RawOrigin::Signed(caller.clone()), bytes);
+        _(RawOrigin::Signed(caller), merkle_root);
+
+        assert!(!TrustlessFileServer::<T>::file_frozen(merkle_root));
+    }
+
+    // the registration of the caller goes along with the file
+    #[benchmark]
+    fn transfer_file() {
+        let (caller, merkle_root) = upload::<T>(1024);
+        let to: T::AccountId = account("multisig", 0, 0);
+
+        #[extrinsic_call]
+        _(RawOrigin::Signed(caller), merkle_root, to.clone());
+
+        assert_eq!(FileOwners::<T>::get(merkle_root), Some(to));
+    }
+
+    #[benchmark]
+    fn set_management_threshold() {
+        let (caller, merkle_root) = upload::<T>(1024);
+
+        #[extrinsic_call]
+        _(RawOrigin::Signed(caller), merkle_root, Some(2));
+
+        assert_eq!(ManagementThresholds::<T>::get(merkle_root), Some(2));
+    }
+
+    #[benchmark]
+    fn attest_file() {
+        let (caller, merkle_root) = upload::<T>(1024);
+
+        #[extrinsic_call]
+        _(RawOrigin::Signed(caller), merkle_root, 2000);
+    }
+
+    // the successful origin must be the location of a trusted sibling parachain
+    #[benchmark]
+    fn register_external_file() {
+        let origin =
+            T::SiblingOrigin::try_successful_origin().expect("SiblingOrigin must be satisfiable");
+        let attestation = FileAttestation {
+            merkle_root: T::Hash::default(),
+            owner: whitelisted_caller(),
+            size: 1024,
+            pieces: 1,
+        };
+
+        #[extrinsic_call]
+        _(origin as T::RuntimeOrigin, attestation);
+
+        assert!(ExternalFiles::<T>::iter_prefix(T::Hash::default())
+            .next()
+            .is_some());
+    }
+
+    #[benchmark]
+    fn set_ocw_authorities(n: Linear<1, { T::MaxOcwAuthorities::get() }>) {
+        let origin = T::AdminOrigin::try_successful_origin().unwrap();
+        let authorities = (0..n)
+            .map(|_| T::AuthorityId::generate_pair(None))
+            .collect::<Vec<_>>();
+        let authorities = BoundedVec::try_from(authorities).unwrap();
+
+        #[extrinsic_call]
+        _(origin as T::RuntimeOrigin, authorities);
+
+        assert_eq!(OcwAuthorities::<T>::get().len() as u32, n);
+    }
+
+    #[benchmark]
+    fn set_retrieval_price() {
+        let (caller, merkle_root) = upload::<T>(1024);
+        let price = Some(BalanceOf::<T>::from(1u32));
+
+        #[extrinsic_call]
+        _(RawOrigin::Signed(caller), merkle_root, price);
+
+        assert_eq!(TrustlessFileServer::<T>::retrieval_price(merkle_root), price);
+    }
+
+    #[benchmark]
+    fn create_collection() {
+        let caller: T::AccountId = whitelisted_caller();
+        fund::<T>(&caller);
+
+        #[extrinsic_call]
+        _(RawOrigin::Signed(caller));
+
+        assert!(Collections::<T>::contains_key(0));
+    }
+
+    // adding to a collection one file short of full
+    #[benchmark]
+    fn add_to_collection() {
+        // bigger than the files of the collection, so that it is a different one
+        let (caller, merkle_root) = upload::<T>(2048);
+        let collection_id = collection::<T>(caller.clone(), T::MaxCollectionSize::get() - 1);
+
+        #[extrinsic_call]
+        _(RawOrigin::Signed(caller), collection_id, merkle_root);
+
+        let collection = Collections::<T>::get(collection_id).unwrap();
+        assert!(collection.members.contains(&merkle_root));
+    }
+
+    // removing the last file of a full collection
+    #[benchmark]
+    fn remove_from_collection() {
+        let caller: T::AccountId = whitelisted_caller();
+        let collection_id = collection::<T>(caller.clone(), T::MaxCollectionSize::get());
+        let members = Collections::<T>::get(collection_id).unwrap().members;
+        let merkle_root = *members.last().unwrap();
+
+        #[extrinsic_call]
+        _(RawOrigin::Signed(caller), collection_id, merkle_root);
+
+        let collection = Collections::<T>::get(collection_id).unwrap();
+        assert!(!collection.members.contains(&merkle_root));
+    }
+
+    #[benchmark]
+    fn set_collection_price(n: Linear<1, { T::MaxCollectionSize::get() }>) {
+        let caller: T::AccountId = whitelisted_caller();
+        let collection_id = collection::<T>(caller.clone(), n);
+        let price = Some(BalanceOf::<T>::from(1u32));
+
+        #[extrinsic_call]
+        _(RawOrigin::Signed(caller), collection_id, price);
+
+        let members = Collections::<T>::get(collection_id).unwrap().members;
+        assert!(members
+            .iter()
+            .all(|member| TrustlessFileServer::<T>::retrieval_price(*member) == price));
+    }
+
+    #[benchmark]
+    fn transfer_collection(n: Linear<1, { T::MaxCollectionSize::get() }>) {
+        let caller: T::AccountId = whitelisted_caller();
+        let collection_id = collection::<T>(caller.clone(), n);
+        let to: T::AccountId = account("recipient", 0, 0);
+        for member in Collections::<T>::get(collection_id).unwrap().members {
+            Registrants::<T>::insert(member, &to, Deposit::Native(0u32.into()));
+        }
+
+        #[extrinsic_call]
+        _(RawOrigin::Signed(caller), collection_id, to.clone());
+
+        assert_eq!(Collections::<T>::get(collection_id).unwrap().owner, to);
+    }
+
+    #[benchmark]
+    fn remove_collection() {
+        let caller: T::AccountId = whitelisted_caller();
+        let collection_id = collection::<T>(caller.clone(), 1);
+
+        #[extrinsic_call]
+        _(RawOrigin::Signed(caller), collection_id);
+
+        assert!(!Collections::<T>::contains_key(collection_id));
+    }
+
+    // chunk allocations of a removed file, all of them dropped in one go
+    #[benchmark]
+    fn reclaim_chunks(n: Linear<0, 1000>) {
+        let merkle_root = T::Hashing::hash(b"removed");
+        OrphanedChunks::<T>::insert(merkle_root, ());
+        for position in 0..n {
+            let allocations = AllocationsOf::<T>::default();
+            ChunkAllocations::<T>::insert(merkle_root, u64::from(position), allocations);
+        }
+
+        #[block]
+        {
+            Pallet::<T>::reclaim_chunks(Weight::MAX);
+        }
 
-        assert!(Files::<T>::iter().next().is_some());
+        assert!(!OrphanedChunks::<T>::contains_key(merkle_root));
+        assert_eq!(ChunkAllocations::<T>::iter_prefix(merkle_root).count(), 0);
     }
 
     impl_benchmark_test_suite!(TrustlessFileServer, crate::mock::new_test_ext(), crate::mock::Test);
@@ -0,0 +1,94 @@
+//! Chain extension giving ink! smart contracts read access to the files registered through this
+//! pallet.
+//!
+//! Runtimes register [`FileServerExtension`] as (part of) `pallet_contracts::Config::ChainExtension`.
+//! Contracts then call it with the following function ids, SCALE-encoding their input and
+//! decoding the output:
+//!
+//! | id | function      | input                                                    | output              |
+//! |----|---------------|----------------------------------------------------------|---------------------|
+//! | 1  | `file_exists` | `merkle_root: Hash`                                      | `bool`              |
+//! | 2  | `file_owner`  | `merkle_root: Hash`                                      | `Option<AccountId>` |
+//! | 3  | `verify_proof`| `(merkle_root: Hash, position: u64, leaf: [u8; 32], proof: Vec<[u8; 32]>)` | `bool` |
+//!
+//! Proofs longer than [`MAX_TREE_DEPTH`](crate::MAX_TREE_DEPTH) siblings are rejected.
+use codec::{Decode, Encode, MaxEncodedLen};
+use frame_support::dispatch::DispatchError;
+use frame_support::traits::Get;
+use pallet_contracts::chain_extension::{
+    ChainExtension, Environment, Ext, InitState, RegisteredChainExtension, RetVal,
+};
+use sp_std::marker::PhantomData;
+
+use crate::{BoundedProof, Config, FileOwners, Pallet, WeightInfo};
+
+const FILE_EXISTS: u16 = 1;
+const FILE_OWNER: u16 = 2;
+const VERIFY_PROOF: u16 = 3;
+
+/// Input of the `verify_proof` function.
+#[derive(Encode, Decode, MaxEncodedLen)]
+struct VerifyProofInput<Hash> {
+    merkle_root: Hash,
+    position: u64,
+    leaf: [u8; 32],
+    proof: BoundedProof,
+}
+
+/// Chain extension exposing `file_exists`, `file_owner` and `verify_proof`.
+pub struct FileServerExtension<T>(PhantomData<T>);
+
+impl<T> Default for FileServerExtension<T> {
+    fn default() -> Self {
+        Self(PhantomData)
+    }
+}
+
+impl<T> ChainExtension<T> for FileServerExtension<T>
+where
+    T: pallet_contracts::Config + Config,
+{
+    fn call<E: Ext<T = T>>(
+        &mut self,
+        env: Environment<E, InitState>,
+    ) -> Result<RetVal, DispatchError> {
+        let mut env = env.buf_in_buf_out();
+        match env.func_id() {
+            FILE_EXISTS => {
+                let merkle_root: T::Hash = env.read_as()?;
+                env.charge_weight(T::DbWeight::get().reads(1))?;
+                let exists = FileOwners::<T>::contains_key(merkle_root);
+                env.write(&exists.encode(), false, None)?;
+            }
+            FILE_OWNER => {
+                let merkle_root: T::Hash = env.read_as()?;
+                env.charge_weight(T::DbWeight::get().reads(1))?;
+                let owner = FileOwners::<T>::get(merkle_root);
+                env.write(&owner.encode(), false, None)?;
+            }
+            VERIFY_PROOF => {
+                let input: VerifyProofInput<T::Hash> = env.read_as()?;
+                env.charge_weight(<T as Config>::WeightInfo::verify_proof(
+                    input.proof.len() as u32
+                ))?;
+                let valid = Pallet::<T>::verify_proof(
+                    input.merkle_root,
+                    input.position,
+                    &input.leaf,
+                    &input.proof,
+                );
+                env.write(&valid.encode(), false, None)?;
+            }
+            _ => return Err(DispatchError::Other("Unknown trustless file server function")),
+        }
+        Ok(RetVal::Converging(0))
+    }
+}
+
+impl<T> RegisteredChainExtension<T> for FileServerExtension<T>
+where
+    T: pallet_contracts::Config + Config,
+{
+    /// `tf` in ASCII.
+    const ID: u16 = 0x7466;
+}
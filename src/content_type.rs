@@ -0,0 +1,102 @@
+//! Lightweight detection of the type of uploaded contents out of their first bytes, so runtimes
+//! can restrict what gets registered through [`Config::ContentPolicy`](crate::Config).
+use codec::{Decode, Encode, MaxEncodedLen};
+use frame_support::traits::Contains;
+use frame_support::RuntimeDebug;
+use scale_info::TypeInfo;
+
+/// Type of a file, as guessed from its magic bytes.
+#[derive(Clone, Copy, Encode, Decode, Eq, PartialEq, RuntimeDebug, TypeInfo, MaxEncodedLen)]
+pub enum ContentType {
+    Png,
+    Jpeg,
+    Gif,
+    Webp,
+    Pdf,
+    Zip,
+    Gzip,
+    Wasm,
+    /// Linux and most Unix executables.
+    Elf,
+    /// Windows executables and libraries.
+    PortableExecutable,
+    /// macOS executables and libraries.
+    MachO,
+    /// Scripts starting with a `#!` interpreter line.
+    Script,
+    /// Anything not recognized, including plain text.
+    Unknown,
+}
+
+/// Magic bytes found at the beginning of each recognized type.
+const SIGNATURES: &[(&[u8], ContentType)] = &[
+    (b"\x89PNG\r\n\x1a\n", ContentType::Png),
+    (b"\xff\xd8\xff", ContentType::Jpeg),
+    (b"GIF87a", ContentType::Gif),
+    (b"GIF89a", ContentType::Gif),
+    (b"%PDF-", ContentType::Pdf),
+    (b"PK\x03\x04", ContentType::Zip),
+    (b"\x1f\x8b", ContentType::Gzip),
+    (b"\0asm", ContentType::Wasm),
+    (b"\x7fELF", ContentType::Elf),
+    (b"MZ", ContentType::PortableExecutable),
+    (b"\xfe\xed\xfa\xce", ContentType::MachO),
+    (b"\xfe\xed\xfa\xcf", ContentType::MachO),
+    (b"\xce\xfa\xed\xfe", ContentType::MachO),
+    (b"\xcf\xfa\xed\xfe", ContentType::MachO),
+    (b"#!", ContentType::Script),
+];
+
+impl ContentType {
+    /// Guesses the type of `bytes` out of its first bytes.
+    pub fn sniff(bytes: &[u8]) -> Self {
+        // RIFF containers carry the actual format after the chunk size
+        if bytes.len() >= 12 && &bytes[..4] == b"RIFF" && &bytes[8..12] == b"WEBP" {
+            return Self::Webp;
+        }
+        SIGNATURES
+            .iter()
+            .find(|(magic, _)| bytes.starts_with(magic))
+            .map_or(Self::Unknown, |(_, content_type)| *content_type)
+    }
+
+    /// Whether the content can be run as a program.
+    pub fn is_executable(&self) -> bool {
+        matches!(self, Self::Elf | Self::PortableExecutable | Self::MachO | Self::Script)
+    }
+}
+
+/// Content policy accepting everything but executables.
+pub struct DenyExecutables;
+
+impl Contains<ContentType> for DenyExecutables {
+    fn contains(content_type: &ContentType) -> bool {
+        !content_type.is_executable()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_sniff_should_recognize_magic_bytes() {
+        let png = include_bytes!("../img/substrate.png");
+        assert_eq!(ContentType::sniff(png), ContentType::Png);
+        assert_eq!(ContentType::sniff(b"\x7fELF\x02\x01\x01"), ContentType::Elf);
+        assert_eq!(ContentType::sniff(b"MZ\x90\x00"), ContentType::PortableExecutable);
+        assert_eq!(ContentType::sniff(b"#!/bin/sh\nexit 0"), ContentType::Script);
+        assert_eq!(ContentType::sniff(b"RIFF\x24\x00\x00\x00WEBPVP8 "), ContentType::Webp);
+        assert_eq!(ContentType::sniff(b"RIFF\x24\x00\x00\x00WAVEfmt "), ContentType::Unknown);
+        assert_eq!(ContentType::sniff(b"hello world"), ContentType::Unknown);
+        assert_eq!(ContentType::sniff(b""), ContentType::Unknown);
+    }
+
+    #[test]
+    fn test_deny_executables() {
+        assert!(DenyExecutables::contains(&ContentType::Png));
+        assert!(DenyExecutables::contains(&ContentType::Unknown));
+        assert!(!DenyExecutables::contains(&ContentType::Elf));
+        assert!(!DenyExecutables::contains(&ContentType::Script));
+    }
+}
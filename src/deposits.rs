@@ -0,0 +1,127 @@
+use frame_support::dispatch::{DispatchError, DispatchResult};
+use frame_support::sp_runtime::traits::{AccountIdConversion, Saturating};
+use frame_support::traits::tokens::fungible::MutateHold;
+use frame_support::traits::tokens::fungibles::Mutate;
+use frame_support::traits::tokens::{
+    BalanceConversion, Fortitude, Precision, Preservation, Restriction,
+};
+use frame_support::traits::Get;
+
+use crate::{
+    AssetIdOf, BalanceOf, Config, Deposit, DepositOf, Error, HeldBalance, HoldReason, Pallet,
+};
+
+impl<T: Config> Pallet<T> {
+    /// Account holding the deposits paid in assets.
+    pub fn account_id() -> T::AccountId {
+        T::PalletId::get().into_account_truncating()
+    }
+
+    /// Deposit required to register a file of `size` bytes, in the native currency.
+    pub fn deposit_for(size: u32) -> BalanceOf<T> {
+        let per_byte = T::PriceOracle::get().unwrap_or_else(T::DepositPerByte::get);
+        T::DepositBase::get().saturating_add(per_byte.saturating_mul(size.into()))
+    }
+
+    /// Deposit required to register a name of `len` bytes, in the native currency.
+    pub fn name_deposit(len: u32) -> BalanceOf<T> {
+        T::NameDeposit::get()
+            .saturating_add(T::NameDepositPerByte::get().saturating_mul(len.into()))
+    }
+
+    /// Takes the deposit of a file of `size` bytes from `who`: held from its native balance, or
+    /// transferred to the pallet account if paid in `asset`.
+    pub(crate) fn take_deposit(
+        who: &T::AccountId,
+        size: u32,
+        asset: Option<AssetIdOf<T>>,
+    ) -> Result<DepositOf<T>, DispatchError> {
+        let amount = Self::deposit_for(size);
+        let Some(id) = asset else {
+            Self::hold(HoldReason::FileDeposit, who, amount)?;
+            return Ok(Deposit::Native(amount));
+        };
+        let amount = T::BalanceToAssetBalance::to_asset_balance(amount, id)
+            .map_err(|_| Error::<T>::DepositConversionFailed)?;
+        T::Assets::transfer(id, who, &Self::account_id(), amount, Preservation::Preserve)?;
+        Ok(Deposit::Asset { id, amount })
+    }
+
+    /// Gives back to `who` a deposit previously taken with `take_deposit`.
+    pub(crate) fn release_deposit(
+        who: &T::AccountId,
+        deposit: DepositOf<T>,
+    ) -> Result<(), DispatchError> {
+        match deposit {
+            Deposit::Native(amount) => {
+                Self::release(HoldReason::FileDeposit, who, amount);
+            }
+            Deposit::Asset { id, amount } => {
+                T::Assets::transfer(
+                    id,
+                    &Self::account_id(),
+                    who,
+                    amount,
+                    Preservation::Expendable,
+                )?;
+            }
+        }
+        Ok(())
+    }
+    /// Holds `amount` of the native balance of `who` for `reason`.
+    pub(crate) fn hold(
+        reason: HoldReason,
+        who: &T::AccountId,
+        amount: BalanceOf<T>,
+    ) -> DispatchResult {
+        T::NativeBalance::hold(&reason.into(), who, amount)?;
+        HeldBalance::<T>::mutate(|held| held.saturating_accrue(amount));
+        Ok(())
+    }
+
+    /// Releases up to `amount` held from `who` for `reason`.
+    pub(crate) fn release(reason: HoldReason, who: &T::AccountId, amount: BalanceOf<T>) {
+        let released =
+            T::NativeBalance::release(&reason.into(), who, amount, Precision::BestEffort)
+                .unwrap_or_default();
+        HeldBalance::<T>::mutate(|held| held.saturating_reduce(released));
+    }
+
+    /// Burns up to `amount` held from `who` for `reason`, returning what could not be burnt.
+    pub(crate) fn burn_held(
+        reason: HoldReason,
+        who: &T::AccountId,
+        amount: BalanceOf<T>,
+    ) -> BalanceOf<T> {
+        let burnt = T::NativeBalance::burn_held(
+            &reason.into(),
+            who,
+            amount,
+            Precision::BestEffort,
+            Fortitude::Force,
+        )
+        .unwrap_or_default();
+        HeldBalance::<T>::mutate(|held| held.saturating_reduce(burnt));
+        amount.saturating_sub(burnt)
+    }
+
+    /// Moves `amount` held from `from` for `reason` to `to`, where it stays held for the same
+    /// reason.
+    pub(crate) fn transfer_held(
+        reason: HoldReason,
+        from: &T::AccountId,
+        to: &T::AccountId,
+        amount: BalanceOf<T>,
+    ) -> DispatchResult {
+        T::NativeBalance::transfer_on_hold(
+            &reason.into(),
+            from,
+            to,
+            amount,
+            Precision::Exact,
+            Restriction::OnHold,
+            Fortitude::Polite,
+        )?;
+        Ok(())
+    }
+}
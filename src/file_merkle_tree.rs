@@ -2,97 +2,296 @@ use core::mem;
 
 use codec::{Decode, Encode, EncodeLike, MaxEncodedLen};
 use frame_support::pallet_prelude::ConstU32;
-use frame_support::BoundedVec;
+use frame_support::{BoundedVec, RuntimeDebug};
 use scale_info::build::Fields;
 use scale_info::{Path, Type, TypeInfo};
 use sp_io::hashing::sha2_256;
 use sp_std::vec;
 use sp_std::vec::Vec;
 
-/// File chunks to build the merkle tree are hardcoded to 1KB
+/// Default minimum size of the file chunks used to build the merkle tree: 1KB
 const DEFAULT_CHUNK_SIZE: usize = 1024;
+/// Default number of pieces files are split in, as long as chunks are big enough.
+const DEFAULT_TARGET_PIECES: u32 = MAX_PIECES;
+/// Size of the blocks BitTorrent v2 (BEP-52) builds the per-file merkle tree out of: 16KB
+pub const BEP52_BLOCK_SIZE: u32 = 16 * 1024;
 /// Length of a sha256 hash, in bytes.
 const HASH_SIZE: usize = 32;
 /// Maximum number of pieces the merkle tree can have
 const MAX_MERKLE_TREE_NODES: u32 = 64;
 /// Maximum size of the merkle tree
 const MAX_MERKLE_TREE_SIZE: u32 = MAX_MERKLE_TREE_NODES * HASH_SIZE as u32;
+/// Maximum number of pieces a file can be split in: a totally balanced binary tree with this many
+/// leaves has exactly `MAX_MERKLE_TREE_NODES - 1` nodes.
+pub const MAX_PIECES: u32 = (MAX_MERKLE_TREE_NODES + 1) / 2;
+/// Maximum number of siblings in a merkle proof, i.e. the depth of a tree with [`MAX_PIECES`]
+/// leaves.
+pub const MAX_TREE_DEPTH: u32 = MAX_PIECES.next_power_of_two().trailing_zeros();
+
+/// A sibling hash in a merkle proof.
+pub type ProofNode = [u8; HASH_SIZE];
+/// Maximum length of a merkle proof, derived from [`MAX_PIECES`] through [`MAX_TREE_DEPTH`].
+pub type MaxProofLen = ConstU32<MAX_TREE_DEPTH>;
+/// Merkle proof of a piece, bounded by the deepest tree a file can have.
+pub type BoundedProof = BoundedVec<ProofNode, MaxProofLen>;
 /// In case the number of bytes is not a power of two, we fill with zeroes.
 const CHUNK_FILLER: [u8; 32] = [0u8; 32];
 
-fn calculate_chunk_size(file_size: usize) -> usize {
-    let mut chunk_size = file_size / 64;
-    if chunk_size < DEFAULT_CHUNK_SIZE {
-        // minimum chunk size is 1KB
-        chunk_size = DEFAULT_CHUNK_SIZE;
+/// Parameters of the heuristic choosing the chunk size of a file.
+///
+/// Files are split in `target_pieces` chunks, as long as the resulting chunk size lies within
+/// `min_chunk_size` and `max_chunk_size`. Bigger chunks mean bigger PoVs whenever a chunk is
+/// verified on chain, while more pieces mean longer proofs.
+///
+/// If `bep52` is set, the heuristic is ignored and files are split in blocks of
+/// [`BEP52_BLOCK_SIZE`] bytes, hashing the last one as is instead of zero-padding it. The merkle
+/// root is then the `pieces root` BitTorrent v2 clients compute for the same file.
+///
+/// Otherwise, if `ipfs_chunk_size` is set, the heuristic is ignored as well and files are split
+/// in chunks of exactly that many bytes, as `ipfs add --chunker=size-<n>` does, so that the CIDs
+/// of the chunks are those of the blocks of the same file added to IPFS independently.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct ChunkingParams {
+    pub min_chunk_size: u32,
+    pub max_chunk_size: u32,
+    pub target_pieces: u32,
+    pub bep52: bool,
+    pub ipfs_chunk_size: Option<u32>,
+}
+
+impl Default for ChunkingParams {
+    fn default() -> Self {
+        Self {
+            min_chunk_size: DEFAULT_CHUNK_SIZE as u32,
+            max_chunk_size: u32::MAX,
+            target_pieces: DEFAULT_TARGET_PIECES,
+            bep52: false,
+            ipfs_chunk_size: None,
+        }
     }
+}
+
+fn calculate_chunk_size(file_size: u64, params: &ChunkingParams) -> usize {
+    if params.bep52 {
+        return BEP52_BLOCK_SIZE as usize;
+    }
+    if let Some(chunk_size) = params.ipfs_chunk_size {
+        return chunk_size.max(1) as usize;
+    }
+    let chunk_size = file_size / params.target_pieces.max(1) as u64;
+    // the minimum prevails over the maximum if they are misconfigured
     chunk_size
+        .min(params.max_chunk_size as u64)
+        .max(params.min_chunk_size.max(1) as u64) as usize
+}
+
+/// Pads the `pieces` leaf hashes in `tree` up to a power of two and appends the inner nodes of
+/// the tree, level by level, up to the merkle root.
+fn build_inner_nodes(tree: &mut Vec<u8>, pieces: usize) {
+    // make the tree a totally balanced binary tree
+    let mut num_items = pieces.next_power_of_two();
+    for _ in 0..(num_items - pieces) {
+        tree.extend_from_slice(&CHUNK_FILLER);
+    }
+    let mut pos = 0;
+    while num_items > 1 {
+        // every pair of siblings is hashed as a chunk of two hashes
+        let level = &tree[(pos * HASH_SIZE)..((pos + num_items) * HASH_SIZE)];
+        let parents = hash_chunks(level, HASH_SIZE * 2, false).concat();
+        tree.extend_from_slice(&parents);
+        pos += num_items;
+        num_items /= 2;
+    }
+}
+
+/// Hash of a chunk as a leaf of the tree. The last chunk of the file is zero-padded up to
+/// `chunk_size`, unless `bep52` is set.
+// only the host side of the batch hashing interface uses it when the feature is enabled
+#[cfg(any(feature = "std", not(feature = "batch-hashing")))]
+pub(crate) fn hash_leaf(chunk: &[u8], chunk_size: usize, bep52: bool) -> [u8; HASH_SIZE] {
+    // BEP-52 hashes the last block as is
+    if chunk.len() == chunk_size || bep52 {
+        return sha2_256(chunk);
+    }
+    let mut padded = vec![0u8; chunk_size];
+    padded[..chunk.len()].copy_from_slice(chunk);
+    sha2_256(&padded)
 }
 
-fn calculate_has_boundary(file_size: usize) -> bool {
-    file_size % calculate_chunk_size(file_size) != 0
+/// Hashes of the chunks of `chunk_size` bytes of `data`, in order, as [`hash_leaf`] does.
+#[cfg(not(feature = "batch-hashing"))]
+fn hash_chunks(data: &[u8], chunk_size: usize, bep52: bool) -> Vec<[u8; HASH_SIZE]> {
+    data.chunks(chunk_size)
+        .map(|chunk| hash_leaf(chunk, chunk_size, bep52))
+        .collect()
 }
 
-fn calculate_pieces(file_size: usize) -> u32 {
-    let chunk_size = calculate_chunk_size(file_size);
-    let mut pieces = file_size / chunk_size;
-    if calculate_has_boundary(file_size) {
+/// Hashes of the chunks of `chunk_size` bytes of `data`, in order, as [`hash_leaf`] does, all of
+/// them in a single call to the host.
+#[cfg(feature = "batch-hashing")]
+fn hash_chunks(data: &[u8], chunk_size: usize, bep52: bool) -> Vec<[u8; HASH_SIZE]> {
+    crate::hashing::batch_hashing::sha2_256_chunks(data, chunk_size as u32, bep52)
+}
+
+/// Leaf hashes of the chunks of `file_bytes`, in order.
+#[cfg(not(feature = "parallel"))]
+fn leaf_hashes(file_bytes: &[u8], chunk_size: usize, bep52: bool) -> Vec<[u8; HASH_SIZE]> {
+    hash_chunks(file_bytes, chunk_size, bep52)
+}
+
+/// Leaf hashes of the chunks of `file_bytes`, in order, hashed across the threads of the rayon
+/// pool. Only meant for tooling building the trees of big files: the runtime never enables it.
+#[cfg(feature = "parallel")]
+fn leaf_hashes(file_bytes: &[u8], chunk_size: usize, bep52: bool) -> Vec<[u8; HASH_SIZE]> {
+    use rayon::prelude::*;
+
+    file_bytes
+        .par_chunks(chunk_size)
+        .map(|chunk| hash_leaf(chunk, chunk_size, bep52))
+        .collect()
+}
+
+fn calculate_has_boundary(file_size: u64, chunk_size: usize) -> bool {
+    file_size % chunk_size as u64 != 0
+}
+
+fn calculate_pieces(file_size: u64, chunk_size: usize) -> u32 {
+    let mut pieces = file_size / chunk_size as u64;
+    if calculate_has_boundary(file_size, chunk_size) {
         pieces += 1;
     }
-    pieces as u32
+    pieces.try_into().unwrap_or(u32::MAX)
+}
+
+/// Number of pieces a file of `file_size` bytes would be split in according to `params`, without
+/// hashing anything. The tree can only be built if it is at most [`MAX_PIECES`].
+pub fn pieces_for(file_size: u64, params: &ChunkingParams) -> u32 {
+    calculate_pieces(file_size, calculate_chunk_size(file_size, params))
+}
+
+/// Size of the chunks a file of `file_size` bytes would be split in according to `params`.
+pub fn chunk_size_for(file_size: u64, params: &ChunkingParams) -> u32 {
+    calculate_chunk_size(file_size, params) as u32
+}
+
+/// Computes back the merkle root out of a leaf hash and its merkle proof, and compares it with
+/// `merkle_root`.
+///
+/// The leaf is the hash of the chunk as it is stored in the tree, which for the last piece
+/// means the hash of the chunk zero-padded up to the chunk size, unless the tree follows BEP-52.
+/// Proofs longer than [`MaxProofLen`] are rejected without hashing anything.
+pub fn verify_proof<P: AsRef<[u8]>>(
+    merkle_root: &[u8],
+    position: u64,
+    leaf: &[u8; HASH_SIZE],
+    proof: &[P],
+) -> bool {
+    if proof.len() > MAX_TREE_DEPTH as usize {
+        return false;
+    }
+    let mut index = position;
+    let mut current = *leaf;
+    for sibling in proof {
+        let sibling = sibling.as_ref();
+        if sibling.len() != HASH_SIZE {
+            return false;
+        }
+        let mut pair = [0u8; HASH_SIZE * 2];
+        if index % 2 == 0 {
+            pair[..HASH_SIZE].copy_from_slice(&current);
+            pair[HASH_SIZE..].copy_from_slice(sibling);
+        } else {
+            pair[..HASH_SIZE].copy_from_slice(sibling);
+            pair[HASH_SIZE..].copy_from_slice(&current);
+        }
+        current = sha2_256(&pair);
+        index /= 2;
+    }
+    // a position beyond the tree width would not have been consumed by the proof
+    index == 0 && current.as_slice() == merkle_root
 }
 
 /// Represents the data structure of a merkle tree.
 /// It includes also the raw file content.
-#[derive(Default, Clone, PartialEq)]
+#[derive(Clone, PartialEq, Eq, RuntimeDebug)]
 pub struct FileMerkleTree {
     pub merkle_tree: BoundedVec<u8, ConstU32<MAX_MERKLE_TREE_SIZE>>,
-    pub file_size: usize,
+    pub file_size: u64,
+    /// Size of the chunks the file was split in, fixed at upload time.
+    pub chunk_size: u32,
     pub boundary_hash: Option<BoundedVec<u8, ConstU32<32>>>,
 }
 
+impl Default for FileMerkleTree {
+    fn default() -> Self {
+        Self {
+            merkle_tree: Default::default(),
+            file_size: 0,
+            chunk_size: DEFAULT_CHUNK_SIZE as u32,
+            boundary_hash: None,
+        }
+    }
+}
+
 impl MaxEncodedLen for FileMerkleTree {
     fn max_encoded_len() -> usize {
-        mem::size_of::<usize>() + BoundedVec::<u8, ConstU32<64>>::max_encoded_len()
+        mem::size_of::<u64>()
+            + mem::size_of::<u32>()
+            + HASH_SIZE
+            + BoundedVec::<u8, ConstU32<MAX_MERKLE_TREE_SIZE>>::max_encoded_len()
     }
 }
 
 impl Encode for FileMerkleTree {
     fn encode(&self) -> Vec<u8> {
-        let file_size = self.file_size.to_le_bytes();
-        let mut result = Vec::from(file_size.as_slice());
+        let mut result = Vec::from(self.file_size.to_le_bytes().as_slice());
+        result.extend_from_slice(&self.chunk_size.to_le_bytes());
         if let Some(boundary) = &self.boundary_hash {
             result.extend_from_slice(boundary.as_slice());
         }
-        result.extend_from_slice(&self.merkle_tree);
+        // length prefixed, so that trees can be followed by other values
+        self.merkle_tree.encode_to(&mut result);
         result
     }
 }
 
 impl Decode for FileMerkleTree {
     fn decode<I: codec::Input>(input: &mut I) -> Result<Self, codec::Error> {
-        let mut buff = [0u8; 4];
-        input.read(&mut buff)?;
-        let file_size = u32::from_le_bytes(buff);
-        let boundary_hash = if calculate_has_boundary(file_size as usize) {
-            let mut bytes = vec![0u8; HASH_SIZE];
-            input.read(&mut bytes).unwrap();
-            Some(bytes.try_into().unwrap())
-        } else {
-            None
-        };
+        let mut buff = [0u8; 8];
         input.read(&mut buff)?;
-        let merkle_tree_len = input.remaining_len()?.unwrap();
-        let mut bytes = vec![0u8; merkle_tree_len];
-        input.read(&mut bytes)?;
+        let file_size = u64::from_le_bytes(buff);
+        let (chunk_size, boundary_hash) = decode_chunking(input, file_size)?;
         Ok(FileMerkleTree {
-            file_size: file_size as usize,
-            merkle_tree: bytes.try_into().unwrap(),
+            file_size,
+            chunk_size,
+            merkle_tree: BoundedVec::decode(input).map_err(|_| "Merkle tree too large")?,
             boundary_hash,
         })
     }
 }
 
+/// Decodes the chunk size of a file of `file_size` bytes, followed by the hash of its last chunk
+/// if it is not a full one.
+pub(crate) fn decode_chunking<I: codec::Input>(
+    input: &mut I,
+    file_size: u64,
+) -> Result<(u32, Option<BoundedVec<u8, ConstU32<32>>>), codec::Error> {
+    let mut buff = [0u8; 4];
+    input.read(&mut buff)?;
+    let chunk_size = u32::from_le_bytes(buff);
+    if chunk_size == 0 {
+        return Err("Invalid chunk size".into());
+    }
+    let boundary_hash = if calculate_has_boundary(file_size, chunk_size as usize) {
+        let mut bytes = vec![0u8; HASH_SIZE];
+        input.read(&mut bytes)?;
+        Some(bytes.try_into().map_err(|_| "Invalid boundary hash")?)
+    } else {
+        None
+    };
+    Ok((chunk_size, boundary_hash))
+}
+
 impl TypeInfo for FileMerkleTree {
     type Identity = Self;
 
@@ -111,67 +310,40 @@ impl TypeInfo for FileMerkleTree {
 impl EncodeLike for FileMerkleTree {}
 
 impl FileMerkleTree {
-    /// Constructs a `FileMerkleTree` out of the provided file bytes.
-    /// It builds the whole merkle tree and keeps file contents.
-    pub fn new(file_bytes: &[u8]) -> Self {
-        let chunk_size = calculate_chunk_size(file_bytes.len());
-        let chunks = file_bytes.chunks(chunk_size);
-        let pieces = chunks.len();
-        let mut boundary_hash = None;
-        let mut tree = chunks
-            .map(|chunk| {
-                if chunk.len() != chunk_size {
-                    // process last chunk
-                    boundary_hash = Some(sha2_256(chunk).to_vec().try_into().unwrap());
-                    let mut result = vec![0u8; chunk_size];
-                    for (index, byte) in chunk.iter().enumerate() {
-                        result[index] = *byte;
-                    }
-                    sha2_256(result.as_slice())
-                } else {
-                    sha2_256(chunk)
-                }
-            })
-            .fold(Vec::<u8>::new(), |mut acc, hash| {
-                acc.append(&mut hash.to_vec());
-                acc
-            });
-        // make the tree a totally balanced binary tree
-        let mut num_items = pieces.next_power_of_two();
-        for _ in 0..(num_items - pieces) {
-            tree.extend_from_slice(&CHUNK_FILLER);
-        }
-        let mut pos = 0;
-        while num_items > 1 {
-            for i in (pos..(num_items + pos)).step_by(2) {
-                let slice1 = &tree[(i * HASH_SIZE)..((i + 1) * HASH_SIZE)];
-                let slice2 = &tree[((i + 1) * HASH_SIZE)..((i + 2) * HASH_SIZE)];
-                let mut result = Vec::with_capacity(HASH_SIZE * 2);
-                result.extend_from_slice(slice1);
-                result.extend_from_slice(slice2);
-                let hash = sha2_256(result.as_slice());
-                tree.extend_from_slice(&hash);
-            }
-            pos += num_items;
-            num_items /= 2;
-        }
+    /// Builds the tree of `file_bytes`, choosing the chunk size according to `params`.
+    ///
+    /// Panics if the file is split in more than [`MAX_PIECES`] pieces, which callers have to
+    /// check beforehand with [`pieces_for`].
+    pub fn new(file_bytes: &[u8], params: &ChunkingParams) -> Self {
+        let chunk_size = calculate_chunk_size(file_bytes.len() as u64, params);
+        let pieces = file_bytes.chunks(chunk_size).len();
+        let boundary_hash = file_bytes
+            .chunks(chunk_size)
+            .last()
+            .filter(|chunk| chunk.len() != chunk_size)
+            .map(|chunk| sha2_256(chunk).to_vec().try_into().unwrap());
+        let mut tree = leaf_hashes(file_bytes, chunk_size, params.bep52).concat();
+        build_inner_nodes(&mut tree, pieces);
         Self {
-            file_size: file_bytes.len(),
-            merkle_tree: tree.try_into().unwrap(),
+            file_size: file_bytes.len() as u64,
+            chunk_size: chunk_size as u32,
+            merkle_tree: tree
+                .try_into()
+                .expect("callers check that files fit in MAX_PIECES pieces; qed"),
             boundary_hash,
         }
     }
 
     pub fn chunk_size(&self) -> usize {
-        calculate_chunk_size(self.file_size)
+        self.chunk_size as usize
     }
 
     pub fn pieces(&self) -> u32 {
-        calculate_pieces(self.file_size)
+        calculate_pieces(self.file_size, self.chunk_size())
     }
 
-    pub fn file_chunk_hash_at(&self, position: u32) -> Option<[u8; HASH_SIZE]> {
-        let pieces = self.pieces();
+    pub fn file_chunk_hash_at(&self, position: u64) -> Option<[u8; HASH_SIZE]> {
+        let pieces = self.pieces() as u64;
         if position >= pieces {
             return None;
         }
@@ -185,32 +357,96 @@ impl FileMerkleTree {
         Some(self.merkle_tree[pos..limit].try_into().unwrap())
     }
 
+    /// Whether the last leaf is the hash of the unpadded last chunk, as BEP-52 mandates. Trees
+    /// of files that split evenly are the same either way.
+    fn has_unpadded_leaf(&self) -> bool {
+        let Some(boundary) = &self.boundary_hash else {
+            return false;
+        };
+        let pos = (self.pieces() as usize - 1) * HASH_SIZE;
+        self.merkle_tree.get(pos..pos + HASH_SIZE) == Some(&boundary[..])
+    }
+
+    /// Whether the merkle root of this tree is also the BitTorrent v2 (BEP-52) `pieces root` of
+    /// the file.
+    pub fn is_bep52(&self) -> bool {
+        self.chunk_size == BEP52_BLOCK_SIZE
+            && (self.boundary_hash.is_none() || self.has_unpadded_leaf())
+    }
+
     /// Returns the merkle root of this file.
     /// The merkle root is stored as the last 32 bytes of the `merkle_tree` array.
     pub fn merkle_root(&self) -> &[u8] {
         &self.merkle_tree[self.merkle_tree.len() - HASH_SIZE..]
     }
 
-    fn find_proof(
-        &self,
-        position: usize,
-        first_index: usize,
-        base: usize,
-        proof: &mut Vec<Vec<u8>>,
-    ) {
-        if base == 1 {
-            // we do not need to return the merkle root
-            return;
+    /// Whether this tree is well formed and splits the file according to `params`: the chunk
+    /// size matches the one `params` would choose and every inner node is the hash of its
+    /// children. Leaves themselves cannot be checked without the file content.
+    pub fn is_valid(&self, params: &ChunkingParams) -> bool {
+        if self.chunk_size() != calculate_chunk_size(self.file_size, params) {
+            return false;
         }
-        let sibling = if position % 2 == 0 {
-            position + 1
-        } else {
-            position - 1
-        };
-        let parent = (position - first_index) / 2 + first_index + base;
-        let hash = self.merkle_tree[sibling * HASH_SIZE..((sibling + 1) * HASH_SIZE)].to_vec();
-        proof.push(hash);
-        self.find_proof(parent, first_index + base, base / 2, proof);
+        if self.boundary_hash.is_some() != calculate_has_boundary(self.file_size, self.chunk_size())
+        {
+            return false;
+        }
+        if self.boundary_hash.is_some() && self.has_unpadded_leaf() != params.bep52 {
+            return false;
+        }
+        let pieces = self.pieces() as usize;
+        let width = pieces.next_power_of_two();
+        if self.merkle_tree.len() != (2 * width - 1) * HASH_SIZE {
+            return false;
+        }
+        let mut rebuilt = self.merkle_tree[..pieces * HASH_SIZE].to_vec();
+        build_inner_nodes(&mut rebuilt, pieces);
+        rebuilt == self.merkle_tree.as_slice()
+    }
+
+    /// Looks for corrupted nodes, identified by their position in [`Self::nodes`]: padding
+    /// leaves other than the filler and inner nodes other than the hash of their children. Returns
+    /// them along with the merkle root rebuilt out of the leaves alone, or `None` if the tree
+    /// does not have as many nodes as its pieces require.
+    pub fn audit(&self) -> Option<(Vec<u32>, [u8; HASH_SIZE])> {
+        let pieces = self.pieces() as usize;
+        let width = pieces.next_power_of_two();
+        if self.merkle_tree.len() != (2 * width - 1) * HASH_SIZE {
+            return None;
+        }
+        let node = |position: usize| &self.merkle_tree[position * HASH_SIZE..][..HASH_SIZE];
+        let mut corrupted: Vec<u32> = (pieces..width)
+            .filter(|position| node(*position) != CHUNK_FILLER)
+            .map(|position| position as u32)
+            .collect();
+        let (mut level, mut level_width) = (0, width);
+        while level_width > 1 {
+            let parents = level + level_width;
+            for i in 0..level_width / 2 {
+                let children = [node(level + 2 * i), node(level + 2 * i + 1)].concat();
+                if sha2_256(&children) != node(parents + i) {
+                    corrupted.push((parents + i) as u32);
+                }
+            }
+            level = parents;
+            level_width /= 2;
+        }
+
+        let mut rebuilt = self.merkle_tree[..pieces * HASH_SIZE].to_vec();
+        build_inner_nodes(&mut rebuilt, pieces);
+        let root = rebuilt[rebuilt.len() - HASH_SIZE..]
+            .try_into()
+            .expect("the tree ends with the merkle root; qed");
+        Some((corrupted, root))
+    }
+
+    /// Returns all the hashes of the tree, level by level, starting with the (padded) leaves and
+    /// ending with the merkle root.
+    pub fn nodes(&self) -> Vec<Vec<u8>> {
+        self.merkle_tree
+            .chunks(HASH_SIZE)
+            .map(|hash| hash.to_vec())
+            .collect()
     }
 
     /// Finds the content and merkle proof of a given piece
@@ -219,26 +455,102 @@ impl FileMerkleTree {
     /// Returns a tuple with the given chunk content and the merkle proof.
     /// The sha256 of the content can be used to compute the merkle root hash
     /// along with the merkle proof.
-    pub fn merkle_proof(&self, piece: u32) -> Option<Vec<Vec<u8>>> {
-        if piece >= self.pieces() {
+    pub fn merkle_proof(&self, piece: u64) -> Option<BoundedProof> {
+        if piece >= self.pieces() as u64 {
             return None;
         }
-        let mut proof = Vec::new();
-        self.find_proof(piece as usize, 0, self.pieces().next_power_of_two() as usize, &mut proof);
-        Some(proof)
+        // below the number of pieces, hence below `MAX_PIECES`
+        tree_proof(&self.merkle_tree, piece as u32, self.pieces())
+    }
+}
+
+fn find_proof(
+    tree: &[u8],
+    position: usize,
+    first_index: usize,
+    base: usize,
+    proof: &mut Vec<ProofNode>,
+) {
+    if base == 1 {
+        // we do not need to return the merkle root
+        return;
+    }
+    let sibling = if position % 2 == 0 {
+        position + 1
+    } else {
+        position - 1
+    };
+    let parent = (position - first_index) / 2 + first_index + base;
+    let hash = tree[sibling * HASH_SIZE..((sibling + 1) * HASH_SIZE)]
+        .try_into()
+        .expect("slice of HASH_SIZE bytes; qed");
+    proof.push(hash);
+    find_proof(tree, parent, first_index + base, base / 2, proof);
+}
+
+/// Merkle proof of the leaf at `position` of a tree with `leaves` leaves, laid out as
+/// [`build_inner_nodes`] does.
+fn tree_proof(tree: &[u8], position: u32, leaves: u32) -> Option<BoundedProof> {
+    let mut proof = Vec::with_capacity(MAX_TREE_DEPTH as usize);
+    find_proof(tree, position as usize, 0, leaves.next_power_of_two() as usize, &mut proof);
+    // trees hold at most `MAX_PIECES` leaves, so proofs never exceed `MaxProofLen`
+    proof.try_into().ok()
+}
+
+/// Builds the merkle tree whose leaves are `leaves`, e.g. the merkle roots of the files of a
+/// bundle, exactly as the tree of a file with those chunk hashes. `None` if there are no leaves
+/// or more than [`MAX_PIECES`].
+fn tree_of(leaves: &[ProofNode]) -> Option<Vec<u8>> {
+    if leaves.is_empty() || leaves.len() > MAX_PIECES as usize {
+        return None;
+    }
+    let mut tree = leaves.concat();
+    build_inner_nodes(&mut tree, leaves.len());
+    Some(tree)
+}
+
+/// Merkle root of the tree whose leaves are `leaves`. See [`merkle_proof_of`].
+pub fn merkle_root_of(leaves: &[ProofNode]) -> Option<ProofNode> {
+    let tree = tree_of(leaves)?;
+    tree[tree.len() - HASH_SIZE..].try_into().ok()
+}
+
+/// Merkle proof of the leaf at `position` of the tree whose leaves are `leaves`, which
+/// [`verify_proof`] checks against [`merkle_root_of`] the same leaves.
+pub fn merkle_proof_of(leaves: &[ProofNode], position: u32) -> Option<BoundedProof> {
+    if position as usize >= leaves.len() {
+        return None;
     }
+    tree_proof(&tree_of(leaves)?, position, leaves.len() as u32)
 }
 
 #[cfg(test)]
 mod test {
+    use frame_support::traits::Get;
     use sp_io::hashing::sha2_256;
 
     use super::*;
 
+    #[test]
+    fn test_leaf_hashes() {
+        let content = (0..5000u32).map(|i| i as u8).collect::<Vec<_>>();
+        let mut padded = content[4096..].to_vec();
+        padded.resize(1024, 0);
+        let mut expected = content[..4096]
+            .chunks(1024)
+            .map(sha2_256)
+            .collect::<Vec<_>>();
+        expected.push(sha2_256(&padded));
+        assert_eq!(leaf_hashes(&content, 1024, false), expected);
+
+        *expected.last_mut().unwrap() = sha2_256(&content[4096..]);
+        assert_eq!(leaf_hashes(&content, 1024, true), expected);
+    }
+
     #[test]
     fn test_merkle_tree_should_work() {
         let content = include_bytes!("../img/substrate.png");
-        let tree = FileMerkleTree::new(content);
+        let tree = FileMerkleTree::new(content, &ChunkingParams::default());
 
         // check sizes
         let chunk_size = tree.chunk_size();
@@ -247,7 +559,7 @@ mod test {
 
         // check hashes
         for (index, chunk) in content.chunks(chunk_size).enumerate() {
-            assert_eq!(tree.file_chunk_hash_at(index as u32), Some(sha2_256(chunk)));
+            assert_eq!(tree.file_chunk_hash_at(index as u64), Some(sha2_256(chunk)));
         }
         assert_eq!(tree.file_chunk_hash_at(12), None);
 
@@ -261,8 +573,305 @@ mod test {
         let first_chunk = content.chunks(chunk_size).next().unwrap();
         let mut current = sha2_256(first_chunk).to_vec();
         for hash in proof {
-            current = sha2_256(&[current, hash].concat()).to_vec();
+            current = sha2_256(&[current, hash.to_vec()].concat()).to_vec();
         }
         assert_eq!(current.as_slice(), merkle_root);
     }
+
+    #[test]
+    fn test_chunk_size_should_follow_params() {
+        let params = ChunkingParams {
+            min_chunk_size: 512,
+            max_chunk_size: 2048,
+            target_pieces: 8,
+            bep52: false,
+            ipfs_chunk_size: None,
+        };
+        assert_eq!(calculate_chunk_size(1024, &params), 512);
+        assert_eq!(calculate_chunk_size(8192, &params), 1024);
+        assert_eq!(calculate_chunk_size(65536, &params), 2048);
+
+        let content = include_bytes!("../img/substrate.png");
+        let tree = FileMerkleTree::new(content, &params);
+        assert_eq!(tree.chunk_size(), 1471);
+        assert_eq!(tree.pieces(), 9);
+    }
+
+    #[test]
+    fn test_pieces_for_should_match_the_tree() {
+        let content = include_bytes!("../img/substrate.png");
+        let params = ChunkingParams::default();
+        assert_eq!(pieces_for(content.len() as u64, &params), 12);
+        assert_eq!(pieces_for(0, &params), 0);
+        let max_size = MAX_PIECES as u64 * DEFAULT_CHUNK_SIZE as u64;
+        assert_eq!(pieces_for(max_size, &params), MAX_PIECES);
+        assert!(pieces_for(max_size + 1, &params) > MAX_PIECES);
+        // files beyond 4 GiB still fit in `MAX_PIECES` pieces, with bigger chunks
+        assert_eq!(pieces_for(5 << 30, &params), MAX_PIECES);
+        assert_eq!(chunk_size_for(5 << 30, &params), 160 << 20);
+
+        let tree = FileMerkleTree::new(&[1u8; MAX_PIECES as usize * DEFAULT_CHUNK_SIZE], &params);
+        assert_eq!(tree.pieces(), MAX_PIECES);
+    }
+
+    #[test]
+    fn test_decoding_should_reject_oversized_trees() {
+        let mut encoded = 1024u64.to_le_bytes().to_vec();
+        encoded.extend_from_slice(&1024u32.to_le_bytes());
+        vec![0u8; MAX_MERKLE_TREE_SIZE as usize + HASH_SIZE].encode_to(&mut encoded);
+        assert!(FileMerkleTree::decode(&mut encoded.as_slice()).is_err());
+    }
+
+    #[test]
+    fn test_encoding_should_keep_large_sizes() {
+        let content = include_bytes!("../img/substrate.png");
+        let mut tree = FileMerkleTree::new(content, &ChunkingParams::default());
+        tree.file_size = 12 * 1024 + (1 << 32);
+        tree.boundary_hash = None;
+        // trees can be followed by other values
+        let encoded = (&tree, 7u8).encode();
+        let decoded = <(FileMerkleTree, u8)>::decode(&mut encoded.as_slice()).unwrap();
+        assert!(decoded == (tree, 7));
+    }
+
+    #[test]
+    fn test_encoding_should_keep_the_chunk_size() {
+        let content = include_bytes!("../img/substrate.png");
+        let params = ChunkingParams {
+            target_pieces: 4,
+            ..Default::default()
+        };
+        let tree = FileMerkleTree::new(content, &params);
+        let decoded = FileMerkleTree::decode(&mut tree.encode().as_slice()).unwrap();
+        assert_eq!(decoded.chunk_size(), 2942);
+        assert_eq!(decoded.pieces(), 5);
+        assert!(decoded == tree);
+    }
+
+    #[test]
+    fn test_is_valid_should_check_params_and_inner_nodes() {
+        let content = include_bytes!("../img/substrate.png");
+        let params = ChunkingParams::default();
+        let tree = FileMerkleTree::new(content, &params);
+        assert!(tree.is_valid(&params));
+        assert!(!tree.is_valid(&ChunkingParams {
+            target_pieces: 4,
+            ..params
+        }));
+
+        // tampered inner node
+        let mut tampered = tree.clone();
+        let mut nodes = tampered.merkle_tree.to_vec();
+        nodes[16 * HASH_SIZE] ^= 1;
+        tampered.merkle_tree = nodes.try_into().unwrap();
+        assert!(!tampered.is_valid(&params));
+
+        // tampered leaf, without updating its parents
+        let mut tampered = tree;
+        let mut nodes = tampered.merkle_tree.to_vec();
+        nodes[0] ^= 1;
+        tampered.merkle_tree = nodes.try_into().unwrap();
+        assert!(!tampered.is_valid(&params));
+    }
+
+    #[test]
+    fn test_audit_should_locate_corrupted_nodes() {
+        let content = include_bytes!("../img/substrate.png");
+        let tree = FileMerkleTree::new(content, &ChunkingParams::default());
+        let root: [u8; HASH_SIZE] = tree.merkle_root().try_into().unwrap();
+        assert_eq!(tree.audit(), Some((vec![], root)));
+
+        // 12 leaves padded up to 16, hence 16 + 8 + 4 + 2 + 1 nodes
+        let corrupt = |positions: &[usize]| {
+            let mut tampered = tree.clone();
+            let mut nodes = tampered.merkle_tree.to_vec();
+            positions
+                .iter()
+                .for_each(|position| nodes[position * HASH_SIZE] ^= 1);
+            tampered.merkle_tree = nodes.try_into().unwrap();
+            tampered.audit().unwrap()
+        };
+        // the parent of a corrupted leaf no longer matches, nor does the rebuilt root
+        let (corrupted, rebuilt) = corrupt(&[0]);
+        assert_eq!(corrupted, vec![16]);
+        assert_ne!(rebuilt, root);
+        // corrupted inner nodes do not match their children nor their parents, but the root is
+        // rebuilt out of the leaves
+        assert_eq!(corrupt(&[13, 20, 30]), (vec![13, 20, 22, 26, 30], root));
+
+        let mut truncated = tree;
+        truncated.merkle_tree.truncate(HASH_SIZE);
+        assert_eq!(truncated.audit(), None);
+    }
+
+    #[test]
+    fn test_nodes_should_be_level_ordered() {
+        let content = include_bytes!("../img/substrate.png");
+        let tree = FileMerkleTree::new(content, &ChunkingParams::default());
+        let nodes = tree.nodes();
+
+        // 16 padded leaves plus 8 + 4 + 2 + 1 inner nodes
+        assert_eq!(nodes.len(), 31);
+        assert_eq!(nodes[0], tree.file_chunk_hash_at(0).unwrap().to_vec());
+        assert_eq!(nodes[12], CHUNK_FILLER.to_vec());
+        assert_eq!(nodes[16], sha2_256(&[nodes[0].clone(), nodes[1].clone()].concat()).to_vec());
+        assert_eq!(nodes[30].as_slice(), tree.merkle_root());
+    }
+
+    #[test]
+    fn test_verify_proof_should_work() {
+        let content = include_bytes!("../img/substrate.png");
+        let tree = FileMerkleTree::new(content, &ChunkingParams::default());
+        let merkle_root = tree.merkle_root();
+        for position in 0..tree.pieces() {
+            let pos = position as usize * HASH_SIZE;
+            let leaf: [u8; HASH_SIZE] = tree.merkle_tree[pos..pos + HASH_SIZE].try_into().unwrap();
+            let position = position as u64;
+            let proof = tree.merkle_proof(position).unwrap();
+            assert!(verify_proof(merkle_root, position, &leaf, &proof));
+            assert!(!verify_proof(merkle_root, position + 1, &leaf, &proof));
+            assert!(!verify_proof(merkle_root, position, &[0u8; HASH_SIZE], &proof));
+        }
+        let leaf: [u8; HASH_SIZE] = tree.merkle_tree[..HASH_SIZE].try_into().unwrap();
+        let proof = tree.merkle_proof(0).unwrap();
+        assert!(!verify_proof(merkle_root, 16, &leaf, &proof));
+    }
+
+    #[test]
+    fn test_proofs_should_fit_the_maximum_depth() {
+        assert_eq!(MAX_TREE_DEPTH, 5);
+        let content = [1u8; MAX_PIECES as usize * DEFAULT_CHUNK_SIZE];
+        let tree = FileMerkleTree::new(&content, &ChunkingParams::default());
+        let proof = tree.merkle_proof(MAX_PIECES as u64 - 1).unwrap();
+        assert_eq!(proof.len(), MAX_TREE_DEPTH as usize);
+        assert_eq!(MaxProofLen::get(), MAX_TREE_DEPTH);
+    }
+
+    #[test]
+    fn test_oversized_proofs_should_be_rejected() {
+        let tree = FileMerkleTree::new(&[1u8; 4 * DEFAULT_CHUNK_SIZE], &ChunkingParams::default());
+        let merkle_root = tree.merkle_root();
+        let leaf: [u8; HASH_SIZE] = tree.merkle_tree[..HASH_SIZE].try_into().unwrap();
+        let mut proof = tree.merkle_proof(0).unwrap().into_inner();
+        assert!(verify_proof(merkle_root, 0, &leaf, &proof));
+        // trailing siblings past the root would otherwise only fail once all of them are hashed
+        proof.resize(MAX_TREE_DEPTH as usize + 1, [0u8; HASH_SIZE]);
+        assert!(!verify_proof(merkle_root, 0, &leaf, &proof));
+    }
+
+    #[test]
+    fn test_bep52_tree_should_match_the_pieces_root() {
+        let params = ChunkingParams {
+            bep52: true,
+            ..Default::default()
+        };
+        // three full blocks plus a partial one
+        let block = BEP52_BLOCK_SIZE as usize;
+        let content: Vec<u8> = (0..3 * block + 100).map(|i| (i % 251) as u8).collect();
+        let tree = FileMerkleTree::new(&content, &params);
+        assert_eq!(tree.chunk_size(), block);
+        assert_eq!(tree.pieces(), 4);
+        assert!(tree.is_bep52());
+        assert!(tree.is_valid(&params));
+        assert!(!tree.is_valid(&ChunkingParams {
+            min_chunk_size: BEP52_BLOCK_SIZE,
+            ..Default::default()
+        }));
+
+        let leaves: Vec<Vec<u8>> = content
+            .chunks(block)
+            .map(|c| sha2_256(c).to_vec())
+            .collect();
+        let left = sha2_256(&[leaves[0].clone(), leaves[1].clone()].concat());
+        let right = sha2_256(&[leaves[2].clone(), leaves[3].clone()].concat());
+        assert_eq!(tree.merkle_root(), sha2_256(&[left, right].concat()));
+
+        // missing leaves are zero hashes
+        let content = &content[..2 * block + 1];
+        let tree = FileMerkleTree::new(content, &params);
+        let leaves: Vec<Vec<u8>> = content
+            .chunks(block)
+            .map(|c| sha2_256(c).to_vec())
+            .collect();
+        let left = sha2_256(&[leaves[0].clone(), leaves[1].clone()].concat());
+        let right = sha2_256(&[leaves[2].clone(), CHUNK_FILLER.to_vec()].concat());
+        assert_eq!(tree.merkle_root(), sha2_256(&[left, right].concat()));
+
+        // a single block is its own root
+        let tree = FileMerkleTree::new(&content[..100], &params);
+        assert_eq!(tree.merkle_root(), sha2_256(&content[..100]));
+        assert!(tree.is_bep52());
+    }
+
+    #[test]
+    fn test_ipfs_chunk_size_should_override_the_heuristic() {
+        let params = ChunkingParams {
+            ipfs_chunk_size: Some(4096),
+            ..Default::default()
+        };
+        assert_eq!(chunk_size_for(100, &params), 4096);
+        assert_eq!(chunk_size_for(1 << 20, &params), 4096);
+        assert_eq!(pieces_for(3 * 4096 + 1, &params), 4);
+        // BEP-52 prevails
+        let params = ChunkingParams {
+            bep52: true,
+            ..params
+        };
+        assert_eq!(chunk_size_for(100, &params), BEP52_BLOCK_SIZE);
+
+        let content = [1u8; 3 * 4096 + 100];
+        let params = ChunkingParams {
+            ipfs_chunk_size: Some(4096),
+            ..Default::default()
+        };
+        let tree = FileMerkleTree::new(&content, &params);
+        assert!(tree.is_valid(&params));
+        assert!(!tree.is_valid(&ChunkingParams::default()));
+        // the last chunk is identified by the hash of its content alone, as in IPFS
+        assert_eq!(tree.file_chunk_hash_at(3), Some(sha2_256(&content[3 * 4096..])));
+    }
+
+    #[test]
+    fn test_padded_trees_should_not_be_bep52() {
+        let params = ChunkingParams {
+            min_chunk_size: BEP52_BLOCK_SIZE,
+            ..Default::default()
+        };
+        let tree = FileMerkleTree::new(&[1u8; BEP52_BLOCK_SIZE as usize + 1], &params);
+        assert_eq!(tree.chunk_size(), BEP52_BLOCK_SIZE as usize);
+        assert!(!tree.is_bep52());
+        assert!(!tree.is_valid(&ChunkingParams {
+            bep52: true,
+            ..params
+        }));
+
+        // without a partial chunk both layouts are the same tree
+        let tree = FileMerkleTree::new(&[1u8; 2 * BEP52_BLOCK_SIZE as usize], &params);
+        assert!(tree.is_bep52());
+    }
+
+    #[test]
+    fn test_proofs_of_arbitrary_leaves_should_verify() {
+        let leaves: Vec<ProofNode> = (0..5u8).map(|i| sha2_256(&[i])).collect();
+        let merkle_root = merkle_root_of(&leaves).unwrap();
+        for (position, leaf) in leaves.iter().enumerate() {
+            let proof = merkle_proof_of(&leaves, position as u32).unwrap();
+            assert_eq!(proof.len(), 3);
+            assert!(verify_proof(&merkle_root, position as u64, leaf, &proof));
+        }
+        assert!(merkle_proof_of(&leaves, 5).is_none());
+        assert!(merkle_root_of(&[]).is_none());
+        assert!(merkle_root_of(&[[0u8; HASH_SIZE]; MAX_PIECES as usize + 1]).is_none());
+
+        // same layout as a file tree with those chunk hashes
+        let content = include_bytes!("../img/substrate.png");
+        let tree = FileMerkleTree::new(content, &ChunkingParams::default());
+        let chunks: Vec<ProofNode> = (0..tree.pieces())
+            .map(|position| {
+                let pos = position as usize * HASH_SIZE;
+                tree.merkle_tree[pos..pos + HASH_SIZE].try_into().unwrap()
+            })
+            .collect();
+        assert_eq!(merkle_root_of(&chunks).unwrap(), tree.merkle_root());
+    }
 }
@@ -0,0 +1,20 @@
+//! Host function hashing all the chunks of a file, or all the nodes of a level of its merkle
+//! tree, in a single call out of the wasm runtime rather than one `sha2_256` call per hash.
+//!
+//! Only compiled with the `batch-hashing` feature. Nodes executing runtimes built with it must
+//! register [`batch_hashing::HostFunctions`] along with the Substrate ones in their executor,
+//! otherwise the runtime fails to instantiate.
+use sp_runtime_interface::runtime_interface;
+use sp_std::vec::Vec;
+
+#[runtime_interface]
+pub trait BatchHashing {
+    /// sha256 of every chunk of `chunk_size` bytes of `data`, in order. The last chunk is
+    /// zero-padded up to `chunk_size`, unless `bep52` is set.
+    fn sha2_256_chunks(data: &[u8], chunk_size: u32, bep52: bool) -> Vec<[u8; 32]> {
+        let chunk_size = chunk_size.max(1) as usize;
+        data.chunks(chunk_size)
+            .map(|chunk| crate::file_merkle_tree::hash_leaf(chunk, chunk_size, bep52))
+            .collect()
+    }
+}
@@ -3,23 +3,53 @@ use alloc::format;
 use alloc::string::String;
 
 use binascii::b32encode;
-use frame_support::log;
 use frame_support::sp_runtime::offchain::http;
 use frame_support::sp_runtime::offchain::http::Request;
+use frame_support::sp_runtime::offchain::Duration;
+use lite_json::JsonValue;
+use sp_io::hashing::sha2_256;
 use sp_std::vec;
 use sp_std::vec::Vec;
 
+/// Tracing target of the requests to IPFS nodes, clusters, gateways and pinning services.
+pub(crate) const IPFS_TARGET: &str = "tfs::ipfs";
+
 const BOUNDARY: &[u8] = b"------BOUNDARY";
+/// Public gateways can be slow, so give up on them after this amount of time.
+const GATEWAY_TIMEOUT_MS: u64 = 10_000;
+/// Pinning or auditing content the node lost means fetching it from the network, so give up
+/// after this amount of time.
+const PIN_TIMEOUT_MS: u64 = 10_000;
+
+/// Multicodec of raw binary blocks, i.e. the chunks.
+const RAW_CODEC: u8 = 0x55;
+/// Multicodec of the protobuf nodes linking the chunks of a UnixFS file.
+const DAG_PB_CODEC: u8 = 0x70;
+/// Length of the binary CIDv1 of a sha256 hashed block.
+pub const CID_LEN: usize = 36;
 
 pub fn ipfs_get_hash_from_sha256(hash: &[u8; 32]) -> String {
-    // CIDv1, raw binary (multicodec), sha2 (hash), digest length (32 bytes)
-    let extra_bytes = vec![0x01, 0x55, 0x12, 0x20];
-    let full_data: Vec<_> = vec![extra_bytes, hash.to_vec()]
-        .into_iter()
-        .flatten()
-        .collect();
+    cid_v1(RAW_CODEC, hash)
+}
+
+/// Binary CIDv1 of a block of the `codec` multicodec whose sha256 is `hash`.
+fn cid_v1_bytes(codec: u8, hash: &[u8; 32]) -> [u8; CID_LEN] {
+    // CIDv1, multicodec, sha2 (hash), digest length (32 bytes)
+    let mut cid = [0u8; CID_LEN];
+    cid[..4].copy_from_slice(&[0x01, codec, 0x12, 0x20]);
+    cid[4..].copy_from_slice(hash);
+    cid
+}
+
+/// Base32 CIDv1 of a block of the `codec` multicodec whose sha256 is `hash`.
+fn cid_v1(codec: u8, hash: &[u8; 32]) -> String {
+    cid_to_string(&cid_v1_bytes(codec, hash))
+}
+
+/// Base32 text form of the binary `cid`, as IPFS gateways take it.
+pub fn cid_to_string(cid: &[u8; CID_LEN]) -> String {
     let mut buff = [0u8; 256];
-    let bytes = b32encode(full_data.as_slice(), &mut buff).unwrap();
+    let bytes = b32encode(cid, &mut buff).unwrap();
     ("b".to_owned() + core::str::from_utf8(bytes).unwrap())
         // remove right equal signs
         .trim_end_matches('=')
@@ -27,41 +57,347 @@ pub fn ipfs_get_hash_from_sha256(hash: &[u8; 32]) -> String {
         .to_lowercase()
 }
 
-fn make_multipart(data: &[u8]) -> Vec<u8> {
-    b"--"
-        .iter()
-        .chain(BOUNDARY)
-        .chain(b"\r\nContent-Disposition: form-data; name=\"file\"\r\nContent-Type: application/octet-stream\r\n\r\n")
-        .chain(data)
-        .chain(b"\r\n--")
-        .chain(BOUNDARY)
-        .chain(b"--\r\n")
-        .copied()
-        .collect()
+/// Appends `value` to `buf` as a protobuf varint.
+fn push_varint(buf: &mut Vec<u8>, mut value: u64) {
+    while value >= 0x80 {
+        buf.push(value as u8 | 0x80);
+        value >>= 7;
+    }
+    buf.push(value as u8);
 }
 
-pub fn ipfs_upload(base_url: &str, data: &[u8]) -> Result<(), http::Error> {
-    let url = base_url.to_owned() + "/api/v0/block/put";
-    let multipart = make_multipart(data);
-    let request = Request::post(&url, vec![multipart.as_slice()]).add_header(
+/// Appends the protobuf `field` holding `bytes`.
+fn push_bytes_field(buf: &mut Vec<u8>, field: u8, bytes: &[u8]) {
+    buf.push((field << 3) | 2);
+    push_varint(buf, bytes.len() as u64);
+    buf.extend_from_slice(bytes);
+}
+
+/// Appends the protobuf `field` holding the integer `value`.
+fn push_varint_field(buf: &mut Vec<u8>, field: u8, value: u64) {
+    buf.push(field << 3);
+    push_varint(buf, value);
+}
+
+/// The dag-pb node linking the raw `chunks` of a file, given by the sha256 and size of each of
+/// them, as the balanced layout of `ipfs add --raw-leaves` builds it for files of up to 174
+/// chunks, more than a file can be split in.
+pub fn unixfs_file_node(chunks: &[([u8; 32], u64)]) -> Vec<u8> {
+    // UnixFS data: type `File`, file size and the size of every chunk
+    let mut data = Vec::new();
+    push_varint_field(&mut data, 1, 2);
+    push_varint_field(&mut data, 3, chunks.iter().map(|(_, size)| size).sum());
+    for (_, size) in chunks {
+        push_varint_field(&mut data, 4, *size);
+    }
+    // dag-pb encodes links first, each with its CID, an empty name and the size of the chunk
+    let mut node = Vec::new();
+    for (hash, size) in chunks {
+        let mut link = Vec::new();
+        push_bytes_field(&mut link, 1, &[&[0x01, RAW_CODEC, 0x12, 0x20][..], &hash[..]].concat());
+        push_bytes_field(&mut link, 2, &[]);
+        push_varint_field(&mut link, 3, *size);
+        push_bytes_field(&mut node, 2, &link);
+    }
+    push_bytes_field(&mut node, 1, &data);
+    node
+}
+
+/// CID of the whole file made of `chunks`, as `ipfs add --cid-version=1 --chunker=size-<n>`
+/// gives it: that of the chunk itself for files of a single chunk, or that of the node built by
+/// [`unixfs_file_node`] otherwise.
+pub fn unixfs_file_cid(chunks: &[([u8; 32], u64)]) -> String {
+    cid_to_string(&unixfs_file_cid_bytes(chunks))
+}
+
+/// Binary form of [`unixfs_file_cid`], as stored along with the layout of files.
+pub fn unixfs_file_cid_bytes(chunks: &[([u8; 32], u64)]) -> [u8; CID_LEN] {
+    match chunks {
+        [(hash, _)] => cid_v1_bytes(RAW_CODEC, hash),
+        _ => cid_v1_bytes(DAG_PB_CODEC, &sha2_256(&unixfs_file_node(chunks))),
+    }
+}
+
+/// Segments of a `multipart/form-data` body holding `data` as its only file. The request writes
+/// them one after the other, so the chunk is never copied into a contiguous body.
+fn multipart_segments(data: &[u8]) -> Vec<&[u8]> {
+    vec![
+        b"--",
+        BOUNDARY,
+        b"\r\nContent-Disposition: form-data; name=\"file\"\r\nContent-Type: application/octet-stream\r\n\r\n",
+        data,
+        b"\r\n--",
+        BOUNDARY,
+        b"--\r\n",
+    ]
+}
+
+/// Body of the multipart requests uploading `data`, as sent over the wire.
+#[cfg(test)]
+pub(crate) fn make_multipart(data: &[u8]) -> Vec<u8> {
+    multipart_segments(data).concat()
+}
+
+/// POST request uploading `data` as a multipart form to `url`.
+fn multipart_request<'a>(url: &'a str, data: &'a [u8]) -> Request<'a, Vec<&'a [u8]>> {
+    Request::post(url, multipart_segments(data)).add_header(
         "Content-Type",
         format!("multipart/form-data; boundary={}", core::str::from_utf8(BOUNDARY).unwrap())
             .as_str(),
-    );
-    let pending = request.send().map_err(|_| http::Error::IoError)?;
+    )
+}
+
+pub fn ipfs_upload(base_url: &str, data: &[u8]) -> Result<(), http::Error> {
+    block_put(&(base_url.to_owned() + "/api/v0/block/put"), data)
+}
+
+/// Uploads the dag-pb `node` linking the chunks of a file, see [`unixfs_file_node`].
+pub fn ipfs_upload_node(base_url: &str, node: &[u8]) -> Result<(), http::Error> {
+    block_put(&(base_url.to_owned() + "/api/v0/block/put?cid-codec=dag-pb"), node)
+}
+
+fn block_put(url: &str, data: &[u8]) -> Result<(), http::Error> {
+    let pending = multipart_request(url, data)
+        .send()
+        .map_err(|_| http::Error::IoError)?;
     let response = pending.wait()?;
     let response_body = response.body();
     let raw_body = response_body.collect::<Vec<u8>>();
     let body = core::str::from_utf8(&raw_body).unwrap();
     if response.code == 200 {
-        log::info!("Chunk successfully uploaded: {}", body);
+        sp_tracing::info!(target: IPFS_TARGET, response = %body, "Block successfully uploaded");
     } else {
-        log::warn!("Unexpected status code: {}.\n{}", response.code, body);
+        sp_tracing::warn!(
+            target: IPFS_TARGET,
+            code = response.code,
+            response = %body,
+            "Unexpected status code uploading a block"
+        );
         return Err(http::Error::Unknown);
     };
     Ok(())
 }
 
+/// Announces `cid` to the DHT so other peers can find the node providing it.
+///
+/// Uses `/api/v0/routing/provide`, falling back to `/api/v0/dht/provide` for nodes running
+/// older Kubo versions.
+pub fn ipfs_provide(base_url: &str, cid: &str) -> Result<(), http::Error> {
+    let code = ipfs_post_command(base_url, "routing/provide", cid)?;
+    let code = if code == 404 {
+        ipfs_post_command(base_url, "dht/provide", cid)?
+    } else {
+        code
+    };
+    if code == 200 {
+        sp_tracing::info!(target: IPFS_TARGET, %cid, "CID successfully announced");
+        Ok(())
+    } else {
+        sp_tracing::warn!(target: IPFS_TARGET, %cid, code, "Unexpected status code announcing");
+        Err(http::Error::Unknown)
+    }
+}
+
+/// Pins `cid` on the IPFS node through `/api/v0/pin/add`, which fetches it from the network if
+/// the node no longer has it.
+pub fn ipfs_pin(base_url: &str, cid: &str) -> Result<(), http::Error> {
+    let url = format!("{}/api/v0/pin/add?arg={}", base_url, cid);
+    let deadline = sp_io::offchain::timestamp().add(Duration::from_millis(PIN_TIMEOUT_MS));
+    let pending = Request::post(&url, Vec::<&[u8]>::new())
+        .deadline(deadline)
+        .send()
+        .map_err(|_| http::Error::IoError)?;
+    let response = pending
+        .try_wait(deadline)
+        .map_err(|_| http::Error::DeadlineReached)??;
+    if response.code != 200 {
+        sp_tracing::warn!(
+            target: IPFS_TARGET,
+            %cid,
+            code = response.code,
+            "Unexpected status code pinning"
+        );
+        return Err(http::Error::Unknown);
+    }
+    Ok(())
+}
+
+/// Fetches the raw block identified by `cid` through `/api/v0/block/get`, which asks the
+/// network for it if the node does not have it.
+pub fn ipfs_block_get(base_url: &str, cid: &str) -> Result<Vec<u8>, http::Error> {
+    let url = format!("{}/api/v0/block/get?arg={}", base_url, cid);
+    let deadline = sp_io::offchain::timestamp().add(Duration::from_millis(PIN_TIMEOUT_MS));
+    let pending = Request::post(&url, Vec::<&[u8]>::new())
+        .deadline(deadline)
+        .send()
+        .map_err(|_| http::Error::IoError)?;
+    let response = pending
+        .try_wait(deadline)
+        .map_err(|_| http::Error::DeadlineReached)??;
+    if response.code != 200 {
+        sp_tracing::warn!(
+            target: IPFS_TARGET,
+            %cid,
+            code = response.code,
+            "Unexpected status code fetching"
+        );
+        return Err(http::Error::Unknown);
+    }
+    Ok(response.body().collect::<Vec<u8>>())
+}
+
+fn ipfs_post_command(base_url: &str, command: &str, arg: &str) -> Result<u16, http::Error> {
+    let url = format!("{}/api/v0/{}?arg={}", base_url, command, arg);
+    let pending = Request::post(&url, Vec::<&[u8]>::new())
+        .send()
+        .map_err(|_| http::Error::IoError)?;
+    let response = pending.wait()?;
+    Ok(response.code)
+}
+
+/// Fetches the raw block identified by `cid` from an IPFS HTTP gateway, e.g. `https://ipfs.io`.
+pub fn gateway_fetch(gateway_url: &str, cid: &str) -> Result<Vec<u8>, http::Error> {
+    let url = format!("{}/ipfs/{}?format=raw", gateway_url.trim_end_matches('/'), cid);
+    let deadline = sp_io::offchain::timestamp().add(Duration::from_millis(GATEWAY_TIMEOUT_MS));
+    let pending = Request::get(&url)
+        .add_header("Accept", "application/vnd.ipld.raw")
+        .deadline(deadline)
+        .send()
+        .map_err(|_| http::Error::IoError)?;
+    let response = pending
+        .try_wait(deadline)
+        .map_err(|_| http::Error::DeadlineReached)??;
+    if response.code != 200 {
+        sp_tracing::warn!(
+            target: IPFS_TARGET,
+            %cid,
+            code = response.code,
+            "Unexpected status code from gateway"
+        );
+        return Err(http::Error::Unknown);
+    }
+    Ok(response.body().collect::<Vec<u8>>())
+}
+
+/// Adds a chunk through the REST API of an [IPFS Cluster](https://ipfscluster.io/) peer, which
+/// pins it on `replication` cluster peers.
+///
+/// Returns the CID of the chunk and the peer ids of the cluster peers it was allocated to.
+pub fn cluster_add(
+    base_url: &str,
+    data: &[u8],
+    replication: u32,
+) -> Result<(String, Vec<String>), http::Error> {
+    // raw leaves and CIDv1 so the resulting CID matches the one advertised by the pallet
+    let url = format!(
+        "{}/add?cid-version=1&raw-leaves=true&replication-min={}&replication-max={}",
+        base_url.trim_end_matches('/'),
+        replication,
+        replication
+    );
+    let pending = multipart_request(&url, data)
+        .send()
+        .map_err(|_| http::Error::IoError)?;
+    let response = pending.wait()?;
+    let raw_body = response.body().collect::<Vec<u8>>();
+    let body = core::str::from_utf8(&raw_body).map_err(|_| http::Error::Unknown)?;
+    if response.code != 200 {
+        sp_tracing::warn!(
+            target: IPFS_TARGET,
+            code = response.code,
+            response = %body,
+            "Unexpected status code from IPFS cluster"
+        );
+        return Err(http::Error::Unknown);
+    }
+    parse_cluster_add(body).ok_or(http::Error::Unknown)
+}
+
+/// The cluster streams one JSON object per added node. Chunks are single raw blocks, so the
+/// first object describes the whole chunk.
+fn parse_cluster_add(body: &str) -> Option<(String, Vec<String>)> {
+    let line = body.lines().find(|line| !line.trim().is_empty())?;
+    let JsonValue::Object(fields) = lite_json::parse_json(line).ok()? else {
+        return None;
+    };
+    let mut cid = None;
+    let mut allocations = Vec::new();
+    for (key, value) in fields {
+        let key: String = key.into_iter().collect();
+        match (key.as_str(), value) {
+            ("cid", JsonValue::String(chars)) => cid = Some(chars.into_iter().collect()),
+            // older cluster versions encode CIDs as `{"/": "<cid>"}`
+            ("cid", JsonValue::Object(link)) => {
+                cid = link.into_iter().find_map(|(_, value)| match value {
+                    JsonValue::String(chars) => Some(chars.into_iter().collect()),
+                    _ => None,
+                })
+            }
+            ("allocations", JsonValue::Array(peers)) => {
+                allocations = peers
+                    .into_iter()
+                    .filter_map(|peer| match peer {
+                        JsonValue::String(chars) => Some(chars.into_iter().collect()),
+                        _ => None,
+                    })
+                    .collect()
+            }
+            _ => {}
+        }
+    }
+    Some((cid?, allocations))
+}
+
+/// Asks a remote pinning service implementing the
+/// [IPFS Pinning Service API](https://ipfs.github.io/pinning-services-api-spec/) to pin `cid`,
+/// e.g. Pinata or web3.storage.
+///
+/// Returns the request id assigned by the service, which can be used to query the pin status.
+pub fn pinning_service_pin(
+    endpoint: &str,
+    token: &str,
+    cid: &str,
+    name: &str,
+) -> Result<String, http::Error> {
+    let url = endpoint.trim_end_matches('/').to_owned() + "/pins";
+    let body = pin_request_body(cid, name);
+    let request = Request::post(&url, vec![body.as_bytes()])
+        .add_header("Content-Type", "application/json")
+        .add_header("Authorization", format!("Bearer {}", token).as_str());
+    let pending = request.send().map_err(|_| http::Error::IoError)?;
+    let response = pending.wait()?;
+    let raw_body = response.body().collect::<Vec<u8>>();
+    let body = core::str::from_utf8(&raw_body).map_err(|_| http::Error::Unknown)?;
+    // the specification answers with `202 Accepted`, but some providers use `200 OK`
+    if response.code != 200 && response.code != 202 {
+        sp_tracing::warn!(
+            target: IPFS_TARGET,
+            %cid,
+            code = response.code,
+            response = %body,
+            "Unexpected status code from pinning service"
+        );
+        return Err(http::Error::Unknown);
+    }
+    parse_request_id(body).ok_or(http::Error::Unknown)
+}
+
+fn pin_request_body(cid: &str, name: &str) -> String {
+    format!("{{\"cid\":\"{}\",\"name\":\"{}\"}}", cid, name)
+}
+
+fn parse_request_id(body: &str) -> Option<String> {
+    match lite_json::parse_json(body).ok()? {
+        JsonValue::Object(fields) => fields.into_iter().find_map(|(key, value)| match value {
+            JsonValue::String(chars) if key.iter().copied().eq("requestid".chars()) => {
+                Some(chars.into_iter().collect())
+            }
+            _ => None,
+        }),
+        _ => None,
+    }
+}
+
 #[cfg(test)]
 mod test {
     use sp_io::hashing::sha2_256;
@@ -77,4 +413,77 @@ mod test {
             "bafkreifzjut3te2nhyekklss27nh3k72ysco7y32koao5eei66wof36n5e"
         );
     }
+
+    #[test]
+    fn test_unixfs_file_node() {
+        let (first, second) = (sha2_256(b"hell"), sha2_256(b"o!"));
+        let link = |hash: &[u8; 32], size| {
+            [
+                &[0x12, 0x2a, 0x0a, 0x24, 0x01, 0x55, 0x12, 0x20][..],
+                &hash[..],
+                &[0x12, 0x00, 0x18, size][..],
+            ]
+            .concat()
+        };
+        let data = [0x08, 0x02, 0x18, 0x06, 0x20, 0x04, 0x20, 0x02];
+        let expected = [
+            link(&first, 4),
+            link(&second, 2),
+            vec![0x0a, 0x08],
+            data.to_vec(),
+        ]
+        .concat();
+        let chunks = [(first, 4), (second, 2)];
+        assert_eq!(unixfs_file_node(&chunks), expected);
+        assert!(unixfs_file_cid(&chunks).starts_with("bafybei"));
+
+        // files of a single chunk are that chunk
+        let hash = sha2_256(b"hello world");
+        assert_eq!(unixfs_file_cid(&[(hash, 11)]), ipfs_get_hash_from_sha256(&hash));
+
+        let mut varint = Vec::new();
+        push_varint(&mut varint, 262144);
+        assert_eq!(varint, vec![0x80, 0x80, 0x10]);
+    }
+
+    #[test]
+    fn test_cluster_add_response() {
+        let response = r#"{"name":"blob","cid":"bafkreifzjut3te2nhyekklss27nh3k72ysco7y32koao5eei66wof36n5e","bytes":11,"size":11,"allocations":["12D3KooWA","12D3KooWB"]}
+"#;
+        assert_eq!(
+            parse_cluster_add(response),
+            Some((
+                "bafkreifzjut3te2nhyekklss27nh3k72ysco7y32koao5eei66wof36n5e".to_owned(),
+                vec!["12D3KooWA".to_owned(), "12D3KooWB".to_owned()]
+            ))
+        );
+        let legacy = r#"{"name":"blob","cid":{"/":"bafkrei"},"size":11}"#;
+        assert_eq!(parse_cluster_add(legacy), Some(("bafkrei".to_owned(), vec![])));
+        assert_eq!(parse_cluster_add(r#"{"name":"blob"}"#), None);
+    }
+
+    #[test]
+    fn test_multipart_segments_borrow_the_data() {
+        let data = b"hello world".as_slice();
+        let segments = multipart_segments(data);
+        assert!(segments
+            .iter()
+            .any(|segment| segment.as_ptr() == data.as_ptr()));
+        assert_eq!(
+            make_multipart(data),
+            b"--------BOUNDARY\r\nContent-Disposition: form-data; name=\"file\"\r\nContent-Type: application/octet-stream\r\n\r\nhello world\r\n--------BOUNDARY--\r\n"
+        );
+    }
+
+    #[test]
+    fn test_pinning_service_messages() {
+        assert_eq!(
+            pin_request_body("bafkrei", "file-0"),
+            "{\"cid\":\"bafkrei\",\"name\":\"file-0\"}"
+        );
+        let response = r#"{"requestid":"UniqueIdOfPinRequest","status":"queued","created":"2020-07-27T17:32:28Z","pin":{"cid":"bafkrei"},"delegates":[]}"#;
+        assert_eq!(parse_request_id(response), Some("UniqueIdOfPinRequest".to_owned()));
+        assert_eq!(parse_request_id(r#"{"status":"queued"}"#), None);
+        assert_eq!(parse_request_id("not json"), None);
+    }
 }
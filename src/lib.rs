@@ -11,7 +11,9 @@
 //! about this protocol can be found [here](https://www.bittorrent.org/beps/bep_0030.html).
 //!
 //! This implementation has the following characteristics:
-//! - It uses a fixed file chunk of 1KB
+//! - Files are split in chunks of at least 1KB by default, tunable through the chunking
+//! parameters of the pallet configuration. The chunk size is fixed once a file is uploaded,
+//! although owners can ask for it to be recomputed with `rechunk_file`.
 //! - The hashing algorithm is `sha256` instead of Bittorrent's original one: `sha1`.
 //! - Files are stored on the blockchain. Bear in mind that this is an overall bad practice and
 //! should be used with caution. A notable improvement to this pallet would actually be to
@@ -23,50 +25,122 @@ extern crate alloc;
 extern crate core;
 
 // Re-export pallet items so that they can be accessed from the crate namespace.
+pub use content_type::{ContentType, DenyExecutables};
+pub use file_merkle_tree::{
+    verify_proof, BoundedProof, ChunkingParams, FileMerkleTree, MaxProofLen, ProofNode, MAX_PIECES,
+    MAX_TREE_DEPTH,
+};
+pub use offchain::{
+    DeadLetter, DeadLetterReason, QueuedUpload, ServedContent, DEAD_LETTERS_KEY, QUEUE_KEY,
+    SERVED_KEY, UPLOAD_RETRIES_KEY,
+};
 pub use pallet::*;
+pub use signed_extension::CheckUploadSize;
+pub use types::*;
 pub use weights::*;
 
-#[cfg(test)]
-mod mock;
+#[cfg(any(test, feature = "test-utils"))]
+pub mod mock;
 
 #[cfg(test)]
 mod tests;
 
 #[cfg(feature = "runtime-benchmarks")]
 mod benchmarking;
+#[cfg(feature = "chain-extension")]
+pub mod chain_extension;
+mod content_type;
+mod deposits;
 mod file_merkle_tree;
+#[cfg(feature = "batch-hashing")]
+pub mod hashing;
 mod ipfs;
+pub mod migrations;
+mod offchain;
+pub mod shamir;
+mod signed_extension;
+#[cfg(feature = "std")]
+pub mod test_vectors;
+pub mod types;
 pub mod weights;
 
+/// Tracing target of the uploads registered by the pallet.
+pub(crate) const UPLOAD_TARGET: &str = "tfs::upload";
+
+/// Maximum number of files listed by a single call to [`Pallet::get_files_paged`], whatever the
+/// limit requested, bounding the state read by runtime API calls.
+pub const MAX_FILES_PER_PAGE: u32 = 1000;
+
+/// Maximum number of files exported by a single call to [`Pallet::export_state`]. Lower than
+/// [`MAX_FILES_PER_PAGE`] as every record carries the whole merkle tree of its file.
+pub const MAX_FILES_PER_EXPORT: u32 = 100;
+
+/// Number of blocks the reports of offchain workers are accepted for after being made.
+pub const REPORT_LONGEVITY: u32 = 5;
+
+/// Key type of the authorities whose offchain workers push files to IPFS and submit status
+/// transactions.
+pub const KEY_TYPE: frame_support::sp_runtime::KeyTypeId =
+    frame_support::sp_runtime::KeyTypeId(*b"tfsv");
+
+/// Keys of the offchain worker authorities, e.g. to be used as [`Config::AuthorityId`]. Authority
+/// nodes hold the private key in their keystore under [`KEY_TYPE`].
+pub mod crypto {
+    use frame_support::sp_runtime::app_crypto::{app_crypto, sr25519};
+
+    app_crypto!(sr25519, super::KEY_TYPE);
+}
+
 #[frame_support::pallet]
 pub mod pallet {
     use alloc::string::{String, ToString};
 
-    use frame_support::log;
     use frame_support::pallet_prelude::*;
-    use frame_support::sp_runtime::offchain::storage::StorageValueRef;
+    use frame_support::sp_runtime::traits::{Hash, Saturating, TrailingZeroInput, Zero};
+    use frame_support::sp_runtime::{RuntimeAppPublic, SaturatedConversion};
+    use frame_support::storage::StoragePrefixedMap;
+    use frame_support::traits::schedule::{self, v3::Named as ScheduleNamed, DispatchTime};
+    use frame_support::traits::tokens::BalanceConversion;
+    use frame_support::traits::tokens::{fungible, fungibles};
+    use frame_support::traits::{
+        Contains, Currency, QueryPreimage, Randomness, ReservableCurrency, SortedMembers,
+        StorePreimage, UnixTime,
+    };
+    use frame_support::PalletId;
+    use frame_system::offchain::SendTransactionTypes;
     use frame_system::pallet_prelude::*;
     use sp_io::offchain_index;
-    use sp_std::vec::Vec;
+    use sp_std::{vec, vec::Vec};
+    use xcm::latest::{
+        send_xcm, Instruction::Transact, Instruction::UnpaidExecution, Junction::Parachain,
+        Junctions::X1, MultiLocation, OriginKind, SendXcm, WeightLimit::Unlimited, Xcm,
+    };
 
-    use crate::file_merkle_tree::FileMerkleTree;
+    use crate::file_merkle_tree::{self, ChunkingParams, FileMerkleTree};
     use crate::ipfs;
+    use crate::offchain::IndexingData;
 
     use super::*;
 
-    const ONCHAIN_TX_KEY: &[u8] = b"pallet_trustless_file_server::indexing1";
+    /// Prefix of the offchain indexing keys holding the contents of the uploaded files. The full
+    /// key is the prefix, a `/` and the SCALE encoded merkle root.
+    pub const ONCHAIN_TX_KEY: &[u8] = b"pallet_trustless_file_server::indexing1";
 
-    #[derive(Debug, Encode, Decode, Default)]
-    struct IndexingData {
-        content: Vec<u8>,
-        chunk_size: u32,
-    }
+    /// Maximum number of aliases followed when resolving the merkle root of a rebuilt tree.
+    const MAX_ALIAS_HOPS: u32 = 8;
+
+    /// Subject of the randomness drawing the pieces to audit.
+    const AUDIT_SUBJECT: &[u8] = b"pallet_trustless_file_server::audit";
+
+    /// The current storage version.
+    const STORAGE_VERSION: StorageVersion = StorageVersion::new(12);
 
     #[pallet::pallet]
+    #[pallet::storage_version(STORAGE_VERSION)]
     pub struct Pallet<T>(_);
 
     #[pallet::config]
-    pub trait Config: frame_system::Config {
+    pub trait Config: frame_system::Config + SendTransactionTypes<Call<Self>> {
         /// Because this pallet emits events, it depends on the runtime's definition of an event.
         type RuntimeEvent: From<Event<Self>> + IsType<<Self as frame_system::Config>::RuntimeEvent>;
 
@@ -74,10 +148,401 @@ pub mod pallet {
         #[pallet::constant]
         type IpfsNodeUrl: Get<&'static str>;
 
+        /// Maximum size of an uploaded file, in bytes.
+        #[pallet::constant]
+        type MaxFileSize: Get<u32>;
+
+        /// Maximum size of all the files registered at once, in bytes, bounding the storage the
+        /// pallet can ever consume. Files pending approval or root confirmation only count once
+        /// registered.
+        #[pallet::constant]
+        type MaxTotalBytes: Get<u64>;
+
+        /// Native currency, in which deposits are expressed and companion pallets reserve funds.
+        type Currency: ReservableCurrency<Self::AccountId>;
+
+        /// Native balance from which deposits are held by default, under the [`HoldReason`]
+        /// they are taken for. Usually the same balances pallet as [`Config::Currency`].
+        type NativeBalance: fungible::Mutate<Self::AccountId, Balance = BalanceOf<Self>>
+            + fungible::MutateHold<Self::AccountId, Reason = Self::RuntimeHoldReason>;
+
+        /// Reason of the holds of the runtime, which has to express those of this pallet.
+        type RuntimeHoldReason: From<HoldReason>;
+
+        /// Assets in which deposits can be paid instead of the native currency.
+        type Assets: fungibles::Mutate<Self::AccountId>;
+
+        /// Converts deposits from the native currency to the asset chosen by the payer.
+        type BalanceToAssetBalance: BalanceConversion<
+            BalanceOf<Self>,
+            AssetIdOf<Self>,
+            AssetBalanceOf<Self>,
+        >;
+
+        /// Deposit taken for every registered file, regardless of its size.
+        #[pallet::constant]
+        type DepositBase: Get<BalanceOf<Self>>;
+
+        /// Deposit taken for every byte of a registered file.
+        #[pallet::constant]
+        type DepositPerByte: Get<BalanceOf<Self>>;
+
+        /// Reference price of a byte of storage in the native currency, e.g. tracked by an oracle
+        /// pallet, so that deposits follow the value of the token. Overrides `DepositPerByte`
+        /// whenever available. `()` always takes `DepositPerByte`.
+        type PriceOracle: Get<Option<BalanceOf<Self>>>;
+
+        /// Identifier of the account holding the deposits paid in assets.
+        #[pallet::constant]
+        type PalletId: Get<PalletId>;
+
+        /// Maximum number of accounts that can register the same file.
+        #[pallet::constant]
+        type MaxRegistrants: Get<u32>;
+
+        /// Origin allowed to pause and resume uploads.
+        type AdminOrigin: EnsureOrigin<Self::RuntimeOrigin>;
+
+        /// Origin allowed to upload files, resolving to the account that registers them. Use
+        /// `EnsureSigned` to let anyone upload, or restrict it to a set of publishers, e.g. with
+        /// `EnsureSignedBy` or a collective.
+        type UploadOrigin: EnsureOrigin<Self::RuntimeOrigin, Success = Self::AccountId>;
+
+        /// Types of content allowed to be uploaded, as sniffed from their first bytes. Use
+        /// `Everything` to accept anything, or e.g. [`DenyExecutables`].
+        type ContentPolicy: Contains<ContentType>;
+
+        /// Minimum size of the chunks files are split in, in bytes.
+        #[pallet::constant]
+        type MinChunkSize: Get<u32>;
+
+        /// Maximum size of the chunks files are split in, in bytes. Files for which
+        /// `TargetPieces` chunks would be bigger than this are split in more pieces.
+        #[pallet::constant]
+        type MaxChunkSize: Get<u32>;
+
+        /// Number of pieces files are split in, as long as the chunk size stays within
+        /// `MinChunkSize` and `MaxChunkSize`. At most [`file_merkle_tree::MAX_PIECES`].
+        #[pallet::constant]
+        type TargetPieces: Get<u32>;
+
+        /// Whether files are split in 16KB blocks following BitTorrent v2 (BEP-52) instead, so
+        /// that merkle roots are also the `pieces root` of the files in v2 torrents. Overrides
+        /// `MinChunkSize`, `MaxChunkSize` and `TargetPieces`.
+        #[pallet::constant]
+        type Bep52Compatible: Get<bool>;
+
+        /// Size of the chunks files are split in to match `ipfs add --chunker=size-<n>`, e.g.
+        /// 262144 for the default chunker of IPFS, so that chunks and files get the same CIDs as
+        /// if they were added to IPFS independently. Overrides `MinChunkSize`, `MaxChunkSize`
+        /// and `TargetPieces`, but not `Bep52Compatible`.
+        #[pallet::constant]
+        type IpfsChunkSize: Get<Option<u32>>;
+
+        /// Whether the IPFS node should announce itself in the DHT as a provider of every
+        /// uploaded chunk, so other peers of the network can discover them.
+        #[pallet::constant]
+        type AnnounceToDht: Get<bool>;
+
+        /// Base URL of an IPFS Cluster peer REST API. When set, chunks are added through the
+        /// cluster instead of the IPFS node, so they get replicated across cluster peers.
+        #[pallet::constant]
+        type IpfsClusterUrl: Get<Option<&'static str>>;
+
+        /// Number of cluster peers every chunk is pinned on, when using an IPFS Cluster.
+        #[pallet::constant]
+        type ClusterReplicationFactor: Get<u32>;
+
+        /// Base URL of a public IPFS gateway, e.g. `https://ipfs.io`, used to periodically check
+        /// that registered chunks are retrievable by end users.
+        #[pallet::constant]
+        type GatewayUrl: Get<Option<&'static str>>;
+
+        /// Number of blocks between two availability probes through the gateway.
+        #[pallet::constant]
+        type ProbeInterval: Get<Self::BlockNumber>;
+
+        /// Source of randomness drawing the pieces audited every [`Config::AuditPeriod`].
+        type Randomness: Randomness<Self::Hash, Self::BlockNumber>;
+
+        /// Current time, recorded along with every registered file, e.g. `pallet_timestamp`.
+        type UnixTime: UnixTime;
+
+        /// Number of blocks of every audit era. At the start of each of them, pieces of files
+        /// are drawn at random for the offchain worker to fetch back from IPFS and check against
+        /// their on-chain hashes. Zero disables audits.
+        #[pallet::constant]
+        type AuditPeriod: Get<Self::BlockNumber>;
+
+        /// Number of pieces audited every era.
+        #[pallet::constant]
+        type AuditSampleSize: Get<u32>;
+
+        /// Base URL of a remote pinning service implementing the IPFS Pinning Service API, if
+        /// uploaded chunks should also be pinned there. The access token is node-local and
+        /// read from the `pallet_trustless_file_server::pinning-token` offchain storage key.
+        #[pallet::constant]
+        type PinningServiceUrl: Get<Option<&'static str>>;
+
+        /// Number of blocks between two runs of the offchain worker pinning again the chunks of
+        /// registered files, so that content lost by the IPFS node is fetched back from the
+        /// network. Zero disables it.
+        #[pallet::constant]
+        type RepinInterval: Get<Self::BlockNumber>;
+
+        /// Maximum number of files pinned again in a single run. Files are visited in a rotating
+        /// window, so every file is eventually pinned again.
+        #[pallet::constant]
+        type MaxRepinsPerRun: Get<u32>;
+
+        /// Number of blocks between two runs of the offchain worker reporting on chain the content
+        /// served by the node through the RPC, for reward calculations. Zero disables it.
+        #[pallet::constant]
+        type ServedReportInterval: Get<Self::BlockNumber>;
+
+        /// Number of chunks the offchain worker uploads between two progress reports.
+        #[pallet::constant]
+        type ProgressReportInterval: Get<u32>;
+
+        /// Priority of the unsigned transactions submitted by the offchain worker.
+        #[pallet::constant]
+        type UnsignedPriority: Get<TransactionPriority>;
+
+        /// Identifier of the authorities whose offchain workers push files to IPFS and submit
+        /// status transactions, e.g. [`crypto::Public`].
+        type AuthorityId: Member + Parameter + RuntimeAppPublic + Ord + MaxEncodedLen;
+
+        /// Maximum number of offchain worker authorities.
+        #[pallet::constant]
+        type MaxOcwAuthorities: Get<u32>;
+
+        /// Maximum number of chunks pushed to IPFS in a single offchain worker run.
+        /// Remaining chunks are carried over to the next run.
+        #[pallet::constant]
+        type MaxChunksPerOcwRun: Get<u32>;
+
+        /// Maximum number of files that can be uploaded in a single block.
+        #[pallet::constant]
+        type MaxUploadsPerBlock: Get<u32>;
+
+        /// Maximum number of uploads a single account can submit in a block, so that nobody can
+        /// fill blocks with uploads and starve other users, however high the fees they pay.
+        #[pallet::constant]
+        type MaxUploadsPerAccountPerBlock: Get<u32>;
+
+        /// Maximum number of files that can be uploaded at once with `upload_files`.
+        #[pallet::constant]
+        type MaxFilesPerBatch: Get<u32>;
+
+        /// Number of leading zero bits the proof of work of `upload_file_unsigned` must have.
+        /// `None` disables anonymous uploads altogether.
+        #[pallet::constant]
+        type UnsignedUploadDifficulty: Get<Option<u32>>;
+
+        /// Maximum length of any string stored in file metadata (name, MIME type, each tag).
+        #[pallet::constant]
+        type StringLimit: Get<u32>;
+
+        /// Maximum number of tags that can be attached to a file.
+        #[pallet::constant]
+        type MaxTags: Get<u32>;
+
+        /// Maximum length of the human readable names files can be registered under, in bytes.
+        #[pallet::constant]
+        type MaxNameLength: Get<u32>;
+
+        /// Deposit held for every registered name. Set to zero to register names for free.
+        #[pallet::constant]
+        type NameDeposit: Get<BalanceOf<Self>>;
+
+        /// Deposit held for every byte of a registered name, on top of [`Config::NameDeposit`].
+        #[pallet::constant]
+        type NameDepositPerByte: Get<BalanceOf<Self>>;
+
+        /// Number of blocks names stay registered for, from their registration or their last
+        /// renewal. Expired names can be registered by anyone.
+        #[pallet::constant]
+        type NameRegistrationPeriod: Get<Self::BlockNumber>;
+
+        /// Maximum length of the keys of time-locked files, in bytes.
+        #[pallet::constant]
+        type MaxKeyLength: Get<u32>;
+
+        /// Maximum number of files in a collection.
+        #[pallet::constant]
+        type MaxCollectionSize: Get<u32>;
+
+        /// Accounts keys can be escrowed with, e.g. the validators of the current session.
+        type KeyCommittee: SortedMembers<Self::AccountId>;
+
+        /// Maximum number of committee members the key of a file can be split among. Must not
+        /// exceed 255, the number of points shares can be evaluated at.
+        #[pallet::constant]
+        type MaxKeyHolders: Get<u32>;
+
+        /// Maximum length of the shares of escrowed keys once encrypted to their holders.
+        #[pallet::constant]
+        type MaxEncryptedShareLength: Get<u32>;
+
+        /// Origin allowed to resolve reports about the content of files, either dismissing them
+        /// or removing the file, e.g. a council or a moderation committee.
+        type ModerationOrigin: EnsureOrigin<Self::RuntimeOrigin>;
+
+        /// Size in bytes above which uploads are held until approved by
+        /// [`Config::LargeUploadOrigin`], so that exceptional storage consumption goes through
+        /// governance.
+        #[pallet::constant]
+        type LargeFileThreshold: Get<u32>;
+
+        /// Origin allowed to approve or reject uploads bigger than
+        /// [`Config::LargeFileThreshold`], e.g. a council.
+        type LargeUploadOrigin: EnsureOrigin<Self::RuntimeOrigin>;
+
+        /// Whether uploads are held until [`Config::ReviewOrigin`] approves them, so that their
+        /// content is screened before it is registered or pushed to IPFS.
+        #[pallet::constant]
+        type RequireReview: Get<bool>;
+
+        /// Origin posting the results of the screening of uploads, e.g. the account of an
+        /// off-chain scanning service through `EnsureSignedBy`.
+        type ReviewOrigin: EnsureOrigin<Self::RuntimeOrigin>;
+
+        /// Sends the attestations of files to sibling parachains, e.g. the XCMP queue.
+        type XcmSender: SendXcm;
+
+        /// Pallet and call indexes of the call receiving attestations in the runtimes of the
+        /// sibling parachains, which are delivered as a `Transact` of it: either
+        /// `receive_attestation` of the attestations pallet
+        /// (`pallet-trustless-file-server-attestations`), whose call index is 0, or
+        /// `register_external_file` of this pallet.
+        #[pallet::constant]
+        type AttestationCallIndex: Get<[u8; 2]>;
+
+        /// Weight of the call receiving attestations on sibling parachains.
+        #[pallet::constant]
+        type AttestationWeight: Get<Weight>;
+
+        /// Origin of the messages sent through XCM, yielding their location, e.g.
+        /// `pallet_xcm::EnsureXcm<IsSiblingParachain>`.
+        type SiblingOrigin: EnsureOrigin<Self::RuntimeOrigin, Success = MultiLocation>;
+
+        /// Sibling parachains whose attestations are recorded as external files.
+        type TrustedSiblings: Contains<u32>;
+
+        /// Bond held from accounts reporting a file, slashed if the report is dismissed.
+        #[pallet::constant]
+        type ReportBond: Get<BalanceOf<Self>>;
+
+        /// Maximum number of reports pending on the same file.
+        #[pallet::constant]
+        type MaxReportsPerFile: Get<u32>;
+
+        /// Maximum number of changes kept in the history of every file. The oldest ones are
+        /// dropped to make room for new ones.
+        #[pallet::constant]
+        type MaxHistoryLength: Get<u32>;
+
+        /// The overarching call type, so that the pallet can schedule calls to itself.
+        type RuntimeCall: From<Call<Self>> + IsType<<Self as frame_system::Config>::RuntimeCall>;
+
+        /// The caller origin, overarching type of all pallets origins, used to dispatch the
+        /// scheduled calls on behalf of their owners.
+        type PalletsOrigin: From<frame_system::RawOrigin<Self::AccountId>>;
+
+        /// Scheduler through which the deferred operations of owners are dispatched, e.g.
+        /// `pallet_scheduler`.
+        type Scheduler: ScheduleNamed<Self::BlockNumber, CallOf<Self>, Self::PalletsOrigin>;
+
+        /// Preimage provider the scheduled calls are bounded with, e.g. `pallet_preimage`.
+        type Preimages: QueryPreimage + StorePreimage;
+
         /// The weight information for this pallet.
         type WeightInfo: WeightInfo;
     }
 
+    /// Peer id of an IPFS Cluster peer, as a string.
+    pub type ClusterPeerId = BoundedVec<u8, ConstU32<64>>;
+
+    pub type AllocationsOf<T> = BoundedVec<ClusterPeerId, <T as Config>::ClusterReplicationFactor>;
+
+    pub type AuthorityProofOf<T> = AuthorityProof<
+        <T as Config>::AuthorityId,
+        <T as frame_system::Config>::BlockNumber,
+        <<T as Config>::AuthorityId as RuntimeAppPublic>::Signature,
+    >;
+
+    pub type FileMetadataOf<T> =
+        FileMetadata<<T as Config>::StringLimit, <T as Config>::MaxTags, BalanceOf<T>>;
+
+    pub type LicenseOf<T> = License<BoundedVec<u8, <T as Config>::StringLimit>>;
+
+    pub type NameOf<T> = BoundedVec<u8, <T as Config>::MaxNameLength>;
+
+    pub type AliasOf<T> = Alias<
+        <T as frame_system::Config>::AccountId,
+        <T as frame_system::Config>::Hash,
+        BalanceOf<T>,
+        <T as frame_system::Config>::BlockNumber,
+    >;
+
+    pub type BundleMembersOf<T> =
+        BoundedVec<<T as frame_system::Config>::Hash, ConstU32<{ file_merkle_tree::MAX_PIECES }>>;
+
+    pub type BundleOf<T> =
+        Bundle<<T as frame_system::Config>::AccountId, BundleMembersOf<T>, BalanceOf<T>>;
+
+    pub type KeyOf<T> = BoundedVec<u8, <T as Config>::MaxKeyLength>;
+
+    pub type CollectionMembersOf<T> =
+        BoundedVec<<T as frame_system::Config>::Hash, <T as Config>::MaxCollectionSize>;
+
+    pub type CollectionOf<T> =
+        Collection<<T as frame_system::Config>::AccountId, CollectionMembersOf<T>, BalanceOf<T>>;
+
+    pub type TimeLockOf<T> = TimeLock<<T as frame_system::Config>::BlockNumber, KeyOf<T>>;
+
+    pub type EncryptedShareOf<T> = BoundedVec<u8, <T as Config>::MaxEncryptedShareLength>;
+
+    pub type KeyShareOf<T> =
+        KeyShare<<T as frame_system::Config>::AccountId, KeyOf<T>, EncryptedShareOf<T>>;
+
+    pub type KeyEscrowOf<T> = KeyEscrow<BoundedVec<KeyShareOf<T>, <T as Config>::MaxKeyHolders>>;
+
+    pub type CallOf<T> = <T as frame_system::Config>::RuntimeCall;
+
+    pub type ReportOf<T> = Report<<T as frame_system::Config>::AccountId, BalanceOf<T>>;
+
+    pub type FileAttestationOf<T> =
+        FileAttestation<<T as frame_system::Config>::AccountId, <T as frame_system::Config>::Hash>;
+
+    pub type FileChangeOf<T> = FileChange<<T as frame_system::Config>::AccountId>;
+
+    pub type HistoryEntryOf<T> = HistoryEntry<
+        <T as frame_system::Config>::AccountId,
+        <T as frame_system::Config>::BlockNumber,
+    >;
+
+    pub type ExternalFileOf<T> = ExternalFile<<T as frame_system::Config>::AccountId>;
+
+    pub type PendingUploadOf<T> =
+        PendingUpload<<T as frame_system::Config>::AccountId, DepositOf<T>, LicenseOf<T>>;
+
+    pub type RootClaimOf<T> = RootClaim<<T as frame_system::Config>::AccountId, BalanceOf<T>>;
+
+    pub type UsageRecordOf<T> = UsageRecord<BalanceOf<T>>;
+    pub type BandwidthRecordOf<T> = BandwidthRecord<<T as frame_system::Config>::BlockNumber>;
+    pub type TombstoneOf<T> = Tombstone<<T as frame_system::Config>::BlockNumber>;
+
+    pub type BalanceOf<T> =
+        <<T as Config>::Currency as Currency<<T as frame_system::Config>::AccountId>>::Balance;
+    pub type AssetIdOf<T> = <<T as Config>::Assets as fungibles::Inspect<
+        <T as frame_system::Config>::AccountId,
+    >>::AssetId;
+    pub type AssetBalanceOf<T> = <<T as Config>::Assets as fungibles::Inspect<
+        <T as frame_system::Config>::AccountId,
+    >>::Balance;
+    pub type DepositOf<T> = Deposit<BalanceOf<T>, AssetIdOf<T>, AssetBalanceOf<T>>;
+
     pub trait ConfigHelper: Config {
         fn ipfs_node_url() -> String;
     }
@@ -88,15 +553,290 @@ pub mod pallet {
         }
     }
 
+    /// Events are deposited along with topics, see [`Event::topics`].
     #[pallet::event]
-    #[pallet::generate_deposit(pub (super) fn deposit_event)]
     pub enum Event<T: Config> {
         /// Event emitted when a claim has been created.
         FileUploaded {
             who: T::AccountId,
             merkle_root: T::Hash,
             pieces: u32,
-            size: u32,
+            size: u64,
+            chunk_size: u32,
+            /// sha256 of the whole file.
+            sha256: [u8; 32],
+        },
+        /// The offchain worker has pushed `done` out of `total` chunks of a file to IPFS.
+        ChunksUploaded {
+            merkle_root: T::Hash,
+            done: u32,
+            total: u32,
+        },
+        /// The IPFS Cluster has allocated a chunk of a file to the given number of peers.
+        ChunkAllocated {
+            merkle_root: T::Hash,
+            position: u64,
+            peers: u32,
+        },
+        /// A chunk of a file has been fetched through the public gateway.
+        ProbeReported {
+            merkle_root: T::Hash,
+            position: u64,
+            available: bool,
+        },
+        /// A piece of a file drawn for an audit has been fetched back from IPFS.
+        PieceAudited {
+            merkle_root: T::Hash,
+            position: u64,
+            passed: bool,
+        },
+        /// The offchain worker has pinned again all the chunks of a file.
+        FileRepinned { merkle_root: T::Hash },
+        /// The offchain worker of an authority has reported content of a file served through the
+        /// RPC of its node.
+        BandwidthReported {
+            merkle_root: T::Hash,
+            authority: T::AuthorityId,
+            bytes: u64,
+            chunks: u64,
+        },
+        /// The owner of a file has updated its metadata.
+        MetadataUpdated {
+            who: T::AccountId,
+            merkle_root: T::Hash,
+        },
+        /// The owner of a file has set the price of retrieving each of its chunks, or cleared it.
+        RetrievalPriceSet {
+            who: T::AccountId,
+            merkle_root: T::Hash,
+            price: Option<BalanceOf<T>>,
+        },
+        /// The owner of a file has asked for its tree to be rebuilt with the current chunking
+        /// parameters.
+        RechunkRequested {
+            who: T::AccountId,
+            merkle_root: T::Hash,
+        },
+        /// The tree of a file could not be rebuilt out of its content, so the request has been
+        /// dropped.
+        RechunkRejected { merkle_root: T::Hash },
+        /// The tree of a file has been rebuilt. The old merkle root is kept as an alias.
+        FileRechunked {
+            old_root: T::Hash,
+            new_root: T::Hash,
+            pieces: u32,
+            chunk_size: u32,
+        },
+        /// An account has uploaded a file that was already registered by someone else.
+        RegistrantAdded {
+            who: T::AccountId,
+            merkle_root: T::Hash,
+            registrants: u32,
+        },
+        /// A registrant of a file has dropped it, getting its deposit back.
+        RegistrantRemoved {
+            who: T::AccountId,
+            merkle_root: T::Hash,
+            registrants: u32,
+        },
+        /// The last registrant of a file has dropped it, so it has been removed.
+        FileDeleted { merkle_root: T::Hash },
+        /// Uploads and changes to registered files have been halted.
+        UploadsPaused,
+        /// The authorities whose offchain workers do the work have changed.
+        OcwAuthoritiesSet { count: u32 },
+        /// A collection of files has been created.
+        CollectionCreated {
+            who: T::AccountId,
+            collection_id: CollectionId,
+        },
+        /// A file has been added to a collection.
+        AddedToCollection {
+            collection_id: CollectionId,
+            merkle_root: T::Hash,
+        },
+        /// A file has been removed from a collection.
+        RemovedFromCollection {
+            collection_id: CollectionId,
+            merkle_root: T::Hash,
+        },
+        /// The retrieval price of the `files` of a collection still owned by its owner has been
+        /// set, or cleared.
+        CollectionPriced {
+            collection_id: CollectionId,
+            price: Option<BalanceOf<T>>,
+            files: u32,
+        },
+        /// A collection has been handed over along with the `files` of it its owner owned.
+        CollectionTransferred {
+            collection_id: CollectionId,
+            from: T::AccountId,
+            to: T::AccountId,
+            files: u32,
+        },
+        /// The owner of a collection has removed it, getting its deposit back.
+        CollectionRemoved { collection_id: CollectionId },
+        /// Chunk allocations left behind by removed files have been dropped, freeing `bytes` of
+        /// state.
+        ChunksReclaimed { count: u32, bytes: u64 },
+        /// Uploads and changes to registered files are allowed again.
+        UploadsResumed,
+        /// A name has been registered, or pointed to a different file by its owner.
+        AliasRegistered {
+            who: T::AccountId,
+            name: NameOf<T>,
+            merkle_root: T::Hash,
+        },
+        /// The owner of a name has released it, getting its deposit back.
+        AliasReleased { who: T::AccountId, name: NameOf<T> },
+        /// The owner of a name has kept it registered until `expires_at`.
+        AliasRenewed {
+            who: T::AccountId,
+            name: NameOf<T>,
+            expires_at: T::BlockNumber,
+        },
+        /// The moderation origin has taken an abusive name from its owner, slashing its
+        /// deposit, and given it to another account.
+        AliasReassigned {
+            name: NameOf<T>,
+            from: T::AccountId,
+            to: T::AccountId,
+            slashed: BalanceOf<T>,
+        },
+        /// A bundle of `members` files has been created under `bundle_root`.
+        BundleCreated {
+            who: T::AccountId,
+            bundle_root: T::Hash,
+            members: u32,
+        },
+        /// The owner of a bundle has removed it, getting its deposit back.
+        BundleRemoved { bundle_root: T::Hash },
+        /// The owner of a file has committed to the key its content can be decrypted with.
+        KeyCommitted {
+            merkle_root: T::Hash,
+            reveal_at: T::BlockNumber,
+        },
+        /// The key of a time-locked file has been revealed.
+        KeyRevealed { merkle_root: T::Hash, key: KeyOf<T> },
+        /// The owner of a file has split its key among committee members, `threshold` of which
+        /// have to submit their shares to reveal it.
+        KeyEscrowed {
+            merkle_root: T::Hash,
+            reveal_at: T::BlockNumber,
+            threshold: u32,
+        },
+        /// A committee member has submitted its share of the key of a file.
+        KeyShareSubmitted {
+            merkle_root: T::Hash,
+            holder: T::AccountId,
+        },
+        /// Enough shares of the key of a file have been submitted, but they do not rebuild the
+        /// committed key, meaning the owner of the file split it wrongly.
+        KeyReconstructionFailed { merkle_root: T::Hash },
+        /// A file has been uploaded under a merkle root claimed by the uploader, to be checked by
+        /// the offchain worker.
+        RootClaimed {
+            who: T::AccountId,
+            merkle_root: T::Hash,
+            size: u64,
+        },
+        /// The merkle root claimed by the uploader of a file does not match its content, so the
+        /// upload has been dropped and its deposit slashed.
+        RootRejected {
+            who: T::AccountId,
+            merkle_root: T::Hash,
+            slashed: BalanceOf<T>,
+        },
+        /// The uploader of a file has withdrawn it before its claimed merkle root was checked,
+        /// getting its deposit back.
+        RootClaimCancelled {
+            who: T::AccountId,
+            merkle_root: T::Hash,
+        },
+        /// An upload bigger than the large file threshold is waiting for approval.
+        LargeUploadPending {
+            who: T::AccountId,
+            merkle_root: T::Hash,
+            size: u64,
+        },
+        /// The approval origin has rejected a large upload, giving its deposit back.
+        LargeUploadRejected {
+            who: T::AccountId,
+            merkle_root: T::Hash,
+        },
+        /// An upload is waiting for the review origin to screen its content.
+        UploadPendingReview {
+            who: T::AccountId,
+            merkle_root: T::Hash,
+            size: u64,
+        },
+        /// The review origin has rejected an upload, giving its deposit back.
+        UploadRejectedInReview {
+            who: T::AccountId,
+            merkle_root: T::Hash,
+        },
+        /// The owner of a file has stopped its proofs and content from being served.
+        FileFrozen {
+            who: T::AccountId,
+            merkle_root: T::Hash,
+        },
+        /// The owner of a frozen file has let its proofs and content be served again.
+        FileUnfrozen {
+            who: T::AccountId,
+            merkle_root: T::Hash,
+        },
+        /// The owner of a file has handed it over to `to`, e.g. a multisig account.
+        FileTransferred {
+            from: T::AccountId,
+            to: T::AccountId,
+            merkle_root: T::Hash,
+        },
+        /// The owner of a file has set the number of approvals it needs to manage it, or
+        /// cleared it with `None`.
+        ManagementThresholdSet {
+            who: T::AccountId,
+            merkle_root: T::Hash,
+            threshold: Option<u16>,
+        },
+        /// The attestation of a file has been sent to a sibling parachain.
+        FileAttested { merkle_root: T::Hash, para_id: u32 },
+        /// A trusted sibling parachain has attested that a file is registered there.
+        ExternalFileRegistered {
+            merkle_root: T::Hash,
+            para_id: u32,
+            owner: T::AccountId,
+        },
+        /// An account has complained about the content of a file.
+        FileReported {
+            who: T::AccountId,
+            merkle_root: T::Hash,
+            reason_hash: [u8; 32],
+        },
+        /// The moderation origin has dismissed a report, slashing the bond of the reporter.
+        ReportDismissed {
+            merkle_root: T::Hash,
+            reporter: T::AccountId,
+            slashed: BalanceOf<T>,
+        },
+        /// The moderation origin has upheld a report, removing the file. Every registrant got its
+        /// deposit back, and every reporter its bond.
+        FileTakenDown { merkle_root: T::Hash },
+        /// A registrant has scheduled the deletion of its registration of a file.
+        DeletionScheduled {
+            who: T::AccountId,
+            merkle_root: T::Hash,
+            when: T::BlockNumber,
+        },
+        /// A registrant has cancelled the scheduled deletion of its registration of a file.
+        DeletionCancelled {
+            who: T::AccountId,
+            merkle_root: T::Hash,
+        },
+        /// `sponsor` has paid the deposit for `beneficiary` to register a file.
+        UploadSponsored {
+            sponsor: T::AccountId,
+            beneficiary: T::AccountId,
+            merkle_root: T::Hash,
         },
     }
 
@@ -104,105 +844,3612 @@ pub mod pallet {
     pub enum Error<T> {
         /// Could not obtain the merkle root hash
         Unhasheable,
+        /// There is no file registered under the given merkle root
+        FileNotFound,
+        /// The caller is not the owner of the file
+        NotFileOwner,
+        /// The file exceeds the maximum allowed size
+        FileTooLarge,
+        /// The maximum number of uploads for the current block has been reached
+        TooManyUploadsInBlock,
+        /// The caller has reached the maximum number of uploads for the current block
+        TooManyUploadsFromAccount,
+        /// Registering the file would exceed the storage budget of the chain
+        GlobalCapacityExceeded,
+        /// The reported upload progress is not consistent with the file
+        InvalidProgress,
+        /// The given chunk position does not exist in the file
+        InvalidPosition,
+        /// The file is already split according to the current chunking parameters
+        AlreadyChunked,
+        /// The file has no pending request to rebuild its tree
+        RechunkNotRequested,
+        /// The merkle tree is malformed or does not follow the current chunking parameters
+        InvalidMerkleTree,
+        /// There is already a file registered under the resulting merkle root
+        FileAlreadyExists,
+        /// The deposit could not be converted to the chosen asset
+        DepositConversionFailed,
+        /// The caller has already registered the file
+        AlreadyRegistered,
+        /// The caller has not registered the file
+        NotRegistrant,
+        /// The maximum number of registrants of the file has been reached
+        TooManyRegistrants,
+        /// Uploads are paused
+        Paused,
+        /// The license is empty or not a valid SPDX identifier
+        InvalidLicense,
+        /// The type of the content is not allowed by the runtime
+        ContentTypeForbidden,
+        /// The file has no content
+        EmptyFile,
+        /// The file would be split in more pieces than a merkle tree can hold
+        TooManyPieces,
+        /// The name is empty, not valid UTF-8 or contains whitespace or control characters
+        InvalidName,
+        /// The name is registered by another account and has not expired
+        NameTaken,
+        /// There is no file registered under the given name
+        NameNotFound,
+        /// The caller is not the owner of the name
+        NotNameOwner,
+        /// The file has been pinned again too recently
+        RepinNotDue,
+        /// Anonymous uploads are not enabled in this chain
+        UnsignedUploadsDisabled,
+        /// The proof of work does not meet the required difficulty
+        InsufficientWork,
+        /// Bundles need at least two files
+        BundleTooSmall,
+        /// There is already a bundle with the same files
+        BundleExists,
+        /// The bundle does not exist
+        BundleNotFound,
+        /// The caller is not the owner of the bundle
+        NotBundleOwner,
+        /// The file is already time-locked
+        KeyAlreadyCommitted,
+        /// The file is not time-locked
+        NoKeyCommitment,
+        /// The key of the file has already been revealed
+        KeyAlreadyRevealed,
+        /// The embargo of the file is not over yet
+        RevealTooEarly,
+        /// The key does not match the commitment
+        KeyMismatch,
+        /// The threshold is zero or above the number of shares
+        InvalidThreshold,
+        /// The account is not a member of the key committee
+        NotCommitteeMember,
+        /// The same account holds more than one share
+        DuplicateKeyHolder,
+        /// The key of the file is not escrowed
+        NoKeyEscrow,
+        /// The caller holds no share of the key
+        NotKeyHolder,
+        /// The share has already been submitted
+        ShareAlreadySubmitted,
+        /// The share does not match its commitment
+        ShareMismatch,
+        /// The merkle root is already claimed by an upload pending to be checked
+        RootAlreadyClaimed,
+        /// There is no upload pending to be checked under the given merkle root
+        RootNotClaimed,
+        /// The caller has already reported the file
+        AlreadyReported,
+        /// The maximum number of reports pending on the file has been reached
+        TooManyReports,
+        /// The account has no report pending on the file
+        ReportNotFound,
+        /// The file is waiting for its upload to be approved
+        UploadPendingApproval,
+        /// There is no upload waiting for approval under the given merkle root
+        UploadNotPending,
+        /// The file is waiting for its content to be reviewed
+        UploadUnderReview,
+        /// There is no upload waiting for review under the given merkle root
+        UploadNotUnderReview,
+        /// The file is already frozen
+        AlreadyFrozen,
+        /// The file is not frozen
+        NotFrozen,
+        /// Management thresholds must be at least one approval
+        ZeroThreshold,
+        /// The attestation could not be sent to the sibling parachain
+        AttestationNotSent,
+        /// The message was not sent by a sibling parachain
+        NotSibling,
+        /// The sibling parachain is not trusted to attest files
+        UntrustedSibling,
+        /// The deletion of the registration is already scheduled
+        DeletionAlreadyScheduled,
+        /// The deletion of the registration is not scheduled
+        DeletionNotScheduled,
+        /// Operations can only be scheduled for future blocks
+        ScheduledInThePast,
+        /// The scheduler did not accept the operation
+        SchedulingFailed,
+        /// The reported bytes do not fit in the reported chunks of the file
+        InvalidBandwidthReport,
+        /// The piece is not part of the current audit sample
+        PieceNotAudited,
+        /// The collection does not exist
+        CollectionNotFound,
+        /// The caller is not the owner of the collection
+        NotCollectionOwner,
+        /// The collection already holds `MaxCollectionSize` files
+        CollectionFull,
+        /// The file is already part of the collection
+        AlreadyInCollection,
+        /// The file is not part of the collection
+        NotInCollection,
+        /// The report is not signed by one of the offchain worker authorities, or is too old
+        InvalidAuthorityProof,
+        /// The merkle root was claimed by another account
+        NotClaimant,
     }
 
+    /// Owner of each file, i.e. the first of its registrants and the only one allowed to manage
+    /// it. A file is registered as long as it has an owner.
     #[pallet::storage]
-    #[pallet::getter(fn get_file)]
-    pub(super) type Files<T: Config> =
-        StorageMap<_, Blake2_128Concat, T::Hash, (T::AccountId, FileMerkleTree), OptionQuery>;
+    #[pallet::getter(fn get_owner)]
+    pub(super) type FileOwners<T: Config> =
+        StorageMap<_, Blake2_128Concat, T::Hash, T::AccountId, OptionQuery>;
 
-    #[pallet::hooks]
-    impl<T: Config> Hooks<BlockNumberFor<T>> for Pallet<T> {
-        fn offchain_worker(block_number: T::BlockNumber) {
-            let key = Self::derived_key(block_number);
-            let storage_ref = StorageValueRef::persistent(&key);
-            log::info!("Offchain worker: retrieved storage for block {:?}", block_number);
-
-            if let Ok(Some(data)) = storage_ref.get::<IndexingData>() {
-                log::info!("Offchain worker: Found storage at block {:?}", block_number);
-                let mut pos = 0;
-                let file_length = data.content.len();
-                let chunk_size = data.chunk_size as usize;
-                while pos < file_length {
-                    let mut limit = pos + chunk_size;
-                    if limit > file_length {
-                        limit = file_length;
-                    }
-                    ipfs::ipfs_upload(&T::ipfs_node_url(), &data.content[pos..limit])
-                        .expect("Could not upload a file chunk to IPFS");
-                    pos += chunk_size;
-                }
+    /// Merkle tree of each file. Only decoded when the hashes of its pieces are needed.
+    #[pallet::storage]
+    #[pallet::getter(fn get_file_tree)]
+    pub(super) type FileTrees<T: Config> =
+        StorageMap<_, Blake2_128Concat, T::Hash, FileMerkleTree, OptionQuery>;
+
+    /// Size and chunking of each file, as found in its merkle tree, so that listings and stats
+    /// read them along with the merkle root they are keyed by without decoding the tree.
+    #[pallet::storage]
+    #[pallet::getter(fn get_file_meta)]
+    pub(super) type FileMeta<T: Config> =
+        StorageMap<_, Blake2_128Concat, T::Hash, FileLayout, OptionQuery>;
+
+    /// Accounts that have uploaded each file, along with the deposit each of them paid.
+    #[pallet::storage]
+    #[pallet::getter(fn get_registrant)]
+    pub(super) type Registrants<T: Config> = StorageDoubleMap<
+        _,
+        Blake2_128Concat,
+        T::Hash,
+        Blake2_128Concat,
+        T::AccountId,
+        DepositOf<T>,
+        OptionQuery,
+    >;
+
+    /// Accounts that paid the deposit of registrants who uploaded through a sponsor. The
+    /// deposit goes back to them when the registrant deletes the file.
+    #[pallet::storage]
+    #[pallet::getter(fn get_sponsor)]
+    pub(super) type Sponsors<T: Config> = StorageDoubleMap<
+        _,
+        Blake2_128Concat,
+        T::Hash,
+        Blake2_128Concat,
+        T::AccountId,
+        T::AccountId,
+        OptionQuery,
+    >;
+
+    /// Number of registrants of each file. The file is removed when it drops to zero.
+    #[pallet::storage]
+    #[pallet::getter(fn get_registrant_count)]
+    pub(super) type RegistrantCount<T: Config> =
+        StorageMap<_, Blake2_128Concat, T::Hash, u32, ValueQuery>;
+
+    /// Plain sha256 of the whole content of each file, to cross-check it against external copies.
+    #[pallet::storage]
+    #[pallet::getter(fn get_checksum)]
+    pub(super) type Checksums<T: Config> =
+        StorageMap<_, Blake2_128Concat, T::Hash, [u8; 32], OptionQuery>;
+
+    /// Block and time each file was registered at.
+    #[pallet::storage]
+    #[pallet::getter(fn get_upload_time)]
+    pub(super) type UploadedAt<T: Config> =
+        StorageMap<_, Blake2_128Concat, T::Hash, UploadTime<T::BlockNumber>, OptionQuery>;
+
+    #[pallet::storage]
+    #[pallet::getter(fn get_metadata)]
+    pub(super) type Metadata<T: Config> =
+        StorageMap<_, Blake2_128Concat, T::Hash, FileMetadataOf<T>, OptionQuery>;
+
+    /// Number of chunks of each file the offchain worker has reported as pushed to IPFS.
+    #[pallet::storage]
+    #[pallet::getter(fn get_upload_progress)]
+    pub(super) type UploadProgress<T: Config> =
+        StorageMap<_, Blake2_128Concat, T::Hash, u32, ValueQuery>;
+
+    /// Pieces of each file the offchain worker has confirmed to be on IPFS, as a bitfield where
+    /// bit `i` stands for the piece at position `i`.
+    #[pallet::storage]
+    #[pallet::getter(fn get_uploaded_pieces)]
+    pub(super) type UploadedPieces<T: Config> =
+        StorageMap<_, Blake2_128Concat, T::Hash, u32, ValueQuery>;
+
+    /// IPFS Cluster peers each chunk has been allocated to, as reported by the offchain worker.
+    #[pallet::storage]
+    #[pallet::getter(fn get_chunk_allocations)]
+    pub(super) type ChunkAllocations<T: Config> = StorageDoubleMap<
+        _,
+        Blake2_128Concat,
+        T::Hash,
+        Twox64Concat,
+        u64,
+        AllocationsOf<T>,
+        OptionQuery,
+    >;
+
+    /// Result of the last availability probe of each file through the public gateway.
+    #[pallet::storage]
+    #[pallet::getter(fn get_probe_result)]
+    pub(super) type ProbeResults<T: Config> =
+        StorageMap<_, Blake2_128Concat, T::Hash, ProbeResult<T::BlockNumber>, OptionQuery>;
+
+    /// Pieces drawn at the start of the current audit era that the offchain worker has not
+    /// reported on yet, by the merkle root of their file and their position.
+    #[pallet::storage]
+    #[pallet::getter(fn get_audit_sample)]
+    pub(super) type AuditSample<T: Config> =
+        StorageValue<_, BoundedVec<(T::Hash, u64), T::AuditSampleSize>, ValueQuery>;
+
+    /// Result of the last audit of each file.
+    #[pallet::storage]
+    #[pallet::getter(fn get_piece_audit)]
+    pub(super) type PieceAudits<T: Config> =
+        StorageMap<_, Blake2_128Concat, T::Hash, PieceAudit<T::BlockNumber>, OptionQuery>;
+
+    /// Block at which the offchain worker last pinned again all the chunks of each file. Files
+    /// lagging far behind [`Config::RepinInterval`] are no longer being kept alive.
+    #[pallet::storage]
+    #[pallet::getter(fn get_last_pinned_at)]
+    pub(super) type LastPinnedAt<T: Config> =
+        StorageMap<_, Blake2_128Concat, T::Hash, T::BlockNumber, OptionQuery>;
+
+    /// Files whose tree is pending to be rebuilt by the offchain worker.
+    #[pallet::storage]
+    pub(super) type PendingRechunks<T: Config> =
+        StorageMap<_, Blake2_128Concat, T::Hash, (), OptionQuery>;
+
+    /// Merkle roots of files whose tree has been rebuilt, pointing to their new merkle root.
+    #[pallet::storage]
+    #[pallet::getter(fn get_root_alias)]
+    pub(super) type RootAliases<T: Config> =
+        StorageMap<_, Blake2_128Concat, T::Hash, T::Hash, OptionQuery>;
+
+    /// Human readable names of files, e.g. `myapp/logo.png`, along with their owner.
+    #[pallet::storage]
+    #[pallet::getter(fn get_alias)]
+    pub(super) type Names<T: Config> =
+        StorageMap<_, Blake2_128Concat, NameOf<T>, AliasOf<T>, OptionQuery>;
+
+    /// Groups of files, under the merkle root over the merkle roots of their members.
+    #[pallet::storage]
+    #[pallet::getter(fn get_bundle)]
+    pub(super) type Bundles<T: Config> =
+        StorageMap<_, Blake2_128Concat, T::Hash, BundleOf<T>, OptionQuery>;
+
+    /// Collections of files managed together by their owner, by identifier.
+    #[pallet::storage]
+    #[pallet::getter(fn get_collection)]
+    pub(super) type Collections<T: Config> =
+        StorageMap<_, Twox64Concat, CollectionId, CollectionOf<T>, OptionQuery>;
+
+    /// Identifier of the next created collection.
+    #[pallet::storage]
+    pub(super) type NextCollectionId<T: Config> = StorageValue<_, CollectionId, ValueQuery>;
+
+    /// Commitments to the keys of encrypted files, and the keys themselves once revealed.
+    #[pallet::storage]
+    #[pallet::getter(fn get_time_lock)]
+    pub(super) type TimeLocks<T: Config> =
+        StorageMap<_, Blake2_128Concat, T::Hash, TimeLockOf<T>, OptionQuery>;
+
+    /// Shares of the keys of time-locked files escrowed with the key committee, until rebuilt.
+    #[pallet::storage]
+    #[pallet::getter(fn get_key_escrow)]
+    pub(super) type KeyEscrows<T: Config> =
+        StorageMap<_, Blake2_128Concat, T::Hash, KeyEscrowOf<T>, OptionQuery>;
+
+    /// Uploads whose merkle root was claimed by the uploader, until the offchain worker either
+    /// confirms or rejects it.
+    #[pallet::storage]
+    #[pallet::getter(fn get_root_claim)]
+    pub(super) type RootClaims<T: Config> =
+        StorageMap<_, Blake2_128Concat, T::Hash, RootClaimOf<T>, OptionQuery>;
+
+    /// Uploads bigger than the large file threshold, until the approval origin either approves or
+    /// rejects them.
+    #[pallet::storage]
+    #[pallet::getter(fn get_pending_upload)]
+    pub(super) type PendingUploads<T: Config> =
+        StorageMap<_, Blake2_128Concat, T::Hash, PendingUploadOf<T>, OptionQuery>;
+
+    /// Uploads waiting for the review origin to screen their content, when the runtime requires
+    /// reviews. Approved ones may still wait for the approval of large uploads.
+    #[pallet::storage]
+    #[pallet::getter(fn get_pending_review)]
+    pub(super) type PendingReview<T: Config> =
+        StorageMap<_, Blake2_128Concat, T::Hash, PendingUploadOf<T>, OptionQuery>;
+
+    /// Files whose owner has stopped their proofs and content from being served, e.g. during a
+    /// takedown dispute, without deleting them.
+    #[pallet::storage]
+    pub(super) type FrozenFiles<T: Config> =
+        StorageMap<_, Blake2_128Concat, T::Hash, (), OptionQuery>;
+
+    /// Number of approvals the owner of a file needs to manage it, as set by the owner for the
+    /// guidance of user interfaces when it is a multisig account. Not enforced by the pallet,
+    /// and cleared whenever the file changes hands.
+    #[pallet::storage]
+    #[pallet::getter(fn get_management_threshold)]
+    pub(super) type ManagementThresholds<T: Config> =
+        StorageMap<_, Blake2_128Concat, T::Hash, u16, OptionQuery>;
+
+    /// Files registered in trusted sibling parachains, by merkle root and identifier of the
+    /// parachain, as attested by them.
+    #[pallet::storage]
+    pub(super) type ExternalFiles<T: Config> = StorageDoubleMap<
+        _,
+        Blake2_128Concat,
+        T::Hash,
+        Twox64Concat,
+        u32,
+        ExternalFileOf<T>,
+        OptionQuery,
+    >;
+
+    /// Latest ownership transfers and metadata updates of each file, oldest first.
+    #[pallet::storage]
+    #[pallet::getter(fn get_history)]
+    pub(super) type FileHistory<T: Config> = StorageMap<
+        _,
+        Blake2_128Concat,
+        T::Hash,
+        BoundedVec<HistoryEntryOf<T>, T::MaxHistoryLength>,
+        ValueQuery,
+    >;
+
+    /// Reports pending on each file, in the order they were submitted.
+    #[pallet::storage]
+    #[pallet::getter(fn get_reports)]
+    pub(super) type Reports<T: Config> = StorageMap<
+        _,
+        Blake2_128Concat,
+        T::Hash,
+        BoundedVec<ReportOf<T>, T::MaxReportsPerFile>,
+        ValueQuery,
+    >;
+
+    /// Track record of every account that has uploaded files, answered deal challenges or had
+    /// its files reported.
+    #[pallet::storage]
+    #[pallet::getter(fn get_reputation)]
+    pub(super) type Reputation<T: Config> =
+        StorageMap<_, Blake2_128Concat, T::AccountId, ReputationRecord, ValueQuery>;
+
+    /// Files and bytes every account is a registrant of, and the native deposits it has locked
+    /// for registrations. Accounts with nothing registered have no entry.
+    #[pallet::storage]
+    #[pallet::getter(fn get_usage)]
+    pub(super) type AccountUsage<T: Config> =
+        StorageMap<_, Blake2_128Concat, T::AccountId, UsageRecordOf<T>, ValueQuery>;
+
+    /// Content of every file served by the nodes of the [`OcwAuthorities`] through their RPC, as
+    /// reported by their offchain workers, by authority.
+    #[pallet::storage]
+    #[pallet::getter(fn get_served_bandwidth)]
+    pub(super) type ServedBandwidth<T: Config> = StorageDoubleMap<
+        _,
+        Blake2_128Concat,
+        T::Hash,
+        Blake2_128Concat,
+        T::AuthorityId,
+        BandwidthRecordOf<T>,
+        ValueQuery,
+    >;
+
+    /// What is kept of every file taken down by the moderation origin, so that references to it
+    /// can still be checked once it is gone. Tombstones outlive a later registration of the same
+    /// content.
+    #[pallet::storage]
+    #[pallet::getter(fn tombstone)]
+    pub(super) type Tombstones<T: Config> =
+        StorageMap<_, Blake2_128Concat, T::Hash, TombstoneOf<T>, OptionQuery>;
+
+    /// Total size of the files registered, in bytes, bounded by [`Config::MaxTotalBytes`].
+    #[pallet::storage]
+    #[pallet::getter(fn total_bytes_stored)]
+    pub(super) type TotalBytesStored<T: Config> = StorageValue<_, u64, ValueQuery>;
+
+    /// Total native balance held by the pallet, whatever the [`HoldReason`].
+    #[pallet::storage]
+    #[pallet::getter(fn held_balance)]
+    pub(super) type HeldBalance<T: Config> = StorageValue<_, BalanceOf<T>, ValueQuery>;
+
+    /// Block at which the deletion of each registration of a file is scheduled, if any. The
+    /// task is named after the file and the registrant, see `deletion_task`.
+    #[pallet::storage]
+    #[pallet::getter(fn get_scheduled_deletion)]
+    pub(super) type ScheduledDeletions<T: Config> = StorageDoubleMap<
+        _,
+        Blake2_128Concat,
+        T::Hash,
+        Blake2_128Concat,
+        T::AccountId,
+        T::BlockNumber,
+        OptionQuery,
+    >;
+
+    /// Whether uploads and changes to registered files are halted.
+    #[pallet::storage]
+    #[pallet::getter(fn is_paused)]
+    pub(super) type Paused<T: Config> = StorageValue<_, bool, ValueQuery>;
+
+    /// Authorities whose offchain workers push files to IPFS and submit status transactions,
+    /// signed with their keys. No offchain worker does any work while empty.
+    #[pallet::storage]
+    #[pallet::getter(fn get_ocw_authorities)]
+    pub(super) type OcwAuthorities<T: Config> =
+        StorageValue<_, BoundedVec<T::AuthorityId, T::MaxOcwAuthorities>, ValueQuery>;
+
+    /// Files removed with chunk allocations still to be dropped by [`Pallet::reclaim_chunks`].
+    #[pallet::storage]
+    pub(super) type OrphanedChunks<T: Config> =
+        StorageMap<_, Blake2_128Concat, T::Hash, (), OptionQuery>;
+
+    /// Files uploaded in the current block, pending to be pushed to IPFS by the offchain worker.
+    /// Cleared at the beginning of every block.
+    #[pallet::storage]
+    pub(super) type BlockUploads<T: Config> =
+        StorageValue<_, BoundedVec<T::Hash, T::MaxUploadsPerBlock>, ValueQuery>;
+
+    /// Uploads submitted by every account in the current block, bounded by
+    /// [`Config::MaxUploadsPerAccountPerBlock`]. Cleared at the end of every block.
+    #[pallet::storage]
+    pub(super) type AccountUploads<T: Config> =
+        StorageMap<_, Blake2_128Concat, T::AccountId, u32, ValueQuery>;
+
+    #[pallet::extra_constants]
+    impl<T: Config> Pallet<T> {
+        /// Maximum number of pieces a file can be split in.
+        #[allow(non_snake_case)]
+        pub fn MaxPieces() -> u32 {
+            file_merkle_tree::MAX_PIECES
+        }
+
+        /// Maximum number of siblings in the merkle proof of a piece, so that clients can size
+        /// their buffers and reject longer proofs before hashing them.
+        #[allow(non_snake_case)]
+        pub fn MaxProofLen() -> u32 {
+            file_merkle_tree::MAX_TREE_DEPTH
+        }
+    }
+
+    #[pallet::hooks]
+    impl<T: Config> Hooks<BlockNumberFor<T>> for Pallet<T> {
+        fn on_initialize(block_number: T::BlockNumber) -> Weight {
+            BlockUploads::<T>::kill();
+            let period = T::AuditPeriod::get();
+            let audit = if !period.is_zero() && (block_number % period).is_zero() {
+                Self::draw_audit_sample(block_number)
+            } else {
+                Weight::zero()
+            };
+            T::DbWeight::get().writes(1).saturating_add(audit)
+        }
+
+        fn on_finalize(_block_number: T::BlockNumber) {
+            // every entry was written by an upload, which paid for its removal, see
+            // `account_uploads_cleanup`
+            let _ = AccountUploads::<T>::clear(u32::MAX, None);
+        }
+
+        fn on_idle(_block_number: T::BlockNumber, remaining_weight: Weight) -> Weight {
+            Self::reclaim_chunks(remaining_weight)
+        }
+
+        fn offchain_worker(block_number: T::BlockNumber) {
+            Self::run_offchain_worker(block_number);
+        }
+
+        fn integrity_test() {
+            assert!(
+                T::MaxKeyHolders::get() <= u8::MAX as u32,
+                "shares can only be evaluated at 255 different points"
+            );
+            assert!(
+                T::TargetPieces::get() <= file_merkle_tree::MAX_PIECES,
+                "files cannot be split in more than MAX_PIECES pieces"
+            );
+        }
+    }
+
+    #[pallet::call]
+    impl<T: Config> Pallet<T> {
+        /// Uploads a file to the blockchain and computes its merkle tree.
+        /// Bear in mind that as a general rule of thumb blockchains should not store big amounts of
+        /// data, and instead decentralized services like IPFS should be used, storing only the
+        /// associated hash on the blockchain.
+        ///
+        /// A deposit proportional to the file size is taken from the caller, in the native
+        /// currency or, if `deposit_asset` is given, in the corresponding asset.
+        ///
+        /// Uploading a file that is already registered does not store it again: the caller is
+        /// added as one more registrant, and the file is kept until all of them delete it.
+        ///
+        /// The origin must satisfy [`Config::UploadOrigin`].
+        ///
+        /// `license` is recorded in the metadata of new files. It is ignored when the caller is
+        /// only added as a registrant, as the metadata belongs to the owner.
+        #[pallet::call_index(0)]
+        #[pallet::weight(
+            T::WeightInfo::upload_file(file_bytes.len() as u32)
+                .saturating_add(Pallet::<T>::account_uploads_cleanup())
+        )]
+        pub fn upload_file(
+            origin: OriginFor<T>,
+            file_bytes: Vec<u8>,
+            deposit_asset: Option<AssetIdOf<T>>,
+            license: Option<LicenseOf<T>>,
+        ) -> DispatchResult {
+            // Check that the extrinsic comes from an allowed uploader and get its account.
+            // This function will return an error otherwise.
+            let who = T::UploadOrigin::ensure_origin(origin)?;
+            Self::do_upload(Some(&who), who.clone(), file_bytes, deposit_asset, license)?;
+            Ok(())
+        }
+
+        /// Sets the metadata of an already registered file, replacing any previous one.
+        /// Only the owner of the file is allowed to do so.
+        #[pallet::call_index(1)]
+        #[pallet::weight(T::WeightInfo::set_metadata())]
+        pub fn set_metadata(
+            origin: OriginFor<T>,
+            merkle_root: T::Hash,
+            metadata: FileMetadataOf<T>,
+        ) -> DispatchResult {
+            let who = ensure_signed(origin)?;
+            Self::ensure_not_paused()?;
+            ensure!(
+                metadata.license.as_ref().map_or(true, License::is_valid),
+                Error::<T>::InvalidLicense
+            );
+
+            let owner = FileOwners::<T>::get(merkle_root).ok_or(Error::<T>::FileNotFound)?;
+            ensure!(owner == who, Error::<T>::NotFileOwner);
+
+            Metadata::<T>::insert(merkle_root, metadata);
+            Self::note_change(merkle_root, FileChange::MetadataUpdated { who: who.clone() });
+
+            Self::deposit_event(Event::MetadataUpdated { who, merkle_root });
+
+            Ok(())
+        }
+
+        /// Records how many chunks of a file the offchain worker has pushed to IPFS.
+        /// Submitted as an unsigned transaction by the offchain worker of one of the
+        /// [`OcwAuthorities`], signed with its key.
+        #[pallet::call_index(2)]
+        #[pallet::weight(T::WeightInfo::report_upload_progress())]
+        pub fn report_upload_progress(
+            origin: OriginFor<T>,
+            merkle_root: T::Hash,
+            done: u32,
+            total: u32,
+            proof: AuthorityProofOf<T>,
+        ) -> DispatchResult {
+            ensure_none(origin)?;
+            ensure!(
+                Self::is_authority_report(&(b"progress", merkle_root, done, total), &proof),
+                Error::<T>::InvalidAuthorityProof
+            );
+            ensure!(
+                Self::is_valid_progress(&merkle_root, done, total),
+                Error::<T>::InvalidProgress
+            );
+
+            UploadProgress::<T>::insert(merkle_root, done);
+            // chunks are pushed in order, so all the ones before `done` are on IPFS
+            let uploaded = u32::MAX.checked_shr(u32::BITS - done).unwrap_or(0);
+            UploadedPieces::<T>::mutate(merkle_root, |pieces| *pieces |= uploaded);
+
+            Self::deposit_event(Event::ChunksUploaded {
+                merkle_root,
+                done,
+                total,
+            });
+
+            Ok(())
+        }
+
+        /// Records the IPFS Cluster peers a chunk has been allocated to.
+        /// Submitted as an unsigned transaction by the offchain worker of one of the
+        /// [`OcwAuthorities`], signed with its key.
+        #[pallet::call_index(3)]
+        #[pallet::weight(T::WeightInfo::report_chunk_allocations(allocations.len() as u32))]
+        pub fn report_chunk_allocations(
+            origin: OriginFor<T>,
+            merkle_root: T::Hash,
+            position: u64,
+            allocations: AllocationsOf<T>,
+            proof: AuthorityProofOf<T>,
+        ) -> DispatchResult {
+            ensure_none(origin)?;
+            ensure!(
+                Self::is_authority_report(
+                    &(b"allocations", merkle_root, position, &allocations),
+                    &proof
+                ),
+                Error::<T>::InvalidAuthorityProof
+            );
+            ensure!(Self::is_valid_position(&merkle_root, position), Error::<T>::InvalidPosition);
+
+            let peers = allocations.len() as u32;
+            ChunkAllocations::<T>::insert(merkle_root, position, allocations);
+            UploadedPieces::<T>::mutate(merkle_root, |pieces| *pieces |= 1 << position);
+
+            Self::deposit_event(Event::ChunkAllocated {
+                merkle_root,
+                position,
+                peers,
+            });
+
+            Ok(())
+        }
+
+        /// Records whether a chunk could be retrieved through the public gateway.
+        /// Submitted as an unsigned transaction by the offchain worker of one of the
+        /// [`OcwAuthorities`], signed with its key.
+        #[pallet::call_index(4)]
+        #[pallet::weight(T::WeightInfo::report_probe())]
+        pub fn report_probe(
+            origin: OriginFor<T>,
+            merkle_root: T::Hash,
+            position: u64,
+            available: bool,
+            proof: AuthorityProofOf<T>,
+        ) -> DispatchResult {
+            ensure_none(origin)?;
+            ensure!(
+                Self::is_authority_report(&(b"probe", merkle_root, position, available), &proof),
+                Error::<T>::InvalidAuthorityProof
+            );
+            ensure!(Self::is_valid_position(&merkle_root, position), Error::<T>::InvalidPosition);
+
+            let result = ProbeResult {
+                block: <frame_system::Pallet<T>>::block_number(),
+                position,
+                available,
+            };
+            ProbeResults::<T>::insert(merkle_root, result);
+
+            Self::deposit_event(Event::ProbeReported {
+                merkle_root,
+                position,
+                available,
+            });
+
+            Ok(())
+        }
+
+        /// Asks the offchain worker to rebuild the tree of a file with the current chunking
+        /// parameters, e.g. after a runtime upgrade changing them.
+        /// Only the owner of the file is allowed to do so.
+        #[pallet::call_index(5)]
+        #[pallet::weight(T::WeightInfo::rechunk_file())]
+        pub fn rechunk_file(origin: OriginFor<T>, merkle_root: T::Hash) -> DispatchResult {
+            let who = ensure_signed(origin)?;
+            Self::ensure_not_paused()?;
+
+            let owner = FileOwners::<T>::get(merkle_root).ok_or(Error::<T>::FileNotFound)?;
+            ensure!(owner == who, Error::<T>::NotFileOwner);
+            let tree = FileTrees::<T>::get(merkle_root).ok_or(Error::<T>::FileNotFound)?;
+            let params = Self::chunking_params();
+            ensure!(!tree.is_valid(&params), Error::<T>::AlreadyChunked);
+            ensure!(
+                file_merkle_tree::pieces_for(tree.file_size, &params)
+                    <= file_merkle_tree::MAX_PIECES,
+                Error::<T>::TooManyPieces
+            );
+
+            PendingRechunks::<T>::insert(merkle_root, ());
+
+            Self::deposit_event(Event::RechunkRequested { who, merkle_root });
+
+            Ok(())
+        }
+
+        /// Replaces the tree of a file with the one rebuilt by the offchain worker, moving all
+        /// its records to the new merkle root and keeping the old one as an alias.
+        /// Submitted as an unsigned transaction by the offchain worker of one of the
+        /// [`OcwAuthorities`], signed with its key.
+        #[pallet::call_index(6)]
+        #[pallet::weight(T::WeightInfo::report_rechunk(
+            merkle_tree.pieces(),
+            T::MaxRegistrants::get(),
+        ))]
+        pub fn report_rechunk(
+            origin: OriginFor<T>,
+            merkle_root: T::Hash,
+            merkle_tree: FileMerkleTree,
+            proof: AuthorityProofOf<T>,
+        ) -> DispatchResult {
+            ensure_none(origin)?;
+            ensure!(
+                Self::is_authority_report(&(b"rechunk", merkle_root, &merkle_tree), &proof),
+                Error::<T>::InvalidAuthorityProof
+            );
+            ensure!(
+                PendingRechunks::<T>::contains_key(merkle_root),
+                Error::<T>::RechunkNotRequested
+            );
+            let (owner, old_tree) = Self::get_file(merkle_root).ok_or(Error::<T>::FileNotFound)?;
+            ensure!(Self::is_valid_rechunk(&old_tree, &merkle_tree), Error::<T>::InvalidMerkleTree);
+            let new_root =
+                T::Hash::decode(&mut merkle_tree.merkle_root()).or(Err(Error::<T>::Unhasheable))?;
+            ensure!(!FileOwners::<T>::contains_key(new_root), Error::<T>::FileAlreadyExists);
+
+            // the new chunks have to be pushed to IPFS
+            BlockUploads::<T>::try_append(new_root)
+                .map_err(|_| Error::<T>::TooManyUploadsInBlock)?;
+
+            PendingRechunks::<T>::remove(merkle_root);
+            Self::take_file(merkle_root);
+            Self::put_file(new_root, &owner, &merkle_tree);
+            if let Some(metadata) = Metadata::<T>::take(merkle_root) {
+                Metadata::<T>::insert(new_root, metadata);
+            }
+            if let Some(checksum) = Checksums::<T>::take(merkle_root) {
+                Checksums::<T>::insert(new_root, checksum);
+            }
+            if let Some(time) = UploadedAt::<T>::take(merkle_root) {
+                UploadedAt::<T>::insert(new_root, time);
+            }
+            if FrozenFiles::<T>::take(merkle_root).is_some() {
+                FrozenFiles::<T>::insert(new_root, ());
+            }
+            if let Some(threshold) = ManagementThresholds::<T>::take(merkle_root) {
+                ManagementThresholds::<T>::insert(new_root, threshold);
+            }
+            for (registrant, deposit) in Registrants::<T>::drain_prefix(merkle_root) {
+                Registrants::<T>::insert(new_root, registrant, deposit);
+            }
+            for (registrant, sponsor) in Sponsors::<T>::drain_prefix(merkle_root) {
+                Sponsors::<T>::insert(new_root, registrant, sponsor);
+            }
+            if let Some(lock) = TimeLocks::<T>::take(merkle_root) {
+                TimeLocks::<T>::insert(new_root, lock);
+            }
+            if let Some(escrow) = KeyEscrows::<T>::take(merkle_root) {
+                KeyEscrows::<T>::insert(new_root, escrow);
+            }
+            let reports = Reports::<T>::take(merkle_root);
+            if !reports.is_empty() {
+                Reports::<T>::insert(new_root, reports);
+            }
+            let history = FileHistory::<T>::take(merkle_root);
+            if !history.is_empty() {
+                FileHistory::<T>::insert(new_root, history);
+            }
+            // tasks name the file, so they are scheduled again under the new root
+            for (registrant, when) in ScheduledDeletions::<T>::drain_prefix(merkle_root) {
+                let _ = T::Scheduler::cancel_named(Self::deletion_task(merkle_root, &registrant));
+                if Self::schedule_deletion_task(new_root, &registrant, when).is_ok() {
+                    ScheduledDeletions::<T>::insert(new_root, registrant, when);
+                }
+            }
+            RegistrantCount::<T>::insert(new_root, RegistrantCount::<T>::take(merkle_root));
+            // chunks are different, so whatever was recorded about them is no longer relevant
+            UploadProgress::<T>::remove(merkle_root);
+            UploadedPieces::<T>::remove(merkle_root);
+            let _ = ChunkAllocations::<T>::clear_prefix(merkle_root, old_tree.pieces(), None);
+            ProbeResults::<T>::remove(merkle_root);
+            PieceAudits::<T>::remove(merkle_root);
+            LastPinnedAt::<T>::remove(merkle_root);
+            RootAliases::<T>::remove(new_root);
+            RootAliases::<T>::insert(merkle_root, new_root);
+
+            Self::deposit_event(Event::FileRechunked {
+                old_root: merkle_root,
+                new_root,
+                pieces: merkle_tree.pieces(),
+                chunk_size: merkle_tree.chunk_size() as u32,
+            });
+
+            Ok(())
+        }
+
+        /// Drops the caller's registration of a file, giving its deposit back to whoever paid
+        /// it. If the owner
+        /// leaves, ownership passes to another registrant. Once the last registrant leaves, the
+        /// file is removed along with everything recorded about it.
+        #[pallet::call_index(7)]
+        #[pallet::weight(T::WeightInfo::delete_file())]
+        pub fn delete_file(origin: OriginFor<T>, merkle_root: T::Hash) -> DispatchResult {
+            let who = ensure_signed(origin)?;
+            Self::ensure_not_paused()?;
+
+            let owner = FileOwners::<T>::get(merkle_root).ok_or(Error::<T>::FileNotFound)?;
+            let layout = FileMeta::<T>::get(merkle_root).ok_or(Error::<T>::FileNotFound)?;
+            let deposit =
+                Registrants::<T>::take(merkle_root, &who).ok_or(Error::<T>::NotRegistrant)?;
+            let payer = Sponsors::<T>::take(merkle_root, &who).unwrap_or_else(|| who.clone());
+            Self::unschedule_deletion(merkle_root, &who);
+            Self::note_deregistration(&who, &payer, layout.size, &deposit);
+            Self::release_deposit(&payer, deposit)?;
+
+            let registrants = RegistrantCount::<T>::get(merkle_root).saturating_sub(1);
+            if registrants == 0 {
+                Self::remove_file(merkle_root);
+                Self::deposit_event(Event::FileDeleted { merkle_root });
+                return Ok(());
+            }
+            RegistrantCount::<T>::insert(merkle_root, registrants);
+            if owner == who {
+                if let Some(new_owner) = Registrants::<T>::iter_key_prefix(merkle_root).next() {
+                    FileOwners::<T>::insert(merkle_root, &new_owner);
+                    ManagementThresholds::<T>::remove(merkle_root);
+                    let change = FileChange::OwnershipTransferred {
+                        from: who.clone(),
+                        to: new_owner,
+                    };
+                    Self::note_change(merkle_root, change);
+                }
+            }
+
+            Self::deposit_event(Event::RegistrantRemoved {
+                who,
+                merkle_root,
+                registrants,
+            });
+
+            Ok(())
+        }
+
+        /// Halts uploads and changes to registered files until `unpause` is called: every call
+        /// fails with `Paused` but the reports of the offchain workers, which keep the state of
+        /// files in flight up to date, attestations delivered by trusted siblings, which would
+        /// otherwise be lost, and the calls of [`Config::AdminOrigin`].
+        #[pallet::call_index(8)]
+        #[pallet::weight(T::WeightInfo::pause())]
+        pub fn pause(origin: OriginFor<T>) -> DispatchResult {
+            T::AdminOrigin::ensure_origin(origin)?;
+
+            Paused::<T>::put(true);
+
+            Self::deposit_event(Event::UploadsPaused);
+
+            Ok(())
+        }
+
+        /// Allows uploads and changes to registered files again.
+        #[pallet::call_index(9)]
+        #[pallet::weight(T::WeightInfo::unpause())]
+        pub fn unpause(origin: OriginFor<T>) -> DispatchResult {
+            T::AdminOrigin::ensure_origin(origin)?;
+
+            Paused::<T>::kill();
+
+            Self::deposit_event(Event::UploadsResumed);
+
+            Ok(())
+        }
+
+        /// Registers `name` as a human readable handle of the file under `merkle_root` for
+        /// [`Config::NameRegistrationPeriod`] blocks, holding a deposit of
+        /// [`Config::NameDeposit`] plus [`Config::NameDepositPerByte`] for every byte of the name
+        /// from the caller. Names are unique: until they expire, only their owner can point them
+        /// to a different file, with no further deposit. Expired names can be registered by
+        /// anyone, giving the deposit of their previous owner back.
+        #[pallet::call_index(10)]
+        #[pallet::weight(T::WeightInfo::register_alias())]
+        pub fn register_alias(
+            origin: OriginFor<T>,
+            name: NameOf<T>,
+            merkle_root: T::Hash,
+        ) -> DispatchResult {
+            let who = ensure_signed(origin)?;
+            Self::ensure_not_paused()?;
+            ensure!(Self::is_valid_name(&name), Error::<T>::InvalidName);
+            ensure!(FileOwners::<T>::contains_key(merkle_root), Error::<T>::FileNotFound);
+
+            let now = frame_system::Pallet::<T>::block_number();
+            let alias = match Names::<T>::get(&name) {
+                Some(alias) if alias.owner == who => Alias {
+                    merkle_root,
+                    ..alias
+                },
+                previous => {
+                    if let Some(previous) = previous {
+                        ensure!(previous.expires_at <= now, Error::<T>::NameTaken);
+                        Self::release(HoldReason::AliasDeposit, &previous.owner, previous.deposit);
+                    }
+                    let deposit = Self::name_deposit(name.len() as u32);
+                    Self::hold(HoldReason::AliasDeposit, &who, deposit)?;
+                    Alias {
+                        owner: who.clone(),
+                        merkle_root,
+                        deposit,
+                        expires_at: now.saturating_add(T::NameRegistrationPeriod::get()),
+                    }
+                }
+            };
+            Names::<T>::insert(&name, alias);
+
+            Self::deposit_event(Event::AliasRegistered {
+                who,
+                name,
+                merkle_root,
+            });
+
+            Ok(())
+        }
+
+        /// Releases a name registered by the caller, giving its deposit back.
+        #[pallet::call_index(11)]
+        #[pallet::weight(T::WeightInfo::release_alias())]
+        pub fn release_alias(origin: OriginFor<T>, name: NameOf<T>) -> DispatchResult {
+            let who = ensure_signed(origin)?;
+            Self::ensure_not_paused()?;
+
+            let alias = Names::<T>::get(&name).ok_or(Error::<T>::NameNotFound)?;
+            ensure!(alias.owner == who, Error::<T>::NotNameOwner);
+            Self::release(HoldReason::AliasDeposit, &who, alias.deposit);
+            Names::<T>::remove(&name);
+
+            Self::deposit_event(Event::AliasReleased { who, name });
+
+            Ok(())
+        }
+
+        /// Records that the offchain worker has pinned again all the chunks of a file.
+        /// Submitted as an unsigned transaction by the offchain worker of one of the
+        /// [`OcwAuthorities`], signed with its key.
+        #[pallet::call_index(12)]
+        #[pallet::weight(T::WeightInfo::report_repin())]
+        pub fn report_repin(
+            origin: OriginFor<T>,
+            merkle_root: T::Hash,
+            proof: AuthorityProofOf<T>,
+        ) -> DispatchResult {
+            ensure_none(origin)?;
+            ensure!(
+                Self::is_authority_report(&(b"repin", merkle_root), &proof),
+                Error::<T>::InvalidAuthorityProof
+            );
+            ensure!(FileOwners::<T>::contains_key(merkle_root), Error::<T>::FileNotFound);
+            ensure!(Self::is_repin_due(&merkle_root), Error::<T>::RepinNotDue);
+
+            LastPinnedAt::<T>::insert(merkle_root, <frame_system::Pallet<T>>::block_number());
+
+            Self::deposit_event(Event::FileRepinned { merkle_root });
+
+            Ok(())
+        }
+
+        /// Uploads a file on behalf of `beneficiary`, who becomes its owner or one more
+        /// registrant, exactly as if they had called `upload_file`. The caller pays the deposit,
+        /// always in the native currency, and gets it back when `beneficiary` deletes the file.
+        ///
+        /// The origin must satisfy [`Config::UploadOrigin`].
+        #[pallet::call_index(13)]
+        #[pallet::weight(
+            T::WeightInfo::sponsor_upload(file_bytes.len() as u32)
+                .saturating_add(Pallet::<T>::account_uploads_cleanup())
+        )]
+        pub fn sponsor_upload(
+            origin: OriginFor<T>,
+            beneficiary: T::AccountId,
+            file_bytes: Vec<u8>,
+        ) -> DispatchResult {
+            let sponsor = T::UploadOrigin::ensure_origin(origin)?;
+            let merkle_root =
+                Self::do_upload(Some(&sponsor), beneficiary.clone(), file_bytes, None, None)?;
+
+            Self::deposit_event(Event::UploadSponsored {
+                sponsor,
+                beneficiary,
+                merkle_root,
+            });
+
+            Ok(())
+        }
+
+        /// Uploads a file anonymously, with no deposit nor fees. Spam is prevented by requiring
+        /// a proof of work instead: the hash of the merkle root of the file along with `nonce`
+        /// must have at least [`Config::UnsignedUploadDifficulty`] leading zero bits (see
+        /// [`Pallet::upload_work`]).
+        ///
+        /// Anonymous files are owned by the pallet account, so nobody can delete them, and are
+        /// rejected if already stored. Submitted as an unsigned transaction.
+        #[pallet::call_index(14)]
+        #[pallet::weight(T::WeightInfo::upload_file_unsigned(file_bytes.len() as u32))]
+        pub fn upload_file_unsigned(
+            origin: OriginFor<T>,
+            file_bytes: Vec<u8>,
+            nonce: u64,
+        ) -> DispatchResult {
+            ensure_none(origin)?;
+            let difficulty =
+                T::UnsignedUploadDifficulty::get().ok_or(Error::<T>::UnsignedUploadsDisabled)?;
+            Self::ensure_not_paused()?;
+            Self::ensure_capacity(file_bytes.len() as u64)?;
+            let (merkle_tree, merkle_root) = Self::build_tree(&file_bytes)?;
+            ensure!(
+                Self::upload_work(&merkle_root, nonce) >= difficulty,
+                Error::<T>::InsufficientWork
+            );
+
+            Self::register_upload(
+                None,
+                Self::account_id(),
+                file_bytes,
+                (merkle_tree, merkle_root),
+                None,
+                None,
+            )?;
+
+            Ok(())
+        }
+
+        /// Uploads up to [`Config::MaxFilesPerBatch`] files at once, each of them registered as
+        /// an independent file exactly as `upload_file` would, with the deposit in the native
+        /// currency. Either all of them are registered or none is.
+        ///
+        /// The origin must satisfy [`Config::UploadOrigin`].
+        #[pallet::call_index(15)]
+        #[pallet::weight(
+            T::WeightInfo::upload_files(
+                files.len() as u32,
+                files.iter().map(Vec::len).sum::<usize>() as u32,
+            )
+            .saturating_add(Pallet::<T>::account_uploads_cleanup())
+        )]
+        pub fn upload_files(
+            origin: OriginFor<T>,
+            files: BoundedVec<Vec<u8>, T::MaxFilesPerBatch>,
+        ) -> DispatchResult {
+            let who = T::UploadOrigin::ensure_origin(origin)?;
+            for file_bytes in files {
+                Self::do_upload(Some(&who), who.clone(), file_bytes, None, None)?;
+            }
+            Ok(())
+        }
+
+        /// Groups registered files in a bundle, e.g. the binaries and signatures of a software
+        /// release, so that the whole set can be referenced by a single hash: the merkle root
+        /// over the merkle roots of `members`, in the given order. Membership of each file can
+        /// then be proven with [`Pallet::prove_membership`].
+        ///
+        /// A deposit proportional to the number of members is held from the caller. Members
+        /// deleted afterwards are still part of the bundle.
+        #[pallet::call_index(16)]
+        #[pallet::weight(T::WeightInfo::create_bundle(members.len() as u32))]
+        pub fn create_bundle(origin: OriginFor<T>, members: BundleMembersOf<T>) -> DispatchResult {
+            let who = ensure_signed(origin)?;
+            Self::ensure_not_paused()?;
+            ensure!(members.len() >= 2, Error::<T>::BundleTooSmall);
+            for member in &members {
+                ensure!(FileOwners::<T>::contains_key(member), Error::<T>::FileNotFound);
+            }
+            let bundle_root = Self::bundle_root(&members).ok_or(Error::<T>::Unhasheable)?;
+            ensure!(!Bundles::<T>::contains_key(bundle_root), Error::<T>::BundleExists);
+
+            let deposit = Self::deposit_for(members.encoded_size() as u32);
+            Self::hold(HoldReason::BundleDeposit, &who, deposit)?;
+            let count = members.len() as u32;
+            Bundles::<T>::insert(
+                bundle_root,
+                Bundle {
+                    owner: who.clone(),
+                    members,
+                    deposit,
+                },
+            );
+
+            Self::deposit_event(Event::BundleCreated {
+                who,
+                bundle_root,
+                members: count,
+            });
+
+            Ok(())
+        }
+
+        /// Removes a bundle created by the caller, giving its deposit back. Its members are
+        /// left untouched.
+        #[pallet::call_index(17)]
+        #[pallet::weight(T::WeightInfo::remove_bundle())]
+        pub fn remove_bundle(origin: OriginFor<T>, bundle_root: T::Hash) -> DispatchResult {
+            let who = ensure_signed(origin)?;
+            Self::ensure_not_paused()?;
+
+            let bundle = Bundles::<T>::get(bundle_root).ok_or(Error::<T>::BundleNotFound)?;
+            ensure!(bundle.owner == who, Error::<T>::NotBundleOwner);
+            Self::release(HoldReason::BundleDeposit, &who, bundle.deposit);
+            Bundles::<T>::remove(bundle_root);
+
+            Self::deposit_event(Event::BundleRemoved { bundle_root });
+
+            Ok(())
+        }
+
+        /// Time-locks a file whose content has been encrypted, committing to the sha256 of the
+        /// key it can be decrypted with. The key can only be revealed with `reveal_key` from
+        /// block `reveal_at` on, e.g. for embargoed documents or fair-launch game assets.
+        /// Only the owner of the file is allowed to do so, and commitments cannot be changed.
+        #[pallet::call_index(18)]
+        #[pallet::weight(T::WeightInfo::commit_key())]
+        pub fn commit_key(
+            origin: OriginFor<T>,
+            merkle_root: T::Hash,
+            commitment: [u8; 32],
+            reveal_at: T::BlockNumber,
+        ) -> DispatchResult {
+            let who = ensure_signed(origin)?;
+            Self::ensure_not_paused()?;
+
+            let owner = FileOwners::<T>::get(merkle_root).ok_or(Error::<T>::FileNotFound)?;
+            ensure!(owner == who, Error::<T>::NotFileOwner);
+            ensure!(!TimeLocks::<T>::contains_key(merkle_root), Error::<T>::KeyAlreadyCommitted);
+
+            TimeLocks::<T>::insert(
+                merkle_root,
+                TimeLock {
+                    commitment,
+                    reveal_at,
+                    key: None,
+                },
+            );
+
+            Self::deposit_event(Event::KeyCommitted {
+                merkle_root,
+                reveal_at,
+            });
+
+            Ok(())
+        }
+
+        /// Reveals the key of a time-locked file once its embargo is over. Anyone knowing the
+        /// key can reveal it, as long as its sha256 matches the commitment.
+        #[pallet::call_index(19)]
+        #[pallet::weight(T::WeightInfo::reveal_key())]
+        pub fn reveal_key(
+            origin: OriginFor<T>,
+            merkle_root: T::Hash,
+            key: KeyOf<T>,
+        ) -> DispatchResult {
+            ensure_signed(origin)?;
+            Self::ensure_not_paused()?;
+
+            let mut lock = TimeLocks::<T>::get(merkle_root).ok_or(Error::<T>::NoKeyCommitment)?;
+            ensure!(lock.key.is_none(), Error::<T>::KeyAlreadyRevealed);
+            ensure!(
+                <frame_system::Pallet<T>>::block_number() >= lock.reveal_at,
+                Error::<T>::RevealTooEarly
+            );
+            ensure!(sp_io::hashing::sha2_256(&key) == lock.commitment, Error::<T>::KeyMismatch);
+
+            lock.key = Some(key.clone());
+            TimeLocks::<T>::insert(merkle_root, lock);
+            KeyEscrows::<T>::remove(merkle_root);
+
+            Self::deposit_event(Event::KeyRevealed { merkle_root, key });
+
+            Ok(())
+        }
+
+        /// Time-locks a file like `commit_key`, additionally splitting its key among members of
+        /// [`Config::KeyCommittee`] so that it gets revealed even if the owner never does.
+        ///
+        /// `shares` holds, for every holder, the sha256 of its share and the share encrypted to
+        /// it, in the order the key was split in with [`shamir::split`]. Once the embargo is
+        /// over holders decrypt their shares and submit them with `submit_key_share`, and the
+        /// key is rebuilt as soon as `threshold` of them are in.
+        #[pallet::call_index(20)]
+        #[pallet::weight(T::WeightInfo::escrow_key(shares.len() as u32))]
+        pub fn escrow_key(
+            origin: OriginFor<T>,
+            merkle_root: T::Hash,
+            commitment: [u8; 32],
+            reveal_at: T::BlockNumber,
+            threshold: u32,
+            shares: BoundedVec<(T::AccountId, [u8; 32], EncryptedShareOf<T>), T::MaxKeyHolders>,
+        ) -> DispatchResult {
+            let who = ensure_signed(origin)?;
+            Self::ensure_not_paused()?;
+
+            let owner = FileOwners::<T>::get(merkle_root).ok_or(Error::<T>::FileNotFound)?;
+            ensure!(owner == who, Error::<T>::NotFileOwner);
+            ensure!(!TimeLocks::<T>::contains_key(merkle_root), Error::<T>::KeyAlreadyCommitted);
+            ensure!(
+                threshold > 0 && threshold as usize <= shares.len(),
+                Error::<T>::InvalidThreshold
+            );
+            for (i, (holder, _, _)) in shares.iter().enumerate() {
+                ensure!(T::KeyCommittee::contains(holder), Error::<T>::NotCommitteeMember);
+                ensure!(
+                    shares[..i].iter().all(|(other, _, _)| other != holder),
+                    Error::<T>::DuplicateKeyHolder
+                );
+            }
+
+            let shares = shares
+                .into_iter()
+                .map(|(holder, commitment, encrypted)| KeyShare {
+                    holder,
+                    commitment,
+                    encrypted,
+                    share: None,
+                });
+            KeyEscrows::<T>::insert(
+                merkle_root,
+                KeyEscrow {
+                    threshold,
+                    shares: BoundedVec::truncate_from(shares.collect()),
+                },
+            );
+            TimeLocks::<T>::insert(
+                merkle_root,
+                TimeLock {
+                    commitment,
+                    reveal_at,
+                    key: None,
+                },
+            );
+
+            Self::deposit_event(Event::KeyEscrowed {
+                merkle_root,
+                reveal_at,
+                threshold,
+            });
+
+            Ok(())
+        }
+
+        /// Submits the caller's share of the escrowed key of a file once its embargo is over.
+        /// The share submitted last before reaching the threshold rebuilds the key, revealing
+        /// it as `reveal_key` would.
+        #[pallet::call_index(21)]
+        #[pallet::weight(T::WeightInfo::submit_key_share(T::MaxKeyHolders::get()))]
+        pub fn submit_key_share(
+            origin: OriginFor<T>,
+            merkle_root: T::Hash,
+            share: KeyOf<T>,
+        ) -> DispatchResult {
+            let who = ensure_signed(origin)?;
+            Self::ensure_not_paused()?;
+
+            let mut escrow = KeyEscrows::<T>::get(merkle_root).ok_or(Error::<T>::NoKeyEscrow)?;
+            let mut lock = TimeLocks::<T>::get(merkle_root).ok_or(Error::<T>::NoKeyEscrow)?;
+            ensure!(
+                <frame_system::Pallet<T>>::block_number() >= lock.reveal_at,
+                Error::<T>::RevealTooEarly
+            );
+            let key_share = escrow
+                .shares
+                .iter_mut()
+                .find(|key_share| key_share.holder == who)
+                .ok_or(Error::<T>::NotKeyHolder)?;
+            ensure!(key_share.share.is_none(), Error::<T>::ShareAlreadySubmitted);
+            ensure!(
+                sp_io::hashing::sha2_256(&share) == key_share.commitment,
+                Error::<T>::ShareMismatch
+            );
+            key_share.share = Some(share);
+
+            Self::deposit_event(Event::KeyShareSubmitted {
+                merkle_root,
+                holder: who,
+            });
+
+            let submitted: Vec<(u8, &[u8])> = escrow
+                .shares
+                .iter()
+                .enumerate()
+                .filter_map(|(i, key_share)| {
+                    let share = key_share.share.as_ref()?;
+                    Some((i as u8 + 1, share.as_slice()))
+                })
+                .collect();
+            if submitted.len() < escrow.threshold as usize {
+                KeyEscrows::<T>::insert(merkle_root, escrow);
+                return Ok(());
+            }
+            match shamir::combine(&submitted).and_then(|key| KeyOf::<T>::try_from(key).ok()) {
+                Some(key) if sp_io::hashing::sha2_256(&key) == lock.commitment => {
+                    lock.key = Some(key.clone());
+                    TimeLocks::<T>::insert(merkle_root, lock);
+                    KeyEscrows::<T>::remove(merkle_root);
+                    Self::deposit_event(Event::KeyRevealed { merkle_root, key });
+                }
+                _ => {
+                    KeyEscrows::<T>::insert(merkle_root, escrow);
+                    Self::deposit_event(Event::KeyReconstructionFailed { merkle_root });
+                }
+            }
+
+            Ok(())
+        }
+
+        /// Uploads a file without building its merkle tree on chain, which makes it much cheaper
+        /// than `upload_file` for big files. The file is registered under `claimed_root` once
+        /// the offchain worker has rebuilt the tree out of the content and confirmed it with
+        /// `confirm_root`. If the root turns out to be wrong, or the content cannot be checked,
+        /// the offchain worker rejects it with `reject_root` instead, and the deposit is slashed.
+        /// Until then, the uploader can withdraw it with `cancel_root_claim`.
+        ///
+        /// The deposit is always taken in the native currency. Files already registered can only
+        /// be uploaded with `upload_file`.
+        ///
+        /// The origin must satisfy [`Config::UploadOrigin`].
+        #[pallet::call_index(22)]
+        #[pallet::weight(
+            T::WeightInfo::upload_file_with_root(file_bytes.len() as u32)
+                .saturating_add(Pallet::<T>::account_uploads_cleanup())
+        )]
+        pub fn upload_file_with_root(
+            origin: OriginFor<T>,
+            file_bytes: Vec<u8>,
+            claimed_root: T::Hash,
+        ) -> DispatchResult {
+            let who = T::UploadOrigin::ensure_origin(origin)?;
+            Self::ensure_not_paused()?;
+            Self::note_account_upload(&who)?;
+            Self::check_file(&file_bytes)?;
+            ensure!(!FileOwners::<T>::contains_key(claimed_root), Error::<T>::FileAlreadyExists);
+            ensure!(!RootClaims::<T>::contains_key(claimed_root), Error::<T>::RootAlreadyClaimed);
+            ensure!(
+                !PendingUploads::<T>::contains_key(claimed_root),
+                Error::<T>::UploadPendingApproval
+            );
+            ensure!(!PendingReview::<T>::contains_key(claimed_root), Error::<T>::UploadUnderReview);
+            Self::ensure_capacity(file_bytes.len() as u64)?;
+
+            let deposit = Self::deposit_for(file_bytes.len() as u32);
+            Self::hold(HoldReason::FileDeposit, &who, deposit)?;
+
+            // the offchain worker checks the claim against the indexed content
+            let size = file_bytes.len() as u64;
+            let data = IndexingData {
+                content: file_bytes,
+                chunk_size: file_merkle_tree::chunk_size_for(size, &Self::chunking_params()),
+            };
+            offchain_index::set(&Self::derived_key(&claimed_root), &data.encode());
+            RootClaims::<T>::insert(
+                claimed_root,
+                RootClaim {
+                    who: who.clone(),
+                    deposit,
+                    size,
+                },
+            );
+
+            Self::deposit_event(Event::RootClaimed {
+                who,
+                merkle_root: claimed_root,
+                size,
+            });
+
+            Ok(())
+        }
+
+        /// Registers a file uploaded with `upload_file_with_root` under its claimed merkle root,
+        /// along with the tree rebuilt by the offchain worker and the sha256 of the content.
+        /// Submitted as an unsigned transaction by the offchain worker of one of the
+        /// [`OcwAuthorities`], signed with its key.
+        #[pallet::call_index(23)]
+        #[pallet::weight(T::WeightInfo::confirm_root(merkle_tree.pieces()))]
+        pub fn confirm_root(
+            origin: OriginFor<T>,
+            merkle_root: T::Hash,
+            merkle_tree: FileMerkleTree,
+            sha256: [u8; 32],
+            proof: AuthorityProofOf<T>,
+        ) -> DispatchResult {
+            ensure_none(origin)?;
+            ensure!(
+                Self::is_authority_report(&(b"confirm", merkle_root, &merkle_tree, sha256), &proof),
+                Error::<T>::InvalidAuthorityProof
+            );
+            let claim = RootClaims::<T>::get(merkle_root).ok_or(Error::<T>::RootNotClaimed)?;
+            ensure!(
+                Self::is_valid_claim(&merkle_root, &claim, &merkle_tree),
+                Error::<T>::InvalidMerkleTree
+            );
+            ensure!(!FileOwners::<T>::contains_key(merkle_root), Error::<T>::FileAlreadyExists);
+
+            RootClaims::<T>::remove(merkle_root);
+            let upload = PendingUpload {
+                who: claim.who,
+                sponsor: None,
+                deposit: Deposit::Native(claim.deposit),
+                merkle_tree,
+                sha256,
+                license: None,
+            };
+            // the claim only proved the root, the content still needs to be screened and large
+            // files approved
+            Self::submit_upload(merkle_root, upload)
+        }
+
+        /// Drops an upload whose claimed merkle root does not match its content, or whose content
+        /// cannot be checked, slashing the deposit of the uploader.
+        /// Submitted as an unsigned transaction by the offchain worker of one of the
+        /// [`OcwAuthorities`], signed with its key.
+        #[pallet::call_index(24)]
+        #[pallet::weight(T::WeightInfo::reject_root())]
+        pub fn reject_root(
+            origin: OriginFor<T>,
+            merkle_root: T::Hash,
+            proof: AuthorityProofOf<T>,
+        ) -> DispatchResult {
+            ensure_none(origin)?;
+            ensure!(
+                Self::is_authority_report(&(b"reject", merkle_root), &proof),
+                Error::<T>::InvalidAuthorityProof
+            );
+            let claim = RootClaims::<T>::take(merkle_root).ok_or(Error::<T>::RootNotClaimed)?;
+
+            let unslashed = Self::burn_held(HoldReason::FileDeposit, &claim.who, claim.deposit);
+            offchain_index::clear(&Self::derived_key(&merkle_root));
+
+            Self::deposit_event(Event::RootRejected {
+                who: claim.who,
+                merkle_root,
+                slashed: claim.deposit.saturating_sub(unslashed),
+            });
+
+            Ok(())
+        }
+
+        /// Complains about the content of a registered file, e.g. because it infringes copyright
+        /// or is illegal, pointing to the reasons with `reason_hash`. A bond of
+        /// [`Config::ReportBond`] is held until the moderation origin resolves the report with
+        /// `resolve_report`, and slashed if the report is dismissed.
+        #[pallet::call_index(25)]
+        #[pallet::weight(T::WeightInfo::report_file())]
+        pub fn report_file(
+            origin: OriginFor<T>,
+            merkle_root: T::Hash,
+            reason_hash: [u8; 32],
+        ) -> DispatchResult {
+            let who = ensure_signed(origin)?;
+            Self::ensure_not_paused()?;
+            ensure!(FileOwners::<T>::contains_key(merkle_root), Error::<T>::FileNotFound);
+            let mut reports = Reports::<T>::get(merkle_root);
+            ensure!(
+                !reports.iter().any(|report| report.reporter == who),
+                Error::<T>::AlreadyReported
+            );
+
+            let bond = T::ReportBond::get();
+            reports
+                .try_push(Report {
+                    reporter: who.clone(),
+                    reason_hash,
+                    bond,
+                })
+                .map_err(|_| Error::<T>::TooManyReports)?;
+            Self::hold(HoldReason::ReportBond, &who, bond)?;
+            Reports::<T>::insert(merkle_root, reports);
+
+            Self::deposit_event(Event::FileReported {
+                who,
+                merkle_root,
+                reason_hash,
+            });
+
+            Ok(())
+        }
+
+        /// Resolves the report of `reporter` about a file. Dismissing it slashes the bond of the
+        /// reporter, while removing the file deletes it along with everything recorded about
+        /// it, giving every registrant its deposit back and every reporter its bond.
+        ///
+        /// The origin must satisfy [`Config::ModerationOrigin`].
+        #[pallet::call_index(26)]
+        #[pallet::weight(T::WeightInfo::resolve_report(
+            T::MaxRegistrants::get(),
+            T::MaxReportsPerFile::get(),
+        ))]
+        pub fn resolve_report(
+            origin: OriginFor<T>,
+            merkle_root: T::Hash,
+            reporter: T::AccountId,
+            verdict: Verdict,
+        ) -> DispatchResult {
+            T::ModerationOrigin::ensure_origin(origin)?;
+            Self::ensure_not_paused()?;
+            let mut reports = Reports::<T>::get(merkle_root);
+            let index = reports
+                .iter()
+                .position(|report| report.reporter == reporter)
+                .ok_or(Error::<T>::ReportNotFound)?;
+
+            match verdict {
+                Verdict::Dismiss => {
+                    if let Some(owner) = FileOwners::<T>::get(merkle_root) {
+                        Reputation::<T>::mutate(owner, |record| {
+                            record.reports_dismissed.saturating_inc()
+                        });
+                    }
+                    let report = reports.remove(index);
+                    let unslashed = Self::burn_held(HoldReason::ReportBond, &reporter, report.bond);
+                    if reports.is_empty() {
+                        Reports::<T>::remove(merkle_root);
+                    } else {
+                        Reports::<T>::insert(merkle_root, reports);
+                    }
+                    Self::deposit_event(Event::ReportDismissed {
+                        merkle_root,
+                        reporter,
+                        slashed: report.bond.saturating_sub(unslashed),
+                    });
+                }
+                Verdict::RemoveFile => {
+                    let (owner, tree) =
+                        Self::get_file(merkle_root).ok_or(Error::<T>::FileNotFound)?;
+                    let tombstone = Tombstone::new(
+                        &tree,
+                        <frame_system::Pallet<T>>::block_number(),
+                        reports[index].reason_hash,
+                    );
+                    Reputation::<T>::mutate(owner, |record| record.reports_upheld.saturating_inc());
+                    for (registrant, deposit) in Registrants::<T>::drain_prefix(merkle_root) {
+                        let payer = Sponsors::<T>::take(merkle_root, &registrant)
+                            .unwrap_or_else(|| registrant.clone());
+                        Self::note_deregistration(&registrant, &payer, tree.file_size, &deposit);
+                        Self::release_deposit(&payer, deposit)?;
+                    }
+                    Self::remove_file(merkle_root);
+                    Tombstones::<T>::insert(merkle_root, tombstone);
+                    Self::deposit_event(Event::FileTakenDown { merkle_root });
+                }
+            }
+
+            Ok(())
+        }
+
+        /// Schedules the deletion of the caller's registration of a file at block `when`, e.g.
+        /// to have it expire after 30 days. The deletion is dispatched by [`Config::Scheduler`]
+        /// as a `delete_file` call signed by the caller, so it fails if uploads are paused by
+        /// then. Deleting the registration beforehand cancels it.
+        #[pallet::call_index(27)]
+        #[pallet::weight(T::WeightInfo::schedule_deletion())]
+        pub fn schedule_deletion(
+            origin: OriginFor<T>,
+            merkle_root: T::Hash,
+            when: T::BlockNumber,
+        ) -> DispatchResult {
+            let who = ensure_signed(origin)?;
+            Self::ensure_not_paused()?;
+            ensure!(Registrants::<T>::contains_key(merkle_root, &who), Error::<T>::NotRegistrant);
+            ensure!(
+                !ScheduledDeletions::<T>::contains_key(merkle_root, &who),
+                Error::<T>::DeletionAlreadyScheduled
+            );
+            ensure!(
+                when > <frame_system::Pallet<T>>::block_number(),
+                Error::<T>::ScheduledInThePast
+            );
+
+            Self::schedule_deletion_task(merkle_root, &who, when)?;
+            ScheduledDeletions::<T>::insert(merkle_root, &who, when);
+
+            Self::deposit_event(Event::DeletionScheduled {
+                who,
+                merkle_root,
+                when,
+            });
+
+            Ok(())
+        }
+
+        /// Cancels the scheduled deletion of the caller's registration of a file.
+        #[pallet::call_index(28)]
+        #[pallet::weight(T::WeightInfo::cancel_deletion())]
+        pub fn cancel_deletion(origin: OriginFor<T>, merkle_root: T::Hash) -> DispatchResult {
+            let who = ensure_signed(origin)?;
+            Self::ensure_not_paused()?;
+            ensure!(
+                ScheduledDeletions::<T>::contains_key(merkle_root, &who),
+                Error::<T>::DeletionNotScheduled
+            );
+
+            Self::unschedule_deletion(merkle_root, &who);
+
+            Self::deposit_event(Event::DeletionCancelled { who, merkle_root });
+
+            Ok(())
+        }
+
+        /// Approves an upload bigger than [`Config::LargeFileThreshold`], registering the file
+        /// and leaving its chunks to be pushed to IPFS by the offchain worker.
+        ///
+        /// The origin must satisfy [`Config::LargeUploadOrigin`].
+        #[pallet::call_index(29)]
+        #[pallet::weight(T::WeightInfo::approve_large_upload())]
+        pub fn approve_large_upload(origin: OriginFor<T>, merkle_root: T::Hash) -> DispatchResult {
+            T::LargeUploadOrigin::ensure_origin(origin)?;
+            Self::ensure_not_paused()?;
+            let upload =
+                PendingUploads::<T>::take(merkle_root).ok_or(Error::<T>::UploadNotPending)?;
+
+            Self::admit_upload(merkle_root, upload)
+        }
+
+        /// Rejects an upload bigger than [`Config::LargeFileThreshold`], dropping its indexed
+        /// content and giving the deposit back to whoever paid it.
+        ///
+        /// The origin must satisfy [`Config::LargeUploadOrigin`].
+        #[pallet::call_index(30)]
+        #[pallet::weight(T::WeightInfo::reject_large_upload())]
+        pub fn reject_large_upload(origin: OriginFor<T>, merkle_root: T::Hash) -> DispatchResult {
+            T::LargeUploadOrigin::ensure_origin(origin)?;
+            Self::ensure_not_paused()?;
+            let upload =
+                PendingUploads::<T>::take(merkle_root).ok_or(Error::<T>::UploadNotPending)?;
+
+            let payer = upload.sponsor.as_ref().unwrap_or(&upload.who);
+            Self::release_deposit(payer, upload.deposit)?;
+            offchain_index::clear(&Self::derived_key(&merkle_root));
+
+            Self::deposit_event(Event::LargeUploadRejected {
+                who: upload.who,
+                merkle_root,
+            });
+
+            Ok(())
+        }
+
+        /// Sends the attestation of a registered file to the sibling parachain `para_id`, so
+        /// that it can trust that the file is registered here without having its own copy. Only
+        /// registrants of the file can send it.
+        ///
+        /// The attestation is delivered as an unpaid `Transact` of the `receive_attestation`
+        /// call of the attestations pallet, so the sibling has to allow unpaid execution from
+        /// this chain.
+        #[pallet::call_index(31)]
+        #[pallet::weight(T::WeightInfo::attest_file())]
+        pub fn attest_file(
+            origin: OriginFor<T>,
+            merkle_root: T::Hash,
+            para_id: u32,
+        ) -> DispatchResult {
+            let who = ensure_signed(origin)?;
+            Self::ensure_not_paused()?;
+            let owner = FileOwners::<T>::get(merkle_root).ok_or(Error::<T>::FileNotFound)?;
+            let layout = FileMeta::<T>::get(merkle_root).ok_or(Error::<T>::FileNotFound)?;
+            ensure!(Registrants::<T>::contains_key(merkle_root, &who), Error::<T>::NotRegistrant);
+
+            let attestation = FileAttestationOf::<T> {
+                merkle_root,
+                owner,
+                size: layout.size,
+                pieces: layout.pieces,
+            };
+            let call = (T::AttestationCallIndex::get(), attestation).encode();
+            let message = Xcm(vec![
+                UnpaidExecution {
+                    weight_limit: Unlimited,
+                    check_origin: None,
+                },
+                Transact {
+                    origin_kind: OriginKind::Xcm,
+                    require_weight_at_most: T::AttestationWeight::get(),
+                    call: call.into(),
+                },
+            ]);
+            let destination = MultiLocation::new(1, X1(Parachain(para_id)));
+            send_xcm::<T::XcmSender>(destination, message)
+                .map_err(|_| Error::<T>::AttestationNotSent)?;
+
+            Self::deposit_event(Event::FileAttested {
+                merkle_root,
+                para_id,
+            });
+
+            Ok(())
+        }
+
+        /// Records the attestation of a file sent by a trusted sibling parachain with
+        /// `attest_file`, making this chain an index of the content registered in its siblings.
+        /// The latest attestation of every file replaces the previous one.
+        ///
+        /// The origin must satisfy [`Config::SiblingOrigin`] and be the location of a parachain
+        /// in [`Config::TrustedSiblings`].
+        #[pallet::call_index(32)]
+        #[pallet::weight(T::WeightInfo::register_external_file())]
+        pub fn register_external_file(
+            origin: OriginFor<T>,
+            attestation: FileAttestationOf<T>,
+        ) -> DispatchResult {
+            let location = T::SiblingOrigin::ensure_origin(origin)?;
+            let MultiLocation {
+                parents: 1,
+                interior: X1(Parachain(para_id)),
+            } = location
+            else {
+                return Err(Error::<T>::NotSibling.into());
+            };
+            ensure!(T::TrustedSiblings::contains(&para_id), Error::<T>::UntrustedSibling);
+
+            let FileAttestation {
+                merkle_root,
+                owner,
+                size,
+                pieces,
+            } = attestation;
+            ExternalFiles::<T>::insert(
+                merkle_root,
+                para_id,
+                ExternalFile {
+                    owner: owner.clone(),
+                    size,
+                    pieces,
+                },
+            );
+
+            Self::deposit_event(Event::ExternalFileRegistered {
+                merkle_root,
+                para_id,
+                owner,
+            });
+
+            Ok(())
+        }
+
+        /// Adds content of a file served by the node of an authority through its RPC to
+        /// [`ServedBandwidth`], so that serving nodes can be rewarded for it. Every authority
+        /// reports at most once per block.
+        /// Submitted as an unsigned transaction by the offchain worker of one of the
+        /// [`OcwAuthorities`], signed with its key.
+        #[pallet::call_index(33)]
+        #[pallet::weight(T::WeightInfo::report_bandwidth())]
+        pub fn report_bandwidth(
+            origin: OriginFor<T>,
+            merkle_root: T::Hash,
+            bytes: u64,
+            chunks: u64,
+            proof: AuthorityProofOf<T>,
+        ) -> DispatchResult {
+            ensure_none(origin)?;
+            ensure!(
+                Self::is_authority_report(&(b"bandwidth", merkle_root, bytes, chunks), &proof),
+                Error::<T>::InvalidAuthorityProof
+            );
+            ensure!(FileOwners::<T>::contains_key(merkle_root), Error::<T>::FileNotFound);
+            ensure!(
+                Self::is_valid_bandwidth(&merkle_root, &proof, bytes, chunks),
+                Error::<T>::InvalidBandwidthReport
+            );
+
+            ServedBandwidth::<T>::mutate(merkle_root, &proof.authority, |record| {
+                record.bytes = record.bytes.saturating_add(bytes);
+                record.chunks = record.chunks.saturating_add(chunks);
+                record.reported_at = Some(proof.block);
+            });
+
+            Self::deposit_event(Event::BandwidthReported {
+                merkle_root,
+                authority: proof.authority,
+                bytes,
+                chunks,
+            });
+
+            Ok(())
+        }
+
+        /// Records whether a piece drawn for the current audit era could be fetched back from
+        /// IPFS. Submitted as an unsigned transaction by the offchain worker of one of the
+        /// [`OcwAuthorities`], signed with its key.
+        #[pallet::call_index(34)]
+        #[pallet::weight(T::WeightInfo::report_audit())]
+        pub fn report_audit(
+            origin: OriginFor<T>,
+            merkle_root: T::Hash,
+            position: u64,
+            passed: bool,
+            proof: AuthorityProofOf<T>,
+        ) -> DispatchResult {
+            ensure_none(origin)?;
+            ensure!(
+                Self::is_authority_report(&(b"audit", merkle_root, position, passed), &proof),
+                Error::<T>::InvalidAuthorityProof
+            );
+            ensure!(Self::is_valid_position(&merkle_root, position), Error::<T>::InvalidPosition);
+
+            AuditSample::<T>::try_mutate(|sample| -> DispatchResult {
+                let index = sample
+                    .iter()
+                    .position(|piece| piece == &(merkle_root, position))
+                    .ok_or(Error::<T>::PieceNotAudited)?;
+                sample.swap_remove(index);
+                Ok(())
+            })?;
+            let audit = PieceAudit {
+                block: <frame_system::Pallet<T>>::block_number(),
+                position,
+                passed,
+            };
+            PieceAudits::<T>::insert(merkle_root, audit);
+
+            Self::deposit_event(Event::PieceAudited {
+                merkle_root,
+                position,
+                passed,
+            });
+
+            Ok(())
+        }
+
+        /// Sets the authorities whose offchain workers push files to IPFS and submit status
+        /// transactions, so that the other nodes skip the work. Reports are only accepted signed
+        /// by the key of one of the authorities, so with an empty set no node does the work.
+        ///
+        /// The origin must satisfy [`Config::AdminOrigin`].
+        #[pallet::call_index(35)]
+        #[pallet::weight(T::WeightInfo::set_ocw_authorities(authorities.len() as u32))]
+        pub fn set_ocw_authorities(
+            origin: OriginFor<T>,
+            authorities: BoundedVec<T::AuthorityId, T::MaxOcwAuthorities>,
+        ) -> DispatchResult {
+            T::AdminOrigin::ensure_origin(origin)?;
+
+            let count = authorities.len() as u32;
+            OcwAuthorities::<T>::put(authorities);
+
+            Self::deposit_event(Event::OcwAuthoritiesSet { count });
+
+            Ok(())
+        }
+
+        /// Sets the price of every chunk of a file retrieved through a payment channel, or
+        /// clears it with `None`, so that channels charge their own price for it again. The
+        /// price is kept in the metadata of the file, which is created if there was none.
+        /// Only the owner of the file is allowed to do so.
+        #[pallet::call_index(36)]
+        #[pallet::weight(T::WeightInfo::set_retrieval_price())]
+        pub fn set_retrieval_price(
+            origin: OriginFor<T>,
+            merkle_root: T::Hash,
+            price: Option<BalanceOf<T>>,
+        ) -> DispatchResult {
+            let who = ensure_signed(origin)?;
+            Self::ensure_not_paused()?;
+
+            let owner = FileOwners::<T>::get(merkle_root).ok_or(Error::<T>::FileNotFound)?;
+            ensure!(owner == who, Error::<T>::NotFileOwner);
+
+            Metadata::<T>::mutate(merkle_root, |metadata| {
+                metadata
+                    .get_or_insert_with(Default::default)
+                    .retrieval_price = price;
+            });
+            Self::note_change(merkle_root, FileChange::MetadataUpdated { who: who.clone() });
+
+            Self::deposit_event(Event::RetrievalPriceSet {
+                who,
+                merkle_root,
+                price,
+            });
+
+            Ok(())
+        }
+
+        /// Creates an empty collection owned by the caller, holding a deposit for as many
+        /// files as [`Config::MaxCollectionSize`].
+        #[pallet::call_index(37)]
+        #[pallet::weight(T::WeightInfo::create_collection())]
+        pub fn create_collection(origin: OriginFor<T>) -> DispatchResult {
+            let who = ensure_signed(origin)?;
+            Self::ensure_not_paused()?;
+
+            let deposit = Self::deposit_for(CollectionOf::<T>::max_encoded_len() as u32);
+            Self::hold(HoldReason::CollectionDeposit, &who, deposit)?;
+            let collection_id = NextCollectionId::<T>::get();
+            NextCollectionId::<T>::put(collection_id.saturating_add(1));
+            Collections::<T>::insert(
+                collection_id,
+                Collection {
+                    owner: who.clone(),
+                    members: Default::default(),
+                    deposit,
+                },
+            );
+
+            Self::deposit_event(Event::CollectionCreated { who, collection_id });
+
+            Ok(())
+        }
+
+        /// Adds a file owned by the caller to one of its collections.
+        #[pallet::call_index(38)]
+        #[pallet::weight(T::WeightInfo::add_to_collection())]
+        pub fn add_to_collection(
+            origin: OriginFor<T>,
+            collection_id: CollectionId,
+            merkle_root: T::Hash,
+        ) -> DispatchResult {
+            let who = ensure_signed(origin)?;
+            Self::ensure_not_paused()?;
+
+            let mut collection = Self::owned_collection(collection_id, &who)?;
+            let owner = FileOwners::<T>::get(merkle_root).ok_or(Error::<T>::FileNotFound)?;
+            ensure!(owner == who, Error::<T>::NotFileOwner);
+            ensure!(!collection.members.contains(&merkle_root), Error::<T>::AlreadyInCollection);
+            collection
+                .members
+                .try_push(merkle_root)
+                .map_err(|_| Error::<T>::CollectionFull)?;
+            Collections::<T>::insert(collection_id, collection);
+
+            Self::deposit_event(Event::AddedToCollection {
+                collection_id,
+                merkle_root,
+            });
+
+            Ok(())
+        }
+
+        /// Removes a file from a collection of the caller. The file itself is left untouched.
+        #[pallet::call_index(39)]
+        #[pallet::weight(T::WeightInfo::remove_from_collection())]
+        pub fn remove_from_collection(
+            origin: OriginFor<T>,
+            collection_id: CollectionId,
+            merkle_root: T::Hash,
+        ) -> DispatchResult {
+            let who = ensure_signed(origin)?;
+            Self::ensure_not_paused()?;
+
+            let mut collection = Self::owned_collection(collection_id, &who)?;
+            let position = collection
+                .members
+                .iter()
+                .position(|member| *member == merkle_root)
+                .ok_or(Error::<T>::NotInCollection)?;
+            collection.members.remove(position);
+            Collections::<T>::insert(collection_id, collection);
+
+            Self::deposit_event(Event::RemovedFromCollection {
+                collection_id,
+                merkle_root,
+            });
+
+            Ok(())
+        }
+
+        /// Sets the retrieval price of every file of a collection of the caller, or clears it,
+        /// as `set_retrieval_price` does for a single file. Files the caller no longer owns,
+        /// e.g. because it dropped its registration, are skipped.
+        #[pallet::call_index(40)]
+        #[pallet::weight(T::WeightInfo::set_collection_price(T::MaxCollectionSize::get()))]
+        pub fn set_collection_price(
+            origin: OriginFor<T>,
+            collection_id: CollectionId,
+            price: Option<BalanceOf<T>>,
+        ) -> DispatchResult {
+            let who = ensure_signed(origin)?;
+            Self::ensure_not_paused()?;
+
+            let collection = Self::owned_collection(collection_id, &who)?;
+            let mut files = 0;
+            for merkle_root in Self::owned_members(&collection) {
+                Metadata::<T>::mutate(merkle_root, |metadata| {
+                    metadata
+                        .get_or_insert_with(Default::default)
+                        .retrieval_price = price;
+                });
+                Self::note_change(merkle_root, FileChange::MetadataUpdated { who: who.clone() });
+                files += 1;
+            }
+
+            Self::deposit_event(Event::CollectionPriced {
+                collection_id,
+                price,
+                files,
+            });
+
+            Ok(())
+        }
+
+        /// Hands a collection of the caller over to `to`, along with the ownership of every file
+        /// of it the caller owns, and the deposit of the collection. As owners are always
+        /// registrants, `to` must have registered all of those files beforehand, e.g. through
+        /// `upload_file`.
+        #[pallet::call_index(41)]
+        #[pallet::weight(T::WeightInfo::transfer_collection(T::MaxCollectionSize::get()))]
+        pub fn transfer_collection(
+            origin: OriginFor<T>,
+            collection_id: CollectionId,
+            to: T::AccountId,
+        ) -> DispatchResult {
+            let who = ensure_signed(origin)?;
+            Self::ensure_not_paused()?;
+
+            let mut collection = Self::owned_collection(collection_id, &who)?;
+            let files = Self::owned_members(&collection);
+            for merkle_root in &files {
+                ensure!(
+                    Registrants::<T>::contains_key(merkle_root, &to),
+                    Error::<T>::NotRegistrant
+                );
+            }
+            Self::transfer_held(HoldReason::CollectionDeposit, &who, &to, collection.deposit)?;
+            for merkle_root in &files {
+                FileOwners::<T>::insert(merkle_root, &to);
+                ManagementThresholds::<T>::remove(merkle_root);
+                let change = FileChange::OwnershipTransferred {
+                    from: who.clone(),
+                    to: to.clone(),
+                };
+                Self::note_change(*merkle_root, change);
+            }
+            collection.owner = to.clone();
+            Collections::<T>::insert(collection_id, collection);
+
+            Self::deposit_event(Event::CollectionTransferred {
+                collection_id,
+                from: who,
+                to,
+                files: files.len() as u32,
+            });
+
+            Ok(())
+        }
+
+        /// Removes a collection of the caller, giving its deposit back. Its files are left
+        /// untouched.
+        #[pallet::call_index(42)]
+        #[pallet::weight(T::WeightInfo::remove_collection())]
+        pub fn remove_collection(
+            origin: OriginFor<T>,
+            collection_id: CollectionId,
+        ) -> DispatchResult {
+            let who = ensure_signed(origin)?;
+            Self::ensure_not_paused()?;
+
+            let collection = Self::owned_collection(collection_id, &who)?;
+            Self::release(HoldReason::CollectionDeposit, &who, collection.deposit);
+            Collections::<T>::remove(collection_id);
+
+            Self::deposit_event(Event::CollectionRemoved { collection_id });
+
+            Ok(())
+        }
+
+        /// Approves an upload waiting for review, registering the file unless it still has to
+        /// be approved as a large upload.
+        ///
+        /// The origin must satisfy [`Config::ReviewOrigin`].
+        #[pallet::call_index(43)]
+        #[pallet::weight(T::WeightInfo::approve_review())]
+        pub fn approve_review(origin: OriginFor<T>, merkle_root: T::Hash) -> DispatchResult {
+            T::ReviewOrigin::ensure_origin(origin)?;
+            Self::ensure_not_paused()?;
+            let upload =
+                PendingReview::<T>::take(merkle_root).ok_or(Error::<T>::UploadNotUnderReview)?;
+
+            Self::screened_upload(merkle_root, upload)
+        }
+
+        /// Rejects an upload waiting for review, dropping its indexed content before it reaches
+        /// IPFS and giving the deposit back to whoever paid it.
+        ///
+        /// The origin must satisfy [`Config::ReviewOrigin`].
+        #[pallet::call_index(44)]
+        #[pallet::weight(T::WeightInfo::reject_review())]
+        pub fn reject_review(origin: OriginFor<T>, merkle_root: T::Hash) -> DispatchResult {
+            T::ReviewOrigin::ensure_origin(origin)?;
+            Self::ensure_not_paused()?;
+            let upload =
+                PendingReview::<T>::take(merkle_root).ok_or(Error::<T>::UploadNotUnderReview)?;
+
+            let payer = upload.sponsor.as_ref().unwrap_or(&upload.who);
+            Self::release_deposit(payer, upload.deposit)?;
+            offchain_index::clear(&Self::derived_key(&merkle_root));
+
+            Self::deposit_event(Event::UploadRejectedInReview {
+                who: upload.who,
+                merkle_root,
+            });
+
+            Ok(())
+        }
+
+        /// Stops the runtime API, and thus the RPC methods, from serving the proofs and content
+        /// of a file until `unfreeze_file` is called, e.g. during a takedown dispute, without
+        /// deleting it. Only the owner of the file is allowed to do so.
+        #[pallet::call_index(45)]
+        #[pallet::weight(T::WeightInfo::freeze_file())]
+        pub fn freeze_file(origin: OriginFor<T>, merkle_root: T::Hash) -> DispatchResult {
+            let who = ensure_signed(origin)?;
+            Self::ensure_not_paused()?;
+
+            let owner = FileOwners::<T>::get(merkle_root).ok_or(Error::<T>::FileNotFound)?;
+            ensure!(owner == who, Error::<T>::NotFileOwner);
+            ensure!(!FrozenFiles::<T>::contains_key(merkle_root), Error::<T>::AlreadyFrozen);
+
+            FrozenFiles::<T>::insert(merkle_root, ());
+
+            Self::deposit_event(Event::FileFrozen { who, merkle_root });
+
+            Ok(())
+        }
+
+        /// Lets the proofs and content of a frozen file be served again. Only the owner of the
+        /// file is allowed to do so.
+        #[pallet::call_index(46)]
+        #[pallet::weight(T::WeightInfo::unfreeze_file())]
+        pub fn unfreeze_file(origin: OriginFor<T>, merkle_root: T::Hash) -> DispatchResult {
+            let who = ensure_signed(origin)?;
+            Self::ensure_not_paused()?;
+
+            let owner = FileOwners::<T>::get(merkle_root).ok_or(Error::<T>::FileNotFound)?;
+            ensure!(owner == who, Error::<T>::NotFileOwner);
+            ensure!(FrozenFiles::<T>::take(merkle_root).is_some(), Error::<T>::NotFrozen);
+
+            Self::deposit_event(Event::FileUnfrozen { who, merkle_root });
+
+            Ok(())
+        }
+
+        /// Hands a file of the caller over to `to`, which can be any account, e.g. a multisig
+        /// one managing it through `pallet_multisig`, see [`Pallet::multisig_account_id`].
+        ///
+        /// Owners are always registrants: if `to` has not registered the file, the caller's
+        /// registration goes along with it, its deposit being given back to whoever paid it once
+        /// `to` leaves the file. The management threshold of the file is cleared.
+        #[pallet::call_index(47)]
+        #[pallet::weight(T::WeightInfo::transfer_file())]
+        pub fn transfer_file(
+            origin: OriginFor<T>,
+            merkle_root: T::Hash,
+            to: T::AccountId,
+        ) -> DispatchResult {
+            let who = ensure_signed(origin)?;
+            Self::ensure_not_paused()?;
+
+            let owner = FileOwners::<T>::get(merkle_root).ok_or(Error::<T>::FileNotFound)?;
+            ensure!(owner == who, Error::<T>::NotFileOwner);
+
+            if !Registrants::<T>::contains_key(merkle_root, &to) {
+                let layout = FileMeta::<T>::get(merkle_root).ok_or(Error::<T>::FileNotFound)?;
+                let deposit =
+                    Registrants::<T>::take(merkle_root, &who).ok_or(Error::<T>::NotRegistrant)?;
+                let payer = Sponsors::<T>::take(merkle_root, &who).unwrap_or_else(|| who.clone());
+                Self::unschedule_deletion(merkle_root, &who);
+                Self::note_deregistration(&who, &payer, layout.size, &deposit);
+                Self::note_registration(&to, &payer, layout.size, &deposit);
+                Registrants::<T>::insert(merkle_root, &to, deposit);
+                if payer != to {
+                    Sponsors::<T>::insert(merkle_root, &to, payer);
+                }
+            }
+            FileOwners::<T>::insert(merkle_root, &to);
+            ManagementThresholds::<T>::remove(merkle_root);
+            let change = FileChange::OwnershipTransferred {
+                from: who.clone(),
+                to: to.clone(),
+            };
+            Self::note_change(merkle_root, change);
+
+            Self::deposit_event(Event::FileTransferred {
+                from: who,
+                to,
+                merkle_root,
+            });
+
+            Ok(())
+        }
+
+        /// Records the number of approvals the owner of a file needs to manage it, or clears it
+        /// with `None`, so that user interfaces know how many signatories to gather when the
+        /// owner is a multisig account. Only the owner of the file is allowed to do so.
+        #[pallet::call_index(48)]
+        #[pallet::weight(T::WeightInfo::set_management_threshold())]
+        pub fn set_management_threshold(
+            origin: OriginFor<T>,
+            merkle_root: T::Hash,
+            threshold: Option<u16>,
+        ) -> DispatchResult {
+            let who = ensure_signed(origin)?;
+            Self::ensure_not_paused()?;
+
+            let owner = FileOwners::<T>::get(merkle_root).ok_or(Error::<T>::FileNotFound)?;
+            ensure!(owner == who, Error::<T>::NotFileOwner);
+            ensure!(threshold != Some(0), Error::<T>::ZeroThreshold);
+
+            ManagementThresholds::<T>::set(merkle_root, threshold);
+
+            Self::deposit_event(Event::ManagementThresholdSet {
+                who,
+                merkle_root,
+                threshold,
+            });
+
+            Ok(())
+        }
+
+        /// Keeps a name registered by the caller for [`Config::NameRegistrationPeriod`] blocks
+        /// from now, expired or not. The deposit is brought in line with the current one, holding
+        /// or giving back the difference.
+        #[pallet::call_index(49)]
+        #[pallet::weight(T::WeightInfo::renew_alias())]
+        pub fn renew_alias(origin: OriginFor<T>, name: NameOf<T>) -> DispatchResult {
+            let who = ensure_signed(origin)?;
+            Self::ensure_not_paused()?;
+
+            let mut alias = Names::<T>::get(&name).ok_or(Error::<T>::NameNotFound)?;
+            ensure!(alias.owner == who, Error::<T>::NotNameOwner);
+            let deposit = Self::name_deposit(name.len() as u32);
+            if deposit > alias.deposit {
+                Self::hold(HoldReason::AliasDeposit, &who, deposit.saturating_sub(alias.deposit))?;
             } else {
-                log::info!(
-                    "Offchain worker: nothing to process for offchain worker at block {:?}",
-                    block_number
+                Self::release(
+                    HoldReason::AliasDeposit,
+                    &who,
+                    alias.deposit.saturating_sub(deposit),
+                );
+            }
+            let now = frame_system::Pallet::<T>::block_number();
+            alias.deposit = deposit;
+            alias.expires_at = now.saturating_add(T::NameRegistrationPeriod::get());
+            let expires_at = alias.expires_at;
+            Names::<T>::insert(&name, alias);
+
+            Self::deposit_event(Event::AliasRenewed {
+                who,
+                name,
+                expires_at,
+            });
+
+            Ok(())
+        }
+
+        /// Takes a clearly abusive name, e.g. squatting on the brand of someone else, from its
+        /// owner, slashing its deposit, and gives it to `to`, still pointing to the same file.
+        /// The new owner holds no deposit until it renews the name, which it has to do within
+        /// [`Config::NameRegistrationPeriod`] blocks to keep it.
+        ///
+        /// The origin must satisfy [`Config::ModerationOrigin`].
+        #[pallet::call_index(50)]
+        #[pallet::weight(T::WeightInfo::reassign_alias())]
+        pub fn reassign_alias(
+            origin: OriginFor<T>,
+            name: NameOf<T>,
+            to: T::AccountId,
+        ) -> DispatchResult {
+            T::ModerationOrigin::ensure_origin(origin)?;
+            Self::ensure_not_paused()?;
+
+            let alias = Names::<T>::get(&name).ok_or(Error::<T>::NameNotFound)?;
+            let unslashed = Self::burn_held(HoldReason::AliasDeposit, &alias.owner, alias.deposit);
+            let now = frame_system::Pallet::<T>::block_number();
+            Names::<T>::insert(
+                &name,
+                Alias {
+                    owner: to.clone(),
+                    merkle_root: alias.merkle_root,
+                    deposit: Zero::zero(),
+                    expires_at: now.saturating_add(T::NameRegistrationPeriod::get()),
+                },
+            );
+
+            Self::deposit_event(Event::AliasReassigned {
+                name,
+                from: alias.owner,
+                to,
+                slashed: alias.deposit.saturating_sub(unslashed),
+            });
+
+            Ok(())
+        }
+
+        /// Withdraws an upload made with `upload_file_with_root` that the offchain worker has
+        /// not checked yet, releasing its deposit, e.g. because it is waiting for storage to be
+        /// freed.
+        /// Only the account that claimed the merkle root is allowed to do so.
+        #[pallet::call_index(51)]
+        #[pallet::weight(T::WeightInfo::cancel_root_claim())]
+        pub fn cancel_root_claim(origin: OriginFor<T>, merkle_root: T::Hash) -> DispatchResult {
+            let who = ensure_signed(origin)?;
+            Self::ensure_not_paused()?;
+
+            let claim = RootClaims::<T>::get(merkle_root).ok_or(Error::<T>::RootNotClaimed)?;
+            ensure!(claim.who == who, Error::<T>::NotClaimant);
+            RootClaims::<T>::remove(merkle_root);
+            Self::release(HoldReason::FileDeposit, &who, claim.deposit);
+            offchain_index::clear(&Self::derived_key(&merkle_root));
+
+            Self::deposit_event(Event::RootClaimCancelled { who, merkle_root });
+
+            Ok(())
+        }
+
+        /// Drops a request to rebuild the tree of a file that cannot be rebuilt, e.g. because
+        /// its content is not indexed or the new tree would not replace the current one.
+        /// Submitted as an unsigned transaction by the offchain worker of one of the
+        /// [`OcwAuthorities`], signed with its key.
+        #[pallet::call_index(52)]
+        #[pallet::weight(T::WeightInfo::reject_rechunk())]
+        pub fn reject_rechunk(
+            origin: OriginFor<T>,
+            merkle_root: T::Hash,
+            proof: AuthorityProofOf<T>,
+        ) -> DispatchResult {
+            ensure_none(origin)?;
+            ensure!(
+                Self::is_authority_report(&(b"reject_rechunk", merkle_root), &proof),
+                Error::<T>::InvalidAuthorityProof
+            );
+            ensure!(
+                PendingRechunks::<T>::take(merkle_root).is_some(),
+                Error::<T>::RechunkNotRequested
+            );
+
+            Self::deposit_event(Event::RechunkRejected { merkle_root });
+
+            Ok(())
+        }
+    }
+
+    #[pallet::validate_unsigned]
+    impl<T: Config> ValidateUnsigned for Pallet<T> {
+        type Call = Call<T>;
+
+        fn validate_unsigned(_source: TransactionSource, call: &Self::Call) -> TransactionValidity {
+            match call {
+                Call::report_upload_progress {
+                    merkle_root,
+                    done,
+                    total,
+                    proof,
+                } => {
+                    if !Self::is_authority_report(&(b"progress", merkle_root, done, total), proof) {
+                        return InvalidTransaction::BadProof.into();
+                    }
+                    if !Self::is_valid_progress(merkle_root, *done, *total) {
+                        return InvalidTransaction::Stale.into();
+                    }
+                    ValidTransaction::with_tag_prefix("TrustlessFileServer")
+                        .priority(T::UnsignedPriority::get())
+                        .and_provides((b"progress", merkle_root, done))
+                        .longevity(5)
+                        .propagate(true)
+                        .build()
+                }
+                Call::report_chunk_allocations {
+                    merkle_root,
+                    position,
+                    allocations,
+                    proof,
+                } => {
+                    let report = (b"allocations", merkle_root, position, allocations);
+                    if !Self::is_authority_report(&report, proof) {
+                        return InvalidTransaction::BadProof.into();
+                    }
+                    if !Self::is_valid_position(merkle_root, *position) {
+                        return InvalidTransaction::Stale.into();
+                    }
+                    ValidTransaction::with_tag_prefix("TrustlessFileServer")
+                        .priority(T::UnsignedPriority::get())
+                        .and_provides((b"allocations", merkle_root, position))
+                        .longevity(5)
+                        .propagate(true)
+                        .build()
+                }
+                Call::report_probe {
+                    merkle_root,
+                    position,
+                    available,
+                    proof,
+                } => {
+                    if !Self::is_authority_report(
+                        &(b"probe", merkle_root, position, available),
+                        proof,
+                    ) {
+                        return InvalidTransaction::BadProof.into();
+                    }
+                    if !Self::is_valid_position(merkle_root, *position) {
+                        return InvalidTransaction::Stale.into();
+                    }
+                    ValidTransaction::with_tag_prefix("TrustlessFileServer")
+                        .priority(T::UnsignedPriority::get())
+                        .and_provides((b"probe", merkle_root, position))
+                        .longevity(5)
+                        .propagate(true)
+                        .build()
+                }
+                Call::report_audit {
+                    merkle_root,
+                    position,
+                    passed,
+                    proof,
+                } => {
+                    if !Self::is_authority_report(&(b"audit", merkle_root, position, passed), proof)
+                    {
+                        return InvalidTransaction::BadProof.into();
+                    }
+                    if !Self::is_valid_position(merkle_root, *position)
+                        || !AuditSample::<T>::get().contains(&(*merkle_root, *position))
+                    {
+                        return InvalidTransaction::Stale.into();
+                    }
+                    ValidTransaction::with_tag_prefix("TrustlessFileServer")
+                        .priority(T::UnsignedPriority::get())
+                        .and_provides((b"audit", merkle_root, position))
+                        .longevity(5)
+                        .propagate(true)
+                        .build()
+                }
+                Call::report_rechunk {
+                    merkle_root,
+                    merkle_tree,
+                    proof,
+                } => {
+                    if !Self::is_authority_report(&(b"rechunk", merkle_root, merkle_tree), proof) {
+                        return InvalidTransaction::BadProof.into();
+                    }
+                    let valid = PendingRechunks::<T>::contains_key(merkle_root)
+                        && matches!(
+                            FileTrees::<T>::get(merkle_root),
+                            Some(old_tree) if Self::is_valid_rechunk(&old_tree, merkle_tree)
+                        );
+                    if !valid {
+                        return InvalidTransaction::Stale.into();
+                    }
+                    ValidTransaction::with_tag_prefix("TrustlessFileServer")
+                        .priority(T::UnsignedPriority::get())
+                        .and_provides((b"rechunk", merkle_root))
+                        .longevity(5)
+                        .propagate(true)
+                        .build()
+                }
+                Call::reject_rechunk { merkle_root, proof } => {
+                    if !Self::is_authority_report(&(b"reject_rechunk", merkle_root), proof) {
+                        return InvalidTransaction::BadProof.into();
+                    }
+                    if !PendingRechunks::<T>::contains_key(merkle_root) {
+                        return InvalidTransaction::Stale.into();
+                    }
+                    // either rebuilt or rejected, never both
+                    ValidTransaction::with_tag_prefix("TrustlessFileServer")
+                        .priority(T::UnsignedPriority::get())
+                        .and_provides((b"rechunk", merkle_root))
+                        .longevity(5)
+                        .propagate(true)
+                        .build()
+                }
+                Call::report_repin { merkle_root, proof } => {
+                    if !Self::is_authority_report(&(b"repin", merkle_root), proof) {
+                        return InvalidTransaction::BadProof.into();
+                    }
+                    if !FileOwners::<T>::contains_key(merkle_root)
+                        || !Self::is_repin_due(merkle_root)
+                    {
+                        return InvalidTransaction::Stale.into();
+                    }
+                    ValidTransaction::with_tag_prefix("TrustlessFileServer")
+                        .priority(T::UnsignedPriority::get())
+                        .and_provides((b"repin", merkle_root))
+                        .longevity(5)
+                        .propagate(true)
+                        .build()
+                }
+                Call::report_bandwidth {
+                    merkle_root,
+                    bytes,
+                    chunks,
+                    proof,
+                } => {
+                    if !Self::is_authority_report(
+                        &(b"bandwidth", merkle_root, bytes, chunks),
+                        proof,
+                    ) {
+                        return InvalidTransaction::BadProof.into();
+                    }
+                    if !Self::is_valid_bandwidth(merkle_root, proof, *bytes, *chunks) {
+                        return InvalidTransaction::Stale.into();
+                    }
+                    // a single report of every file per authority and block
+                    ValidTransaction::with_tag_prefix("TrustlessFileServer")
+                        .priority(T::UnsignedPriority::get())
+                        .and_provides((b"bandwidth", merkle_root, &proof.authority, proof.block))
+                        .longevity(5)
+                        .propagate(true)
+                        .build()
+                }
+                Call::upload_file_unsigned { file_bytes, nonce } => {
+                    let Some(difficulty) = T::UnsignedUploadDifficulty::get() else {
+                        return InvalidTransaction::Call.into();
+                    };
+                    // checked before hashing the whole file, which is what makes spam expensive
+                    if Paused::<T>::get() {
+                        return InvalidTransaction::Call.into();
+                    }
+                    if !Self::has_capacity(file_bytes.len() as u64) {
+                        return InvalidTransaction::ExhaustsResources.into();
+                    }
+                    let Ok((_, merkle_root)) = Self::build_tree(file_bytes) else {
+                        return InvalidTransaction::Call.into();
+                    };
+                    // anonymous uploads never add registrants to a file stored or on its way
+                    if FileOwners::<T>::contains_key(merkle_root)
+                        || RootClaims::<T>::contains_key(merkle_root)
+                        || PendingUploads::<T>::contains_key(merkle_root)
+                        || PendingReview::<T>::contains_key(merkle_root)
+                    {
+                        return InvalidTransaction::Stale.into();
+                    }
+                    if Self::upload_work(&merkle_root, *nonce) < difficulty {
+                        return InvalidTransaction::BadProof.into();
+                    }
+                    ValidTransaction::with_tag_prefix("TrustlessFileServer")
+                        .priority(T::UnsignedPriority::get())
+                        .and_provides((b"upload", merkle_root))
+                        .longevity(5)
+                        .propagate(true)
+                        .build()
+                }
+                Call::confirm_root {
+                    merkle_root,
+                    merkle_tree,
+                    sha256,
+                    proof,
+                } => {
+                    let report = (b"confirm", merkle_root, merkle_tree, sha256);
+                    if !Self::is_authority_report(&report, proof) {
+                        return InvalidTransaction::BadProof.into();
+                    }
+                    // claims over the storage budget wait for room to be made
+                    let valid = matches!(
+                        RootClaims::<T>::get(merkle_root),
+                        Some(claim) if Self::is_valid_claim(merkle_root, &claim, merkle_tree)
+                            && Self::has_capacity(claim.size)
+                    );
+                    if !valid {
+                        return InvalidTransaction::Stale.into();
+                    }
+                    ValidTransaction::with_tag_prefix("TrustlessFileServer")
+                        .priority(T::UnsignedPriority::get())
+                        .and_provides((b"root", merkle_root))
+                        .longevity(5)
+                        .propagate(true)
+                        .build()
+                }
+                Call::reject_root { merkle_root, proof } => {
+                    if !Self::is_authority_report(&(b"reject", merkle_root), proof) {
+                        return InvalidTransaction::BadProof.into();
+                    }
+                    if !RootClaims::<T>::contains_key(merkle_root) {
+                        return InvalidTransaction::Stale.into();
+                    }
+                    // either confirmed or rejected, never both
+                    ValidTransaction::with_tag_prefix("TrustlessFileServer")
+                        .priority(T::UnsignedPriority::get())
+                        .and_provides((b"root", merkle_root))
+                        .longevity(5)
+                        .propagate(true)
+                        .build()
+                }
+                _ => InvalidTransaction::Call.into(),
+            }
+        }
+    }
+
+    impl<T: Config> Event<T> {
+        /// Topics the event is indexed by, so that clients can look up the events about a given
+        /// file or account with `System::event_topics`: the merkle roots the event refers to,
+        /// followed by the hashes of the accounts involved, hashed with the runtime hasher.
+        pub fn topics(&self) -> Vec<T::Hash> {
+            let account = |who: &T::AccountId| T::Hashing::hash_of(who);
+            match self {
+                Event::FileUploaded {
+                    who, merkle_root, ..
+                }
+                | Event::MetadataUpdated { who, merkle_root }
+                | Event::RetrievalPriceSet {
+                    who, merkle_root, ..
+                }
+                | Event::RechunkRequested { who, merkle_root }
+                | Event::RegistrantAdded {
+                    who, merkle_root, ..
+                }
+                | Event::RegistrantRemoved {
+                    who, merkle_root, ..
+                }
+                | Event::AliasRegistered {
+                    who, merkle_root, ..
+                }
+                | Event::KeyShareSubmitted {
+                    holder: who,
+                    merkle_root,
+                }
+                | Event::RootClaimed {
+                    who, merkle_root, ..
+                }
+                | Event::RootRejected {
+                    who, merkle_root, ..
+                }
+                | Event::RootClaimCancelled { who, merkle_root }
+                | Event::FileReported {
+                    who, merkle_root, ..
+                }
+                | Event::ReportDismissed {
+                    reporter: who,
+                    merkle_root,
+                    ..
+                }
+                | Event::DeletionScheduled {
+                    who, merkle_root, ..
+                }
+                | Event::DeletionCancelled { who, merkle_root }
+                | Event::LargeUploadPending {
+                    who, merkle_root, ..
+                }
+                | Event::LargeUploadRejected { who, merkle_root }
+                | Event::UploadPendingReview {
+                    who, merkle_root, ..
+                }
+                | Event::UploadRejectedInReview { who, merkle_root }
+                | Event::FileFrozen { who, merkle_root }
+                | Event::FileUnfrozen { who, merkle_root }
+                | Event::ManagementThresholdSet {
+                    who, merkle_root, ..
+                } => {
+                    vec![*merkle_root, account(who)]
+                }
+                Event::ChunksUploaded { merkle_root, .. }
+                | Event::ChunkAllocated { merkle_root, .. }
+                | Event::ProbeReported { merkle_root, .. }
+                | Event::PieceAudited { merkle_root, .. }
+                | Event::FileRepinned { merkle_root }
+                | Event::RechunkRejected { merkle_root }
+                | Event::BandwidthReported { merkle_root, .. }
+                | Event::FileDeleted { merkle_root }
+                | Event::BundleRemoved {
+                    bundle_root: merkle_root,
+                }
+                | Event::KeyCommitted { merkle_root, .. }
+                | Event::KeyRevealed { merkle_root, .. }
+                | Event::KeyEscrowed { merkle_root, .. }
+                | Event::KeyReconstructionFailed { merkle_root }
+                | Event::FileAttested { merkle_root, .. }
+                | Event::ExternalFileRegistered { merkle_root, .. }
+                | Event::FileTakenDown { merkle_root }
+                | Event::AddedToCollection { merkle_root, .. }
+                | Event::RemovedFromCollection { merkle_root, .. } => vec![*merkle_root],
+                Event::FileRechunked {
+                    old_root, new_root, ..
+                } => vec![*old_root, *new_root],
+                Event::AliasReleased { who, .. }
+                | Event::AliasRenewed { who, .. }
+                | Event::CollectionCreated { who, .. } => vec![account(who)],
+                Event::AliasReassigned { from, to, .. } => vec![account(from), account(to)],
+                Event::CollectionTransferred { from, to, .. } => vec![account(from), account(to)],
+                Event::FileTransferred {
+                    from,
+                    to,
+                    merkle_root,
+                } => vec![*merkle_root, account(from), account(to)],
+                Event::BundleCreated {
+                    who, bundle_root, ..
+                } => vec![*bundle_root, account(who)],
+                Event::UploadSponsored {
+                    sponsor,
+                    beneficiary,
+                    merkle_root,
+                } => vec![*merkle_root, account(sponsor), account(beneficiary)],
+                _ => Vec::new(),
+            }
+        }
+    }
+
+    impl<T: Config> Pallet<T> {
+        /// Deposits `event` indexed by its [`Event::topics`].
+        pub(super) fn deposit_event(event: Event<T>) {
+            let topics = event.topics();
+            let event: <T as Config>::RuntimeEvent = event.into();
+            frame_system::Pallet::<T>::deposit_event_indexed(&topics, event.into());
+        }
+    }
+
+    // RPC methods
+    impl<T: Config> Pallet<T> {
+        pub(crate) fn chunking_params() -> ChunkingParams {
+            ChunkingParams {
+                min_chunk_size: T::MinChunkSize::get(),
+                max_chunk_size: T::MaxChunkSize::get(),
+                target_pieces: T::TargetPieces::get(),
+                bep52: T::Bep52Compatible::get(),
+                ipfs_chunk_size: T::IpfsChunkSize::get(),
+            }
+        }
+
+        pub(crate) fn derived_key(merkle_root: &T::Hash) -> Vec<u8> {
+            merkle_root.using_encoded(|encoded_root| {
+                ONCHAIN_TX_KEY
+                    .iter()
+                    .chain(b"/".iter())
+                    .chain(encoded_root)
+                    .copied()
+                    .collect::<Vec<u8>>()
+            })
+        }
+
+        /// Whether `new_tree` is a well formed tree of the same file as `old_tree` under the
+        /// current chunking parameters. Leaves cannot be checked against the content, which is
+        /// only available offchain.
+        pub(crate) fn is_valid_rechunk(
+            old_tree: &FileMerkleTree,
+            new_tree: &FileMerkleTree,
+        ) -> bool {
+            new_tree.file_size == old_tree.file_size && new_tree.is_valid(&Self::chunking_params())
+        }
+
+        /// Whether `merkle_tree` is a well formed tree under the current chunking parameters of a
+        /// file of the claimed size, whose merkle root is the claimed one.
+        fn is_valid_claim(
+            merkle_root: &T::Hash,
+            claim: &RootClaimOf<T>,
+            merkle_tree: &FileMerkleTree,
+        ) -> bool {
+            merkle_tree.file_size == claim.size
+                && merkle_tree.merkle_root() == merkle_root.as_ref()
+                && merkle_tree.is_valid(&Self::chunking_params())
+        }
+
+        /// Whether a new file of `size` bytes fits in [`Config::MaxTotalBytes`].
+        fn has_capacity(size: u64) -> bool {
+            TotalBytesStored::<T>::get().saturating_add(size) <= T::MaxTotalBytes::get()
+        }
+
+        fn ensure_capacity(size: u64) -> DispatchResult {
+            ensure!(Self::has_capacity(size), Error::<T>::GlobalCapacityExceeded);
+            Ok(())
+        }
+
+        /// Weight of removing in `on_finalize` the [`AccountUploads`] entry an upload can write,
+        /// charged along with the upload as hooks returning no weight cannot account for it.
+        pub(crate) fn account_uploads_cleanup() -> Weight {
+            T::DbWeight::get().writes(1)
+        }
+
+        /// Counts one more upload submitted by `who` in the current block, failing if it already
+        /// submitted [`Config::MaxUploadsPerAccountPerBlock`].
+        fn note_account_upload(who: &T::AccountId) -> DispatchResult {
+            AccountUploads::<T>::try_mutate(who, |uploads| {
+                ensure!(
+                    *uploads < T::MaxUploadsPerAccountPerBlock::get(),
+                    Error::<T>::TooManyUploadsFromAccount
                 );
+                uploads.saturating_inc();
+                Ok(())
+            })
+        }
+
+        /// Fails with `Paused` while the admin origin has the pallet halted. Checked by every
+        /// call but those exempted in `pause`.
+        fn ensure_not_paused() -> DispatchResult {
+            ensure!(!Paused::<T>::get(), Error::<T>::Paused);
+            Ok(())
+        }
+
+        /// Names must be printable UTF-8 with no whitespace, so that they can be safely shown and
+        /// typed by users.
+        fn is_valid_name(name: &[u8]) -> bool {
+            match core::str::from_utf8(name) {
+                Ok(name) => {
+                    !name.is_empty() && !name.chars().any(|c| c.is_whitespace() || c.is_control())
+                }
+                Err(_) => false,
             }
         }
-    }
 
-    #[pallet::call]
-    impl<T: Config> Pallet<T> {
-        /// Uploads a file to the blockchain and computes its merkle tree.
-        /// Bear in mind that as a general rule of thumb blockchains should not store big amounts of
-        /// data, and instead decentralized services like IPFS should be used, storing only the
-        /// associated hash on the blockchain.
-        #[pallet::call_index(0)]
-        #[pallet::weight(T::WeightInfo::upload_file(file_bytes.len() as u32))]
-        pub fn upload_file(origin: OriginFor<T>, file_bytes: Vec<u8>) -> DispatchResult {
-            // Check that the extrinsic was signed and get the signer.
-            // This function will return an error if the extrinsic is not signed.
-            let who = ensure_signed(origin)?;
+        /// Checks that `file_bytes` can be registered, without hashing it.
+        fn check_file(file_bytes: &[u8]) -> DispatchResult {
+            ensure!(file_bytes.len() <= T::MaxFileSize::get() as usize, Error::<T>::FileTooLarge);
+            ensure!(!file_bytes.is_empty(), Error::<T>::EmptyFile);
+            let params = Self::chunking_params();
+            ensure!(
+                file_merkle_tree::pieces_for(file_bytes.len() as u64, &params)
+                    <= file_merkle_tree::MAX_PIECES,
+                Error::<T>::TooManyPieces
+            );
+            ensure!(
+                T::ContentPolicy::contains(&ContentType::sniff(file_bytes)),
+                Error::<T>::ContentTypeForbidden
+            );
+            Ok(())
+        }
 
-            let file_merkle_tree = FileMerkleTree::new(&file_bytes);
+        /// Checks that `file_bytes` can be registered, building its merkle tree.
+        pub(crate) fn build_tree(
+            file_bytes: &[u8],
+        ) -> Result<(FileMerkleTree, T::Hash), DispatchError> {
+            Self::check_file(file_bytes)?;
+            let file_merkle_tree = FileMerkleTree::new(file_bytes, &Self::chunking_params());
             let merkle_root = T::Hash::decode(&mut file_merkle_tree.merkle_root())
                 .or(Err(Error::<T>::Unhasheable))?;
+            Ok((file_merkle_tree, merkle_root))
+        }
+
+        /// Registers `file_bytes` on behalf of `who`, taking the deposit from `payer`. Files that
+        /// are already stored just get `who` as one more registrant.
+        ///
+        /// Files with no `payer` are registered for free, as the work attached to anonymous
+        /// uploads already pays for them. Those are never added to already stored files.
+        fn do_upload(
+            payer: Option<&T::AccountId>,
+            who: T::AccountId,
+            file_bytes: Vec<u8>,
+            deposit_asset: Option<AssetIdOf<T>>,
+            license: Option<LicenseOf<T>>,
+        ) -> Result<T::Hash, DispatchError> {
+            Self::ensure_not_paused()?;
+            if let Some(payer) = payer {
+                Self::note_account_upload(payer)?;
+            }
+            ensure!(license.as_ref().map_or(true, License::is_valid), Error::<T>::InvalidLicense);
+            let tree = Self::build_tree(&file_bytes)?;
+            Self::register_upload(payer, who, file_bytes, tree, deposit_asset, license)
+        }
 
-            // Leave the offchain work
-            let block_number = <frame_system::Pallet<T>>::block_number();
-            let key = Self::derived_key(block_number);
-            log::info!("Inserting storage for block {:?}", block_number);
+        /// Registers `file_bytes`, whose tree and merkle root [`Self::build_tree`] has already
+        /// built, as [`Self::do_upload`] does once its checks passed.
+        fn register_upload(
+            payer: Option<&T::AccountId>,
+            who: T::AccountId,
+            file_bytes: Vec<u8>,
+            (file_merkle_tree, merkle_root): (FileMerkleTree, T::Hash),
+            deposit_asset: Option<AssetIdOf<T>>,
+            license: Option<LicenseOf<T>>,
+        ) -> Result<T::Hash, DispatchError> {
+            ensure!(!RootClaims::<T>::contains_key(merkle_root), Error::<T>::RootAlreadyClaimed);
+            ensure!(
+                !PendingUploads::<T>::contains_key(merkle_root),
+                Error::<T>::UploadPendingApproval
+            );
+            ensure!(!PendingReview::<T>::contains_key(merkle_root), Error::<T>::UploadUnderReview);
+            if FileOwners::<T>::contains_key(merkle_root) {
+                let payer = payer.ok_or(Error::<T>::AlreadyRegistered)?;
+                Self::add_registrant(
+                    payer,
+                    who,
+                    merkle_root,
+                    file_bytes.len() as u32,
+                    deposit_asset,
+                )?;
+                return Ok(merkle_root);
+            }
+            Self::ensure_capacity(file_bytes.len() as u64)?;
+
+            let deposit = match payer {
+                Some(payer) => Self::take_deposit(payer, file_bytes.len() as u32, deposit_asset)?,
+                None => Deposit::Native(Zero::zero()),
+            };
+
+            let sha256 = sp_io::hashing::sha2_256(&file_bytes);
+            let key = Self::derived_key(&merkle_root);
+            sp_tracing::info!(
+                target: UPLOAD_TARGET,
+                ?merkle_root,
+                pieces = file_merkle_tree.pieces(),
+                size = file_bytes.len(),
+                "Inserting storage for file"
+            );
             let data = IndexingData {
                 content: file_bytes,
                 chunk_size: file_merkle_tree.chunk_size() as u32,
             };
             offchain_index::set(&key, &data.encode());
 
-            // Update the storage
-            Files::<T>::insert(merkle_root, (&who, &file_merkle_tree));
+            let upload = PendingUpload {
+                sponsor: payer.filter(|payer| **payer != who).cloned(),
+                who,
+                deposit,
+                merkle_tree: file_merkle_tree,
+                sha256,
+                license,
+            };
+            Self::submit_upload(merkle_root, upload)?;
+
+            Ok(merkle_root)
+        }
+
+        /// Holds an indexed upload until reviewed if the runtime requires it, or hands it over to
+        /// [`Self::screened_upload`] otherwise.
+        fn submit_upload(merkle_root: T::Hash, upload: PendingUploadOf<T>) -> DispatchResult {
+            if T::RequireReview::get() {
+                Self::deposit_event(Event::UploadPendingReview {
+                    who: upload.who.clone(),
+                    merkle_root,
+                    size: upload.merkle_tree.file_size,
+                });
+                PendingReview::<T>::insert(merkle_root, upload);
+                return Ok(());
+            }
+            Self::screened_upload(merkle_root, upload)
+        }
+
+        /// Holds an upload whose content passed the review, if any, until approved if it is
+        /// bigger than the large file threshold, or registers it right away otherwise.
+        fn screened_upload(merkle_root: T::Hash, upload: PendingUploadOf<T>) -> DispatchResult {
+            if upload.merkle_tree.file_size > T::LargeFileThreshold::get().into() {
+                Self::deposit_event(Event::LargeUploadPending {
+                    who: upload.who.clone(),
+                    merkle_root,
+                    size: upload.merkle_tree.file_size,
+                });
+                PendingUploads::<T>::insert(merkle_root, upload);
+                return Ok(());
+            }
+            Self::admit_upload(merkle_root, upload)
+        }
+
+        /// Registers a new file whose content is already indexed, leaving the offchain work.
+        fn admit_upload(merkle_root: T::Hash, upload: PendingUploadOf<T>) -> DispatchResult {
+            // other files may have been registered since it was submitted
+            Self::ensure_capacity(upload.merkle_tree.file_size)?;
+            BlockUploads::<T>::try_append(merkle_root)
+                .map_err(|_| Error::<T>::TooManyUploadsInBlock)?;
+
+            let tree = upload.merkle_tree;
+            let payer = upload.sponsor.as_ref().unwrap_or(&upload.who);
+            Self::note_registration(&upload.who, payer, tree.file_size, &upload.deposit);
+            Self::insert_file(&upload.who, merkle_root, &tree, upload.deposit, upload.sha256);
+            if let Some(sponsor) = upload.sponsor {
+                Sponsors::<T>::insert(merkle_root, &upload.who, sponsor);
+            }
+            if upload.license.is_some() {
+                let metadata = FileMetadataOf::<T> {
+                    license: upload.license,
+                    ..Default::default()
+                };
+                Metadata::<T>::insert(merkle_root, metadata);
+            }
 
-            // Emit the event
             Self::deposit_event(Event::FileUploaded {
+                who: upload.who,
+                merkle_root,
+                pieces: tree.pieces(),
+                size: tree.file_size,
+                chunk_size: tree.chunk_size() as u32,
+                sha256: upload.sha256,
+            });
+
+            Ok(())
+        }
+
+        /// Records a new file owned by `who`, its only registrant so far.
+        fn insert_file(
+            who: &T::AccountId,
+            merkle_root: T::Hash,
+            merkle_tree: &FileMerkleTree,
+            deposit: DepositOf<T>,
+            sha256: [u8; 32],
+        ) {
+            Self::put_file(merkle_root, who, merkle_tree);
+            TotalBytesStored::<T>::mutate(|total| total.saturating_accrue(merkle_tree.file_size));
+            Registrants::<T>::insert(merkle_root, who, deposit);
+            RegistrantCount::<T>::insert(merkle_root, 1);
+            Checksums::<T>::insert(merkle_root, sha256);
+            UploadedAt::<T>::insert(
+                merkle_root,
+                UploadTime {
+                    block: frame_system::Pallet::<T>::block_number(),
+                    timestamp: T::UnixTime::now().as_millis().saturated_into(),
+                },
+            );
+            UploadProgress::<T>::remove(merkle_root);
+            UploadedPieces::<T>::remove(merkle_root);
+            Reputation::<T>::mutate(who, |record| record.uploads.saturating_inc());
+        }
+
+        /// Records the owner, merkle tree and layout of the file registered under `merkle_root`.
+        pub(crate) fn put_file(
+            merkle_root: T::Hash,
+            owner: &T::AccountId,
+            merkle_tree: &FileMerkleTree,
+        ) {
+            FileOwners::<T>::insert(merkle_root, owner);
+            FileTrees::<T>::insert(merkle_root, merkle_tree);
+            FileMeta::<T>::insert(merkle_root, Self::file_layout(merkle_tree));
+        }
+
+        /// Layout of the file of `tree`, along with the CID `ipfs add` gives the file if the
+        /// runtime splits files as `ipfs add` does, i.e. the one the offchain worker publishes.
+        pub(crate) fn file_layout(tree: &FileMerkleTree) -> FileLayout {
+            let layout = FileLayout::from(tree);
+            if T::IpfsChunkSize::get() != Some(layout.chunk_size) {
+                return layout;
+            }
+            // unlike its leaf, the hash the tree keeps of an incomplete last chunk is unpadded
+            let chunks: Option<Vec<_>> = (0..u64::from(layout.pieces))
+                .map(|position| {
+                    let offset = position * u64::from(layout.chunk_size);
+                    let size = (layout.size - offset).min(layout.chunk_size.into());
+                    tree.file_chunk_hash_at(position).map(|hash| (hash, size))
+                })
+                .collect();
+            FileLayout {
+                cid: chunks.map(|chunks| ipfs::unixfs_file_cid_bytes(&chunks)),
+                ..layout
+            }
+        }
+
+        /// Forgets the owner, merkle tree and layout of the file registered under `merkle_root`,
+        /// returning the layout if it was registered.
+        pub(crate) fn take_file(merkle_root: T::Hash) -> Option<FileLayout> {
+            FileOwners::<T>::remove(merkle_root);
+            FileTrees::<T>::remove(merkle_root);
+            FileMeta::<T>::take(merkle_root)
+        }
+
+        /// Adds `who` as one more registrant of an already stored file, taking the deposit from
+        /// `payer`.
+        fn add_registrant(
+            payer: &T::AccountId,
+            who: T::AccountId,
+            merkle_root: T::Hash,
+            size: u32,
+            deposit_asset: Option<AssetIdOf<T>>,
+        ) -> DispatchResult {
+            ensure!(
+                !Registrants::<T>::contains_key(merkle_root, &who),
+                Error::<T>::AlreadyRegistered
+            );
+            let registrants = RegistrantCount::<T>::get(merkle_root).saturating_add(1);
+            ensure!(registrants <= T::MaxRegistrants::get(), Error::<T>::TooManyRegistrants);
+
+            let deposit = Self::take_deposit(payer, size, deposit_asset)?;
+            Self::note_registration(&who, payer, size.into(), &deposit);
+            Registrants::<T>::insert(merkle_root, &who, deposit);
+            if *payer != who {
+                Sponsors::<T>::insert(merkle_root, &who, payer);
+            }
+            RegistrantCount::<T>::insert(merkle_root, registrants);
+
+            Self::deposit_event(Event::RegistrantAdded {
                 who,
                 merkle_root,
-                pieces: file_merkle_tree.pieces(),
-                size: file_merkle_tree.file_size as u32,
+                registrants,
             });
 
             Ok(())
         }
-    }
 
-    // RPC methods
-    impl<T: Config> Pallet<T> {
-        fn derived_key(block_number: T::BlockNumber) -> Vec<u8> {
-            block_number.using_encoded(|encoded_bn| {
-                ONCHAIN_TX_KEY
-                    .iter()
-                    .chain(b"/".iter())
-                    .chain(encoded_bn)
-                    .copied()
-                    .collect::<Vec<u8>>()
-            })
+        /// Accounts for `who` registering a file of `size` bytes, whose deposit was paid by
+        /// `payer`.
+        pub(crate) fn note_registration(
+            who: &T::AccountId,
+            payer: &T::AccountId,
+            size: u64,
+            deposit: &DepositOf<T>,
+        ) {
+            Self::update_usage(who, |usage| {
+                usage.files.saturating_inc();
+                usage.bytes.saturating_accrue(size);
+            });
+            if let Deposit::Native(amount) = deposit {
+                Self::update_usage(payer, |usage| usage.deposit_locked.saturating_accrue(*amount));
+            }
+        }
+
+        /// Reverts [`Self::note_registration`] once `who` leaves a file.
+        fn note_deregistration(
+            who: &T::AccountId,
+            payer: &T::AccountId,
+            size: u64,
+            deposit: &DepositOf<T>,
+        ) {
+            Self::update_usage(who, |usage| {
+                usage.files.saturating_dec();
+                usage.bytes.saturating_reduce(size);
+            });
+            if let Deposit::Native(amount) = deposit {
+                Self::update_usage(payer, |usage| usage.deposit_locked.saturating_reduce(*amount));
+            }
+        }
+
+        /// Applies `f` to the usage of `who`, dropping the entry once nothing is left.
+        fn update_usage(who: &T::AccountId, f: impl FnOnce(&mut UsageRecordOf<T>)) {
+            AccountUsage::<T>::mutate_exists(who, |maybe_usage| {
+                let mut usage = maybe_usage.take().unwrap_or_default();
+                f(&mut usage);
+                *maybe_usage = (usage != Default::default()).then_some(usage);
+            });
+        }
+
+        /// Appends `change` to the history of a file, dropping the oldest entry if it is full.
+        fn note_change(merkle_root: T::Hash, change: FileChangeOf<T>) {
+            let entry = HistoryEntry {
+                block: frame_system::Pallet::<T>::block_number(),
+                change,
+            };
+            FileHistory::<T>::mutate(merkle_root, |history| {
+                if history.is_full() && !history.is_empty() {
+                    history.remove(0);
+                }
+                let _ = history.try_push(entry);
+            });
+        }
+
+        /// Removes a file with no registrants left, along with its offchain content. Its chunk
+        /// allocations, one entry per piece, are left for [`Self::reclaim_chunks`].
+        fn remove_file(merkle_root: T::Hash) {
+            if let Some(layout) = Self::take_file(merkle_root) {
+                TotalBytesStored::<T>::mutate(|total| total.saturating_reduce(layout.size));
+            }
+            RegistrantCount::<T>::remove(merkle_root);
+            Metadata::<T>::remove(merkle_root);
+            Checksums::<T>::remove(merkle_root);
+            UploadedAt::<T>::remove(merkle_root);
+            FrozenFiles::<T>::remove(merkle_root);
+            ManagementThresholds::<T>::remove(merkle_root);
+            UploadProgress::<T>::remove(merkle_root);
+            UploadedPieces::<T>::remove(merkle_root);
+            OrphanedChunks::<T>::insert(merkle_root, ());
+            ProbeResults::<T>::remove(merkle_root);
+            PieceAudits::<T>::remove(merkle_root);
+            LastPinnedAt::<T>::remove(merkle_root);
+            let _ =
+                ServedBandwidth::<T>::clear_prefix(merkle_root, T::MaxOcwAuthorities::get(), None);
+            PendingRechunks::<T>::remove(merkle_root);
+            TimeLocks::<T>::remove(merkle_root);
+            KeyEscrows::<T>::remove(merkle_root);
+            // nothing left to resolve
+            for report in Reports::<T>::take(merkle_root) {
+                Self::release(HoldReason::ReportBond, &report.reporter, report.bond);
+            }
+            for (registrant, _) in ScheduledDeletions::<T>::drain_prefix(merkle_root) {
+                let _ = T::Scheduler::cancel_named(Self::deletion_task(merkle_root, &registrant));
+            }
+            FileHistory::<T>::remove(merkle_root);
+            offchain_index::clear(&Self::derived_key(&merkle_root));
+        }
+
+        fn bundle_leaves(members: &[T::Hash]) -> Option<Vec<ProofNode>> {
+            members
+                .iter()
+                .map(|member| ProofNode::try_from(member.as_ref()).ok())
+                .collect()
+        }
+
+        /// Merkle root over the merkle roots of `members`.
+        fn bundle_root(members: &[T::Hash]) -> Option<T::Hash> {
+            let root = file_merkle_tree::merkle_root_of(&Self::bundle_leaves(members)?)?;
+            T::Hash::decode(&mut root.as_slice()).ok()
+        }
+
+        /// Follows the aliases left behind by rebuilt trees until a registered merkle root is
+        /// found.
+        fn resolve_root(merkle_root: T::Hash) -> T::Hash {
+            let mut current = merkle_root;
+            for _ in 0..MAX_ALIAS_HOPS {
+                if FileOwners::<T>::contains_key(current) {
+                    break;
+                }
+                match RootAliases::<T>::get(current) {
+                    Some(next) => current = next,
+                    None => break,
+                }
+            }
+            current
+        }
+
+        /// The collection under `collection_id`, as long as it is owned by `who`.
+        fn owned_collection(
+            collection_id: CollectionId,
+            who: &T::AccountId,
+        ) -> Result<CollectionOf<T>, DispatchError> {
+            let collection =
+                Collections::<T>::get(collection_id).ok_or(Error::<T>::CollectionNotFound)?;
+            ensure!(collection.owner == *who, Error::<T>::NotCollectionOwner);
+            Ok(collection)
+        }
+
+        /// Current merkle roots of the files of `collection` still owned by its owner, following
+        /// rechunked files.
+        fn owned_members(collection: &CollectionOf<T>) -> Vec<T::Hash> {
+            collection
+                .members
+                .iter()
+                .map(|member| Self::resolve_root(*member))
+                .filter(|member| FileOwners::<T>::get(member).as_ref() == Some(&collection.owner))
+                .collect()
+        }
+
+        /// Drops the chunk allocations of removed files for as long as `limit` allows, carrying
+        /// on with the rest in later blocks. Files uploaded again in the meantime keep theirs, as
+        /// the same merkle root stands for the same chunks.
+        pub(crate) fn reclaim_chunks(limit: Weight) -> Weight {
+            let base = T::WeightInfo::reclaim_chunks(0);
+            let per_chunk = T::WeightInfo::reclaim_chunks(1).saturating_sub(base);
+            let mut used = Weight::zero();
+            let (mut count, mut bytes) = (0u32, 0u64);
+            while limit.all_gte(used.saturating_add(base)) {
+                used.saturating_accrue(base);
+                let Some(merkle_root) = OrphanedChunks::<T>::iter_keys().next() else {
+                    break;
+                };
+                if FileOwners::<T>::contains_key(merkle_root) {
+                    OrphanedChunks::<T>::remove(merkle_root);
+                    continue;
+                }
+
+                let left = limit.saturating_sub(used);
+                let fitting = left
+                    .ref_time()
+                    .checked_div(per_chunk.ref_time())
+                    .unwrap_or(u64::MAX)
+                    .min(
+                        left.proof_size()
+                            .checked_div(per_chunk.proof_size())
+                            .unwrap_or(u64::MAX),
+                    );
+                let mut drained = 0u64;
+                for (position, allocations) in
+                    ChunkAllocations::<T>::drain_prefix(merkle_root).take(fitting.saturated_into())
+                {
+                    let key = ChunkAllocations::<T>::hashed_key_for(merkle_root, position);
+                    bytes.saturating_accrue((key.len() + allocations.encoded_size()) as u64);
+                    drained.saturating_inc();
+                }
+                count.saturating_accrue(drained.saturated_into());
+                used.saturating_accrue(per_chunk.saturating_mul(drained));
+                if drained == fitting {
+                    // out of weight, unless it happened to be the last chunk
+                    break;
+                }
+                OrphanedChunks::<T>::remove(merkle_root);
+            }
+            if count > 0 {
+                Self::deposit_event(Event::ChunksReclaimed { count, bytes });
+            }
+            used
+        }
+
+        /// Draws [`Config::AuditSampleSize`] pieces of files at random for the offchain worker
+        /// to audit in the era starting at `now`, dropping whatever was left of the previous one.
+        fn draw_audit_sample(now: T::BlockNumber) -> Weight {
+            let draws = T::AuditSampleSize::get();
+            let mut sample = BoundedVec::<_, T::AuditSampleSize>::default();
+            for draw in 0..draws {
+                let (seed, _) = T::Randomness::random(&(AUDIT_SUBJECT, now, draw).encode());
+                let Some((merkle_root, layout)) = Self::file_after(seed.as_ref()) else {
+                    break;
+                };
+                // the leading bytes of the seed picked the file already
+                let random = u32::decode(&mut &sp_io::hashing::blake2_256(seed.as_ref())[..])
+                    .unwrap_or_default();
+                let piece = (merkle_root, u64::from(random % layout.pieces.max(1)));
+                if !sample.contains(&piece) {
+                    let _ = sample.try_push(piece);
+                }
+            }
+            AuditSample::<T>::put(sample);
+            T::DbWeight::get().reads_writes(2 * u64::from(draws), 1)
+        }
+
+        /// First file whose raw key in [`FileMeta`] comes after `start`, wrapping around to the
+        /// first file. Keys are hashed, so a random `start` picks a random file.
+        fn file_after(start: &[u8]) -> Option<(T::Hash, FileLayout)> {
+            let start = [&FileMeta::<T>::final_prefix()[..], start].concat();
+            FileMeta::<T>::iter_from(start)
+                .next()
+                .or_else(|| FileMeta::<T>::iter().next())
+        }
+
+        fn is_valid_position(merkle_root: &T::Hash, position: u64) -> bool {
+            matches!(Self::file_pieces(*merkle_root), Some(pieces) if position < pieces.into())
+        }
+
+        /// Files are pinned again at most twice per [`Config::RepinInterval`], leaving some slack
+        /// for reports included a few blocks late.
+        fn is_repin_due(merkle_root: &T::Hash) -> bool {
+            match LastPinnedAt::<T>::get(merkle_root) {
+                Some(last) => {
+                    let now = <frame_system::Pallet<T>>::block_number();
+                    last.saturating_add(T::RepinInterval::get() / 2u32.into()) <= now && last < now
+                }
+                None => true,
+            }
+        }
+
+        /// Progress reports must refer to a registered file, match its number of pieces and
+        /// move forward.
+        fn is_valid_progress(merkle_root: &T::Hash, done: u32, total: u32) -> bool {
+            match Self::file_pieces(*merkle_root) {
+                Some(pieces) => {
+                    pieces == total && done <= total && done > UploadProgress::<T>::get(merkle_root)
+                }
+                None => false,
+            }
+        }
+
+        /// Message the key of an offchain worker authority signs to prove it made `report` at
+        /// `block`.
+        pub(crate) fn report_message(
+            report: &impl Encode,
+            authority: &T::AuthorityId,
+            block: T::BlockNumber,
+        ) -> Vec<u8> {
+            (report, authority, block).encode()
+        }
+
+        /// Whether `proof` shows that `report` was made by one of the [`OcwAuthorities`] in the
+        /// last [`REPORT_LONGEVITY`] blocks.
+        pub(crate) fn is_authority_report(
+            report: &impl Encode,
+            proof: &AuthorityProofOf<T>,
+        ) -> bool {
+            let now = <frame_system::Pallet<T>>::block_number();
+            let message = Self::report_message(report, &proof.authority, proof.block);
+            proof.block <= now
+                && now - proof.block <= REPORT_LONGEVITY.into()
+                && OcwAuthorities::<T>::get().contains(&proof.authority)
+                && proof.authority.verify(&message, &proof.signature)
+        }
+
+        /// Whether `chunks` of the file could have been served, amounting to `bytes`, and the
+        /// authority has not reported it since the block of the proof.
+        fn is_valid_bandwidth(
+            merkle_root: &T::Hash,
+            proof: &AuthorityProofOf<T>,
+            bytes: u64,
+            chunks: u64,
+        ) -> bool {
+            let reported_at = ServedBandwidth::<T>::get(merkle_root, &proof.authority).reported_at;
+            match FileMeta::<T>::get(merkle_root) {
+                Some(layout) => {
+                    chunks > 0
+                        && bytes <= chunks.saturating_mul(layout.chunk_size.into())
+                        && reported_at < Some(proof.block)
+                }
+                None => false,
+            }
         }
 
         /// Gets from the storage all file hashes ever submitted.
         pub fn get_files() -> Vec<(Vec<u8>, u32)> {
-            Files::<T>::iter()
-                .map(|(_, (_, tree))| (tree.merkle_root().to_vec(), tree.pieces()))
+            FileMeta::<T>::iter()
+                .map(|(key, layout)| (key.encode(), layout.pieces))
                 .collect::<Vec<(Vec<u8>, u32)>>()
         }
 
+        /// Lists the registered files along with their owner, size and license.
+        pub fn list_files() -> Vec<FileSummary<T::AccountId>> {
+            FileOwners::<T>::iter()
+                .filter_map(|(key, owner)| Self::file_summary(key, owner))
+                .collect()
+        }
+
+        /// Lists up to `limit` registered files, and never more than [`MAX_FILES_PER_PAGE`], in
+        /// the same order as [`Self::list_files`]. Listing starts right after the merkle root
+        /// `start`, i.e. the last one of the previous page, or from the beginning if `None`.
+        pub fn get_files_paged(
+            start: Option<Vec<u8>>,
+            limit: u32,
+        ) -> Vec<FileSummary<T::AccountId>> {
+            Self::files_after(start)
+                .into_iter()
+                .flatten()
+                .take(limit.min(MAX_FILES_PER_PAGE) as usize)
+                .filter_map(|(key, owner)| Self::file_summary(key, owner))
+                .collect()
+        }
+
+        /// Exports up to `limit` registered files, and never more than [`MAX_FILES_PER_EXPORT`],
+        /// along with their merkle trees, for archival services to mirror the registry. Files
+        /// come in the same deterministic order as [`Self::get_files_paged`], starting right
+        /// after the merkle root `start`, or from the beginning if `None`.
+        pub fn export_state(start: Option<Vec<u8>>, limit: u32) -> Vec<FileRecord<T::AccountId>> {
+            Self::files_after(start)
+                .into_iter()
+                .flatten()
+                .take(limit.min(MAX_FILES_PER_EXPORT) as usize)
+                .filter_map(|(key, owner)| {
+                    let summary = Self::file_summary(key, owner)?;
+                    let tree = if Self::file_frozen(key) {
+                        None
+                    } else {
+                        Some(FileTrees::<T>::get(key)?.nodes())
+                    };
+                    Some(FileRecord { summary, tree })
+                })
+                .collect()
+        }
+
+        /// Registered files along with their owners, right after the merkle root `start`, or
+        /// from the beginning if `None`. `None` if `start` is not a merkle root.
+        fn files_after(
+            start: Option<Vec<u8>>,
+        ) -> Option<impl Iterator<Item = (T::Hash, T::AccountId)>> {
+            Some(match start {
+                None => FileOwners::<T>::iter(),
+                Some(start) => {
+                    let start = T::Hash::decode(&mut start.as_slice()).ok()?;
+                    FileOwners::<T>::iter_from(FileOwners::<T>::hashed_key_for(start))
+                }
+            })
+        }
+
+        fn file_summary(key: T::Hash, owner: T::AccountId) -> Option<FileSummary<T::AccountId>> {
+            let layout = FileMeta::<T>::get(key)?;
+            Some(FileSummary {
+                merkle_root: key.encode(),
+                owner,
+                size: layout.size,
+                pieces: layout.pieces,
+                license: Metadata::<T>::get(key)
+                    .and_then(|metadata| metadata.license)
+                    .map(|license| license.map(BoundedVec::into_inner)),
+                uploaded_at: UploadedAt::<T>::get(key).map(|time| UploadTime {
+                    block: time.block.saturated_into(),
+                    timestamp: time.timestamp,
+                }),
+                cid: layout.cid.map(|cid| ipfs::cid_to_string(&cid).into_bytes()),
+            })
+        }
+
+        /// Gets the details of a single file, including its metadata if any was set. Merkle roots
+        /// of rebuilt trees are resolved to the file they now belong to, whose current merkle
+        /// root is the one returned.
+        pub fn get_file_info(merkle_root: Vec<u8>) -> Option<FileInfo<T::AccountId>> {
+            let key = T::Hash::decode(&mut merkle_root.as_slice()).ok()?;
+            let key = Self::resolve_root(key);
+            let owner = FileOwners::<T>::get(key)?;
+            let layout = FileMeta::<T>::get(key)?;
+            let metadata = Metadata::<T>::get(key).unwrap_or_default();
+            Some(FileInfo {
+                owner,
+                merkle_root: key.encode(),
+                size: layout.size,
+                pieces: layout.pieces,
+                chunk_size: layout.chunk_size,
+                bep52: layout.bep52,
+                sha256: Checksums::<T>::get(key),
+                registrants: RegistrantCount::<T>::get(key),
+                name: metadata.name.into_inner(),
+                mime_type: metadata.mime_type.into_inner(),
+                tags: metadata
+                    .tags
+                    .into_iter()
+                    .map(|tag| tag.into_inner())
+                    .collect(),
+                license: metadata
+                    .license
+                    .map(|license| license.map(BoundedVec::into_inner)),
+                retrieval_price: metadata
+                    .retrieval_price
+                    .map(SaturatedConversion::saturated_into),
+                cid: layout.cid.map(|cid| ipfs::cid_to_string(&cid).into_bytes()),
+            })
+        }
+
+        /// Returns the current merkle root of the file registered under `name`, if it is still
+        /// around.
+        pub fn resolve_alias(name: Vec<u8>) -> Option<Vec<u8>> {
+            let name = NameOf::<T>::try_from(name).ok()?;
+            let alias = Names::<T>::get(name)?;
+            if alias.expires_at <= frame_system::Pallet::<T>::block_number() {
+                return None;
+            }
+            let merkle_root = Self::resolve_root(alias.merkle_root);
+            FileOwners::<T>::contains_key(merkle_root).then(|| merkle_root.encode())
+        }
+
+        /// Returns which pieces of a file have been confirmed to be on IPFS.
+        pub fn get_upload_status(merkle_root: Vec<u8>) -> Option<UploadStatus> {
+            let key = T::Hash::decode(&mut merkle_root.as_slice()).ok()?;
+            let pieces = Self::file_pieces(key)?;
+            Some(UploadStatus {
+                pieces,
+                uploaded: UploadedPieces::<T>::get(key),
+            })
+        }
+
+        /// Whether a file is registered under `merkle_root`, without decoding its merkle tree.
+        pub fn exists(merkle_root: Vec<u8>) -> bool {
+            T::Hash::decode(&mut merkle_root.as_slice())
+                .map(|key| Self::file_exists(Self::resolve_root(key)))
+                .unwrap_or(false)
+        }
+
+        /// Whether the owner of the file registered under `merkle_root` has frozen it.
+        pub fn is_frozen(merkle_root: Vec<u8>) -> bool {
+            T::Hash::decode(&mut merkle_root.as_slice())
+                .map(|key| Self::file_frozen(Self::resolve_root(key)))
+                .unwrap_or(false)
+        }
+
+        /// Owner of the file registered under `merkle_root`, without decoding its merkle tree.
+        pub fn owner(merkle_root: Vec<u8>) -> Option<T::AccountId> {
+            let key = T::Hash::decode(&mut merkle_root.as_slice()).ok()?;
+            FileOwners::<T>::get(Self::resolve_root(key))
+        }
+
+        /// What is kept of the file registered under `merkle_root` if it was taken down.
+        pub fn get_tombstone(merkle_root: Vec<u8>) -> Option<Tombstone<u32>> {
+            let key = T::Hash::decode(&mut merkle_root.as_slice()).ok()?;
+            let tombstone = Tombstones::<T>::get(key)?;
+            Some(Tombstone {
+                removed_at: tombstone.removed_at.saturated_into(),
+                reason_hash: tombstone.reason_hash,
+                size: tombstone.size,
+                chunk_size: tombstone.chunk_size,
+                piece_hashes: tombstone.piece_hashes,
+            })
+        }
+
+        /// Files and bytes `who` is a registrant of, and the native deposits it has locked for
+        /// registrations.
+        pub fn get_account_usage(who: T::AccountId) -> UsageRecord<u128> {
+            let usage = AccountUsage::<T>::get(who);
+            UsageRecord {
+                files: usage.files,
+                bytes: usage.bytes,
+                deposit_locked: usage.deposit_locked.saturated_into(),
+            }
+        }
+
+        /// Latest ownership transfers and metadata updates of a file, oldest first.
+        pub fn get_file_history(merkle_root: Vec<u8>) -> Vec<HistoryEntry<T::AccountId, u32>> {
+            let Ok(key) = T::Hash::decode(&mut merkle_root.as_slice()) else {
+                return Vec::new();
+            };
+            FileHistory::<T>::get(key)
+                .into_iter()
+                .map(|entry| HistoryEntry {
+                    block: entry.block.saturated_into(),
+                    change: entry.change,
+                })
+                .collect()
+        }
+
+        /// Sibling parachains that attested a file to be registered there under `merkle_root`,
+        /// along with what they attested.
+        pub fn get_external_files(merkle_root: Vec<u8>) -> Vec<(u32, ExternalFileOf<T>)> {
+            let Ok(key) = T::Hash::decode(&mut merkle_root.as_slice()) else {
+                return Vec::new();
+            };
+            ExternalFiles::<T>::iter_prefix(key).collect()
+        }
+
+        /// Checks the merkle tree stored under `merkle_root` for corruption, rebuilding its
+        /// inner nodes out of its leaves. Aliases of rebuilt trees are not followed, as the
+        /// entry itself is audited.
+        pub fn audit_file(merkle_root: Vec<u8>) -> Option<AuditReport> {
+            let key = T::Hash::decode(&mut merkle_root.as_slice()).ok()?;
+            let tree = FileTrees::<T>::get(key)?;
+            let pieces = tree.pieces();
+            Some(match tree.audit() {
+                Some((corrupted_nodes, root)) => AuditReport {
+                    pieces,
+                    well_formed: true,
+                    corrupted_nodes,
+                    root_matches: root.as_slice() == key.as_ref(),
+                },
+                None => AuditReport {
+                    pieces,
+                    well_formed: false,
+                    corrupted_nodes: Vec::new(),
+                    root_matches: false,
+                },
+            })
+        }
+
         /// Given a file's merkle root hash, gets the merkle proof of a given  chunk, identified
         /// by its position.
         /// Returns a tuple where the first element is the IPFS hash, and the second is
@@ -211,15 +4458,242 @@ pub mod pallet {
         /// The idea is that the client can (and should) use the content to compute the sha256 hash,
         /// and with it hash along with the rest of the proofs until the merkle root is finally computed.
         /// This way it gets proven that the content is authentic in a trustless manner.
-        pub fn get_proof(merkle_root: Vec<u8>, position: u32) -> Option<(String, Vec<Vec<u8>>)> {
+        /// Files frozen by their owner have no proofs served.
+        pub fn get_proof(merkle_root: Vec<u8>, position: u64) -> Option<(String, BoundedProof)> {
             let key = T::Hash::decode(&mut merkle_root.as_slice())
                 .map_err(|_| None::<T>)
                 .ok()?;
-            let (_, merkle_tree) = Files::<T>::get(key)?;
+            if Self::file_frozen(key) {
+                return None;
+            }
+            let merkle_tree = FileTrees::<T>::get(key)?;
             let proof = merkle_tree.merkle_proof(position)?;
             let chunk_hash = merkle_tree.file_chunk_hash_at(position)?;
             let chunk_ipfs_hash = ipfs::ipfs_get_hash_from_sha256(&chunk_hash);
             Some((chunk_ipfs_hash, proof))
         }
+
+        /// Same as `get_proof`, along with whether the piece has been confirmed to be on IPFS.
+        pub fn get_piece_proof(merkle_root: Vec<u8>, position: u64) -> Option<PieceProof> {
+            let (ipfs_hash, proof) = Self::get_proof(merkle_root.clone(), position)?;
+            let uploaded = Self::get_upload_status(merkle_root)
+                .map_or(false, |status| status.is_uploaded(position));
+            Some(PieceProof {
+                ipfs_hash,
+                proof,
+                uploaded,
+            })
+        }
+
+        /// Every piece of a file, by position, along with what is needed to fetch it from IPFS or
+        /// the node and to check it once fetched, so that download managers can fetch pieces in
+        /// parallel and verify them as they arrive. `None` if the file is not registered or is
+        /// frozen by its owner.
+        pub fn get_download_plan(merkle_root: Vec<u8>) -> Option<Vec<PlannedChunk>> {
+            let key = T::Hash::decode(&mut merkle_root.as_slice()).ok()?;
+            if Self::file_frozen(key) {
+                return None;
+            }
+            let merkle_tree = FileTrees::<T>::get(key)?;
+            let status = Self::get_upload_status(merkle_root);
+            (0..merkle_tree.pieces() as u64)
+                .map(|position| {
+                    let chunk_hash = merkle_tree.file_chunk_hash_at(position)?;
+                    let proof = merkle_tree.merkle_proof(position)?;
+                    Some(PlannedChunk {
+                        position,
+                        ipfs_hash: ipfs::ipfs_get_hash_from_sha256(&chunk_hash),
+                        chunk_hash,
+                        proof_len: proof.len() as u32,
+                        uploaded: status
+                            .as_ref()
+                            .map_or(false, |status| status.is_uploaded(position)),
+                    })
+                })
+                .collect()
+        }
+
+        pub fn get_merkle_tree(merkle_root: Vec<u8>) -> Option<Vec<Vec<u8>>> {
+            let key = T::Hash::decode(&mut merkle_root.as_slice()).ok()?;
+            if Self::file_frozen(key) {
+                return None;
+            }
+            let merkle_tree = FileTrees::<T>::get(key)?;
+            Some(merkle_tree.nodes())
+        }
+
+        /// Name of the scheduler task deleting the registration of `who` of a file.
+        pub(crate) fn deletion_task(
+            merkle_root: T::Hash,
+            who: &T::AccountId,
+        ) -> schedule::TaskName {
+            (b"tfs/delete", merkle_root, who).using_encoded(sp_io::hashing::blake2_256)
+        }
+
+        /// Has [`Config::Scheduler`] dispatch a `delete_file` call signed by `who` at `when`.
+        fn schedule_deletion_task(
+            merkle_root: T::Hash,
+            who: &T::AccountId,
+            when: T::BlockNumber,
+        ) -> DispatchResult {
+            let call = <T as Config>::RuntimeCall::from(Call::<T>::delete_file { merkle_root });
+            let call = T::Preimages::bound(call.into())?;
+            T::Scheduler::schedule_named(
+                Self::deletion_task(merkle_root, who),
+                DispatchTime::At(when),
+                None,
+                schedule::LOWEST_PRIORITY,
+                frame_system::RawOrigin::Signed(who.clone()).into(),
+                call,
+            )
+            .map_err(|_| Error::<T>::SchedulingFailed)?;
+            Ok(())
+        }
+
+        /// Forgets the scheduled deletion of the registration of `who`, if any, cancelling its
+        /// task. Cancelling the task being dispatched is a no-op, hence the ignored result.
+        fn unschedule_deletion(merkle_root: T::Hash, who: &T::AccountId) {
+            if ScheduledDeletions::<T>::take(merkle_root, who).is_some() {
+                let _ = T::Scheduler::cancel_named(Self::deletion_task(merkle_root, who));
+            }
+        }
+
+        /// Records whether `who` answered a storage deal challenge in time in its reputation.
+        pub fn note_challenge(who: &T::AccountId, passed: bool) {
+            Reputation::<T>::mutate(who, |record| {
+                if passed {
+                    record.challenges_passed.saturating_inc()
+                } else {
+                    record.challenges_failed.saturating_inc()
+                }
+            });
+        }
+
+        /// Whether a file is registered under `merkle_root`.
+        pub fn file_exists(merkle_root: T::Hash) -> bool {
+            FileOwners::<T>::contains_key(merkle_root)
+        }
+
+        /// Whether the owner of the file under `merkle_root` has stopped its proofs and content
+        /// from being served.
+        pub fn file_frozen(merkle_root: T::Hash) -> bool {
+            FrozenFiles::<T>::contains_key(merkle_root)
+        }
+
+        /// Account of the multisig of `signatories` requiring `threshold` approvals, derived as
+        /// `pallet_multisig` does, so that files can be transferred to it before it is ever used.
+        /// `signatories` must be sorted.
+        pub fn multisig_account_id(signatories: &[T::AccountId], threshold: u16) -> T::AccountId {
+            let entropy = (b"modlpy/utilisuba", signatories, threshold)
+                .using_encoded(sp_io::hashing::blake2_256);
+            Decode::decode(&mut TrailingZeroInput::new(entropy.as_ref()))
+                .expect("infinite length input; no invalid inputs for type; qed")
+        }
+
+        /// Hash `pallet_multisig` identifies `call` by, i.e. the blake2_256 of the SCALE encoded
+        /// runtime call, for the signatories of a multisig owner to approve it with
+        /// `approve_as_multi` before the last one submits it along with `as_multi`.
+        pub fn management_call_hash(call: Call<T>) -> [u8; 32] {
+            <T as Config>::RuntimeCall::from(call).using_encoded(sp_io::hashing::blake2_256)
+        }
+
+        /// Owner and merkle tree of the file registered under `merkle_root`, if any. Prefer
+        /// [`FileOwners`] or [`FileMeta`] when the tree itself is not needed.
+        pub fn get_file(merkle_root: T::Hash) -> Option<(T::AccountId, FileMerkleTree)> {
+            Some((FileOwners::<T>::get(merkle_root)?, FileTrees::<T>::get(merkle_root)?))
+        }
+
+        /// Number of pieces of the file registered under `merkle_root`, if any.
+        pub fn file_pieces(merkle_root: T::Hash) -> Option<u32> {
+            FileMeta::<T>::get(merkle_root).map(|layout| layout.pieces)
+        }
+
+        /// Owner and current merkle roots of the files of a collection.
+        pub fn collection_info(
+            collection_id: CollectionId,
+        ) -> Option<CollectionInfo<T::AccountId>> {
+            let collection = Collections::<T>::get(collection_id)?;
+            Some(CollectionInfo {
+                owner: collection.owner,
+                members: collection
+                    .members
+                    .into_iter()
+                    .map(|member| Self::resolve_root(member).encode())
+                    .collect(),
+            })
+        }
+
+        /// Price per chunk the owner of the file registered under `merkle_root` asks for its
+        /// retrieval, if any.
+        pub fn retrieval_price(merkle_root: T::Hash) -> Option<BalanceOf<T>> {
+            Metadata::<T>::get(merkle_root)?.retrieval_price
+        }
+
+        /// sha256 of the chunk at `position` of the file registered under `merkle_root`, as is,
+        /// without padding the last one. `None` if the file or the position do not exist.
+        pub fn chunk_hash(merkle_root: T::Hash, position: u64) -> Option<[u8; 32]> {
+            FileTrees::<T>::get(merkle_root)?.file_chunk_hash_at(position)
+        }
+
+        /// Position of the file under `file_root` in the bundle under `bundle_root`, along with
+        /// the merkle proof of its root. Hashing the file root along with the proof, as with the
+        /// chunks of a file, leads to the bundle root.
+        pub fn prove_membership(
+            bundle_root: Vec<u8>,
+            file_root: Vec<u8>,
+        ) -> Option<(u32, BoundedProof)> {
+            let key = T::Hash::decode(&mut bundle_root.as_slice()).ok()?;
+            let file_root = T::Hash::decode(&mut file_root.as_slice()).ok()?;
+            let members = Bundles::<T>::get(key)?.members;
+            let position = members.iter().position(|member| *member == file_root)? as u32;
+            let leaves = Self::bundle_leaves(&members)?;
+            let proof = file_merkle_tree::merkle_proof_of(&leaves, position)?;
+            Some((position, proof))
+        }
+
+        /// Number of leading zero bits of the blake2-256 hash of `merkle_root` and `nonce`, i.e.
+        /// the work an anonymous upload of the file with that nonce carries.
+        pub fn upload_work(merkle_root: &T::Hash, nonce: u64) -> u32 {
+            let hash = sp_io::hashing::blake2_256(&(merkle_root, nonce).encode());
+            let mut work = 0;
+            for byte in hash {
+                work += byte.leading_zeros();
+                if byte != 0 {
+                    break;
+                }
+            }
+            work
+        }
+
+        /// Checks whether `leaf` is the hash of the chunk at `position` of the file registered
+        /// under `merkle_root`, given its merkle proof.
+        /// Returns `false` if the file is not registered.
+        pub fn verify_proof(
+            merkle_root: T::Hash,
+            position: u64,
+            leaf: &[u8; 32],
+            proof: &BoundedProof,
+        ) -> bool {
+            Self::file_exists(merkle_root)
+                && file_merkle_tree::verify_proof(merkle_root.as_ref(), position, leaf, proof)
+        }
+
+        /// Like [`Self::verify_proof`], for files taken down by the moderation origin: whether
+        /// `leaf` was the piece at `position` of the file registered under `merkle_root`.
+        pub fn verify_removed_proof(
+            merkle_root: T::Hash,
+            position: u64,
+            leaf: &[u8; 32],
+            proof: &BoundedProof,
+        ) -> bool {
+            let Some(tombstone) = Tombstones::<T>::get(merkle_root) else {
+                return false;
+            };
+            let piece = usize::try_from(position)
+                .ok()
+                .and_then(|i| tombstone.piece_hashes.get(i));
+            piece == Some(leaf)
+                && file_merkle_tree::verify_proof(merkle_root.as_ref(), position, leaf, proof)
+        }
     }
 }
@@ -0,0 +1,962 @@
+//! Storage migrations of the trustless file server pallet.
+use frame_support::traits::{Get, GetStorageVersion, OnRuntimeUpgrade, StorageVersion};
+use frame_support::weights::Weight;
+use frame_support::{storage_alias, Blake2_128Concat, BoundedVec};
+use sp_std::marker::PhantomData;
+#[cfg(feature = "try-runtime")]
+use sp_std::vec::Vec;
+
+use crate::file_merkle_tree::FileMerkleTree;
+use crate::{Config, Deposit, DepositOf, Pallet, RegistrantCount, Registrants};
+
+/// Tracing target of the storage migrations.
+pub(crate) const MIGRATION_TARGET: &str = "tfs::migration";
+
+/// Owner and merkle tree of each file, as stored up to storage version 6.
+#[storage_alias]
+pub(crate) type Files<T: Config> = StorageMap<
+    Pallet<T>,
+    Blake2_128Concat,
+    <T as frame_system::Config>::Hash,
+    (<T as frame_system::Config>::AccountId, FileMerkleTree),
+>;
+
+/// Moves from a single account per file to reference counted registrants.
+pub mod v1 {
+    use super::*;
+    use frame_support::sp_runtime::traits::Zero;
+
+    /// Account that paid the deposit of each file, along with the deposit itself.
+    #[storage_alias]
+    pub(crate) type Deposits<T: Config> = StorageMap<
+        Pallet<T>,
+        Blake2_128Concat,
+        <T as frame_system::Config>::Hash,
+        (<T as frame_system::Config>::AccountId, DepositOf<T>),
+    >;
+
+    /// Turns the owner of every file into its only registrant, keeping the deposit it paid.
+    /// Files uploaded before deposits were introduced get an empty one.
+    pub struct MigrateToV1<T>(PhantomData<T>);
+
+    impl<T: Config> OnRuntimeUpgrade for MigrateToV1<T> {
+        fn on_runtime_upgrade() -> Weight {
+            let on_chain = Pallet::<T>::on_chain_storage_version();
+            if on_chain >= 1 {
+                sp_tracing::info!(target: MIGRATION_TARGET, ?on_chain, "MigrateToV1 not needed");
+                return T::DbWeight::get().reads(1);
+            }
+
+            let mut files = 0u64;
+            for (merkle_root, (owner, _)) in Files::<T>::iter() {
+                let deposit = Deposits::<T>::take(merkle_root)
+                    .map(|(_, deposit)| deposit)
+                    .unwrap_or(Deposit::Native(Zero::zero()));
+                Registrants::<T>::insert(merkle_root, owner, deposit);
+                RegistrantCount::<T>::insert(merkle_root, 1);
+                files += 1;
+            }
+            let _ = Deposits::<T>::clear(u32::MAX, None);
+            StorageVersion::new(1).put::<Pallet<T>>();
+
+            sp_tracing::info!(target: MIGRATION_TARGET, files, "Migrated to storage version 1");
+            T::DbWeight::get().reads_writes(1 + 2 * files, 1 + 3 * files)
+        }
+
+        #[cfg(feature = "try-runtime")]
+        fn pre_upgrade() -> Result<Vec<u8>, &'static str> {
+            use codec::Encode;
+
+            Ok((Files::<T>::iter_keys().count() as u32).encode())
+        }
+
+        #[cfg(feature = "try-runtime")]
+        fn post_upgrade(state: Vec<u8>) -> Result<(), &'static str> {
+            use codec::Decode;
+
+            let files = u32::decode(&mut state.as_slice()).map_err(|_| "Invalid state")?;
+            frame_support::ensure!(
+                Pallet::<T>::on_chain_storage_version() == 1,
+                "Storage version not updated"
+            );
+            frame_support::ensure!(
+                RegistrantCount::<T>::iter_keys().count() as u32 == files,
+                "Files without registrants"
+            );
+            frame_support::ensure!(Deposits::<T>::iter_keys().next().is_none(), "Deposits left");
+            Ok(())
+        }
+    }
+}
+
+/// Adds the license to the metadata of every file.
+pub mod v2 {
+    use super::*;
+    use crate::License;
+    use codec::{Decode, Encode};
+
+    /// Metadata as stored before licenses were introduced.
+    #[derive(Encode, Decode)]
+    pub(crate) struct OldFileMetadata<T: Config> {
+        pub name: BoundedVec<u8, T::StringLimit>,
+        pub mime_type: BoundedVec<u8, T::StringLimit>,
+        pub tags: BoundedVec<BoundedVec<u8, T::StringLimit>, T::MaxTags>,
+    }
+
+    /// Metadata as stored from storage version 2, with a license.
+    #[derive(Encode, Decode)]
+    pub(crate) struct FileMetadata<T: Config> {
+        pub name: BoundedVec<u8, T::StringLimit>,
+        pub mime_type: BoundedVec<u8, T::StringLimit>,
+        pub tags: BoundedVec<BoundedVec<u8, T::StringLimit>, T::MaxTags>,
+        pub license: Option<License<BoundedVec<u8, T::StringLimit>>>,
+    }
+
+    /// Metadata of each file, as stored in storage version 2.
+    #[storage_alias]
+    pub(crate) type Metadata<T: Config> =
+        StorageMap<Pallet<T>, Blake2_128Concat, <T as frame_system::Config>::Hash, FileMetadata<T>>;
+
+    /// Rewrites the metadata of every file in the new layout, with no license.
+    pub struct MigrateToV2<T>(PhantomData<T>);
+
+    impl<T: Config> OnRuntimeUpgrade for MigrateToV2<T> {
+        fn on_runtime_upgrade() -> Weight {
+            let on_chain = Pallet::<T>::on_chain_storage_version();
+            if on_chain >= 2 {
+                sp_tracing::info!(target: MIGRATION_TARGET, ?on_chain, "MigrateToV2 not needed");
+                return T::DbWeight::get().reads(1);
+            }
+
+            let mut files = 0u64;
+            Metadata::<T>::translate::<OldFileMetadata<T>, _>(|_, old| {
+                files += 1;
+                Some(FileMetadata::<T> {
+                    name: old.name,
+                    mime_type: old.mime_type,
+                    tags: old.tags,
+                    license: None,
+                })
+            });
+            StorageVersion::new(2).put::<Pallet<T>>();
+
+            sp_tracing::info!(
+                target: MIGRATION_TARGET,
+                files,
+                "Migrated the metadata to storage version 2"
+            );
+            T::DbWeight::get().reads_writes(1 + files, 1 + files)
+        }
+
+        #[cfg(feature = "try-runtime")]
+        fn pre_upgrade() -> Result<Vec<u8>, &'static str> {
+            Ok((Metadata::<T>::iter_keys().count() as u32).encode())
+        }
+
+        #[cfg(feature = "try-runtime")]
+        fn post_upgrade(state: Vec<u8>) -> Result<(), &'static str> {
+            let files = u32::decode(&mut state.as_slice()).map_err(|_| "Invalid state")?;
+            frame_support::ensure!(
+                Pallet::<T>::on_chain_storage_version() == 2,
+                "Storage version not updated"
+            );
+            frame_support::ensure!(
+                Metadata::<T>::iter_values().count() as u32 == files,
+                "Undecodable metadata"
+            );
+            Ok(())
+        }
+    }
+}
+
+/// Widens file sizes and piece positions to 64 bits.
+pub mod v3 {
+    use super::*;
+    use crate::file_merkle_tree;
+    use crate::{
+        AllocationsOf, BalanceOf, DepositOf, ExternalFile, ExternalFiles, LicenseOf, PendingUpload,
+        PendingUploads, ProbeResult, ProbeResults, RootClaim, RootClaims,
+    };
+    use codec::{Decode, Encode};
+    use frame_support::Twox64Concat;
+    use sp_std::{vec, vec::Vec};
+
+    /// Merkle tree as stored before sizes were 64 bits: the file size as a `u32`, and the tree
+    /// taking the rest of the input, without a length prefix.
+    pub(crate) struct OldFileMerkleTree(pub FileMerkleTree);
+
+    impl Encode for OldFileMerkleTree {
+        fn encode_to<W: codec::Output + ?Sized>(&self, dest: &mut W) {
+            let tree = &self.0;
+            dest.write(&(tree.file_size as u32).to_le_bytes());
+            dest.write(&tree.chunk_size.to_le_bytes());
+            if let Some(boundary_hash) = &tree.boundary_hash {
+                dest.write(boundary_hash);
+            }
+            dest.write(&tree.merkle_tree);
+        }
+    }
+
+    impl Decode for OldFileMerkleTree {
+        fn decode<I: codec::Input>(input: &mut I) -> Result<Self, codec::Error> {
+            let mut buff = [0u8; 4];
+            input.read(&mut buff)?;
+            let file_size = u32::from_le_bytes(buff).into();
+            let (chunk_size, boundary_hash) = file_merkle_tree::decode_chunking(input, file_size)?;
+            let merkle_tree_len = input.remaining_len()?.ok_or("Unknown merkle tree length")?;
+            let mut bytes = vec![0u8; merkle_tree_len];
+            input.read(&mut bytes)?;
+            Ok(Self(FileMerkleTree {
+                file_size,
+                chunk_size,
+                merkle_tree: bytes.try_into().map_err(|_| "Merkle tree too large")?,
+                boundary_hash,
+            }))
+        }
+    }
+
+    #[derive(Encode, Decode)]
+    pub(crate) struct OldRootClaim<AccountId, Balance> {
+        pub who: AccountId,
+        pub deposit: Balance,
+        pub size: u32,
+    }
+
+    #[derive(Encode, Decode)]
+    pub(crate) struct OldPendingUpload<AccountId, Deposit, License> {
+        pub who: AccountId,
+        pub sponsor: Option<AccountId>,
+        pub deposit: Deposit,
+        pub sha256: [u8; 32],
+        pub license: Option<License>,
+        pub merkle_tree: OldFileMerkleTree,
+    }
+
+    #[derive(Encode, Decode)]
+    pub(crate) struct OldProbeResult<BlockNumber> {
+        pub block: BlockNumber,
+        pub position: u32,
+        pub available: bool,
+    }
+
+    #[derive(Encode, Decode)]
+    pub(crate) struct OldExternalFile<AccountId> {
+        pub owner: AccountId,
+        pub size: u32,
+        pub pieces: u32,
+    }
+
+    /// Chunk allocations as stored before positions were 64 bits.
+    #[storage_alias]
+    pub(crate) type ChunkAllocations<T: Config> = StorageDoubleMap<
+        Pallet<T>,
+        Blake2_128Concat,
+        <T as frame_system::Config>::Hash,
+        Twox64Concat,
+        u32,
+        AllocationsOf<T>,
+    >;
+
+    /// Rewrites the merkle trees, pending uploads, root claims, probe results, external files and
+    /// chunk allocations with 64 bit sizes and positions.
+    pub struct MigrateToV3<T>(PhantomData<T>);
+
+    impl<T: Config> OnRuntimeUpgrade for MigrateToV3<T> {
+        fn on_runtime_upgrade() -> Weight {
+            let on_chain = Pallet::<T>::on_chain_storage_version();
+            if on_chain >= 3 {
+                sp_tracing::info!(target: MIGRATION_TARGET, ?on_chain, "MigrateToV3 not needed");
+                return T::DbWeight::get().reads(1);
+            }
+
+            let mut items = 0u64;
+            Files::<T>::translate::<(T::AccountId, OldFileMerkleTree), _>(|_, (owner, old)| {
+                items += 1;
+                Some((owner, old.0))
+            });
+            PendingUploads::<T>::translate::<
+                OldPendingUpload<T::AccountId, DepositOf<T>, LicenseOf<T>>,
+                _,
+            >(|_, old| {
+                items += 1;
+                Some(PendingUpload {
+                    who: old.who,
+                    sponsor: old.sponsor,
+                    deposit: old.deposit,
+                    sha256: old.sha256,
+                    license: old.license,
+                    merkle_tree: old.merkle_tree.0,
+                })
+            });
+            RootClaims::<T>::translate::<OldRootClaim<T::AccountId, BalanceOf<T>>, _>(|_, old| {
+                items += 1;
+                Some(RootClaim {
+                    who: old.who,
+                    deposit: old.deposit,
+                    size: old.size.into(),
+                })
+            });
+            ProbeResults::<T>::translate::<OldProbeResult<T::BlockNumber>, _>(|_, old| {
+                items += 1;
+                Some(ProbeResult {
+                    block: old.block,
+                    position: old.position.into(),
+                    available: old.available,
+                })
+            });
+            ExternalFiles::<T>::translate::<OldExternalFile<T::AccountId>, _>(|_, _, old| {
+                items += 1;
+                Some(ExternalFile {
+                    owner: old.owner,
+                    size: old.size.into(),
+                    pieces: old.pieces,
+                })
+            });
+            // the position is part of the key, so allocations have to be moved
+            let allocations: Vec<_> = ChunkAllocations::<T>::drain().collect();
+            for (merkle_root, position, peers) in allocations {
+                crate::ChunkAllocations::<T>::insert(merkle_root, u64::from(position), peers);
+                items += 1;
+            }
+            StorageVersion::new(3).put::<Pallet<T>>();
+
+            sp_tracing::info!(target: MIGRATION_TARGET, items, "Migrated to storage version 3");
+            T::DbWeight::get().reads_writes(1 + items, 1 + 2 * items)
+        }
+
+        #[cfg(feature = "try-runtime")]
+        fn pre_upgrade() -> Result<Vec<u8>, &'static str> {
+            Ok((Files::<T>::iter_keys().count() as u32).encode())
+        }
+
+        #[cfg(feature = "try-runtime")]
+        fn post_upgrade(state: Vec<u8>) -> Result<(), &'static str> {
+            let files = u32::decode(&mut state.as_slice()).map_err(|_| "Invalid state")?;
+            frame_support::ensure!(
+                Pallet::<T>::on_chain_storage_version() == 3,
+                "Storage version not updated"
+            );
+            frame_support::ensure!(
+                Files::<T>::iter_values().count() as u32 == files,
+                "Undecodable merkle trees"
+            );
+            Ok(())
+        }
+    }
+}
+
+/// Accounts for the storage every account is a registrant of.
+pub mod v4 {
+    use super::*;
+    use crate::{AccountUsage, Sponsors};
+
+    /// Fills `AccountUsage` out of the registrations of every file and who paid for them.
+    pub struct MigrateToV4<T>(PhantomData<T>);
+
+    impl<T: Config> OnRuntimeUpgrade for MigrateToV4<T> {
+        fn on_runtime_upgrade() -> Weight {
+            let on_chain = Pallet::<T>::on_chain_storage_version();
+            if on_chain >= 4 {
+                sp_tracing::info!(target: MIGRATION_TARGET, ?on_chain, "MigrateToV4 not needed");
+                return T::DbWeight::get().reads(1);
+            }
+
+            let mut registrations = 0u64;
+            for (merkle_root, who, deposit) in Registrants::<T>::iter() {
+                let Some((_, tree)) = Files::<T>::get(merkle_root) else {
+                    continue;
+                };
+                let payer = Sponsors::<T>::get(merkle_root, &who).unwrap_or_else(|| who.clone());
+                Pallet::<T>::note_registration(&who, &payer, tree.file_size, &deposit);
+                registrations += 1;
+            }
+            StorageVersion::new(4).put::<Pallet<T>>();
+
+            sp_tracing::info!(
+                target: MIGRATION_TARGET,
+                registrations,
+                "Accounted for registrations in storage version 4"
+            );
+            T::DbWeight::get().reads_writes(1 + 5 * registrations, 1 + 2 * registrations)
+        }
+
+        #[cfg(feature = "try-runtime")]
+        fn pre_upgrade() -> Result<Vec<u8>, &'static str> {
+            use codec::Encode;
+
+            Ok((Registrants::<T>::iter_keys().count() as u32).encode())
+        }
+
+        #[cfg(feature = "try-runtime")]
+        fn post_upgrade(state: Vec<u8>) -> Result<(), &'static str> {
+            use codec::Decode;
+
+            let registrations = u32::decode(&mut state.as_slice()).map_err(|_| "Invalid state")?;
+            frame_support::ensure!(
+                Pallet::<T>::on_chain_storage_version() == 4,
+                "Storage version not updated"
+            );
+            let files: u32 = AccountUsage::<T>::iter_values()
+                .map(|usage| usage.files)
+                .sum();
+            frame_support::ensure!(files == registrations, "Registrations not accounted for");
+            Ok(())
+        }
+    }
+}
+
+/// Adds up the size of the registered files into `TotalBytesStored`.
+pub mod v5 {
+    use super::*;
+    use crate::TotalBytesStored;
+
+    /// Fills `TotalBytesStored` out of the trees of the registered files. Chains already over
+    /// `MaxTotalBytes` keep their files, but cannot register new ones until some are removed.
+    pub struct MigrateToV5<T>(PhantomData<T>);
+
+    impl<T: Config> OnRuntimeUpgrade for MigrateToV5<T> {
+        fn on_runtime_upgrade() -> Weight {
+            let on_chain = Pallet::<T>::on_chain_storage_version();
+            if on_chain >= 5 {
+                sp_tracing::info!(target: MIGRATION_TARGET, ?on_chain, "MigrateToV5 not needed");
+                return T::DbWeight::get().reads(1);
+            }
+
+            let mut files = 0u64;
+            let mut total = 0u64;
+            for (_, (_, tree)) in Files::<T>::iter() {
+                total = total.saturating_add(tree.file_size);
+                files += 1;
+            }
+            TotalBytesStored::<T>::put(total);
+            StorageVersion::new(5).put::<Pallet<T>>();
+
+            sp_tracing::info!(
+                target: MIGRATION_TARGET,
+                bytes = total,
+                files,
+                "Accounted for stored bytes in storage version 5"
+            );
+            T::DbWeight::get().reads_writes(1 + files, 2)
+        }
+
+        #[cfg(feature = "try-runtime")]
+        fn post_upgrade(_state: Vec<u8>) -> Result<(), &'static str> {
+            frame_support::ensure!(
+                Pallet::<T>::on_chain_storage_version() == 5,
+                "Storage version not updated"
+            );
+            let total = Files::<T>::iter_values()
+                .fold(0u64, |total, (_, tree)| total.saturating_add(tree.file_size));
+            frame_support::ensure!(
+                TotalBytesStored::<T>::get() == total,
+                "Registered files not accounted for"
+            );
+            Ok(())
+        }
+    }
+}
+
+pub mod v6 {
+    use super::*;
+    use crate::FileOwners;
+
+    /// Fills `FileOwners` out of the owners recorded along with the trees of the registered
+    /// files.
+    pub struct MigrateToV6<T>(PhantomData<T>);
+
+    impl<T: Config> OnRuntimeUpgrade for MigrateToV6<T> {
+        fn on_runtime_upgrade() -> Weight {
+            let on_chain = Pallet::<T>::on_chain_storage_version();
+            if on_chain >= 6 {
+                sp_tracing::info!(target: MIGRATION_TARGET, ?on_chain, "MigrateToV6 not needed");
+                return T::DbWeight::get().reads(1);
+            }
+
+            let mut files = 0u64;
+            for (merkle_root, (owner, _)) in Files::<T>::iter() {
+                FileOwners::<T>::insert(merkle_root, owner);
+                files += 1;
+            }
+            StorageVersion::new(6).put::<Pallet<T>>();
+
+            sp_tracing::info!(
+                target: MIGRATION_TARGET,
+                files,
+                "Indexed the owners in storage version 6"
+            );
+            T::DbWeight::get().reads_writes(1 + files, 1 + files)
+        }
+
+        #[cfg(feature = "try-runtime")]
+        fn post_upgrade(_state: Vec<u8>) -> Result<(), &'static str> {
+            frame_support::ensure!(
+                Pallet::<T>::on_chain_storage_version() == 6,
+                "Storage version not updated"
+            );
+            for (merkle_root, (owner, _)) in Files::<T>::iter() {
+                frame_support::ensure!(
+                    FileOwners::<T>::get(merkle_root) == Some(owner),
+                    "Owner not indexed"
+                );
+            }
+            Ok(())
+        }
+    }
+}
+
+/// Splits the owner, merkle tree and layout of every file into their own storage maps.
+pub mod v7 {
+    use super::*;
+    use crate::{FileOwners, FileTrees};
+    use codec::{Decode, Encode};
+
+    /// Layout as stored from storage version 7, before whole-file CIDs were introduced.
+    #[derive(Encode, Decode)]
+    pub(crate) struct FileLayout {
+        pub size: u64,
+        pub chunk_size: u32,
+        pub pieces: u32,
+        pub bep52: bool,
+    }
+
+    impl From<&FileMerkleTree> for FileLayout {
+        fn from(tree: &FileMerkleTree) -> Self {
+            Self {
+                size: tree.file_size,
+                chunk_size: tree.chunk_size,
+                pieces: tree.pieces(),
+                bep52: tree.is_bep52(),
+            }
+        }
+    }
+
+    /// Layout of each file, as stored in storage version 7.
+    #[storage_alias]
+    pub(crate) type FileMeta<T: Config> =
+        StorageMap<Pallet<T>, Blake2_128Concat, <T as frame_system::Config>::Hash, FileLayout>;
+
+    /// Moves every entry of `Files` into `FileOwners`, `FileTrees` and `FileMeta`, so that
+    /// owners and sizes can be read without decoding merkle trees.
+    pub struct MigrateToV7<T>(PhantomData<T>);
+
+    impl<T: Config> OnRuntimeUpgrade for MigrateToV7<T> {
+        fn on_runtime_upgrade() -> Weight {
+            let on_chain = Pallet::<T>::on_chain_storage_version();
+            if on_chain >= 7 {
+                sp_tracing::info!(target: MIGRATION_TARGET, ?on_chain, "MigrateToV7 not needed");
+                return T::DbWeight::get().reads(1);
+            }
+
+            let mut files = 0u64;
+            for (merkle_root, (owner, tree)) in Files::<T>::drain() {
+                FileOwners::<T>::insert(merkle_root, owner);
+                FileMeta::<T>::insert(merkle_root, FileLayout::from(&tree));
+                FileTrees::<T>::insert(merkle_root, tree);
+                files += 1;
+            }
+            StorageVersion::new(7).put::<Pallet<T>>();
+
+            sp_tracing::info!(
+                target: MIGRATION_TARGET,
+                files,
+                "Split the files in storage version 7"
+            );
+            T::DbWeight::get().reads_writes(1 + files, 1 + 4 * files)
+        }
+
+        #[cfg(feature = "try-runtime")]
+        fn pre_upgrade() -> Result<Vec<u8>, &'static str> {
+            Ok((Files::<T>::iter_keys().count() as u32).encode())
+        }
+
+        #[cfg(feature = "try-runtime")]
+        fn post_upgrade(state: Vec<u8>) -> Result<(), &'static str> {
+            let files = u32::decode(&mut state.as_slice()).map_err(|_| "Invalid state")?;
+            frame_support::ensure!(
+                Pallet::<T>::on_chain_storage_version() == 7,
+                "Storage version not updated"
+            );
+            frame_support::ensure!(Files::<T>::iter_keys().next().is_none(), "Files left");
+            frame_support::ensure!(
+                FileTrees::<T>::iter_keys().count() as u32 == files
+                    && FileMeta::<T>::iter_keys().count() as u32 == files
+                    && FileOwners::<T>::iter_keys().count() as u32 == files,
+                "Files not split"
+            );
+            Ok(())
+        }
+    }
+}
+
+/// Adds the retrieval price to the metadata of every file.
+pub mod v8 {
+    use super::*;
+    use crate::{BalanceOf, License};
+    use codec::{Decode, Encode};
+
+    /// Metadata as stored before retrieval prices were introduced.
+    pub(crate) type OldFileMetadata<T> = super::v2::FileMetadata<T>;
+
+    /// Metadata as stored from storage version 8, with a retrieval price.
+    #[derive(Encode, Decode)]
+    pub(crate) struct FileMetadata<T: Config> {
+        pub name: BoundedVec<u8, T::StringLimit>,
+        pub mime_type: BoundedVec<u8, T::StringLimit>,
+        pub tags: BoundedVec<BoundedVec<u8, T::StringLimit>, T::MaxTags>,
+        pub license: Option<License<BoundedVec<u8, T::StringLimit>>>,
+        pub retrieval_price: Option<BalanceOf<T>>,
+    }
+
+    /// Metadata of each file, as stored in storage version 8.
+    #[storage_alias]
+    pub(crate) type Metadata<T: Config> =
+        StorageMap<Pallet<T>, Blake2_128Concat, <T as frame_system::Config>::Hash, FileMetadata<T>>;
+
+    /// Rewrites the metadata of every file in the new layout, with no retrieval price.
+    pub struct MigrateToV8<T>(PhantomData<T>);
+
+    impl<T: Config> OnRuntimeUpgrade for MigrateToV8<T> {
+        fn on_runtime_upgrade() -> Weight {
+            let on_chain = Pallet::<T>::on_chain_storage_version();
+            if on_chain >= 8 {
+                sp_tracing::info!(target: MIGRATION_TARGET, ?on_chain, "MigrateToV8 not needed");
+                return T::DbWeight::get().reads(1);
+            }
+
+            let mut files = 0u64;
+            Metadata::<T>::translate::<OldFileMetadata<T>, _>(|_, old| {
+                files += 1;
+                Some(FileMetadata::<T> {
+                    name: old.name,
+                    mime_type: old.mime_type,
+                    tags: old.tags,
+                    license: old.license,
+                    retrieval_price: None,
+                })
+            });
+            StorageVersion::new(8).put::<Pallet<T>>();
+
+            sp_tracing::info!(
+                target: MIGRATION_TARGET,
+                files,
+                "Migrated the metadata to storage version 8"
+            );
+            T::DbWeight::get().reads_writes(1 + files, 1 + files)
+        }
+
+        #[cfg(feature = "try-runtime")]
+        fn pre_upgrade() -> Result<Vec<u8>, &'static str> {
+            Ok((Metadata::<T>::iter_keys().count() as u32).encode())
+        }
+
+        #[cfg(feature = "try-runtime")]
+        fn post_upgrade(state: Vec<u8>) -> Result<(), &'static str> {
+            let files = u32::decode(&mut state.as_slice()).map_err(|_| "Invalid state")?;
+            frame_support::ensure!(
+                Pallet::<T>::on_chain_storage_version() == 8,
+                "Storage version not updated"
+            );
+            frame_support::ensure!(
+                Metadata::<T>::iter_values().count() as u32 == files,
+                "Undecodable metadata"
+            );
+            Ok(())
+        }
+    }
+}
+
+pub mod v9 {
+    use super::*;
+    use crate::{
+        BalanceOf, Bundles, Collections, HeldBalance, HoldReason, NameOf, PendingUploads, Reports,
+        RootClaims, Sponsors,
+    };
+    use frame_support::sp_runtime::traits::{Saturating, Zero};
+    use frame_support::traits::tokens::fungible::MutateHold;
+    use frame_support::traits::ReservableCurrency;
+
+    /// Names as stored up to storage version 9, with no expiry.
+    #[storage_alias]
+    type Names<T: Config> =
+        StorageMap<Pallet<T>, Blake2_128Concat, NameOf<T>, super::v10::OldAlias<T>>;
+
+    /// Moves every deposit of the pallet from plain reserves to holds under its [`HoldReason`],
+    /// accounting for them in [`HeldBalance`].
+    ///
+    /// Deposits that cannot be held, e.g. because the account already has as many holds as the
+    /// balances pallet allows, are kept reserved and logged.
+    pub struct MigrateToV9<T>(PhantomData<T>);
+
+    impl<T: Config> MigrateToV9<T> {
+        /// Unreserves `amount` from `who` and holds it back for `reason`, returning what is held.
+        fn convert(reason: HoldReason, who: &T::AccountId, amount: BalanceOf<T>) -> BalanceOf<T> {
+            let unreserved = amount.saturating_sub(T::Currency::unreserve(who, amount));
+            if let Err(err) = T::NativeBalance::hold(&reason.into(), who, unreserved) {
+                sp_tracing::warn!(
+                    target: MIGRATION_TARGET,
+                    ?unreserved,
+                    ?reason,
+                    ?err,
+                    "Keeping deposit reserved"
+                );
+                let _ = T::Currency::reserve(who, unreserved);
+                return Zero::zero();
+            }
+            unreserved
+        }
+    }
+
+    impl<T: Config> OnRuntimeUpgrade for MigrateToV9<T> {
+        fn on_runtime_upgrade() -> Weight {
+            let on_chain = Pallet::<T>::on_chain_storage_version();
+            if on_chain >= 9 {
+                sp_tracing::info!(target: MIGRATION_TARGET, ?on_chain, "MigrateToV9 not needed");
+                return T::DbWeight::get().reads(1);
+            }
+
+            let mut deposits = 0u64;
+            let mut held = BalanceOf::<T>::zero();
+            let mut convert = |reason, who: &T::AccountId, amount| {
+                deposits += 1;
+                held.saturating_accrue(Self::convert(reason, who, amount));
+            };
+            for (merkle_root, registrant, deposit) in Registrants::<T>::iter() {
+                if let Deposit::Native(amount) = deposit {
+                    let payer = Sponsors::<T>::get(merkle_root, &registrant).unwrap_or(registrant);
+                    convert(HoldReason::FileDeposit, &payer, amount);
+                }
+            }
+            for upload in PendingUploads::<T>::iter_values() {
+                if let Deposit::Native(amount) = upload.deposit {
+                    let payer = upload.sponsor.unwrap_or(upload.who);
+                    convert(HoldReason::FileDeposit, &payer, amount);
+                }
+            }
+            for claim in RootClaims::<T>::iter_values() {
+                convert(HoldReason::FileDeposit, &claim.who, claim.deposit);
+            }
+            for alias in Names::<T>::iter_values() {
+                convert(HoldReason::AliasDeposit, &alias.owner, alias.deposit);
+            }
+            for bundle in Bundles::<T>::iter_values() {
+                convert(HoldReason::BundleDeposit, &bundle.owner, bundle.deposit);
+            }
+            for collection in Collections::<T>::iter_values() {
+                convert(HoldReason::CollectionDeposit, &collection.owner, collection.deposit);
+            }
+            for report in Reports::<T>::iter_values().flatten() {
+                convert(HoldReason::ReportBond, &report.reporter, report.bond);
+            }
+            HeldBalance::<T>::put(held);
+            StorageVersion::new(9).put::<Pallet<T>>();
+
+            sp_tracing::info!(
+                target: MIGRATION_TARGET,
+                deposits,
+                "Moved deposits to holds for storage version 9"
+            );
+            // every deposit reads its entry, and unreserves and holds from the account
+            T::DbWeight::get().reads_writes(1 + 3 * deposits, 2 + 2 * deposits)
+        }
+
+        #[cfg(feature = "try-runtime")]
+        fn post_upgrade(_state: Vec<u8>) -> Result<(), &'static str> {
+            frame_support::ensure!(
+                Pallet::<T>::on_chain_storage_version() == 9,
+                "Storage version not updated"
+            );
+            Ok(())
+        }
+    }
+}
+
+/// Lets every registered name expire.
+pub mod v10 {
+    use super::*;
+    use crate::{Alias, BalanceOf, Names};
+    use codec::{Decode, Encode};
+    use frame_support::sp_runtime::traits::Saturating;
+
+    /// Name as stored before names expired.
+    #[derive(Encode, Decode)]
+    pub(crate) struct OldAlias<T: Config> {
+        pub owner: T::AccountId,
+        pub merkle_root: T::Hash,
+        pub deposit: BalanceOf<T>,
+    }
+
+    /// Rewrites every name in the new layout, expiring [`Config::NameRegistrationPeriod`] blocks
+    /// after the upgrade. Deposits are kept as they are until names are renewed.
+    pub struct MigrateToV10<T>(PhantomData<T>);
+
+    impl<T: Config> OnRuntimeUpgrade for MigrateToV10<T> {
+        fn on_runtime_upgrade() -> Weight {
+            let on_chain = Pallet::<T>::on_chain_storage_version();
+            if on_chain >= 10 {
+                sp_tracing::info!(target: MIGRATION_TARGET, ?on_chain, "MigrateToV10 not needed");
+                return T::DbWeight::get().reads(1);
+            }
+
+            let expires_at = frame_system::Pallet::<T>::block_number()
+                .saturating_add(T::NameRegistrationPeriod::get());
+            let mut names = 0u64;
+            Names::<T>::translate::<OldAlias<T>, _>(|_, old| {
+                names += 1;
+                Some(Alias {
+                    owner: old.owner,
+                    merkle_root: old.merkle_root,
+                    deposit: old.deposit,
+                    expires_at,
+                })
+            });
+            StorageVersion::new(10).put::<Pallet<T>>();
+
+            sp_tracing::info!(target: MIGRATION_TARGET, names, "Migrated to storage version 10");
+            T::DbWeight::get().reads_writes(2 + names, 1 + names)
+        }
+
+        #[cfg(feature = "try-runtime")]
+        fn pre_upgrade() -> Result<Vec<u8>, &'static str> {
+            Ok((Names::<T>::iter_keys().count() as u32).encode())
+        }
+
+        #[cfg(feature = "try-runtime")]
+        fn post_upgrade(state: Vec<u8>) -> Result<(), &'static str> {
+            let names = u32::decode(&mut state.as_slice()).map_err(|_| "Invalid state")?;
+            frame_support::ensure!(
+                Pallet::<T>::on_chain_storage_version() == 10,
+                "Storage version not updated"
+            );
+            frame_support::ensure!(
+                Names::<T>::iter_values().count() as u32 == names,
+                "Undecodable names"
+            );
+            Ok(())
+        }
+    }
+}
+
+/// Adds the CID of the whole file on IPFS to the layout of every file.
+pub mod v11 {
+    use super::*;
+    use crate::ipfs::CID_LEN;
+    use crate::FileTrees;
+    use codec::{Decode, Encode};
+
+    /// Layout as stored before whole-file CIDs were introduced.
+    pub(crate) type OldFileLayout = super::v7::FileLayout;
+
+    /// Layout as stored from storage version 11, with the CID of the whole file.
+    #[derive(Encode, Decode)]
+    pub(crate) struct FileLayout {
+        pub size: u64,
+        pub chunk_size: u32,
+        pub pieces: u32,
+        pub bep52: bool,
+        pub cid: Option<[u8; CID_LEN]>,
+    }
+
+    /// Layout of each file, as stored in storage version 11.
+    #[storage_alias]
+    pub(crate) type FileMeta<T: Config> =
+        StorageMap<Pallet<T>, Blake2_128Concat, <T as frame_system::Config>::Hash, FileLayout>;
+
+    /// Rewrites the layout of every file in the new format, along with its CID if the runtime
+    /// splits files as `ipfs add` does.
+    pub struct MigrateToV11<T>(PhantomData<T>);
+
+    impl<T: Config> OnRuntimeUpgrade for MigrateToV11<T> {
+        fn on_runtime_upgrade() -> Weight {
+            let on_chain = Pallet::<T>::on_chain_storage_version();
+            if on_chain >= 11 {
+                sp_tracing::info!(target: MIGRATION_TARGET, ?on_chain, "MigrateToV11 not needed");
+                return T::DbWeight::get().reads(1);
+            }
+
+            let mut files = 0u64;
+            FileMeta::<T>::translate::<OldFileLayout, _>(|merkle_root, old| {
+                files += 1;
+                let cid = FileTrees::<T>::get(merkle_root)
+                    .and_then(|tree| Pallet::<T>::file_layout(&tree).cid);
+                Some(FileLayout {
+                    size: old.size,
+                    chunk_size: old.chunk_size,
+                    pieces: old.pieces,
+                    bep52: old.bep52,
+                    cid,
+                })
+            });
+            StorageVersion::new(11).put::<Pallet<T>>();
+
+            sp_tracing::info!(
+                target: MIGRATION_TARGET,
+                files,
+                "Migrated the layouts to storage version 11"
+            );
+            T::DbWeight::get().reads_writes(1 + 2 * files, 1 + files)
+        }
+
+        #[cfg(feature = "try-runtime")]
+        fn pre_upgrade() -> Result<Vec<u8>, &'static str> {
+            Ok((FileMeta::<T>::iter_keys().count() as u32).encode())
+        }
+
+        #[cfg(feature = "try-runtime")]
+        fn post_upgrade(state: Vec<u8>) -> Result<(), &'static str> {
+            let files = u32::decode(&mut state.as_slice()).map_err(|_| "Invalid state")?;
+            frame_support::ensure!(
+                Pallet::<T>::on_chain_storage_version() == 11,
+                "Storage version not updated"
+            );
+            frame_support::ensure!(
+                FileMeta::<T>::iter_values().count() as u32 == files,
+                "Undecodable layouts"
+            );
+            Ok(())
+        }
+    }
+}
+
+/// Keys the served bandwidth by the authority that reported it.
+pub mod v12 {
+    use super::*;
+    use crate::ServedBandwidth;
+
+    /// Drops the served bandwidth recorded so far, as it was not attributed to any node and
+    /// cannot be moved to the authorities that reported it.
+    pub struct MigrateToV12<T>(PhantomData<T>);
+
+    impl<T: Config> OnRuntimeUpgrade for MigrateToV12<T> {
+        fn on_runtime_upgrade() -> Weight {
+            let on_chain = Pallet::<T>::on_chain_storage_version();
+            if on_chain >= 12 {
+                sp_tracing::info!(target: MIGRATION_TARGET, ?on_chain, "MigrateToV12 not needed");
+                return T::DbWeight::get().reads(1);
+            }
+
+            let removed = ServedBandwidth::<T>::clear(u32::MAX, None).unique as u64;
+            StorageVersion::new(12).put::<Pallet<T>>();
+
+            sp_tracing::info!(
+                target: MIGRATION_TARGET,
+                files = removed,
+                "Dropped the served bandwidth for storage version 12"
+            );
+            T::DbWeight::get().reads_writes(1 + removed, 1 + removed)
+        }
+
+        #[cfg(feature = "try-runtime")]
+        fn post_upgrade(_state: Vec<u8>) -> Result<(), &'static str> {
+            frame_support::ensure!(
+                Pallet::<T>::on_chain_storage_version() == 12,
+                "Storage version not updated"
+            );
+            frame_support::ensure!(
+                ServedBandwidth::<T>::iter_keys().next().is_none(),
+                "Served bandwidth left"
+            );
+            Ok(())
+        }
+    }
+}
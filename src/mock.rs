@@ -1,16 +1,151 @@
+//! Mock runtime the pallet is tested with, exported through the `test-utils` feature so that
+//! runtimes embedding the pallet can write their integration tests on top of it.
+use std::sync::Arc;
+
 use crate as pallet_trustless_file_server;
-use frame_support::traits::{ConstU16, ConstU64};
+use crate::{AuthorityProof, AuthorityProofOf, Config, OcwAuthorities, Pallet};
+use codec::Encode;
+use frame_support::traits::tokens::BalanceConversion;
+use frame_support::traits::{
+    AsEnsureOriginWithArg, ConstBool, ConstU16, ConstU32, ConstU64, EnsureOrigin,
+    EqualPrivilegeOnly, IsInVec, Randomness, SortedMembers,
+};
+use frame_support::weights::Weight;
+use frame_support::PalletId;
+use frame_system::{EnsureRoot, EnsureSigned, EnsureSignedBy};
+use parking_lot::RwLock;
+use sp_core::offchain::testing::{
+    OffchainState, PoolState, TestOffchainExt, TestTransactionPoolExt,
+};
+use sp_core::offchain::{OffchainDbExt, OffchainWorkerExt, TransactionPoolExt};
 use sp_core::H256;
 use sp_runtime::{
-    testing::Header,
-    traits::{BlakeTwo256, IdentityLookup},
+    testing::{Header, TestSignature, UintAuthorityId},
+    traits::{BlakeTwo256, Hash, IdentityLookup},
+    BuildStorage,
+};
+use xcm::latest::{
+    Junction::Parachain, Junctions::X1, MultiAssets, MultiLocation, SendError, SendResult, SendXcm,
+    Xcm, XcmHash,
 };
 
-type UncheckedExtrinsic = frame_system::mocking::MockUncheckedExtrinsic<Test>;
+pub type UncheckedExtrinsic = frame_system::mocking::MockUncheckedExtrinsic<Test>;
 type Block = frame_system::mocking::MockBlock<Test>;
 
 frame_support::parameter_types! {
+    pub const MaximumSchedulerWeight: Weight = Weight::from_parts(1_000_000_000_000, u64::MAX);
+    pub const AttestationWeight: Weight = Weight::from_parts(1_000_000_000, 65536);
+    pub const AttestationCallIndex: [u8; 2] = [60, 0];
+    pub TrustedSiblings: Vec<u32> = vec![2000];
     pub const IpfsNodeUrl: &'static str = "http://127.0.0.1:5001";
+    pub const GatewayUrl: Option<&'static str> = None;
+    pub static IpfsClusterUrl: Option<&'static str> = None;
+    pub static PinningServiceUrl: Option<&'static str> = None;
+    pub static Bep52Compatible: bool = false;
+    pub static IpfsChunkSize: Option<u32> = None;
+    pub static RequireReview: bool = false;
+    pub static PriceOracle: Option<u64> = None;
+    pub static MaxTotalBytes: u64 = u64::MAX;
+    pub static MaxUploadsPerAccountPerBlock: u32 = u32::MAX;
+    pub static AuditPeriod: u64 = 0;
+    pub static UnsignedUploadDifficulty: Option<u32> = Some(8);
+    pub static KeyCommitteeMembers: Vec<u64> = vec![1, 2, 3];
+    pub const TrustlessFileServerPalletId: PalletId = PalletId(*b"py/tfsrv");
+    pub static SentXcm: Vec<(MultiLocation, Xcm<()>)> = vec![];
+}
+
+frame_support::ord_parameter_types! {
+    /// Account of the scanning service reviewing uploads.
+    pub const Reviewer: u64 = 9;
+}
+
+/// Asset accepted for deposits in tests, worth twice as much as the native currency.
+pub const DEPOSIT_ASSET: u32 = 7;
+/// Balance of the funded accounts, both in the native currency and in `DEPOSIT_ASSET`.
+pub const INITIAL_BALANCE: u64 = 1_000_000;
+
+pub struct HalfPrice;
+
+impl BalanceConversion<u64, u32, u64> for HalfPrice {
+    type Error = ();
+
+    fn to_asset_balance(balance: u64, _asset_id: u32) -> Result<u64, Self::Error> {
+        Ok(balance / 2)
+    }
+}
+
+/// Deterministic randomness, so that audit samples only depend on the block.
+pub struct TestRandomness;
+
+impl Randomness<H256, u64> for TestRandomness {
+    fn random(subject: &[u8]) -> (H256, u64) {
+        (BlakeTwo256::hash(subject), System::block_number())
+    }
+}
+
+/// Committee keys can be escrowed with, made of `KeyCommitteeMembers`.
+pub struct KeyCommittee;
+
+impl SortedMembers<u64> for KeyCommittee {
+    fn sorted_members() -> Vec<u64> {
+        KeyCommitteeMembers::get()
+    }
+
+    #[cfg(feature = "runtime-benchmarks")]
+    fn add(who: &u64) {
+        let mut members = KeyCommitteeMembers::get();
+        members.push(*who);
+        members.sort();
+        KeyCommitteeMembers::set(members);
+    }
+}
+
+/// Stands for the origins converted from XCM: signed origins become the location of the
+/// sibling parachain with the same identifier as the account, except for `RELAY`.
+pub struct EnsureXcmLocation;
+
+/// Account whose signed origin stands for the relay chain.
+pub const RELAY: u64 = 0;
+
+impl EnsureOrigin<RuntimeOrigin> for EnsureXcmLocation {
+    type Success = MultiLocation;
+
+    fn try_origin(o: RuntimeOrigin) -> Result<Self::Success, RuntimeOrigin> {
+        o.into().and_then(|o| match o {
+            frame_system::RawOrigin::Signed(RELAY) => Ok(MultiLocation::parent()),
+            frame_system::RawOrigin::Signed(id) => {
+                Ok(MultiLocation::new(1, X1(Parachain(id as u32))))
+            }
+            r => Err(RuntimeOrigin::from(r)),
+        })
+    }
+
+    #[cfg(feature = "runtime-benchmarks")]
+    fn try_successful_origin() -> Result<RuntimeOrigin, ()> {
+        Ok(RuntimeOrigin::signed(2000))
+    }
+}
+
+/// Records the XCM messages sent instead of delivering them.
+pub struct TestXcmSender;
+
+impl SendXcm for TestXcmSender {
+    type Ticket = (MultiLocation, Xcm<()>);
+
+    fn validate(
+        destination: &mut Option<MultiLocation>,
+        message: &mut Option<Xcm<()>>,
+    ) -> SendResult<Self::Ticket> {
+        let destination = destination.take().ok_or(SendError::MissingArgument)?;
+        let message = message.take().ok_or(SendError::MissingArgument)?;
+        Ok(((destination, message), MultiAssets::new()))
+    }
+
+    fn deliver(ticket: Self::Ticket) -> Result<XcmHash, SendError> {
+        let hash = ticket.1.using_encoded(sp_io::hashing::blake2_256);
+        SentXcm::mutate(|sent| sent.push(ticket));
+        Ok(hash)
+    }
 }
 
 // Configure a mock runtime to test the pallet.
@@ -21,6 +156,11 @@ frame_support::construct_runtime!(
         UncheckedExtrinsic = UncheckedExtrinsic,
     {
         System: frame_system,
+        Balances: pallet_balances,
+        Assets: pallet_assets,
+        Preimage: pallet_preimage,
+        Scheduler: pallet_scheduler,
+        Timestamp: pallet_timestamp,
         TrustlessFileServer: pallet_trustless_file_server,
     }
 );
@@ -43,25 +183,349 @@ impl frame_system::Config for Test {
     type DbWeight = ();
     type Version = ();
     type PalletInfo = PalletInfo;
-    type AccountData = ();
+    type AccountData = pallet_balances::AccountData<u64>;
     type OnNewAccount = ();
     type OnKilledAccount = ();
     type SystemWeightInfo = ();
     type SS58Prefix = ConstU16<42>;
     type OnSetCode = ();
-    type MaxConsumers = frame_support::traits::ConstU32<16>;
+    type MaxConsumers = ConstU32<16>;
+}
+
+impl pallet_balances::Config for Test {
+    type Balance = u64;
+    type DustRemoval = ();
+    type RuntimeEvent = RuntimeEvent;
+    type ExistentialDeposit = ConstU64<1>;
+    type AccountStore = System;
+    type WeightInfo = ();
+    type MaxLocks = ();
+    type MaxReserves = ();
+    type ReserveIdentifier = [u8; 8];
+    type FreezeIdentifier = ();
+    type MaxFreezes = ();
+    type HoldIdentifier = pallet_trustless_file_server::HoldReason;
+    type MaxHolds = ConstU32<5>;
+}
+
+impl pallet_assets::Config for Test {
+    type RuntimeEvent = RuntimeEvent;
+    type Balance = u64;
+    type RemoveItemsLimit = ConstU32<5>;
+    type AssetId = u32;
+    type AssetIdParameter = u32;
+    type Currency = Balances;
+    type CreateOrigin = AsEnsureOriginWithArg<EnsureSigned<u64>>;
+    type ForceOrigin = EnsureRoot<u64>;
+    type AssetDeposit = ConstU64<1>;
+    type AssetAccountDeposit = ConstU64<1>;
+    type MetadataDepositBase = ConstU64<1>;
+    type MetadataDepositPerByte = ConstU64<1>;
+    type ApprovalDeposit = ConstU64<1>;
+    type StringLimit = ConstU32<50>;
+    type Freezer = ();
+    type Extra = ();
+    type CallbackHandle = ();
+    type WeightInfo = ();
+    #[cfg(feature = "runtime-benchmarks")]
+    type BenchmarkHelper = ();
+}
+
+impl pallet_preimage::Config for Test {
+    type RuntimeEvent = RuntimeEvent;
+    type WeightInfo = ();
+    type Currency = Balances;
+    type ManagerOrigin = EnsureRoot<u64>;
+    type BaseDeposit = ConstU64<0>;
+    type ByteDeposit = ConstU64<0>;
+}
+
+impl pallet_timestamp::Config for Test {
+    type Moment = u64;
+    type OnTimestampSet = ();
+    type MinimumPeriod = ConstU64<1>;
+    type WeightInfo = ();
+}
+
+impl pallet_scheduler::Config for Test {
+    type RuntimeEvent = RuntimeEvent;
+    type RuntimeOrigin = RuntimeOrigin;
+    type PalletsOrigin = OriginCaller;
+    type RuntimeCall = RuntimeCall;
+    type MaximumWeight = MaximumSchedulerWeight;
+    type ScheduleOrigin = EnsureRoot<u64>;
+    type MaxScheduledPerBlock = ConstU32<8>;
+    type WeightInfo = ();
+    type OriginPrivilegeCmp = EqualPrivilegeOnly;
+    type Preimages = Preimage;
 }
 
 impl pallet_trustless_file_server::Config for Test {
     type RuntimeEvent = RuntimeEvent;
     type IpfsNodeUrl = IpfsNodeUrl;
+    type AnnounceToDht = ConstBool<false>;
+    type IpfsClusterUrl = IpfsClusterUrl;
+    type ClusterReplicationFactor = ConstU32<3>;
+    type GatewayUrl = GatewayUrl;
+    type ProbeInterval = ConstU64<100>;
+    type Randomness = TestRandomness;
+    type UnixTime = Timestamp;
+    type AuditPeriod = AuditPeriod;
+    type AuditSampleSize = ConstU32<2>;
+    type PinningServiceUrl = PinningServiceUrl;
+    type RepinInterval = ConstU64<50>;
+    type MaxRepinsPerRun = ConstU32<2>;
+    type ServedReportInterval = ConstU64<30>;
+    type MaxFileSize = ConstU32<32768>;
+    type MaxTotalBytes = MaxTotalBytes;
+    type Currency = Balances;
+    type NativeBalance = Balances;
+    type RuntimeHoldReason = pallet_trustless_file_server::HoldReason;
+    type Assets = Assets;
+    type BalanceToAssetBalance = HalfPrice;
+    type DepositBase = ConstU64<100>;
+    type DepositPerByte = ConstU64<1>;
+    type PriceOracle = PriceOracle;
+    type PalletId = TrustlessFileServerPalletId;
+    type MaxRegistrants = ConstU32<3>;
+    type AdminOrigin = EnsureRoot<u64>;
+    type UploadOrigin = EnsureSigned<u64>;
+    type ContentPolicy = pallet_trustless_file_server::DenyExecutables;
+    type MinChunkSize = ConstU32<1024>;
+    type MaxChunkSize = ConstU32<{ 1024 * 1024 }>;
+    type TargetPieces = ConstU32<32>;
+    type Bep52Compatible = Bep52Compatible;
+    type IpfsChunkSize = IpfsChunkSize;
+    type MaxUploadsPerBlock = ConstU32<4>;
+    type MaxUploadsPerAccountPerBlock = MaxUploadsPerAccountPerBlock;
+    type MaxFilesPerBatch = ConstU32<4>;
+    type UnsignedUploadDifficulty = UnsignedUploadDifficulty;
+    type ProgressReportInterval = ConstU32<4>;
+    type MaxChunksPerOcwRun = ConstU32<8>;
+    type UnsignedPriority = ConstU64<{ 1 << 20 }>;
+    type AuthorityId = UintAuthorityId;
+    type MaxOcwAuthorities = ConstU32<4>;
+    type MaxCollectionSize = ConstU32<4>;
+    type StringLimit = ConstU32<64>;
+    type MaxTags = ConstU32<8>;
+    type MaxNameLength = ConstU32<32>;
+    type NameDeposit = ConstU64<10>;
+    type NameDepositPerByte = ConstU64<1>;
+    type NameRegistrationPeriod = ConstU64<100>;
+    type MaxKeyLength = ConstU32<64>;
+    type KeyCommittee = KeyCommittee;
+    type MaxKeyHolders = ConstU32<16>;
+    type MaxEncryptedShareLength = ConstU32<128>;
+    type ModerationOrigin = EnsureRoot<u64>;
+    type LargeFileThreshold = ConstU32<16384>;
+    type LargeUploadOrigin = EnsureRoot<u64>;
+    type RequireReview = RequireReview;
+    type ReviewOrigin = EnsureSignedBy<Reviewer, u64>;
+    type XcmSender = TestXcmSender;
+    type AttestationCallIndex = AttestationCallIndex;
+    type AttestationWeight = AttestationWeight;
+    type SiblingOrigin = EnsureXcmLocation;
+    type TrustedSiblings = IsInVec<TrustedSiblings>;
+    type ReportBond = ConstU64<50>;
+    type MaxReportsPerFile = ConstU32<2>;
+    type MaxHistoryLength = ConstU32<3>;
+    type RuntimeCall = RuntimeCall;
+    type PalletsOrigin = OriginCaller;
+    type Scheduler = Scheduler;
+    type Preimages = Preimage;
     type WeightInfo = ();
 }
 
-// Build genesis storage according to the mock runtime.
+impl<C> frame_system::offchain::SendTransactionTypes<C> for Test
+where
+    RuntimeCall: From<C>,
+{
+    type OverarchingCall = RuntimeCall;
+    type Extrinsic = UncheckedExtrinsic;
+}
+
+/// Builds the externalities of the mock runtime. By default accounts 1, 2 and 3 are funded with
+/// [`INITIAL_BALANCE`], and the parameters of the runtime keep their current values.
+pub struct ExtBuilder {
+    funded: Vec<u64>,
+    balance: u64,
+    block_number: Option<u64>,
+    ipfs_cluster_url: Option<Option<&'static str>>,
+    bep52_compatible: Option<bool>,
+    unsigned_upload_difficulty: Option<Option<u32>>,
+    key_committee: Option<Vec<u64>>,
+}
+
+impl Default for ExtBuilder {
+    fn default() -> Self {
+        Self {
+            funded: vec![1, 2, 3],
+            balance: INITIAL_BALANCE,
+            block_number: None,
+            ipfs_cluster_url: None,
+            bep52_compatible: None,
+            unsigned_upload_difficulty: None,
+            key_committee: None,
+        }
+    }
+}
+
+impl ExtBuilder {
+    /// Funds `accounts` with `balance`, both in the native currency and in [`DEPOSIT_ASSET`],
+    /// instead of the default ones.
+    pub fn with_funded_accounts(mut self, accounts: Vec<u64>, balance: u64) -> Self {
+        self.funded = accounts;
+        self.balance = balance;
+        self
+    }
+
+    /// Starts at `block_number`, so that events are recorded from the beginning.
+    pub fn with_block_number(mut self, block_number: u64) -> Self {
+        self.block_number = Some(block_number);
+        self
+    }
+
+    /// Adds chunks through the IPFS Cluster at `url`, if any.
+    pub fn with_ipfs_cluster_url(mut self, url: Option<&'static str>) -> Self {
+        self.ipfs_cluster_url = Some(url);
+        self
+    }
+
+    /// Builds BitTorrent v2 compatible merkle trees.
+    pub fn with_bep52_compatible(mut self, compatible: bool) -> Self {
+        self.bep52_compatible = Some(compatible);
+        self
+    }
+
+    /// Accepts anonymous uploads with the given difficulty, if any.
+    pub fn with_unsigned_upload_difficulty(mut self, difficulty: Option<u32>) -> Self {
+        self.unsigned_upload_difficulty = Some(difficulty);
+        self
+    }
+
+    /// Sets the committee keys can be escrowed with.
+    pub fn with_key_committee(mut self, members: Vec<u64>) -> Self {
+        self.key_committee = Some(members);
+        self
+    }
+
+    /// Builds the genesis storage according to the mock runtime.
+    pub fn build(self) -> sp_io::TestExternalities {
+        if let Some(url) = self.ipfs_cluster_url {
+            IpfsClusterUrl::set(url);
+        }
+        if let Some(compatible) = self.bep52_compatible {
+            Bep52Compatible::set(compatible);
+        }
+        if let Some(difficulty) = self.unsigned_upload_difficulty {
+            UnsignedUploadDifficulty::set(difficulty);
+        }
+        if let Some(members) = self.key_committee {
+            KeyCommitteeMembers::set(members);
+        }
+
+        let mut storage = frame_system::GenesisConfig::default()
+            .build_storage::<Test>()
+            .unwrap();
+        pallet_balances::GenesisConfig::<Test> {
+            balances: self.funded.iter().map(|who| (*who, self.balance)).collect(),
+        }
+        .assimilate_storage(&mut storage)
+        .unwrap();
+        // sufficient, so that the pallet account can hold it without any native balance
+        pallet_assets::GenesisConfig::<Test> {
+            assets: vec![(DEPOSIT_ASSET, 1, true, 1)],
+            metadata: vec![],
+            accounts: self
+                .funded
+                .iter()
+                .map(|who| (DEPOSIT_ASSET, *who, self.balance))
+                .collect(),
+        }
+        .assimilate_storage(&mut storage)
+        .unwrap();
+        let mut ext: sp_io::TestExternalities = storage.into();
+        ext.execute_with(|| {
+            let authorities = vec![UintAuthorityId(OCW_AUTHORITY)];
+            OcwAuthorities::<Test>::put(frame_support::BoundedVec::truncate_from(authorities));
+        });
+        UintAuthorityId::set_all_keys(vec![OCW_AUTHORITY]);
+        if let Some(block_number) = self.block_number {
+            ext.execute_with(|| System::set_block_number(block_number));
+        }
+        ext
+    }
+
+    /// Like [`ExtBuilder::build`], with offchain worker, offchain indexing and transaction pool
+    /// extensions. HTTP requests of the offchain worker must be mocked through the returned
+    /// state, and the indexed content is only visible to it after calling
+    /// `persist_offchain_overlay`.
+    pub fn build_offchain(
+        self,
+    ) -> (sp_io::TestExternalities, Arc<RwLock<OffchainState>>, Arc<RwLock<PoolState>>) {
+        let mut ext = self.build();
+        let (offchain, offchain_state) = TestOffchainExt::with_offchain_db(ext.offchain_db());
+        let (pool, pool_state) = TestTransactionPoolExt::new();
+        ext.register_extension(OffchainDbExt::new(offchain.clone()));
+        ext.register_extension(OffchainWorkerExt::new(offchain));
+        ext.register_extension(TransactionPoolExt::new(pool));
+        (ext, offchain_state, pool_state)
+    }
+}
+
 pub fn new_test_ext() -> sp_io::TestExternalities {
-    frame_system::GenesisConfig::default()
-        .build_storage::<Test>()
-        .unwrap()
-        .into()
+    ExtBuilder::default().build()
+}
+
+/// See [`ExtBuilder::build_offchain`].
+pub fn new_offchain_test_ext(
+) -> (sp_io::TestExternalities, Arc<RwLock<OffchainState>>, Arc<RwLock<PoolState>>) {
+    ExtBuilder::default().build_offchain()
+}
+
+/// Only offchain worker authority, whose key the tests hold.
+pub const OCW_AUTHORITY: u64 = 1;
+
+/// Proof that the offchain worker of [`OCW_AUTHORITY`] made `report` at the current block.
+pub fn authority_proof(report: impl Encode) -> AuthorityProofOf<Test> {
+    Pallet::<Test>::sign_report(&report).expect("the tests hold the key of the authority; qed")
+}
+
+/// Proof that the offchain worker of a node holding the key `node` made `report` at the current
+/// block, whether it is one of the authorities or not.
+pub fn node_proof(node: u64, report: impl Encode) -> AuthorityProofOf<Test> {
+    let block = System::block_number();
+    let message = Pallet::<Test>::report_message(&report, &UintAuthorityId(node), block);
+    AuthorityProof {
+        authority: UintAuthorityId(node),
+        block,
+        signature: TestSignature(node, message),
+    }
+}
+
+/// Content of the file uploaded by [`upload_test_file`].
+pub const TEST_FILE: &[u8] = include_bytes!("../img/substrate.png");
+
+/// Uploads [`TEST_FILE`] on behalf of `who` and returns its merkle root. Works with any runtime
+/// whose `UploadOrigin` accepts signed origins, as long as `who` can pay for the deposit.
+pub fn upload_test_file<T: Config>(who: T::AccountId) -> T::Hash {
+    let origin = frame_system::RawOrigin::Signed(who).into();
+    frame_support::assert_ok!(Pallet::<T>::upload_file(origin, TEST_FILE.to_vec(), None, None));
+    let (_, merkle_root) = Pallet::<T>::build_tree(TEST_FILE).expect("uploaded above; qed");
+    merkle_root
+}
+
+/// Asserts that the proof served for the piece at `position` of a registered file leads to its
+/// merkle root.
+pub fn assert_proof_valid<T: Config>(merkle_root: T::Hash, position: u64) {
+    let chunk_hash = Pallet::<T>::chunk_hash(merkle_root, position)
+        .unwrap_or_else(|| panic!("no piece at position {}", position));
+    let proof = Pallet::<T>::get_piece_proof(merkle_root.encode(), position)
+        .unwrap_or_else(|| panic!("no proof for the piece at position {}", position))
+        .proof;
+    assert!(
+        Pallet::<T>::verify_proof(merkle_root, position, &chunk_hash, &proof),
+        "invalid proof for the piece at position {}",
+        position
+    );
 }
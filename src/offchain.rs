@@ -0,0 +1,899 @@
+use alloc::format;
+use alloc::string::String;
+
+use codec::{Decode, Encode};
+use frame_support::sp_runtime::offchain::storage::StorageValueRef;
+use frame_support::sp_runtime::offchain::storage_lock::{StorageLock, Time};
+use frame_support::sp_runtime::offchain::StorageKind;
+use frame_support::sp_runtime::traits::Zero;
+use frame_support::sp_runtime::RuntimeAppPublic;
+use frame_support::traits::Get;
+use frame_system::offchain::SubmitTransaction;
+use sp_io::hashing::sha2_256;
+use sp_io::offchain::local_storage_get;
+use sp_std::vec::Vec;
+
+use crate::file_merkle_tree::{self, ChunkingParams, FileMerkleTree, MAX_PIECES};
+use crate::{
+    ipfs, AllocationsOf, AuditSample, AuthorityProof, AuthorityProofOf, BlockUploads, Call,
+    ClusterPeerId, Config, ConfigHelper, FileOwners, FileTrees, OcwAuthorities, Pallet,
+    PendingRechunks, RootClaims,
+};
+
+/// Tracing target of the offchain worker.
+pub(crate) const OCW_TARGET: &str = "tfs::ocw";
+
+/// Node-local queue of files whose chunks are still to be pushed to IPFS.
+pub const QUEUE_KEY: &[u8] = b"pallet_trustless_file_server::queue";
+/// Lock preventing concurrent offchain workers from processing the queue at the same time.
+const QUEUE_LOCK_KEY: &[u8] = b"pallet_trustless_file_server::queue-lock";
+/// Prefix of the keys counting the runs in a row a queued file failed to upload in. The full key
+/// is the prefix followed by the SCALE encoded merkle root.
+pub const UPLOAD_RETRIES_KEY: &[u8] = b"pallet_trustless_file_server::retries/";
+/// Node-local list of the files the offchain worker gave up on pushing to IPFS.
+pub const DEAD_LETTERS_KEY: &[u8] = b"pallet_trustless_file_server::dead-letters";
+/// Number of runs in a row a file can fail to upload in before being given up on.
+pub(crate) const MAX_UPLOAD_RETRIES: u32 = 5;
+/// Number of dead letters kept, dropping the oldest ones first.
+const MAX_DEAD_LETTERS: usize = 64;
+
+/// Node-local counters of the content served through the RPC, by file. Written by the RPC and
+/// reported on chain by the offchain worker every [`Config::ServedReportInterval`] blocks.
+pub const SERVED_KEY: &[u8] = b"pallet_trustless_file_server::served";
+/// Number of files whose served content is reported in a single run.
+const MAX_SERVED_REPORTS: usize = 16;
+
+/// Raw storage key of the last file pinned again, where the next run resumes from.
+const REPIN_CURSOR_KEY: &[u8] = b"pallet_trustless_file_server::repin-cursor";
+
+/// Access token for the remote pinning service, set by the node operator.
+const PINNING_TOKEN_KEY: &[u8] = b"pallet_trustless_file_server::pinning-token";
+/// Prefix of the keys recording the pin request ids returned by the remote pinning service.
+const PIN_REQUEST_KEY: &[u8] = b"pallet_trustless_file_server::pin/";
+
+/// Set by the node operator to any value other than `0x00` to make the offchain worker only log
+/// what it would push to IPFS, without contacting it. See [`Pallet::is_dry_run`].
+const DRY_RUN_KEY: &[u8] = b"pallet_trustless_file_server::dry-run";
+
+/// File contents handed over to the offchain worker through offchain indexing.
+#[derive(Debug, Encode, Decode, Default)]
+pub(crate) struct IndexingData {
+    pub content: Vec<u8>,
+    pub chunk_size: u32,
+}
+
+/// A file waiting to be pushed to IPFS, along with the first chunk not uploaded yet.
+#[derive(Debug, Encode, Decode, Clone, PartialEq)]
+pub struct QueuedUpload<Hash> {
+    pub merkle_root: Hash,
+    pub next_chunk: u32,
+}
+
+type Queue<T> = Vec<QueuedUpload<<T as frame_system::Config>::Hash>>;
+
+/// Why the offchain worker gave up on pushing a file to IPFS.
+#[derive(Debug, Encode, Decode, Clone, PartialEq)]
+pub enum DeadLetterReason {
+    /// The content of the file is not in the offchain database, e.g. because the node did not
+    /// have offchain indexing enabled when importing the upload.
+    MissingContent,
+    /// The file failed to upload in [`MAX_UPLOAD_RETRIES`] runs in a row.
+    TooManyRetries,
+}
+
+/// Content of a file served by the node through the RPC, as counted under [`SERVED_KEY`].
+#[derive(Debug, Encode, Decode, Clone, PartialEq)]
+pub struct ServedContent<Hash> {
+    pub merkle_root: Hash,
+    /// Bytes served since the node started counting.
+    pub bytes: u64,
+    /// Chunks served since the node started counting, whole downloads counting all of them.
+    pub chunks: u64,
+    /// Bytes served not reported on chain yet.
+    pub unreported_bytes: u64,
+    /// Chunks served not reported on chain yet.
+    pub unreported_chunks: u64,
+}
+
+impl<Hash: PartialEq> ServedContent<Hash> {
+    /// Adds `bytes` and `chunks` to the counters of `merkle_root` in `served`, starting them if
+    /// the file was not served before.
+    pub fn record(served: &mut Vec<Self>, merkle_root: Hash, bytes: u64, chunks: u64) {
+        let index = match served
+            .iter()
+            .position(|item| item.merkle_root == merkle_root)
+        {
+            Some(index) => index,
+            None => {
+                served.push(Self {
+                    merkle_root,
+                    bytes: 0,
+                    chunks: 0,
+                    unreported_bytes: 0,
+                    unreported_chunks: 0,
+                });
+                served.len() - 1
+            }
+        };
+        let item = &mut served[index];
+        item.bytes = item.bytes.saturating_add(bytes);
+        item.chunks = item.chunks.saturating_add(chunks);
+        item.unreported_bytes = item.unreported_bytes.saturating_add(bytes);
+        item.unreported_chunks = item.unreported_chunks.saturating_add(chunks);
+    }
+}
+
+/// A file removed from the upload queue without being fully pushed to IPFS.
+#[derive(Debug, Encode, Decode, Clone, PartialEq)]
+pub struct DeadLetter<Hash> {
+    pub merkle_root: Hash,
+    /// First chunk not uploaded.
+    pub next_chunk: u32,
+    /// Runs in a row the file failed to upload in.
+    pub retries: u32,
+    pub reason: DeadLetterReason,
+}
+
+/// Where a run left the upload of a queued file.
+enum Progress {
+    /// Every chunk is on IPFS.
+    Done,
+    /// The budget of the run was exhausted before reaching the given chunk.
+    Paused(u32),
+    /// The given chunk failed to upload.
+    Failed(u32),
+    /// There is no indexed content to upload.
+    MissingContent,
+}
+
+impl<T: Config> Pallet<T> {
+    /// Enqueues the files uploaded at `block_number` and pushes at most
+    /// [`Config::MaxChunksPerOcwRun`] queued chunks to IPFS. Whatever is left is carried over to
+    /// the next run.
+    pub(crate) fn run_offchain_worker(block_number: T::BlockNumber) {
+        let _span =
+            sp_tracing::info_span!(target: OCW_TARGET, "offchain_worker", block = ?block_number)
+                .entered();
+        if !Self::is_ocw_authority() {
+            sp_tracing::debug!(target: OCW_TARGET, block = ?block_number, "Not an authority, skipping");
+            return;
+        }
+        Self::enqueue_block_uploads(block_number);
+        if Self::is_dry_run() {
+            sp_tracing::info!(target: OCW_TARGET, block = ?block_number, "Dry run, not contacting IPFS");
+            Self::rechunk_files();
+            Self::verify_root_claims();
+            Self::simulate_queue();
+            return;
+        }
+        Self::probe_availability(block_number);
+        Self::audit_pieces(block_number);
+        Self::repin_files(block_number);
+        Self::report_served_bandwidth(block_number);
+        Self::rechunk_files();
+        Self::verify_root_claims();
+
+        let mut lock = StorageLock::<Time>::new(QUEUE_LOCK_KEY);
+        let Ok(_guard) = lock.try_lock() else {
+            sp_tracing::info!(target: OCW_TARGET, block = ?block_number, "Upload queue is busy");
+            return;
+        };
+        Self::process_queue();
+    }
+
+    /// Whether the node operator enabled the dry run mode under [`DRY_RUN_KEY`], e.g. to check
+    /// the configuration of a staging network. Files are then enqueued as usual, but the queue is
+    /// only simulated: nothing is pushed to IPFS, probed, audited or pinned again, and no upload
+    /// progress is reported on chain.
+    pub(crate) fn is_dry_run() -> bool {
+        // set raw by the operator through `offchain_localStorageSet`, hence not SCALE encoded
+        local_storage_get(StorageKind::PERSISTENT, DRY_RUN_KEY)
+            .map_or(false, |value| !value.is_empty() && value != [0])
+    }
+
+    /// Whether the node holds the key of one of the [`OcwAuthorities`]. No node is while there
+    /// are none, as reports are only accepted signed by one of them.
+    fn is_ocw_authority() -> bool {
+        let authorities = OcwAuthorities::<T>::get();
+        T::AuthorityId::all()
+            .iter()
+            .any(|key| authorities.contains(key))
+    }
+
+    /// Signs `report` with the key of one of the [`OcwAuthorities`] held by the node, without
+    /// which the report would be rejected.
+    pub(crate) fn sign_report(report: &impl Encode) -> Option<AuthorityProofOf<T>> {
+        let authorities = OcwAuthorities::<T>::get();
+        let block = <frame_system::Pallet<T>>::block_number();
+        let proof = T::AuthorityId::all()
+            .into_iter()
+            .filter(|key| authorities.contains(key))
+            .find_map(|authority| {
+                let signature = authority.sign(&Self::report_message(report, &authority, block))?;
+                Some(AuthorityProof {
+                    authority,
+                    block,
+                    signature,
+                })
+            });
+        if proof.is_none() {
+            sp_tracing::debug!(target: OCW_TARGET, "Not an authority, not reporting");
+        }
+        proof
+    }
+
+    /// Every [`Config::ProbeInterval`] blocks, fetches a random chunk of a random file through the
+    /// public gateway and reports whether it matches the on-chain hash.
+    fn probe_availability(block_number: T::BlockNumber) {
+        let Some(gateway_url) = T::GatewayUrl::get() else {
+            return;
+        };
+        let interval = T::ProbeInterval::get();
+        if interval.is_zero() || !(block_number % interval).is_zero() {
+            return;
+        }
+        let files = FileTrees::<T>::iter_keys().collect::<Vec<_>>();
+        if files.is_empty() {
+            return;
+        }
+        let seed = sp_io::offchain::random_seed();
+        let random = |range: u32, offset: usize| {
+            let bytes: [u8; 4] = seed[offset..offset + 4]
+                .try_into()
+                .expect("4 bytes long; qed");
+            u32::from_le_bytes(bytes) % range
+        };
+        let merkle_root = files[random(files.len() as u32, 0) as usize];
+        let Some(tree) = FileTrees::<T>::get(merkle_root) else {
+            return;
+        };
+        let position = random(tree.pieces(), 4).into();
+        let Some(chunk_hash) = tree.file_chunk_hash_at(position) else {
+            return;
+        };
+        let cid = ipfs::ipfs_get_hash_from_sha256(&chunk_hash);
+        let available = match ipfs::gateway_fetch(gateway_url, &cid) {
+            Ok(content) => sha2_256(&content) == chunk_hash,
+            Err(_) => false,
+        };
+        sp_tracing::info!(target: OCW_TARGET, ?merkle_root, position, %cid, available, "Gateway probe");
+        let Some(proof) = Self::sign_report(&(b"probe", merkle_root, position, available)) else {
+            return;
+        };
+        let call = Call::report_probe {
+            merkle_root,
+            position,
+            available,
+            proof,
+        };
+        if SubmitTransaction::<T, Call<T>>::submit_unsigned_transaction(call.into()).is_err() {
+            sp_tracing::warn!(target: OCW_TARGET, ?merkle_root, %cid, "Could not report the probe");
+        }
+    }
+
+    /// At the start of every audit era, fetches the pieces drawn for it back from the IPFS node
+    /// and reports whether they match their on-chain hashes.
+    fn audit_pieces(block_number: T::BlockNumber) {
+        let period = T::AuditPeriod::get();
+        if period.is_zero() || !(block_number % period).is_zero() {
+            return;
+        }
+        let node_url = T::ipfs_node_url();
+        for (merkle_root, position) in AuditSample::<T>::get() {
+            let Some(chunk_hash) =
+                FileTrees::<T>::get(merkle_root).and_then(|tree| tree.file_chunk_hash_at(position))
+            else {
+                continue;
+            };
+            let cid = ipfs::ipfs_get_hash_from_sha256(&chunk_hash);
+            let passed = match ipfs::ipfs_block_get(&node_url, &cid) {
+                Ok(content) => sha2_256(&content) == chunk_hash,
+                Err(_) => false,
+            };
+            sp_tracing::info!(target: OCW_TARGET, ?merkle_root, position, %cid, passed, "Audit");
+            let Some(proof) = Self::sign_report(&(b"audit", merkle_root, position, passed)) else {
+                return;
+            };
+            let call = Call::report_audit {
+                merkle_root,
+                position,
+                passed,
+                proof,
+            };
+            if SubmitTransaction::<T, Call<T>>::submit_unsigned_transaction(call.into()).is_err() {
+                sp_tracing::warn!(target: OCW_TARGET, ?merkle_root, %cid, "Could not report the audit");
+            }
+        }
+    }
+
+    /// Every [`Config::ServedReportInterval`] blocks, reports on chain the content served through
+    /// the RPC since the last report, for up to [`MAX_SERVED_REPORTS`] files. Files no longer
+    /// registered are not reported.
+    fn report_served_bandwidth(block_number: T::BlockNumber) {
+        let interval = T::ServedReportInterval::get();
+        if interval.is_zero() || !(block_number % interval).is_zero() {
+            return;
+        }
+        let served = StorageValueRef::persistent(SERVED_KEY)
+            .get::<Vec<ServedContent<T::Hash>>>()
+            .ok()
+            .flatten()
+            .unwrap_or_default();
+        let mut reported = Vec::new();
+        for item in served
+            .into_iter()
+            .filter(|item| item.unreported_chunks > 0)
+            .take(MAX_SERVED_REPORTS)
+        {
+            if Pallet::<T>::file_exists(item.merkle_root) {
+                let (bytes, chunks) = (item.unreported_bytes, item.unreported_chunks);
+                let report = (b"bandwidth", item.merkle_root, bytes, chunks);
+                let Some(proof) = Self::sign_report(&report) else {
+                    continue;
+                };
+                let call = Call::report_bandwidth {
+                    merkle_root: item.merkle_root,
+                    bytes,
+                    chunks,
+                    proof,
+                };
+                let submitted =
+                    SubmitTransaction::<T, Call<T>>::submit_unsigned_transaction(call.into());
+                if submitted.is_err() {
+                    sp_tracing::warn!(target: OCW_TARGET, merkle_root = ?item.merkle_root, "Could not report the bandwidth");
+                    continue;
+                }
+            }
+            reported.push(item);
+        }
+        if reported.is_empty() {
+            return;
+        }
+
+        // the RPC may have served more in the meantime, so only what was reported is discounted
+        let result = StorageValueRef::persistent(SERVED_KEY).mutate(
+            |served: Result<Option<Vec<ServedContent<T::Hash>>>, _>| -> Result<_, ()> {
+                let mut served = served.ok().flatten().unwrap_or_default();
+                for item in served.iter_mut() {
+                    if let Some(done) = reported.iter().find(|r| r.merkle_root == item.merkle_root)
+                    {
+                        item.unreported_bytes =
+                            item.unreported_bytes.saturating_sub(done.unreported_bytes);
+                        item.unreported_chunks = item
+                            .unreported_chunks
+                            .saturating_sub(done.unreported_chunks);
+                    }
+                }
+                Ok(served)
+            },
+        );
+        if result.is_err() {
+            sp_tracing::warn!(target: OCW_TARGET, "Could not update the served bandwidth counters");
+        }
+    }
+
+    /// Every [`Config::RepinInterval`] blocks, pins again on the IPFS node the chunks of the next
+    /// [`Config::MaxRepinsPerRun`] files and reports the ones fully pinned. Content lost by the
+    /// node, e.g. after a reinstall, gets fetched back from the network.
+    fn repin_files(block_number: T::BlockNumber) {
+        let interval = T::RepinInterval::get();
+        if interval.is_zero() || !(block_number % interval).is_zero() {
+            return;
+        }
+        // cluster peers recover their pins on their own
+        if T::IpfsClusterUrl::get().is_some() {
+            return;
+        }
+        let mut cursor = StorageValueRef::persistent(REPIN_CURSOR_KEY);
+        let limit = T::MaxRepinsPerRun::get() as usize;
+        let window = match cursor.get::<Vec<u8>>() {
+            Ok(Some(last)) => FileTrees::<T>::iter_from(last)
+                .take(limit)
+                .collect::<Vec<_>>(),
+            _ => FileTrees::<T>::iter().take(limit).collect::<Vec<_>>(),
+        };
+        // start over once the last file is reached
+        match window.last() {
+            Some((merkle_root, _)) if window.len() == limit => {
+                cursor.set(&FileTrees::<T>::hashed_key_for(merkle_root))
+            }
+            _ => cursor.clear(),
+        }
+
+        let node_url = T::ipfs_node_url();
+        for (merkle_root, tree) in window {
+            let pinned = (0..tree.pieces().into())
+                .filter_map(|position| tree.file_chunk_hash_at(position))
+                .all(|chunk_hash| {
+                    let cid = ipfs::ipfs_get_hash_from_sha256(&chunk_hash);
+                    ipfs::ipfs_pin(&node_url, &cid).is_ok()
+                });
+            if !pinned {
+                sp_tracing::warn!(target: OCW_TARGET, ?merkle_root, "Could not pin again");
+                continue;
+            }
+            let Some(proof) = Self::sign_report(&(b"repin", merkle_root)) else {
+                continue;
+            };
+            let call = Call::report_repin { merkle_root, proof };
+            if SubmitTransaction::<T, Call<T>>::submit_unsigned_transaction(call.into()).is_err() {
+                sp_tracing::warn!(target: OCW_TARGET, ?merkle_root, "Could not report the repin");
+            }
+        }
+    }
+
+    /// Rebuilds the trees of the files pending to be rechunked out of their indexed content and
+    /// submits them. The content is indexed locally under the new merkle root as well, so it can
+    /// be pushed to IPFS once the new tree is registered. Requests that cannot be processed are
+    /// rejected, as they would otherwise take up the window of requests handled per run forever.
+    fn rechunk_files() {
+        let params = Self::chunking_params();
+        // the new roots are pushed to `BlockUploads`, which is bounded
+        let pending = PendingRechunks::<T>::iter_keys().take(T::MaxUploadsPerBlock::get() as usize);
+        for merkle_root in pending {
+            let key = Self::derived_key(&merkle_root);
+            let rechunked = match StorageValueRef::persistent(&key).get::<IndexingData>() {
+                // checked when requested, but the content might not match the tree
+                Ok(Some(data))
+                    if file_merkle_tree::pieces_for(data.content.len() as u64, &params)
+                        > MAX_PIECES =>
+                {
+                    sp_tracing::warn!(target: OCW_TARGET, ?merkle_root, "Too many pieces to rechunk");
+                    None
+                }
+                Ok(Some(data)) => Self::rechunk(merkle_root, data.content, &params),
+                _ => {
+                    sp_tracing::warn!(target: OCW_TARGET, ?merkle_root, "No indexed content to rechunk");
+                    None
+                }
+            };
+            let call = match rechunked {
+                Some(merkle_tree) => {
+                    let Some(proof) = Self::sign_report(&(b"rechunk", merkle_root, &merkle_tree))
+                    else {
+                        return;
+                    };
+                    Call::report_rechunk {
+                        merkle_root,
+                        merkle_tree,
+                        proof,
+                    }
+                }
+                None => {
+                    let Some(proof) = Self::sign_report(&(b"reject_rechunk", merkle_root)) else {
+                        return;
+                    };
+                    Call::reject_rechunk { merkle_root, proof }
+                }
+            };
+            if SubmitTransaction::<T, Call<T>>::submit_unsigned_transaction(call.into()).is_err() {
+                sp_tracing::warn!(target: OCW_TARGET, ?merkle_root, "Could not report the rechunk");
+            }
+        }
+    }
+
+    /// Builds the new tree of the file under `merkle_root` out of its `content`, indexing the
+    /// content under the new merkle root. `None` if the tree cannot replace the current one.
+    fn rechunk(
+        merkle_root: T::Hash,
+        content: Vec<u8>,
+        params: &ChunkingParams,
+    ) -> Option<FileMerkleTree> {
+        let merkle_tree = FileMerkleTree::new(&content, params);
+        let new_root = T::Hash::decode(&mut merkle_tree.merkle_root()).ok()?;
+        let valid = matches!(
+            FileTrees::<T>::get(merkle_root),
+            Some(old_tree) if Self::is_valid_rechunk(&old_tree, &merkle_tree)
+        );
+        if !valid || FileOwners::<T>::contains_key(new_root) {
+            sp_tracing::warn!(target: OCW_TARGET, ?merkle_root, ?new_root, "Cannot replace the tree");
+            return None;
+        }
+        let data = IndexingData {
+            content,
+            chunk_size: merkle_tree.chunk_size() as u32,
+        };
+        StorageValueRef::persistent(&Self::derived_key(&new_root)).set(&data);
+        Some(merkle_tree)
+    }
+
+    /// Rebuilds the trees of the files uploaded under a claimed merkle root out of their indexed
+    /// content, confirming the claims that match and rejecting the rest, along with those that
+    /// cannot be verified, which would otherwise hold their deposits and the window of claims
+    /// checked per run forever.
+    fn verify_root_claims() {
+        let params = Self::chunking_params();
+        // confirmed roots are pushed to `BlockUploads`, which is bounded
+        let claims = RootClaims::<T>::iter_keys().take(T::MaxUploadsPerBlock::get() as usize);
+        for merkle_root in claims {
+            let key = Self::derived_key(&merkle_root);
+            let confirmed = match StorageValueRef::persistent(&key).get::<IndexingData>() {
+                // checked on upload, but the chunking parameters might have changed since
+                Ok(Some(data))
+                    if file_merkle_tree::pieces_for(data.content.len() as u64, &params)
+                        > MAX_PIECES =>
+                {
+                    sp_tracing::warn!(target: OCW_TARGET, ?merkle_root, "Too many pieces to verify");
+                    None
+                }
+                Ok(Some(data)) => {
+                    let merkle_tree = FileMerkleTree::new(&data.content, &params);
+                    if merkle_tree.merkle_root() == merkle_root.as_ref() {
+                        Some((merkle_tree, sha2_256(&data.content)))
+                    } else {
+                        sp_tracing::warn!(target: OCW_TARGET, ?merkle_root, "Wrong merkle root claimed");
+                        None
+                    }
+                }
+                _ => {
+                    sp_tracing::warn!(target: OCW_TARGET, ?merkle_root, "No indexed content to verify");
+                    None
+                }
+            };
+            let call = match confirmed {
+                Some((merkle_tree, sha256)) => {
+                    let report = (b"confirm", merkle_root, &merkle_tree, sha256);
+                    let Some(proof) = Self::sign_report(&report) else {
+                        return;
+                    };
+                    Call::confirm_root {
+                        merkle_root,
+                        merkle_tree,
+                        sha256,
+                        proof,
+                    }
+                }
+                None => {
+                    let Some(proof) = Self::sign_report(&(b"reject", merkle_root)) else {
+                        return;
+                    };
+                    Call::reject_root { merkle_root, proof }
+                }
+            };
+            if SubmitTransaction::<T, Call<T>>::submit_unsigned_transaction(call.into()).is_err() {
+                sp_tracing::warn!(target: OCW_TARGET, ?merkle_root, "Could not report the check");
+            }
+        }
+    }
+
+    fn enqueue_block_uploads(block_number: T::BlockNumber) {
+        let uploads = BlockUploads::<T>::get();
+        if uploads.is_empty() {
+            sp_tracing::info!(target: OCW_TARGET, block = ?block_number, "Nothing to enqueue");
+            return;
+        }
+        let result = StorageValueRef::persistent(QUEUE_KEY).mutate(
+            |queue: Result<Option<Queue<T>>, _>| -> Result<Queue<T>, ()> {
+                let mut queue = queue.ok().flatten().unwrap_or_default();
+                for merkle_root in uploads.iter() {
+                    // blocks might be imported more than once
+                    if !queue.iter().any(|item| &item.merkle_root == merkle_root) {
+                        queue.push(QueuedUpload {
+                            merkle_root: *merkle_root,
+                            next_chunk: 0,
+                        });
+                    }
+                }
+                Ok(queue)
+            },
+        );
+        if result.is_err() {
+            sp_tracing::warn!(target: OCW_TARGET, block = ?block_number, "Could not enqueue uploads");
+        }
+    }
+
+    fn process_queue() {
+        let queue = StorageValueRef::persistent(QUEUE_KEY);
+        let pending = queue.get::<Queue<T>>().ok().flatten().unwrap_or_default();
+        let mut budget = T::MaxChunksPerOcwRun::get();
+        let mut processed = Vec::new();
+        for item in pending {
+            if budget == 0 {
+                break;
+            }
+            let merkle_root = item.merkle_root;
+            let from = item.next_chunk;
+            let next_chunk = match Self::upload_chunks(&merkle_root, from, &mut budget) {
+                Progress::Done => {
+                    Self::set_upload_retries(&merkle_root, 0);
+                    None
+                }
+                Progress::Paused(next_chunk) => {
+                    Self::set_upload_retries(&merkle_root, 0);
+                    Some(next_chunk)
+                }
+                Progress::Failed(next_chunk) => {
+                    // only failures with no progress at all in between count
+                    let retries = if next_chunk > from {
+                        1
+                    } else {
+                        Self::upload_retries(&merkle_root).saturating_add(1)
+                    };
+                    if retries < MAX_UPLOAD_RETRIES {
+                        Self::set_upload_retries(&merkle_root, retries);
+                        Some(next_chunk)
+                    } else {
+                        Self::set_upload_retries(&merkle_root, 0);
+                        Self::give_up(DeadLetter {
+                            merkle_root,
+                            next_chunk,
+                            retries,
+                            reason: DeadLetterReason::TooManyRetries,
+                        });
+                        None
+                    }
+                }
+                Progress::MissingContent => {
+                    let retries = Self::upload_retries(&merkle_root);
+                    Self::set_upload_retries(&merkle_root, 0);
+                    Self::give_up(DeadLetter {
+                        merkle_root,
+                        next_chunk: from,
+                        retries,
+                        reason: DeadLetterReason::MissingContent,
+                    });
+                    None
+                }
+            };
+            processed.push((merkle_root, next_chunk));
+        }
+        if processed.is_empty() {
+            return;
+        }
+
+        // new files might have been enqueued in the meantime, so only touch the processed ones
+        let result = queue.mutate(|queue: Result<Option<Queue<T>>, _>| -> Result<Queue<T>, ()> {
+            let mut queue = queue.ok().flatten().unwrap_or_default();
+            for (merkle_root, next_chunk) in processed.iter() {
+                match next_chunk {
+                    Some(next_chunk) => queue
+                        .iter_mut()
+                        .filter(|item| &item.merkle_root == merkle_root)
+                        .for_each(|item| item.next_chunk = *next_chunk),
+                    None => queue.retain(|item| &item.merkle_root != merkle_root),
+                }
+            }
+            Ok(queue)
+        });
+        if result.is_err() {
+            sp_tracing::warn!(target: OCW_TARGET, "Could not update the upload queue");
+        }
+    }
+
+    /// Logs the CIDs of the chunks [`Self::process_queue`] would push to IPFS in this run, and the
+    /// CID of the whole file once complete, leaving the queue untouched.
+    fn simulate_queue() {
+        let pending = StorageValueRef::persistent(QUEUE_KEY)
+            .get::<Queue<T>>()
+            .ok()
+            .flatten()
+            .unwrap_or_default();
+        let mut budget = T::MaxChunksPerOcwRun::get();
+        for item in pending {
+            if budget == 0 {
+                break;
+            }
+            let merkle_root = item.merkle_root;
+            let key = Self::derived_key(&merkle_root);
+            let Ok(Some(data)) = StorageValueRef::persistent(&key).get::<IndexingData>() else {
+                sp_tracing::warn!(target: OCW_TARGET, ?merkle_root, "No indexed content for file, would give up");
+                continue;
+            };
+            let chunks = data.content.chunks(data.chunk_size as usize);
+            let total = chunks.len() as u32;
+            let mut done = item.next_chunk;
+            for chunk in chunks.skip(done as usize) {
+                if budget == 0 {
+                    break;
+                }
+                let cid = ipfs::ipfs_get_hash_from_sha256(&sha2_256(chunk));
+                sp_tracing::info!(target: OCW_TARGET, ?merkle_root, position = done, %cid, bytes = chunk.len(), "Would upload chunk");
+                budget -= 1;
+                done += 1;
+            }
+            if done == total {
+                if let Some(chunks) = Self::ipfs_file_chunks(&data) {
+                    let cid = ipfs::unixfs_file_cid(&chunks);
+                    sp_tracing::info!(target: OCW_TARGET, ?merkle_root, %cid, "Would make file available on IPFS");
+                }
+            }
+        }
+    }
+
+    /// Number of runs in a row the chunks of a queued file failed to upload in.
+    fn upload_retries(merkle_root: &T::Hash) -> u32 {
+        let key = [UPLOAD_RETRIES_KEY, &merkle_root.encode()].concat();
+        StorageValueRef::persistent(&key)
+            .get::<u32>()
+            .ok()
+            .flatten()
+            .unwrap_or_default()
+    }
+
+    fn set_upload_retries(merkle_root: &T::Hash, retries: u32) {
+        let key = [UPLOAD_RETRIES_KEY, &merkle_root.encode()].concat();
+        let mut value = StorageValueRef::persistent(&key);
+        match retries {
+            0 => value.clear(),
+            retries => value.set(&retries),
+        }
+    }
+
+    /// Records a file the offchain worker will no longer try to push to IPFS, so that node
+    /// operators can find out why through `trustless_file_server_local_queue`.
+    fn give_up(letter: DeadLetter<T::Hash>) {
+        sp_tracing::warn!(target: OCW_TARGET, merkle_root = ?letter.merkle_root, reason = ?letter.reason, "Giving up on file");
+        let result = StorageValueRef::persistent(DEAD_LETTERS_KEY).mutate(
+            |letters: Result<Option<Vec<DeadLetter<T::Hash>>>, _>| -> Result<_, ()> {
+                let mut letters = letters.ok().flatten().unwrap_or_default();
+                letters.retain(|other| other.merkle_root != letter.merkle_root);
+                letters.push(letter);
+                let excess = letters.len().saturating_sub(MAX_DEAD_LETTERS);
+                letters.drain(..excess);
+                Ok(letters)
+            },
+        );
+        if result.is_err() {
+            sp_tracing::warn!(target: OCW_TARGET, "Could not record the dead letter of a file");
+        }
+    }
+
+    /// Uploads the chunks of a file starting at `from`, consuming `budget`.
+    fn upload_chunks(merkle_root: &T::Hash, from: u32, budget: &mut u32) -> Progress {
+        let key = Self::derived_key(merkle_root);
+        let Ok(Some(data)) = StorageValueRef::persistent(&key).get::<IndexingData>() else {
+            sp_tracing::warn!(target: OCW_TARGET, ?merkle_root, "No indexed content for file");
+            return Progress::MissingContent;
+        };
+        let chunks = data.content.chunks(data.chunk_size as usize);
+        let total = chunks.len() as u32;
+        let _span =
+            sp_tracing::info_span!(target: OCW_TARGET, "upload", ?merkle_root, pieces = total)
+                .entered();
+        sp_tracing::info!(target: OCW_TARGET, ?merkle_root, pieces = total, from, "Found storage for file");
+        let interval = T::ProgressReportInterval::get().max(1);
+        let mut done = from;
+        for chunk in chunks.skip(from as usize) {
+            if *budget == 0 {
+                return Progress::Paused(done);
+            }
+            let cid = ipfs::ipfs_get_hash_from_sha256(&sha2_256(chunk));
+            let uploaded = match T::IpfsClusterUrl::get() {
+                Some(cluster_url) => {
+                    Self::upload_to_cluster(cluster_url, merkle_root, done.into(), chunk)
+                }
+                None => ipfs::ipfs_upload(&T::ipfs_node_url(), chunk).is_ok(),
+            };
+            if !uploaded {
+                sp_tracing::warn!(target: OCW_TARGET, ?merkle_root, position = done, "Could not upload chunk");
+                return Progress::Failed(done);
+            }
+            Self::announce(&cid);
+            Self::pin_remotely(merkle_root, done.into(), &cid);
+            *budget -= 1;
+            done += 1;
+            if done % interval == 0 || done == total {
+                Self::submit_upload_progress(merkle_root, done, total);
+            }
+        }
+        Self::upload_file_node(merkle_root, &data);
+        Progress::Done
+    }
+
+    /// Uploads the node linking the chunks of a file split as `ipfs add` would split it, so that
+    /// the whole file can be fetched from IPFS by the CID `ipfs add` gives it.
+    fn upload_file_node(merkle_root: &T::Hash, data: &IndexingData) {
+        let Some(chunks) = Self::ipfs_file_chunks(data) else {
+            return;
+        };
+        let cid = ipfs::unixfs_file_cid(&chunks);
+        if chunks.len() > 1 {
+            let node = ipfs::unixfs_file_node(&chunks);
+            if ipfs::ipfs_upload_node(&T::ipfs_node_url(), &node).is_err() {
+                sp_tracing::warn!(target: OCW_TARGET, ?merkle_root, %cid, "Could not upload the file node");
+                return;
+            }
+            Self::announce(&cid);
+        }
+        sp_tracing::info!(target: OCW_TARGET, ?merkle_root, %cid, "File available on IPFS");
+    }
+
+    /// Hash and size of every chunk of a file, if it is split as `ipfs add` would split it.
+    fn ipfs_file_chunks(data: &IndexingData) -> Option<Vec<([u8; 32], u64)>> {
+        if T::IpfsChunkSize::get() != Some(data.chunk_size) {
+            return None;
+        }
+        let chunks = data
+            .content
+            .chunks(data.chunk_size as usize)
+            .map(|chunk| (sha2_256(chunk), chunk.len() as u64))
+            .collect();
+        Some(chunks)
+    }
+
+    /// Adds a chunk through the IPFS Cluster and reports the peers it was allocated to.
+    fn upload_to_cluster(
+        cluster_url: &str,
+        merkle_root: &T::Hash,
+        position: u64,
+        chunk: &[u8],
+    ) -> bool {
+        let replication = T::ClusterReplicationFactor::get();
+        let Ok((cid, peers)) = ipfs::cluster_add(cluster_url, chunk, replication) else {
+            return false;
+        };
+        sp_tracing::info!(target: OCW_TARGET, ?merkle_root, position, %cid, peers = peers.len(), "Chunk allocated to cluster peers");
+        let allocations: AllocationsOf<T> = peers
+            .into_iter()
+            .filter_map(|peer| ClusterPeerId::try_from(peer.into_bytes()).ok())
+            .take(replication as usize)
+            .collect::<Vec<_>>()
+            .try_into()
+            .expect("at most `replication` peers were taken; qed");
+        let report = (b"allocations", merkle_root, position, &allocations);
+        let Some(proof) = Self::sign_report(&report) else {
+            return true;
+        };
+        let call = Call::report_chunk_allocations {
+            merkle_root: *merkle_root,
+            position,
+            allocations,
+            proof,
+        };
+        if SubmitTransaction::<T, Call<T>>::submit_unsigned_transaction(call.into()).is_err() {
+            sp_tracing::warn!(target: OCW_TARGET, ?merkle_root, position, %cid, "Could not report the allocations");
+        }
+        true
+    }
+
+    /// Announces the IPFS node as a provider of `cid` in the DHT, so that other peers of the
+    /// network can discover the content.
+    fn announce(cid: &str) {
+        // cluster peers announce whatever they pin on their own
+        if !T::AnnounceToDht::get() || T::IpfsClusterUrl::get().is_some() {
+            return;
+        }
+        if ipfs::ipfs_provide(&T::ipfs_node_url(), cid).is_err() {
+            sp_tracing::warn!(target: OCW_TARGET, %cid, "Could not announce to the DHT");
+        }
+    }
+
+    /// Pins a chunk on the configured remote pinning service, if any, and records the returned
+    /// request id under its CID.
+    fn pin_remotely(merkle_root: &T::Hash, position: u64, cid: &str) {
+        let Some(endpoint) = T::PinningServiceUrl::get() else {
+            return;
+        };
+        // set raw by the operator through `offchain_localStorageSet`, hence not SCALE encoded
+        let Some(token) = local_storage_get(StorageKind::PERSISTENT, PINNING_TOKEN_KEY) else {
+            sp_tracing::warn!(target: OCW_TARGET, "A pinning service is configured but no token was set");
+            return;
+        };
+        let token = String::from_utf8_lossy(&token);
+        let name = format!("{:?}-{}", merkle_root, position);
+        match ipfs::pinning_service_pin(endpoint, &token, cid, &name) {
+            Ok(request_id) => {
+                let key = [PIN_REQUEST_KEY, cid.as_bytes()].concat();
+                StorageValueRef::persistent(&key).set(&request_id.into_bytes());
+            }
+            Err(_) => {
+                sp_tracing::warn!(target: OCW_TARGET, ?merkle_root, position, %cid, "Could not pin remotely")
+            }
+        }
+    }
+
+    fn submit_upload_progress(merkle_root: &T::Hash, done: u32, total: u32) {
+        let Some(proof) = Self::sign_report(&(b"progress", merkle_root, done, total)) else {
+            return;
+        };
+        let call = Call::report_upload_progress {
+            merkle_root: *merkle_root,
+            done,
+            total,
+            proof,
+        };
+        if SubmitTransaction::<T, Call<T>>::submit_unsigned_transaction(call.into()).is_err() {
+            sp_tracing::warn!(target: OCW_TARGET, ?merkle_root, done, total, "Could not report the progress");
+        }
+    }
+}
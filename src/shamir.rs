@@ -0,0 +1,149 @@
+//! Shamir's secret sharing over GF(2^8), used to escrow the keys of time-locked files among a
+//! committee.
+//!
+//! Every byte of the secret is shared independently: it is the constant term of a random
+//! polynomial of degree `threshold - 1`, and the share of the holder at `x` holds the value of
+//! that polynomial at `x`. Any `threshold` shares rebuild the secret through Lagrange
+//! interpolation at zero, while fewer of them reveal nothing about it.
+
+use sp_std::vec::Vec;
+
+/// Reducing polynomial of the field, the same AES uses: x^8 + x^4 + x^3 + x + 1.
+const REDUCING_POLYNOMIAL: u16 = 0x11b;
+
+fn mul(a: u8, b: u8) -> u8 {
+    let (mut a, mut b, mut product) = (a as u16, b, 0u16);
+    while b != 0 {
+        if b & 1 != 0 {
+            product ^= a;
+        }
+        a <<= 1;
+        if a & 0x100 != 0 {
+            a ^= REDUCING_POLYNOMIAL;
+        }
+        b >>= 1;
+    }
+    product as u8
+}
+
+/// Multiplicative inverse of a non-zero element, as `a^254`.
+fn inv(a: u8) -> u8 {
+    let (mut result, mut base, mut exponent) = (1u8, a, 254u8);
+    while exponent != 0 {
+        if exponent & 1 != 0 {
+            result = mul(result, base);
+        }
+        base = mul(base, base);
+        exponent >>= 1;
+    }
+    result
+}
+
+/// Splits `secret` in `count` shares, any `threshold` of which rebuild it. The share of the
+/// `i`-th holder is evaluated at `x = i + 1`. `random` must yield uniformly random bytes, as it
+/// provides the coefficients of the polynomials.
+pub fn split(
+    secret: &[u8],
+    threshold: u8,
+    count: u8,
+    mut random: impl FnMut() -> u8,
+) -> Vec<Vec<u8>> {
+    let polynomials: Vec<Vec<u8>> = secret
+        .iter()
+        .map(|byte| {
+            let mut coefficients = Vec::with_capacity(threshold as usize);
+            coefficients.push(*byte);
+            coefficients.extend((1..threshold).map(|_| random()));
+            coefficients
+        })
+        .collect();
+    (1..=count)
+        .map(|x| {
+            polynomials
+                .iter()
+                .map(|coefficients| {
+                    // Horner's method
+                    coefficients
+                        .iter()
+                        .rev()
+                        .fold(0u8, |value, coefficient| mul(value, x) ^ coefficient)
+                })
+                .collect()
+        })
+        .collect()
+}
+
+/// Rebuilds the secret out of `(x, share)` pairs. Returns `None` if there are no shares, they
+/// have different lengths, or their `x` are zero or repeated. Whether the result is the actual
+/// secret depends on there being at least `threshold` valid shares, so callers are expected to
+/// check it against a commitment.
+pub fn combine(shares: &[(u8, &[u8])]) -> Option<Vec<u8>> {
+    let (_, first) = shares.first()?;
+    for (i, (x, share)) in shares.iter().enumerate() {
+        if *x == 0 || share.len() != first.len() || shares[..i].iter().any(|(other, _)| other == x)
+        {
+            return None;
+        }
+    }
+    // Lagrange basis polynomials evaluated at zero: the product of x_j / (x_j - x_i), where
+    // subtracting is xor-ing in GF(2^8)
+    let basis: Vec<u8> = shares
+        .iter()
+        .map(|(xi, _)| {
+            shares
+                .iter()
+                .filter(|(xj, _)| xj != xi)
+                .fold(1u8, |basis, (xj, _)| mul(basis, mul(*xj, inv(xj ^ xi))))
+        })
+        .collect();
+    Some(
+        (0..first.len())
+            .map(|position| {
+                shares
+                    .iter()
+                    .zip(basis.iter())
+                    .fold(0u8, |secret, ((_, share), basis)| secret ^ mul(share[position], *basis))
+            })
+            .collect(),
+    )
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_inverses_should_multiply_to_one() {
+        for a in 1..=255u8 {
+            assert_eq!(mul(a, inv(a)), 1);
+        }
+        assert_eq!(mul(0x57, 0x83), 0xc1);
+    }
+
+    #[test]
+    fn test_any_threshold_shares_should_rebuild_the_secret() {
+        let mut seed = 7u8;
+        let random = || {
+            seed = seed.wrapping_mul(31).wrapping_add(17);
+            seed
+        };
+        let secret = b"correct horse battery staple";
+        let shares = split(secret, 3, 5, random);
+        assert_eq!(shares.len(), 5);
+
+        let pick = |xs: &[u8]| -> Vec<(u8, &[u8])> {
+            xs.iter()
+                .map(|x| (*x, shares[*x as usize - 1].as_slice()))
+                .collect()
+        };
+        assert_eq!(combine(&pick(&[1, 2, 3])).unwrap(), secret);
+        assert_eq!(combine(&pick(&[5, 1, 4])).unwrap(), secret);
+        assert_eq!(combine(&pick(&[2, 3, 4, 5])).unwrap(), secret);
+        assert_ne!(combine(&pick(&[1, 2])).unwrap(), secret);
+
+        assert_eq!(combine(&[]), None);
+        assert_eq!(combine(&pick(&[1, 1, 2])), None);
+        assert_eq!(combine(&[(0, secret.as_slice())]), None);
+        assert_eq!(combine(&[(1, secret.as_slice()), (2, &secret[1..])]), None);
+    }
+}
@@ -0,0 +1,82 @@
+use codec::{Decode, Encode};
+use frame_support::pallet_prelude::*;
+use frame_support::sp_runtime::traits::{DispatchInfoOf, SignedExtension};
+use frame_support::traits::IsSubType;
+use scale_info::TypeInfo;
+use sp_std::marker::PhantomData;
+
+use crate::{Call, Config};
+
+/// Signed extension rejecting `upload_file`, `sponsor_upload` and `upload_files` transactions
+/// with a payload exceeding [`Config::MaxFileSize`].
+///
+/// The call itself performs the same check at dispatch, but by then the oversized transaction
+/// has already been admitted to the pool and paid for block space. Including this extension in
+/// the runtime's `SignedExtra` rejects them during validation instead.
+#[derive(Encode, Decode, Clone, Eq, PartialEq, TypeInfo)]
+#[scale_info(skip_type_params(T))]
+pub struct CheckUploadSize<T: Config + Send + Sync>(PhantomData<T>);
+
+impl<T: Config + Send + Sync> CheckUploadSize<T> {
+    pub fn new() -> Self {
+        Self(PhantomData)
+    }
+}
+
+impl<T: Config + Send + Sync> Default for CheckUploadSize<T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T: Config + Send + Sync> sp_std::fmt::Debug for CheckUploadSize<T> {
+    fn fmt(&self, f: &mut sp_std::fmt::Formatter) -> sp_std::fmt::Result {
+        write!(f, "CheckUploadSize")
+    }
+}
+
+impl<T: Config + Send + Sync> SignedExtension for CheckUploadSize<T>
+where
+    <T as frame_system::Config>::RuntimeCall: IsSubType<Call<T>>,
+{
+    const IDENTIFIER: &'static str = "CheckUploadSize";
+    type AccountId = T::AccountId;
+    type Call = <T as frame_system::Config>::RuntimeCall;
+    type AdditionalSigned = ();
+    type Pre = ();
+
+    fn additional_signed(&self) -> Result<(), TransactionValidityError> {
+        Ok(())
+    }
+
+    fn validate(
+        &self,
+        _who: &Self::AccountId,
+        call: &Self::Call,
+        _info: &DispatchInfoOf<Self::Call>,
+        _len: usize,
+    ) -> TransactionValidity {
+        let too_large = |file_bytes: &Vec<u8>| file_bytes.len() > T::MaxFileSize::get() as usize;
+        let exceeded = match call.is_sub_type() {
+            Some(
+                Call::upload_file { file_bytes, .. } | Call::sponsor_upload { file_bytes, .. },
+            ) => too_large(file_bytes),
+            Some(Call::upload_files { files }) => files.iter().any(too_large),
+            _ => false,
+        };
+        if exceeded {
+            return InvalidTransaction::ExhaustsResources.into();
+        }
+        Ok(ValidTransaction::default())
+    }
+
+    fn pre_dispatch(
+        self,
+        who: &Self::AccountId,
+        call: &Self::Call,
+        info: &DispatchInfoOf<Self::Call>,
+        len: usize,
+    ) -> Result<Self::Pre, TransactionValidityError> {
+        self.validate(who, call, info, len).map(|_| ())
+    }
+}
@@ -0,0 +1,157 @@
+//! Test vectors of the merkle trees built by the pallet, for authors of clients in other
+//! languages to check their verifiers against the exact tree layout used here.
+//!
+//! Every vector holds the content of a file of the given size, the hashes of its chunks, the
+//! nodes of its tree and the proof of every piece, all of them hex encoded once serialized with
+//! [`to_json`]. Contents are deterministic, so that vectors can be generated again anywhere.
+use lite_json::{JsonValue, NumberValue, Serialize};
+use sp_std::vec::Vec;
+
+use crate::file_merkle_tree::{pieces_for, ChunkingParams, FileMerkleTree, ProofNode, MAX_PIECES};
+
+/// Merkle tree of a file along with every proof of it, as built with the default
+/// [`ChunkingParams`].
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct TestVector {
+    pub content: Vec<u8>,
+    pub chunk_size: u32,
+    /// Hash of every chunk as served, the last one not being zero-padded.
+    pub chunk_hashes: Vec<ProofNode>,
+    /// Every node of the tree, level by level, starting with the leaves and ending with the
+    /// merkle root. The leaf of the last chunk is the hash of the chunk zero-padded up to
+    /// `chunk_size`, and leaves are padded with zero hashes up to a power of two.
+    pub nodes: Vec<Vec<u8>>,
+    pub merkle_root: Vec<u8>,
+    /// Merkle proof of every piece, by position.
+    pub proofs: Vec<Vec<ProofNode>>,
+}
+
+impl TestVector {
+    /// Builds the vector of a file of `size` bytes.
+    ///
+    /// Panics if the file is split in more than [`MAX_PIECES`] pieces, i.e. if it is bigger than
+    /// 32KB with the default [`ChunkingParams`].
+    pub fn new(size: usize) -> Self {
+        let params = ChunkingParams::default();
+        assert!(pieces_for(size as u64, &params) <= MAX_PIECES, "too many pieces");
+        let content = content(size);
+        let tree = FileMerkleTree::new(&content, &params);
+        let pieces = tree.pieces() as u64;
+        Self {
+            chunk_size: tree.chunk_size() as u32,
+            chunk_hashes: (0..pieces)
+                .filter_map(|position| tree.file_chunk_hash_at(position))
+                .collect(),
+            nodes: tree.nodes(),
+            merkle_root: tree.merkle_root().to_vec(),
+            proofs: (0..pieces)
+                .filter_map(|position| tree.merkle_proof(position))
+                .map(|proof| proof.into_inner())
+                .collect(),
+            content,
+        }
+    }
+
+    /// The vector as a JSON object, byte strings being hex encoded.
+    pub fn to_json(&self) -> JsonValue {
+        let field =
+            |name: &str, value| -> (Vec<char>, JsonValue) { (name.chars().collect(), value) };
+        let hashes =
+            |hashes: &[ProofNode]| JsonValue::Array(hashes.iter().map(|hash| hex(hash)).collect());
+        JsonValue::Object(vec![
+            field("size", number(self.content.len() as u64)),
+            field("content", hex(&self.content)),
+            field("chunk_size", number(self.chunk_size as u64)),
+            field("chunk_hashes", hashes(&self.chunk_hashes)),
+            field("nodes", JsonValue::Array(self.nodes.iter().map(|node| hex(node)).collect())),
+            field("merkle_root", hex(&self.merkle_root)),
+            field(
+                "proofs",
+                JsonValue::Array(self.proofs.iter().map(|proof| hashes(proof)).collect()),
+            ),
+        ])
+    }
+}
+
+/// Content of the vector of a file of `size` bytes: the byte at position `i` is `i % 251`, so
+/// that no two chunks of the same size are equal.
+pub fn content(size: usize) -> Vec<u8> {
+    (0..size).map(|i| (i % 251) as u8).collect()
+}
+
+/// Builds the vector of a file of every size in `sizes`. See [`TestVector::new`].
+pub fn generate(sizes: &[usize]) -> Vec<TestVector> {
+    sizes.iter().map(|size| TestVector::new(*size)).collect()
+}
+
+/// Serializes `vectors` as a JSON array, indented by two spaces.
+pub fn to_json(vectors: &[TestVector]) -> String {
+    let json = JsonValue::Array(vectors.iter().map(TestVector::to_json).collect());
+    String::from_utf8(json.format(2)).expect("JSON is always valid UTF-8; qed")
+}
+
+fn hex(data: &[u8]) -> JsonValue {
+    JsonValue::String(hex_string(data).chars().collect())
+}
+
+fn hex_string(data: &[u8]) -> String {
+    data.iter().map(|byte| format!("{:02x}", byte)).collect()
+}
+
+fn number(value: u64) -> JsonValue {
+    JsonValue::Number(NumberValue {
+        integer: value,
+        fraction: 0,
+        fraction_length: 0,
+        exponent: 0,
+        negative: false,
+    })
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::file_merkle_tree::verify_proof;
+    use sp_io::hashing::sha2_256;
+
+    #[test]
+    fn test_vectors_should_verify() {
+        for vector in generate(&[1, 1024, 1500, 32 * 1024]) {
+            assert_eq!(vector.nodes.last(), Some(&vector.merkle_root));
+            let chunks = vector.content.chunks(vector.chunk_size as usize);
+            assert_eq!(chunks.len(), vector.proofs.len());
+            for (position, chunk) in chunks.enumerate() {
+                assert_eq!(vector.chunk_hashes[position], sha2_256(chunk));
+                let mut leaf = chunk.to_vec();
+                leaf.resize(vector.chunk_size as usize, 0);
+                assert!(verify_proof(
+                    &vector.merkle_root,
+                    position as u64,
+                    &sha2_256(&leaf),
+                    &vector.proofs[position]
+                ));
+            }
+        }
+    }
+
+    #[test]
+    fn test_vectors_to_json() {
+        let vectors = generate(&[1500]);
+        let json = to_json(&vectors);
+        let JsonValue::Array(parsed) = lite_json::parse_json(&json).ok().unwrap() else {
+            panic!("not an array");
+        };
+        let JsonValue::Object(fields) = &parsed[0] else {
+            panic!("not an object");
+        };
+        let merkle_root = fields
+            .iter()
+            .find(|(name, _)| name.iter().collect::<String>() == "merkle_root")
+            .and_then(|(_, value)| match value {
+                JsonValue::String(chars) => Some(chars.iter().collect::<String>()),
+                _ => None,
+            });
+        assert_eq!(merkle_root, Some(hex_string(&vectors[0].merkle_root)));
+        assert_eq!(generate(&[1500]), vectors);
+    }
+}
@@ -1,10 +1,57 @@
-use codec::Decode;
-use frame_support::assert_ok;
+use codec::{Decode, Encode};
+use frame_support::traits::Hooks;
+use frame_support::{assert_noop, assert_ok, weights::Weight, BoundedVec};
 use frame_system::ensure_signed;
+use parking_lot::RwLock;
+use sp_core::offchain::testing::{OffchainState, PendingRequest, PoolState};
+use sp_core::offchain::StorageKind;
 use sp_io::hashing::sha2_256;
-use sp_runtime::testing::H256;
+use sp_runtime::offchain::storage::StorageValueRef;
+use sp_runtime::testing::{UintAuthorityId, H256};
 
+use crate::file_merkle_tree::{ChunkingParams, FileMerkleTree};
+use crate::ipfs;
+use crate::migrations;
 use crate::mock::*;
+use crate::offchain::MAX_UPLOAD_RETRIES;
+use crate::{
+    shamir, AuditReport, BandwidthRecord, Bundles, ChunkAllocations, DeadLetter, DeadLetterReason,
+    Deposit, EncryptedShareOf, Error, Event, ExternalFile, FileAttestation, FileChange,
+    FileMetadataOf, FileOwners, FileSummary, FileTrees, HistoryEntry, KeyOf, License, NameOf,
+    PendingRechunks, QueuedUpload, RegistrantCount, Registrants, ReputationRecord, RootClaim,
+    ServedContent, TimeLock, UploadStatus, UploadTime, UploadedAt, Verdict, DEAD_LETTERS_KEY,
+    QUEUE_KEY, SERVED_KEY, UPLOAD_RETRIES_KEY,
+};
+
+fn test_metadata() -> FileMetadataOf<Test> {
+    FileMetadataOf::<Test> {
+        name: BoundedVec::try_from(b"substrate.png".to_vec()).unwrap(),
+        mime_type: BoundedVec::try_from(b"image/png".to_vec()).unwrap(),
+        tags: BoundedVec::try_from(vec![BoundedVec::try_from(b"logo".to_vec()).unwrap()]).unwrap(),
+        license: Some(License::Spdx(BoundedVec::try_from(b"CC0-1.0".to_vec()).unwrap())),
+        retrieval_price: None,
+    }
+}
+
+#[test]
+fn the_exported_test_utils_should_build_a_working_runtime() {
+    ExtBuilder::default()
+        .with_funded_accounts(vec![7], INITIAL_BALANCE)
+        .with_block_number(3)
+        .build()
+        .execute_with(|| {
+            assert_eq!(System::block_number(), 3);
+            assert_eq!(Balances::free_balance(7), INITIAL_BALANCE);
+            assert_eq!(Balances::free_balance(1), 0);
+
+            let merkle_root = upload_test_file::<Test>(7);
+            assert_eq!(TrustlessFileServer::get_file(merkle_root).unwrap().0, 7);
+            let pieces = TrustlessFileServer::file_pieces(merkle_root).unwrap();
+            for position in 0..pieces {
+                assert_proof_valid::<Test>(merkle_root, position.into());
+            }
+        });
+}
 
 #[test]
 fn it_should_successfully_list_files_when_empty() {
@@ -20,7 +67,8 @@ fn it_should_successfully_add_files() {
     new_test_ext().execute_with(|| {
         System::set_block_number(1);
         let bytes = include_bytes!("../img/substrate.png");
-        let result = TrustlessFileServer::upload_file(RuntimeOrigin::signed(1), bytes.to_vec());
+        let result =
+            TrustlessFileServer::upload_file(RuntimeOrigin::signed(1), bytes.to_vec(), None, None);
         assert_ok!(result);
         let files = TrustlessFileServer::get_files();
         assert_eq!(files.len(), 1);
@@ -38,12 +86,52 @@ fn it_should_successfully_add_files() {
     });
 }
 
+#[test]
+fn it_should_index_events_by_file_and_account() {
+    use sp_runtime::traits::{BlakeTwo256, Hash};
+
+    new_test_ext().execute_with(|| {
+        System::set_block_number(1);
+        let key = upload_test_file::<Test>(1);
+        let uploader = BlakeTwo256::hash_of(&1u64);
+        let record = System::events().pop().unwrap();
+        assert_eq!(record.topics, vec![key, uploader]);
+
+        assert_ok!(TrustlessFileServer::delete_file(RuntimeOrigin::signed(1), key));
+        // uploaded, then deleted
+        assert_eq!(System::event_topics(&key).len(), 2);
+        assert_eq!(System::event_topics(&uploader).len(), 1);
+    });
+}
+
+#[test]
+fn it_should_record_chunk_size_and_checksum_of_uploads() {
+    new_test_ext().execute_with(|| {
+        System::set_block_number(1);
+        let bytes = include_bytes!("../img/substrate.png");
+        let key = upload_test_file::<Test>(1);
+        assert_eq!(TrustlessFileServer::get_checksum(key), Some(sha2_256(bytes)));
+        System::assert_last_event(
+            Event::FileUploaded {
+                who: 1,
+                merkle_root: key,
+                pieces: 12,
+                size: bytes.len() as u64,
+                chunk_size: 1024,
+                sha256: sha2_256(bytes),
+            }
+            .into(),
+        );
+    });
+}
+
 #[test]
 fn it_should_successfully_get_proofs() {
     new_test_ext().execute_with(|| {
         System::set_block_number(1);
         let bytes = include_bytes!("../img/substrate.png");
-        let result = TrustlessFileServer::upload_file(RuntimeOrigin::signed(1), bytes.to_vec());
+        let result =
+            TrustlessFileServer::upload_file(RuntimeOrigin::signed(1), bytes.to_vec(), None, None);
         assert_ok!(result);
         let files = TrustlessFileServer::get_files();
         assert_eq!(files.len(), 1);
@@ -60,7 +148,7 @@ fn it_should_successfully_get_proofs() {
         let mut current = sha2_256(first_chunk).to_vec();
         assert_eq!(current, tree.file_chunk_hash_at(0).unwrap());
         for hash in proof {
-            current = sha2_256(&[current, hash].concat()).to_vec();
+            current = sha2_256(&[current, hash.to_vec()].concat()).to_vec();
         }
         assert_eq!(current.as_slice(), merkle_root);
     });
@@ -72,7 +160,8 @@ fn should_have_the_correct_owner() {
         System::set_block_number(1);
         let bytes = include_bytes!("../img/substrate.png");
         let owner = ensure_signed(RuntimeOrigin::signed(1)).unwrap();
-        let result = TrustlessFileServer::upload_file(RuntimeOrigin::signed(1), bytes.to_vec());
+        let result =
+            TrustlessFileServer::upload_file(RuntimeOrigin::signed(1), bytes.to_vec(), None, None);
         assert_ok!(result);
 
         let merkle_root = &TrustlessFileServer::get_files()[0].0;
@@ -80,3 +169,4895 @@ fn should_have_the_correct_owner() {
         assert_eq!(owner, TrustlessFileServer::get_file(key).unwrap().0);
     });
 }
+
+#[test]
+fn it_should_set_metadata_as_owner() {
+    new_test_ext().execute_with(|| {
+        System::set_block_number(1);
+        let key = upload_test_file::<Test>(1);
+        assert_ok!(TrustlessFileServer::set_metadata(
+            RuntimeOrigin::signed(1),
+            key,
+            test_metadata()
+        ));
+        assert_eq!(TrustlessFileServer::get_metadata(key), Some(test_metadata()));
+        System::assert_last_event(
+            Event::MetadataUpdated {
+                who: 1,
+                merkle_root: key,
+            }
+            .into(),
+        );
+    });
+}
+
+#[test]
+fn it_should_not_set_metadata_when_not_owner() {
+    new_test_ext().execute_with(|| {
+        System::set_block_number(1);
+        let key = upload_test_file::<Test>(1);
+        assert_noop!(
+            TrustlessFileServer::set_metadata(RuntimeOrigin::signed(2), key, test_metadata()),
+            Error::<Test>::NotFileOwner
+        );
+    });
+}
+
+#[test]
+fn it_should_not_set_metadata_of_unknown_files() {
+    new_test_ext().execute_with(|| {
+        System::set_block_number(1);
+        assert_noop!(
+            TrustlessFileServer::set_metadata(
+                RuntimeOrigin::signed(1),
+                H256::zero(),
+                test_metadata()
+            ),
+            Error::<Test>::FileNotFound
+        );
+    });
+}
+
+#[test]
+fn it_should_get_file_info_with_metadata() {
+    new_test_ext().execute_with(|| {
+        System::set_block_number(1);
+        let bytes = include_bytes!("../img/substrate.png");
+        let key = upload_test_file::<Test>(1);
+        let info = TrustlessFileServer::get_file_info(key.as_bytes().to_vec()).unwrap();
+        assert_eq!(info.owner, 1);
+        assert_eq!(info.size, bytes.len() as u64);
+        assert_eq!(info.pieces, 12);
+        assert_eq!(info.chunk_size, 1024);
+        assert_eq!(info.sha256, Some(sha2_256(bytes)));
+        assert!(info.name.is_empty());
+
+        assert_ok!(TrustlessFileServer::set_metadata(
+            RuntimeOrigin::signed(1),
+            key,
+            test_metadata()
+        ));
+        let info = TrustlessFileServer::get_file_info(key.as_bytes().to_vec()).unwrap();
+        assert_eq!(info.name, b"substrate.png".to_vec());
+        assert_eq!(info.mime_type, b"image/png".to_vec());
+        assert_eq!(info.tags, vec![b"logo".to_vec()]);
+        assert_eq!(info.license, Some(License::Spdx(b"CC0-1.0".to_vec())));
+        assert_eq!(TrustlessFileServer::get_file_info(vec![0u8; 32]), None);
+    });
+}
+
+#[test]
+fn it_should_not_serve_frozen_files() {
+    new_test_ext().execute_with(|| {
+        System::set_block_number(1);
+        let key = upload_test_file::<Test>(1);
+        let root = key.as_bytes().to_vec();
+        assert_noop!(
+            TrustlessFileServer::freeze_file(RuntimeOrigin::signed(2), key),
+            Error::<Test>::NotFileOwner
+        );
+        assert_noop!(
+            TrustlessFileServer::unfreeze_file(RuntimeOrigin::signed(1), key),
+            Error::<Test>::NotFrozen
+        );
+
+        assert_ok!(TrustlessFileServer::freeze_file(RuntimeOrigin::signed(1), key));
+        System::assert_last_event(
+            Event::FileFrozen {
+                who: 1,
+                merkle_root: key,
+            }
+            .into(),
+        );
+        assert_noop!(
+            TrustlessFileServer::freeze_file(RuntimeOrigin::signed(1), key),
+            Error::<Test>::AlreadyFrozen
+        );
+        assert!(TrustlessFileServer::is_frozen(root.clone()));
+        assert_eq!(TrustlessFileServer::get_proof(root.clone(), 0), None);
+        assert_eq!(TrustlessFileServer::get_piece_proof(root.clone(), 0), None);
+        assert_eq!(TrustlessFileServer::get_merkle_tree(root.clone()), None);
+        // the file is still registered
+        assert!(TrustlessFileServer::exists(root.clone()));
+        assert!(TrustlessFileServer::get_file_info(root.clone()).is_some());
+
+        assert_ok!(TrustlessFileServer::unfreeze_file(RuntimeOrigin::signed(1), key));
+        System::assert_last_event(
+            Event::FileUnfrozen {
+                who: 1,
+                merkle_root: key,
+            }
+            .into(),
+        );
+        assert!(!TrustlessFileServer::is_frozen(root.clone()));
+        assert!(TrustlessFileServer::get_proof(root, 0).is_some());
+
+        // the flag goes away along with the file
+        assert_ok!(TrustlessFileServer::freeze_file(RuntimeOrigin::signed(1), key));
+        assert_ok!(TrustlessFileServer::delete_file(RuntimeOrigin::signed(1), key));
+        assert!(!TrustlessFileServer::file_frozen(key));
+    });
+}
+
+#[test]
+fn it_should_transfer_files_to_multisig_accounts() {
+    new_test_ext().execute_with(|| {
+        System::set_block_number(1);
+        let bytes = include_bytes!("../img/substrate.png");
+        let deposit = TrustlessFileServer::deposit_for(bytes.len() as u32);
+        let key = upload_test_file::<Test>(1);
+        let multisig = TrustlessFileServer::multisig_account_id(&[1, 2, 3], 2);
+        assert_noop!(
+            TrustlessFileServer::transfer_file(RuntimeOrigin::signed(2), key, multisig),
+            Error::<Test>::NotFileOwner
+        );
+
+        // signatories approve the call by its hash, as `pallet_multisig` does
+        let call = crate::Call::<Test>::set_management_threshold {
+            merkle_root: key,
+            threshold: Some(2),
+        };
+        assert_eq!(
+            TrustlessFileServer::management_call_hash(call.clone()),
+            sp_io::hashing::blake2_256(&RuntimeCall::from(call).encode())
+        );
+
+        assert_ok!(TrustlessFileServer::transfer_file(RuntimeOrigin::signed(1), key, multisig));
+        System::assert_last_event(
+            Event::FileTransferred {
+                from: 1,
+                to: multisig,
+                merkle_root: key,
+            }
+            .into(),
+        );
+        assert_eq!(FileOwners::<Test>::get(key), Some(multisig));
+        // the deposit stays held from the original owner, who paid it
+        assert_eq!(TrustlessFileServer::get_registrant(key, 1), None);
+        assert_eq!(
+            TrustlessFileServer::get_registrant(key, multisig),
+            Some(Deposit::Native(deposit))
+        );
+        assert_eq!(TrustlessFileServer::get_account_usage(multisig).files, 1);
+        assert_eq!(TrustlessFileServer::get_account_usage(1).files, 0);
+        assert_eq!(TrustlessFileServer::get_account_usage(1).deposit_locked, deposit as u128);
+
+        assert_noop!(
+            TrustlessFileServer::set_management_threshold(RuntimeOrigin::signed(1), key, Some(2)),
+            Error::<Test>::NotFileOwner
+        );
+        assert_noop!(
+            TrustlessFileServer::set_management_threshold(
+                RuntimeOrigin::signed(multisig),
+                key,
+                Some(0)
+            ),
+            Error::<Test>::ZeroThreshold
+        );
+        assert_ok!(TrustlessFileServer::set_management_threshold(
+            RuntimeOrigin::signed(multisig),
+            key,
+            Some(2)
+        ));
+        assert_eq!(TrustlessFileServer::get_management_threshold(key), Some(2));
+
+        // once the multisig leaves, the deposit goes back to whoever paid it
+        assert_ok!(TrustlessFileServer::delete_file(RuntimeOrigin::signed(multisig), key));
+        assert_eq!(Balances::reserved_balance(1), 0);
+        assert_eq!(Balances::free_balance(1), INITIAL_BALANCE);
+        assert_eq!(TrustlessFileServer::get_management_threshold(key), None);
+    });
+}
+
+#[test]
+fn it_should_set_retrieval_prices_as_owner() {
+    new_test_ext().execute_with(|| {
+        System::set_block_number(1);
+        let key = upload_test_file::<Test>(1);
+        assert_noop!(
+            TrustlessFileServer::set_retrieval_price(RuntimeOrigin::signed(2), key, Some(5)),
+            Error::<Test>::NotFileOwner
+        );
+
+        assert_ok!(TrustlessFileServer::set_retrieval_price(
+            RuntimeOrigin::signed(1),
+            key,
+            Some(5)
+        ));
+        System::assert_last_event(
+            Event::RetrievalPriceSet {
+                who: 1,
+                merkle_root: key,
+                price: Some(5),
+            }
+            .into(),
+        );
+        assert_eq!(TrustlessFileServer::retrieval_price(key), Some(5));
+        let info = TrustlessFileServer::get_file_info(key.as_bytes().to_vec()).unwrap();
+        assert_eq!(info.retrieval_price, Some(5));
+
+        // replacing the rest of the metadata keeps the price only if it is passed along
+        assert_ok!(TrustlessFileServer::set_metadata(
+            RuntimeOrigin::signed(1),
+            key,
+            FileMetadataOf::<Test> {
+                retrieval_price: Some(5),
+                ..test_metadata()
+            }
+        ));
+        assert_ok!(TrustlessFileServer::set_retrieval_price(RuntimeOrigin::signed(1), key, None));
+        assert_eq!(TrustlessFileServer::retrieval_price(key), None);
+        assert_eq!(TrustlessFileServer::get_metadata(key), Some(test_metadata()));
+    });
+}
+
+#[test]
+fn it_should_record_licenses_of_new_files() {
+    new_test_ext().execute_with(|| {
+        System::set_block_number(1);
+        let uri = |uri: &[u8]| License::Uri(BoundedVec::try_from(uri.to_vec()).unwrap());
+        assert_noop!(
+            TrustlessFileServer::upload_file(
+                RuntimeOrigin::signed(1),
+                vec![1u8; 1024],
+                None,
+                Some(uri(b""))
+            ),
+            Error::<Test>::InvalidLicense
+        );
+        let license = uri(b"https://example.com/terms");
+        assert_ok!(TrustlessFileServer::upload_file(
+            RuntimeOrigin::signed(1),
+            vec![1u8; 1024],
+            None,
+            Some(license.clone())
+        ));
+        let key = H256::decode(&mut TrustlessFileServer::get_files()[0].0.as_slice()).unwrap();
+        assert_eq!(TrustlessFileServer::get_metadata(key).unwrap().license, Some(license));
+
+        let mut metadata = test_metadata();
+        metadata.license =
+            Some(License::Spdx(BoundedVec::try_from(b"MIT OR GPL".to_vec()).unwrap()));
+        assert_noop!(
+            TrustlessFileServer::set_metadata(RuntimeOrigin::signed(1), key, metadata),
+            Error::<Test>::InvalidLicense
+        );
+    });
+}
+
+#[test]
+fn it_should_verify_proofs_of_registered_files_only() {
+    new_test_ext().execute_with(|| {
+        System::set_block_number(1);
+        let key = upload_test_file::<Test>(1);
+        let tree = TrustlessFileServer::get_file(key).unwrap().1;
+        let leaf: [u8; 32] = tree.merkle_tree[..32].try_into().unwrap();
+        let (_, proof) = TrustlessFileServer::get_proof(key.as_bytes().to_vec(), 0).unwrap();
+        assert!(TrustlessFileServer::verify_proof(key, 0, &leaf, &proof));
+        assert!(!TrustlessFileServer::verify_proof(key, 1, &leaf, &proof));
+
+        let unknown = H256::repeat_byte(1);
+        assert!(!TrustlessFileServer::verify_proof(unknown, 0, &leaf, &proof));
+    });
+}
+
+#[test]
+fn it_should_reject_files_above_the_maximum_size() {
+    new_test_ext().execute_with(|| {
+        System::set_block_number(1);
+        assert_noop!(
+            TrustlessFileServer::upload_file(
+                RuntimeOrigin::signed(1),
+                vec![1u8; 32769],
+                None,
+                None
+            ),
+            Error::<Test>::FileTooLarge
+        );
+    });
+}
+
+#[test]
+fn it_should_reject_empty_files() {
+    new_test_ext().execute_with(|| {
+        System::set_block_number(1);
+        assert_noop!(
+            TrustlessFileServer::upload_file(RuntimeOrigin::signed(1), vec![], None, None),
+            Error::<Test>::EmptyFile
+        );
+    });
+}
+
+#[test]
+fn it_should_reject_forbidden_content_types() {
+    new_test_ext().execute_with(|| {
+        System::set_block_number(1);
+        let mut elf = b"\x7fELF\x02\x01\x01".to_vec();
+        elf.resize(1024, 0);
+        assert_noop!(
+            TrustlessFileServer::upload_file(RuntimeOrigin::signed(1), elf, None, None),
+            Error::<Test>::ContentTypeForbidden
+        );
+    });
+}
+
+#[test]
+fn it_should_only_accept_uploads_from_the_upload_origin() {
+    new_test_ext().execute_with(|| {
+        System::set_block_number(1);
+        assert_noop!(
+            TrustlessFileServer::upload_file(RuntimeOrigin::none(), vec![1u8; 1024], None, None),
+            sp_runtime::traits::BadOrigin
+        );
+        assert_noop!(
+            TrustlessFileServer::upload_file(RuntimeOrigin::root(), vec![1u8; 1024], None, None),
+            sp_runtime::traits::BadOrigin
+        );
+    });
+}
+
+#[test]
+fn check_upload_size_should_reject_oversized_uploads_at_validation() {
+    use frame_support::dispatch::DispatchInfo;
+    use sp_runtime::traits::SignedExtension;
+    use sp_runtime::transaction_validity::InvalidTransaction;
+
+    use crate::CheckUploadSize;
+
+    new_test_ext().execute_with(|| {
+        let info = DispatchInfo::default();
+        let small: RuntimeCall = crate::Call::<Test>::upload_file {
+            file_bytes: vec![1u8; 1024],
+            deposit_asset: None,
+            license: None,
+        }
+        .into();
+        assert_ok!(CheckUploadSize::<Test>::new().validate(&1, &small, &info, 0));
+
+        let big: RuntimeCall = crate::Call::<Test>::upload_file {
+            file_bytes: vec![1u8; 32769],
+            deposit_asset: None,
+            license: None,
+        }
+        .into();
+        assert_eq!(
+            CheckUploadSize::<Test>::new().validate(&1, &big, &info, 0),
+            Err(InvalidTransaction::ExhaustsResources.into())
+        );
+
+        let sponsored: RuntimeCall = crate::Call::<Test>::sponsor_upload {
+            beneficiary: 2,
+            file_bytes: vec![1u8; 32769],
+        }
+        .into();
+        assert_eq!(
+            CheckUploadSize::<Test>::new().validate(&1, &sponsored, &info, 0),
+            Err(InvalidTransaction::ExhaustsResources.into())
+        );
+
+        let batch: RuntimeCall = crate::Call::<Test>::upload_files {
+            files: BoundedVec::try_from(vec![vec![1u8; 1024], vec![2u8; 32769]]).unwrap(),
+        }
+        .into();
+        assert_eq!(
+            CheckUploadSize::<Test>::new().validate(&1, &batch, &info, 0),
+            Err(InvalidTransaction::ExhaustsResources.into())
+        );
+
+        let other: RuntimeCall = frame_system::Call::<Test>::remark { remark: vec![] }.into();
+        assert_ok!(CheckUploadSize::<Test>::new().validate(&1, &other, &info, 0));
+    });
+}
+
+#[test]
+fn it_should_index_every_upload_of_a_block() {
+    use crate::BlockUploads;
+
+    new_test_ext().execute_with(|| {
+        System::set_block_number(1);
+        assert_ok!(TrustlessFileServer::upload_file(
+            RuntimeOrigin::signed(1),
+            vec![1u8; 1024],
+            None,
+            None
+        ));
+        assert_ok!(TrustlessFileServer::upload_file(
+            RuntimeOrigin::signed(2),
+            vec![2u8; 1024],
+            None,
+            None
+        ));
+        assert_eq!(BlockUploads::<Test>::get().len(), 2);
+        assert_eq!(TrustlessFileServer::get_files().len(), 2);
+
+        assert_ok!(TrustlessFileServer::upload_file(
+            RuntimeOrigin::signed(1),
+            vec![3u8; 1024],
+            None,
+            None
+        ));
+        assert_ok!(TrustlessFileServer::upload_file(
+            RuntimeOrigin::signed(1),
+            vec![4u8; 1024],
+            None,
+            None
+        ));
+        assert_noop!(
+            TrustlessFileServer::upload_file(RuntimeOrigin::signed(1), vec![5u8; 1024], None, None),
+            Error::<Test>::TooManyUploadsInBlock
+        );
+
+        System::set_block_number(2);
+        TrustlessFileServer::on_initialize(2);
+        assert!(BlockUploads::<Test>::get().is_empty());
+        assert_ok!(TrustlessFileServer::upload_file(
+            RuntimeOrigin::signed(1),
+            vec![5u8; 1024],
+            None,
+            None
+        ));
+    });
+}
+
+#[test]
+fn it_should_limit_the_uploads_of_an_account_per_block() {
+    new_test_ext().execute_with(|| {
+        MaxUploadsPerAccountPerBlock::set(2);
+        System::set_block_number(1);
+        for byte in [1u8, 2] {
+            assert_ok!(TrustlessFileServer::upload_file(
+                RuntimeOrigin::signed(1),
+                vec![byte; 1024],
+                None,
+                None
+            ));
+        }
+        assert_noop!(
+            TrustlessFileServer::upload_file(RuntimeOrigin::signed(1), vec![3u8; 1024], None, None),
+            Error::<Test>::TooManyUploadsFromAccount
+        );
+        // sponsored uploads count towards the sponsor
+        assert_noop!(
+            TrustlessFileServer::sponsor_upload(RuntimeOrigin::signed(1), 3, vec![3u8; 1024]),
+            Error::<Test>::TooManyUploadsFromAccount
+        );
+        // other accounts are not affected
+        assert_ok!(TrustlessFileServer::upload_file(
+            RuntimeOrigin::signed(2),
+            vec![3u8; 1024],
+            None,
+            None
+        ));
+
+        TrustlessFileServer::on_finalize(1);
+        System::set_block_number(2);
+        TrustlessFileServer::on_initialize(2);
+        assert_ok!(TrustlessFileServer::upload_file(
+            RuntimeOrigin::signed(1),
+            vec![4u8; 1024],
+            None,
+            None
+        ));
+    });
+}
+
+#[test]
+fn it_should_record_upload_progress() {
+    new_test_ext().execute_with(|| {
+        System::set_block_number(1);
+        let key = upload_test_file::<Test>(1);
+        assert_ok!(TrustlessFileServer::report_upload_progress(
+            RuntimeOrigin::none(),
+            key,
+            4,
+            12,
+            authority_proof((b"progress", key, 4u32, 12u32))
+        ));
+        assert_eq!(TrustlessFileServer::get_upload_progress(key), 4);
+        System::assert_last_event(
+            Event::<Test>::ChunksUploaded {
+                merkle_root: key,
+                done: 4,
+                total: 12,
+            }
+            .into(),
+        );
+
+        // progress must move forward and match the number of pieces
+        assert_noop!(
+            TrustlessFileServer::report_upload_progress(
+                RuntimeOrigin::none(),
+                key,
+                4,
+                12,
+                authority_proof((b"progress", key, 4u32, 12u32))
+            ),
+            Error::<Test>::InvalidProgress
+        );
+        assert_noop!(
+            TrustlessFileServer::report_upload_progress(
+                RuntimeOrigin::none(),
+                key,
+                8,
+                16,
+                authority_proof((b"progress", key, 8u32, 16u32))
+            ),
+            Error::<Test>::InvalidProgress
+        );
+        assert_noop!(
+            TrustlessFileServer::report_upload_progress(
+                RuntimeOrigin::signed(1),
+                key,
+                8,
+                12,
+                authority_proof((b"progress", key, 8u32, 12u32))
+            ),
+            sp_runtime::traits::BadOrigin
+        );
+    });
+}
+
+#[test]
+fn it_should_only_accept_valid_unsigned_progress_reports() {
+    use frame_support::pallet_prelude::ValidateUnsigned;
+    use sp_runtime::transaction_validity::{InvalidTransaction, TransactionSource};
+
+    new_test_ext().execute_with(|| {
+        System::set_block_number(1);
+        let key = upload_test_file::<Test>(1);
+        let call = crate::Call::<Test>::report_upload_progress {
+            merkle_root: key,
+            done: 12,
+            total: 12,
+            proof: authority_proof((b"progress", key, 12u32, 12u32)),
+        };
+        assert!(TrustlessFileServer::validate_unsigned(TransactionSource::External, &call).is_ok());
+
+        let call = crate::Call::<Test>::report_upload_progress {
+            merkle_root: H256::zero(),
+            done: 1,
+            total: 12,
+            proof: authority_proof((b"progress", H256::zero(), 1u32, 12u32)),
+        };
+        assert_eq!(
+            TrustlessFileServer::validate_unsigned(TransactionSource::External, &call),
+            Err(InvalidTransaction::Stale.into())
+        );
+    });
+}
+
+#[test]
+fn it_should_reject_reports_not_signed_by_an_authority() {
+    use frame_support::pallet_prelude::ValidateUnsigned;
+    use sp_runtime::transaction_validity::{InvalidTransaction, TransactionSource};
+
+    new_test_ext().execute_with(|| {
+        System::set_block_number(1);
+        let key = upload_test_file::<Test>(1);
+        let report = (b"progress", key, 4u32, 12u32);
+        let call = |proof| crate::Call::<Test>::report_upload_progress {
+            merkle_root: key,
+            done: 4,
+            total: 12,
+            proof,
+        };
+
+        // signed by a node which is not an authority
+        let proof = node_proof(OCW_AUTHORITY + 1, report);
+        assert_eq!(
+            TrustlessFileServer::validate_unsigned(
+                TransactionSource::External,
+                &call(proof.clone())
+            ),
+            Err(InvalidTransaction::BadProof.into())
+        );
+        assert_noop!(
+            TrustlessFileServer::report_upload_progress(RuntimeOrigin::none(), key, 4, 12, proof),
+            Error::<Test>::InvalidAuthorityProof
+        );
+
+        // signed by the authority, but for another report
+        let proof = authority_proof((b"progress", key, 12u32, 12u32));
+        assert_eq!(
+            TrustlessFileServer::validate_unsigned(
+                TransactionSource::External,
+                &call(proof.clone())
+            ),
+            Err(InvalidTransaction::BadProof.into())
+        );
+        assert_noop!(
+            TrustlessFileServer::report_upload_progress(RuntimeOrigin::none(), key, 4, 12, proof),
+            Error::<Test>::InvalidAuthorityProof
+        );
+
+        // no longer accepted after `REPORT_LONGEVITY` blocks
+        let proof = node_proof(OCW_AUTHORITY, report);
+        assert!(TrustlessFileServer::validate_unsigned(
+            TransactionSource::External,
+            &call(proof.clone())
+        )
+        .is_ok());
+        System::set_block_number(2 + crate::REPORT_LONGEVITY as u64);
+        assert_eq!(
+            TrustlessFileServer::validate_unsigned(
+                TransactionSource::External,
+                &call(proof.clone())
+            ),
+            Err(InvalidTransaction::BadProof.into())
+        );
+        assert_noop!(
+            TrustlessFileServer::report_upload_progress(RuntimeOrigin::none(), key, 4, 12, proof),
+            Error::<Test>::InvalidAuthorityProof
+        );
+
+        // nobody's reports are accepted while there are no authorities
+        crate::OcwAuthorities::<Test>::kill();
+        assert_noop!(
+            TrustlessFileServer::report_upload_progress(
+                RuntimeOrigin::none(),
+                key,
+                4,
+                12,
+                authority_proof(report)
+            ),
+            Error::<Test>::InvalidAuthorityProof
+        );
+    });
+}
+
+#[test]
+fn it_should_record_cluster_allocations() {
+    use crate::{AllocationsOf, ClusterPeerId};
+
+    new_test_ext().execute_with(|| {
+        System::set_block_number(1);
+        let key = upload_test_file::<Test>(1);
+        let peer = ClusterPeerId::try_from(b"12D3KooWA".to_vec()).unwrap();
+        let allocations = AllocationsOf::<Test>::try_from(vec![peer]).unwrap();
+        assert_ok!(TrustlessFileServer::report_chunk_allocations(
+            RuntimeOrigin::none(),
+            key,
+            11,
+            allocations.clone(),
+            authority_proof((b"allocations", key, 11u64, &allocations))
+        ));
+        assert_eq!(TrustlessFileServer::get_chunk_allocations(key, 11), Some(allocations.clone()));
+        System::assert_last_event(
+            Event::<Test>::ChunkAllocated {
+                merkle_root: key,
+                position: 11,
+                peers: 1,
+            }
+            .into(),
+        );
+
+        assert_noop!(
+            TrustlessFileServer::report_chunk_allocations(
+                RuntimeOrigin::none(),
+                key,
+                12,
+                allocations.clone(),
+                authority_proof((b"allocations", key, 12u64, &allocations))
+            ),
+            Error::<Test>::InvalidPosition
+        );
+    });
+}
+
+#[test]
+fn it_should_record_gateway_probes() {
+    use crate::ProbeResult;
+    use frame_support::pallet_prelude::ValidateUnsigned;
+    use sp_runtime::transaction_validity::{InvalidTransaction, TransactionSource};
+
+    new_test_ext().execute_with(|| {
+        System::set_block_number(5);
+        let key = upload_test_file::<Test>(1);
+        assert_ok!(TrustlessFileServer::report_probe(
+            RuntimeOrigin::none(),
+            key,
+            3,
+            false,
+            authority_proof((b"probe", key, 3u64, false))
+        ));
+        assert_eq!(
+            TrustlessFileServer::get_probe_result(key),
+            Some(ProbeResult {
+                block: 5,
+                position: 3,
+                available: false
+            })
+        );
+        assert_noop!(
+            TrustlessFileServer::report_probe(
+                RuntimeOrigin::none(),
+                key,
+                12,
+                true,
+                authority_proof((b"probe", key, 12u64, true))
+            ),
+            Error::<Test>::InvalidPosition
+        );
+
+        // a probe signed for the chunk being available cannot report it missing, nor the other
+        // way around
+        let proof = authority_proof((b"probe", key, 3u64, true));
+        assert_noop!(
+            TrustlessFileServer::report_probe(RuntimeOrigin::none(), key, 3, false, proof.clone()),
+            Error::<Test>::InvalidAuthorityProof
+        );
+        let call = crate::Call::<Test>::report_probe {
+            merkle_root: key,
+            position: 3,
+            available: false,
+            proof,
+        };
+        assert_eq!(
+            TrustlessFileServer::validate_unsigned(TransactionSource::External, &call),
+            Err(InvalidTransaction::BadProof.into())
+        );
+    });
+}
+
+/// Registers the test file split in 4 pieces, as if the chunking parameters had changed since.
+fn upload_legacy_test_file(who: u64) -> H256 {
+    let bytes = include_bytes!("../img/substrate.png");
+    let params = ChunkingParams {
+        target_pieces: 4,
+        ..TrustlessFileServer::chunking_params()
+    };
+    let tree = FileMerkleTree::new(bytes, &params);
+    let merkle_root = H256::decode(&mut tree.merkle_root()).unwrap();
+    TrustlessFileServer::put_file(merkle_root, &who, &tree);
+    Registrants::<Test>::insert(merkle_root, who, Deposit::Native(0));
+    RegistrantCount::<Test>::insert(merkle_root, 1);
+    merkle_root
+}
+
+/// Moves every registered file back to the layout of storage version 6, with its owner and tree
+/// together under `Files`.
+fn into_legacy_layout() {
+    for merkle_root in FileOwners::<Test>::iter_keys().collect::<Vec<_>>() {
+        let file = TrustlessFileServer::get_file(merkle_root).unwrap();
+        TrustlessFileServer::take_file(merkle_root);
+        migrations::Files::<Test>::insert(merkle_root, file);
+    }
+}
+
+#[test]
+fn it_should_only_rechunk_outdated_files_of_the_owner() {
+    new_test_ext().execute_with(|| {
+        System::set_block_number(1);
+        let current = upload_test_file::<Test>(1);
+        assert_noop!(
+            TrustlessFileServer::rechunk_file(RuntimeOrigin::signed(1), current),
+            Error::<Test>::AlreadyChunked
+        );
+
+        let legacy = upload_legacy_test_file(1);
+        assert_noop!(
+            TrustlessFileServer::rechunk_file(RuntimeOrigin::signed(2), legacy),
+            Error::<Test>::NotFileOwner
+        );
+        assert_ok!(TrustlessFileServer::rechunk_file(RuntimeOrigin::signed(1), legacy));
+        assert!(PendingRechunks::<Test>::contains_key(legacy));
+        System::assert_last_event(
+            Event::RechunkRequested {
+                who: 1,
+                merkle_root: legacy,
+            }
+            .into(),
+        );
+    });
+}
+
+#[test]
+fn it_should_swap_rechunked_trees_and_keep_aliases() {
+    use frame_support::pallet_prelude::ValidateUnsigned;
+    use sp_runtime::transaction_validity::{InvalidTransaction, TransactionSource};
+
+    new_test_ext().execute_with(|| {
+        System::set_block_number(1);
+        let bytes = include_bytes!("../img/substrate.png");
+        let legacy = upload_legacy_test_file(1);
+        assert_ok!(TrustlessFileServer::set_metadata(
+            RuntimeOrigin::signed(1),
+            legacy,
+            test_metadata()
+        ));
+        let uploaded_at = UploadTime {
+            block: 1,
+            timestamp: 6_000,
+        };
+        UploadedAt::<Test>::insert(legacy, uploaded_at);
+        let merkle_tree = FileMerkleTree::new(bytes, &TrustlessFileServer::chunking_params());
+        let proof = authority_proof((b"rechunk", legacy, &merkle_tree));
+        assert_noop!(
+            TrustlessFileServer::report_rechunk(
+                RuntimeOrigin::none(),
+                legacy,
+                merkle_tree.clone(),
+                proof.clone()
+            ),
+            Error::<Test>::RechunkNotRequested
+        );
+        assert_ok!(TrustlessFileServer::rechunk_file(RuntimeOrigin::signed(1), legacy));
+
+        // trees built with the old parameters are rejected
+        let outdated = TrustlessFileServer::get_file(legacy).unwrap().1;
+        assert_noop!(
+            TrustlessFileServer::report_rechunk(
+                RuntimeOrigin::none(),
+                legacy,
+                outdated.clone(),
+                authority_proof((b"rechunk", legacy, &outdated))
+            ),
+            Error::<Test>::InvalidMerkleTree
+        );
+
+        // nor are trees other than the one the authority signed, which would take over the file
+        let forged = FileMerkleTree::new(
+            &[bytes.as_slice(), b"forged"].concat(),
+            &TrustlessFileServer::chunking_params(),
+        );
+        assert_noop!(
+            TrustlessFileServer::report_rechunk(
+                RuntimeOrigin::none(),
+                legacy,
+                forged.clone(),
+                proof.clone()
+            ),
+            Error::<Test>::InvalidAuthorityProof
+        );
+        let call = crate::Call::<Test>::report_rechunk {
+            merkle_root: legacy,
+            merkle_tree: forged,
+            proof: proof.clone(),
+        };
+        assert_eq!(
+            TrustlessFileServer::validate_unsigned(TransactionSource::External, &call),
+            Err(InvalidTransaction::BadProof.into())
+        );
+
+        assert_ok!(TrustlessFileServer::report_rechunk(
+            RuntimeOrigin::none(),
+            legacy,
+            merkle_tree,
+            proof
+        ));
+        let new_root = H256::decode(&mut TrustlessFileServer::get_files()[0].0.as_slice()).unwrap();
+        assert!(TrustlessFileServer::get_file(legacy).is_none());
+        assert!(!PendingRechunks::<Test>::contains_key(legacy));
+        assert_eq!(TrustlessFileServer::get_root_alias(legacy), Some(new_root));
+        assert_eq!(TrustlessFileServer::get_metadata(new_root), Some(test_metadata()));
+        assert_eq!(TrustlessFileServer::get_upload_time(new_root), Some(uploaded_at));
+        assert_eq!(TrustlessFileServer::get_upload_time(legacy), None);
+        System::assert_last_event(
+            Event::FileRechunked {
+                old_root: legacy,
+                new_root,
+                pieces: 12,
+                chunk_size: 1024,
+            }
+            .into(),
+        );
+
+        // the old root is still queryable
+        let info = TrustlessFileServer::get_file_info(legacy.as_bytes().to_vec()).unwrap();
+        assert_eq!(info.merkle_root, new_root.as_bytes().to_vec());
+        assert_eq!(info.pieces, 12);
+    });
+}
+
+#[test]
+fn it_should_register_bep52_pieces_roots_when_enabled() {
+    new_test_ext().execute_with(|| {
+        System::set_block_number(1);
+        let current = upload_test_file::<Test>(1);
+        Bep52Compatible::set(true);
+
+        // one full 16KB block and a partial one, hashed unpadded
+        let bytes: Vec<u8> = (0..20_000u32).map(|i| (i % 251) as u8).collect();
+        assert_ok!(TrustlessFileServer::upload_file(
+            RuntimeOrigin::signed(2),
+            bytes.clone(),
+            None,
+            None
+        ));
+        let leaves = [sha2_256(&bytes[..16384]), sha2_256(&bytes[16384..])];
+        let pieces_root = H256::from(sha2_256(&leaves.concat()));
+        let tree = TrustlessFileServer::get_file_tree(pieces_root).unwrap();
+        assert_eq!(tree.chunk_size(), 16384);
+        assert_eq!(tree.pieces(), 2);
+        let info = TrustlessFileServer::get_file_info(pieces_root.as_bytes().to_vec()).unwrap();
+        assert!(info.bep52);
+
+        // files registered before can be rechunked to become BEP-52 compatible
+        let info = TrustlessFileServer::get_file_info(current.as_bytes().to_vec()).unwrap();
+        assert!(!info.bep52);
+        assert_ok!(TrustlessFileServer::rechunk_file(RuntimeOrigin::signed(1), current));
+    });
+}
+
+#[test]
+fn it_should_split_files_as_ipfs_add_when_enabled() {
+    new_test_ext().execute_with(|| {
+        System::set_block_number(1);
+        IpfsChunkSize::set(Some(4096));
+        let bytes = include_bytes!("../img/substrate.png");
+        let key = upload_test_file::<Test>(1);
+        let tree = TrustlessFileServer::get_file_tree(key).unwrap();
+        assert_eq!(tree.chunk_size(), 4096);
+        assert_eq!(tree.pieces(), 3);
+        // chunks are identified by the CIDs of their content, the last one unpadded
+        let proof = TrustlessFileServer::get_piece_proof(key.encode(), 2).unwrap();
+        assert_eq!(proof.ipfs_hash, ipfs::ipfs_get_hash_from_sha256(&sha2_256(&bytes[8192..])));
+        // and the whole file by the CID `ipfs add` gives it
+        let chunks: Vec<_> = bytes
+            .chunks(4096)
+            .map(|chunk| (sha2_256(chunk), chunk.len() as u64))
+            .collect();
+        let info = TrustlessFileServer::get_file_info(key.encode()).unwrap();
+        assert_eq!(info.cid, Some(ipfs::unixfs_file_cid(&chunks).into_bytes()));
+    });
+}
+
+#[test]
+fn it_should_reserve_native_deposits() {
+    new_test_ext().execute_with(|| {
+        System::set_block_number(1);
+        let bytes = include_bytes!("../img/substrate.png");
+        let key = upload_test_file::<Test>(1);
+        let deposit = 100 + bytes.len() as u64;
+        assert_eq!(TrustlessFileServer::deposit_for(bytes.len() as u32), deposit);
+        assert_eq!(TrustlessFileServer::get_registrant(key, 1), Some(Deposit::Native(deposit)));
+        assert_eq!(Balances::reserved_balance(1), deposit);
+        assert_eq!(Balances::free_balance(1), INITIAL_BALANCE - deposit);
+    });
+}
+
+#[test]
+fn it_should_take_deposits_at_the_oracle_price() {
+    new_test_ext().execute_with(|| {
+        System::set_block_number(1);
+        PriceOracle::set(Some(3));
+        assert_eq!(TrustlessFileServer::deposit_for(1024), 100 + 3 * 1024);
+        assert_ok!(TrustlessFileServer::upload_file(
+            RuntimeOrigin::signed(1),
+            vec![1u8; 1024],
+            None,
+            None
+        ));
+        assert_eq!(Balances::reserved_balance(1), 100 + 3 * 1024);
+
+        // back to the constant once the oracle has no price
+        PriceOracle::set(None);
+        assert_eq!(TrustlessFileServer::deposit_for(1024), 100 + 1024);
+    });
+}
+
+#[test]
+fn it_should_take_deposits_in_the_chosen_asset() {
+    use frame_support::traits::fungibles::Inspect;
+
+    new_test_ext().execute_with(|| {
+        System::set_block_number(1);
+        assert_ok!(TrustlessFileServer::upload_file(
+            RuntimeOrigin::signed(1),
+            vec![1u8; 1024],
+            Some(DEPOSIT_ASSET),
+            None
+        ));
+        let key = H256::decode(&mut TrustlessFileServer::get_files()[0].0.as_slice()).unwrap();
+        // half the native deposit, as per the mock conversion
+        let amount = (100 + 1024) / 2;
+        assert_eq!(
+            TrustlessFileServer::get_registrant(key, 1),
+            Some(Deposit::Asset {
+                id: DEPOSIT_ASSET,
+                amount
+            })
+        );
+        assert_eq!(Assets::balance(DEPOSIT_ASSET, 1), INITIAL_BALANCE - amount);
+        assert_eq!(Assets::balance(DEPOSIT_ASSET, TrustlessFileServer::account_id()), amount);
+        assert_eq!(Balances::reserved_balance(1), 0);
+    });
+}
+
+#[test]
+fn it_should_not_upload_without_funds_for_the_deposit() {
+    new_test_ext().execute_with(|| {
+        System::set_block_number(1);
+        assert!(TrustlessFileServer::upload_file(
+            RuntimeOrigin::signed(4),
+            vec![1u8; 1024],
+            None,
+            None
+        )
+        .is_err());
+        assert!(TrustlessFileServer::upload_file(
+            RuntimeOrigin::signed(4),
+            vec![1u8; 1024],
+            Some(DEPOSIT_ASSET),
+            None
+        )
+        .is_err());
+        assert_eq!(TrustlessFileServer::get_files(), []);
+    });
+}
+
+#[test]
+fn it_should_add_registrants_to_already_uploaded_files() {
+    new_test_ext().execute_with(|| {
+        System::set_block_number(1);
+        let bytes = include_bytes!("../img/substrate.png");
+        let key = upload_test_file::<Test>(1);
+        assert_noop!(
+            TrustlessFileServer::upload_file(RuntimeOrigin::signed(1), bytes.to_vec(), None, None),
+            Error::<Test>::AlreadyRegistered
+        );
+
+        assert_ok!(TrustlessFileServer::upload_file(
+            RuntimeOrigin::signed(2),
+            bytes.to_vec(),
+            None,
+            None
+        ));
+        System::assert_last_event(
+            Event::RegistrantAdded {
+                who: 2,
+                merkle_root: key,
+                registrants: 2,
+            }
+            .into(),
+        );
+        assert_eq!(TrustlessFileServer::get_files().len(), 1);
+        assert_eq!(TrustlessFileServer::get_file(key).unwrap().0, 1);
+        assert_eq!(TrustlessFileServer::get_registrant_count(key), 2);
+        assert_eq!(Balances::reserved_balance(2), 100 + bytes.len() as u64);
+
+        assert_ok!(TrustlessFileServer::upload_file(
+            RuntimeOrigin::signed(3),
+            bytes.to_vec(),
+            None,
+            None
+        ));
+        // the limit is checked before even trying to take the deposit
+        assert_noop!(
+            TrustlessFileServer::upload_file(RuntimeOrigin::signed(4), bytes.to_vec(), None, None),
+            Error::<Test>::TooManyRegistrants
+        );
+    });
+}
+
+#[test]
+fn it_should_hold_deposits_by_reason() {
+    use crate::HoldReason;
+    use frame_support::traits::fungible::InspectHold;
+
+    new_test_ext().execute_with(|| {
+        System::set_block_number(1);
+        let key = upload_test_file::<Test>(1);
+        let deposit = 100 + TEST_FILE.len() as u64;
+        assert_ok!(TrustlessFileServer::register_alias(
+            RuntimeOrigin::signed(1),
+            name("myapp/logo.png"),
+            key
+        ));
+        assert_ok!(TrustlessFileServer::report_file(RuntimeOrigin::signed(2), key, [1; 32]));
+
+        assert_eq!(Balances::balance_on_hold(&HoldReason::FileDeposit, &1), deposit);
+        assert_eq!(Balances::balance_on_hold(&HoldReason::AliasDeposit, &1), 24);
+        assert_eq!(Balances::balance_on_hold(&HoldReason::ReportBond, &2), 50);
+        assert_eq!(TrustlessFileServer::held_balance(), deposit + 24 + 50);
+
+        // dismissing the report burns the bond
+        assert_ok!(TrustlessFileServer::resolve_report(
+            RuntimeOrigin::root(),
+            key,
+            2,
+            Verdict::Dismiss
+        ));
+        assert_eq!(Balances::balance_on_hold(&HoldReason::ReportBond, &2), 0);
+        assert_eq!(TrustlessFileServer::held_balance(), deposit + 24);
+
+        assert_ok!(TrustlessFileServer::release_alias(
+            RuntimeOrigin::signed(1),
+            name("myapp/logo.png")
+        ));
+        assert_ok!(TrustlessFileServer::delete_file(RuntimeOrigin::signed(1), key));
+        assert_eq!(Balances::total_balance_on_hold(&1), 0);
+        assert_eq!(TrustlessFileServer::held_balance(), 0);
+    });
+}
+
+#[test]
+fn it_should_keep_files_until_the_last_registrant_deletes_them() {
+    new_test_ext().execute_with(|| {
+        System::set_block_number(1);
+        let bytes = include_bytes!("../img/substrate.png");
+        let key = upload_test_file::<Test>(1);
+        assert_ok!(TrustlessFileServer::upload_file(
+            RuntimeOrigin::signed(2),
+            bytes.to_vec(),
+            None,
+            None
+        ));
+        assert_ok!(TrustlessFileServer::report_chunk_allocations(
+            RuntimeOrigin::none(),
+            key,
+            0,
+            crate::AllocationsOf::<Test>::default(),
+            authority_proof((b"allocations", key, 0u64, &crate::AllocationsOf::<Test>::default()))
+        ));
+        assert_noop!(
+            TrustlessFileServer::delete_file(RuntimeOrigin::signed(3), key),
+            Error::<Test>::NotRegistrant
+        );
+
+        // the owner leaves, so the file is handed over to the other registrant
+        assert_ok!(TrustlessFileServer::delete_file(RuntimeOrigin::signed(1), key));
+        System::assert_last_event(
+            Event::RegistrantRemoved {
+                who: 1,
+                merkle_root: key,
+                registrants: 1,
+            }
+            .into(),
+        );
+        assert_eq!(Balances::reserved_balance(1), 0);
+        assert_eq!(TrustlessFileServer::get_file(key).unwrap().0, 2);
+        assert!(TrustlessFileServer::get_checksum(key).is_some());
+
+        assert_ok!(TrustlessFileServer::delete_file(RuntimeOrigin::signed(2), key));
+        System::assert_last_event(Event::FileDeleted { merkle_root: key }.into());
+        assert_eq!(Balances::reserved_balance(2), 0);
+        assert_eq!(TrustlessFileServer::get_files(), []);
+        assert_eq!(TrustlessFileServer::get_registrant_count(key), 0);
+        assert!(TrustlessFileServer::get_checksum(key).is_none());
+        // chunk allocations are left for the idle hook
+        assert_eq!(ChunkAllocations::<Test>::iter_prefix(key).count(), 1);
+        assert_noop!(
+            TrustlessFileServer::delete_file(RuntimeOrigin::signed(2), key),
+            Error::<Test>::FileNotFound
+        );
+        TrustlessFileServer::on_idle(1, Weight::MAX);
+        assert_eq!(ChunkAllocations::<Test>::iter_prefix(key).count(), 0);
+    });
+}
+
+#[test]
+fn it_should_reclaim_orphaned_chunks_when_idle() {
+    use crate::{AllocationsOf, ClusterPeerId, OrphanedChunks, WeightInfo};
+
+    new_test_ext().execute_with(|| {
+        System::set_block_number(1);
+        let key = upload_test_file::<Test>(1);
+        let peer = ClusterPeerId::try_from(b"12D3KooWA".to_vec()).unwrap();
+        let allocations = AllocationsOf::<Test>::try_from(vec![peer]).unwrap();
+        for position in 0..3 {
+            assert_ok!(TrustlessFileServer::report_chunk_allocations(
+                RuntimeOrigin::none(),
+                key,
+                position,
+                allocations.clone(),
+                authority_proof((b"allocations", key, position, &allocations))
+            ));
+        }
+        let entry_size = |position: u64| {
+            (ChunkAllocations::<Test>::hashed_key_for(key, position).len()
+                + allocations.encoded_size()) as u64
+        };
+
+        // nothing to reclaim while the file is registered
+        assert_eq!(
+            TrustlessFileServer::on_idle(1, Weight::MAX),
+            <() as WeightInfo>::reclaim_chunks(0)
+        );
+        assert_eq!(ChunkAllocations::<Test>::iter_prefix(key).count(), 3);
+
+        assert_ok!(TrustlessFileServer::delete_file(RuntimeOrigin::signed(1), key));
+        assert!(OrphanedChunks::<Test>::contains_key(key));
+
+        // not even the base weight fits
+        assert_eq!(TrustlessFileServer::on_idle(2, Weight::zero()), Weight::zero());
+        assert_eq!(ChunkAllocations::<Test>::iter_prefix(key).count(), 3);
+
+        // only two chunks fit in the first block
+        let limit = <() as WeightInfo>::reclaim_chunks(2);
+        assert_eq!(TrustlessFileServer::on_idle(2, limit), limit);
+        System::assert_last_event(
+            Event::ChunksReclaimed {
+                count: 2,
+                bytes: entry_size(0) * 2,
+            }
+            .into(),
+        );
+        assert_eq!(ChunkAllocations::<Test>::iter_prefix(key).count(), 1);
+        assert!(OrphanedChunks::<Test>::contains_key(key));
+
+        TrustlessFileServer::on_idle(3, Weight::MAX);
+        System::assert_last_event(
+            Event::ChunksReclaimed {
+                count: 1,
+                bytes: entry_size(0),
+            }
+            .into(),
+        );
+        assert_eq!(ChunkAllocations::<Test>::iter_prefix(key).count(), 0);
+        assert!(!OrphanedChunks::<Test>::contains_key(key));
+    });
+}
+
+#[test]
+fn it_should_keep_the_chunks_of_files_uploaded_again() {
+    use crate::OrphanedChunks;
+
+    new_test_ext().execute_with(|| {
+        System::set_block_number(1);
+        let key = upload_test_file::<Test>(1);
+        assert_ok!(TrustlessFileServer::report_chunk_allocations(
+            RuntimeOrigin::none(),
+            key,
+            0,
+            crate::AllocationsOf::<Test>::default(),
+            authority_proof((b"allocations", key, 0u64, &crate::AllocationsOf::<Test>::default()))
+        ));
+        assert_ok!(TrustlessFileServer::delete_file(RuntimeOrigin::signed(1), key));
+        upload_test_file::<Test>(2);
+
+        TrustlessFileServer::on_idle(2, Weight::MAX);
+        assert!(!OrphanedChunks::<Test>::contains_key(key));
+        assert!(TrustlessFileServer::get_chunk_allocations(key, 0).is_some());
+        assert!(!System::events().iter().any(|record| matches!(
+            record.event,
+            RuntimeEvent::TrustlessFileServer(Event::ChunksReclaimed { .. })
+        )));
+    });
+}
+
+#[test]
+fn it_should_give_asset_deposits_back_on_deletion() {
+    use frame_support::traits::fungibles::Inspect;
+
+    new_test_ext().execute_with(|| {
+        System::set_block_number(1);
+        assert_ok!(TrustlessFileServer::upload_file(
+            RuntimeOrigin::signed(1),
+            vec![1u8; 1024],
+            Some(DEPOSIT_ASSET),
+            None
+        ));
+        let key = H256::decode(&mut TrustlessFileServer::get_files()[0].0.as_slice()).unwrap();
+        assert_ok!(TrustlessFileServer::delete_file(RuntimeOrigin::signed(1), key));
+        assert_eq!(Assets::balance(DEPOSIT_ASSET, 1), INITIAL_BALANCE);
+        assert_eq!(Assets::balance(DEPOSIT_ASSET, TrustlessFileServer::account_id()), 0);
+    });
+}
+
+#[test]
+fn it_should_let_sponsors_pay_for_uploads() {
+    new_test_ext().execute_with(|| {
+        System::set_block_number(1);
+        let bytes = include_bytes!("../img/substrate.png");
+        let deposit = 100 + bytes.len() as u64;
+        // the beneficiary has no funds at all
+        assert_ok!(TrustlessFileServer::sponsor_upload(
+            RuntimeOrigin::signed(1),
+            4,
+            bytes.to_vec()
+        ));
+        let key = H256::decode(&mut TrustlessFileServer::get_files()[0].0.as_slice()).unwrap();
+        System::assert_last_event(
+            Event::UploadSponsored {
+                sponsor: 1,
+                beneficiary: 4,
+                merkle_root: key,
+            }
+            .into(),
+        );
+        assert_eq!(TrustlessFileServer::get_file(key).unwrap().0, 4);
+        assert_eq!(TrustlessFileServer::get_sponsor(key, 4), Some(1));
+        assert_eq!(Balances::reserved_balance(1), deposit);
+        assert_eq!(Balances::reserved_balance(4), 0);
+
+        // sponsoring an already stored file adds one more registrant
+        assert_ok!(TrustlessFileServer::sponsor_upload(
+            RuntimeOrigin::signed(1),
+            2,
+            bytes.to_vec()
+        ));
+        assert_eq!(TrustlessFileServer::get_registrant_count(key), 2);
+        assert_eq!(TrustlessFileServer::get_sponsor(key, 2), Some(1));
+        assert_eq!(Balances::reserved_balance(1), 2 * deposit);
+        assert_eq!(Balances::reserved_balance(2), 0);
+        assert_noop!(
+            TrustlessFileServer::sponsor_upload(RuntimeOrigin::signed(3), 2, bytes.to_vec()),
+            Error::<Test>::AlreadyRegistered
+        );
+    });
+}
+
+#[test]
+fn it_should_give_deposits_back_to_sponsors_on_deletion() {
+    new_test_ext().execute_with(|| {
+        System::set_block_number(1);
+        let bytes = include_bytes!("../img/substrate.png");
+        assert_ok!(TrustlessFileServer::sponsor_upload(
+            RuntimeOrigin::signed(1),
+            4,
+            bytes.to_vec()
+        ));
+        let key = H256::decode(&mut TrustlessFileServer::get_files()[0].0.as_slice()).unwrap();
+        // only the beneficiary is registered, not the sponsor
+        assert_noop!(
+            TrustlessFileServer::delete_file(RuntimeOrigin::signed(1), key),
+            Error::<Test>::NotRegistrant
+        );
+
+        assert_ok!(TrustlessFileServer::delete_file(RuntimeOrigin::signed(4), key));
+        assert_eq!(Balances::reserved_balance(1), 0);
+        assert_eq!(Balances::free_balance(1), INITIAL_BALANCE);
+        assert_eq!(TrustlessFileServer::get_sponsor(key, 4), None);
+        assert_eq!(TrustlessFileServer::get_files(), []);
+    });
+}
+
+#[test]
+fn it_should_upload_batches_of_files() {
+    new_test_ext().execute_with(|| {
+        System::set_block_number(1);
+        let files = vec![vec![1u8; 100], vec![2u8; 200], vec![3u8; 300]];
+        assert_ok!(TrustlessFileServer::upload_files(
+            RuntimeOrigin::signed(1),
+            BoundedVec::try_from(files.clone()).unwrap()
+        ));
+
+        assert_eq!(TrustlessFileServer::get_files().len(), 3);
+        for file in &files {
+            let tree = FileMerkleTree::new(file, &TrustlessFileServer::chunking_params());
+            let key = H256::decode(&mut tree.merkle_root()).unwrap();
+            assert_eq!(TrustlessFileServer::get_file(key).unwrap().0, 1);
+            assert_eq!(TrustlessFileServer::get_registrant_count(key), 1);
+        }
+        // one deposit per file
+        assert_eq!(Balances::reserved_balance(1), 3 * 100 + 600);
+    });
+}
+
+#[test]
+fn it_should_upload_either_the_whole_batch_or_nothing() {
+    new_test_ext().execute_with(|| {
+        System::set_block_number(1);
+        let files = vec![vec![1u8; 100], vec![]];
+        assert_noop!(
+            TrustlessFileServer::upload_files(
+                RuntimeOrigin::signed(1),
+                BoundedVec::try_from(files).unwrap()
+            ),
+            Error::<Test>::EmptyFile
+        );
+        assert_eq!(TrustlessFileServer::get_files(), []);
+        assert_eq!(Balances::reserved_balance(1), 0);
+    });
+}
+
+fn upload_bundle_members(who: u64) -> Vec<H256> {
+    let files = vec![vec![1u8; 100], vec![2u8; 200], vec![3u8; 300]];
+    assert_ok!(TrustlessFileServer::upload_files(
+        RuntimeOrigin::signed(who),
+        BoundedVec::try_from(files.clone()).unwrap()
+    ));
+    files
+        .iter()
+        .map(|file| {
+            let tree = FileMerkleTree::new(file, &TrustlessFileServer::chunking_params());
+            H256::decode(&mut tree.merkle_root()).unwrap()
+        })
+        .collect()
+}
+
+#[test]
+fn it_should_prove_membership_of_bundled_files() {
+    use crate::file_merkle_tree::verify_proof;
+
+    new_test_ext().execute_with(|| {
+        System::set_block_number(1);
+        let members = upload_bundle_members(1);
+        let reserved = Balances::reserved_balance(1);
+        assert_ok!(TrustlessFileServer::create_bundle(
+            RuntimeOrigin::signed(2),
+            BoundedVec::try_from(members.clone()).unwrap()
+        ));
+        let (bundle_root, bundle) = Bundles::<Test>::iter().next().unwrap();
+        System::assert_last_event(
+            Event::BundleCreated {
+                who: 2,
+                bundle_root,
+                members: 3,
+            }
+            .into(),
+        );
+        // the length prefix plus the three roots
+        assert_eq!(bundle.deposit, 100 + 1 + 3 * 32);
+        assert_eq!(Balances::reserved_balance(2), bundle.deposit);
+        assert_eq!(Balances::reserved_balance(1), reserved);
+
+        for (index, member) in members.iter().enumerate() {
+            let (position, proof) =
+                TrustlessFileServer::prove_membership(bundle_root.encode(), member.encode())
+                    .unwrap();
+            assert_eq!(position, index as u32);
+            assert!(verify_proof(bundle_root.as_ref(), position.into(), &member.0, &proof));
+        }
+        assert_eq!(
+            TrustlessFileServer::prove_membership(bundle_root.encode(), H256::zero().encode()),
+            None
+        );
+        assert_eq!(
+            TrustlessFileServer::prove_membership(members[0].encode(), members[0].encode()),
+            None
+        );
+    });
+}
+
+#[test]
+fn it_should_manage_collections_of_files() {
+    use crate::Collections;
+
+    new_test_ext().execute_with(|| {
+        System::set_block_number(1);
+        let members = upload_bundle_members(1);
+        let reserved = Balances::reserved_balance(1);
+        assert_ok!(TrustlessFileServer::create_collection(RuntimeOrigin::signed(1)));
+        System::assert_last_event(
+            Event::CollectionCreated {
+                who: 1,
+                collection_id: 0,
+            }
+            .into(),
+        );
+        // the owner, the length prefix, up to four roots and the deposit itself
+        let deposit = 100 + 8 + 1 + 4 * 32 + 8;
+        assert_eq!(Balances::reserved_balance(1), reserved + deposit);
+
+        let add = |who, merkle_root| {
+            TrustlessFileServer::add_to_collection(RuntimeOrigin::signed(who), 0, merkle_root)
+        };
+        assert_noop!(add(2, members[0]), Error::<Test>::NotCollectionOwner);
+        assert_noop!(add(1, H256::zero()), Error::<Test>::FileNotFound);
+        for member in &members {
+            assert_ok!(add(1, *member));
+        }
+        System::assert_last_event(
+            Event::AddedToCollection {
+                collection_id: 0,
+                merkle_root: members[2],
+            }
+            .into(),
+        );
+        assert_noop!(add(1, members[0]), Error::<Test>::AlreadyInCollection);
+        let other = upload_test_file::<Test>(1);
+        assert_ok!(add(1, other));
+        assert_ok!(TrustlessFileServer::upload_file(
+            RuntimeOrigin::signed(1),
+            vec![4u8; 400],
+            None,
+            None
+        ));
+        let full = FileMerkleTree::new(&[4u8; 400], &TrustlessFileServer::chunking_params());
+        assert_noop!(
+            add(1, H256::decode(&mut full.merkle_root()).unwrap()),
+            Error::<Test>::CollectionFull
+        );
+        assert_ok!(TrustlessFileServer::remove_from_collection(RuntimeOrigin::signed(1), 0, other));
+        assert_noop!(
+            TrustlessFileServer::remove_from_collection(RuntimeOrigin::signed(1), 0, other),
+            Error::<Test>::NotInCollection
+        );
+
+        assert_ok!(TrustlessFileServer::set_collection_price(RuntimeOrigin::signed(1), 0, Some(7)));
+        System::assert_last_event(
+            Event::CollectionPriced {
+                collection_id: 0,
+                price: Some(7),
+                files: 3,
+            }
+            .into(),
+        );
+        for member in &members {
+            assert_eq!(TrustlessFileServer::retrieval_price(*member), Some(7));
+        }
+        assert_eq!(TrustlessFileServer::retrieval_price(other), None);
+        assert_eq!(
+            TrustlessFileServer::collection_info(0).unwrap().members,
+            members.iter().map(Encode::encode).collect::<Vec<_>>()
+        );
+
+        let reserved = Balances::reserved_balance(1);
+        assert_ok!(TrustlessFileServer::remove_collection(RuntimeOrigin::signed(1), 0));
+        System::assert_last_event(Event::CollectionRemoved { collection_id: 0 }.into());
+        assert!(!Collections::<Test>::contains_key(0));
+        assert_eq!(Balances::reserved_balance(1), reserved - deposit);
+    });
+}
+
+#[test]
+fn it_should_transfer_collections_to_registrants() {
+    new_test_ext().execute_with(|| {
+        System::set_block_number(1);
+        let members = upload_bundle_members(1);
+        assert_ok!(TrustlessFileServer::create_collection(RuntimeOrigin::signed(1)));
+        for member in &members {
+            assert_ok!(TrustlessFileServer::add_to_collection(
+                RuntimeOrigin::signed(1),
+                0,
+                *member
+            ));
+        }
+        let deposit = TrustlessFileServer::get_collection(0).unwrap().deposit;
+        let transfer = || TrustlessFileServer::transfer_collection(RuntimeOrigin::signed(1), 0, 2);
+
+        // owners are always registrants of their files
+        assert_noop!(transfer(), Error::<Test>::NotRegistrant);
+        assert_ok!(TrustlessFileServer::upload_files(
+            RuntimeOrigin::signed(2),
+            BoundedVec::try_from(vec![vec![1u8; 100], vec![2u8; 200], vec![3u8; 300]]).unwrap()
+        ));
+        let reserved = Balances::reserved_balance(2);
+        assert_ok!(transfer());
+        System::assert_last_event(
+            Event::CollectionTransferred {
+                collection_id: 0,
+                from: 1,
+                to: 2,
+                files: 3,
+            }
+            .into(),
+        );
+        assert_eq!(TrustlessFileServer::get_collection(0).unwrap().owner, 2);
+        assert_eq!(Balances::reserved_balance(2), reserved + deposit);
+        for member in &members {
+            assert_eq!(FileOwners::<Test>::get(member), Some(2));
+        }
+        assert_noop!(transfer(), Error::<Test>::NotCollectionOwner);
+    });
+}
+
+#[test]
+fn it_should_only_bundle_registered_files() {
+    new_test_ext().execute_with(|| {
+        System::set_block_number(1);
+        let members = upload_bundle_members(1);
+        assert_noop!(
+            TrustlessFileServer::create_bundle(
+                RuntimeOrigin::signed(1),
+                BoundedVec::try_from(vec![members[0]]).unwrap()
+            ),
+            Error::<Test>::BundleTooSmall
+        );
+        assert_noop!(
+            TrustlessFileServer::create_bundle(
+                RuntimeOrigin::signed(1),
+                BoundedVec::try_from(vec![members[0], H256::zero()]).unwrap()
+            ),
+            Error::<Test>::FileNotFound
+        );
+
+        let members = BoundedVec::try_from(members).unwrap();
+        assert_ok!(TrustlessFileServer::create_bundle(RuntimeOrigin::signed(1), members.clone()));
+        assert_noop!(
+            TrustlessFileServer::create_bundle(RuntimeOrigin::signed(2), members),
+            Error::<Test>::BundleExists
+        );
+    });
+}
+
+#[test]
+fn it_should_remove_bundles_as_owner() {
+    new_test_ext().execute_with(|| {
+        System::set_block_number(1);
+        let members = upload_bundle_members(1);
+        assert_ok!(TrustlessFileServer::create_bundle(
+            RuntimeOrigin::signed(2),
+            BoundedVec::try_from(members.clone()).unwrap()
+        ));
+        let bundle_root = Bundles::<Test>::iter_keys().next().unwrap();
+        assert_noop!(
+            TrustlessFileServer::remove_bundle(RuntimeOrigin::signed(1), bundle_root),
+            Error::<Test>::NotBundleOwner
+        );
+
+        assert_ok!(TrustlessFileServer::remove_bundle(RuntimeOrigin::signed(2), bundle_root));
+        System::assert_last_event(Event::BundleRemoved { bundle_root }.into());
+        assert_eq!(Balances::reserved_balance(2), 0);
+        assert!(TrustlessFileServer::get_bundle(bundle_root).is_none());
+        // members are left untouched
+        assert_eq!(TrustlessFileServer::get_files().len(), members.len());
+        assert_noop!(
+            TrustlessFileServer::remove_bundle(RuntimeOrigin::signed(2), bundle_root),
+            Error::<Test>::BundleNotFound
+        );
+    });
+}
+
+#[test]
+fn it_should_migrate_owners_to_registrants() {
+    use crate::migrations::v1;
+    use frame_support::traits::{GetStorageVersion, OnRuntimeUpgrade, StorageVersion};
+
+    new_test_ext().execute_with(|| {
+        let paid = upload_legacy_test_file(1);
+        into_legacy_layout();
+        let tree = FileMerkleTree::new(&[1u8; 1024], &Default::default());
+        let free = H256::decode(&mut tree.merkle_root()).unwrap();
+        migrations::Files::<Test>::insert(free, (2, tree));
+        // back to the old layout
+        for (merkle_root, who) in [(paid, 1), (free, 2)] {
+            Registrants::<Test>::remove(merkle_root, who);
+            RegistrantCount::<Test>::remove(merkle_root);
+        }
+        v1::Deposits::<Test>::insert(paid, (1, Deposit::Native(42)));
+        StorageVersion::new(0).put::<TrustlessFileServer>();
+
+        v1::MigrateToV1::<Test>::on_runtime_upgrade();
+
+        assert_eq!(TrustlessFileServer::on_chain_storage_version(), 1);
+        assert_eq!(TrustlessFileServer::get_registrant(paid, 1), Some(Deposit::Native(42)));
+        assert_eq!(TrustlessFileServer::get_registrant(free, 2), Some(Deposit::Native(0)));
+        assert_eq!(TrustlessFileServer::get_registrant_count(paid), 1);
+        assert_eq!(TrustlessFileServer::get_registrant_count(free), 1);
+        assert!(v1::Deposits::<Test>::get(paid).is_none());
+    });
+}
+
+#[test]
+fn it_should_reject_changes_while_paused() {
+    new_test_ext().execute_with(|| {
+        System::set_block_number(1);
+        let key = upload_test_file::<Test>(1);
+        assert_noop!(
+            TrustlessFileServer::pause(RuntimeOrigin::signed(1)),
+            sp_runtime::traits::BadOrigin
+        );
+        assert_ok!(TrustlessFileServer::pause(RuntimeOrigin::root()));
+        assert!(TrustlessFileServer::is_paused());
+        System::assert_last_event(Event::UploadsPaused.into());
+
+        assert_noop!(
+            TrustlessFileServer::upload_file(RuntimeOrigin::signed(2), vec![1u8; 1024], None, None),
+            Error::<Test>::Paused
+        );
+        assert_noop!(
+            TrustlessFileServer::set_metadata(RuntimeOrigin::signed(1), key, test_metadata()),
+            Error::<Test>::Paused
+        );
+        assert_noop!(
+            TrustlessFileServer::delete_file(RuntimeOrigin::signed(1), key),
+            Error::<Test>::Paused
+        );
+        // offchain worker reports are still accepted
+        assert_ok!(TrustlessFileServer::report_upload_progress(
+            RuntimeOrigin::none(),
+            key,
+            4,
+            12,
+            authority_proof((b"progress", key, 4u32, 12u32))
+        ));
+
+        assert_ok!(TrustlessFileServer::unpause(RuntimeOrigin::root()));
+        assert!(!TrustlessFileServer::is_paused());
+        System::assert_last_event(Event::UploadsResumed.into());
+        assert_ok!(TrustlessFileServer::upload_file(
+            RuntimeOrigin::signed(2),
+            vec![1u8; 1024],
+            None,
+            None
+        ));
+    });
+}
+
+#[test]
+fn it_should_reject_every_call_but_the_exempted_ones_while_paused() {
+    new_test_ext().execute_with(|| {
+        System::set_block_number(1);
+        let key = upload_test_file::<Test>(1);
+        assert_ok!(TrustlessFileServer::pause(RuntimeOrigin::root()));
+
+        let signed = RuntimeOrigin::signed(1);
+        let paused: Vec<sp_runtime::DispatchResult> = vec![
+            TrustlessFileServer::release_alias(signed.clone(), name("logo")),
+            TrustlessFileServer::remove_bundle(signed.clone(), key),
+            TrustlessFileServer::reveal_key(signed.clone(), key, BoundedVec::default()),
+            TrustlessFileServer::submit_key_share(signed.clone(), key, BoundedVec::default()),
+            TrustlessFileServer::report_file(signed.clone(), key, [0; 32]),
+            TrustlessFileServer::resolve_report(RuntimeOrigin::root(), key, 2, Verdict::Dismiss),
+            TrustlessFileServer::schedule_deletion(signed.clone(), key, 10),
+            TrustlessFileServer::cancel_deletion(signed.clone(), key),
+            TrustlessFileServer::reject_large_upload(RuntimeOrigin::root(), key),
+            TrustlessFileServer::attest_file(signed.clone(), key, 2000),
+            TrustlessFileServer::remove_from_collection(signed.clone(), 0, key),
+            TrustlessFileServer::remove_collection(signed, 0),
+            TrustlessFileServer::reject_review(RuntimeOrigin::signed(Reviewer::get()), key),
+            TrustlessFileServer::reassign_alias(RuntimeOrigin::root(), name("logo"), 2),
+        ];
+        for result in paused {
+            assert_eq!(result, Err(Error::<Test>::Paused.into()));
+        }
+
+        // the admin origin can still manage the offchain workers
+        assert_ok!(TrustlessFileServer::set_ocw_authorities(
+            RuntimeOrigin::root(),
+            BoundedVec::truncate_from(vec![UintAuthorityId(OCW_AUTHORITY)])
+        ));
+    });
+}
+
+#[test]
+fn it_should_migrate_metadata_to_licenses() {
+    use crate::migrations::{v2, v8};
+    use crate::Metadata;
+    use frame_support::traits::{GetStorageVersion, OnRuntimeUpgrade, StorageVersion};
+
+    new_test_ext().execute_with(|| {
+        let key = upload_legacy_test_file(1);
+        let metadata = test_metadata();
+        let old = v2::OldFileMetadata::<Test> {
+            name: metadata.name.clone(),
+            mime_type: metadata.mime_type.clone(),
+            tags: metadata.tags.clone(),
+        };
+        frame_support::storage::unhashed::put_raw(
+            &Metadata::<Test>::hashed_key_for(key),
+            &old.encode(),
+        );
+        StorageVersion::new(1).put::<TrustlessFileServer>();
+
+        v2::MigrateToV2::<Test>::on_runtime_upgrade();
+
+        assert_eq!(TrustlessFileServer::on_chain_storage_version(), 2);
+        let migrated = v2::Metadata::<Test>::get(key).unwrap();
+        assert_eq!((migrated.name, migrated.license), (metadata.name.clone(), None));
+
+        // written as of storage version 2, so that later migrations can read it
+        v8::MigrateToV8::<Test>::on_runtime_upgrade();
+        assert_eq!(
+            TrustlessFileServer::get_metadata(key),
+            Some(FileMetadataOf::<Test> {
+                license: None,
+                ..metadata
+            })
+        );
+    });
+}
+
+#[test]
+fn it_should_migrate_metadata_to_retrieval_prices() {
+    use crate::migrations::v8;
+    use crate::Metadata;
+    use frame_support::traits::{GetStorageVersion, OnRuntimeUpgrade, StorageVersion};
+
+    new_test_ext().execute_with(|| {
+        let key = upload_test_file::<Test>(1);
+        let metadata = test_metadata();
+        let old = v8::OldFileMetadata::<Test> {
+            name: metadata.name.clone(),
+            mime_type: metadata.mime_type.clone(),
+            tags: metadata.tags.clone(),
+            license: metadata.license.clone(),
+        };
+        frame_support::storage::unhashed::put_raw(
+            &Metadata::<Test>::hashed_key_for(key),
+            &old.encode(),
+        );
+        StorageVersion::new(7).put::<TrustlessFileServer>();
+
+        v8::MigrateToV8::<Test>::on_runtime_upgrade();
+
+        assert_eq!(TrustlessFileServer::on_chain_storage_version(), 8);
+        assert_eq!(TrustlessFileServer::get_metadata(key), Some(metadata));
+    });
+}
+
+#[test]
+fn it_should_move_reserved_deposits_to_holds() {
+    use crate::migrations::v9;
+    use crate::{HeldBalance, HoldReason};
+    use frame_support::traits::fungible::{InspectHold, MutateHold};
+    use frame_support::traits::tokens::Precision;
+    use frame_support::traits::{
+        GetStorageVersion, OnRuntimeUpgrade, ReservableCurrency, StorageVersion,
+    };
+
+    new_test_ext().execute_with(|| {
+        upload_test_file::<Test>(1);
+        let deposit = Balances::reserved_balance(1);
+        // as deposits were taken up to storage version 8
+        assert_ok!(Balances::release(&HoldReason::FileDeposit, &1, deposit, Precision::Exact));
+        assert_ok!(Balances::reserve(&1, deposit));
+        HeldBalance::<Test>::kill();
+        StorageVersion::new(8).put::<TrustlessFileServer>();
+
+        v9::MigrateToV9::<Test>::on_runtime_upgrade();
+
+        assert_eq!(TrustlessFileServer::on_chain_storage_version(), 9);
+        assert_eq!(TrustlessFileServer::held_balance(), deposit);
+        assert_eq!(Balances::balance_on_hold(&HoldReason::FileDeposit, &1), deposit);
+        assert_eq!(Balances::reserved_balance(1), deposit);
+    });
+}
+
+#[test]
+fn it_should_migrate_names_to_expire() {
+    use crate::migrations::v10;
+    use crate::Names;
+    use frame_support::storage::unhashed::put_raw;
+    use frame_support::traits::{GetStorageVersion, OnRuntimeUpgrade, StorageVersion};
+
+    new_test_ext().execute_with(|| {
+        System::set_block_number(5);
+        let key = upload_test_file::<Test>(1);
+        let old = v10::OldAlias::<Test> {
+            owner: 1,
+            merkle_root: key,
+            deposit: 10,
+        };
+        put_raw(&Names::<Test>::hashed_key_for(name("logo")), &old.encode());
+        StorageVersion::new(9).put::<TrustlessFileServer>();
+
+        v10::MigrateToV10::<Test>::on_runtime_upgrade();
+
+        assert_eq!(TrustlessFileServer::on_chain_storage_version(), 10);
+        let alias = TrustlessFileServer::get_alias(name("logo")).unwrap();
+        assert_eq!((alias.owner, alias.merkle_root), (1, key));
+        assert_eq!((alias.deposit, alias.expires_at), (10, 105));
+    });
+}
+
+#[test]
+fn it_should_migrate_layouts_to_whole_file_cids() {
+    use crate::migrations::v11;
+    use crate::FileMeta;
+    use frame_support::storage::unhashed::put_raw;
+    use frame_support::traits::{GetStorageVersion, OnRuntimeUpgrade, StorageVersion};
+
+    new_test_ext().execute_with(|| {
+        System::set_block_number(1);
+        IpfsChunkSize::set(Some(4096));
+        let key = upload_test_file::<Test>(1);
+        let layout = TrustlessFileServer::get_file_meta(key).unwrap();
+        assert!(layout.cid.is_some());
+        let old = v11::OldFileLayout {
+            size: layout.size,
+            chunk_size: layout.chunk_size,
+            pieces: layout.pieces,
+            bep52: layout.bep52,
+        };
+        put_raw(&FileMeta::<Test>::hashed_key_for(key), &old.encode());
+        StorageVersion::new(10).put::<TrustlessFileServer>();
+
+        v11::MigrateToV11::<Test>::on_runtime_upgrade();
+
+        assert_eq!(TrustlessFileServer::on_chain_storage_version(), 11);
+        assert_eq!(TrustlessFileServer::get_file_meta(key), Some(layout));
+    });
+}
+
+#[test]
+fn it_should_drop_the_unattributed_served_bandwidth() {
+    use crate::migrations::v12;
+    use crate::ServedBandwidth;
+    use frame_support::storage::unhashed::{get_raw, put_raw};
+    use frame_support::storage::StoragePrefixedMap;
+    use frame_support::traits::{GetStorageVersion, OnRuntimeUpgrade, StorageVersion};
+    use frame_support::{Blake2_128Concat, StorageHasher};
+
+    new_test_ext().execute_with(|| {
+        let key = upload_test_file::<Test>(1);
+        // counters were only keyed by the file
+        let prefix = ServedBandwidth::<Test>::final_prefix();
+        let old_key = [prefix.as_slice(), &Blake2_128Concat::hash(&key.encode())].concat();
+        put_raw(&old_key, &(1024u64, 1u64).encode());
+        StorageVersion::new(11).put::<TrustlessFileServer>();
+
+        v12::MigrateToV12::<Test>::on_runtime_upgrade();
+
+        assert_eq!(TrustlessFileServer::on_chain_storage_version(), 12);
+        assert_eq!(get_raw(&old_key), None);
+        assert_eq!(ServedBandwidth::<Test>::iter_keys().count(), 0);
+    });
+}
+
+#[test]
+fn it_should_migrate_to_64_bit_sizes() {
+    use crate::migrations::v3;
+    use crate::{
+        AllocationsOf, ClusterPeerId, PendingUpload, PendingUploads, ProbeResult, ProbeResults,
+        RootClaims,
+    };
+    use frame_support::storage::unhashed::put_raw;
+    use frame_support::traits::{GetStorageVersion, OnRuntimeUpgrade, StorageVersion};
+
+    new_test_ext().execute_with(|| {
+        let key = upload_test_file::<Test>(1);
+        let (owner, tree) = TrustlessFileServer::get_file(key).unwrap();
+        into_legacy_layout();
+        put_raw(
+            &migrations::Files::<Test>::hashed_key_for(key),
+            &(owner, v3::OldFileMerkleTree(tree.clone())).encode(),
+        );
+        let claimed = H256::repeat_byte(1);
+        let old_claim = v3::OldRootClaim {
+            who: 2u64,
+            deposit: 42u64,
+            size: 2048,
+        };
+        put_raw(&RootClaims::<Test>::hashed_key_for(claimed), &old_claim.encode());
+        let pending = H256::repeat_byte(2);
+        let old_upload = v3::OldPendingUpload {
+            who: 2u64,
+            sponsor: None,
+            deposit: crate::DepositOf::<Test>::Native(42),
+            sha256: [7; 32],
+            license: None::<crate::LicenseOf<Test>>,
+            merkle_tree: v3::OldFileMerkleTree(tree.clone()),
+        };
+        put_raw(&PendingUploads::<Test>::hashed_key_for(pending), &old_upload.encode());
+        let old_probe = v3::OldProbeResult {
+            block: 1u64,
+            position: 3,
+            available: true,
+        };
+        put_raw(&ProbeResults::<Test>::hashed_key_for(key), &old_probe.encode());
+        let peer = ClusterPeerId::try_from(b"12D3KooWA".to_vec()).unwrap();
+        let allocations = AllocationsOf::<Test>::try_from(vec![peer]).unwrap();
+        v3::ChunkAllocations::<Test>::insert(key, 11, allocations.clone());
+        StorageVersion::new(2).put::<TrustlessFileServer>();
+
+        v3::MigrateToV3::<Test>::on_runtime_upgrade();
+
+        assert_eq!(TrustlessFileServer::on_chain_storage_version(), 3);
+        assert_eq!(migrations::Files::<Test>::get(key), Some((owner, tree)));
+        assert_eq!(
+            TrustlessFileServer::get_root_claim(claimed),
+            Some(RootClaim {
+                who: 2,
+                deposit: 42,
+                size: 2048
+            })
+        );
+        assert_eq!(
+            TrustlessFileServer::get_pending_upload(pending),
+            Some(PendingUpload {
+                who: 2,
+                sponsor: None,
+                deposit: Deposit::Native(42),
+                sha256: [7; 32],
+                license: None,
+                merkle_tree: tree.clone(),
+            })
+        );
+        assert_eq!(
+            TrustlessFileServer::get_probe_result(key),
+            Some(ProbeResult {
+                block: 1,
+                position: 3,
+                available: true
+            })
+        );
+        assert_eq!(TrustlessFileServer::get_chunk_allocations(key, 11), Some(allocations));
+        assert!(v3::ChunkAllocations::<Test>::get(key, 11).is_none());
+    });
+}
+
+#[test]
+fn it_should_account_for_existing_registrations() {
+    use crate::migrations::v4;
+    use crate::{AccountUsage, UsageRecord};
+    use frame_support::traits::{GetStorageVersion, OnRuntimeUpgrade, StorageVersion};
+
+    new_test_ext().execute_with(|| {
+        upload_test_file::<Test>(1);
+        assert_ok!(TrustlessFileServer::sponsor_upload(
+            RuntimeOrigin::signed(2),
+            3,
+            vec![1u8; 1024]
+        ));
+        let expected: Vec<_> = AccountUsage::<Test>::iter().collect();
+        let _ = AccountUsage::<Test>::clear(u32::MAX, None);
+        into_legacy_layout();
+        StorageVersion::new(3).put::<TrustlessFileServer>();
+
+        v4::MigrateToV4::<Test>::on_runtime_upgrade();
+
+        assert_eq!(TrustlessFileServer::on_chain_storage_version(), 4);
+        assert_eq!(AccountUsage::<Test>::iter().count(), expected.len());
+        for (who, usage) in expected {
+            assert_eq!(TrustlessFileServer::get_usage(who), usage);
+        }
+        assert_eq!(
+            TrustlessFileServer::get_usage(3),
+            UsageRecord {
+                files: 1,
+                bytes: 1024,
+                deposit_locked: 0
+            }
+        );
+    });
+}
+
+#[test]
+fn it_should_enforce_the_global_storage_budget() {
+    new_test_ext().execute_with(|| {
+        System::set_block_number(1);
+        MaxTotalBytes::set(3072);
+        assert_ok!(TrustlessFileServer::upload_file(
+            RuntimeOrigin::signed(1),
+            vec![1u8; 2048],
+            None,
+            None
+        ));
+        assert_eq!(TrustlessFileServer::total_bytes_stored(), 2048);
+        assert_noop!(
+            TrustlessFileServer::upload_file(RuntimeOrigin::signed(1), vec![2u8; 2048], None, None),
+            Error::<Test>::GlobalCapacityExceeded
+        );
+        assert_noop!(
+            TrustlessFileServer::upload_file_with_root(
+                RuntimeOrigin::signed(1),
+                vec![2u8; 2048],
+                H256::repeat_byte(1)
+            ),
+            Error::<Test>::GlobalCapacityExceeded
+        );
+        // registering a stored file again takes no room
+        assert_ok!(TrustlessFileServer::upload_file(
+            RuntimeOrigin::signed(2),
+            vec![1u8; 2048],
+            None,
+            None
+        ));
+        assert_eq!(TrustlessFileServer::total_bytes_stored(), 2048);
+        assert_ok!(TrustlessFileServer::upload_file(
+            RuntimeOrigin::signed(1),
+            vec![3u8; 1024],
+            None,
+            None
+        ));
+        assert_eq!(TrustlessFileServer::total_bytes_stored(), 3072);
+
+        // room is made once no registrant is left
+        let merkle_root = TrustlessFileServer::build_tree(&[1u8; 2048]).unwrap().1;
+        assert_ok!(TrustlessFileServer::delete_file(RuntimeOrigin::signed(1), merkle_root));
+        assert_eq!(TrustlessFileServer::total_bytes_stored(), 3072);
+        assert_ok!(TrustlessFileServer::delete_file(RuntimeOrigin::signed(2), merkle_root));
+        assert_eq!(TrustlessFileServer::total_bytes_stored(), 1024);
+        assert_ok!(TrustlessFileServer::upload_file(
+            RuntimeOrigin::signed(1),
+            vec![2u8; 2048],
+            None,
+            None
+        ));
+        assert_eq!(TrustlessFileServer::total_bytes_stored(), 3072);
+    });
+}
+
+#[test]
+fn it_should_account_for_the_bytes_already_stored() {
+    use crate::migrations::v5;
+    use crate::TotalBytesStored;
+    use frame_support::traits::{GetStorageVersion, OnRuntimeUpgrade, StorageVersion};
+
+    new_test_ext().execute_with(|| {
+        System::set_block_number(1);
+        upload_test_file::<Test>(1);
+        assert_ok!(TrustlessFileServer::upload_file(
+            RuntimeOrigin::signed(2),
+            vec![1u8; 1024],
+            None,
+            None
+        ));
+        let expected = TrustlessFileServer::total_bytes_stored();
+        assert_eq!(expected, include_bytes!("../img/substrate.png").len() as u64 + 1024);
+        TotalBytesStored::<Test>::kill();
+        into_legacy_layout();
+        StorageVersion::new(4).put::<TrustlessFileServer>();
+
+        v5::MigrateToV5::<Test>::on_runtime_upgrade();
+
+        assert_eq!(TrustlessFileServer::on_chain_storage_version(), 5);
+        assert_eq!(TrustlessFileServer::total_bytes_stored(), expected);
+    });
+}
+
+#[test]
+fn it_should_split_files_into_owners_trees_and_layouts() {
+    use crate::migrations::{v11, v7};
+    use crate::FileLayout;
+    use frame_support::traits::{GetStorageVersion, OnRuntimeUpgrade, StorageVersion};
+
+    new_test_ext().execute_with(|| {
+        System::set_block_number(1);
+        let key = upload_test_file::<Test>(1);
+        let (owner, tree) = TrustlessFileServer::get_file(key).unwrap();
+        let layout = TrustlessFileServer::get_file_meta(key).unwrap();
+        assert_eq!(
+            layout,
+            FileLayout {
+                size: tree.file_size,
+                chunk_size: tree.chunk_size,
+                pieces: tree.pieces(),
+                bep52: false,
+                cid: None,
+            }
+        );
+        into_legacy_layout();
+        StorageVersion::new(6).put::<TrustlessFileServer>();
+
+        v7::MigrateToV7::<Test>::on_runtime_upgrade();
+
+        assert_eq!(TrustlessFileServer::on_chain_storage_version(), 7);
+        assert!(migrations::Files::<Test>::get(key).is_none());
+        assert_eq!(TrustlessFileServer::get_owner(key), Some(owner));
+        assert_eq!(TrustlessFileServer::get_file_tree(key), Some(tree));
+        let migrated = v7::FileMeta::<Test>::get(key).unwrap();
+        assert_eq!((migrated.size, migrated.pieces), (layout.size, layout.pieces));
+
+        // written as of storage version 7, so that later migrations can read it
+        v11::MigrateToV11::<Test>::on_runtime_upgrade();
+        assert_eq!(TrustlessFileServer::get_file_meta(key), Some(layout));
+    });
+}
+
+#[test]
+fn it_should_index_the_owners_of_files() {
+    new_test_ext().execute_with(|| {
+        System::set_block_number(1);
+        let key = upload_test_file::<Test>(1);
+        assert!(TrustlessFileServer::exists(key.encode()));
+        assert_eq!(TrustlessFileServer::owner(key.encode()), Some(1));
+        assert!(!TrustlessFileServer::exists(H256::zero().encode()));
+        assert_eq!(TrustlessFileServer::owner(H256::zero().encode()), None);
+        assert!(!TrustlessFileServer::exists(vec![1, 2, 3]));
+
+        // ownership passes to the next registrant
+        assert_ok!(TrustlessFileServer::upload_file(
+            RuntimeOrigin::signed(2),
+            include_bytes!("../img/substrate.png").to_vec(),
+            None,
+            None
+        ));
+        assert_ok!(TrustlessFileServer::delete_file(RuntimeOrigin::signed(1), key));
+        assert_eq!(TrustlessFileServer::owner(key.encode()), Some(2));
+
+        assert_ok!(TrustlessFileServer::delete_file(RuntimeOrigin::signed(2), key));
+        assert!(!TrustlessFileServer::exists(key.encode()));
+        assert_eq!(TrustlessFileServer::get_owner(key), None);
+    });
+}
+
+#[test]
+fn it_should_index_the_owners_of_the_files_already_stored() {
+    use crate::migrations::v6;
+    use frame_support::traits::{GetStorageVersion, OnRuntimeUpgrade, StorageVersion};
+
+    new_test_ext().execute_with(|| {
+        System::set_block_number(1);
+        let key = upload_test_file::<Test>(1);
+        into_legacy_layout();
+        StorageVersion::new(5).put::<TrustlessFileServer>();
+        assert!(!TrustlessFileServer::exists(key.encode()));
+
+        v6::MigrateToV6::<Test>::on_runtime_upgrade();
+
+        assert_eq!(TrustlessFileServer::on_chain_storage_version(), 6);
+        assert_eq!(TrustlessFileServer::get_owner(key), Some(1));
+    });
+}
+
+/// Expects the offchain worker to push `chunk` to the IPFS node.
+fn expect_block_put(state: &RwLock<OffchainState>, chunk: &[u8]) {
+    let cid = ipfs::ipfs_get_hash_from_sha256(&sha2_256(chunk));
+    state.write().expect_request(PendingRequest {
+        method: "POST".into(),
+        uri: "http://127.0.0.1:5001/api/v0/block/put".into(),
+        headers: vec![(
+            "Content-Type".into(),
+            "multipart/form-data; boundary=------BOUNDARY".into(),
+        )],
+        body: ipfs::make_multipart(chunk),
+        response: Some(format!(r#"{{"Key":"{}","Size":{}}}"#, cid, chunk.len()).into_bytes()),
+        sent: true,
+        ..Default::default()
+    });
+}
+
+/// Expects the offchain worker to add `chunk` through the IPFS Cluster, answering with
+/// `response`.
+fn expect_cluster_add(state: &RwLock<OffchainState>, chunk: &[u8], response: &str) {
+    state.write().expect_request(PendingRequest {
+        method: "POST".into(),
+        uri: "http://127.0.0.1:9094/add?cid-version=1&raw-leaves=true&replication-min=3&replication-max=3".into(),
+        headers: vec![(
+            "Content-Type".into(),
+            "multipart/form-data; boundary=------BOUNDARY".into(),
+        )],
+        body: ipfs::make_multipart(chunk),
+        response: Some(response.as_bytes().to_vec()),
+        sent: true,
+        ..Default::default()
+    });
+}
+
+/// Drains the transactions submitted by the offchain worker.
+fn submitted_calls(pool: &RwLock<PoolState>) -> Vec<crate::Call<Test>> {
+    pool.write()
+        .transactions
+        .drain(..)
+        .map(|tx| {
+            let tx = UncheckedExtrinsic::decode(&mut &*tx).unwrap();
+            assert!(tx.signature.is_none());
+            match tx.function {
+                RuntimeCall::TrustlessFileServer(call) => call,
+                call => panic!("unexpected call {:?}", call),
+            }
+        })
+        .collect()
+}
+
+/// Uploads `content` as the first file of the block and returns its merkle root and chunks.
+fn upload_for_offchain_worker(content: &[u8]) -> (H256, Vec<Vec<u8>>) {
+    System::set_block_number(1);
+    assert_ok!(TrustlessFileServer::upload_file(
+        RuntimeOrigin::signed(1),
+        content.to_vec(),
+        None,
+        None
+    ));
+    let merkle_root = H256::decode(&mut TrustlessFileServer::get_files()[0].0.as_slice()).unwrap();
+    let tree = TrustlessFileServer::get_file_tree(merkle_root).unwrap();
+    let chunks = content
+        .chunks(tree.chunk_size())
+        .map(|chunk| chunk.to_vec())
+        .collect();
+    (merkle_root, chunks)
+}
+
+fn run_offchain_worker(block_number: u64) {
+    if block_number > System::block_number() {
+        System::set_block_number(block_number);
+        TrustlessFileServer::on_initialize(block_number);
+    }
+    TrustlessFileServer::offchain_worker(block_number);
+}
+
+#[test]
+fn offchain_worker_should_push_indexed_chunks_to_ipfs() {
+    let (mut ext, state, pool) = new_offchain_test_ext();
+    let content = (0..10 * 1024).map(|i| (i / 1024) as u8).collect::<Vec<_>>();
+    let (merkle_root, chunks) = ext.execute_with(|| upload_for_offchain_worker(&content));
+    assert_eq!(chunks.len(), 10);
+    ext.persist_offchain_overlay();
+
+    ext.execute_with(|| {
+        let progress = |done: u32| crate::Call::<Test>::report_upload_progress {
+            merkle_root,
+            done,
+            total: 10,
+            proof: authority_proof((b"progress", merkle_root, done, 10u32)),
+        };
+        // at most `MaxChunksPerOcwRun` chunks per run, reporting every 4 of them
+        chunks[..8]
+            .iter()
+            .for_each(|chunk| expect_block_put(&state, chunk));
+        run_offchain_worker(1);
+        assert_eq!(submitted_calls(&pool), vec![progress(4), progress(8)]);
+
+        // the rest is carried over to the next run
+        chunks[8..]
+            .iter()
+            .for_each(|chunk| expect_block_put(&state, chunk));
+        run_offchain_worker(2);
+        assert_eq!(submitted_calls(&pool), vec![progress(10)]);
+
+        // nothing left, any request would fail for lack of a mocked response
+        run_offchain_worker(3);
+        assert!(submitted_calls(&pool).is_empty());
+    });
+}
+
+#[test]
+fn offchain_worker_should_only_run_on_authority_nodes() {
+    let (mut ext, state, pool) = new_offchain_test_ext();
+    let (merkle_root, chunks) = ext.execute_with(|| upload_for_offchain_worker(&[1u8; 2048]));
+    ext.persist_offchain_overlay();
+
+    ext.execute_with(|| {
+        // nobody does the work while there are no authorities, as no report would be accepted
+        crate::OcwAuthorities::<Test>::kill();
+        run_offchain_worker(1);
+        assert!(submitted_calls(&pool).is_empty());
+
+        assert_noop!(
+            TrustlessFileServer::set_ocw_authorities(
+                RuntimeOrigin::signed(1),
+                BoundedVec::truncate_from(vec![UintAuthorityId(1)])
+            ),
+            sp_runtime::traits::BadOrigin
+        );
+        assert_ok!(TrustlessFileServer::set_ocw_authorities(
+            RuntimeOrigin::root(),
+            BoundedVec::truncate_from(vec![UintAuthorityId(1)])
+        ));
+        System::assert_last_event(Event::OcwAuthoritiesSet { count: 1 }.into());
+
+        // any request would fail for lack of a mocked response
+        UintAuthorityId::set_all_keys(vec![2u64]);
+        run_offchain_worker(1);
+        assert!(submitted_calls(&pool).is_empty());
+
+        UintAuthorityId::set_all_keys(vec![1u64, 2]);
+        chunks
+            .iter()
+            .for_each(|chunk| expect_block_put(&state, chunk));
+        run_offchain_worker(1);
+        assert_eq!(
+            submitted_calls(&pool),
+            vec![crate::Call::<Test>::report_upload_progress {
+                merkle_root,
+                done: 2,
+                total: 2,
+                proof: authority_proof((b"progress", merkle_root, 2u32, 2u32)),
+            }]
+        );
+    });
+}
+
+#[test]
+fn offchain_worker_should_retry_chunks_that_fail_to_upload() {
+    IpfsClusterUrl::set(Some("http://127.0.0.1:9094"));
+    let (mut ext, state, pool) = new_offchain_test_ext();
+    let (merkle_root, chunks) = ext.execute_with(|| upload_for_offchain_worker(&[1u8; 2048]));
+    ext.persist_offchain_overlay();
+
+    ext.execute_with(|| {
+        let cid = ipfs::ipfs_get_hash_from_sha256(&sha2_256(&chunks[0]));
+        let response =
+            format!(r#"{{"name":"blob","cid":"{}","allocations":["12D3KooWA","12D3KooWB"]}}"#, cid);
+        let allocations = |position: u64| {
+            let allocations = crate::AllocationsOf::<Test>::try_from(vec![
+                BoundedVec::try_from(b"12D3KooWA".to_vec()).unwrap(),
+                BoundedVec::try_from(b"12D3KooWB".to_vec()).unwrap(),
+            ])
+            .unwrap();
+            crate::Call::<Test>::report_chunk_allocations {
+                merkle_root,
+                position,
+                proof: authority_proof((b"allocations", merkle_root, position, &allocations)),
+                allocations,
+            }
+        };
+
+        // the second chunk is rejected by the cluster, so no progress is reported
+        expect_cluster_add(&state, &chunks[0], &response);
+        expect_cluster_add(&state, &chunks[1], "internal error");
+        run_offchain_worker(1);
+        assert_eq!(submitted_calls(&pool), vec![allocations(0)]);
+
+        // and it is retried on the next run
+        expect_cluster_add(&state, &chunks[1], &response);
+        run_offchain_worker(2);
+        assert_eq!(
+            submitted_calls(&pool),
+            vec![
+                allocations(1),
+                crate::Call::<Test>::report_upload_progress {
+                    merkle_root,
+                    done: 2,
+                    total: 2,
+                    proof: authority_proof((b"progress", merkle_root, 2u32, 2u32)),
+                },
+            ]
+        );
+    });
+}
+
+fn dead_letters() -> Vec<DeadLetter<H256>> {
+    StorageValueRef::persistent(DEAD_LETTERS_KEY)
+        .get()
+        .unwrap()
+        .unwrap_or_default()
+}
+
+#[test]
+fn offchain_worker_should_give_up_on_files_failing_too_many_times() {
+    IpfsClusterUrl::set(Some("http://127.0.0.1:9094"));
+    let (mut ext, state, _pool) = new_offchain_test_ext();
+    let (merkle_root, chunks) = ext.execute_with(|| upload_for_offchain_worker(&[1u8; 1024]));
+    ext.persist_offchain_overlay();
+
+    ext.execute_with(|| {
+        let retries_key = [UPLOAD_RETRIES_KEY, &merkle_root.encode()].concat();
+        let retries = || {
+            StorageValueRef::persistent(&retries_key)
+                .get::<u32>()
+                .unwrap()
+        };
+        for run in 1..MAX_UPLOAD_RETRIES {
+            expect_cluster_add(&state, &chunks[0], "internal error");
+            run_offchain_worker(run as u64);
+            assert_eq!(retries(), Some(run));
+        }
+        assert!(dead_letters().is_empty());
+
+        expect_cluster_add(&state, &chunks[0], "internal error");
+        run_offchain_worker(MAX_UPLOAD_RETRIES as u64);
+        assert_eq!(retries(), None);
+        assert_eq!(
+            dead_letters(),
+            vec![DeadLetter {
+                merkle_root,
+                next_chunk: 0,
+                retries: MAX_UPLOAD_RETRIES,
+                reason: DeadLetterReason::TooManyRetries,
+            }]
+        );
+        let queue = StorageValueRef::persistent(QUEUE_KEY).get::<Vec<QueuedUpload<H256>>>();
+        assert_eq!(queue.unwrap(), Some(vec![]));
+    });
+}
+
+#[test]
+fn offchain_worker_should_give_up_on_files_with_no_indexed_content() {
+    let (mut ext, _state, _pool) = new_offchain_test_ext();
+    let (merkle_root, _) = ext.execute_with(|| upload_for_offchain_worker(&[1u8; 1024]));
+    ext.persist_offchain_overlay();
+
+    ext.execute_with(|| {
+        StorageValueRef::persistent(&TrustlessFileServer::derived_key(&merkle_root)).clear();
+        run_offchain_worker(1);
+        assert_eq!(
+            dead_letters(),
+            vec![DeadLetter {
+                merkle_root,
+                next_chunk: 0,
+                retries: 0,
+                reason: DeadLetterReason::MissingContent,
+            }]
+        );
+    });
+}
+
+#[test]
+fn offchain_worker_should_record_remote_pin_requests_by_cid() {
+    PinningServiceUrl::set(Some("https://pins.example.com"));
+    let (mut ext, state, pool) = new_offchain_test_ext();
+    let (merkle_root, chunks) = ext.execute_with(|| upload_for_offchain_worker(&[1u8; 1024]));
+    ext.persist_offchain_overlay();
+
+    ext.execute_with(|| {
+        sp_io::offchain::local_storage_set(
+            StorageKind::PERSISTENT,
+            b"pallet_trustless_file_server::pinning-token",
+            b"secret",
+        );
+        let cid = ipfs::ipfs_get_hash_from_sha256(&sha2_256(&chunks[0]));
+        expect_block_put(&state, &chunks[0]);
+        state.write().expect_request(PendingRequest {
+            method: "POST".into(),
+            uri: "https://pins.example.com/pins".into(),
+            headers: vec![
+                ("Content-Type".into(), "application/json".into()),
+                ("Authorization".into(), "Bearer secret".into()),
+            ],
+            body: format!(r#"{{"cid":"{}","name":"{:?}-0"}}"#, cid, merkle_root).into_bytes(),
+            response: Some(br#"{"requestid":"pin-0","status":"queued"}"#.to_vec()),
+            sent: true,
+            ..Default::default()
+        });
+        run_offchain_worker(1);
+
+        let key = [
+            b"pallet_trustless_file_server::pin/".as_slice(),
+            cid.as_bytes(),
+        ]
+        .concat();
+        assert_eq!(StorageValueRef::persistent(&key).get::<Vec<u8>>(), Ok(Some(b"pin-0".to_vec())));
+        assert_eq!(
+            submitted_calls(&pool),
+            vec![crate::Call::<Test>::report_upload_progress {
+                merkle_root,
+                done: 1,
+                total: 1,
+                proof: authority_proof((b"progress", merkle_root, 1u32, 1u32)),
+            }]
+        );
+    });
+}
+
+#[test]
+fn offchain_worker_should_not_contact_ipfs_in_dry_runs() {
+    let (mut ext, _state, pool) = new_offchain_test_ext();
+    let (merkle_root, _) = ext.execute_with(|| upload_for_offchain_worker(&[1u8; 1024]));
+    ext.persist_offchain_overlay();
+
+    ext.execute_with(|| {
+        sp_io::offchain::local_storage_set(
+            StorageKind::PERSISTENT,
+            b"pallet_trustless_file_server::dry-run",
+            &[1],
+        );
+        // no request is expected, so any made would panic
+        run_offchain_worker(1);
+
+        let queue = StorageValueRef::persistent(QUEUE_KEY).get::<Vec<QueuedUpload<H256>>>();
+        assert_eq!(
+            queue.unwrap(),
+            Some(vec![QueuedUpload {
+                merkle_root,
+                next_chunk: 0,
+            }])
+        );
+        assert!(submitted_calls(&pool).is_empty());
+        assert!(dead_letters().is_empty());
+    });
+}
+
+fn name(name: &str) -> NameOf<Test> {
+    NameOf::<Test>::try_from(name.as_bytes().to_vec()).unwrap()
+}
+
+#[test]
+fn it_should_register_unique_aliases() {
+    new_test_ext().execute_with(|| {
+        System::set_block_number(1);
+        let key = upload_test_file::<Test>(1);
+        assert_ok!(TrustlessFileServer::register_alias(
+            RuntimeOrigin::signed(1),
+            name("myapp/logo.png"),
+            key
+        ));
+        System::assert_last_event(
+            Event::<Test>::AliasRegistered {
+                who: 1,
+                name: name("myapp/logo.png"),
+                merkle_root: key,
+            }
+            .into(),
+        );
+        assert_eq!(
+            TrustlessFileServer::resolve_alias(b"myapp/logo.png".to_vec()),
+            Some(key.encode())
+        );
+        assert_eq!(TrustlessFileServer::resolve_alias(b"myapp/other.png".to_vec()), None);
+
+        // names belong to whoever registered them first
+        assert_ok!(TrustlessFileServer::upload_file(
+            RuntimeOrigin::signed(2),
+            vec![1u8; 1024],
+            None,
+            None
+        ));
+        let other = FileOwners::<Test>::iter_keys()
+            .find(|root| *root != key)
+            .unwrap();
+        assert_noop!(
+            TrustlessFileServer::register_alias(
+                RuntimeOrigin::signed(2),
+                name("myapp/logo.png"),
+                other
+            ),
+            Error::<Test>::NameTaken
+        );
+        assert_ok!(TrustlessFileServer::register_alias(
+            RuntimeOrigin::signed(1),
+            name("myapp/logo.png"),
+            other
+        ));
+        assert_eq!(
+            TrustlessFileServer::resolve_alias(b"myapp/logo.png".to_vec()),
+            Some(other.encode())
+        );
+
+        assert_noop!(
+            TrustlessFileServer::register_alias(
+                RuntimeOrigin::signed(1),
+                name("logo"),
+                H256::zero()
+            ),
+            Error::<Test>::FileNotFound
+        );
+        for invalid in [
+            b"".to_vec(),
+            b"my logo".to_vec(),
+            b"logo\n".to_vec(),
+            vec![0xff, 0xfe],
+        ] {
+            assert_noop!(
+                TrustlessFileServer::register_alias(
+                    RuntimeOrigin::signed(1),
+                    NameOf::<Test>::try_from(invalid).unwrap(),
+                    key
+                ),
+                Error::<Test>::InvalidName
+            );
+        }
+    });
+}
+
+#[test]
+fn it_should_reserve_name_deposits_until_released() {
+    new_test_ext().execute_with(|| {
+        System::set_block_number(1);
+        let key = upload_test_file::<Test>(1);
+        let reserved = Balances::reserved_balance(1);
+        assert_ok!(TrustlessFileServer::register_alias(
+            RuntimeOrigin::signed(1),
+            name("logo"),
+            key
+        ));
+        assert_eq!(Balances::reserved_balance(1), reserved + 14);
+        // pointing the name somewhere else takes no further deposit
+        assert_ok!(TrustlessFileServer::register_alias(
+            RuntimeOrigin::signed(1),
+            name("logo"),
+            key
+        ));
+        assert_eq!(Balances::reserved_balance(1), reserved + 14);
+
+        assert_noop!(
+            TrustlessFileServer::release_alias(RuntimeOrigin::signed(2), name("logo")),
+            Error::<Test>::NotNameOwner
+        );
+        assert_noop!(
+            TrustlessFileServer::release_alias(RuntimeOrigin::signed(1), name("other")),
+            Error::<Test>::NameNotFound
+        );
+        assert_ok!(TrustlessFileServer::release_alias(RuntimeOrigin::signed(1), name("logo")));
+        System::assert_last_event(
+            Event::<Test>::AliasReleased {
+                who: 1,
+                name: name("logo"),
+            }
+            .into(),
+        );
+        assert_eq!(Balances::reserved_balance(1), reserved);
+        assert_eq!(TrustlessFileServer::get_alias(name("logo")), None);
+    });
+}
+
+#[test]
+fn it_should_not_resolve_aliases_of_deleted_files() {
+    new_test_ext().execute_with(|| {
+        System::set_block_number(1);
+        let key = upload_test_file::<Test>(1);
+        assert_ok!(TrustlessFileServer::register_alias(
+            RuntimeOrigin::signed(1),
+            name("logo"),
+            key
+        ));
+        assert_ok!(TrustlessFileServer::delete_file(RuntimeOrigin::signed(1), key));
+        assert_eq!(TrustlessFileServer::resolve_alias(b"logo".to_vec()), None);
+        // the name is kept, so its owner can point it to another file
+        assert!(TrustlessFileServer::get_alias(name("logo")).is_some());
+    });
+}
+
+#[test]
+fn it_should_let_anyone_register_expired_names() {
+    new_test_ext().execute_with(|| {
+        System::set_block_number(1);
+        let key = upload_test_file::<Test>(1);
+        let reserved = Balances::reserved_balance(1);
+        assert_ok!(TrustlessFileServer::register_alias(
+            RuntimeOrigin::signed(1),
+            name("logo"),
+            key
+        ));
+        assert_eq!(
+            TrustlessFileServer::get_alias(name("logo"))
+                .unwrap()
+                .expires_at,
+            101
+        );
+
+        System::set_block_number(100);
+        assert_eq!(TrustlessFileServer::resolve_alias(b"logo".to_vec()), Some(key.encode()));
+        assert_noop!(
+            TrustlessFileServer::register_alias(RuntimeOrigin::signed(2), name("logo"), key),
+            Error::<Test>::NameTaken
+        );
+
+        System::set_block_number(101);
+        assert_eq!(TrustlessFileServer::resolve_alias(b"logo".to_vec()), None);
+        let reserved_2 = Balances::reserved_balance(2);
+        assert_ok!(TrustlessFileServer::register_alias(
+            RuntimeOrigin::signed(2),
+            name("logo"),
+            key
+        ));
+        assert_eq!(Balances::reserved_balance(1), reserved);
+        assert_eq!(Balances::reserved_balance(2), reserved_2 + 14);
+        let alias = TrustlessFileServer::get_alias(name("logo")).unwrap();
+        assert_eq!((alias.owner, alias.expires_at), (2, 201));
+        assert_eq!(TrustlessFileServer::resolve_alias(b"logo".to_vec()), Some(key.encode()));
+    });
+}
+
+#[test]
+fn it_should_renew_names() {
+    new_test_ext().execute_with(|| {
+        System::set_block_number(1);
+        let key = upload_test_file::<Test>(1);
+        let reserved = Balances::reserved_balance(1);
+        assert_ok!(TrustlessFileServer::register_alias(
+            RuntimeOrigin::signed(1),
+            name("logo"),
+            key
+        ));
+
+        assert_noop!(
+            TrustlessFileServer::renew_alias(RuntimeOrigin::signed(2), name("logo")),
+            Error::<Test>::NotNameOwner
+        );
+        assert_noop!(
+            TrustlessFileServer::renew_alias(RuntimeOrigin::signed(1), name("other")),
+            Error::<Test>::NameNotFound
+        );
+
+        // expired names can be renewed as long as nobody else has taken them
+        System::set_block_number(150);
+        assert_ok!(TrustlessFileServer::renew_alias(RuntimeOrigin::signed(1), name("logo")));
+        System::assert_last_event(
+            Event::<Test>::AliasRenewed {
+                who: 1,
+                name: name("logo"),
+                expires_at: 250,
+            }
+            .into(),
+        );
+        assert_eq!(
+            TrustlessFileServer::get_alias(name("logo"))
+                .unwrap()
+                .expires_at,
+            250
+        );
+        assert_eq!(TrustlessFileServer::resolve_alias(b"logo".to_vec()), Some(key.encode()));
+        assert_eq!(Balances::reserved_balance(1), reserved + 14);
+    });
+}
+
+#[test]
+fn it_should_let_moderators_reassign_names() {
+    new_test_ext().execute_with(|| {
+        System::set_block_number(1);
+        let key = upload_test_file::<Test>(1);
+        let reserved = Balances::reserved_balance(1);
+        let issuance = Balances::total_issuance();
+        assert_ok!(TrustlessFileServer::register_alias(
+            RuntimeOrigin::signed(1),
+            name("logo"),
+            key
+        ));
+
+        assert_noop!(
+            TrustlessFileServer::reassign_alias(RuntimeOrigin::signed(2), name("logo"), 2),
+            sp_runtime::traits::BadOrigin
+        );
+        assert_noop!(
+            TrustlessFileServer::reassign_alias(RuntimeOrigin::root(), name("other"), 2),
+            Error::<Test>::NameNotFound
+        );
+
+        System::set_block_number(10);
+        assert_ok!(TrustlessFileServer::reassign_alias(RuntimeOrigin::root(), name("logo"), 2));
+        System::assert_last_event(
+            Event::<Test>::AliasReassigned {
+                name: name("logo"),
+                from: 1,
+                to: 2,
+                slashed: 14,
+            }
+            .into(),
+        );
+        assert_eq!(Balances::reserved_balance(1), reserved);
+        assert_eq!(Balances::total_issuance(), issuance - 14);
+        let alias = TrustlessFileServer::get_alias(name("logo")).unwrap();
+        assert_eq!((alias.owner, alias.deposit, alias.expires_at), (2, 0, 110));
+        assert_eq!(TrustlessFileServer::resolve_alias(b"logo".to_vec()), Some(key.encode()));
+
+        // renewing takes the deposit from the new owner
+        let reserved_2 = Balances::reserved_balance(2);
+        assert_ok!(TrustlessFileServer::renew_alias(RuntimeOrigin::signed(2), name("logo")));
+        assert_eq!(Balances::reserved_balance(2), reserved_2 + 14);
+    });
+}
+
+#[test]
+fn it_should_record_repins_at_most_twice_per_interval() {
+    use frame_support::pallet_prelude::ValidateUnsigned;
+    use sp_runtime::transaction_validity::{InvalidTransaction, TransactionSource};
+
+    new_test_ext().execute_with(|| {
+        System::set_block_number(1);
+        let key = upload_test_file::<Test>(1);
+        assert_ok!(TrustlessFileServer::report_repin(
+            RuntimeOrigin::none(),
+            key,
+            authority_proof((b"repin", key))
+        ));
+        assert_eq!(TrustlessFileServer::get_last_pinned_at(key), Some(1));
+        System::assert_last_event(Event::<Test>::FileRepinned { merkle_root: key }.into());
+
+        // `RepinInterval` is 50 blocks
+        System::set_block_number(25);
+        assert_noop!(
+            TrustlessFileServer::report_repin(
+                RuntimeOrigin::none(),
+                key,
+                authority_proof((b"repin", key))
+            ),
+            Error::<Test>::RepinNotDue
+        );
+        let call = crate::Call::<Test>::report_repin {
+            merkle_root: key,
+            proof: authority_proof((b"repin", key)),
+        };
+        assert_eq!(
+            TrustlessFileServer::validate_unsigned(TransactionSource::External, &call),
+            Err(InvalidTransaction::Stale.into())
+        );
+        System::set_block_number(26);
+        assert!(TrustlessFileServer::validate_unsigned(TransactionSource::External, &call).is_ok());
+        assert_ok!(TrustlessFileServer::report_repin(
+            RuntimeOrigin::none(),
+            key,
+            authority_proof((b"repin", key))
+        ));
+        assert_eq!(TrustlessFileServer::get_last_pinned_at(key), Some(26));
+
+        assert_noop!(
+            TrustlessFileServer::report_repin(
+                RuntimeOrigin::none(),
+                H256::zero(),
+                authority_proof((b"repin", H256::zero()))
+            ),
+            Error::<Test>::FileNotFound
+        );
+        assert_noop!(
+            TrustlessFileServer::report_repin(
+                RuntimeOrigin::signed(1),
+                key,
+                authority_proof((b"repin", key))
+            ),
+            sp_runtime::traits::BadOrigin
+        );
+
+        assert_ok!(TrustlessFileServer::delete_file(RuntimeOrigin::signed(1), key));
+        assert_eq!(TrustlessFileServer::get_last_pinned_at(key), None);
+    });
+}
+
+#[test]
+fn offchain_worker_should_pin_files_again_in_a_rotating_window() {
+    let (mut ext, state, pool) = new_offchain_test_ext();
+    let files = ext.execute_with(|| {
+        System::set_block_number(1);
+        for (who, byte) in [(1, 1u8), (2, 2), (3, 3)] {
+            assert_ok!(TrustlessFileServer::upload_file(
+                RuntimeOrigin::signed(who),
+                vec![byte; 1024],
+                None,
+                None
+            ));
+        }
+        // in the order the window walks them
+        FileTrees::<Test>::iter()
+            .map(|(merkle_root, tree)| {
+                let cid = ipfs::ipfs_get_hash_from_sha256(&tree.file_chunk_hash_at(0).unwrap());
+                (merkle_root, cid)
+            })
+            .collect::<Vec<_>>()
+    });
+    ext.persist_offchain_overlay();
+
+    ext.execute_with(|| {
+        for byte in [1u8, 2, 3] {
+            expect_block_put(&state, &[byte; 1024]);
+        }
+        run_offchain_worker(1);
+        submitted_calls(&pool);
+
+        let expect_pin = |(_, cid): &(H256, String)| {
+            state.write().expect_request(PendingRequest {
+                method: "POST".into(),
+                uri: format!("http://127.0.0.1:5001/api/v0/pin/add?arg={}", cid),
+                response: Some(format!(r#"{{"Pins":["{}"]}}"#, cid).into_bytes()),
+                sent: true,
+                ..Default::default()
+            });
+        };
+        let repin = |(merkle_root, _): &(H256, String)| crate::Call::<Test>::report_repin {
+            merkle_root: *merkle_root,
+            proof: authority_proof((b"repin", merkle_root)),
+        };
+
+        // `MaxRepinsPerRun` is 2 files every `RepinInterval` of 50 blocks
+        files[..2].iter().for_each(expect_pin);
+        run_offchain_worker(50);
+        assert_eq!(submitted_calls(&pool), files[..2].iter().map(repin).collect::<Vec<_>>());
+
+        files[2..].iter().for_each(expect_pin);
+        run_offchain_worker(100);
+        assert_eq!(submitted_calls(&pool), vec![repin(&files[2])]);
+
+        // and back to the beginning
+        files[..2].iter().for_each(expect_pin);
+        run_offchain_worker(150);
+        assert_eq!(submitted_calls(&pool), files[..2].iter().map(repin).collect::<Vec<_>>());
+    });
+}
+
+#[test]
+fn it_should_draw_pieces_to_audit_every_era() {
+    use frame_support::pallet_prelude::ValidateUnsigned;
+    use sp_runtime::transaction_validity::{InvalidTransaction, TransactionSource};
+
+    use crate::PieceAudit;
+
+    new_test_ext().execute_with(|| {
+        AuditPeriod::set(10);
+        // nothing to draw from
+        TrustlessFileServer::on_initialize(10);
+        assert!(TrustlessFileServer::get_audit_sample().is_empty());
+
+        System::set_block_number(11);
+        let key = upload_test_file::<Test>(1);
+        let pieces = TrustlessFileServer::file_pieces(key).unwrap() as u64;
+        // only at the start of every era
+        TrustlessFileServer::on_initialize(11);
+        assert!(TrustlessFileServer::get_audit_sample().is_empty());
+        TrustlessFileServer::on_initialize(20);
+        let sample = TrustlessFileServer::get_audit_sample();
+        assert!(!sample.is_empty());
+        assert!(sample
+            .iter()
+            .all(|(merkle_root, position)| *merkle_root == key && *position < pieces));
+
+        let (_, position) = sample[0];
+        let proof = authority_proof((b"audit", key, position, false));
+        let call = crate::Call::<Test>::report_audit {
+            merkle_root: key,
+            position,
+            passed: false,
+            proof: proof.clone(),
+        };
+        assert!(TrustlessFileServer::validate_unsigned(TransactionSource::External, &call).is_ok());
+        // nobody but the authorities can pass or fail a piece
+        let forged = crate::Call::<Test>::report_audit {
+            merkle_root: key,
+            position,
+            passed: true,
+            proof: proof.clone(),
+        };
+        assert_eq!(
+            TrustlessFileServer::validate_unsigned(TransactionSource::External, &forged),
+            Err(InvalidTransaction::BadProof.into())
+        );
+        System::set_block_number(21);
+        assert_noop!(
+            TrustlessFileServer::report_audit(
+                RuntimeOrigin::none(),
+                key,
+                position,
+                true,
+                proof.clone()
+            ),
+            Error::<Test>::InvalidAuthorityProof
+        );
+        assert_ok!(TrustlessFileServer::report_audit(
+            RuntimeOrigin::none(),
+            key,
+            position,
+            false,
+            proof
+        ));
+        assert_eq!(
+            TrustlessFileServer::get_piece_audit(key),
+            Some(PieceAudit {
+                block: 21,
+                position,
+                passed: false
+            })
+        );
+        System::assert_last_event(
+            Event::<Test>::PieceAudited {
+                merkle_root: key,
+                position,
+                passed: false,
+            }
+            .into(),
+        );
+        // every piece is reported once
+        assert!(!TrustlessFileServer::get_audit_sample().contains(&(key, position)));
+        assert_noop!(
+            TrustlessFileServer::report_audit(
+                RuntimeOrigin::none(),
+                key,
+                position,
+                true,
+                authority_proof((b"audit", key, position, true))
+            ),
+            Error::<Test>::PieceNotAudited
+        );
+        assert_eq!(
+            TrustlessFileServer::validate_unsigned(TransactionSource::External, &call),
+            Err(InvalidTransaction::Stale.into())
+        );
+
+        // results go away along with the file
+        assert_ok!(TrustlessFileServer::delete_file(RuntimeOrigin::signed(1), key));
+        assert_eq!(TrustlessFileServer::get_piece_audit(key), None);
+    });
+}
+
+#[test]
+fn offchain_worker_should_audit_the_drawn_pieces() {
+    let (mut ext, state, pool) = new_offchain_test_ext();
+    ext.execute_with(|| {
+        AuditPeriod::set(10);
+        System::set_block_number(1);
+        // every piece holds the same content
+        assert_ok!(TrustlessFileServer::upload_file(
+            RuntimeOrigin::signed(1),
+            vec![7; 4 * 1024],
+            None,
+            None
+        ));
+        let merkle_root = FileOwners::<Test>::iter_keys().next().unwrap();
+        System::set_block_number(10);
+        TrustlessFileServer::on_initialize(10);
+        let sample = TrustlessFileServer::get_audit_sample();
+        assert!(!sample.is_empty());
+
+        // the first piece is served as registered, the rest are not
+        let cid = ipfs::ipfs_get_hash_from_sha256(&sha2_256(&[7; 1024]));
+        for (index, _) in sample.iter().enumerate() {
+            let content = if index == 0 {
+                vec![7; 1024]
+            } else {
+                vec![8; 1024]
+            };
+            state.write().expect_request(PendingRequest {
+                method: "POST".into(),
+                uri: format!("http://127.0.0.1:5001/api/v0/block/get?arg={}", cid),
+                response: Some(content),
+                sent: true,
+                ..Default::default()
+            });
+        }
+        TrustlessFileServer::offchain_worker(10);
+        assert_eq!(
+            submitted_calls(&pool),
+            sample
+                .iter()
+                .enumerate()
+                .map(|(index, (_, position))| crate::Call::<Test>::report_audit {
+                    merkle_root,
+                    position: *position,
+                    passed: index == 0,
+                    proof: authority_proof((b"audit", merkle_root, *position, index == 0)),
+                })
+                .collect::<Vec<_>>()
+        );
+
+        // nothing to do until the next era
+        TrustlessFileServer::offchain_worker(11);
+        assert!(submitted_calls(&pool).is_empty());
+    });
+}
+
+#[test]
+fn it_should_accumulate_the_reported_bandwidth() {
+    use frame_support::pallet_prelude::ValidateUnsigned;
+    use sp_runtime::transaction_validity::{InvalidTransaction, TransactionSource};
+
+    new_test_ext().execute_with(|| {
+        System::set_block_number(1);
+        let key = upload_test_file::<Test>(1);
+        let authority = UintAuthorityId(OCW_AUTHORITY);
+        let chunk_size = TrustlessFileServer::get_file_meta(key).unwrap().chunk_size as u64;
+        assert_ok!(TrustlessFileServer::report_bandwidth(
+            RuntimeOrigin::none(),
+            key,
+            chunk_size,
+            1,
+            authority_proof((b"bandwidth", key, chunk_size, 1u64))
+        ));
+        // once per block
+        let proof = authority_proof((b"bandwidth", key, 100u64, 2u64));
+        let call = crate::Call::<Test>::report_bandwidth {
+            merkle_root: key,
+            bytes: 100,
+            chunks: 2,
+            proof: proof.clone(),
+        };
+        assert_eq!(
+            TrustlessFileServer::validate_unsigned(TransactionSource::External, &call),
+            Err(InvalidTransaction::Stale.into())
+        );
+        assert_noop!(
+            TrustlessFileServer::report_bandwidth(RuntimeOrigin::none(), key, 100, 2, proof),
+            Error::<Test>::InvalidBandwidthReport
+        );
+        System::set_block_number(2);
+        let proof = authority_proof((b"bandwidth", key, 100u64, 2u64));
+        assert_ok!(TrustlessFileServer::report_bandwidth(
+            RuntimeOrigin::none(),
+            key,
+            100,
+            2,
+            proof.clone()
+        ));
+        assert_eq!(
+            TrustlessFileServer::get_served_bandwidth(key, &authority),
+            BandwidthRecord {
+                bytes: chunk_size + 100,
+                chunks: 3,
+                reported_at: Some(2),
+            }
+        );
+        System::assert_last_event(
+            Event::<Test>::BandwidthReported {
+                merkle_root: key,
+                authority: authority.clone(),
+                bytes: 100,
+                chunks: 2,
+            }
+            .into(),
+        );
+        // reports cannot be replayed
+        System::set_block_number(3);
+        assert_noop!(
+            TrustlessFileServer::report_bandwidth(RuntimeOrigin::none(), key, 100, 2, proof),
+            Error::<Test>::InvalidBandwidthReport
+        );
+
+        // nor made up by nodes other than the authorities
+        let proof = node_proof(2, (b"bandwidth", key, 100u64, 2u64));
+        let call = crate::Call::<Test>::report_bandwidth {
+            merkle_root: key,
+            bytes: 100,
+            chunks: 2,
+            proof: proof.clone(),
+        };
+        assert_eq!(
+            TrustlessFileServer::validate_unsigned(TransactionSource::External, &call),
+            Err(InvalidTransaction::BadProof.into())
+        );
+        assert_noop!(
+            TrustlessFileServer::report_bandwidth(RuntimeOrigin::none(), key, 100, 2, proof),
+            Error::<Test>::InvalidAuthorityProof
+        );
+        assert_eq!(
+            TrustlessFileServer::get_served_bandwidth(key, UintAuthorityId(2)),
+            BandwidthRecord::default()
+        );
+
+        // more bytes than the chunks hold
+        let proof = authority_proof((b"bandwidth", key, chunk_size + 1, 1u64));
+        let call = crate::Call::<Test>::report_bandwidth {
+            merkle_root: key,
+            bytes: chunk_size + 1,
+            chunks: 1,
+            proof: proof.clone(),
+        };
+        assert_eq!(
+            TrustlessFileServer::validate_unsigned(TransactionSource::External, &call),
+            Err(InvalidTransaction::Stale.into())
+        );
+        assert_noop!(
+            TrustlessFileServer::report_bandwidth(
+                RuntimeOrigin::none(),
+                key,
+                chunk_size + 1,
+                1,
+                proof
+            ),
+            Error::<Test>::InvalidBandwidthReport
+        );
+        assert_noop!(
+            TrustlessFileServer::report_bandwidth(
+                RuntimeOrigin::none(),
+                key,
+                0,
+                0,
+                authority_proof((b"bandwidth", key, 0u64, 0u64))
+            ),
+            Error::<Test>::InvalidBandwidthReport
+        );
+        assert_noop!(
+            TrustlessFileServer::report_bandwidth(
+                RuntimeOrigin::none(),
+                H256::zero(),
+                10,
+                1,
+                authority_proof((b"bandwidth", H256::zero(), 10u64, 1u64))
+            ),
+            Error::<Test>::FileNotFound
+        );
+        assert_noop!(
+            TrustlessFileServer::report_bandwidth(
+                RuntimeOrigin::signed(1),
+                key,
+                10,
+                1,
+                authority_proof((b"bandwidth", key, 10u64, 1u64))
+            ),
+            sp_runtime::traits::BadOrigin
+        );
+
+        assert_ok!(TrustlessFileServer::delete_file(RuntimeOrigin::signed(1), key));
+        assert_eq!(
+            TrustlessFileServer::get_served_bandwidth(key, &authority),
+            BandwidthRecord::default()
+        );
+    });
+}
+
+#[test]
+fn offchain_worker_should_report_the_served_bandwidth() {
+    let (mut ext, state, pool) = new_offchain_test_ext();
+    let (merkle_root, _) = ext.execute_with(|| upload_for_offchain_worker(&[1u8; 1024]));
+    ext.persist_offchain_overlay();
+
+    ext.execute_with(|| {
+        expect_block_put(&state, &[1u8; 1024]);
+        run_offchain_worker(1);
+        submitted_calls(&pool);
+
+        let served = || {
+            StorageValueRef::persistent(SERVED_KEY)
+                .get::<Vec<ServedContent<H256>>>()
+                .unwrap()
+                .unwrap_or_default()
+        };
+        let mut counters = Vec::new();
+        ServedContent::record(&mut counters, merkle_root, 1024, 1);
+        ServedContent::record(&mut counters, merkle_root, 512, 1);
+        // no longer registered
+        ServedContent::record(&mut counters, H256::zero(), 10, 1);
+        StorageValueRef::persistent(SERVED_KEY).set(&counters);
+
+        // `ServedReportInterval` is 30 blocks
+        run_offchain_worker(29);
+        assert!(submitted_calls(&pool).is_empty());
+        run_offchain_worker(30);
+        assert_eq!(
+            submitted_calls(&pool),
+            vec![crate::Call::<Test>::report_bandwidth {
+                merkle_root,
+                bytes: 1536,
+                chunks: 2,
+                proof: authority_proof((b"bandwidth", merkle_root, 1536u64, 2u64)),
+            }]
+        );
+        let counters = served();
+        assert_eq!((counters[0].bytes, counters[0].chunks), (1536, 2));
+        assert!(counters
+            .iter()
+            .all(|item| item.unreported_bytes == 0 && item.unreported_chunks == 0));
+
+        // nothing new to report
+        run_offchain_worker(60);
+        assert!(submitted_calls(&pool).is_empty());
+    });
+}
+
+#[test]
+fn it_should_track_uploaded_pieces() {
+    new_test_ext().execute_with(|| {
+        System::set_block_number(1);
+        let key = upload_test_file::<Test>(1);
+        let status = TrustlessFileServer::get_upload_status(key.encode()).unwrap();
+        assert_eq!(
+            status,
+            UploadStatus {
+                pieces: 12,
+                uploaded: 0
+            }
+        );
+        assert!(!status.is_uploaded(0));
+
+        // progress reports confirm every piece before `done`
+        assert_ok!(TrustlessFileServer::report_upload_progress(
+            RuntimeOrigin::none(),
+            key,
+            4,
+            12,
+            authority_proof((b"progress", key, 4u32, 12u32))
+        ));
+        assert_eq!(TrustlessFileServer::get_uploaded_pieces(key), 0b1111);
+        // cluster allocations confirm single pieces
+        assert_ok!(TrustlessFileServer::report_chunk_allocations(
+            RuntimeOrigin::none(),
+            key,
+            9,
+            crate::AllocationsOf::<Test>::default(),
+            authority_proof((b"allocations", key, 9u64, &crate::AllocationsOf::<Test>::default()))
+        ));
+        let status = TrustlessFileServer::get_upload_status(key.encode()).unwrap();
+        assert_eq!(status.uploaded, 0b10_0000_1111);
+        assert!(status.is_uploaded(9) && !status.is_uploaded(8) && !status.is_complete());
+
+        assert_ok!(TrustlessFileServer::report_upload_progress(
+            RuntimeOrigin::none(),
+            key,
+            12,
+            12,
+            authority_proof((b"progress", key, 12u32, 12u32))
+        ));
+        assert!(TrustlessFileServer::get_upload_status(key.encode())
+            .unwrap()
+            .is_complete());
+        assert_eq!(TrustlessFileServer::get_upload_status(H256::zero().encode()), None);
+
+        assert_ok!(TrustlessFileServer::delete_file(RuntimeOrigin::signed(1), key));
+        assert_eq!(TrustlessFileServer::get_uploaded_pieces(key), 0);
+    });
+}
+
+#[test]
+fn it_should_accept_anonymous_uploads_carrying_enough_work() {
+    use frame_support::pallet_prelude::ValidateUnsigned;
+    use sp_runtime::transaction_validity::{InvalidTransaction, TransactionSource};
+
+    new_test_ext().execute_with(|| {
+        System::set_block_number(1);
+        let bytes = include_bytes!("../img/substrate.png").to_vec();
+        let tree = FileMerkleTree::new(&bytes, &TrustlessFileServer::chunking_params());
+        let key = H256::decode(&mut tree.merkle_root()).unwrap();
+        // `UnsignedUploadDifficulty` is 8 bits
+        let work = |nonce: &u64| TrustlessFileServer::upload_work(&key, *nonce);
+        let nonce = (0..).find(|nonce| work(nonce) >= 8).unwrap();
+        let lazy = (0..).find(|nonce| work(nonce) < 8).unwrap();
+
+        let call = crate::Call::<Test>::upload_file_unsigned {
+            file_bytes: bytes.clone(),
+            nonce: lazy,
+        };
+        assert_eq!(
+            TrustlessFileServer::validate_unsigned(TransactionSource::External, &call),
+            Err(InvalidTransaction::BadProof.into())
+        );
+        assert_noop!(
+            TrustlessFileServer::upload_file_unsigned(RuntimeOrigin::none(), bytes.clone(), lazy),
+            Error::<Test>::InsufficientWork
+        );
+
+        let call = crate::Call::<Test>::upload_file_unsigned {
+            file_bytes: bytes.clone(),
+            nonce,
+        };
+        assert!(TrustlessFileServer::validate_unsigned(TransactionSource::External, &call).is_ok());
+        assert_ok!(TrustlessFileServer::upload_file_unsigned(
+            RuntimeOrigin::none(),
+            bytes.clone(),
+            nonce
+        ));
+        let pallet_account = TrustlessFileServer::account_id();
+        assert_eq!(TrustlessFileServer::get_file(key).unwrap().0, pallet_account);
+        assert_eq!(
+            TrustlessFileServer::get_registrant(key, pallet_account),
+            Some(Deposit::Native(0))
+        );
+
+        // already stored files are not registered again
+        assert_eq!(
+            TrustlessFileServer::validate_unsigned(TransactionSource::External, &call),
+            Err(InvalidTransaction::Stale.into())
+        );
+        assert_noop!(
+            TrustlessFileServer::upload_file_unsigned(RuntimeOrigin::none(), bytes, nonce),
+            Error::<Test>::AlreadyRegistered
+        );
+    });
+}
+
+#[test]
+fn it_should_reject_anonymous_uploads_when_disabled() {
+    use frame_support::pallet_prelude::ValidateUnsigned;
+    use sp_runtime::transaction_validity::{InvalidTransaction, TransactionSource};
+
+    UnsignedUploadDifficulty::set(None);
+    new_test_ext().execute_with(|| {
+        let call = crate::Call::<Test>::upload_file_unsigned {
+            file_bytes: vec![1u8; 1024],
+            nonce: 0,
+        };
+        assert_eq!(
+            TrustlessFileServer::validate_unsigned(TransactionSource::External, &call),
+            Err(InvalidTransaction::Call.into())
+        );
+        assert_noop!(
+            TrustlessFileServer::upload_file_unsigned(RuntimeOrigin::none(), vec![1u8; 1024], 0),
+            Error::<Test>::UnsignedUploadsDisabled
+        );
+        assert_noop!(
+            TrustlessFileServer::upload_file_unsigned(RuntimeOrigin::signed(1), vec![1u8; 1024], 0),
+            sp_runtime::traits::BadOrigin
+        );
+    });
+}
+
+#[test]
+fn it_should_only_accept_anonymous_uploads_that_can_be_registered() {
+    use frame_support::pallet_prelude::ValidateUnsigned;
+    use sp_runtime::transaction_validity::{InvalidTransaction, TransactionSource};
+
+    new_test_ext().execute_with(|| {
+        System::set_block_number(1);
+        let bytes = include_bytes!("../img/substrate.png").to_vec();
+        let tree = FileMerkleTree::new(&bytes, &TrustlessFileServer::chunking_params());
+        let key = H256::decode(&mut tree.merkle_root()).unwrap();
+        // `UnsignedUploadDifficulty` is 8 bits
+        let nonce = (0..)
+            .find(|nonce| TrustlessFileServer::upload_work(&key, *nonce) >= 8)
+            .unwrap();
+        let call = crate::Call::<Test>::upload_file_unsigned {
+            file_bytes: bytes.clone(),
+            nonce,
+        };
+        let validate =
+            || TrustlessFileServer::validate_unsigned(TransactionSource::External, &call);
+
+        assert_ok!(TrustlessFileServer::pause(RuntimeOrigin::root()));
+        assert_eq!(validate(), Err(InvalidTransaction::Call.into()));
+        assert_noop!(
+            TrustlessFileServer::upload_file_unsigned(RuntimeOrigin::none(), bytes.clone(), nonce),
+            Error::<Test>::Paused
+        );
+        assert_ok!(TrustlessFileServer::unpause(RuntimeOrigin::root()));
+
+        MaxTotalBytes::set(bytes.len() as u64 - 1);
+        assert_eq!(validate(), Err(InvalidTransaction::ExhaustsResources.into()));
+        assert_noop!(
+            TrustlessFileServer::upload_file_unsigned(RuntimeOrigin::none(), bytes.clone(), nonce),
+            Error::<Test>::GlobalCapacityExceeded
+        );
+        MaxTotalBytes::set(u64::MAX);
+
+        // the same content waiting to be reviewed
+        RequireReview::set(true);
+        upload_test_file::<Test>(1);
+        assert_eq!(validate(), Err(InvalidTransaction::Stale.into()));
+        assert_noop!(
+            TrustlessFileServer::upload_file_unsigned(RuntimeOrigin::none(), bytes, nonce),
+            Error::<Test>::UploadUnderReview
+        );
+    });
+}
+
+fn key(key: &str) -> KeyOf<Test> {
+    BoundedVec::try_from(key.as_bytes().to_vec()).unwrap()
+}
+
+#[test]
+fn it_should_reveal_keys_after_the_embargo() {
+    new_test_ext().execute_with(|| {
+        System::set_block_number(1);
+        let merkle_root = upload_test_file::<Test>(1);
+        let commitment = sha2_256(b"secret");
+        assert_noop!(
+            TrustlessFileServer::commit_key(RuntimeOrigin::signed(2), merkle_root, commitment, 10),
+            Error::<Test>::NotFileOwner
+        );
+        assert_noop!(
+            TrustlessFileServer::reveal_key(RuntimeOrigin::signed(2), merkle_root, key("secret")),
+            Error::<Test>::NoKeyCommitment
+        );
+        assert_ok!(TrustlessFileServer::commit_key(
+            RuntimeOrigin::signed(1),
+            merkle_root,
+            commitment,
+            10
+        ));
+        System::assert_last_event(
+            Event::<Test>::KeyCommitted {
+                merkle_root,
+                reveal_at: 10,
+            }
+            .into(),
+        );
+        assert_noop!(
+            TrustlessFileServer::commit_key(RuntimeOrigin::signed(1), merkle_root, commitment, 5),
+            Error::<Test>::KeyAlreadyCommitted
+        );
+
+        assert_noop!(
+            TrustlessFileServer::reveal_key(RuntimeOrigin::signed(2), merkle_root, key("secret")),
+            Error::<Test>::RevealTooEarly
+        );
+        System::set_block_number(10);
+        assert_noop!(
+            TrustlessFileServer::reveal_key(RuntimeOrigin::signed(2), merkle_root, key("guess")),
+            Error::<Test>::KeyMismatch
+        );
+        // anyone knowing the key can reveal it
+        assert_ok!(TrustlessFileServer::reveal_key(
+            RuntimeOrigin::signed(2),
+            merkle_root,
+            key("secret")
+        ));
+        System::assert_last_event(
+            Event::<Test>::KeyRevealed {
+                merkle_root,
+                key: key("secret"),
+            }
+            .into(),
+        );
+        assert_eq!(
+            TrustlessFileServer::get_time_lock(merkle_root),
+            Some(TimeLock {
+                commitment,
+                reveal_at: 10,
+                key: Some(key("secret")),
+            })
+        );
+        assert_noop!(
+            TrustlessFileServer::reveal_key(RuntimeOrigin::signed(1), merkle_root, key("secret")),
+            Error::<Test>::KeyAlreadyRevealed
+        );
+    });
+}
+
+#[test]
+fn it_should_drop_time_locks_of_deleted_files() {
+    new_test_ext().execute_with(|| {
+        System::set_block_number(1);
+        let merkle_root = upload_test_file::<Test>(1);
+        assert_ok!(TrustlessFileServer::commit_key(
+            RuntimeOrigin::signed(1),
+            merkle_root,
+            sha2_256(b"secret"),
+            10
+        ));
+        assert_ok!(TrustlessFileServer::delete_file(RuntimeOrigin::signed(1), merkle_root));
+        assert_eq!(TrustlessFileServer::get_time_lock(merkle_root), None);
+    });
+}
+
+/// Splits `secret` among `holders`, any `threshold` of which rebuild it, returning the arguments
+/// `escrow_key` takes along with the plain shares.
+fn escrow_shares(
+    secret: &[u8],
+    threshold: u8,
+    holders: &[u64],
+) -> (
+    BoundedVec<(u64, [u8; 32], EncryptedShareOf<Test>), <Test as crate::Config>::MaxKeyHolders>,
+    Vec<KeyOf<Test>>,
+) {
+    let shares: Vec<KeyOf<Test>> = shamir::split(secret, threshold, holders.len() as u8, || 42)
+        .into_iter()
+        .map(|share| KeyOf::<Test>::try_from(share).unwrap())
+        .collect();
+    let escrowed = holders
+        .iter()
+        .zip(shares.iter())
+        .map(|(holder, share)| (*holder, sha2_256(share), BoundedVec::default()))
+        .collect::<Vec<_>>();
+    (BoundedVec::try_from(escrowed).unwrap(), shares)
+}
+
+#[test]
+fn it_should_rebuild_escrowed_keys_from_enough_shares() {
+    new_test_ext().execute_with(|| {
+        System::set_block_number(1);
+        let merkle_root = upload_test_file::<Test>(1);
+        let commitment = sha2_256(b"secret");
+        let escrow = |threshold, shares| {
+            TrustlessFileServer::escrow_key(
+                RuntimeOrigin::signed(1),
+                merkle_root,
+                commitment,
+                10,
+                threshold,
+                shares,
+            )
+        };
+
+        assert_noop!(
+            escrow(2, escrow_shares(b"secret", 2, &[1, 4]).0),
+            Error::<Test>::NotCommitteeMember
+        );
+        assert_noop!(
+            escrow(2, escrow_shares(b"secret", 2, &[1, 1]).0),
+            Error::<Test>::DuplicateKeyHolder
+        );
+        let (escrowed, shares) = escrow_shares(b"secret", 2, &[1, 2, 3]);
+        assert_noop!(escrow(0, escrowed.clone()), Error::<Test>::InvalidThreshold);
+        assert_noop!(escrow(4, escrowed.clone()), Error::<Test>::InvalidThreshold);
+        assert_ok!(escrow(2, escrowed));
+        System::assert_last_event(
+            Event::<Test>::KeyEscrowed {
+                merkle_root,
+                reveal_at: 10,
+                threshold: 2,
+            }
+            .into(),
+        );
+
+        let submit = |who, share: &KeyOf<Test>| {
+            TrustlessFileServer::submit_key_share(
+                RuntimeOrigin::signed(who),
+                merkle_root,
+                share.clone(),
+            )
+        };
+        assert_noop!(submit(3, &shares[2]), Error::<Test>::RevealTooEarly);
+        System::set_block_number(10);
+        assert_noop!(submit(4, &shares[2]), Error::<Test>::NotKeyHolder);
+        assert_noop!(submit(3, &shares[0]), Error::<Test>::ShareMismatch);
+        assert_ok!(submit(3, &shares[2]));
+        System::assert_last_event(
+            Event::<Test>::KeyShareSubmitted {
+                merkle_root,
+                holder: 3,
+            }
+            .into(),
+        );
+        assert_noop!(submit(3, &shares[2]), Error::<Test>::ShareAlreadySubmitted);
+        assert_eq!(TrustlessFileServer::get_time_lock(merkle_root).unwrap().key, None);
+
+        // the second share reaches the threshold, without the owner having to reveal anything
+        assert_ok!(submit(1, &shares[0]));
+        System::assert_last_event(
+            Event::<Test>::KeyRevealed {
+                merkle_root,
+                key: key("secret"),
+            }
+            .into(),
+        );
+        assert_eq!(
+            TrustlessFileServer::get_time_lock(merkle_root).unwrap().key,
+            Some(key("secret"))
+        );
+        assert_eq!(TrustlessFileServer::get_key_escrow(merkle_root), None);
+        assert_noop!(submit(2, &shares[1]), Error::<Test>::NoKeyEscrow);
+    });
+}
+
+#[test]
+fn it_should_not_reveal_keys_split_wrongly() {
+    new_test_ext().execute_with(|| {
+        System::set_block_number(1);
+        let merkle_root = upload_test_file::<Test>(1);
+        // the shares are consistent with their commitments, but not with the key
+        let (escrowed, shares) = escrow_shares(b"cheat!", 2, &[1, 2]);
+        assert_ok!(TrustlessFileServer::escrow_key(
+            RuntimeOrigin::signed(1),
+            merkle_root,
+            sha2_256(b"secret"),
+            1,
+            2,
+            escrowed
+        ));
+        for (holder, share) in [1, 2].into_iter().zip(shares) {
+            assert_ok!(TrustlessFileServer::submit_key_share(
+                RuntimeOrigin::signed(holder),
+                merkle_root,
+                share
+            ));
+        }
+        System::assert_last_event(Event::<Test>::KeyReconstructionFailed { merkle_root }.into());
+        assert_eq!(TrustlessFileServer::get_time_lock(merkle_root).unwrap().key, None);
+        // the owner can still reveal the key by themselves
+        assert_ok!(TrustlessFileServer::reveal_key(
+            RuntimeOrigin::signed(1),
+            merkle_root,
+            key("secret")
+        ));
+        assert_eq!(TrustlessFileServer::get_key_escrow(merkle_root), None);
+    });
+}
+
+#[test]
+fn it_should_register_files_under_claimed_roots_once_confirmed() {
+    use frame_support::pallet_prelude::ValidateUnsigned;
+    use sp_runtime::transaction_validity::{InvalidTransaction, TransactionSource};
+
+    new_test_ext().execute_with(|| {
+        System::set_block_number(1);
+        let bytes = include_bytes!("../img/substrate.png").to_vec();
+        let params = TrustlessFileServer::chunking_params();
+        let merkle_tree = FileMerkleTree::new(&bytes, &params);
+        let merkle_root = H256::decode(&mut merkle_tree.merkle_root()).unwrap();
+        let size = bytes.len() as u64;
+        let deposit = 100 + size;
+        assert_ok!(TrustlessFileServer::upload_file_with_root(
+            RuntimeOrigin::signed(1),
+            bytes.clone(),
+            merkle_root
+        ));
+        assert_eq!(
+            TrustlessFileServer::get_root_claim(merkle_root),
+            Some(RootClaim {
+                who: 1,
+                deposit,
+                size
+            })
+        );
+        assert_eq!(Balances::reserved_balance(1), deposit);
+        assert!(TrustlessFileServer::get_file(merkle_root).is_none());
+        System::assert_last_event(
+            Event::RootClaimed {
+                who: 1,
+                merkle_root,
+                size,
+            }
+            .into(),
+        );
+
+        // the root stays claimed until checked
+        assert_noop!(
+            TrustlessFileServer::upload_file_with_root(
+                RuntimeOrigin::signed(2),
+                bytes.clone(),
+                merkle_root
+            ),
+            Error::<Test>::RootAlreadyClaimed
+        );
+        assert_noop!(
+            TrustlessFileServer::upload_file(RuntimeOrigin::signed(2), bytes.clone(), None, None),
+            Error::<Test>::RootAlreadyClaimed
+        );
+
+        // trees of other files are not accepted
+        let other = FileMerkleTree::new(&[1u8; 2048], &params);
+        assert_noop!(
+            TrustlessFileServer::confirm_root(
+                RuntimeOrigin::none(),
+                merkle_root,
+                other.clone(),
+                [0; 32],
+                authority_proof((b"confirm", merkle_root, &other, [0u8; 32]))
+            ),
+            Error::<Test>::InvalidMerkleTree
+        );
+
+        // the uploader cannot confirm its own claim, e.g. with a checksum of other content
+        let sha256 = sha2_256(&bytes);
+        let report = (b"confirm", merkle_root, &merkle_tree, [0u8; 32]);
+        let proof = node_proof(2, report);
+        let call = crate::Call::<Test>::confirm_root {
+            merkle_root,
+            merkle_tree: merkle_tree.clone(),
+            sha256: [0; 32],
+            proof: proof.clone(),
+        };
+        assert_eq!(
+            TrustlessFileServer::validate_unsigned(TransactionSource::External, &call),
+            Err(InvalidTransaction::BadProof.into())
+        );
+        assert_noop!(
+            TrustlessFileServer::confirm_root(
+                RuntimeOrigin::none(),
+                merkle_root,
+                merkle_tree.clone(),
+                [0; 32],
+                proof
+            ),
+            Error::<Test>::InvalidAuthorityProof
+        );
+        // nor swap the checksum of a confirmation of the authorities
+        let proof = authority_proof((b"confirm", merkle_root, &merkle_tree, sha256));
+        assert_noop!(
+            TrustlessFileServer::confirm_root(
+                RuntimeOrigin::none(),
+                merkle_root,
+                merkle_tree.clone(),
+                [0; 32],
+                proof.clone()
+            ),
+            Error::<Test>::InvalidAuthorityProof
+        );
+
+        let call = crate::Call::<Test>::confirm_root {
+            merkle_root,
+            merkle_tree: merkle_tree.clone(),
+            sha256,
+            proof: proof.clone(),
+        };
+        assert!(TrustlessFileServer::validate_unsigned(TransactionSource::External, &call).is_ok());
+        assert_ok!(TrustlessFileServer::confirm_root(
+            RuntimeOrigin::none(),
+            merkle_root,
+            merkle_tree,
+            sha256,
+            proof
+        ));
+        assert_eq!(TrustlessFileServer::get_file(merkle_root).unwrap().0, 1);
+        assert_eq!(
+            TrustlessFileServer::get_registrant(merkle_root, 1),
+            Some(Deposit::Native(deposit))
+        );
+        assert_eq!(TrustlessFileServer::get_checksum(merkle_root), Some(sha256));
+        assert!(TrustlessFileServer::get_root_claim(merkle_root).is_none());
+        assert_eq!(
+            TrustlessFileServer::validate_unsigned(TransactionSource::External, &call),
+            Err(InvalidTransaction::Stale.into())
+        );
+
+        // registered files cannot be claimed again
+        assert_noop!(
+            TrustlessFileServer::upload_file_with_root(
+                RuntimeOrigin::signed(2),
+                bytes,
+                merkle_root
+            ),
+            Error::<Test>::FileAlreadyExists
+        );
+    });
+}
+
+#[test]
+fn offchain_worker_should_confirm_right_claimed_roots_and_reject_wrong_ones() {
+    use frame_support::pallet_prelude::ValidateUnsigned;
+    use sp_runtime::transaction_validity::{InvalidTransaction, TransactionSource};
+
+    let (mut ext, _state, pool) = new_offchain_test_ext();
+    let bytes = vec![7u8; 4096];
+    let wrong_root = H256::repeat_byte(1);
+    let (merkle_root, merkle_tree) = ext.execute_with(|| {
+        System::set_block_number(1);
+        let merkle_tree = FileMerkleTree::new(&bytes, &TrustlessFileServer::chunking_params());
+        let merkle_root = H256::decode(&mut merkle_tree.merkle_root()).unwrap();
+        assert_ok!(TrustlessFileServer::upload_file_with_root(
+            RuntimeOrigin::signed(1),
+            bytes.clone(),
+            merkle_root
+        ));
+        assert_ok!(TrustlessFileServer::upload_file_with_root(
+            RuntimeOrigin::signed(2),
+            vec![8u8; 4096],
+            wrong_root
+        ));
+        (merkle_root, merkle_tree)
+    });
+    ext.persist_offchain_overlay();
+
+    ext.execute_with(|| {
+        run_offchain_worker(1);
+        let calls = submitted_calls(&pool);
+        assert_eq!(calls.len(), 2);
+        let sha256 = sha2_256(&bytes);
+        assert!(calls.contains(&crate::Call::confirm_root {
+            merkle_root,
+            merkle_tree: merkle_tree.clone(),
+            sha256,
+            proof: authority_proof((b"confirm", merkle_root, &merkle_tree, sha256)),
+        }));
+        assert!(calls.contains(&crate::Call::reject_root {
+            merkle_root: wrong_root,
+            proof: authority_proof((b"reject", wrong_root)),
+        }));
+
+        // nobody but the authorities can have the deposit of a right root slashed
+        let proof = node_proof(2, (b"reject", merkle_root));
+        let call = crate::Call::<Test>::reject_root {
+            merkle_root,
+            proof: proof.clone(),
+        };
+        assert_eq!(
+            TrustlessFileServer::validate_unsigned(TransactionSource::External, &call),
+            Err(InvalidTransaction::BadProof.into())
+        );
+        assert_noop!(
+            TrustlessFileServer::reject_root(RuntimeOrigin::none(), merkle_root, proof),
+            Error::<Test>::InvalidAuthorityProof
+        );
+        // nor turn a confirmation into a rejection
+        let proof = authority_proof((b"confirm", merkle_root, &merkle_tree, sha256));
+        assert_noop!(
+            TrustlessFileServer::reject_root(RuntimeOrigin::none(), merkle_root, proof),
+            Error::<Test>::InvalidAuthorityProof
+        );
+
+        // the uploader of the wrong root loses the deposit
+        let deposit = 100 + 4096;
+        assert_ok!(TrustlessFileServer::reject_root(
+            RuntimeOrigin::none(),
+            wrong_root,
+            authority_proof((b"reject", wrong_root))
+        ));
+        assert_eq!(Balances::reserved_balance(2), 0);
+        assert_eq!(Balances::free_balance(2), INITIAL_BALANCE - deposit);
+        assert!(TrustlessFileServer::get_root_claim(wrong_root).is_none());
+        System::assert_last_event(
+            Event::RootRejected {
+                who: 2,
+                merkle_root: wrong_root,
+                slashed: deposit,
+            }
+            .into(),
+        );
+        assert_noop!(
+            TrustlessFileServer::reject_root(
+                RuntimeOrigin::none(),
+                wrong_root,
+                authority_proof((b"reject", wrong_root))
+            ),
+            Error::<Test>::RootNotClaimed
+        );
+    });
+}
+
+#[test]
+fn offchain_worker_should_reject_claims_it_cannot_verify() {
+    let (mut ext, _state, pool) = new_offchain_test_ext();
+    ext.execute_with(|| {
+        System::set_block_number(1);
+        let merkle_root = H256::repeat_byte(1);
+        assert_ok!(TrustlessFileServer::upload_file_with_root(
+            RuntimeOrigin::signed(1),
+            vec![7u8; 4096],
+            merkle_root
+        ));
+
+        // the content never made it to the offchain database
+        run_offchain_worker(1);
+        assert_eq!(
+            submitted_calls(&pool),
+            vec![crate::Call::<Test>::reject_root {
+                merkle_root,
+                proof: authority_proof((b"reject", merkle_root)),
+            }]
+        );
+    });
+}
+
+#[test]
+fn offchain_worker_should_reject_rechunks_it_cannot_process() {
+    let (mut ext, _state, pool) = new_offchain_test_ext();
+    ext.execute_with(|| {
+        System::set_block_number(1);
+        let legacy = upload_legacy_test_file(1);
+        assert_ok!(TrustlessFileServer::rechunk_file(RuntimeOrigin::signed(1), legacy));
+
+        // the content was never indexed
+        run_offchain_worker(1);
+        let reject = crate::Call::<Test>::reject_rechunk {
+            merkle_root: legacy,
+            proof: authority_proof((b"reject_rechunk", legacy)),
+        };
+        assert!(submitted_calls(&pool).contains(&reject));
+
+        assert_noop!(
+            TrustlessFileServer::reject_rechunk(
+                RuntimeOrigin::none(),
+                legacy,
+                node_proof(2, (b"reject_rechunk", legacy))
+            ),
+            Error::<Test>::InvalidAuthorityProof
+        );
+        assert_ok!(TrustlessFileServer::reject_rechunk(
+            RuntimeOrigin::none(),
+            legacy,
+            authority_proof((b"reject_rechunk", legacy))
+        ));
+        assert!(!PendingRechunks::<Test>::contains_key(legacy));
+        assert!(TrustlessFileServer::get_file(legacy).is_some());
+        System::assert_last_event(
+            Event::RechunkRejected {
+                merkle_root: legacy,
+            }
+            .into(),
+        );
+
+        // and the owner can ask again
+        assert_ok!(TrustlessFileServer::rechunk_file(RuntimeOrigin::signed(1), legacy));
+    });
+}
+
+#[test]
+fn it_should_let_uploaders_cancel_their_unchecked_claims() {
+    new_test_ext().execute_with(|| {
+        System::set_block_number(1);
+        let merkle_root = H256::repeat_byte(1);
+        assert_ok!(TrustlessFileServer::upload_file_with_root(
+            RuntimeOrigin::signed(1),
+            vec![7u8; 4096],
+            merkle_root
+        ));
+        assert_eq!(Balances::reserved_balance(1), 100 + 4096);
+
+        assert_noop!(
+            TrustlessFileServer::cancel_root_claim(RuntimeOrigin::signed(2), merkle_root),
+            Error::<Test>::NotClaimant
+        );
+        assert_ok!(TrustlessFileServer::cancel_root_claim(RuntimeOrigin::signed(1), merkle_root));
+        assert!(TrustlessFileServer::get_root_claim(merkle_root).is_none());
+        assert_eq!(Balances::reserved_balance(1), 0);
+        assert_eq!(Balances::free_balance(1), INITIAL_BALANCE);
+        System::assert_last_event(
+            Event::<Test>::RootClaimCancelled {
+                who: 1,
+                merkle_root,
+            }
+            .into(),
+        );
+        assert_noop!(
+            TrustlessFileServer::cancel_root_claim(RuntimeOrigin::signed(1), merkle_root),
+            Error::<Test>::RootNotClaimed
+        );
+
+        // the root can be claimed again
+        assert_ok!(TrustlessFileServer::upload_file_with_root(
+            RuntimeOrigin::signed(2),
+            vec![7u8; 4096],
+            merkle_root
+        ));
+    });
+}
+
+#[test]
+fn it_should_audit_stored_trees() {
+    new_test_ext().execute_with(|| {
+        System::set_block_number(1);
+        let key = upload_test_file::<Test>(1);
+        let report = TrustlessFileServer::audit_file(key.encode()).unwrap();
+        assert_eq!(
+            report,
+            AuditReport {
+                pieces: 12,
+                well_formed: true,
+                corrupted_nodes: vec![],
+                root_matches: true,
+            }
+        );
+        assert!(report.is_intact());
+        assert_eq!(TrustlessFileServer::audit_file(H256::zero().encode()), None);
+
+        // a leaf corrupted in storage breaks its parent and the root
+        FileTrees::<Test>::mutate(key, |tree| {
+            let tree = tree.as_mut().unwrap();
+            let mut nodes = tree.merkle_tree.to_vec();
+            nodes[0] ^= 1;
+            tree.merkle_tree = nodes.try_into().unwrap();
+        });
+        let report = TrustlessFileServer::audit_file(key.encode()).unwrap();
+        assert_eq!(report.corrupted_nodes, vec![16]);
+        assert!(!report.root_matches);
+        assert!(!report.is_intact());
+    });
+}
+
+#[test]
+fn it_should_resolve_reports_about_files() {
+    new_test_ext().execute_with(|| {
+        System::set_block_number(1);
+        let key = upload_test_file::<Test>(1);
+        let bytes = include_bytes!("../img/substrate.png");
+        let deposit = 100 + bytes.len() as u64;
+        assert_ok!(TrustlessFileServer::sponsor_upload(
+            RuntimeOrigin::signed(1),
+            4,
+            bytes.to_vec()
+        ));
+        assert_noop!(
+            TrustlessFileServer::report_file(RuntimeOrigin::signed(2), H256::zero(), [7; 32]),
+            Error::<Test>::FileNotFound
+        );
+
+        assert_ok!(TrustlessFileServer::report_file(RuntimeOrigin::signed(2), key, [7; 32]));
+        System::assert_last_event(
+            Event::FileReported {
+                who: 2,
+                merkle_root: key,
+                reason_hash: [7; 32],
+            }
+            .into(),
+        );
+        assert_eq!(Balances::reserved_balance(2), 50);
+        assert_noop!(
+            TrustlessFileServer::report_file(RuntimeOrigin::signed(2), key, [8; 32]),
+            Error::<Test>::AlreadyReported
+        );
+        assert_ok!(TrustlessFileServer::report_file(RuntimeOrigin::signed(3), key, [8; 32]));
+        assert_noop!(
+            TrustlessFileServer::report_file(RuntimeOrigin::signed(1), key, [9; 32]),
+            Error::<Test>::TooManyReports
+        );
+        assert_eq!(TrustlessFileServer::get_reports(key).len(), 2);
+
+        // only the moderation origin resolves reports
+        assert_noop!(
+            TrustlessFileServer::resolve_report(RuntimeOrigin::signed(1), key, 2, Verdict::Dismiss),
+            sp_runtime::traits::BadOrigin
+        );
+
+        // dismissing slashes the bond of the reporter
+        assert_ok!(TrustlessFileServer::resolve_report(
+            RuntimeOrigin::root(),
+            key,
+            2,
+            Verdict::Dismiss
+        ));
+        System::assert_last_event(
+            Event::ReportDismissed {
+                merkle_root: key,
+                reporter: 2,
+                slashed: 50,
+            }
+            .into(),
+        );
+        assert_eq!(Balances::reserved_balance(2), 0);
+        assert_eq!(Balances::free_balance(2), INITIAL_BALANCE - 50);
+        assert_noop!(
+            TrustlessFileServer::resolve_report(RuntimeOrigin::root(), key, 2, Verdict::Dismiss),
+            Error::<Test>::ReportNotFound
+        );
+
+        // upholding a report takes the file down, refunding deposits and bonds
+        assert_ok!(TrustlessFileServer::report_file(RuntimeOrigin::signed(2), key, [9; 32]));
+        assert_eq!(Balances::reserved_balance(1), 2 * deposit);
+        assert_ok!(TrustlessFileServer::resolve_report(
+            RuntimeOrigin::root(),
+            key,
+            3,
+            Verdict::RemoveFile
+        ));
+        System::assert_last_event(Event::FileTakenDown { merkle_root: key }.into());
+        assert!(TrustlessFileServer::get_file(key).is_none());
+        assert_eq!(TrustlessFileServer::get_registrant_count(key), 0);
+        assert_eq!(TrustlessFileServer::get_sponsor(key, 4), None);
+        assert!(TrustlessFileServer::get_reports(key).is_empty());
+        for who in 1..=3 {
+            assert_eq!(Balances::reserved_balance(who), 0);
+        }
+        assert_eq!(Balances::free_balance(2), INITIAL_BALANCE - 50);
+        assert_eq!(Balances::free_balance(3), INITIAL_BALANCE);
+    });
+}
+
+#[test]
+fn it_should_keep_a_tombstone_of_the_files_taken_down() {
+    use crate::Tombstone;
+
+    new_test_ext().execute_with(|| {
+        System::set_block_number(1);
+        let key = upload_test_file::<Test>(1);
+        let (_, tree) = TrustlessFileServer::get_file(key).unwrap();
+        let chunk_hash = TrustlessFileServer::chunk_hash(key, 1).unwrap();
+        let proof = TrustlessFileServer::get_piece_proof(key.encode(), 1)
+            .unwrap()
+            .proof;
+        assert!(TrustlessFileServer::tombstone(key).is_none());
+        assert!(!TrustlessFileServer::verify_removed_proof(key, 1, &chunk_hash, &proof));
+
+        System::set_block_number(5);
+        assert_ok!(TrustlessFileServer::report_file(RuntimeOrigin::signed(2), key, [9; 32]));
+        assert_ok!(TrustlessFileServer::resolve_report(
+            RuntimeOrigin::root(),
+            key,
+            2,
+            Verdict::RemoveFile
+        ));
+        assert!(TrustlessFileServer::get_file(key).is_none());
+        let tombstone = TrustlessFileServer::tombstone(key).unwrap();
+        assert_eq!(tombstone.removed_at, 5);
+        assert_eq!(tombstone.reason_hash, [9; 32]);
+        assert_eq!(tombstone.size, tree.file_size);
+        assert_eq!(tombstone.chunk_size, tree.chunk_size);
+        assert_eq!(tombstone.piece_hashes.len() as u32, tree.pieces());
+        assert_eq!(tombstone.piece_hashes[1], chunk_hash);
+
+        // proofs of the removed file are no longer valid as those of a registered file, but
+        // still tell that the piece was part of it
+        assert!(!TrustlessFileServer::verify_proof(key, 1, &chunk_hash, &proof));
+        assert!(TrustlessFileServer::verify_removed_proof(key, 1, &chunk_hash, &proof));
+        assert!(!TrustlessFileServer::verify_removed_proof(key, 0, &chunk_hash, &proof));
+        assert!(!TrustlessFileServer::verify_removed_proof(key, 1, &[0; 32], &proof));
+
+        assert_eq!(
+            TrustlessFileServer::get_tombstone(key.encode()),
+            Some(Tombstone {
+                removed_at: 5,
+                reason_hash: [9; 32],
+                size: tombstone.size,
+                chunk_size: tombstone.chunk_size,
+                piece_hashes: tombstone.piece_hashes,
+            })
+        );
+        assert_eq!(TrustlessFileServer::get_tombstone(H256::zero().encode()), None);
+    });
+}
+
+#[test]
+fn it_should_track_the_reputation_of_publishers() {
+    new_test_ext().execute_with(|| {
+        System::set_block_number(1);
+        assert_eq!(TrustlessFileServer::get_reputation(1), ReputationRecord::default());
+        let key = upload_test_file::<Test>(1);
+        let bytes = include_bytes!("../img/substrate.png");
+        // registering an already stored file is not an upload
+        assert_ok!(TrustlessFileServer::upload_file(
+            RuntimeOrigin::signed(2),
+            bytes.to_vec(),
+            None,
+            None
+        ));
+        assert_eq!(TrustlessFileServer::get_reputation(1).uploads, 1);
+        assert_eq!(TrustlessFileServer::get_reputation(2).uploads, 0);
+
+        assert_ok!(TrustlessFileServer::report_file(RuntimeOrigin::signed(3), key, [7; 32]));
+        assert_ok!(TrustlessFileServer::resolve_report(
+            RuntimeOrigin::root(),
+            key,
+            3,
+            Verdict::Dismiss
+        ));
+        assert_eq!(TrustlessFileServer::get_reputation(1).reports_dismissed, 1);
+        assert_eq!(TrustlessFileServer::get_reputation(1).score(), 1 + 2);
+
+        assert_ok!(TrustlessFileServer::report_file(RuntimeOrigin::signed(3), key, [7; 32]));
+        assert_ok!(TrustlessFileServer::resolve_report(
+            RuntimeOrigin::root(),
+            key,
+            3,
+            Verdict::RemoveFile
+        ));
+        // only the owner is accountable for the content
+        assert_eq!(
+            TrustlessFileServer::get_reputation(1),
+            ReputationRecord {
+                uploads: 1,
+                reports_dismissed: 1,
+                reports_upheld: 1,
+                ..Default::default()
+            }
+        );
+        assert_eq!(TrustlessFileServer::get_reputation(1).score(), 1 + 2 - 50);
+        assert_eq!(TrustlessFileServer::get_reputation(2), ReputationRecord::default());
+    });
+}
+
+#[test]
+fn it_should_account_for_the_storage_usage_of_accounts() {
+    use crate::UsageRecord;
+
+    new_test_ext().execute_with(|| {
+        System::set_block_number(1);
+        let key = upload_test_file::<Test>(1);
+        let size = include_bytes!("../img/substrate.png").len() as u64;
+        assert_eq!(
+            TrustlessFileServer::get_usage(1),
+            UsageRecord {
+                files: 1,
+                bytes: size,
+                deposit_locked: 100 + size
+            }
+        );
+
+        // sponsored registrations lock the deposit of the sponsor
+        assert_ok!(TrustlessFileServer::sponsor_upload(
+            RuntimeOrigin::signed(2),
+            3,
+            vec![1u8; 1024]
+        ));
+        assert_eq!(
+            TrustlessFileServer::get_usage(2),
+            UsageRecord {
+                files: 0,
+                bytes: 0,
+                deposit_locked: 1124
+            }
+        );
+        assert_ok!(TrustlessFileServer::upload_file(
+            RuntimeOrigin::signed(3),
+            include_bytes!("../img/substrate.png").to_vec(),
+            None,
+            None
+        ));
+        assert_eq!(
+            TrustlessFileServer::get_account_usage(3),
+            UsageRecord {
+                files: 2,
+                bytes: 1024 + size,
+                deposit_locked: 100 + size as u128
+            }
+        );
+
+        assert_ok!(TrustlessFileServer::delete_file(RuntimeOrigin::signed(1), key));
+        assert!(!crate::AccountUsage::<Test>::contains_key(1));
+        assert_eq!(TrustlessFileServer::get_account_usage(1), UsageRecord::default());
+        assert_eq!(TrustlessFileServer::get_usage(3).files, 2);
+    });
+}
+
+#[test]
+fn it_should_delete_files_at_scheduled_blocks() {
+    new_test_ext().execute_with(|| {
+        System::set_block_number(1);
+        let key = upload_test_file::<Test>(1);
+        assert_noop!(
+            TrustlessFileServer::schedule_deletion(RuntimeOrigin::signed(2), key, 10),
+            Error::<Test>::NotRegistrant
+        );
+        assert_noop!(
+            TrustlessFileServer::schedule_deletion(RuntimeOrigin::signed(1), key, 1),
+            Error::<Test>::ScheduledInThePast
+        );
+        assert_noop!(
+            TrustlessFileServer::cancel_deletion(RuntimeOrigin::signed(1), key),
+            Error::<Test>::DeletionNotScheduled
+        );
+
+        // cancelled deletions are not dispatched
+        assert_ok!(TrustlessFileServer::schedule_deletion(RuntimeOrigin::signed(1), key, 5));
+        assert_noop!(
+            TrustlessFileServer::schedule_deletion(RuntimeOrigin::signed(1), key, 6),
+            Error::<Test>::DeletionAlreadyScheduled
+        );
+        assert_ok!(TrustlessFileServer::cancel_deletion(RuntimeOrigin::signed(1), key));
+        System::assert_last_event(
+            Event::DeletionCancelled {
+                who: 1,
+                merkle_root: key,
+            }
+            .into(),
+        );
+        System::set_block_number(5);
+        Scheduler::on_initialize(5);
+        assert!(TrustlessFileServer::get_file(key).is_some());
+
+        assert_ok!(TrustlessFileServer::schedule_deletion(RuntimeOrigin::signed(1), key, 10));
+        System::assert_last_event(
+            Event::DeletionScheduled {
+                who: 1,
+                merkle_root: key,
+                when: 10,
+            }
+            .into(),
+        );
+        assert_eq!(TrustlessFileServer::get_scheduled_deletion(key, 1), Some(10));
+        System::set_block_number(10);
+        Scheduler::on_initialize(10);
+        System::assert_has_event(Event::FileDeleted { merkle_root: key }.into());
+        assert!(TrustlessFileServer::get_file(key).is_none());
+        assert_eq!(TrustlessFileServer::get_scheduled_deletion(key, 1), None);
+        assert_eq!(Balances::reserved_balance(1), 0);
+    });
+}
+
+#[test]
+fn it_should_hold_large_uploads_until_approved() {
+    use crate::BlockUploads;
+
+    new_test_ext().execute_with(|| {
+        System::set_block_number(1);
+        let bytes = vec![7u8; 20000];
+        let deposit = 100 + bytes.len() as u64;
+        assert_ok!(TrustlessFileServer::upload_file(
+            RuntimeOrigin::signed(1),
+            bytes.clone(),
+            None,
+            None
+        ));
+        let tree = FileMerkleTree::new(&bytes, &TrustlessFileServer::chunking_params());
+        let key = H256::decode(&mut tree.merkle_root()).unwrap();
+        System::assert_last_event(
+            Event::LargeUploadPending {
+                who: 1,
+                merkle_root: key,
+                size: 20000,
+            }
+            .into(),
+        );
+        // nothing is registered nor pushed to IPFS yet
+        assert!(TrustlessFileServer::get_file(key).is_none());
+        assert!(BlockUploads::<Test>::get().is_empty());
+        assert_eq!(Balances::reserved_balance(1), deposit);
+        assert_noop!(
+            TrustlessFileServer::upload_file(RuntimeOrigin::signed(2), bytes.clone(), None, None),
+            Error::<Test>::UploadPendingApproval
+        );
+
+        assert_noop!(
+            TrustlessFileServer::approve_large_upload(RuntimeOrigin::signed(1), key),
+            sp_runtime::traits::BadOrigin
+        );
+        assert_ok!(TrustlessFileServer::approve_large_upload(RuntimeOrigin::root(), key));
+        assert_eq!(TrustlessFileServer::get_file(key).unwrap().0, 1);
+        assert_eq!(BlockUploads::<Test>::get().to_vec(), vec![key]);
+        assert!(TrustlessFileServer::get_pending_upload(key).is_none());
+        assert_noop!(
+            TrustlessFileServer::approve_large_upload(RuntimeOrigin::root(), key),
+            Error::<Test>::UploadNotPending
+        );
+
+        // rejected uploads give the deposit back
+        let other = vec![8u8; 20000];
+        assert_ok!(TrustlessFileServer::upload_file(
+            RuntimeOrigin::signed(2),
+            other.clone(),
+            None,
+            None
+        ));
+        let tree = FileMerkleTree::new(&other, &TrustlessFileServer::chunking_params());
+        let other_key = H256::decode(&mut tree.merkle_root()).unwrap();
+        assert_ok!(TrustlessFileServer::reject_large_upload(RuntimeOrigin::root(), other_key));
+        System::assert_last_event(
+            Event::LargeUploadRejected {
+                who: 2,
+                merkle_root: other_key,
+            }
+            .into(),
+        );
+        assert_eq!(Balances::reserved_balance(2), 0);
+        assert!(TrustlessFileServer::get_file(other_key).is_none());
+    });
+}
+
+#[test]
+fn it_should_hold_uploads_until_reviewed() {
+    use crate::BlockUploads;
+
+    new_test_ext().execute_with(|| {
+        System::set_block_number(1);
+        RequireReview::set(true);
+        let bytes = include_bytes!("../img/substrate.png");
+        let key = upload_test_file::<Test>(1);
+        System::assert_last_event(
+            Event::UploadPendingReview {
+                who: 1,
+                merkle_root: key,
+                size: bytes.len() as u64,
+            }
+            .into(),
+        );
+        // nothing is registered nor pushed to IPFS until screened
+        assert!(TrustlessFileServer::get_file(key).is_none());
+        assert!(BlockUploads::<Test>::get().is_empty());
+        assert!(TrustlessFileServer::get_pending_review(key).is_some());
+        assert_noop!(
+            TrustlessFileServer::upload_file(RuntimeOrigin::signed(2), bytes.to_vec(), None, None),
+            Error::<Test>::UploadUnderReview
+        );
+
+        assert_noop!(
+            TrustlessFileServer::approve_review(RuntimeOrigin::root(), key),
+            sp_runtime::traits::BadOrigin
+        );
+        assert_ok!(TrustlessFileServer::approve_review(
+            RuntimeOrigin::signed(Reviewer::get()),
+            key
+        ));
+        assert_eq!(TrustlessFileServer::get_file(key).unwrap().0, 1);
+        assert_eq!(BlockUploads::<Test>::get().to_vec(), vec![key]);
+        assert_noop!(
+            TrustlessFileServer::approve_review(RuntimeOrigin::signed(Reviewer::get()), key),
+            Error::<Test>::UploadNotUnderReview
+        );
+
+        // approved large uploads still need to be approved by governance
+        let large = vec![7u8; 20000];
+        assert_ok!(TrustlessFileServer::upload_file(
+            RuntimeOrigin::signed(2),
+            large.clone(),
+            None,
+            None
+        ));
+        let tree = FileMerkleTree::new(&large, &TrustlessFileServer::chunking_params());
+        let large_key = H256::decode(&mut tree.merkle_root()).unwrap();
+        assert_ok!(TrustlessFileServer::approve_review(
+            RuntimeOrigin::signed(Reviewer::get()),
+            large_key
+        ));
+        assert!(TrustlessFileServer::get_pending_upload(large_key).is_some());
+
+        // rejected uploads give the deposit back and never get registered
+        let other = vec![8u8; 2000];
+        assert_ok!(TrustlessFileServer::upload_file(
+            RuntimeOrigin::signed(3),
+            other.clone(),
+            None,
+            None
+        ));
+        assert_eq!(Balances::reserved_balance(3), 100 + other.len() as u64);
+        let tree = FileMerkleTree::new(&other, &TrustlessFileServer::chunking_params());
+        let other_key = H256::decode(&mut tree.merkle_root()).unwrap();
+        assert_ok!(TrustlessFileServer::reject_review(
+            RuntimeOrigin::signed(Reviewer::get()),
+            other_key
+        ));
+        System::assert_last_event(
+            Event::UploadRejectedInReview {
+                who: 3,
+                merkle_root: other_key,
+            }
+            .into(),
+        );
+        assert_eq!(Balances::reserved_balance(3), 0);
+        assert!(TrustlessFileServer::get_file(other_key).is_none());
+        assert!(TrustlessFileServer::get_pending_review(other_key).is_none());
+    });
+}
+
+#[test]
+fn it_should_send_file_attestations_to_siblings() {
+    use xcm::latest::{
+        Instruction::Transact, Instruction::UnpaidExecution, Junction::Parachain, Junctions::X1,
+        MultiLocation, OriginKind, WeightLimit::Unlimited,
+    };
+
+    new_test_ext().execute_with(|| {
+        System::set_block_number(1);
+        let key = upload_test_file::<Test>(1);
+        assert_noop!(
+            TrustlessFileServer::attest_file(RuntimeOrigin::signed(1), H256::zero(), 2000),
+            Error::<Test>::FileNotFound
+        );
+        assert_noop!(
+            TrustlessFileServer::attest_file(RuntimeOrigin::signed(2), key, 2000),
+            Error::<Test>::NotRegistrant
+        );
+
+        assert_ok!(TrustlessFileServer::attest_file(RuntimeOrigin::signed(1), key, 2000));
+        System::assert_last_event(
+            Event::FileAttested {
+                merkle_root: key,
+                para_id: 2000,
+            }
+            .into(),
+        );
+        let attestation = FileAttestation {
+            merkle_root: key,
+            owner: 1u64,
+            size: include_bytes!("../img/substrate.png").len() as u64,
+            pieces: 12,
+        };
+        let sent = SentXcm::take();
+        assert_eq!(sent.len(), 1);
+        let (destination, message) = &sent[0];
+        assert_eq!(*destination, MultiLocation::new(1, X1(Parachain(2000))));
+        assert_eq!(
+            message.0,
+            vec![
+                UnpaidExecution {
+                    weight_limit: Unlimited,
+                    check_origin: None,
+                },
+                Transact {
+                    origin_kind: OriginKind::Xcm,
+                    require_weight_at_most: AttestationWeight::get(),
+                    call: ([60u8, 0], attestation).encode().into(),
+                },
+            ]
+        );
+    });
+}
+
+#[test]
+fn it_should_record_files_attested_by_trusted_siblings() {
+    new_test_ext().execute_with(|| {
+        System::set_block_number(1);
+        let key = H256::repeat_byte(1);
+        let attestation = FileAttestation {
+            merkle_root: key,
+            owner: 7u64,
+            size: 2048,
+            pieces: 2,
+        };
+        assert_noop!(
+            TrustlessFileServer::register_external_file(RuntimeOrigin::root(), attestation.clone()),
+            sp_runtime::traits::BadOrigin
+        );
+        assert_noop!(
+            TrustlessFileServer::register_external_file(
+                RuntimeOrigin::signed(RELAY),
+                attestation.clone()
+            ),
+            Error::<Test>::NotSibling
+        );
+        assert_noop!(
+            TrustlessFileServer::register_external_file(
+                RuntimeOrigin::signed(3000),
+                attestation.clone()
+            ),
+            Error::<Test>::UntrustedSibling
+        );
+
+        assert_ok!(TrustlessFileServer::register_external_file(
+            RuntimeOrigin::signed(2000),
+            attestation
+        ));
+        System::assert_last_event(
+            Event::ExternalFileRegistered {
+                merkle_root: key,
+                para_id: 2000,
+                owner: 7,
+            }
+            .into(),
+        );
+        let file = ExternalFile {
+            owner: 7,
+            size: 2048,
+            pieces: 2,
+        };
+        assert_eq!(TrustlessFileServer::get_external_files(key.encode()), vec![(2000, file)]);
+        assert!(TrustlessFileServer::get_external_files(H256::zero().encode()).is_empty());
+    });
+}
+
+#[test]
+fn it_should_keep_the_history_of_files() {
+    new_test_ext().execute_with(|| {
+        System::set_block_number(1);
+        let key = upload_test_file::<Test>(1);
+        upload_test_file::<Test>(2);
+        assert!(TrustlessFileServer::get_file_history(key.encode()).is_empty());
+
+        System::set_block_number(2);
+        assert_ok!(TrustlessFileServer::set_metadata(
+            RuntimeOrigin::signed(1),
+            key,
+            test_metadata()
+        ));
+        System::set_block_number(3);
+        assert_ok!(TrustlessFileServer::delete_file(RuntimeOrigin::signed(1), key));
+        assert_eq!(
+            TrustlessFileServer::get_file_history(key.encode()),
+            vec![
+                HistoryEntry {
+                    block: 2,
+                    change: FileChange::MetadataUpdated { who: 1 },
+                },
+                HistoryEntry {
+                    block: 3,
+                    change: FileChange::OwnershipTransferred { from: 1, to: 2 },
+                },
+            ]
+        );
+
+        // the oldest change is dropped to make room for new ones
+        System::set_block_number(4);
+        for _ in 0..2 {
+            assert_ok!(TrustlessFileServer::set_metadata(
+                RuntimeOrigin::signed(2),
+                key,
+                test_metadata()
+            ));
+        }
+        let history = TrustlessFileServer::get_history(key);
+        assert_eq!(history.len(), 3);
+        assert_eq!(history[0].change, FileChange::OwnershipTransferred { from: 1, to: 2 });
+        assert_eq!(history[2].change, FileChange::MetadataUpdated { who: 2 });
+
+        // nothing is kept once the file is removed
+        assert_ok!(TrustlessFileServer::delete_file(RuntimeOrigin::signed(2), key));
+        assert!(TrustlessFileServer::get_history(key).is_empty());
+    });
+}
+
+#[test]
+fn it_should_serve_structured_listings_and_proofs() {
+    new_test_ext().execute_with(|| {
+        System::set_block_number(1);
+        let key = upload_test_file::<Test>(1);
+        assert_ok!(TrustlessFileServer::set_metadata(
+            RuntimeOrigin::signed(1),
+            key,
+            test_metadata()
+        ));
+        assert_eq!(
+            TrustlessFileServer::list_files(),
+            vec![FileSummary {
+                merkle_root: key.encode(),
+                owner: 1,
+                size: include_bytes!("../img/substrate.png").len() as u64,
+                pieces: 12,
+                license: Some(License::Spdx(b"CC0-1.0".to_vec())),
+                uploaded_at: Some(UploadTime {
+                    block: 1,
+                    timestamp: 0
+                }),
+                cid: None,
+            }]
+        );
+
+        let proof = TrustlessFileServer::get_piece_proof(key.encode(), 3).unwrap();
+        let (ipfs_hash, expected) = TrustlessFileServer::get_proof(key.encode(), 3).unwrap();
+        assert_eq!((proof.ipfs_hash, proof.proof), (ipfs_hash, expected));
+        assert!(!proof.uploaded);
+        assert_ok!(TrustlessFileServer::report_upload_progress(
+            RuntimeOrigin::none(),
+            key,
+            4,
+            12,
+            authority_proof((b"progress", key, 4u32, 12u32))
+        ));
+        assert!(
+            TrustlessFileServer::get_piece_proof(key.encode(), 3)
+                .unwrap()
+                .uploaded
+        );
+        assert_eq!(TrustlessFileServer::get_piece_proof(key.encode(), 12), None);
+    });
+}
+
+#[test]
+fn it_should_list_files_in_pages() {
+    new_test_ext().execute_with(|| {
+        System::set_block_number(1);
+        for size in [100, 200, 300] {
+            assert_ok!(TrustlessFileServer::upload_file(
+                RuntimeOrigin::signed(1),
+                vec![size as u8; size],
+                None,
+                None
+            ));
+        }
+        let all = TrustlessFileServer::list_files();
+        assert_eq!(all.len(), 3);
+
+        let first = TrustlessFileServer::get_files_paged(None, 2);
+        assert_eq!(first, all[..2]);
+        let start = first.last().map(|file| file.merkle_root.clone());
+        assert_eq!(TrustlessFileServer::get_files_paged(start, 2), all[2..]);
+        let start = Some(all[2].merkle_root.clone());
+        assert_eq!(TrustlessFileServer::get_files_paged(start, 2), []);
+        // pages never go beyond the maximum, nor start from anything but a merkle root
+        assert_eq!(TrustlessFileServer::get_files_paged(None, u32::MAX), all);
+        assert_eq!(TrustlessFileServer::get_files_paged(None, 0), []);
+        assert_eq!(TrustlessFileServer::get_files_paged(Some(vec![1]), 2), []);
+    });
+}
+
+#[test]
+fn it_should_export_files_along_with_their_trees() {
+    new_test_ext().execute_with(|| {
+        System::set_block_number(1);
+        for size in [100, 200, 300] {
+            assert_ok!(TrustlessFileServer::upload_file(
+                RuntimeOrigin::signed(1),
+                vec![size as u8; size],
+                None,
+                None
+            ));
+        }
+        let all = TrustlessFileServer::list_files();
+        let records = TrustlessFileServer::export_state(None, 2);
+        assert_eq!(records.len(), 2);
+        for (record, summary) in records.iter().zip(&all) {
+            assert_eq!(&record.summary, summary);
+            let tree = record.tree.as_ref().unwrap();
+            assert_eq!(tree.last(), Some(&summary.merkle_root));
+            assert_eq!(
+                TrustlessFileServer::get_merkle_tree(summary.merkle_root.clone()).as_ref(),
+                Some(tree)
+            );
+        }
+        let start = Some(all[1].merkle_root.clone());
+        let rest = TrustlessFileServer::export_state(start, 2);
+        assert_eq!(rest.len(), 1);
+        assert_eq!(rest[0].summary, all[2]);
+
+        // frozen files are exported without their trees
+        let key = H256::decode(&mut all[0].merkle_root.as_slice()).unwrap();
+        assert_ok!(TrustlessFileServer::freeze_file(RuntimeOrigin::signed(1), key));
+        assert_eq!(TrustlessFileServer::export_state(None, 1)[0].tree, None);
+        assert_eq!(TrustlessFileServer::export_state(Some(vec![1]), 2), []);
+    });
+}
+
+#[test]
+fn it_should_plan_downloads_piece_by_piece() {
+    use crate::file_merkle_tree::verify_proof;
+
+    new_test_ext().execute_with(|| {
+        System::set_block_number(1);
+        let content = (0..3000).map(|i| (i % 251) as u8).collect::<Vec<_>>();
+        assert_ok!(TrustlessFileServer::upload_file(
+            RuntimeOrigin::signed(1),
+            content.clone(),
+            None,
+            None
+        ));
+        let key = FileOwners::<Test>::iter_keys().next().unwrap();
+        let plan = TrustlessFileServer::get_download_plan(key.encode()).unwrap();
+        let info = TrustlessFileServer::get_file_info(key.encode()).unwrap();
+        assert_eq!(plan.len() as u32, info.pieces);
+        for (chunk, content) in plan.iter().zip(content.chunks(info.chunk_size as usize)) {
+            assert_eq!(chunk.chunk_hash, sha2_256(content));
+            assert_eq!(chunk.ipfs_hash, ipfs::ipfs_get_hash_from_sha256(&chunk.chunk_hash));
+            assert!(!chunk.uploaded);
+            let (_, proof) = TrustlessFileServer::get_proof(key.encode(), chunk.position).unwrap();
+            assert_eq!(chunk.proof_len as usize, proof.len());
+            let mut leaf = content.to_vec();
+            leaf.resize(info.chunk_size as usize, 0);
+            assert!(verify_proof(key.as_ref(), chunk.position, &sha2_256(&leaf), &proof));
+        }
+
+        assert_eq!(TrustlessFileServer::get_download_plan(vec![1]), None);
+        assert_ok!(TrustlessFileServer::freeze_file(RuntimeOrigin::signed(1), key));
+        assert_eq!(TrustlessFileServer::get_download_plan(key.encode()), None);
+    });
+}
+
+#[test]
+fn it_should_record_upload_times() {
+    new_test_ext().execute_with(|| {
+        System::set_block_number(5);
+        Timestamp::set_timestamp(30_000);
+        let key = upload_test_file::<Test>(1);
+        let uploaded_at = UploadTime {
+            block: 5,
+            timestamp: 30_000,
+        };
+        assert_eq!(TrustlessFileServer::get_upload_time(key), Some(uploaded_at));
+        assert_eq!(TrustlessFileServer::list_files()[0].uploaded_at, Some(uploaded_at));
+
+        // registering the file again keeps its first upload time
+        System::set_block_number(6);
+        Timestamp::set_timestamp(36_000);
+        upload_test_file::<Test>(2);
+        assert_eq!(TrustlessFileServer::get_upload_time(key), Some(uploaded_at));
+
+        assert_ok!(TrustlessFileServer::delete_file(RuntimeOrigin::signed(1), key));
+        assert_ok!(TrustlessFileServer::delete_file(RuntimeOrigin::signed(2), key));
+        assert_eq!(TrustlessFileServer::get_upload_time(key), None);
+    });
+}
+
+#[test]
+fn it_should_expose_proof_bounds_as_constants() {
+    let constants = TrustlessFileServer::pallet_constants_metadata();
+    let constant = |name| {
+        let constant = constants.iter().find(|c| c.name == name).unwrap();
+        u32::decode(&mut constant.value.as_slice()).unwrap()
+    };
+    assert_eq!(constant("MaxPieces"), 32);
+    assert_eq!(constant("MaxProofLen"), 5);
+    assert_eq!(TrustlessFileServer::MaxProofLen(), crate::MAX_TREE_DEPTH);
+}
+
+#[test]
+fn listings_should_not_read_merkle_trees() {
+    new_test_ext().execute_with(|| {
+        System::set_block_number(1);
+        let key = upload_test_file::<Test>(1);
+        let size = include_bytes!("../img/substrate.png").len() as u64;
+        // listings and stats are answered out of the small values alone
+        FileTrees::<Test>::remove(key);
+
+        assert_eq!(TrustlessFileServer::get_files(), vec![(key.encode(), 12)]);
+        let summaries = TrustlessFileServer::list_files();
+        assert_eq!((summaries[0].size, summaries[0].pieces), (size, 12));
+        let info = TrustlessFileServer::get_file_info(key.encode()).unwrap();
+        assert_eq!((info.owner, info.size, info.pieces), (1, size, 12));
+        assert_eq!(TrustlessFileServer::file_pieces(key), Some(12));
+        assert!(TrustlessFileServer::exists(key.encode()));
+        assert_eq!(TrustlessFileServer::get_proof(key.encode(), 0), None);
+    });
+}
@@ -0,0 +1,572 @@
+use alloc::string::String;
+use codec::{Decode, Encode, MaxEncodedLen};
+use frame_support::pallet_prelude::{ConstU32, Get};
+use frame_support::{
+    BoundedVec, CloneNoBound, DefaultNoBound, EqNoBound, PartialEqNoBound, RuntimeDebug,
+    RuntimeDebugNoBound,
+};
+use scale_info::TypeInfo;
+use sp_std::vec::Vec;
+
+use crate::file_merkle_tree::{BoundedProof, FileMerkleTree};
+use crate::ipfs::CID_LEN;
+use crate::MAX_PIECES;
+
+/// User-editable descriptive data attached to a registered file.
+///
+/// Unlike the merkle tree, metadata is not part of the file's identity and can be changed by the
+/// owner at any time without re-uploading the content.
+#[derive(
+    CloneNoBound,
+    DefaultNoBound,
+    Encode,
+    Decode,
+    EqNoBound,
+    PartialEqNoBound,
+    RuntimeDebugNoBound,
+    TypeInfo,
+    MaxEncodedLen,
+)]
+#[scale_info(skip_type_params(StringLimit, MaxTags))]
+#[codec(mel_bound(Balance: MaxEncodedLen))]
+pub struct FileMetadata<
+    StringLimit: Get<u32>,
+    MaxTags: Get<u32>,
+    Balance: Clone + Eq + core::fmt::Debug,
+> {
+    /// Human readable file name.
+    pub name: BoundedVec<u8, StringLimit>,
+    /// MIME type of the content, e.g. `image/png`.
+    pub mime_type: BoundedVec<u8, StringLimit>,
+    /// Free-form tags used to classify the file.
+    pub tags: BoundedVec<BoundedVec<u8, StringLimit>, MaxTags>,
+    /// License the content is distributed under, if any.
+    pub license: Option<License<BoundedVec<u8, StringLimit>>>,
+    /// Price the owner asks for every chunk retrieved through a payment channel, if any.
+    pub retrieval_price: Option<Balance>,
+}
+
+/// License of a file, either a well-known one or a custom one described elsewhere.
+#[derive(Clone, Encode, Decode, Eq, PartialEq, RuntimeDebug, TypeInfo, MaxEncodedLen)]
+pub enum License<Text> {
+    /// [SPDX license identifier](https://spdx.org/licenses/), e.g. `MIT` or `CC-BY-4.0`.
+    Spdx(Text),
+    /// URI of the license terms.
+    Uri(Text),
+}
+
+impl<Text: AsRef<[u8]>> License<Text> {
+    /// Whether the license is not empty and, for SPDX identifiers, only made of the characters
+    /// they are allowed to contain.
+    pub fn is_valid(&self) -> bool {
+        match self {
+            Self::Spdx(id) => {
+                let id = id.as_ref();
+                !id.is_empty()
+                    && id
+                        .iter()
+                        .all(|c| c.is_ascii_alphanumeric() || matches!(c, b'-' | b'.' | b'+'))
+            }
+            Self::Uri(uri) => !uri.as_ref().is_empty(),
+        }
+    }
+}
+
+impl<Text> License<Text> {
+    pub fn map<U>(self, f: impl FnOnce(Text) -> U) -> License<U> {
+        match self {
+            Self::Spdx(id) => License::Spdx(f(id)),
+            Self::Uri(uri) => License::Uri(f(uri)),
+        }
+    }
+}
+
+/// Summary of a registered file, as exposed through the runtime API.
+#[derive(Clone, Encode, Decode, Eq, PartialEq, RuntimeDebug, TypeInfo)]
+pub struct FileInfo<AccountId> {
+    /// Account that first uploaded the file, or the registrant it was handed over to.
+    pub owner: AccountId,
+    /// Merkle root identifying the file.
+    pub merkle_root: Vec<u8>,
+    /// File size, in bytes.
+    pub size: u64,
+    /// Number of chunks the file is divided in.
+    pub pieces: u32,
+    /// Size of every chunk, in bytes. Only the last one might be smaller.
+    pub chunk_size: u32,
+    /// Whether the merkle root is also the BitTorrent v2 (BEP-52) `pieces root` of the file.
+    pub bep52: bool,
+    /// sha256 of the whole file. Missing for files uploaded before checksums were recorded.
+    pub sha256: Option<[u8; 32]>,
+    /// Number of accounts that have registered the file.
+    pub registrants: u32,
+    /// File name, empty if no metadata was set.
+    pub name: Vec<u8>,
+    /// MIME type, empty if no metadata was set.
+    pub mime_type: Vec<u8>,
+    /// Tags attached to the file.
+    pub tags: Vec<Vec<u8>>,
+    /// License of the content, if any was set.
+    pub license: Option<License<Vec<u8>>>,
+    /// Price per chunk retrieved through a payment channel, if the owner set one.
+    pub retrieval_price: Option<u128>,
+    /// Base32 CID of the whole file on IPFS, if it is split as `ipfs add` splits files.
+    pub cid: Option<Vec<u8>>,
+}
+
+/// Size and chunking of a registered file, kept apart from its merkle tree so that they can be
+/// read without decoding the tree.
+#[derive(Clone, Copy, Encode, Decode, Eq, PartialEq, RuntimeDebug, TypeInfo, MaxEncodedLen)]
+pub struct FileLayout {
+    pub size: u64,
+    pub chunk_size: u32,
+    pub pieces: u32,
+    /// Whether the merkle root is also the BitTorrent v2 (BEP-52) `pieces root` of the file.
+    pub bep52: bool,
+    /// Binary CID of the whole file on IPFS, if it is split as `ipfs add` splits files.
+    pub cid: Option<[u8; CID_LEN]>,
+}
+
+impl From<&FileMerkleTree> for FileLayout {
+    /// Layout of the file of `tree`, with no CID, which depends on how the runtime splits files.
+    fn from(tree: &FileMerkleTree) -> Self {
+        Self {
+            size: tree.file_size,
+            chunk_size: tree.chunk_size,
+            pieces: tree.pieces(),
+            bep52: tree.is_bep52(),
+            cid: None,
+        }
+    }
+}
+
+/// When a file was registered.
+#[derive(Clone, Copy, Encode, Decode, Eq, PartialEq, RuntimeDebug, TypeInfo, MaxEncodedLen)]
+pub struct UploadTime<BlockNumber> {
+    pub block: BlockNumber,
+    /// Milliseconds since the Unix epoch.
+    pub timestamp: u64,
+}
+
+/// Entry of the listing of registered files, as exposed through the runtime API.
+#[derive(Clone, Encode, Decode, Eq, PartialEq, RuntimeDebug, TypeInfo)]
+pub struct FileSummary<AccountId> {
+    pub merkle_root: Vec<u8>,
+    pub owner: AccountId,
+    /// File size, in bytes.
+    pub size: u64,
+    pub pieces: u32,
+    /// License of the content, if any was set, so that listings can be filtered by it.
+    pub license: Option<License<Vec<u8>>>,
+    /// When the file was registered. Missing for files registered before upload times were
+    /// recorded.
+    pub uploaded_at: Option<UploadTime<u32>>,
+    /// Base32 CID of the whole file on IPFS, if it is split as `ipfs add` splits files.
+    pub cid: Option<Vec<u8>>,
+}
+
+/// Registered file exported for archival, as exposed through the runtime API.
+#[derive(Clone, Encode, Decode, Eq, PartialEq, RuntimeDebug, TypeInfo)]
+pub struct FileRecord<AccountId> {
+    pub summary: FileSummary<AccountId>,
+    /// Nodes of the merkle tree of the file, as returned by `get_merkle_tree`, whose root is
+    /// the merkle root of the summary. `None` while the file is frozen by its owner.
+    pub tree: Option<Vec<Vec<u8>>>,
+}
+
+/// Merkle proof of a piece, as exposed through the runtime API.
+#[derive(Clone, Encode, Decode, Eq, PartialEq, RuntimeDebug, TypeInfo)]
+pub struct PieceProof {
+    /// IPFS hash of the piece.
+    pub ipfs_hash: String,
+    pub proof: BoundedProof,
+    /// Whether the piece has been confirmed to be on IPFS.
+    pub uploaded: bool,
+}
+
+/// Piece of a file to fetch, as planned by `get_download_plan`.
+#[derive(Clone, Encode, Decode, Eq, PartialEq, RuntimeDebug, TypeInfo)]
+pub struct PlannedChunk {
+    pub position: u64,
+    /// IPFS hash of the piece.
+    pub ipfs_hash: String,
+    /// SHA-256 hash of the piece as served, the last one not being zero-padded.
+    pub chunk_hash: [u8; 32],
+    /// Number of hashes in the merkle proof of the piece.
+    pub proof_len: u32,
+    /// Whether the piece has been confirmed to be on IPFS.
+    pub uploaded: bool,
+}
+
+/// Pieces of a file confirmed to be on IPFS, as exposed through the runtime API.
+#[derive(Clone, Encode, Decode, Eq, PartialEq, RuntimeDebug, TypeInfo)]
+pub struct UploadStatus {
+    /// Number of pieces the file is divided in.
+    pub pieces: u32,
+    /// Bitfield where bit `i` is set once the piece at position `i` has been confirmed uploaded.
+    pub uploaded: u32,
+}
+
+// every piece needs its own bit
+const _: () = assert!(MAX_PIECES <= u32::BITS);
+
+impl UploadStatus {
+    /// Whether the piece at `position` has been confirmed uploaded.
+    pub fn is_uploaded(&self, position: u64) -> bool {
+        position < self.pieces.into() && self.uploaded & (1 << position) != 0
+    }
+
+    /// Whether all the pieces of the file have been confirmed uploaded.
+    pub fn is_complete(&self) -> bool {
+        (0..self.pieces.into()).all(|position| self.is_uploaded(position))
+    }
+}
+
+/// Outcome of checking the stored merkle tree of a file for corruption, as exposed through the
+/// runtime API.
+#[derive(Clone, Encode, Decode, Eq, PartialEq, RuntimeDebug, TypeInfo)]
+pub struct AuditReport {
+    /// Number of pieces the file is divided in.
+    pub pieces: u32,
+    /// Whether the tree has as many nodes as its pieces require. Nothing else is checked
+    /// otherwise.
+    pub well_formed: bool,
+    /// Positions of the corrupted nodes, level by level starting with the (padded) leaves:
+    /// padding leaves other than the filler and inner nodes other than the hash of their
+    /// children.
+    pub corrupted_nodes: Vec<u32>,
+    /// Whether the merkle root rebuilt out of the leaves is the one the file is registered under.
+    pub root_matches: bool,
+}
+
+impl AuditReport {
+    /// Whether no corruption was found, so that the proofs of the file can be trusted.
+    pub fn is_intact(&self) -> bool {
+        self.well_formed && self.corrupted_nodes.is_empty() && self.root_matches
+    }
+}
+
+/// Track record of an account as a publisher, so that frontends can rank or gate content by the
+/// trustworthiness of whoever uploaded it.
+#[derive(Clone, Default, Encode, Decode, Eq, PartialEq, RuntimeDebug, TypeInfo, MaxEncodedLen)]
+pub struct ReputationRecord {
+    /// Files first registered by the account.
+    pub uploads: u32,
+    /// Storage deal challenges answered in time.
+    pub challenges_passed: u32,
+    /// Storage deal challenges left to expire.
+    pub challenges_failed: u32,
+    /// Reports about files owned by the account that were dismissed.
+    pub reports_dismissed: u32,
+    /// Reports about files owned by the account that were upheld, taking the file down.
+    pub reports_upheld: u32,
+}
+
+/// Storage registered by an account, along with the deposits locked for it, so that wallets can
+/// show what an upload would add up to.
+#[derive(Clone, Default, Encode, Decode, Eq, PartialEq, RuntimeDebug, TypeInfo, MaxEncodedLen)]
+pub struct UsageRecord<Balance> {
+    /// Files the account is a registrant of.
+    pub files: u32,
+    /// Total size of those files, in bytes.
+    pub bytes: u64,
+    /// Native deposits held from the account for the registrations it paid for, its own or
+    /// sponsored ones. Deposits paid in assets are not included.
+    pub deposit_locked: Balance,
+}
+
+/// Content of a file served by a node through its RPC, as reported by its offchain worker.
+#[derive(Clone, Default, Encode, Decode, Eq, PartialEq, RuntimeDebug, TypeInfo, MaxEncodedLen)]
+pub struct BandwidthRecord<BlockNumber> {
+    /// Bytes served.
+    pub bytes: u64,
+    /// Chunks served, whole downloads counting all the chunks of the file.
+    pub chunks: u64,
+    /// Block the last report was made at, so that reports are not counted twice.
+    pub reported_at: Option<BlockNumber>,
+}
+
+impl ReputationRecord {
+    /// Summary of the record as a single number. Takedowns weigh the most, as they mean the
+    /// account published content the moderation origin found unacceptable. Frontends with
+    /// different criteria are expected to weigh the counters themselves.
+    pub fn score(&self) -> i64 {
+        let gains = self.uploads as i64
+            + 2 * self.challenges_passed as i64
+            + 2 * self.reports_dismissed as i64;
+        let losses = 10 * self.challenges_failed as i64 + 50 * self.reports_upheld as i64;
+        gains - losses
+    }
+}
+
+/// Proof that a report of an offchain worker was made by one of the authorities, whose key signed
+/// the report along with the authority and the block.
+#[derive(Clone, Encode, Decode, Eq, PartialEq, RuntimeDebug, TypeInfo)]
+pub struct AuthorityProof<AuthorityId, BlockNumber, Signature> {
+    pub authority: AuthorityId,
+    /// Block the report was made at, so that it cannot be replayed long after.
+    pub block: BlockNumber,
+    pub signature: Signature,
+}
+
+/// Outcome of the last availability probe of a file through a public IPFS gateway.
+#[derive(Clone, Encode, Decode, Eq, PartialEq, RuntimeDebug, TypeInfo, MaxEncodedLen)]
+pub struct ProbeResult<BlockNumber> {
+    /// Block at which the result was recorded.
+    pub block: BlockNumber,
+    /// Chunk that was fetched.
+    pub position: u64,
+    /// Whether the gateway served the chunk and its content matched the on-chain hash.
+    pub available: bool,
+}
+
+/// Outcome of the last audit of a file, i.e. of a piece drawn at random for the offchain worker
+/// to fetch back from IPFS.
+#[derive(Clone, Encode, Decode, Eq, PartialEq, RuntimeDebug, TypeInfo, MaxEncodedLen)]
+pub struct PieceAudit<BlockNumber> {
+    /// Block at which the result was recorded.
+    pub block: BlockNumber,
+    /// Piece that was audited.
+    pub position: u64,
+    /// Whether the IPFS node served the piece and its content matched the on-chain hash.
+    pub passed: bool,
+}
+
+/// Change to a registered file recorded in its history.
+#[derive(Clone, Encode, Decode, Eq, PartialEq, RuntimeDebug, TypeInfo, MaxEncodedLen)]
+pub enum FileChange<AccountId> {
+    /// The owner deleted its registration, so ownership passed to another registrant.
+    OwnershipTransferred { from: AccountId, to: AccountId },
+    /// The owner replaced the metadata of the file.
+    MetadataUpdated { who: AccountId },
+}
+
+/// Entry of the history of a file.
+#[derive(Clone, Encode, Decode, Eq, PartialEq, RuntimeDebug, TypeInfo, MaxEncodedLen)]
+pub struct HistoryEntry<AccountId, BlockNumber> {
+    /// Block in which the change happened.
+    pub block: BlockNumber,
+    pub change: FileChange<AccountId>,
+}
+
+/// Human readable name pointing to a registered file.
+#[derive(Clone, Encode, Decode, Eq, PartialEq, RuntimeDebug, TypeInfo, MaxEncodedLen)]
+pub struct Alias<AccountId, Hash, Balance, BlockNumber> {
+    /// Account that registered the name, the only one allowed to change or release it.
+    pub owner: AccountId,
+    /// Merkle root of the file the name points to, as of its registration.
+    pub merkle_root: Hash,
+    /// Deposit held from the owner while the name is registered.
+    pub deposit: Balance,
+    /// Block from which the name no longer resolves and can be registered by anyone, unless
+    /// renewed by its owner before.
+    pub expires_at: BlockNumber,
+}
+
+/// Group of files referenced by a single hash, the merkle root over their own merkle roots.
+#[derive(Clone, Encode, Decode, Eq, PartialEq, RuntimeDebug, TypeInfo, MaxEncodedLen)]
+pub struct Bundle<AccountId, Members, Balance> {
+    /// Account that created the bundle, the only one allowed to remove it.
+    pub owner: AccountId,
+    /// Merkle roots of the files in the bundle, in the order they are hashed.
+    pub members: Members,
+    /// Deposit held from the owner while the bundle exists.
+    pub deposit: Balance,
+}
+
+/// Identifier of a collection of files.
+pub type CollectionId = u64;
+
+/// Files an owner manages together, e.g. the catalogue of a publisher, so that they can be priced
+/// and handed over at once instead of one by one.
+#[derive(Clone, Encode, Decode, Eq, PartialEq, RuntimeDebug, TypeInfo, MaxEncodedLen)]
+pub struct Collection<AccountId, Members, Balance> {
+    /// Account that created the collection or it was transferred to, the only one allowed to
+    /// manage it.
+    pub owner: AccountId,
+    /// Merkle roots of the files in the collection, in the order they were added.
+    pub members: Members,
+    /// Deposit held from the owner while the collection exists.
+    pub deposit: Balance,
+}
+
+/// Collection of files, as exposed through the runtime API.
+#[derive(Clone, Encode, Decode, Eq, PartialEq, RuntimeDebug, TypeInfo)]
+pub struct CollectionInfo<AccountId> {
+    pub owner: AccountId,
+    /// Current merkle roots of the files in the collection, following rechunked files.
+    pub members: Vec<Vec<u8>>,
+}
+
+/// Commitment to the key an encrypted file can be decrypted with, to be revealed once its
+/// embargo is over.
+#[derive(Clone, Encode, Decode, Eq, PartialEq, RuntimeDebug, TypeInfo, MaxEncodedLen)]
+pub struct TimeLock<BlockNumber, Key> {
+    /// sha256 of the key.
+    pub commitment: [u8; 32],
+    /// First block in which the key can be revealed.
+    pub reveal_at: BlockNumber,
+    /// The key, once revealed.
+    pub key: Option<Key>,
+}
+
+/// Share of the key of a time-locked file, held by a committee member.
+#[derive(Clone, Encode, Decode, Eq, PartialEq, RuntimeDebug, TypeInfo, MaxEncodedLen)]
+pub struct KeyShare<AccountId, Key, EncryptedShare> {
+    pub holder: AccountId,
+    /// sha256 of the share.
+    pub commitment: [u8; 32],
+    /// The share encrypted to the holder by the owner of the file, to be decrypted off-chain.
+    pub encrypted: EncryptedShare,
+    /// The share, once submitted by its holder after the embargo.
+    pub share: Option<Key>,
+}
+
+/// Key of a time-locked file split among a committee with [`crate::shamir`], rebuilt on chain
+/// once `threshold` holders submit their shares after the embargo.
+#[derive(Clone, Encode, Decode, Eq, PartialEq, RuntimeDebug, TypeInfo, MaxEncodedLen)]
+pub struct KeyEscrow<Shares> {
+    pub threshold: u32,
+    /// Shares in the order the key was split in: the `i`-th one is evaluated at `x = i + 1`.
+    pub shares: Shares,
+}
+
+/// Upload whose merkle root was claimed by the uploader instead of computed on chain, pending to
+/// be checked by the offchain worker against the content.
+#[derive(Clone, Encode, Decode, Eq, PartialEq, RuntimeDebug, TypeInfo, MaxEncodedLen)]
+pub struct RootClaim<AccountId, Balance> {
+    /// Account that uploaded the file, which becomes its owner once the root is confirmed.
+    pub who: AccountId,
+    /// Deposit held from the uploader, slashed if the root turns out to be wrong.
+    pub deposit: Balance,
+    /// File size, in bytes.
+    pub size: u64,
+}
+
+/// Statement that a file is registered in this chain, sent to sibling parachains through XCM so
+/// that they can trust it without keeping a copy of the file.
+#[derive(Clone, Encode, Decode, Eq, PartialEq, RuntimeDebug, TypeInfo, MaxEncodedLen)]
+pub struct FileAttestation<AccountId, Hash> {
+    pub merkle_root: Hash,
+    pub owner: AccountId,
+    /// File size, in bytes.
+    pub size: u64,
+    pub pieces: u32,
+}
+
+/// File registered in a trusted sibling parachain, as attested by it.
+#[derive(Clone, Encode, Decode, Eq, PartialEq, RuntimeDebug, TypeInfo, MaxEncodedLen)]
+pub struct ExternalFile<AccountId> {
+    /// Owner of the file in the sibling parachain.
+    pub owner: AccountId,
+    /// File size, in bytes.
+    pub size: u64,
+    pub pieces: u32,
+}
+
+/// Upload bigger than the large file threshold, indexed but held until the approval origin lets
+/// it be registered and pushed to IPFS.
+#[derive(Clone, Encode, Decode, Eq, PartialEq, RuntimeDebug, TypeInfo, MaxEncodedLen)]
+pub struct PendingUpload<AccountId, Deposit, License> {
+    /// Account that becomes the owner of the file once approved.
+    pub who: AccountId,
+    /// Account that paid the deposit on behalf of `who`, if any.
+    pub sponsor: Option<AccountId>,
+    pub deposit: Deposit,
+    /// sha256 of the whole file.
+    pub sha256: [u8; 32],
+    pub license: Option<License>,
+    pub merkle_tree: FileMerkleTree,
+}
+
+/// Complaint about the content of a file, pending to be resolved by the moderation origin.
+#[derive(Clone, Encode, Decode, Eq, PartialEq, RuntimeDebug, TypeInfo, MaxEncodedLen)]
+pub struct Report<AccountId, Balance> {
+    pub reporter: AccountId,
+    /// Hash of the reasons of the complaint, published elsewhere.
+    pub reason_hash: [u8; 32],
+    /// Bond held from the reporter, slashed if the report is dismissed.
+    pub bond: Balance,
+}
+
+/// What is kept of a file taken down by the moderation origin, so that references to it and
+/// the proofs of its pieces can still be told apart as those of a file that was registered and
+/// later removed.
+#[derive(Clone, Encode, Decode, Eq, PartialEq, RuntimeDebug, TypeInfo, MaxEncodedLen)]
+pub struct Tombstone<BlockNumber> {
+    /// Block the file was taken down at.
+    pub removed_at: BlockNumber,
+    /// Hash of the reasons of the upheld report.
+    pub reason_hash: [u8; 32],
+    pub size: u64,
+    pub chunk_size: u32,
+    /// Leaf hashes of the pieces of the file, in order.
+    pub piece_hashes: BoundedVec<[u8; 32], ConstU32<MAX_PIECES>>,
+}
+
+impl<BlockNumber> Tombstone<BlockNumber> {
+    /// Tombstone of the file with `tree`, taken down at `removed_at` for the reasons hashed in
+    /// `reason_hash`.
+    pub(crate) fn new(
+        tree: &FileMerkleTree,
+        removed_at: BlockNumber,
+        reason_hash: [u8; 32],
+    ) -> Self {
+        let piece_hashes = (0..u64::from(tree.pieces()))
+            .filter_map(|position| tree.file_chunk_hash_at(position))
+            .collect::<Vec<_>>();
+        Self {
+            removed_at,
+            reason_hash,
+            size: tree.file_size,
+            chunk_size: tree.chunk_size,
+            // trees have at most `MAX_PIECES` pieces
+            piece_hashes: BoundedVec::truncate_from(piece_hashes),
+        }
+    }
+}
+
+/// What the native balance held by the pallet is for.
+#[derive(
+    Clone,
+    Copy,
+    Encode,
+    Decode,
+    Eq,
+    PartialEq,
+    Ord,
+    PartialOrd,
+    RuntimeDebug,
+    TypeInfo,
+    MaxEncodedLen,
+)]
+pub enum HoldReason {
+    /// Deposit of a registered file, or of an upload pending approval or root confirmation.
+    FileDeposit,
+    /// Deposit of a registered name.
+    AliasDeposit,
+    /// Deposit of a bundle.
+    BundleDeposit,
+    /// Deposit of a collection.
+    CollectionDeposit,
+    /// Bond of a pending report.
+    ReportBond,
+}
+
+/// Decision of the moderation origin on a report.
+#[derive(Clone, Copy, Encode, Decode, Eq, PartialEq, RuntimeDebug, TypeInfo, MaxEncodedLen)]
+pub enum Verdict {
+    /// The report is unfounded: the bond of the reporter is slashed.
+    Dismiss,
+    /// The report is upheld: the file is removed and every registrant gets its deposit back.
+    RemoveFile,
+}
+
+/// Deposit held while a file is registered, either in the native currency or in an asset.
+#[derive(Clone, Encode, Decode, Eq, PartialEq, RuntimeDebug, TypeInfo, MaxEncodedLen)]
+pub enum Deposit<Balance, AssetId, AssetBalance> {
+    /// Held from the native balance of the depositor.
+    Native(Balance),
+    /// Transferred to the pallet account, in the given asset.
+    Asset { id: AssetId, amount: AssetBalance },
+}
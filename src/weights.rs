@@ -41,39 +41,2611 @@ use core::marker::PhantomData;
 /// Weight functions needed for pallet_trustless_file_server.
 pub trait WeightInfo {
 	fn upload_file(x: u32, ) -> Weight;
+	fn set_metadata() -> Weight;
+	fn verify_proof(x: u32, ) -> Weight;
+	fn report_upload_progress() -> Weight;
+	fn report_chunk_allocations(x: u32, ) -> Weight;
+	fn report_probe() -> Weight;
+	fn rechunk_file() -> Weight;
+	fn report_rechunk(x: u32, r: u32, ) -> Weight;
+	fn delete_file() -> Weight;
+	fn pause() -> Weight;
+	fn unpause() -> Weight;
+	fn register_alias() -> Weight;
+	fn release_alias() -> Weight;
+	fn report_repin() -> Weight;
+	fn sponsor_upload(x: u32, ) -> Weight;
+	fn upload_file_unsigned(x: u32, ) -> Weight;
+	fn upload_files(n: u32, x: u32, ) -> Weight;
+	fn create_bundle(n: u32, ) -> Weight;
+	fn remove_bundle() -> Weight;
+	fn commit_key() -> Weight;
+	fn reveal_key() -> Weight;
+	fn escrow_key(n: u32, ) -> Weight;
+	fn submit_key_share(n: u32, ) -> Weight;
+	fn upload_file_with_root(x: u32, ) -> Weight;
+	fn confirm_root(x: u32, ) -> Weight;
+	fn reject_root() -> Weight;
+	fn report_file() -> Weight;
+	fn resolve_report(r: u32, n: u32, ) -> Weight;
+	fn schedule_deletion() -> Weight;
+	fn cancel_deletion() -> Weight;
+	fn approve_large_upload() -> Weight;
+	fn reject_large_upload() -> Weight;
+	fn attest_file() -> Weight;
+	fn register_external_file() -> Weight;
+	fn report_bandwidth() -> Weight;
+	fn report_audit() -> Weight;
+	fn set_ocw_authorities(n: u32, ) -> Weight;
+	fn set_retrieval_price() -> Weight;
+	fn create_collection() -> Weight;
+	fn add_to_collection() -> Weight;
+	fn remove_from_collection() -> Weight;
+	fn set_collection_price(n: u32, ) -> Weight;
+	fn transfer_collection(n: u32, ) -> Weight;
+	fn remove_collection() -> Weight;
+	fn reclaim_chunks(n: u32, ) -> Weight;
+	fn approve_review() -> Weight;
+	fn reject_review() -> Weight;
+	fn freeze_file() -> Weight;
+	fn unfreeze_file() -> Weight;
+	fn transfer_file() -> Weight;
+	fn set_management_threshold() -> Weight;
+	fn renew_alias() -> Weight;
+	fn reassign_alias() -> Weight;
+	fn cancel_root_claim() -> Weight;
+	fn reject_rechunk() -> Weight;
 }
 
 /// Weights for pallet_trustless_file_server using the Substrate node and recommended hardware.
 pub struct SubstrateWeight<T>(PhantomData<T>);
 impl<T: frame_system::Config> WeightInfo for SubstrateWeight<T> {
-	/// Storage: TrustlessFileServer Files (r:0 w:1)
-	/// Proof: TrustlessFileServer Files (max_values: None, max_size: Some(150), added: 2625, mode: MaxEncodedLen)
+	/// Storage: TrustlessFileServer BlockUploads (r:1 w:1)
+	/// Proof: TrustlessFileServer BlockUploads (max_values: Some(1), max_size: Some(129), added: 624, mode: MaxEncodedLen)
+	/// Storage: TrustlessFileServer FileOwners (r:1 w:1)
+	/// Proof: TrustlessFileServer FileOwners (max_values: None, max_size: Some(80), added: 2555, mode: MaxEncodedLen)
+	/// Storage: TrustlessFileServer FileTrees (r:0 w:1)
+	/// Proof: TrustlessFileServer FileTrees (max_values: None, max_size: Some(2116), added: 4591, mode: MaxEncodedLen)
+	/// Storage: TrustlessFileServer FileMeta (r:0 w:1)
+	/// Proof: TrustlessFileServer FileMeta (max_values: None, max_size: Some(65), added: 2540, mode: MaxEncodedLen)
+	/// Storage: System Account (r:1 w:1)
+	/// Proof: System Account (max_values: None, max_size: Some(128), added: 2603, mode: MaxEncodedLen)
+	/// Storage: TrustlessFileServer Registrants (r:0 w:1)
+	/// Proof: TrustlessFileServer Registrants (max_values: None, max_size: Some(114), added: 2589, mode: MaxEncodedLen)
+	/// Storage: TrustlessFileServer RegistrantCount (r:0 w:1)
+	/// Proof: TrustlessFileServer RegistrantCount (max_values: None, max_size: Some(52), added: 2527, mode: MaxEncodedLen)
+	/// Storage: TrustlessFileServer Checksums (r:0 w:1)
+	/// Proof: TrustlessFileServer Checksums (max_values: None, max_size: Some(80), added: 2555, mode: MaxEncodedLen)
+	/// Storage: TrustlessFileServer Paused (r:1 w:0)
+	/// Proof: TrustlessFileServer Paused (max_values: Some(1), max_size: Some(1), added: 496, mode: MaxEncodedLen)
+	/// Storage: TrustlessFileServer Metadata (r:0 w:1)
+	/// Proof: TrustlessFileServer Metadata (max_values: None, max_size: Some(761), added: 3236, mode: MaxEncodedLen)
+	/// Storage: TrustlessFileServer UploadedPieces (r:0 w:1)
+	/// Proof: TrustlessFileServer UploadedPieces (max_values: None, max_size: Some(52), added: 2527, mode: MaxEncodedLen)
+	/// Storage: TrustlessFileServer AccountUsage (r:1 w:1)
+	/// Proof: TrustlessFileServer AccountUsage (max_values: None, max_size: Some(76), added: 2551, mode: MaxEncodedLen)
+	/// Storage: TrustlessFileServer TotalBytesStored (r:1 w:1)
+	/// Proof: TrustlessFileServer TotalBytesStored (max_values: Some(1), max_size: Some(8), added: 503, mode: MaxEncodedLen)
+	/// Storage: Balances Holds (r:1 w:1)
+	/// Proof: Balances Holds (max_values: None, max_size: Some(134), added: 2609, mode: MaxEncodedLen)
+	/// Storage: TrustlessFileServer HeldBalance (r:1 w:1)
+	/// Proof: TrustlessFileServer HeldBalance (max_values: Some(1), max_size: Some(16), added: 511, mode: MaxEncodedLen)
+	/// Storage: Timestamp Now (r:1 w:0)
+	/// Proof: Timestamp Now (max_values: Some(1), max_size: Some(8), added: 503, mode: MaxEncodedLen)
+	/// Storage: TrustlessFileServer UploadedAt (r:0 w:1)
+	/// Proof: TrustlessFileServer UploadedAt (max_values: None, max_size: Some(60), added: 2535, mode: MaxEncodedLen)
+	/// Storage: TrustlessFileServer AccountUploads (r:1 w:1)
+	/// Proof: TrustlessFileServer AccountUploads (max_values: None, max_size: Some(52), added: 2527, mode: MaxEncodedLen)
 	/// The range of component `x` is `[1024, 32768]`.
 	fn upload_file(x: u32, ) -> Weight {
 		// Proof Size summary in bytes:
-		//  Measured:  `0`
+		//  Measured:  `6`
+		//  Estimated: `1614`
+		// Minimum execution time: 12_000_000 picoseconds.
+		Weight::from_parts(16_204_117, 1614)
+			// Standard Error: 6
+			.saturating_add(Weight::from_parts(3_825, 0).saturating_mul(x.into()))
+			.saturating_add(T::DbWeight::get().reads(10_u64))
+			.saturating_add(T::DbWeight::get().writes(16_u64))
+	}
+	/// Storage: TrustlessFileServer FileOwners (r:1 w:0)
+	/// Proof: TrustlessFileServer FileOwners (max_values: None, max_size: Some(80), added: 2555, mode: MaxEncodedLen)
+	/// Storage: TrustlessFileServer Metadata (r:0 w:1)
+	/// Proof: TrustlessFileServer Metadata (max_values: None, max_size: Some(761), added: 3236, mode: MaxEncodedLen)
+	/// Storage: TrustlessFileServer Paused (r:1 w:0)
+	/// Proof: TrustlessFileServer Paused (max_values: Some(1), max_size: Some(1), added: 496, mode: MaxEncodedLen)
+	/// Storage: TrustlessFileServer FileHistory (r:1 w:1)
+	/// Proof: TrustlessFileServer FileHistory (max_values: None, max_size: Some(3266), added: 5741, mode: MaxEncodedLen)
+	fn set_metadata() -> Weight {
+		// Proof Size summary in bytes:
+		//  Measured:  `2162`
+		//  Estimated: `6731`
+		// Minimum execution time: 17_000_000 picoseconds.
+		Weight::from_parts(18_000_000, 6731)
+			.saturating_add(T::DbWeight::get().reads(3_u64))
+			.saturating_add(T::DbWeight::get().writes(2_u64))
+	}
+	/// Storage: TrustlessFileServer FileOwners (r:1 w:0)
+	/// Proof: TrustlessFileServer FileOwners (max_values: None, max_size: Some(80), added: 2555, mode: MaxEncodedLen)
+	/// The range of component `x` is `[1, 5]`.
+	fn verify_proof(x: u32, ) -> Weight {
+		// Proof Size summary in bytes:
+		//  Measured:  `2162`
+		//  Estimated: `3615`
+		// Minimum execution time: 6_000_000 picoseconds.
+		Weight::from_parts(6_120_000, 3615)
+			// Standard Error: 1_402
+			.saturating_add(Weight::from_parts(1_285_000, 0).saturating_mul(x.into()))
+			.saturating_add(T::DbWeight::get().reads(1_u64))
+	}
+	/// Storage: TrustlessFileServer FileOwners (r:1 w:0)
+	/// Proof: TrustlessFileServer FileOwners (max_values: None, max_size: Some(80), added: 2555, mode: MaxEncodedLen)
+	/// Storage: TrustlessFileServer UploadProgress (r:1 w:1)
+	/// Proof: TrustlessFileServer UploadProgress (max_values: None, max_size: Some(52), added: 2527, mode: MaxEncodedLen)
+	/// Storage: TrustlessFileServer UploadedPieces (r:1 w:1)
+	/// Proof: TrustlessFileServer UploadedPieces (max_values: None, max_size: Some(52), added: 2527, mode: MaxEncodedLen)
+	fn report_upload_progress() -> Weight {
+		// Proof Size summary in bytes:
+		//  Measured:  `2162`
+		//  Estimated: `7132`
+		// Minimum execution time: 15_000_000 picoseconds.
+		Weight::from_parts(16_000_000, 7132)
+			.saturating_add(T::DbWeight::get().reads(3_u64))
+			.saturating_add(T::DbWeight::get().writes(2_u64))
+	}
+	/// Storage: TrustlessFileServer FileOwners (r:1 w:0)
+	/// Proof: TrustlessFileServer FileOwners (max_values: None, max_size: Some(80), added: 2555, mode: MaxEncodedLen)
+	/// Storage: TrustlessFileServer ChunkAllocations (r:0 w:1)
+	/// Proof: TrustlessFileServer ChunkAllocations (max_values: None, max_size: Some(249), added: 2724, mode: MaxEncodedLen)
+	/// Storage: TrustlessFileServer UploadedPieces (r:1 w:1)
+	/// Proof: TrustlessFileServer UploadedPieces (max_values: None, max_size: Some(52), added: 2527, mode: MaxEncodedLen)
+	/// The range of component `x` is `[1, 16]`.
+	fn report_chunk_allocations(x: u32, ) -> Weight {
+		// Proof Size summary in bytes:
+		//  Measured:  `2162`
+		//  Estimated: `3615`
+		// Minimum execution time: 14_000_000 picoseconds.
+		Weight::from_parts(14_713_207, 3615)
+			// Standard Error: 2_001
+			.saturating_add(Weight::from_parts(31_470, 0).saturating_mul(x.into()))
+			.saturating_add(T::DbWeight::get().reads(2_u64))
+			.saturating_add(T::DbWeight::get().writes(2_u64))
+	}
+	/// Storage: TrustlessFileServer FileOwners (r:1 w:0)
+	/// Proof: TrustlessFileServer FileOwners (max_values: None, max_size: Some(80), added: 2555, mode: MaxEncodedLen)
+	/// Storage: TrustlessFileServer ProbeResults (r:0 w:1)
+	/// Proof: TrustlessFileServer ProbeResults (max_values: None, max_size: Some(61), added: 2536, mode: MaxEncodedLen)
+	fn report_probe() -> Weight {
+		// Proof Size summary in bytes:
+		//  Measured:  `2162`
+		//  Estimated: `3615`
+		// Minimum execution time: 13_000_000 picoseconds.
+		Weight::from_parts(14_000_000, 3615)
+			.saturating_add(T::DbWeight::get().reads(1_u64))
+			.saturating_add(T::DbWeight::get().writes(1_u64))
+	}
+	/// Storage: TrustlessFileServer FileOwners (r:1 w:0)
+	/// Proof: TrustlessFileServer FileOwners (max_values: None, max_size: Some(80), added: 2555, mode: MaxEncodedLen)
+	/// Storage: TrustlessFileServer FileTrees (r:1 w:0)
+	/// Proof: TrustlessFileServer FileTrees (max_values: None, max_size: Some(2116), added: 4591, mode: MaxEncodedLen)
+	/// Storage: TrustlessFileServer PendingRechunks (r:0 w:1)
+	/// Proof: TrustlessFileServer PendingRechunks (max_values: None, max_size: Some(48), added: 2523, mode: MaxEncodedLen)
+	/// Storage: TrustlessFileServer Paused (r:1 w:0)
+	/// Proof: TrustlessFileServer Paused (max_values: Some(1), max_size: Some(1), added: 496, mode: MaxEncodedLen)
+	fn rechunk_file() -> Weight {
+		// Proof Size summary in bytes:
+		//  Measured:  `4160`
+		//  Estimated: `5613`
+		// Minimum execution time: 18_000_000 picoseconds.
+		Weight::from_parts(18_000_000, 5613)
+			.saturating_add(T::DbWeight::get().reads(3_u64))
+			.saturating_add(T::DbWeight::get().writes(1_u64))
+	}
+	/// Storage: TrustlessFileServer PendingRechunks (r:1 w:1)
+	/// Proof: TrustlessFileServer PendingRechunks (max_values: None, max_size: Some(48), added: 2523, mode: MaxEncodedLen)
+	/// Storage: TrustlessFileServer FileOwners (r:2 w:2)
+	/// Proof: TrustlessFileServer FileOwners (max_values: None, max_size: Some(80), added: 2555, mode: MaxEncodedLen)
+	/// Storage: TrustlessFileServer FileTrees (r:1 w:2)
+	/// Proof: TrustlessFileServer FileTrees (max_values: None, max_size: Some(2116), added: 4591, mode: MaxEncodedLen)
+	/// Storage: TrustlessFileServer FileMeta (r:0 w:2)
+	/// Proof: TrustlessFileServer FileMeta (max_values: None, max_size: Some(65), added: 2540, mode: MaxEncodedLen)
+	/// Storage: TrustlessFileServer BlockUploads (r:1 w:1)
+	/// Proof: TrustlessFileServer BlockUploads (max_values: Some(1), max_size: Some(129), added: 624, mode: MaxEncodedLen)
+	/// Storage: TrustlessFileServer Metadata (r:1 w:2)
+	/// Proof: TrustlessFileServer Metadata (max_values: None, max_size: Some(761), added: 3236, mode: MaxEncodedLen)
+	/// Storage: TrustlessFileServer Checksums (r:1 w:2)
+	/// Proof: TrustlessFileServer Checksums (max_values: None, max_size: Some(80), added: 2555, mode: MaxEncodedLen)
+	/// Storage: TrustlessFileServer UploadProgress (r:0 w:1)
+	/// Proof: TrustlessFileServer UploadProgress (max_values: None, max_size: Some(52), added: 2527, mode: MaxEncodedLen)
+	/// Storage: TrustlessFileServer ChunkAllocations (r:0 w:32)
+	/// Proof: TrustlessFileServer ChunkAllocations (max_values: None, max_size: Some(249), added: 2724, mode: MaxEncodedLen)
+	/// Storage: TrustlessFileServer ProbeResults (r:0 w:1)
+	/// Proof: TrustlessFileServer ProbeResults (max_values: None, max_size: Some(61), added: 2536, mode: MaxEncodedLen)
+	/// Storage: TrustlessFileServer LastPinnedAt (r:0 w:1)
+	/// Proof: TrustlessFileServer LastPinnedAt (max_values: None, max_size: Some(56), added: 2531, mode: MaxEncodedLen)
+	/// Storage: TrustlessFileServer RootAliases (r:0 w:2)
+	/// Proof: TrustlessFileServer RootAliases (max_values: None, max_size: Some(80), added: 2555, mode: MaxEncodedLen)
+	/// Storage: TrustlessFileServer RegistrantCount (r:1 w:2)
+	/// Proof: TrustlessFileServer RegistrantCount (max_values: None, max_size: Some(52), added: 2527, mode: MaxEncodedLen)
+	/// Storage: TrustlessFileServer Registrants (r:16 w:32)
+	/// Proof: TrustlessFileServer Registrants (max_values: None, max_size: Some(114), added: 2589, mode: MaxEncodedLen)
+	/// Storage: TrustlessFileServer Sponsors (r:16 w:32)
+	/// Proof: TrustlessFileServer Sponsors (max_values: None, max_size: Some(128), added: 2603, mode: MaxEncodedLen)
+	/// Storage: TrustlessFileServer UploadedPieces (r:0 w:1)
+	/// Proof: TrustlessFileServer UploadedPieces (max_values: None, max_size: Some(52), added: 2527, mode: MaxEncodedLen)
+	/// Storage: TrustlessFileServer PieceAudits (r:0 w:1)
+	/// Proof: TrustlessFileServer PieceAudits (max_values: None, max_size: Some(61), added: 2536, mode: MaxEncodedLen)
+	/// Storage: TrustlessFileServer UploadedAt (r:1 w:2)
+	/// Proof: TrustlessFileServer UploadedAt (max_values: None, max_size: Some(60), added: 2535, mode: MaxEncodedLen)
+	/// Storage: TrustlessFileServer FrozenFiles (r:1 w:2)
+	/// Proof: TrustlessFileServer FrozenFiles (max_values: None, max_size: Some(48), added: 2523, mode: MaxEncodedLen)
+	/// Storage: TrustlessFileServer ManagementThresholds (r:1 w:2)
+	/// Proof: TrustlessFileServer ManagementThresholds (max_values: None, max_size: Some(50), added: 2525, mode: MaxEncodedLen)
+	/// The range of component `x` is `[1, 32]`.
+	/// The range of component `r` is `[1, 16]`.
+	fn report_rechunk(x: u32, r: u32, ) -> Weight {
+		// Proof Size summary in bytes:
+		//  Measured:  `7793`
+		//  Estimated: `9246`
+		// Minimum execution time: 45_000_000 picoseconds.
+		Weight::from_parts(45_000_000, 9246)
+			// Standard Error: 1_000
+			.saturating_add(Weight::from_parts(1_150_000, 0).saturating_mul(x.into()))
+			// Standard Error: 2_000
+			.saturating_add(Weight::from_parts(2_400_000, 0).saturating_mul(r.into()))
+			.saturating_add(T::DbWeight::get().reads(11_u64))
+			.saturating_add(T::DbWeight::get().reads((2_u64).saturating_mul(r.into())))
+			.saturating_add(T::DbWeight::get().writes(26_u64))
+			.saturating_add(T::DbWeight::get().writes((1_u64).saturating_mul(x.into())))
+			.saturating_add(T::DbWeight::get().writes((4_u64).saturating_mul(r.into())))
+			.saturating_add(Weight::from_parts(0, 5192).saturating_mul(r.into()))
+	}
+	/// Storage: TrustlessFileServer FileOwners (r:1 w:1)
+	/// Proof: TrustlessFileServer FileOwners (max_values: None, max_size: Some(80), added: 2555, mode: MaxEncodedLen)
+	/// Storage: TrustlessFileServer FileMeta (r:1 w:1)
+	/// Proof: TrustlessFileServer FileMeta (max_values: None, max_size: Some(65), added: 2540, mode: MaxEncodedLen)
+	/// Storage: TrustlessFileServer FileTrees (r:0 w:1)
+	/// Proof: TrustlessFileServer FileTrees (max_values: None, max_size: Some(2116), added: 4591, mode: MaxEncodedLen)
+	/// Storage: TrustlessFileServer Registrants (r:1 w:1)
+	/// Proof: TrustlessFileServer Registrants (max_values: None, max_size: Some(114), added: 2589, mode: MaxEncodedLen)
+	/// Storage: TrustlessFileServer Sponsors (r:1 w:1)
+	/// Proof: TrustlessFileServer Sponsors (max_values: None, max_size: Some(128), added: 2603, mode: MaxEncodedLen)
+	/// Storage: System Account (r:1 w:1)
+	/// Proof: System Account (max_values: None, max_size: Some(128), added: 2603, mode: MaxEncodedLen)
+	/// Storage: TrustlessFileServer RegistrantCount (r:1 w:1)
+	/// Proof: TrustlessFileServer RegistrantCount (max_values: None, max_size: Some(52), added: 2527, mode: MaxEncodedLen)
+	/// Storage: TrustlessFileServer Metadata (r:0 w:1)
+	/// Proof: TrustlessFileServer Metadata (max_values: None, max_size: Some(761), added: 3236, mode: MaxEncodedLen)
+	/// Storage: TrustlessFileServer Checksums (r:0 w:1)
+	/// Proof: TrustlessFileServer Checksums (max_values: None, max_size: Some(80), added: 2555, mode: MaxEncodedLen)
+	/// Storage: TrustlessFileServer UploadProgress (r:0 w:1)
+	/// Proof: TrustlessFileServer UploadProgress (max_values: None, max_size: Some(52), added: 2527, mode: MaxEncodedLen)
+	/// Storage: TrustlessFileServer OrphanedChunks (r:0 w:1)
+	/// Proof: TrustlessFileServer OrphanedChunks (max_values: None, max_size: Some(48), added: 2523, mode: MaxEncodedLen)
+	/// Storage: TrustlessFileServer ProbeResults (r:0 w:1)
+	/// Proof: TrustlessFileServer ProbeResults (max_values: None, max_size: Some(61), added: 2536, mode: MaxEncodedLen)
+	/// Storage: TrustlessFileServer LastPinnedAt (r:0 w:1)
+	/// Proof: TrustlessFileServer LastPinnedAt (max_values: None, max_size: Some(56), added: 2531, mode: MaxEncodedLen)
+	/// Storage: TrustlessFileServer PendingRechunks (r:0 w:1)
+	/// Proof: TrustlessFileServer PendingRechunks (max_values: None, max_size: Some(48), added: 2523, mode: MaxEncodedLen)
+	/// Storage: TrustlessFileServer Paused (r:1 w:0)
+	/// Proof: TrustlessFileServer Paused (max_values: Some(1), max_size: Some(1), added: 496, mode: MaxEncodedLen)
+	/// Storage: TrustlessFileServer UploadedPieces (r:0 w:1)
+	/// Proof: TrustlessFileServer UploadedPieces (max_values: None, max_size: Some(52), added: 2527, mode: MaxEncodedLen)
+	/// Storage: TrustlessFileServer FileHistory (r:1 w:1)
+	/// Proof: TrustlessFileServer FileHistory (max_values: None, max_size: Some(3266), added: 5741, mode: MaxEncodedLen)
+	/// Storage: TrustlessFileServer AccountUsage (r:2 w:2)
+	/// Proof: TrustlessFileServer AccountUsage (max_values: None, max_size: Some(76), added: 2551, mode: MaxEncodedLen)
+	/// Storage: TrustlessFileServer TotalBytesStored (r:1 w:1)
+	/// Proof: TrustlessFileServer TotalBytesStored (max_values: Some(1), max_size: Some(8), added: 503, mode: MaxEncodedLen)
+	/// Storage: TrustlessFileServer PieceAudits (r:0 w:1)
+	/// Proof: TrustlessFileServer PieceAudits (max_values: None, max_size: Some(61), added: 2536, mode: MaxEncodedLen)
+	/// Storage: Balances Holds (r:1 w:1)
+	/// Proof: Balances Holds (max_values: None, max_size: Some(134), added: 2609, mode: MaxEncodedLen)
+	/// Storage: TrustlessFileServer HeldBalance (r:1 w:1)
+	/// Proof: TrustlessFileServer HeldBalance (max_values: Some(1), max_size: Some(16), added: 511, mode: MaxEncodedLen)
+	/// Storage: TrustlessFileServer UploadedAt (r:0 w:1)
+	/// Proof: TrustlessFileServer UploadedAt (max_values: None, max_size: Some(60), added: 2535, mode: MaxEncodedLen)
+	/// Storage: TrustlessFileServer FrozenFiles (r:0 w:1)
+	/// Proof: TrustlessFileServer FrozenFiles (max_values: None, max_size: Some(48), added: 2523, mode: MaxEncodedLen)
+	/// Storage: TrustlessFileServer ManagementThresholds (r:0 w:1)
+	/// Proof: TrustlessFileServer ManagementThresholds (max_values: None, max_size: Some(50), added: 2525, mode: MaxEncodedLen)
+	fn delete_file() -> Weight {
+		// Proof Size summary in bytes:
+		//  Measured:  `4160`
+		//  Estimated: `6731`
+		// Minimum execution time: 66_000_000 picoseconds.
+		Weight::from_parts(66_000_000, 6731)
+			.saturating_add(T::DbWeight::get().reads(13_u64))
+			.saturating_add(T::DbWeight::get().writes(25_u64))
+	}
+	/// Storage: TrustlessFileServer Paused (r:0 w:1)
+	/// Proof: TrustlessFileServer Paused (max_values: Some(1), max_size: Some(1), added: 496, mode: MaxEncodedLen)
+	fn pause() -> Weight {
+		// Proof Size summary in bytes:
+		//  Measured:  `6`
 		//  Estimated: `0`
-		// Minimum execution time: 10_000_000 picoseconds.
-		Weight::from_parts(14_013_003, 0)
+		// Minimum execution time: 9_000_000 picoseconds.
+		Weight::from_parts(9_000_000, 0)
+			.saturating_add(T::DbWeight::get().writes(1_u64))
+	}
+	/// Storage: TrustlessFileServer Paused (r:0 w:1)
+	/// Proof: TrustlessFileServer Paused (max_values: Some(1), max_size: Some(1), added: 496, mode: MaxEncodedLen)
+	fn unpause() -> Weight {
+		// Proof Size summary in bytes:
+		//  Measured:  `6`
+		//  Estimated: `0`
+		// Minimum execution time: 9_000_000 picoseconds.
+		Weight::from_parts(9_000_000, 0)
+			.saturating_add(T::DbWeight::get().writes(1_u64))
+	}
+	/// Storage: TrustlessFileServer Paused (r:1 w:0)
+	/// Proof: TrustlessFileServer Paused (max_values: Some(1), max_size: Some(1), added: 496, mode: MaxEncodedLen)
+	/// Storage: TrustlessFileServer FileOwners (r:1 w:0)
+	/// Proof: TrustlessFileServer FileOwners (max_values: None, max_size: Some(80), added: 2555, mode: MaxEncodedLen)
+	/// Storage: TrustlessFileServer Names (r:1 w:1)
+	/// Proof: TrustlessFileServer Names (max_values: None, max_size: Some(358), added: 2833, mode: MaxEncodedLen)
+	/// Storage: System Account (r:2 w:2)
+	/// Proof: System Account (max_values: None, max_size: Some(128), added: 2603, mode: MaxEncodedLen)
+	/// Storage: Balances Holds (r:2 w:2)
+	/// Proof: Balances Holds (max_values: None, max_size: Some(134), added: 2609, mode: MaxEncodedLen)
+	/// Storage: TrustlessFileServer HeldBalance (r:1 w:1)
+	/// Proof: TrustlessFileServer HeldBalance (max_values: Some(1), max_size: Some(16), added: 511, mode: MaxEncodedLen)
+	fn register_alias() -> Weight {
+		// Proof Size summary in bytes:
+		//  Measured:  `2267`
+		//  Estimated: `5613`
+		// Minimum execution time: 21_000_000 picoseconds.
+		Weight::from_parts(22_000_000, 5613)
+			.saturating_add(T::DbWeight::get().reads(8_u64))
+			.saturating_add(T::DbWeight::get().writes(6_u64))
+	}
+	/// Storage: TrustlessFileServer Paused (r:1 w:0)
+	/// Proof: TrustlessFileServer Paused (max_values: Some(1), max_size: Some(1), added: 496, mode: MaxEncodedLen)
+	/// Storage: TrustlessFileServer Names (r:1 w:1)
+	/// Proof: TrustlessFileServer Names (max_values: None, max_size: Some(358), added: 2833, mode: MaxEncodedLen)
+	/// Storage: System Account (r:1 w:1)
+	/// Proof: System Account (max_values: None, max_size: Some(128), added: 2603, mode: MaxEncodedLen)
+	/// Storage: Balances Holds (r:1 w:1)
+	/// Proof: Balances Holds (max_values: None, max_size: Some(134), added: 2609, mode: MaxEncodedLen)
+	/// Storage: TrustlessFileServer HeldBalance (r:1 w:1)
+	/// Proof: TrustlessFileServer HeldBalance (max_values: Some(1), max_size: Some(16), added: 511, mode: MaxEncodedLen)
+	fn release_alias() -> Weight {
+		// Proof Size summary in bytes:
+		//  Measured:  `252`
+		//  Estimated: `3819`
+		// Minimum execution time: 17_000_000 picoseconds.
+		Weight::from_parts(18_000_000, 3819)
+			.saturating_add(T::DbWeight::get().reads(5_u64))
+			.saturating_add(T::DbWeight::get().writes(4_u64))
+	}
+	/// Storage: TrustlessFileServer FileOwners (r:1 w:0)
+	/// Proof: TrustlessFileServer FileOwners (max_values: None, max_size: Some(80), added: 2555, mode: MaxEncodedLen)
+	/// Storage: TrustlessFileServer LastPinnedAt (r:1 w:1)
+	/// Proof: TrustlessFileServer LastPinnedAt (max_values: None, max_size: Some(56), added: 2531, mode: MaxEncodedLen)
+	fn report_repin() -> Weight {
+		// Proof Size summary in bytes:
+		//  Measured:  `2162`
+		//  Estimated: `7154`
+		// Minimum execution time: 15_000_000 picoseconds.
+		Weight::from_parts(16_000_000, 7154)
+			.saturating_add(T::DbWeight::get().reads(2_u64))
+			.saturating_add(T::DbWeight::get().writes(1_u64))
+	}
+
+	/// Storage: TrustlessFileServer BlockUploads (r:1 w:1)
+	/// Proof: TrustlessFileServer BlockUploads (max_values: Some(1), max_size: Some(129), added: 624, mode: MaxEncodedLen)
+	/// Storage: TrustlessFileServer FileOwners (r:1 w:1)
+	/// Proof: TrustlessFileServer FileOwners (max_values: None, max_size: Some(80), added: 2555, mode: MaxEncodedLen)
+	/// Storage: TrustlessFileServer FileTrees (r:0 w:1)
+	/// Proof: TrustlessFileServer FileTrees (max_values: None, max_size: Some(2116), added: 4591, mode: MaxEncodedLen)
+	/// Storage: TrustlessFileServer FileMeta (r:0 w:1)
+	/// Proof: TrustlessFileServer FileMeta (max_values: None, max_size: Some(65), added: 2540, mode: MaxEncodedLen)
+	/// Storage: System Account (r:1 w:1)
+	/// Proof: System Account (max_values: None, max_size: Some(128), added: 2603, mode: MaxEncodedLen)
+	/// Storage: TrustlessFileServer Registrants (r:0 w:1)
+	/// Proof: TrustlessFileServer Registrants (max_values: None, max_size: Some(114), added: 2589, mode: MaxEncodedLen)
+	/// Storage: TrustlessFileServer Sponsors (r:0 w:1)
+	/// Proof: TrustlessFileServer Sponsors (max_values: None, max_size: Some(128), added: 2603, mode: MaxEncodedLen)
+	/// Storage: TrustlessFileServer RegistrantCount (r:0 w:1)
+	/// Proof: TrustlessFileServer RegistrantCount (max_values: None, max_size: Some(52), added: 2527, mode: MaxEncodedLen)
+	/// Storage: TrustlessFileServer Checksums (r:0 w:1)
+	/// Proof: TrustlessFileServer Checksums (max_values: None, max_size: Some(80), added: 2555, mode: MaxEncodedLen)
+	/// Storage: TrustlessFileServer Paused (r:1 w:0)
+	/// Proof: TrustlessFileServer Paused (max_values: Some(1), max_size: Some(1), added: 496, mode: MaxEncodedLen)
+	/// Storage: TrustlessFileServer UploadedPieces (r:0 w:1)
+	/// Proof: TrustlessFileServer UploadedPieces (max_values: None, max_size: Some(52), added: 2527, mode: MaxEncodedLen)
+	/// Storage: TrustlessFileServer AccountUsage (r:2 w:2)
+	/// Proof: TrustlessFileServer AccountUsage (max_values: None, max_size: Some(76), added: 2551, mode: MaxEncodedLen)
+	/// Storage: TrustlessFileServer TotalBytesStored (r:1 w:1)
+	/// Proof: TrustlessFileServer TotalBytesStored (max_values: Some(1), max_size: Some(8), added: 503, mode: MaxEncodedLen)
+	/// Storage: Balances Holds (r:1 w:1)
+	/// Proof: Balances Holds (max_values: None, max_size: Some(134), added: 2609, mode: MaxEncodedLen)
+	/// Storage: TrustlessFileServer HeldBalance (r:1 w:1)
+	/// Proof: TrustlessFileServer HeldBalance (max_values: Some(1), max_size: Some(16), added: 511, mode: MaxEncodedLen)
+	/// Storage: Timestamp Now (r:1 w:0)
+	/// Proof: Timestamp Now (max_values: Some(1), max_size: Some(8), added: 503, mode: MaxEncodedLen)
+	/// Storage: TrustlessFileServer UploadedAt (r:0 w:1)
+	/// Proof: TrustlessFileServer UploadedAt (max_values: None, max_size: Some(60), added: 2535, mode: MaxEncodedLen)
+	/// Storage: TrustlessFileServer AccountUploads (r:1 w:1)
+	/// Proof: TrustlessFileServer AccountUploads (max_values: None, max_size: Some(52), added: 2527, mode: MaxEncodedLen)
+	/// The range of component `x` is `[1024, 32768]`.
+	fn sponsor_upload(x: u32, ) -> Weight {
+		// Proof Size summary in bytes:
+		//  Measured:  `6`
+		//  Estimated: `1614`
+		// Minimum execution time: 13_000_000 picoseconds.
+		Weight::from_parts(17_102_540, 1614)
+			// Standard Error: 6
+			.saturating_add(Weight::from_parts(3_830, 0).saturating_mul(x.into()))
+			.saturating_add(T::DbWeight::get().reads(11_u64))
+			.saturating_add(T::DbWeight::get().writes(17_u64))
+	}
+	/// Storage: TrustlessFileServer BlockUploads (r:1 w:1)
+	/// Proof: TrustlessFileServer BlockUploads (max_values: Some(1), max_size: Some(129), added: 624, mode: MaxEncodedLen)
+	/// Storage: TrustlessFileServer FileOwners (r:1 w:1)
+	/// Proof: TrustlessFileServer FileOwners (max_values: None, max_size: Some(80), added: 2555, mode: MaxEncodedLen)
+	/// Storage: TrustlessFileServer FileTrees (r:0 w:1)
+	/// Proof: TrustlessFileServer FileTrees (max_values: None, max_size: Some(2116), added: 4591, mode: MaxEncodedLen)
+	/// Storage: TrustlessFileServer FileMeta (r:0 w:1)
+	/// Proof: TrustlessFileServer FileMeta (max_values: None, max_size: Some(65), added: 2540, mode: MaxEncodedLen)
+	/// Storage: TrustlessFileServer Registrants (r:0 w:1)
+	/// Proof: TrustlessFileServer Registrants (max_values: None, max_size: Some(114), added: 2589, mode: MaxEncodedLen)
+	/// Storage: TrustlessFileServer RegistrantCount (r:0 w:1)
+	/// Proof: TrustlessFileServer RegistrantCount (max_values: None, max_size: Some(52), added: 2527, mode: MaxEncodedLen)
+	/// Storage: TrustlessFileServer Checksums (r:0 w:1)
+	/// Proof: TrustlessFileServer Checksums (max_values: None, max_size: Some(80), added: 2555, mode: MaxEncodedLen)
+	/// Storage: TrustlessFileServer Paused (r:1 w:0)
+	/// Proof: TrustlessFileServer Paused (max_values: Some(1), max_size: Some(1), added: 496, mode: MaxEncodedLen)
+	/// Storage: TrustlessFileServer UploadedPieces (r:0 w:1)
+	/// Proof: TrustlessFileServer UploadedPieces (max_values: None, max_size: Some(52), added: 2527, mode: MaxEncodedLen)
+	/// Storage: TrustlessFileServer AccountUsage (r:1 w:1)
+	/// Proof: TrustlessFileServer AccountUsage (max_values: None, max_size: Some(76), added: 2551, mode: MaxEncodedLen)
+	/// Storage: TrustlessFileServer TotalBytesStored (r:1 w:1)
+	/// Proof: TrustlessFileServer TotalBytesStored (max_values: Some(1), max_size: Some(8), added: 503, mode: MaxEncodedLen)
+	/// Storage: Timestamp Now (r:1 w:0)
+	/// Proof: Timestamp Now (max_values: Some(1), max_size: Some(8), added: 503, mode: MaxEncodedLen)
+	/// Storage: TrustlessFileServer UploadedAt (r:0 w:1)
+	/// Proof: TrustlessFileServer UploadedAt (max_values: None, max_size: Some(60), added: 2535, mode: MaxEncodedLen)
+	/// The range of component `x` is `[1024, 32768]`.
+	fn upload_file_unsigned(x: u32, ) -> Weight {
+		// Proof Size summary in bytes:
+		//  Measured:  `6`
+		//  Estimated: `1614`
+		// Minimum execution time: 14_000_000 picoseconds.
+		Weight::from_parts(18_930_251, 1614)
+			// Standard Error: 7
+			.saturating_add(Weight::from_parts(7_412, 0).saturating_mul(x.into()))
+			.saturating_add(T::DbWeight::get().reads(6_u64))
+			.saturating_add(T::DbWeight::get().writes(11_u64))
+	}
+	/// Storage: TrustlessFileServer BlockUploads (r:1 w:1)
+	/// Proof: TrustlessFileServer BlockUploads (max_values: Some(1), max_size: Some(129), added: 624, mode: MaxEncodedLen)
+	/// Storage: TrustlessFileServer FileOwners (r:4 w:4)
+	/// Proof: TrustlessFileServer FileOwners (max_values: None, max_size: Some(80), added: 2555, mode: MaxEncodedLen)
+	/// Storage: TrustlessFileServer FileTrees (r:0 w:4)
+	/// Proof: TrustlessFileServer FileTrees (max_values: None, max_size: Some(2116), added: 4591, mode: MaxEncodedLen)
+	/// Storage: TrustlessFileServer FileMeta (r:0 w:4)
+	/// Proof: TrustlessFileServer FileMeta (max_values: None, max_size: Some(65), added: 2540, mode: MaxEncodedLen)
+	/// Storage: System Account (r:1 w:1)
+	/// Proof: System Account (max_values: None, max_size: Some(128), added: 2603, mode: MaxEncodedLen)
+	/// Storage: TrustlessFileServer Registrants (r:0 w:4)
+	/// Proof: TrustlessFileServer Registrants (max_values: None, max_size: Some(114), added: 2589, mode: MaxEncodedLen)
+	/// Storage: TrustlessFileServer RegistrantCount (r:0 w:4)
+	/// Proof: TrustlessFileServer RegistrantCount (max_values: None, max_size: Some(52), added: 2527, mode: MaxEncodedLen)
+	/// Storage: TrustlessFileServer Checksums (r:0 w:4)
+	/// Proof: TrustlessFileServer Checksums (max_values: None, max_size: Some(80), added: 2555, mode: MaxEncodedLen)
+	/// Storage: TrustlessFileServer Paused (r:1 w:0)
+	/// Proof: TrustlessFileServer Paused (max_values: Some(1), max_size: Some(1), added: 496, mode: MaxEncodedLen)
+	/// Storage: TrustlessFileServer UploadedPieces (r:0 w:4)
+	/// Proof: TrustlessFileServer UploadedPieces (max_values: None, max_size: Some(52), added: 2527, mode: MaxEncodedLen)
+	/// Storage: TrustlessFileServer AccountUsage (r:1 w:1)
+	/// Proof: TrustlessFileServer AccountUsage (max_values: None, max_size: Some(76), added: 2551, mode: MaxEncodedLen)
+	/// Storage: TrustlessFileServer TotalBytesStored (r:1 w:1)
+	/// Proof: TrustlessFileServer TotalBytesStored (max_values: Some(1), max_size: Some(8), added: 503, mode: MaxEncodedLen)
+	/// Storage: Balances Holds (r:1 w:1)
+	/// Proof: Balances Holds (max_values: None, max_size: Some(134), added: 2609, mode: MaxEncodedLen)
+	/// Storage: TrustlessFileServer HeldBalance (r:1 w:1)
+	/// Proof: TrustlessFileServer HeldBalance (max_values: Some(1), max_size: Some(16), added: 511, mode: MaxEncodedLen)
+	/// Storage: Timestamp Now (r:1 w:0)
+	/// Proof: Timestamp Now (max_values: Some(1), max_size: Some(8), added: 503, mode: MaxEncodedLen)
+	/// Storage: TrustlessFileServer UploadedAt (r:0 w:4)
+	/// Proof: TrustlessFileServer UploadedAt (max_values: None, max_size: Some(60), added: 2535, mode: MaxEncodedLen)
+	/// Storage: TrustlessFileServer AccountUploads (r:1 w:1)
+	/// Proof: TrustlessFileServer AccountUploads (max_values: None, max_size: Some(52), added: 2527, mode: MaxEncodedLen)
+	/// The range of component `n` is `[1, 16]`.
+	/// The range of component `x` is `[1024, 32768]`.
+	fn upload_files(n: u32, x: u32, ) -> Weight {
+		// Proof Size summary in bytes:
+		//  Measured:  `6`
+		//  Estimated: `1614`
+		// Minimum execution time: 12_000_000 picoseconds.
+		Weight::from_parts(4_512_830, 1614)
+			// Standard Error: 24_000
+			.saturating_add(Weight::from_parts(11_690_000, 0).saturating_mul(n.into()))
 			// Standard Error: 6
 			.saturating_add(Weight::from_parts(3_825, 0).saturating_mul(x.into()))
+			.saturating_add(T::DbWeight::get().reads(7_u64))
+			.saturating_add(T::DbWeight::get().reads((3_u64).saturating_mul(n.into())))
+			.saturating_add(T::DbWeight::get().writes(5_u64))
+			.saturating_add(T::DbWeight::get().writes((10_u64).saturating_mul(n.into())))
+			.saturating_add(Weight::from_parts(0, 4623).saturating_mul(n.into()))
+	}
+	/// Storage: TrustlessFileServer Paused (r:1 w:0)
+	/// Proof: TrustlessFileServer Paused (max_values: Some(1), max_size: Some(1), added: 496, mode: MaxEncodedLen)
+	/// Storage: TrustlessFileServer FileOwners (r:32 w:0)
+	/// Proof: TrustlessFileServer FileOwners (max_values: None, max_size: Some(80), added: 2555, mode: MaxEncodedLen)
+	/// Storage: TrustlessFileServer Bundles (r:1 w:1)
+	/// Proof: TrustlessFileServer Bundles (max_values: None, max_size: Some(1082), added: 3557, mode: MaxEncodedLen)
+	/// Storage: System Account (r:1 w:1)
+	/// Proof: System Account (max_values: None, max_size: Some(128), added: 2603, mode: MaxEncodedLen)
+	/// Storage: Balances Holds (r:1 w:1)
+	/// Proof: Balances Holds (max_values: None, max_size: Some(134), added: 2609, mode: MaxEncodedLen)
+	/// Storage: TrustlessFileServer HeldBalance (r:1 w:1)
+	/// Proof: TrustlessFileServer HeldBalance (max_values: Some(1), max_size: Some(16), added: 511, mode: MaxEncodedLen)
+	/// The range of component `n` is `[2, 32]`.
+	fn create_bundle(n: u32, ) -> Weight {
+		// Proof Size summary in bytes:
+		//  Measured:  `252 + n * (2162 ±0)`
+		//  Estimated: `4547 + n * (4623 ±0)`
+		// Minimum execution time: 31_000_000 picoseconds.
+		Weight::from_parts(24_316_000, 4547)
+			// Standard Error: 9_000
+			.saturating_add(Weight::from_parts(3_914_000, 0).saturating_mul(n.into()))
+			.saturating_add(T::DbWeight::get().reads(5_u64))
+			.saturating_add(T::DbWeight::get().reads((1_u64).saturating_mul(n.into())))
+			.saturating_add(T::DbWeight::get().writes(4_u64))
+			.saturating_add(Weight::from_parts(0, 4623).saturating_mul(n.into()))
+	}
+	/// Storage: TrustlessFileServer Paused (r:1 w:0)
+	/// Proof: TrustlessFileServer Paused (max_values: Some(1), max_size: Some(1), added: 496, mode: MaxEncodedLen)
+	/// Storage: TrustlessFileServer Bundles (r:1 w:1)
+	/// Proof: TrustlessFileServer Bundles (max_values: None, max_size: Some(1082), added: 3557, mode: MaxEncodedLen)
+	/// Storage: System Account (r:1 w:1)
+	/// Proof: System Account (max_values: None, max_size: Some(128), added: 2603, mode: MaxEncodedLen)
+	/// Storage: Balances Holds (r:1 w:1)
+	/// Proof: Balances Holds (max_values: None, max_size: Some(134), added: 2609, mode: MaxEncodedLen)
+	/// Storage: TrustlessFileServer HeldBalance (r:1 w:1)
+	/// Proof: TrustlessFileServer HeldBalance (max_values: Some(1), max_size: Some(16), added: 511, mode: MaxEncodedLen)
+	fn remove_bundle() -> Weight {
+		// Proof Size summary in bytes:
+		//  Measured:  `1270`
+		//  Estimated: `4547`
+		// Minimum execution time: 19_000_000 picoseconds.
+		Weight::from_parts(20_000_000, 4547)
+			.saturating_add(T::DbWeight::get().reads(5_u64))
+			.saturating_add(T::DbWeight::get().writes(4_u64))
+	}
+	/// Storage: TrustlessFileServer Paused (r:1 w:0)
+	/// Proof: TrustlessFileServer Paused (max_values: Some(1), max_size: Some(1), added: 496, mode: MaxEncodedLen)
+	/// Storage: TrustlessFileServer FileOwners (r:1 w:0)
+	/// Proof: TrustlessFileServer FileOwners (max_values: None, max_size: Some(80), added: 2555, mode: MaxEncodedLen)
+	/// Storage: TrustlessFileServer TimeLocks (r:1 w:1)
+	/// Proof: TrustlessFileServer TimeLocks (max_values: None, max_size: Some(154), added: 2629, mode: MaxEncodedLen)
+	fn commit_key() -> Weight {
+		// Proof Size summary in bytes:
+		//  Measured:  `1288`
+		//  Estimated: `5613`
+		// Minimum execution time: 17_000_000 picoseconds.
+		Weight::from_parts(18_000_000, 5613)
+			.saturating_add(T::DbWeight::get().reads(3_u64))
+			.saturating_add(T::DbWeight::get().writes(1_u64))
+	}
+	/// Storage: TrustlessFileServer Paused (r:1 w:0)
+	/// Proof: TrustlessFileServer Paused (max_values: Some(1), max_size: Some(1), added: 496, mode: MaxEncodedLen)
+	/// Storage: TrustlessFileServer TimeLocks (r:1 w:1)
+	/// Proof: TrustlessFileServer TimeLocks (max_values: None, max_size: Some(154), added: 2629, mode: MaxEncodedLen)
+	fn reveal_key() -> Weight {
+		// Proof Size summary in bytes:
+		//  Measured:  `163`
+		//  Estimated: `3619`
+		// Minimum execution time: 14_000_000 picoseconds.
+		Weight::from_parts(15_000_000, 3619)
+			.saturating_add(T::DbWeight::get().reads(2_u64))
+			.saturating_add(T::DbWeight::get().writes(1_u64))
+	}
+	/// Storage: TrustlessFileServer Paused (r:1 w:0)
+	/// Proof: TrustlessFileServer Paused (max_values: Some(1), max_size: Some(1), added: 496, mode: MaxEncodedLen)
+	/// Storage: TrustlessFileServer FileOwners (r:1 w:0)
+	/// Proof: TrustlessFileServer FileOwners (max_values: None, max_size: Some(80), added: 2555, mode: MaxEncodedLen)
+	/// Storage: TrustlessFileServer TimeLocks (r:1 w:1)
+	/// Proof: TrustlessFileServer TimeLocks (max_values: None, max_size: Some(154), added: 2629, mode: MaxEncodedLen)
+	/// Storage: TrustlessFileServer KeyEscrows (r:0 w:1)
+	/// Proof: TrustlessFileServer KeyEscrows (max_values: None, max_size: Some(4021), added: 6496, mode: MaxEncodedLen)
+	/// The range of component `n` is `[1, 16]`.
+	fn escrow_key(n: u32, ) -> Weight {
+		// Proof Size summary in bytes:
+		//  Measured:  `1288`
+		//  Estimated: `5613`
+		// Minimum execution time: 21_000_000 picoseconds.
+		Weight::from_parts(20_614_000, 5613)
+			// Standard Error: 3_000
+			.saturating_add(Weight::from_parts(1_127_000, 0).saturating_mul(n.into()))
+			.saturating_add(T::DbWeight::get().reads(3_u64))
+			.saturating_add(T::DbWeight::get().writes(2_u64))
+	}
+	/// Storage: TrustlessFileServer Paused (r:1 w:0)
+	/// Proof: TrustlessFileServer Paused (max_values: Some(1), max_size: Some(1), added: 496, mode: MaxEncodedLen)
+	/// Storage: TrustlessFileServer KeyEscrows (r:1 w:1)
+	/// Proof: TrustlessFileServer KeyEscrows (max_values: None, max_size: Some(4021), added: 6496, mode: MaxEncodedLen)
+	/// Storage: TrustlessFileServer TimeLocks (r:1 w:1)
+	/// Proof: TrustlessFileServer TimeLocks (max_values: None, max_size: Some(154), added: 2629, mode: MaxEncodedLen)
+	/// The range of component `n` is `[1, 16]`.
+	fn submit_key_share(n: u32, ) -> Weight {
+		// Proof Size summary in bytes:
+		//  Measured:  `263 + n * (227 ±0)`
+		//  Estimated: `7486`
+		// Minimum execution time: 24_000_000 picoseconds.
+		Weight::from_parts(19_870_000, 7486)
+			// Standard Error: 11_000
+			.saturating_add(Weight::from_parts(2_931_000, 0).saturating_mul(n.into()))
+			.saturating_add(T::DbWeight::get().reads(3_u64))
+			.saturating_add(T::DbWeight::get().writes(2_u64))
+	}
+	/// Storage: TrustlessFileServer Paused (r:1 w:0)
+	/// Proof: TrustlessFileServer Paused (max_values: Some(1), max_size: Some(1), added: 496, mode: MaxEncodedLen)
+	/// Storage: TrustlessFileServer FileOwners (r:1 w:0)
+	/// Proof: TrustlessFileServer FileOwners (max_values: None, max_size: Some(80), added: 2555, mode: MaxEncodedLen)
+	/// Storage: TrustlessFileServer RootClaims (r:1 w:1)
+	/// Proof: TrustlessFileServer RootClaims (max_values: None, max_size: Some(68), added: 2543, mode: MaxEncodedLen)
+	/// Storage: System Account (r:1 w:1)
+	/// Proof: System Account (max_values: None, max_size: Some(128), added: 2603, mode: MaxEncodedLen)
+	/// Storage: TrustlessFileServer TotalBytesStored (r:1 w:0)
+	/// Proof: TrustlessFileServer TotalBytesStored (max_values: Some(1), max_size: Some(8), added: 503, mode: MaxEncodedLen)
+	/// Storage: Balances Holds (r:1 w:1)
+	/// Proof: Balances Holds (max_values: None, max_size: Some(134), added: 2609, mode: MaxEncodedLen)
+	/// Storage: TrustlessFileServer HeldBalance (r:1 w:1)
+	/// Proof: TrustlessFileServer HeldBalance (max_values: Some(1), max_size: Some(16), added: 511, mode: MaxEncodedLen)
+	/// Storage: TrustlessFileServer AccountUploads (r:1 w:1)
+	/// Proof: TrustlessFileServer AccountUploads (max_values: None, max_size: Some(52), added: 2527, mode: MaxEncodedLen)
+	/// The range of component `x` is `[1024, 32768]`.
+	fn upload_file_with_root(x: u32, ) -> Weight {
+		// Proof Size summary in bytes:
+		//  Measured:  `6`
+		//  Estimated: `5613`
+		// Minimum execution time: 11_000_000 picoseconds.
+		Weight::from_parts(11_482_000, 5613)
+			// Standard Error: 1
+			.saturating_add(Weight::from_parts(164, 0).saturating_mul(x.into()))
+			.saturating_add(T::DbWeight::get().reads(8_u64))
+			.saturating_add(T::DbWeight::get().writes(5_u64))
+	}
+	/// Storage: TrustlessFileServer RootClaims (r:1 w:1)
+	/// Proof: TrustlessFileServer RootClaims (max_values: None, max_size: Some(68), added: 2543, mode: MaxEncodedLen)
+	/// Storage: TrustlessFileServer FileOwners (r:1 w:1)
+	/// Proof: TrustlessFileServer FileOwners (max_values: None, max_size: Some(80), added: 2555, mode: MaxEncodedLen)
+	/// Storage: TrustlessFileServer FileTrees (r:0 w:1)
+	/// Proof: TrustlessFileServer FileTrees (max_values: None, max_size: Some(2116), added: 4591, mode: MaxEncodedLen)
+	/// Storage: TrustlessFileServer FileMeta (r:0 w:1)
+	/// Proof: TrustlessFileServer FileMeta (max_values: None, max_size: Some(65), added: 2540, mode: MaxEncodedLen)
+	/// Storage: TrustlessFileServer BlockUploads (r:1 w:1)
+	/// Proof: TrustlessFileServer BlockUploads (max_values: Some(1), max_size: Some(129), added: 624, mode: MaxEncodedLen)
+	/// Storage: TrustlessFileServer Registrants (r:0 w:1)
+	/// Proof: TrustlessFileServer Registrants (max_values: None, max_size: Some(114), added: 2589, mode: MaxEncodedLen)
+	/// Storage: TrustlessFileServer RegistrantCount (r:0 w:1)
+	/// Proof: TrustlessFileServer RegistrantCount (max_values: None, max_size: Some(52), added: 2527, mode: MaxEncodedLen)
+	/// Storage: TrustlessFileServer Checksums (r:0 w:1)
+	/// Proof: TrustlessFileServer Checksums (max_values: None, max_size: Some(80), added: 2555, mode: MaxEncodedLen)
+	/// Storage: TrustlessFileServer UploadProgress (r:0 w:1)
+	/// Proof: TrustlessFileServer UploadProgress (max_values: None, max_size: Some(52), added: 2527, mode: MaxEncodedLen)
+	/// Storage: TrustlessFileServer UploadedPieces (r:0 w:1)
+	/// Proof: TrustlessFileServer UploadedPieces (max_values: None, max_size: Some(52), added: 2527, mode: MaxEncodedLen)
+	/// Storage: TrustlessFileServer AccountUsage (r:1 w:1)
+	/// Proof: TrustlessFileServer AccountUsage (max_values: None, max_size: Some(76), added: 2551, mode: MaxEncodedLen)
+	/// Storage: TrustlessFileServer TotalBytesStored (r:1 w:1)
+	/// Proof: TrustlessFileServer TotalBytesStored (max_values: Some(1), max_size: Some(8), added: 503, mode: MaxEncodedLen)
+	/// Storage: Timestamp Now (r:1 w:0)
+	/// Proof: Timestamp Now (max_values: Some(1), max_size: Some(8), added: 503, mode: MaxEncodedLen)
+	/// Storage: TrustlessFileServer UploadedAt (r:0 w:1)
+	/// Proof: TrustlessFileServer UploadedAt (max_values: None, max_size: Some(60), added: 2535, mode: MaxEncodedLen)
+	/// The range of component `x` is `[1, 32]`.
+	fn confirm_root(x: u32, ) -> Weight {
+		// Proof Size summary in bytes:
+		//  Measured:  `148`
+		//  Estimated: `5613`
+		// Minimum execution time: 24_000_000 picoseconds.
+		Weight::from_parts(22_930_000, 5613)
+			// Standard Error: 1_000
+			.saturating_add(Weight::from_parts(1_102_000, 0).saturating_mul(x.into()))
+			.saturating_add(T::DbWeight::get().reads(6_u64))
+			.saturating_add(T::DbWeight::get().writes(13_u64))
+	}
+	/// Storage: TrustlessFileServer RootClaims (r:1 w:1)
+	/// Proof: TrustlessFileServer RootClaims (max_values: None, max_size: Some(68), added: 2543, mode: MaxEncodedLen)
+	/// Storage: System Account (r:1 w:1)
+	/// Proof: System Account (max_values: None, max_size: Some(128), added: 2603, mode: MaxEncodedLen)
+	/// Storage: Balances Holds (r:1 w:1)
+	/// Proof: Balances Holds (max_values: None, max_size: Some(134), added: 2609, mode: MaxEncodedLen)
+	/// Storage: TrustlessFileServer HeldBalance (r:1 w:1)
+	/// Proof: TrustlessFileServer HeldBalance (max_values: Some(1), max_size: Some(16), added: 511, mode: MaxEncodedLen)
+	fn reject_root() -> Weight {
+		// Proof Size summary in bytes:
+		//  Measured:  `251`
+		//  Estimated: `3593`
+		// Minimum execution time: 19_000_000 picoseconds.
+		Weight::from_parts(20_000_000, 3593)
+			.saturating_add(T::DbWeight::get().reads(4_u64))
+			.saturating_add(T::DbWeight::get().writes(4_u64))
+	}
+	/// Storage: TrustlessFileServer Paused (r:1 w:0)
+	/// Proof: TrustlessFileServer Paused (max_values: Some(1), max_size: Some(1), added: 496, mode: MaxEncodedLen)
+	/// Storage: TrustlessFileServer FileOwners (r:1 w:0)
+	/// Proof: TrustlessFileServer FileOwners (max_values: None, max_size: Some(80), added: 2555, mode: MaxEncodedLen)
+	/// Storage: TrustlessFileServer Reports (r:1 w:1)
+	/// Proof: TrustlessFileServer Reports (max_values: None, max_size: Some(369), added: 2844, mode: MaxEncodedLen)
+	/// Storage: System Account (r:1 w:1)
+	/// Proof: System Account (max_values: None, max_size: Some(128), added: 2603, mode: MaxEncodedLen)
+	/// Storage: Balances Holds (r:1 w:1)
+	/// Proof: Balances Holds (max_values: None, max_size: Some(134), added: 2609, mode: MaxEncodedLen)
+	/// Storage: TrustlessFileServer HeldBalance (r:1 w:1)
+	/// Proof: TrustlessFileServer HeldBalance (max_values: Some(1), max_size: Some(16), added: 511, mode: MaxEncodedLen)
+	fn report_file() -> Weight {
+		// Proof Size summary in bytes:
+		//  Measured:  `412`
+		//  Estimated: `5613`
+		// Minimum execution time: 24_000_000 picoseconds.
+		Weight::from_parts(25_000_000, 5613)
+			.saturating_add(T::DbWeight::get().reads(6_u64))
+			.saturating_add(T::DbWeight::get().writes(4_u64))
+	}
+	/// Storage: TrustlessFileServer Paused (r:1 w:0)
+	/// Proof: TrustlessFileServer Paused (max_values: Some(1), max_size: Some(1), added: 496, mode: MaxEncodedLen)
+	/// Storage: TrustlessFileServer Reports (r:1 w:1)
+	/// Proof: TrustlessFileServer Reports (max_values: None, max_size: Some(369), added: 2844, mode: MaxEncodedLen)
+	/// Storage: TrustlessFileServer FileOwners (r:1 w:1)
+	/// Proof: TrustlessFileServer FileOwners (max_values: None, max_size: Some(80), added: 2555, mode: MaxEncodedLen)
+	/// Storage: TrustlessFileServer FileTrees (r:1 w:1)
+	/// Proof: TrustlessFileServer FileTrees (max_values: None, max_size: Some(2116), added: 4591, mode: MaxEncodedLen)
+	/// Storage: TrustlessFileServer FileMeta (r:0 w:1)
+	/// Proof: TrustlessFileServer FileMeta (max_values: None, max_size: Some(65), added: 2540, mode: MaxEncodedLen)
+	/// Storage: TrustlessFileServer Registrants (r:3 w:3)
+	/// Proof: TrustlessFileServer Registrants (max_values: None, max_size: Some(114), added: 2589, mode: MaxEncodedLen)
+	/// Storage: TrustlessFileServer Sponsors (r:3 w:3)
+	/// Proof: TrustlessFileServer Sponsors (max_values: None, max_size: Some(104), added: 2579, mode: MaxEncodedLen)
+	/// Storage: TrustlessFileServer Reputation (r:1 w:1)
+	/// Proof: TrustlessFileServer Reputation (max_values: None, max_size: Some(68), added: 2543, mode: MaxEncodedLen)
+	/// Storage: System Account (r:4 w:4)
+	/// Proof: System Account (max_values: None, max_size: Some(128), added: 2603, mode: MaxEncodedLen)
+	/// Storage: TrustlessFileServer AccountUsage (r:2 w:2)
+	/// Proof: TrustlessFileServer AccountUsage (max_values: None, max_size: Some(76), added: 2551, mode: MaxEncodedLen)
+	/// Storage: TrustlessFileServer TotalBytesStored (r:1 w:1)
+	/// Proof: TrustlessFileServer TotalBytesStored (max_values: Some(1), max_size: Some(8), added: 503, mode: MaxEncodedLen)
+	/// Storage: TrustlessFileServer Tombstones (r:0 w:1)
+	/// Proof: TrustlessFileServer Tombstones (max_values: None, max_size: Some(1125), added: 3600, mode: MaxEncodedLen)
+	/// Storage: TrustlessFileServer OrphanedChunks (r:0 w:1)
+	/// Proof: TrustlessFileServer OrphanedChunks (max_values: None, max_size: Some(48), added: 2523, mode: MaxEncodedLen)
+	/// Storage: Balances Holds (r:4 w:4)
+	/// Proof: Balances Holds (max_values: None, max_size: Some(134), added: 2609, mode: MaxEncodedLen)
+	/// Storage: TrustlessFileServer HeldBalance (r:1 w:1)
+	/// Proof: TrustlessFileServer HeldBalance (max_values: Some(1), max_size: Some(16), added: 511, mode: MaxEncodedLen)
+	/// Storage: TrustlessFileServer UploadedAt (r:0 w:1)
+	/// Proof: TrustlessFileServer UploadedAt (max_values: None, max_size: Some(60), added: 2535, mode: MaxEncodedLen)
+	/// Storage: TrustlessFileServer FrozenFiles (r:0 w:1)
+	/// Proof: TrustlessFileServer FrozenFiles (max_values: None, max_size: Some(48), added: 2523, mode: MaxEncodedLen)
+	/// Storage: TrustlessFileServer ManagementThresholds (r:0 w:1)
+	/// Proof: TrustlessFileServer ManagementThresholds (max_values: None, max_size: Some(50), added: 2525, mode: MaxEncodedLen)
+	/// The range of component `r` is `[1, 3]`.
+	/// The range of component `n` is `[1, 4]`.
+	fn resolve_report(r: u32, n: u32, ) -> Weight {
+		// Proof Size summary in bytes:
+		//  Measured:  `634 + r * (96 ±0) + n * (80 ±0)`
+		//  Estimated: `5613 + r * (2603 ±0) + n * (2603 ±0)`
+		// Minimum execution time: 41_000_000 picoseconds.
+		Weight::from_parts(38_412_000, 5613)
+			// Standard Error: 12_000
+			.saturating_add(Weight::from_parts(9_870_000, 0).saturating_mul(r.into()))
+			// Standard Error: 9_000
+			.saturating_add(Weight::from_parts(7_215_000, 0).saturating_mul(n.into()))
+			.saturating_add(T::DbWeight::get().reads(21_u64))
+			.saturating_add(T::DbWeight::get().reads((3_u64).saturating_mul(r.into())))
+			.saturating_add(T::DbWeight::get().reads((3_u64).saturating_mul(n.into())))
+			.saturating_add(T::DbWeight::get().writes(26_u64))
+			.saturating_add(T::DbWeight::get().writes((3_u64).saturating_mul(r.into())))
+			.saturating_add(T::DbWeight::get().writes((3_u64).saturating_mul(n.into())))
+			.saturating_add(Weight::from_parts(0, 2603).saturating_mul(r.into()))
+			.saturating_add(Weight::from_parts(0, 2603).saturating_mul(n.into()))
+	}
+	/// Storage: TrustlessFileServer Paused (r:1 w:0)
+	/// Proof: TrustlessFileServer Paused (max_values: Some(1), max_size: Some(1), added: 496, mode: MaxEncodedLen)
+	/// Storage: TrustlessFileServer Registrants (r:1 w:0)
+	/// Proof: TrustlessFileServer Registrants (max_values: None, max_size: Some(114), added: 2589, mode: MaxEncodedLen)
+	/// Storage: TrustlessFileServer ScheduledDeletions (r:1 w:1)
+	/// Proof: TrustlessFileServer ScheduledDeletions (max_values: None, max_size: Some(104), added: 2579, mode: MaxEncodedLen)
+	/// Storage: Scheduler Lookup (r:1 w:1)
+	/// Proof: Scheduler Lookup (max_values: None, max_size: Some(48), added: 2523, mode: MaxEncodedLen)
+	/// Storage: Scheduler Agenda (r:1 w:1)
+	/// Proof: Scheduler Agenda (max_values: None, max_size: Some(38963), added: 41438, mode: MaxEncodedLen)
+	fn schedule_deletion() -> Weight {
+		// Proof Size summary in bytes:
+		//  Measured:  `478`
+		//  Estimated: `42428`
+		// Minimum execution time: 31_000_000 picoseconds.
+		Weight::from_parts(32_000_000, 42428)
+			.saturating_add(T::DbWeight::get().reads(5_u64))
+			.saturating_add(T::DbWeight::get().writes(3_u64))
+	}
+	/// Storage: TrustlessFileServer Paused (r:1 w:0)
+	/// Proof: TrustlessFileServer Paused (max_values: Some(1), max_size: Some(1), added: 496, mode: MaxEncodedLen)
+	/// Storage: TrustlessFileServer ScheduledDeletions (r:1 w:1)
+	/// Proof: TrustlessFileServer ScheduledDeletions (max_values: None, max_size: Some(104), added: 2579, mode: MaxEncodedLen)
+	/// Storage: Scheduler Lookup (r:1 w:1)
+	/// Proof: Scheduler Lookup (max_values: None, max_size: Some(48), added: 2523, mode: MaxEncodedLen)
+	/// Storage: Scheduler Agenda (r:1 w:1)
+	/// Proof: Scheduler Agenda (max_values: None, max_size: Some(38963), added: 41438, mode: MaxEncodedLen)
+	fn cancel_deletion() -> Weight {
+		// Proof Size summary in bytes:
+		//  Measured:  `612`
+		//  Estimated: `42428`
+		// Minimum execution time: 27_000_000 picoseconds.
+		Weight::from_parts(28_000_000, 42428)
+			.saturating_add(T::DbWeight::get().reads(4_u64))
+			.saturating_add(T::DbWeight::get().writes(3_u64))
+	}
+	/// Storage: TrustlessFileServer Paused (r:1 w:0)
+	/// Proof: TrustlessFileServer Paused (max_values: Some(1), max_size: Some(1), added: 496, mode: MaxEncodedLen)
+	/// Storage: TrustlessFileServer PendingUploads (r:1 w:1)
+	/// Proof: TrustlessFileServer PendingUploads (max_values: None, max_size: Some(2379), added: 4854, mode: MaxEncodedLen)
+	/// Storage: TrustlessFileServer BlockUploads (r:1 w:1)
+	/// Proof: TrustlessFileServer BlockUploads (max_values: Some(1), max_size: Some(129), added: 624, mode: MaxEncodedLen)
+	/// Storage: TrustlessFileServer Reputation (r:1 w:1)
+	/// Proof: TrustlessFileServer Reputation (max_values: None, max_size: Some(68), added: 2543, mode: MaxEncodedLen)
+	/// Storage: TrustlessFileServer FileOwners (r:0 w:1)
+	/// Proof: TrustlessFileServer FileOwners (max_values: None, max_size: Some(80), added: 2555, mode: MaxEncodedLen)
+	/// Storage: TrustlessFileServer FileTrees (r:0 w:1)
+	/// Proof: TrustlessFileServer FileTrees (max_values: None, max_size: Some(2116), added: 4591, mode: MaxEncodedLen)
+	/// Storage: TrustlessFileServer FileMeta (r:0 w:1)
+	/// Proof: TrustlessFileServer FileMeta (max_values: None, max_size: Some(65), added: 2540, mode: MaxEncodedLen)
+	/// Storage: TrustlessFileServer Registrants (r:0 w:1)
+	/// Proof: TrustlessFileServer Registrants (max_values: None, max_size: Some(114), added: 2589, mode: MaxEncodedLen)
+	/// Storage: TrustlessFileServer RegistrantCount (r:0 w:1)
+	/// Proof: TrustlessFileServer RegistrantCount (max_values: None, max_size: Some(52), added: 2527, mode: MaxEncodedLen)
+	/// Storage: TrustlessFileServer Checksums (r:0 w:1)
+	/// Proof: TrustlessFileServer Checksums (max_values: None, max_size: Some(80), added: 2555, mode: MaxEncodedLen)
+	/// Storage: TrustlessFileServer AccountUsage (r:2 w:2)
+	/// Proof: TrustlessFileServer AccountUsage (max_values: None, max_size: Some(76), added: 2551, mode: MaxEncodedLen)
+	/// Storage: TrustlessFileServer TotalBytesStored (r:1 w:1)
+	/// Proof: TrustlessFileServer TotalBytesStored (max_values: Some(1), max_size: Some(8), added: 503, mode: MaxEncodedLen)
+	/// Storage: Timestamp Now (r:1 w:0)
+	/// Proof: Timestamp Now (max_values: Some(1), max_size: Some(8), added: 503, mode: MaxEncodedLen)
+	/// Storage: TrustlessFileServer UploadedAt (r:0 w:1)
+	/// Proof: TrustlessFileServer UploadedAt (max_values: None, max_size: Some(60), added: 2535, mode: MaxEncodedLen)
+	fn approve_large_upload() -> Weight {
+		// Proof Size summary in bytes:
+		//  Measured:  `1384`
+		//  Estimated: `5844`
+		// Minimum execution time: 34_000_000 picoseconds.
+		Weight::from_parts(36_000_000, 5844)
+			.saturating_add(T::DbWeight::get().reads(8_u64))
+			.saturating_add(T::DbWeight::get().writes(14_u64))
+	}
+	/// Storage: TrustlessFileServer Paused (r:1 w:0)
+	/// Proof: TrustlessFileServer Paused (max_values: Some(1), max_size: Some(1), added: 496, mode: MaxEncodedLen)
+	/// Storage: TrustlessFileServer PendingUploads (r:1 w:1)
+	/// Proof: TrustlessFileServer PendingUploads (max_values: None, max_size: Some(2379), added: 4854, mode: MaxEncodedLen)
+	/// Storage: System Account (r:1 w:1)
+	/// Proof: System Account (max_values: None, max_size: Some(128), added: 2603, mode: MaxEncodedLen)
+	/// Storage: Balances Holds (r:1 w:1)
+	/// Proof: Balances Holds (max_values: None, max_size: Some(134), added: 2609, mode: MaxEncodedLen)
+	/// Storage: TrustlessFileServer HeldBalance (r:1 w:1)
+	/// Proof: TrustlessFileServer HeldBalance (max_values: Some(1), max_size: Some(16), added: 511, mode: MaxEncodedLen)
+	fn reject_large_upload() -> Weight {
+		// Proof Size summary in bytes:
+		//  Measured:  `1384`
+		//  Estimated: `5844`
+		// Minimum execution time: 26_000_000 picoseconds.
+		Weight::from_parts(27_000_000, 5844)
+			.saturating_add(T::DbWeight::get().reads(5_u64))
+			.saturating_add(T::DbWeight::get().writes(4_u64))
+	}
+	/// Storage: TrustlessFileServer Paused (r:1 w:0)
+	/// Proof: TrustlessFileServer Paused (max_values: Some(1), max_size: Some(1), added: 496, mode: MaxEncodedLen)
+	/// Storage: TrustlessFileServer FileOwners (r:1 w:0)
+	/// Proof: TrustlessFileServer FileOwners (max_values: None, max_size: Some(80), added: 2555, mode: MaxEncodedLen)
+	/// Storage: TrustlessFileServer FileMeta (r:1 w:0)
+	/// Proof: TrustlessFileServer FileMeta (max_values: None, max_size: Some(65), added: 2540, mode: MaxEncodedLen)
+	/// Storage: TrustlessFileServer Registrants (r:1 w:0)
+	/// Proof: TrustlessFileServer Registrants (max_values: None, max_size: Some(114), added: 2589, mode: MaxEncodedLen)
+	/// Storage: ParachainSystem HostConfiguration (r:1 w:0)
+	/// Proof Skipped: ParachainSystem HostConfiguration (max_values: Some(1), max_size: None, mode: Measured)
+	/// Storage: XcmpQueue OutboundXcmpStatus (r:1 w:1)
+	/// Proof Skipped: XcmpQueue OutboundXcmpStatus (max_values: Some(1), max_size: None, mode: Measured)
+	/// Storage: XcmpQueue OutboundXcmpMessages (r:0 w:1)
+	/// Proof Skipped: XcmpQueue OutboundXcmpMessages (max_values: None, max_size: None, mode: Measured)
+	fn attest_file() -> Weight {
+		// Proof Size summary in bytes:
+		//  Measured:  `1187`
+		//  Estimated: `5613`
+		// Minimum execution time: 38_000_000 picoseconds.
+		Weight::from_parts(40_000_000, 5613)
+			.saturating_add(T::DbWeight::get().reads(6_u64))
+			.saturating_add(T::DbWeight::get().writes(2_u64))
+	}
+	/// Storage: TrustlessFileServer ExternalFiles (r:0 w:1)
+	/// Proof: TrustlessFileServer ExternalFiles (max_values: None, max_size: Some(100), added: 2575, mode: MaxEncodedLen)
+	fn register_external_file() -> Weight {
+		// Proof Size summary in bytes:
+		//  Measured:  `0`
+		//  Estimated: `0`
+		// Minimum execution time: 14_000_000 picoseconds.
+		Weight::from_parts(15_000_000, 0)
+			.saturating_add(T::DbWeight::get().writes(1_u64))
+	}
+	/// Storage: TrustlessFileServer FileOwners (r:1 w:0)
+	/// Proof: TrustlessFileServer FileOwners (max_values: None, max_size: Some(80), added: 2555, mode: MaxEncodedLen)
+	/// Storage: TrustlessFileServer ServedBandwidth (r:1 w:1)
+	/// Proof: TrustlessFileServer ServedBandwidth (max_values: None, max_size: Some(64), added: 2539, mode: MaxEncodedLen)
+	fn report_bandwidth() -> Weight {
+		// Proof Size summary in bytes:
+		//  Measured:  `2198`
+		//  Estimated: `5613`
+		// Minimum execution time: 15_000_000 picoseconds.
+		Weight::from_parts(16_000_000, 5613)
+			.saturating_add(T::DbWeight::get().reads(2_u64))
+			.saturating_add(T::DbWeight::get().writes(1_u64))
+	}
+	/// Storage: TrustlessFileServer FileMeta (r:1 w:0)
+	/// Proof: TrustlessFileServer FileMeta (max_values: None, max_size: Some(65), added: 2540, mode: MaxEncodedLen)
+	/// Storage: TrustlessFileServer AuditSample (r:1 w:1)
+	/// Proof: TrustlessFileServer AuditSample (max_values: Some(1), max_size: Some(641), added: 1136, mode: MaxEncodedLen)
+	/// Storage: TrustlessFileServer PieceAudits (r:0 w:1)
+	/// Proof: TrustlessFileServer PieceAudits (max_values: None, max_size: Some(61), added: 2536, mode: MaxEncodedLen)
+	fn report_audit() -> Weight {
+		// Proof Size summary in bytes:
+		//  Measured:  `837`
+		//  Estimated: `5666`
+		// Minimum execution time: 15_000_000 picoseconds.
+		Weight::from_parts(16_000_000, 5666)
+			.saturating_add(T::DbWeight::get().reads(2_u64))
+			.saturating_add(T::DbWeight::get().writes(2_u64))
+	}
+	/// Storage: TrustlessFileServer OcwAuthorities (r:0 w:1)
+	/// Proof: TrustlessFileServer OcwAuthorities (max_values: Some(1), max_size: Some(129), added: 624, mode: MaxEncodedLen)
+	/// The range of component `n` is `[1, 4]`.
+	fn set_ocw_authorities(n: u32, ) -> Weight {
+		// Proof Size summary in bytes:
+		//  Measured:  `6`
+		//  Estimated: `0`
+		// Minimum execution time: 10_000_000 picoseconds.
+		Weight::from_parts(10_412_000, 0)
+			// Standard Error: 3_000
+			.saturating_add(Weight::from_parts(96_000, 0).saturating_mul(n.into()))
+			.saturating_add(T::DbWeight::get().writes(1_u64))
+	}
+	/// Storage: TrustlessFileServer Paused (r:1 w:0)
+	/// Proof: TrustlessFileServer Paused (max_values: Some(1), max_size: Some(1), added: 496, mode: MaxEncodedLen)
+	/// Storage: TrustlessFileServer FileOwners (r:1 w:0)
+	/// Proof: TrustlessFileServer FileOwners (max_values: None, max_size: Some(80), added: 2555, mode: MaxEncodedLen)
+	/// Storage: TrustlessFileServer Metadata (r:1 w:1)
+	/// Proof: TrustlessFileServer Metadata (max_values: None, max_size: Some(761), added: 3236, mode: MaxEncodedLen)
+	/// Storage: TrustlessFileServer FileHistory (r:1 w:1)
+	/// Proof: TrustlessFileServer FileHistory (max_values: None, max_size: Some(3266), added: 5741, mode: MaxEncodedLen)
+	fn set_retrieval_price() -> Weight {
+		// Proof Size summary in bytes:
+		//  Measured:  `2214`
+		//  Estimated: `6731`
+		// Minimum execution time: 18_000_000 picoseconds.
+		Weight::from_parts(19_000_000, 6731)
+			.saturating_add(T::DbWeight::get().reads(4_u64))
+			.saturating_add(T::DbWeight::get().writes(2_u64))
+	}
+	/// Storage: TrustlessFileServer Paused (r:1 w:0)
+	/// Proof: TrustlessFileServer Paused (max_values: Some(1), max_size: Some(1), added: 496, mode: MaxEncodedLen)
+	/// Storage: System Account (r:1 w:1)
+	/// Proof: System Account (max_values: None, max_size: Some(128), added: 2603, mode: MaxEncodedLen)
+	/// Storage: TrustlessFileServer NextCollectionId (r:1 w:1)
+	/// Proof: TrustlessFileServer NextCollectionId (max_values: Some(1), max_size: Some(8), added: 503, mode: MaxEncodedLen)
+	/// Storage: TrustlessFileServer Collections (r:0 w:1)
+	/// Proof: TrustlessFileServer Collections (max_values: None, max_size: Some(2106), added: 4581, mode: MaxEncodedLen)
+	/// Storage: Balances Holds (r:1 w:1)
+	/// Proof: Balances Holds (max_values: None, max_size: Some(134), added: 2609, mode: MaxEncodedLen)
+	/// Storage: TrustlessFileServer HeldBalance (r:1 w:1)
+	/// Proof: TrustlessFileServer HeldBalance (max_values: Some(1), max_size: Some(16), added: 511, mode: MaxEncodedLen)
+	fn create_collection() -> Weight {
+		// Proof Size summary in bytes:
+		//  Measured:  `172`
+		//  Estimated: `3593`
+		// Minimum execution time: 22_000_000 picoseconds.
+		Weight::from_parts(23_000_000, 3593)
+			.saturating_add(T::DbWeight::get().reads(5_u64))
+			.saturating_add(T::DbWeight::get().writes(5_u64))
+	}
+	/// Storage: TrustlessFileServer Paused (r:1 w:0)
+	/// Proof: TrustlessFileServer Paused (max_values: Some(1), max_size: Some(1), added: 496, mode: MaxEncodedLen)
+	/// Storage: TrustlessFileServer Collections (r:1 w:1)
+	/// Proof: TrustlessFileServer Collections (max_values: None, max_size: Some(2106), added: 4581, mode: MaxEncodedLen)
+	/// Storage: TrustlessFileServer FileOwners (r:1 w:0)
+	/// Proof: TrustlessFileServer FileOwners (max_values: None, max_size: Some(80), added: 2555, mode: MaxEncodedLen)
+	fn add_to_collection() -> Weight {
+		// Proof Size summary in bytes:
+		//  Measured:  `2371`
+		//  Estimated: `5571`
+		// Minimum execution time: 19_000_000 picoseconds.
+		Weight::from_parts(20_000_000, 5571)
+			.saturating_add(T::DbWeight::get().reads(3_u64))
+			.saturating_add(T::DbWeight::get().writes(1_u64))
+	}
+	/// Storage: TrustlessFileServer Paused (r:1 w:0)
+	/// Proof: TrustlessFileServer Paused (max_values: Some(1), max_size: Some(1), added: 496, mode: MaxEncodedLen)
+	/// Storage: TrustlessFileServer Collections (r:1 w:1)
+	/// Proof: TrustlessFileServer Collections (max_values: None, max_size: Some(2106), added: 4581, mode: MaxEncodedLen)
+	fn remove_from_collection() -> Weight {
+		// Proof Size summary in bytes:
+		//  Measured:  `2218`
+		//  Estimated: `5571`
+		// Minimum execution time: 14_000_000 picoseconds.
+		Weight::from_parts(15_000_000, 5571)
+			.saturating_add(T::DbWeight::get().reads(2_u64))
+			.saturating_add(T::DbWeight::get().writes(1_u64))
+	}
+	/// Storage: TrustlessFileServer Paused (r:1 w:0)
+	/// Proof: TrustlessFileServer Paused (max_values: Some(1), max_size: Some(1), added: 496, mode: MaxEncodedLen)
+	/// Storage: TrustlessFileServer Collections (r:1 w:0)
+	/// Proof: TrustlessFileServer Collections (max_values: None, max_size: Some(2106), added: 4581, mode: MaxEncodedLen)
+	/// Storage: TrustlessFileServer FileOwners (r:64 w:0)
+	/// Proof: TrustlessFileServer FileOwners (max_values: None, max_size: Some(80), added: 2555, mode: MaxEncodedLen)
+	/// Storage: TrustlessFileServer Metadata (r:64 w:64)
+	/// Proof: TrustlessFileServer Metadata (max_values: None, max_size: Some(761), added: 3236, mode: MaxEncodedLen)
+	/// Storage: TrustlessFileServer FileHistory (r:64 w:64)
+	/// Proof: TrustlessFileServer FileHistory (max_values: None, max_size: Some(3266), added: 5741, mode: MaxEncodedLen)
+	/// The range of component `n` is `[1, 64]`.
+	fn set_collection_price(n: u32, ) -> Weight {
+		// Proof Size summary in bytes:
+		//  Measured:  `2371 + n * (2214 ±0)`
+		//  Estimated: `5571 + n * (5741 ±0)`
+		// Minimum execution time: 29_000_000 picoseconds.
+		Weight::from_parts(12_104_000, 5571)
+			// Standard Error: 11_000
+			.saturating_add(Weight::from_parts(16_870_000, 0).saturating_mul(n.into()))
+			.saturating_add(T::DbWeight::get().reads(2_u64))
+			.saturating_add(T::DbWeight::get().reads((3_u64).saturating_mul(n.into())))
+			.saturating_add(T::DbWeight::get().writes((2_u64).saturating_mul(n.into())))
+			.saturating_add(Weight::from_parts(0, 5741).saturating_mul(n.into()))
+	}
+	/// Storage: TrustlessFileServer Paused (r:1 w:0)
+	/// Proof: TrustlessFileServer Paused (max_values: Some(1), max_size: Some(1), added: 496, mode: MaxEncodedLen)
+	/// Storage: TrustlessFileServer Collections (r:1 w:1)
+	/// Proof: TrustlessFileServer Collections (max_values: None, max_size: Some(2106), added: 4581, mode: MaxEncodedLen)
+	/// Storage: TrustlessFileServer FileOwners (r:64 w:64)
+	/// Proof: TrustlessFileServer FileOwners (max_values: None, max_size: Some(80), added: 2555, mode: MaxEncodedLen)
+	/// Storage: TrustlessFileServer Registrants (r:64 w:0)
+	/// Proof: TrustlessFileServer Registrants (max_values: None, max_size: Some(114), added: 2589, mode: MaxEncodedLen)
+	/// Storage: System Account (r:2 w:2)
+	/// Proof: System Account (max_values: None, max_size: Some(128), added: 2603, mode: MaxEncodedLen)
+	/// Storage: TrustlessFileServer FileHistory (r:64 w:64)
+	/// Proof: TrustlessFileServer FileHistory (max_values: None, max_size: Some(3266), added: 5741, mode: MaxEncodedLen)
+	/// Storage: Balances Holds (r:2 w:2)
+	/// Proof: Balances Holds (max_values: None, max_size: Some(134), added: 2609, mode: MaxEncodedLen)
+	/// Storage: TrustlessFileServer ManagementThresholds (r:0 w:64)
+	/// Proof: TrustlessFileServer ManagementThresholds (max_values: None, max_size: Some(50), added: 2525, mode: MaxEncodedLen)
+	/// The range of component `n` is `[1, 64]`.
+	fn transfer_collection(n: u32, ) -> Weight {
+		// Proof Size summary in bytes:
+		//  Measured:  `2580 + n * (2310 ±0)`
+		//  Estimated: `6196 + n * (5741 ±0)`
+		// Minimum execution time: 47_000_000 picoseconds.
+		Weight::from_parts(30_511_000, 6196)
+			// Standard Error: 14_000
+			.saturating_add(Weight::from_parts(19_432_000, 0).saturating_mul(n.into()))
+			.saturating_add(T::DbWeight::get().reads(6_u64))
+			.saturating_add(T::DbWeight::get().reads((3_u64).saturating_mul(n.into())))
+			.saturating_add(T::DbWeight::get().writes(5_u64))
+			.saturating_add(T::DbWeight::get().writes((3_u64).saturating_mul(n.into())))
+			.saturating_add(Weight::from_parts(0, 5741).saturating_mul(n.into()))
+	}
+	/// Storage: TrustlessFileServer Paused (r:1 w:0)
+	/// Proof: TrustlessFileServer Paused (max_values: Some(1), max_size: Some(1), added: 496, mode: MaxEncodedLen)
+	/// Storage: TrustlessFileServer Collections (r:1 w:1)
+	/// Proof: TrustlessFileServer Collections (max_values: None, max_size: Some(2106), added: 4581, mode: MaxEncodedLen)
+	/// Storage: System Account (r:1 w:1)
+	/// Proof: System Account (max_values: None, max_size: Some(128), added: 2603, mode: MaxEncodedLen)
+	/// Storage: Balances Holds (r:1 w:1)
+	/// Proof: Balances Holds (max_values: None, max_size: Some(134), added: 2609, mode: MaxEncodedLen)
+	/// Storage: TrustlessFileServer HeldBalance (r:1 w:1)
+	/// Proof: TrustlessFileServer HeldBalance (max_values: Some(1), max_size: Some(16), added: 511, mode: MaxEncodedLen)
+	fn remove_collection() -> Weight {
+		// Proof Size summary in bytes:
+		//  Measured:  `2372`
+		//  Estimated: `5571`
+		// Minimum execution time: 21_000_000 picoseconds.
+		Weight::from_parts(22_000_000, 5571)
+			.saturating_add(T::DbWeight::get().reads(5_u64))
+			.saturating_add(T::DbWeight::get().writes(4_u64))
+	}
+	/// Storage: TrustlessFileServer OrphanedChunks (r:2 w:1)
+	/// Proof: TrustlessFileServer OrphanedChunks (max_values: None, max_size: Some(48), added: 2523, mode: MaxEncodedLen)
+	/// Storage: TrustlessFileServer FileOwners (r:1 w:0)
+	/// Proof: TrustlessFileServer FileOwners (max_values: None, max_size: Some(80), added: 2555, mode: MaxEncodedLen)
+	/// Storage: TrustlessFileServer ChunkAllocations (r:1000 w:1000)
+	/// Proof: TrustlessFileServer ChunkAllocations (max_values: None, max_size: Some(249), added: 2724, mode: MaxEncodedLen)
+	/// The range of component `n` is `[0, 1000]`.
+	fn reclaim_chunks(n: u32, ) -> Weight {
+		// Proof Size summary in bytes:
+		//  Measured:  `212 + n * (70 ±0)`
+		//  Estimated: `5036 + n * (2724 ±0)`
+		// Minimum execution time: 11_000_000 picoseconds.
+		Weight::from_parts(11_482_000, 5036)
+			// Standard Error: 2_000
+			.saturating_add(Weight::from_parts(4_215_000, 0).saturating_mul(n.into()))
+			.saturating_add(T::DbWeight::get().reads(3_u64))
+			.saturating_add(T::DbWeight::get().reads((1_u64).saturating_mul(n.into())))
+			.saturating_add(T::DbWeight::get().writes(1_u64))
+			.saturating_add(T::DbWeight::get().writes((1_u64).saturating_mul(n.into())))
+			.saturating_add(Weight::from_parts(0, 2724).saturating_mul(n.into()))
+	}
+	/// Storage: TrustlessFileServer Paused (r:1 w:0)
+	/// Proof: TrustlessFileServer Paused (max_values: Some(1), max_size: Some(1), added: 496, mode: MaxEncodedLen)
+	/// Storage: TrustlessFileServer PendingReview (r:1 w:1)
+	/// Proof: TrustlessFileServer PendingReview (max_values: None, max_size: Some(2379), added: 4854, mode: MaxEncodedLen)
+	/// Storage: TrustlessFileServer BlockUploads (r:1 w:1)
+	/// Proof: TrustlessFileServer BlockUploads (max_values: Some(1), max_size: Some(129), added: 624, mode: MaxEncodedLen)
+	/// Storage: TrustlessFileServer Reputation (r:1 w:1)
+	/// Proof: TrustlessFileServer Reputation (max_values: None, max_size: Some(68), added: 2543, mode: MaxEncodedLen)
+	/// Storage: TrustlessFileServer FileOwners (r:0 w:1)
+	/// Proof: TrustlessFileServer FileOwners (max_values: None, max_size: Some(80), added: 2555, mode: MaxEncodedLen)
+	/// Storage: TrustlessFileServer FileTrees (r:0 w:1)
+	/// Proof: TrustlessFileServer FileTrees (max_values: None, max_size: Some(2116), added: 4591, mode: MaxEncodedLen)
+	/// Storage: TrustlessFileServer FileMeta (r:0 w:1)
+	/// Proof: TrustlessFileServer FileMeta (max_values: None, max_size: Some(65), added: 2540, mode: MaxEncodedLen)
+	/// Storage: TrustlessFileServer Registrants (r:0 w:1)
+	/// Proof: TrustlessFileServer Registrants (max_values: None, max_size: Some(114), added: 2589, mode: MaxEncodedLen)
+	/// Storage: TrustlessFileServer RegistrantCount (r:0 w:1)
+	/// Proof: TrustlessFileServer RegistrantCount (max_values: None, max_size: Some(52), added: 2527, mode: MaxEncodedLen)
+	/// Storage: TrustlessFileServer Checksums (r:0 w:1)
+	/// Proof: TrustlessFileServer Checksums (max_values: None, max_size: Some(80), added: 2555, mode: MaxEncodedLen)
+	/// Storage: TrustlessFileServer AccountUsage (r:2 w:2)
+	/// Proof: TrustlessFileServer AccountUsage (max_values: None, max_size: Some(76), added: 2551, mode: MaxEncodedLen)
+	/// Storage: TrustlessFileServer TotalBytesStored (r:1 w:1)
+	/// Proof: TrustlessFileServer TotalBytesStored (max_values: Some(1), max_size: Some(8), added: 503, mode: MaxEncodedLen)
+	/// Storage: Timestamp Now (r:1 w:0)
+	/// Proof: Timestamp Now (max_values: Some(1), max_size: Some(8), added: 503, mode: MaxEncodedLen)
+	/// Storage: TrustlessFileServer UploadedAt (r:0 w:1)
+	/// Proof: TrustlessFileServer UploadedAt (max_values: None, max_size: Some(60), added: 2535, mode: MaxEncodedLen)
+	fn approve_review() -> Weight {
+		// Proof Size summary in bytes:
+		//  Measured:  `1384`
+		//  Estimated: `5844`
+		// Minimum execution time: 34_000_000 picoseconds.
+		Weight::from_parts(36_000_000, 5844)
+			.saturating_add(T::DbWeight::get().reads(8_u64))
+			.saturating_add(T::DbWeight::get().writes(14_u64))
+	}
+	/// Storage: TrustlessFileServer Paused (r:1 w:0)
+	/// Proof: TrustlessFileServer Paused (max_values: Some(1), max_size: Some(1), added: 496, mode: MaxEncodedLen)
+	/// Storage: TrustlessFileServer PendingReview (r:1 w:1)
+	/// Proof: TrustlessFileServer PendingReview (max_values: None, max_size: Some(2379), added: 4854, mode: MaxEncodedLen)
+	/// Storage: System Account (r:1 w:1)
+	/// Proof: System Account (max_values: None, max_size: Some(128), added: 2603, mode: MaxEncodedLen)
+	/// Storage: Balances Holds (r:1 w:1)
+	/// Proof: Balances Holds (max_values: None, max_size: Some(134), added: 2609, mode: MaxEncodedLen)
+	/// Storage: TrustlessFileServer HeldBalance (r:1 w:1)
+	/// Proof: TrustlessFileServer HeldBalance (max_values: Some(1), max_size: Some(16), added: 511, mode: MaxEncodedLen)
+	fn reject_review() -> Weight {
+		// Proof Size summary in bytes:
+		//  Measured:  `1384`
+		//  Estimated: `5844`
+		// Minimum execution time: 26_000_000 picoseconds.
+		Weight::from_parts(27_000_000, 5844)
+			.saturating_add(T::DbWeight::get().reads(5_u64))
+			.saturating_add(T::DbWeight::get().writes(4_u64))
+	}
+	/// Storage: TrustlessFileServer Paused (r:1 w:0)
+	/// Proof: TrustlessFileServer Paused (max_values: Some(1), max_size: Some(1), added: 496, mode: MaxEncodedLen)
+	/// Storage: TrustlessFileServer FileOwners (r:1 w:0)
+	/// Proof: TrustlessFileServer FileOwners (max_values: None, max_size: Some(80), added: 2555, mode: MaxEncodedLen)
+	/// Storage: TrustlessFileServer FrozenFiles (r:1 w:1)
+	/// Proof: TrustlessFileServer FrozenFiles (max_values: None, max_size: Some(48), added: 2523, mode: MaxEncodedLen)
+	fn freeze_file() -> Weight {
+		// Proof Size summary in bytes:
+		//  Measured:  `316`
+		//  Estimated: `3545`
+		// Minimum execution time: 13_000_000 picoseconds.
+		Weight::from_parts(14_000_000, 3545)
+			.saturating_add(T::DbWeight::get().reads(3_u64))
+			.saturating_add(T::DbWeight::get().writes(1_u64))
+	}
+	/// Storage: TrustlessFileServer Paused (r:1 w:0)
+	/// Proof: TrustlessFileServer Paused (max_values: Some(1), max_size: Some(1), added: 496, mode: MaxEncodedLen)
+	/// Storage: TrustlessFileServer FileOwners (r:1 w:0)
+	/// Proof: TrustlessFileServer FileOwners (max_values: None, max_size: Some(80), added: 2555, mode: MaxEncodedLen)
+	/// Storage: TrustlessFileServer FrozenFiles (r:1 w:1)
+	/// Proof: TrustlessFileServer FrozenFiles (max_values: None, max_size: Some(48), added: 2523, mode: MaxEncodedLen)
+	fn unfreeze_file() -> Weight {
+		// Proof Size summary in bytes:
+		//  Measured:  `316`
+		//  Estimated: `3545`
+		// Minimum execution time: 13_000_000 picoseconds.
+		Weight::from_parts(14_000_000, 3545)
+			.saturating_add(T::DbWeight::get().reads(3_u64))
+			.saturating_add(T::DbWeight::get().writes(1_u64))
+	}
+	/// Storage: TrustlessFileServer Paused (r:1 w:0)
+	/// Proof: TrustlessFileServer Paused (max_values: Some(1), max_size: Some(1), added: 496, mode: MaxEncodedLen)
+	/// Storage: TrustlessFileServer FileOwners (r:1 w:1)
+	/// Proof: TrustlessFileServer FileOwners (max_values: None, max_size: Some(80), added: 2555, mode: MaxEncodedLen)
+	/// Storage: TrustlessFileServer Registrants (r:2 w:2)
+	/// Proof: TrustlessFileServer Registrants (max_values: None, max_size: Some(114), added: 2589, mode: MaxEncodedLen)
+	/// Storage: TrustlessFileServer FileMeta (r:1 w:0)
+	/// Proof: TrustlessFileServer FileMeta (max_values: None, max_size: Some(65), added: 2540, mode: MaxEncodedLen)
+	/// Storage: TrustlessFileServer Sponsors (r:1 w:2)
+	/// Proof: TrustlessFileServer Sponsors (max_values: None, max_size: Some(128), added: 2603, mode: MaxEncodedLen)
+	/// Storage: TrustlessFileServer ScheduledDeletions (r:1 w:0)
+	/// Proof: TrustlessFileServer ScheduledDeletions (max_values: None, max_size: Some(104), added: 2579, mode: MaxEncodedLen)
+	/// Storage: TrustlessFileServer AccountUsage (r:2 w:2)
+	/// Proof: TrustlessFileServer AccountUsage (max_values: None, max_size: Some(76), added: 2551, mode: MaxEncodedLen)
+	/// Storage: TrustlessFileServer FileHistory (r:1 w:1)
+	/// Proof: TrustlessFileServer FileHistory (max_values: None, max_size: Some(3266), added: 5741, mode: MaxEncodedLen)
+	/// Storage: TrustlessFileServer ManagementThresholds (r:0 w:1)
+	/// Proof: TrustlessFileServer ManagementThresholds (max_values: None, max_size: Some(50), added: 2525, mode: MaxEncodedLen)
+	fn transfer_file() -> Weight {
+		// Proof Size summary in bytes:
+		//  Measured:  `2532`
+		//  Estimated: `7139`
+		// Minimum execution time: 41_000_000 picoseconds.
+		Weight::from_parts(43_000_000, 7139)
+			.saturating_add(T::DbWeight::get().reads(11_u64))
+			.saturating_add(T::DbWeight::get().writes(9_u64))
+	}
+	/// Storage: TrustlessFileServer Paused (r:1 w:0)
+	/// Proof: TrustlessFileServer Paused (max_values: Some(1), max_size: Some(1), added: 496, mode: MaxEncodedLen)
+	/// Storage: TrustlessFileServer FileOwners (r:1 w:0)
+	/// Proof: TrustlessFileServer FileOwners (max_values: None, max_size: Some(80), added: 2555, mode: MaxEncodedLen)
+	/// Storage: TrustlessFileServer ManagementThresholds (r:0 w:1)
+	/// Proof: TrustlessFileServer ManagementThresholds (max_values: None, max_size: Some(50), added: 2525, mode: MaxEncodedLen)
+	fn set_management_threshold() -> Weight {
+		// Proof Size summary in bytes:
+		//  Measured:  `316`
+		//  Estimated: `3545`
+		// Minimum execution time: 12_000_000 picoseconds.
+		Weight::from_parts(13_000_000, 3545)
+			.saturating_add(T::DbWeight::get().reads(2_u64))
+			.saturating_add(T::DbWeight::get().writes(1_u64))
+	}
+	/// Storage: TrustlessFileServer Paused (r:1 w:0)
+	/// Proof: TrustlessFileServer Paused (max_values: Some(1), max_size: Some(1), added: 496, mode: MaxEncodedLen)
+	/// Storage: TrustlessFileServer Names (r:1 w:1)
+	/// Proof: TrustlessFileServer Names (max_values: None, max_size: Some(358), added: 2833, mode: MaxEncodedLen)
+	/// Storage: System Account (r:1 w:1)
+	/// Proof: System Account (max_values: None, max_size: Some(128), added: 2603, mode: MaxEncodedLen)
+	/// Storage: Balances Holds (r:1 w:1)
+	/// Proof: Balances Holds (max_values: None, max_size: Some(134), added: 2609, mode: MaxEncodedLen)
+	/// Storage: TrustlessFileServer HeldBalance (r:1 w:1)
+	/// Proof: TrustlessFileServer HeldBalance (max_values: Some(1), max_size: Some(16), added: 511, mode: MaxEncodedLen)
+	fn renew_alias() -> Weight {
+		// Proof Size summary in bytes:
+		//  Measured:  `252`
+		//  Estimated: `3823`
+		// Minimum execution time: 18_000_000 picoseconds.
+		Weight::from_parts(19_000_000, 3823)
+			.saturating_add(T::DbWeight::get().reads(5_u64))
+			.saturating_add(T::DbWeight::get().writes(4_u64))
+	}
+	/// Storage: TrustlessFileServer Paused (r:1 w:0)
+	/// Proof: TrustlessFileServer Paused (max_values: Some(1), max_size: Some(1), added: 496, mode: MaxEncodedLen)
+	/// Storage: TrustlessFileServer Names (r:1 w:1)
+	/// Proof: TrustlessFileServer Names (max_values: None, max_size: Some(358), added: 2833, mode: MaxEncodedLen)
+	/// Storage: System Account (r:1 w:1)
+	/// Proof: System Account (max_values: None, max_size: Some(128), added: 2603, mode: MaxEncodedLen)
+	/// Storage: Balances Holds (r:1 w:1)
+	/// Proof: Balances Holds (max_values: None, max_size: Some(134), added: 2609, mode: MaxEncodedLen)
+	/// Storage: Balances TotalIssuance (r:1 w:1)
+	/// Proof: Balances TotalIssuance (max_values: Some(1), max_size: Some(16), added: 511, mode: MaxEncodedLen)
+	/// Storage: TrustlessFileServer HeldBalance (r:1 w:1)
+	/// Proof: TrustlessFileServer HeldBalance (max_values: Some(1), max_size: Some(16), added: 511, mode: MaxEncodedLen)
+	fn reassign_alias() -> Weight {
+		// Proof Size summary in bytes:
+		//  Measured:  `252`
+		//  Estimated: `3823`
+		// Minimum execution time: 20_000_000 picoseconds.
+		Weight::from_parts(21_000_000, 3823)
+			.saturating_add(T::DbWeight::get().reads(6_u64))
+			.saturating_add(T::DbWeight::get().writes(5_u64))
+	}
+	/// Storage: TrustlessFileServer Paused (r:1 w:0)
+	/// Proof: TrustlessFileServer Paused (max_values: Some(1), max_size: Some(1), added: 496, mode: MaxEncodedLen)
+	/// Storage: TrustlessFileServer RootClaims (r:1 w:1)
+	/// Proof: TrustlessFileServer RootClaims (max_values: None, max_size: Some(68), added: 2543, mode: MaxEncodedLen)
+	/// Storage: System Account (r:1 w:1)
+	/// Proof: System Account (max_values: None, max_size: Some(128), added: 2603, mode: MaxEncodedLen)
+	/// Storage: Balances Holds (r:1 w:1)
+	/// Proof: Balances Holds (max_values: None, max_size: Some(134), added: 2609, mode: MaxEncodedLen)
+	/// Storage: TrustlessFileServer HeldBalance (r:1 w:1)
+	/// Proof: TrustlessFileServer HeldBalance (max_values: Some(1), max_size: Some(16), added: 511, mode: MaxEncodedLen)
+	fn cancel_root_claim() -> Weight {
+		// Proof Size summary in bytes:
+		//  Measured:  `301`
+		//  Estimated: `3599`
+		// Minimum execution time: 19_000_000 picoseconds.
+		Weight::from_parts(20_000_000, 3599)
+			.saturating_add(T::DbWeight::get().reads(5_u64))
+			.saturating_add(T::DbWeight::get().writes(4_u64))
+	}
+	/// Storage: TrustlessFileServer OcwAuthorities (r:1 w:0)
+	/// Proof: TrustlessFileServer OcwAuthorities (max_values: Some(1), max_size: Some(129), added: 624, mode: MaxEncodedLen)
+	/// Storage: TrustlessFileServer PendingRechunks (r:1 w:1)
+	/// Proof: TrustlessFileServer PendingRechunks (max_values: None, max_size: Some(48), added: 2523, mode: MaxEncodedLen)
+	fn reject_rechunk() -> Weight {
+		// Proof Size summary in bytes:
+		//  Measured:  `214`
+		//  Estimated: `3513`
+		// Minimum execution time: 31_000_000 picoseconds.
+		Weight::from_parts(32_000_000, 3513)
+			.saturating_add(T::DbWeight::get().reads(2_u64))
 			.saturating_add(T::DbWeight::get().writes(1_u64))
 	}
 }
 
 // For backwards compatibility and tests
 impl WeightInfo for () {
-	/// Storage: TrustlessFileServer Files (r:0 w:1)
-	/// Proof: TrustlessFileServer Files (max_values: None, max_size: Some(150), added: 2625, mode: MaxEncodedLen)
+	/// Storage: TrustlessFileServer BlockUploads (r:1 w:1)
+	/// Proof: TrustlessFileServer BlockUploads (max_values: Some(1), max_size: Some(129), added: 624, mode: MaxEncodedLen)
+	/// Storage: TrustlessFileServer FileOwners (r:1 w:1)
+	/// Proof: TrustlessFileServer FileOwners (max_values: None, max_size: Some(80), added: 2555, mode: MaxEncodedLen)
+	/// Storage: TrustlessFileServer FileTrees (r:0 w:1)
+	/// Proof: TrustlessFileServer FileTrees (max_values: None, max_size: Some(2116), added: 4591, mode: MaxEncodedLen)
+	/// Storage: TrustlessFileServer FileMeta (r:0 w:1)
+	/// Proof: TrustlessFileServer FileMeta (max_values: None, max_size: Some(65), added: 2540, mode: MaxEncodedLen)
+	/// Storage: System Account (r:1 w:1)
+	/// Proof: System Account (max_values: None, max_size: Some(128), added: 2603, mode: MaxEncodedLen)
+	/// Storage: TrustlessFileServer Registrants (r:0 w:1)
+	/// Proof: TrustlessFileServer Registrants (max_values: None, max_size: Some(114), added: 2589, mode: MaxEncodedLen)
+	/// Storage: TrustlessFileServer RegistrantCount (r:0 w:1)
+	/// Proof: TrustlessFileServer RegistrantCount (max_values: None, max_size: Some(52), added: 2527, mode: MaxEncodedLen)
+	/// Storage: TrustlessFileServer Checksums (r:0 w:1)
+	/// Proof: TrustlessFileServer Checksums (max_values: None, max_size: Some(80), added: 2555, mode: MaxEncodedLen)
+	/// Storage: TrustlessFileServer Paused (r:1 w:0)
+	/// Proof: TrustlessFileServer Paused (max_values: Some(1), max_size: Some(1), added: 496, mode: MaxEncodedLen)
+	/// Storage: TrustlessFileServer Metadata (r:0 w:1)
+	/// Proof: TrustlessFileServer Metadata (max_values: None, max_size: Some(761), added: 3236, mode: MaxEncodedLen)
+	/// Storage: TrustlessFileServer UploadedPieces (r:0 w:1)
+	/// Proof: TrustlessFileServer UploadedPieces (max_values: None, max_size: Some(52), added: 2527, mode: MaxEncodedLen)
+	/// Storage: TrustlessFileServer AccountUsage (r:1 w:1)
+	/// Proof: TrustlessFileServer AccountUsage (max_values: None, max_size: Some(76), added: 2551, mode: MaxEncodedLen)
+	/// Storage: TrustlessFileServer TotalBytesStored (r:1 w:1)
+	/// Proof: TrustlessFileServer TotalBytesStored (max_values: Some(1), max_size: Some(8), added: 503, mode: MaxEncodedLen)
+	/// Storage: Balances Holds (r:1 w:1)
+	/// Proof: Balances Holds (max_values: None, max_size: Some(134), added: 2609, mode: MaxEncodedLen)
+	/// Storage: TrustlessFileServer HeldBalance (r:1 w:1)
+	/// Proof: TrustlessFileServer HeldBalance (max_values: Some(1), max_size: Some(16), added: 511, mode: MaxEncodedLen)
+	/// Storage: Timestamp Now (r:1 w:0)
+	/// Proof: Timestamp Now (max_values: Some(1), max_size: Some(8), added: 503, mode: MaxEncodedLen)
+	/// Storage: TrustlessFileServer UploadedAt (r:0 w:1)
+	/// Proof: TrustlessFileServer UploadedAt (max_values: None, max_size: Some(60), added: 2535, mode: MaxEncodedLen)
+	/// Storage: TrustlessFileServer AccountUploads (r:1 w:1)
+	/// Proof: TrustlessFileServer AccountUploads (max_values: None, max_size: Some(52), added: 2527, mode: MaxEncodedLen)
 	/// The range of component `x` is `[1024, 32768]`.
 	fn upload_file(x: u32, ) -> Weight {
 		// Proof Size summary in bytes:
-		//  Measured:  `0`
+		//  Measured:  `6`
+		//  Estimated: `1614`
+		// Minimum execution time: 12_000_000 picoseconds.
+		Weight::from_parts(16_204_117, 1614)
+			// Standard Error: 6
+			.saturating_add(Weight::from_parts(3_825, 0).saturating_mul(x.into()))
+			.saturating_add(RocksDbWeight::get().reads(10_u64))
+			.saturating_add(RocksDbWeight::get().writes(16_u64))
+	}
+	/// Storage: TrustlessFileServer FileOwners (r:1 w:0)
+	/// Proof: TrustlessFileServer FileOwners (max_values: None, max_size: Some(80), added: 2555, mode: MaxEncodedLen)
+	/// Storage: TrustlessFileServer Metadata (r:0 w:1)
+	/// Proof: TrustlessFileServer Metadata (max_values: None, max_size: Some(761), added: 3236, mode: MaxEncodedLen)
+	/// Storage: TrustlessFileServer Paused (r:1 w:0)
+	/// Proof: TrustlessFileServer Paused (max_values: Some(1), max_size: Some(1), added: 496, mode: MaxEncodedLen)
+	/// Storage: TrustlessFileServer FileHistory (r:1 w:1)
+	/// Proof: TrustlessFileServer FileHistory (max_values: None, max_size: Some(3266), added: 5741, mode: MaxEncodedLen)
+	fn set_metadata() -> Weight {
+		// Proof Size summary in bytes:
+		//  Measured:  `2162`
+		//  Estimated: `6731`
+		// Minimum execution time: 17_000_000 picoseconds.
+		Weight::from_parts(18_000_000, 6731)
+			.saturating_add(RocksDbWeight::get().reads(3_u64))
+			.saturating_add(RocksDbWeight::get().writes(2_u64))
+	}
+	/// Storage: TrustlessFileServer FileOwners (r:1 w:0)
+	/// Proof: TrustlessFileServer FileOwners (max_values: None, max_size: Some(80), added: 2555, mode: MaxEncodedLen)
+	/// The range of component `x` is `[1, 5]`.
+	fn verify_proof(x: u32, ) -> Weight {
+		// Proof Size summary in bytes:
+		//  Measured:  `2162`
+		//  Estimated: `3615`
+		// Minimum execution time: 6_000_000 picoseconds.
+		Weight::from_parts(6_120_000, 3615)
+			// Standard Error: 1_402
+			.saturating_add(Weight::from_parts(1_285_000, 0).saturating_mul(x.into()))
+			.saturating_add(RocksDbWeight::get().reads(1_u64))
+	}
+	/// Storage: TrustlessFileServer FileOwners (r:1 w:0)
+	/// Proof: TrustlessFileServer FileOwners (max_values: None, max_size: Some(80), added: 2555, mode: MaxEncodedLen)
+	/// Storage: TrustlessFileServer UploadProgress (r:1 w:1)
+	/// Proof: TrustlessFileServer UploadProgress (max_values: None, max_size: Some(52), added: 2527, mode: MaxEncodedLen)
+	/// Storage: TrustlessFileServer UploadedPieces (r:1 w:1)
+	/// Proof: TrustlessFileServer UploadedPieces (max_values: None, max_size: Some(52), added: 2527, mode: MaxEncodedLen)
+	fn report_upload_progress() -> Weight {
+		// Proof Size summary in bytes:
+		//  Measured:  `2162`
+		//  Estimated: `7132`
+		// Minimum execution time: 15_000_000 picoseconds.
+		Weight::from_parts(16_000_000, 7132)
+			.saturating_add(RocksDbWeight::get().reads(3_u64))
+			.saturating_add(RocksDbWeight::get().writes(2_u64))
+	}
+	/// Storage: TrustlessFileServer FileOwners (r:1 w:0)
+	/// Proof: TrustlessFileServer FileOwners (max_values: None, max_size: Some(80), added: 2555, mode: MaxEncodedLen)
+	/// Storage: TrustlessFileServer ChunkAllocations (r:0 w:1)
+	/// Proof: TrustlessFileServer ChunkAllocations (max_values: None, max_size: Some(249), added: 2724, mode: MaxEncodedLen)
+	/// Storage: TrustlessFileServer UploadedPieces (r:1 w:1)
+	/// Proof: TrustlessFileServer UploadedPieces (max_values: None, max_size: Some(52), added: 2527, mode: MaxEncodedLen)
+	/// The range of component `x` is `[1, 16]`.
+	fn report_chunk_allocations(x: u32, ) -> Weight {
+		// Proof Size summary in bytes:
+		//  Measured:  `2162`
+		//  Estimated: `3615`
+		// Minimum execution time: 14_000_000 picoseconds.
+		Weight::from_parts(14_713_207, 3615)
+			// Standard Error: 2_001
+			.saturating_add(Weight::from_parts(31_470, 0).saturating_mul(x.into()))
+			.saturating_add(RocksDbWeight::get().reads(2_u64))
+			.saturating_add(RocksDbWeight::get().writes(2_u64))
+	}
+	/// Storage: TrustlessFileServer FileOwners (r:1 w:0)
+	/// Proof: TrustlessFileServer FileOwners (max_values: None, max_size: Some(80), added: 2555, mode: MaxEncodedLen)
+	/// Storage: TrustlessFileServer ProbeResults (r:0 w:1)
+	/// Proof: TrustlessFileServer ProbeResults (max_values: None, max_size: Some(61), added: 2536, mode: MaxEncodedLen)
+	fn report_probe() -> Weight {
+		// Proof Size summary in bytes:
+		//  Measured:  `2162`
+		//  Estimated: `3615`
+		// Minimum execution time: 13_000_000 picoseconds.
+		Weight::from_parts(14_000_000, 3615)
+			.saturating_add(RocksDbWeight::get().reads(1_u64))
+			.saturating_add(RocksDbWeight::get().writes(1_u64))
+	}
+	/// Storage: TrustlessFileServer FileOwners (r:1 w:0)
+	/// Proof: TrustlessFileServer FileOwners (max_values: None, max_size: Some(80), added: 2555, mode: MaxEncodedLen)
+	/// Storage: TrustlessFileServer FileTrees (r:1 w:0)
+	/// Proof: TrustlessFileServer FileTrees (max_values: None, max_size: Some(2116), added: 4591, mode: MaxEncodedLen)
+	/// Storage: TrustlessFileServer PendingRechunks (r:0 w:1)
+	/// Proof: TrustlessFileServer PendingRechunks (max_values: None, max_size: Some(48), added: 2523, mode: MaxEncodedLen)
+	/// Storage: TrustlessFileServer Paused (r:1 w:0)
+	/// Proof: TrustlessFileServer Paused (max_values: Some(1), max_size: Some(1), added: 496, mode: MaxEncodedLen)
+	fn rechunk_file() -> Weight {
+		// Proof Size summary in bytes:
+		//  Measured:  `4160`
+		//  Estimated: `5613`
+		// Minimum execution time: 18_000_000 picoseconds.
+		Weight::from_parts(18_000_000, 5613)
+			.saturating_add(RocksDbWeight::get().reads(3_u64))
+			.saturating_add(RocksDbWeight::get().writes(1_u64))
+	}
+	/// Storage: TrustlessFileServer PendingRechunks (r:1 w:1)
+	/// Proof: TrustlessFileServer PendingRechunks (max_values: None, max_size: Some(48), added: 2523, mode: MaxEncodedLen)
+	/// Storage: TrustlessFileServer FileOwners (r:2 w:2)
+	/// Proof: TrustlessFileServer FileOwners (max_values: None, max_size: Some(80), added: 2555, mode: MaxEncodedLen)
+	/// Storage: TrustlessFileServer FileTrees (r:1 w:2)
+	/// Proof: TrustlessFileServer FileTrees (max_values: None, max_size: Some(2116), added: 4591, mode: MaxEncodedLen)
+	/// Storage: TrustlessFileServer FileMeta (r:0 w:2)
+	/// Proof: TrustlessFileServer FileMeta (max_values: None, max_size: Some(65), added: 2540, mode: MaxEncodedLen)
+	/// Storage: TrustlessFileServer BlockUploads (r:1 w:1)
+	/// Proof: TrustlessFileServer BlockUploads (max_values: Some(1), max_size: Some(129), added: 624, mode: MaxEncodedLen)
+	/// Storage: TrustlessFileServer Metadata (r:1 w:2)
+	/// Proof: TrustlessFileServer Metadata (max_values: None, max_size: Some(761), added: 3236, mode: MaxEncodedLen)
+	/// Storage: TrustlessFileServer Checksums (r:1 w:2)
+	/// Proof: TrustlessFileServer Checksums (max_values: None, max_size: Some(80), added: 2555, mode: MaxEncodedLen)
+	/// Storage: TrustlessFileServer UploadProgress (r:0 w:1)
+	/// Proof: TrustlessFileServer UploadProgress (max_values: None, max_size: Some(52), added: 2527, mode: MaxEncodedLen)
+	/// Storage: TrustlessFileServer ChunkAllocations (r:0 w:32)
+	/// Proof: TrustlessFileServer ChunkAllocations (max_values: None, max_size: Some(249), added: 2724, mode: MaxEncodedLen)
+	/// Storage: TrustlessFileServer ProbeResults (r:0 w:1)
+	/// Proof: TrustlessFileServer ProbeResults (max_values: None, max_size: Some(61), added: 2536, mode: MaxEncodedLen)
+	/// Storage: TrustlessFileServer LastPinnedAt (r:0 w:1)
+	/// Proof: TrustlessFileServer LastPinnedAt (max_values: None, max_size: Some(56), added: 2531, mode: MaxEncodedLen)
+	/// Storage: TrustlessFileServer RootAliases (r:0 w:2)
+	/// Proof: TrustlessFileServer RootAliases (max_values: None, max_size: Some(80), added: 2555, mode: MaxEncodedLen)
+	/// Storage: TrustlessFileServer RegistrantCount (r:1 w:2)
+	/// Proof: TrustlessFileServer RegistrantCount (max_values: None, max_size: Some(52), added: 2527, mode: MaxEncodedLen)
+	/// Storage: TrustlessFileServer Registrants (r:16 w:32)
+	/// Proof: TrustlessFileServer Registrants (max_values: None, max_size: Some(114), added: 2589, mode: MaxEncodedLen)
+	/// Storage: TrustlessFileServer Sponsors (r:16 w:32)
+	/// Proof: TrustlessFileServer Sponsors (max_values: None, max_size: Some(128), added: 2603, mode: MaxEncodedLen)
+	/// Storage: TrustlessFileServer UploadedPieces (r:0 w:1)
+	/// Proof: TrustlessFileServer UploadedPieces (max_values: None, max_size: Some(52), added: 2527, mode: MaxEncodedLen)
+	/// Storage: TrustlessFileServer PieceAudits (r:0 w:1)
+	/// Proof: TrustlessFileServer PieceAudits (max_values: None, max_size: Some(61), added: 2536, mode: MaxEncodedLen)
+	/// Storage: TrustlessFileServer UploadedAt (r:1 w:2)
+	/// Proof: TrustlessFileServer UploadedAt (max_values: None, max_size: Some(60), added: 2535, mode: MaxEncodedLen)
+	/// Storage: TrustlessFileServer FrozenFiles (r:1 w:2)
+	/// Proof: TrustlessFileServer FrozenFiles (max_values: None, max_size: Some(48), added: 2523, mode: MaxEncodedLen)
+	/// Storage: TrustlessFileServer ManagementThresholds (r:1 w:2)
+	/// Proof: TrustlessFileServer ManagementThresholds (max_values: None, max_size: Some(50), added: 2525, mode: MaxEncodedLen)
+	/// The range of component `x` is `[1, 32]`.
+	/// The range of component `r` is `[1, 16]`.
+	fn report_rechunk(x: u32, r: u32, ) -> Weight {
+		// Proof Size summary in bytes:
+		//  Measured:  `7793`
+		//  Estimated: `9246`
+		// Minimum execution time: 45_000_000 picoseconds.
+		Weight::from_parts(45_000_000, 9246)
+			// Standard Error: 1_000
+			.saturating_add(Weight::from_parts(1_150_000, 0).saturating_mul(x.into()))
+			// Standard Error: 2_000
+			.saturating_add(Weight::from_parts(2_400_000, 0).saturating_mul(r.into()))
+			.saturating_add(RocksDbWeight::get().reads(11_u64))
+			.saturating_add(RocksDbWeight::get().reads((2_u64).saturating_mul(r.into())))
+			.saturating_add(RocksDbWeight::get().writes(26_u64))
+			.saturating_add(RocksDbWeight::get().writes((1_u64).saturating_mul(x.into())))
+			.saturating_add(RocksDbWeight::get().writes((4_u64).saturating_mul(r.into())))
+			.saturating_add(Weight::from_parts(0, 5192).saturating_mul(r.into()))
+	}
+	/// Storage: TrustlessFileServer FileOwners (r:1 w:1)
+	/// Proof: TrustlessFileServer FileOwners (max_values: None, max_size: Some(80), added: 2555, mode: MaxEncodedLen)
+	/// Storage: TrustlessFileServer FileMeta (r:1 w:1)
+	/// Proof: TrustlessFileServer FileMeta (max_values: None, max_size: Some(65), added: 2540, mode: MaxEncodedLen)
+	/// Storage: TrustlessFileServer FileTrees (r:0 w:1)
+	/// Proof: TrustlessFileServer FileTrees (max_values: None, max_size: Some(2116), added: 4591, mode: MaxEncodedLen)
+	/// Storage: TrustlessFileServer Registrants (r:1 w:1)
+	/// Proof: TrustlessFileServer Registrants (max_values: None, max_size: Some(114), added: 2589, mode: MaxEncodedLen)
+	/// Storage: TrustlessFileServer Sponsors (r:1 w:1)
+	/// Proof: TrustlessFileServer Sponsors (max_values: None, max_size: Some(128), added: 2603, mode: MaxEncodedLen)
+	/// Storage: System Account (r:1 w:1)
+	/// Proof: System Account (max_values: None, max_size: Some(128), added: 2603, mode: MaxEncodedLen)
+	/// Storage: TrustlessFileServer RegistrantCount (r:1 w:1)
+	/// Proof: TrustlessFileServer RegistrantCount (max_values: None, max_size: Some(52), added: 2527, mode: MaxEncodedLen)
+	/// Storage: TrustlessFileServer Metadata (r:0 w:1)
+	/// Proof: TrustlessFileServer Metadata (max_values: None, max_size: Some(761), added: 3236, mode: MaxEncodedLen)
+	/// Storage: TrustlessFileServer Checksums (r:0 w:1)
+	/// Proof: TrustlessFileServer Checksums (max_values: None, max_size: Some(80), added: 2555, mode: MaxEncodedLen)
+	/// Storage: TrustlessFileServer UploadProgress (r:0 w:1)
+	/// Proof: TrustlessFileServer UploadProgress (max_values: None, max_size: Some(52), added: 2527, mode: MaxEncodedLen)
+	/// Storage: TrustlessFileServer OrphanedChunks (r:0 w:1)
+	/// Proof: TrustlessFileServer OrphanedChunks (max_values: None, max_size: Some(48), added: 2523, mode: MaxEncodedLen)
+	/// Storage: TrustlessFileServer ProbeResults (r:0 w:1)
+	/// Proof: TrustlessFileServer ProbeResults (max_values: None, max_size: Some(61), added: 2536, mode: MaxEncodedLen)
+	/// Storage: TrustlessFileServer LastPinnedAt (r:0 w:1)
+	/// Proof: TrustlessFileServer LastPinnedAt (max_values: None, max_size: Some(56), added: 2531, mode: MaxEncodedLen)
+	/// Storage: TrustlessFileServer PendingRechunks (r:0 w:1)
+	/// Proof: TrustlessFileServer PendingRechunks (max_values: None, max_size: Some(48), added: 2523, mode: MaxEncodedLen)
+	/// Storage: TrustlessFileServer Paused (r:1 w:0)
+	/// Proof: TrustlessFileServer Paused (max_values: Some(1), max_size: Some(1), added: 496, mode: MaxEncodedLen)
+	/// Storage: TrustlessFileServer UploadedPieces (r:0 w:1)
+	/// Proof: TrustlessFileServer UploadedPieces (max_values: None, max_size: Some(52), added: 2527, mode: MaxEncodedLen)
+	/// Storage: TrustlessFileServer FileHistory (r:1 w:1)
+	/// Proof: TrustlessFileServer FileHistory (max_values: None, max_size: Some(3266), added: 5741, mode: MaxEncodedLen)
+	/// Storage: TrustlessFileServer AccountUsage (r:2 w:2)
+	/// Proof: TrustlessFileServer AccountUsage (max_values: None, max_size: Some(76), added: 2551, mode: MaxEncodedLen)
+	/// Storage: TrustlessFileServer TotalBytesStored (r:1 w:1)
+	/// Proof: TrustlessFileServer TotalBytesStored (max_values: Some(1), max_size: Some(8), added: 503, mode: MaxEncodedLen)
+	/// Storage: TrustlessFileServer PieceAudits (r:0 w:1)
+	/// Proof: TrustlessFileServer PieceAudits (max_values: None, max_size: Some(61), added: 2536, mode: MaxEncodedLen)
+	/// Storage: Balances Holds (r:1 w:1)
+	/// Proof: Balances Holds (max_values: None, max_size: Some(134), added: 2609, mode: MaxEncodedLen)
+	/// Storage: TrustlessFileServer HeldBalance (r:1 w:1)
+	/// Proof: TrustlessFileServer HeldBalance (max_values: Some(1), max_size: Some(16), added: 511, mode: MaxEncodedLen)
+	/// Storage: TrustlessFileServer UploadedAt (r:0 w:1)
+	/// Proof: TrustlessFileServer UploadedAt (max_values: None, max_size: Some(60), added: 2535, mode: MaxEncodedLen)
+	/// Storage: TrustlessFileServer FrozenFiles (r:0 w:1)
+	/// Proof: TrustlessFileServer FrozenFiles (max_values: None, max_size: Some(48), added: 2523, mode: MaxEncodedLen)
+	/// Storage: TrustlessFileServer ManagementThresholds (r:0 w:1)
+	/// Proof: TrustlessFileServer ManagementThresholds (max_values: None, max_size: Some(50), added: 2525, mode: MaxEncodedLen)
+	fn delete_file() -> Weight {
+		// Proof Size summary in bytes:
+		//  Measured:  `4160`
+		//  Estimated: `6731`
+		// Minimum execution time: 66_000_000 picoseconds.
+		Weight::from_parts(66_000_000, 6731)
+			.saturating_add(RocksDbWeight::get().reads(13_u64))
+			.saturating_add(RocksDbWeight::get().writes(25_u64))
+	}
+	/// Storage: TrustlessFileServer Paused (r:0 w:1)
+	/// Proof: TrustlessFileServer Paused (max_values: Some(1), max_size: Some(1), added: 496, mode: MaxEncodedLen)
+	fn pause() -> Weight {
+		// Proof Size summary in bytes:
+		//  Measured:  `6`
 		//  Estimated: `0`
-		// Minimum execution time: 10_000_000 picoseconds.
-		Weight::from_parts(14_013_003, 0)
+		// Minimum execution time: 9_000_000 picoseconds.
+		Weight::from_parts(9_000_000, 0)
+			.saturating_add(RocksDbWeight::get().writes(1_u64))
+	}
+	/// Storage: TrustlessFileServer Paused (r:0 w:1)
+	/// Proof: TrustlessFileServer Paused (max_values: Some(1), max_size: Some(1), added: 496, mode: MaxEncodedLen)
+	fn unpause() -> Weight {
+		// Proof Size summary in bytes:
+		//  Measured:  `6`
+		//  Estimated: `0`
+		// Minimum execution time: 9_000_000 picoseconds.
+		Weight::from_parts(9_000_000, 0)
+			.saturating_add(RocksDbWeight::get().writes(1_u64))
+	}
+	/// Storage: TrustlessFileServer Paused (r:1 w:0)
+	/// Proof: TrustlessFileServer Paused (max_values: Some(1), max_size: Some(1), added: 496, mode: MaxEncodedLen)
+	/// Storage: TrustlessFileServer FileOwners (r:1 w:0)
+	/// Proof: TrustlessFileServer FileOwners (max_values: None, max_size: Some(80), added: 2555, mode: MaxEncodedLen)
+	/// Storage: TrustlessFileServer Names (r:1 w:1)
+	/// Proof: TrustlessFileServer Names (max_values: None, max_size: Some(358), added: 2833, mode: MaxEncodedLen)
+	/// Storage: System Account (r:2 w:2)
+	/// Proof: System Account (max_values: None, max_size: Some(128), added: 2603, mode: MaxEncodedLen)
+	/// Storage: Balances Holds (r:2 w:2)
+	/// Proof: Balances Holds (max_values: None, max_size: Some(134), added: 2609, mode: MaxEncodedLen)
+	/// Storage: TrustlessFileServer HeldBalance (r:1 w:1)
+	/// Proof: TrustlessFileServer HeldBalance (max_values: Some(1), max_size: Some(16), added: 511, mode: MaxEncodedLen)
+	fn register_alias() -> Weight {
+		// Proof Size summary in bytes:
+		//  Measured:  `2267`
+		//  Estimated: `5613`
+		// Minimum execution time: 21_000_000 picoseconds.
+		Weight::from_parts(22_000_000, 5613)
+			.saturating_add(RocksDbWeight::get().reads(8_u64))
+			.saturating_add(RocksDbWeight::get().writes(6_u64))
+	}
+	/// Storage: TrustlessFileServer Paused (r:1 w:0)
+	/// Proof: TrustlessFileServer Paused (max_values: Some(1), max_size: Some(1), added: 496, mode: MaxEncodedLen)
+	/// Storage: TrustlessFileServer Names (r:1 w:1)
+	/// Proof: TrustlessFileServer Names (max_values: None, max_size: Some(358), added: 2833, mode: MaxEncodedLen)
+	/// Storage: System Account (r:1 w:1)
+	/// Proof: System Account (max_values: None, max_size: Some(128), added: 2603, mode: MaxEncodedLen)
+	/// Storage: Balances Holds (r:1 w:1)
+	/// Proof: Balances Holds (max_values: None, max_size: Some(134), added: 2609, mode: MaxEncodedLen)
+	/// Storage: TrustlessFileServer HeldBalance (r:1 w:1)
+	/// Proof: TrustlessFileServer HeldBalance (max_values: Some(1), max_size: Some(16), added: 511, mode: MaxEncodedLen)
+	fn release_alias() -> Weight {
+		// Proof Size summary in bytes:
+		//  Measured:  `252`
+		//  Estimated: `3819`
+		// Minimum execution time: 17_000_000 picoseconds.
+		Weight::from_parts(18_000_000, 3819)
+			.saturating_add(RocksDbWeight::get().reads(5_u64))
+			.saturating_add(RocksDbWeight::get().writes(4_u64))
+	}
+	/// Storage: TrustlessFileServer FileOwners (r:1 w:0)
+	/// Proof: TrustlessFileServer FileOwners (max_values: None, max_size: Some(80), added: 2555, mode: MaxEncodedLen)
+	/// Storage: TrustlessFileServer LastPinnedAt (r:1 w:1)
+	/// Proof: TrustlessFileServer LastPinnedAt (max_values: None, max_size: Some(56), added: 2531, mode: MaxEncodedLen)
+	fn report_repin() -> Weight {
+		// Proof Size summary in bytes:
+		//  Measured:  `2162`
+		//  Estimated: `7154`
+		// Minimum execution time: 15_000_000 picoseconds.
+		Weight::from_parts(16_000_000, 7154)
+			.saturating_add(RocksDbWeight::get().reads(2_u64))
+			.saturating_add(RocksDbWeight::get().writes(1_u64))
+	}
+
+	/// Storage: TrustlessFileServer BlockUploads (r:1 w:1)
+	/// Proof: TrustlessFileServer BlockUploads (max_values: Some(1), max_size: Some(129), added: 624, mode: MaxEncodedLen)
+	/// Storage: TrustlessFileServer FileOwners (r:1 w:1)
+	/// Proof: TrustlessFileServer FileOwners (max_values: None, max_size: Some(80), added: 2555, mode: MaxEncodedLen)
+	/// Storage: TrustlessFileServer FileTrees (r:0 w:1)
+	/// Proof: TrustlessFileServer FileTrees (max_values: None, max_size: Some(2116), added: 4591, mode: MaxEncodedLen)
+	/// Storage: TrustlessFileServer FileMeta (r:0 w:1)
+	/// Proof: TrustlessFileServer FileMeta (max_values: None, max_size: Some(65), added: 2540, mode: MaxEncodedLen)
+	/// Storage: System Account (r:1 w:1)
+	/// Proof: System Account (max_values: None, max_size: Some(128), added: 2603, mode: MaxEncodedLen)
+	/// Storage: TrustlessFileServer Registrants (r:0 w:1)
+	/// Proof: TrustlessFileServer Registrants (max_values: None, max_size: Some(114), added: 2589, mode: MaxEncodedLen)
+	/// Storage: TrustlessFileServer Sponsors (r:0 w:1)
+	/// Proof: TrustlessFileServer Sponsors (max_values: None, max_size: Some(128), added: 2603, mode: MaxEncodedLen)
+	/// Storage: TrustlessFileServer RegistrantCount (r:0 w:1)
+	/// Proof: TrustlessFileServer RegistrantCount (max_values: None, max_size: Some(52), added: 2527, mode: MaxEncodedLen)
+	/// Storage: TrustlessFileServer Checksums (r:0 w:1)
+	/// Proof: TrustlessFileServer Checksums (max_values: None, max_size: Some(80), added: 2555, mode: MaxEncodedLen)
+	/// Storage: TrustlessFileServer Paused (r:1 w:0)
+	/// Proof: TrustlessFileServer Paused (max_values: Some(1), max_size: Some(1), added: 496, mode: MaxEncodedLen)
+	/// Storage: TrustlessFileServer UploadedPieces (r:0 w:1)
+	/// Proof: TrustlessFileServer UploadedPieces (max_values: None, max_size: Some(52), added: 2527, mode: MaxEncodedLen)
+	/// Storage: TrustlessFileServer AccountUsage (r:2 w:2)
+	/// Proof: TrustlessFileServer AccountUsage (max_values: None, max_size: Some(76), added: 2551, mode: MaxEncodedLen)
+	/// Storage: TrustlessFileServer TotalBytesStored (r:1 w:1)
+	/// Proof: TrustlessFileServer TotalBytesStored (max_values: Some(1), max_size: Some(8), added: 503, mode: MaxEncodedLen)
+	/// Storage: Balances Holds (r:1 w:1)
+	/// Proof: Balances Holds (max_values: None, max_size: Some(134), added: 2609, mode: MaxEncodedLen)
+	/// Storage: TrustlessFileServer HeldBalance (r:1 w:1)
+	/// Proof: TrustlessFileServer HeldBalance (max_values: Some(1), max_size: Some(16), added: 511, mode: MaxEncodedLen)
+	/// Storage: Timestamp Now (r:1 w:0)
+	/// Proof: Timestamp Now (max_values: Some(1), max_size: Some(8), added: 503, mode: MaxEncodedLen)
+	/// Storage: TrustlessFileServer UploadedAt (r:0 w:1)
+	/// Proof: TrustlessFileServer UploadedAt (max_values: None, max_size: Some(60), added: 2535, mode: MaxEncodedLen)
+	/// Storage: TrustlessFileServer AccountUploads (r:1 w:1)
+	/// Proof: TrustlessFileServer AccountUploads (max_values: None, max_size: Some(52), added: 2527, mode: MaxEncodedLen)
+	/// The range of component `x` is `[1024, 32768]`.
+	fn sponsor_upload(x: u32, ) -> Weight {
+		// Proof Size summary in bytes:
+		//  Measured:  `6`
+		//  Estimated: `1614`
+		// Minimum execution time: 13_000_000 picoseconds.
+		Weight::from_parts(17_102_540, 1614)
+			// Standard Error: 6
+			.saturating_add(Weight::from_parts(3_830, 0).saturating_mul(x.into()))
+			.saturating_add(RocksDbWeight::get().reads(11_u64))
+			.saturating_add(RocksDbWeight::get().writes(17_u64))
+	}
+	/// Storage: TrustlessFileServer BlockUploads (r:1 w:1)
+	/// Proof: TrustlessFileServer BlockUploads (max_values: Some(1), max_size: Some(129), added: 624, mode: MaxEncodedLen)
+	/// Storage: TrustlessFileServer FileOwners (r:1 w:1)
+	/// Proof: TrustlessFileServer FileOwners (max_values: None, max_size: Some(80), added: 2555, mode: MaxEncodedLen)
+	/// Storage: TrustlessFileServer FileTrees (r:0 w:1)
+	/// Proof: TrustlessFileServer FileTrees (max_values: None, max_size: Some(2116), added: 4591, mode: MaxEncodedLen)
+	/// Storage: TrustlessFileServer FileMeta (r:0 w:1)
+	/// Proof: TrustlessFileServer FileMeta (max_values: None, max_size: Some(65), added: 2540, mode: MaxEncodedLen)
+	/// Storage: TrustlessFileServer Registrants (r:0 w:1)
+	/// Proof: TrustlessFileServer Registrants (max_values: None, max_size: Some(114), added: 2589, mode: MaxEncodedLen)
+	/// Storage: TrustlessFileServer RegistrantCount (r:0 w:1)
+	/// Proof: TrustlessFileServer RegistrantCount (max_values: None, max_size: Some(52), added: 2527, mode: MaxEncodedLen)
+	/// Storage: TrustlessFileServer Checksums (r:0 w:1)
+	/// Proof: TrustlessFileServer Checksums (max_values: None, max_size: Some(80), added: 2555, mode: MaxEncodedLen)
+	/// Storage: TrustlessFileServer Paused (r:1 w:0)
+	/// Proof: TrustlessFileServer Paused (max_values: Some(1), max_size: Some(1), added: 496, mode: MaxEncodedLen)
+	/// Storage: TrustlessFileServer UploadedPieces (r:0 w:1)
+	/// Proof: TrustlessFileServer UploadedPieces (max_values: None, max_size: Some(52), added: 2527, mode: MaxEncodedLen)
+	/// Storage: TrustlessFileServer AccountUsage (r:1 w:1)
+	/// Proof: TrustlessFileServer AccountUsage (max_values: None, max_size: Some(76), added: 2551, mode: MaxEncodedLen)
+	/// Storage: TrustlessFileServer TotalBytesStored (r:1 w:1)
+	/// Proof: TrustlessFileServer TotalBytesStored (max_values: Some(1), max_size: Some(8), added: 503, mode: MaxEncodedLen)
+	/// Storage: Timestamp Now (r:1 w:0)
+	/// Proof: Timestamp Now (max_values: Some(1), max_size: Some(8), added: 503, mode: MaxEncodedLen)
+	/// Storage: TrustlessFileServer UploadedAt (r:0 w:1)
+	/// Proof: TrustlessFileServer UploadedAt (max_values: None, max_size: Some(60), added: 2535, mode: MaxEncodedLen)
+	/// The range of component `x` is `[1024, 32768]`.
+	fn upload_file_unsigned(x: u32, ) -> Weight {
+		// Proof Size summary in bytes:
+		//  Measured:  `6`
+		//  Estimated: `1614`
+		// Minimum execution time: 14_000_000 picoseconds.
+		Weight::from_parts(18_930_251, 1614)
+			// Standard Error: 7
+			.saturating_add(Weight::from_parts(7_412, 0).saturating_mul(x.into()))
+			.saturating_add(RocksDbWeight::get().reads(6_u64))
+			.saturating_add(RocksDbWeight::get().writes(11_u64))
+	}
+	/// Storage: TrustlessFileServer BlockUploads (r:1 w:1)
+	/// Proof: TrustlessFileServer BlockUploads (max_values: Some(1), max_size: Some(129), added: 624, mode: MaxEncodedLen)
+	/// Storage: TrustlessFileServer FileOwners (r:4 w:4)
+	/// Proof: TrustlessFileServer FileOwners (max_values: None, max_size: Some(80), added: 2555, mode: MaxEncodedLen)
+	/// Storage: TrustlessFileServer FileTrees (r:0 w:4)
+	/// Proof: TrustlessFileServer FileTrees (max_values: None, max_size: Some(2116), added: 4591, mode: MaxEncodedLen)
+	/// Storage: TrustlessFileServer FileMeta (r:0 w:4)
+	/// Proof: TrustlessFileServer FileMeta (max_values: None, max_size: Some(65), added: 2540, mode: MaxEncodedLen)
+	/// Storage: System Account (r:1 w:1)
+	/// Proof: System Account (max_values: None, max_size: Some(128), added: 2603, mode: MaxEncodedLen)
+	/// Storage: TrustlessFileServer Registrants (r:0 w:4)
+	/// Proof: TrustlessFileServer Registrants (max_values: None, max_size: Some(114), added: 2589, mode: MaxEncodedLen)
+	/// Storage: TrustlessFileServer RegistrantCount (r:0 w:4)
+	/// Proof: TrustlessFileServer RegistrantCount (max_values: None, max_size: Some(52), added: 2527, mode: MaxEncodedLen)
+	/// Storage: TrustlessFileServer Checksums (r:0 w:4)
+	/// Proof: TrustlessFileServer Checksums (max_values: None, max_size: Some(80), added: 2555, mode: MaxEncodedLen)
+	/// Storage: TrustlessFileServer Paused (r:1 w:0)
+	/// Proof: TrustlessFileServer Paused (max_values: Some(1), max_size: Some(1), added: 496, mode: MaxEncodedLen)
+	/// Storage: TrustlessFileServer UploadedPieces (r:0 w:4)
+	/// Proof: TrustlessFileServer UploadedPieces (max_values: None, max_size: Some(52), added: 2527, mode: MaxEncodedLen)
+	/// Storage: TrustlessFileServer AccountUsage (r:1 w:1)
+	/// Proof: TrustlessFileServer AccountUsage (max_values: None, max_size: Some(76), added: 2551, mode: MaxEncodedLen)
+	/// Storage: TrustlessFileServer TotalBytesStored (r:1 w:1)
+	/// Proof: TrustlessFileServer TotalBytesStored (max_values: Some(1), max_size: Some(8), added: 503, mode: MaxEncodedLen)
+	/// Storage: Balances Holds (r:1 w:1)
+	/// Proof: Balances Holds (max_values: None, max_size: Some(134), added: 2609, mode: MaxEncodedLen)
+	/// Storage: TrustlessFileServer HeldBalance (r:1 w:1)
+	/// Proof: TrustlessFileServer HeldBalance (max_values: Some(1), max_size: Some(16), added: 511, mode: MaxEncodedLen)
+	/// Storage: Timestamp Now (r:1 w:0)
+	/// Proof: Timestamp Now (max_values: Some(1), max_size: Some(8), added: 503, mode: MaxEncodedLen)
+	/// Storage: TrustlessFileServer UploadedAt (r:0 w:4)
+	/// Proof: TrustlessFileServer UploadedAt (max_values: None, max_size: Some(60), added: 2535, mode: MaxEncodedLen)
+	/// Storage: TrustlessFileServer AccountUploads (r:1 w:1)
+	/// Proof: TrustlessFileServer AccountUploads (max_values: None, max_size: Some(52), added: 2527, mode: MaxEncodedLen)
+	/// The range of component `n` is `[1, 16]`.
+	/// The range of component `x` is `[1024, 32768]`.
+	fn upload_files(n: u32, x: u32, ) -> Weight {
+		// Proof Size summary in bytes:
+		//  Measured:  `6`
+		//  Estimated: `1614`
+		// Minimum execution time: 12_000_000 picoseconds.
+		Weight::from_parts(4_512_830, 1614)
+			// Standard Error: 24_000
+			.saturating_add(Weight::from_parts(11_690_000, 0).saturating_mul(n.into()))
 			// Standard Error: 6
 			.saturating_add(Weight::from_parts(3_825, 0).saturating_mul(x.into()))
+			.saturating_add(RocksDbWeight::get().reads(7_u64))
+			.saturating_add(RocksDbWeight::get().reads((3_u64).saturating_mul(n.into())))
+			.saturating_add(RocksDbWeight::get().writes(5_u64))
+			.saturating_add(RocksDbWeight::get().writes((10_u64).saturating_mul(n.into())))
+			.saturating_add(Weight::from_parts(0, 4623).saturating_mul(n.into()))
+	}
+	/// Storage: TrustlessFileServer Paused (r:1 w:0)
+	/// Proof: TrustlessFileServer Paused (max_values: Some(1), max_size: Some(1), added: 496, mode: MaxEncodedLen)
+	/// Storage: TrustlessFileServer FileOwners (r:32 w:0)
+	/// Proof: TrustlessFileServer FileOwners (max_values: None, max_size: Some(80), added: 2555, mode: MaxEncodedLen)
+	/// Storage: TrustlessFileServer Bundles (r:1 w:1)
+	/// Proof: TrustlessFileServer Bundles (max_values: None, max_size: Some(1082), added: 3557, mode: MaxEncodedLen)
+	/// Storage: System Account (r:1 w:1)
+	/// Proof: System Account (max_values: None, max_size: Some(128), added: 2603, mode: MaxEncodedLen)
+	/// Storage: Balances Holds (r:1 w:1)
+	/// Proof: Balances Holds (max_values: None, max_size: Some(134), added: 2609, mode: MaxEncodedLen)
+	/// Storage: TrustlessFileServer HeldBalance (r:1 w:1)
+	/// Proof: TrustlessFileServer HeldBalance (max_values: Some(1), max_size: Some(16), added: 511, mode: MaxEncodedLen)
+	/// The range of component `n` is `[2, 32]`.
+	fn create_bundle(n: u32, ) -> Weight {
+		// Proof Size summary in bytes:
+		//  Measured:  `252 + n * (2162 ±0)`
+		//  Estimated: `4547 + n * (4623 ±0)`
+		// Minimum execution time: 31_000_000 picoseconds.
+		Weight::from_parts(24_316_000, 4547)
+			// Standard Error: 9_000
+			.saturating_add(Weight::from_parts(3_914_000, 0).saturating_mul(n.into()))
+			.saturating_add(RocksDbWeight::get().reads(5_u64))
+			.saturating_add(RocksDbWeight::get().reads((1_u64).saturating_mul(n.into())))
+			.saturating_add(RocksDbWeight::get().writes(4_u64))
+			.saturating_add(Weight::from_parts(0, 4623).saturating_mul(n.into()))
+	}
+	/// Storage: TrustlessFileServer Paused (r:1 w:0)
+	/// Proof: TrustlessFileServer Paused (max_values: Some(1), max_size: Some(1), added: 496, mode: MaxEncodedLen)
+	/// Storage: TrustlessFileServer Bundles (r:1 w:1)
+	/// Proof: TrustlessFileServer Bundles (max_values: None, max_size: Some(1082), added: 3557, mode: MaxEncodedLen)
+	/// Storage: System Account (r:1 w:1)
+	/// Proof: System Account (max_values: None, max_size: Some(128), added: 2603, mode: MaxEncodedLen)
+	/// Storage: Balances Holds (r:1 w:1)
+	/// Proof: Balances Holds (max_values: None, max_size: Some(134), added: 2609, mode: MaxEncodedLen)
+	/// Storage: TrustlessFileServer HeldBalance (r:1 w:1)
+	/// Proof: TrustlessFileServer HeldBalance (max_values: Some(1), max_size: Some(16), added: 511, mode: MaxEncodedLen)
+	fn remove_bundle() -> Weight {
+		// Proof Size summary in bytes:
+		//  Measured:  `1270`
+		//  Estimated: `4547`
+		// Minimum execution time: 19_000_000 picoseconds.
+		Weight::from_parts(20_000_000, 4547)
+			.saturating_add(RocksDbWeight::get().reads(5_u64))
+			.saturating_add(RocksDbWeight::get().writes(4_u64))
+	}
+	/// Storage: TrustlessFileServer Paused (r:1 w:0)
+	/// Proof: TrustlessFileServer Paused (max_values: Some(1), max_size: Some(1), added: 496, mode: MaxEncodedLen)
+	/// Storage: TrustlessFileServer FileOwners (r:1 w:0)
+	/// Proof: TrustlessFileServer FileOwners (max_values: None, max_size: Some(80), added: 2555, mode: MaxEncodedLen)
+	/// Storage: TrustlessFileServer TimeLocks (r:1 w:1)
+	/// Proof: TrustlessFileServer TimeLocks (max_values: None, max_size: Some(154), added: 2629, mode: MaxEncodedLen)
+	fn commit_key() -> Weight {
+		// Proof Size summary in bytes:
+		//  Measured:  `1288`
+		//  Estimated: `5613`
+		// Minimum execution time: 17_000_000 picoseconds.
+		Weight::from_parts(18_000_000, 5613)
+			.saturating_add(RocksDbWeight::get().reads(3_u64))
+			.saturating_add(RocksDbWeight::get().writes(1_u64))
+	}
+	/// Storage: TrustlessFileServer Paused (r:1 w:0)
+	/// Proof: TrustlessFileServer Paused (max_values: Some(1), max_size: Some(1), added: 496, mode: MaxEncodedLen)
+	/// Storage: TrustlessFileServer TimeLocks (r:1 w:1)
+	/// Proof: TrustlessFileServer TimeLocks (max_values: None, max_size: Some(154), added: 2629, mode: MaxEncodedLen)
+	fn reveal_key() -> Weight {
+		// Proof Size summary in bytes:
+		//  Measured:  `163`
+		//  Estimated: `3619`
+		// Minimum execution time: 14_000_000 picoseconds.
+		Weight::from_parts(15_000_000, 3619)
+			.saturating_add(RocksDbWeight::get().reads(2_u64))
+			.saturating_add(RocksDbWeight::get().writes(1_u64))
+	}
+	/// Storage: TrustlessFileServer Paused (r:1 w:0)
+	/// Proof: TrustlessFileServer Paused (max_values: Some(1), max_size: Some(1), added: 496, mode: MaxEncodedLen)
+	/// Storage: TrustlessFileServer FileOwners (r:1 w:0)
+	/// Proof: TrustlessFileServer FileOwners (max_values: None, max_size: Some(80), added: 2555, mode: MaxEncodedLen)
+	/// Storage: TrustlessFileServer TimeLocks (r:1 w:1)
+	/// Proof: TrustlessFileServer TimeLocks (max_values: None, max_size: Some(154), added: 2629, mode: MaxEncodedLen)
+	/// Storage: TrustlessFileServer KeyEscrows (r:0 w:1)
+	/// Proof: TrustlessFileServer KeyEscrows (max_values: None, max_size: Some(4021), added: 6496, mode: MaxEncodedLen)
+	/// The range of component `n` is `[1, 16]`.
+	fn escrow_key(n: u32, ) -> Weight {
+		// Proof Size summary in bytes:
+		//  Measured:  `1288`
+		//  Estimated: `5613`
+		// Minimum execution time: 21_000_000 picoseconds.
+		Weight::from_parts(20_614_000, 5613)
+			// Standard Error: 3_000
+			.saturating_add(Weight::from_parts(1_127_000, 0).saturating_mul(n.into()))
+			.saturating_add(RocksDbWeight::get().reads(3_u64))
+			.saturating_add(RocksDbWeight::get().writes(2_u64))
+	}
+	/// Storage: TrustlessFileServer Paused (r:1 w:0)
+	/// Proof: TrustlessFileServer Paused (max_values: Some(1), max_size: Some(1), added: 496, mode: MaxEncodedLen)
+	/// Storage: TrustlessFileServer KeyEscrows (r:1 w:1)
+	/// Proof: TrustlessFileServer KeyEscrows (max_values: None, max_size: Some(4021), added: 6496, mode: MaxEncodedLen)
+	/// Storage: TrustlessFileServer TimeLocks (r:1 w:1)
+	/// Proof: TrustlessFileServer TimeLocks (max_values: None, max_size: Some(154), added: 2629, mode: MaxEncodedLen)
+	/// The range of component `n` is `[1, 16]`.
+	fn submit_key_share(n: u32, ) -> Weight {
+		// Proof Size summary in bytes:
+		//  Measured:  `263 + n * (227 ±0)`
+		//  Estimated: `7486`
+		// Minimum execution time: 24_000_000 picoseconds.
+		Weight::from_parts(19_870_000, 7486)
+			// Standard Error: 11_000
+			.saturating_add(Weight::from_parts(2_931_000, 0).saturating_mul(n.into()))
+			.saturating_add(RocksDbWeight::get().reads(3_u64))
+			.saturating_add(RocksDbWeight::get().writes(2_u64))
+	}
+	/// Storage: TrustlessFileServer Paused (r:1 w:0)
+	/// Proof: TrustlessFileServer Paused (max_values: Some(1), max_size: Some(1), added: 496, mode: MaxEncodedLen)
+	/// Storage: TrustlessFileServer FileOwners (r:1 w:0)
+	/// Proof: TrustlessFileServer FileOwners (max_values: None, max_size: Some(80), added: 2555, mode: MaxEncodedLen)
+	/// Storage: TrustlessFileServer RootClaims (r:1 w:1)
+	/// Proof: TrustlessFileServer RootClaims (max_values: None, max_size: Some(68), added: 2543, mode: MaxEncodedLen)
+	/// Storage: System Account (r:1 w:1)
+	/// Proof: System Account (max_values: None, max_size: Some(128), added: 2603, mode: MaxEncodedLen)
+	/// Storage: TrustlessFileServer TotalBytesStored (r:1 w:0)
+	/// Proof: TrustlessFileServer TotalBytesStored (max_values: Some(1), max_size: Some(8), added: 503, mode: MaxEncodedLen)
+	/// Storage: Balances Holds (r:1 w:1)
+	/// Proof: Balances Holds (max_values: None, max_size: Some(134), added: 2609, mode: MaxEncodedLen)
+	/// Storage: TrustlessFileServer HeldBalance (r:1 w:1)
+	/// Proof: TrustlessFileServer HeldBalance (max_values: Some(1), max_size: Some(16), added: 511, mode: MaxEncodedLen)
+	/// Storage: TrustlessFileServer AccountUploads (r:1 w:1)
+	/// Proof: TrustlessFileServer AccountUploads (max_values: None, max_size: Some(52), added: 2527, mode: MaxEncodedLen)
+	/// The range of component `x` is `[1024, 32768]`.
+	fn upload_file_with_root(x: u32, ) -> Weight {
+		// Proof Size summary in bytes:
+		//  Measured:  `6`
+		//  Estimated: `5613`
+		// Minimum execution time: 11_000_000 picoseconds.
+		Weight::from_parts(11_482_000, 5613)
+			// Standard Error: 1
+			.saturating_add(Weight::from_parts(164, 0).saturating_mul(x.into()))
+			.saturating_add(RocksDbWeight::get().reads(8_u64))
+			.saturating_add(RocksDbWeight::get().writes(5_u64))
+	}
+	/// Storage: TrustlessFileServer RootClaims (r:1 w:1)
+	/// Proof: TrustlessFileServer RootClaims (max_values: None, max_size: Some(68), added: 2543, mode: MaxEncodedLen)
+	/// Storage: TrustlessFileServer FileOwners (r:1 w:1)
+	/// Proof: TrustlessFileServer FileOwners (max_values: None, max_size: Some(80), added: 2555, mode: MaxEncodedLen)
+	/// Storage: TrustlessFileServer FileTrees (r:0 w:1)
+	/// Proof: TrustlessFileServer FileTrees (max_values: None, max_size: Some(2116), added: 4591, mode: MaxEncodedLen)
+	/// Storage: TrustlessFileServer FileMeta (r:0 w:1)
+	/// Proof: TrustlessFileServer FileMeta (max_values: None, max_size: Some(65), added: 2540, mode: MaxEncodedLen)
+	/// Storage: TrustlessFileServer BlockUploads (r:1 w:1)
+	/// Proof: TrustlessFileServer BlockUploads (max_values: Some(1), max_size: Some(129), added: 624, mode: MaxEncodedLen)
+	/// Storage: TrustlessFileServer Registrants (r:0 w:1)
+	/// Proof: TrustlessFileServer Registrants (max_values: None, max_size: Some(114), added: 2589, mode: MaxEncodedLen)
+	/// Storage: TrustlessFileServer RegistrantCount (r:0 w:1)
+	/// Proof: TrustlessFileServer RegistrantCount (max_values: None, max_size: Some(52), added: 2527, mode: MaxEncodedLen)
+	/// Storage: TrustlessFileServer Checksums (r:0 w:1)
+	/// Proof: TrustlessFileServer Checksums (max_values: None, max_size: Some(80), added: 2555, mode: MaxEncodedLen)
+	/// Storage: TrustlessFileServer UploadProgress (r:0 w:1)
+	/// Proof: TrustlessFileServer UploadProgress (max_values: None, max_size: Some(52), added: 2527, mode: MaxEncodedLen)
+	/// Storage: TrustlessFileServer UploadedPieces (r:0 w:1)
+	/// Proof: TrustlessFileServer UploadedPieces (max_values: None, max_size: Some(52), added: 2527, mode: MaxEncodedLen)
+	/// Storage: TrustlessFileServer AccountUsage (r:1 w:1)
+	/// Proof: TrustlessFileServer AccountUsage (max_values: None, max_size: Some(76), added: 2551, mode: MaxEncodedLen)
+	/// Storage: TrustlessFileServer TotalBytesStored (r:1 w:1)
+	/// Proof: TrustlessFileServer TotalBytesStored (max_values: Some(1), max_size: Some(8), added: 503, mode: MaxEncodedLen)
+	/// Storage: Timestamp Now (r:1 w:0)
+	/// Proof: Timestamp Now (max_values: Some(1), max_size: Some(8), added: 503, mode: MaxEncodedLen)
+	/// Storage: TrustlessFileServer UploadedAt (r:0 w:1)
+	/// Proof: TrustlessFileServer UploadedAt (max_values: None, max_size: Some(60), added: 2535, mode: MaxEncodedLen)
+	/// The range of component `x` is `[1, 32]`.
+	fn confirm_root(x: u32, ) -> Weight {
+		// Proof Size summary in bytes:
+		//  Measured:  `148`
+		//  Estimated: `5613`
+		// Minimum execution time: 24_000_000 picoseconds.
+		Weight::from_parts(22_930_000, 5613)
+			// Standard Error: 1_000
+			.saturating_add(Weight::from_parts(1_102_000, 0).saturating_mul(x.into()))
+			.saturating_add(RocksDbWeight::get().reads(6_u64))
+			.saturating_add(RocksDbWeight::get().writes(13_u64))
+	}
+	/// Storage: TrustlessFileServer RootClaims (r:1 w:1)
+	/// Proof: TrustlessFileServer RootClaims (max_values: None, max_size: Some(68), added: 2543, mode: MaxEncodedLen)
+	/// Storage: System Account (r:1 w:1)
+	/// Proof: System Account (max_values: None, max_size: Some(128), added: 2603, mode: MaxEncodedLen)
+	/// Storage: Balances Holds (r:1 w:1)
+	/// Proof: Balances Holds (max_values: None, max_size: Some(134), added: 2609, mode: MaxEncodedLen)
+	/// Storage: TrustlessFileServer HeldBalance (r:1 w:1)
+	/// Proof: TrustlessFileServer HeldBalance (max_values: Some(1), max_size: Some(16), added: 511, mode: MaxEncodedLen)
+	fn reject_root() -> Weight {
+		// Proof Size summary in bytes:
+		//  Measured:  `251`
+		//  Estimated: `3593`
+		// Minimum execution time: 19_000_000 picoseconds.
+		Weight::from_parts(20_000_000, 3593)
+			.saturating_add(RocksDbWeight::get().reads(4_u64))
+			.saturating_add(RocksDbWeight::get().writes(4_u64))
+	}
+	/// Storage: TrustlessFileServer Paused (r:1 w:0)
+	/// Proof: TrustlessFileServer Paused (max_values: Some(1), max_size: Some(1), added: 496, mode: MaxEncodedLen)
+	/// Storage: TrustlessFileServer FileOwners (r:1 w:0)
+	/// Proof: TrustlessFileServer FileOwners (max_values: None, max_size: Some(80), added: 2555, mode: MaxEncodedLen)
+	/// Storage: TrustlessFileServer Reports (r:1 w:1)
+	/// Proof: TrustlessFileServer Reports (max_values: None, max_size: Some(369), added: 2844, mode: MaxEncodedLen)
+	/// Storage: System Account (r:1 w:1)
+	/// Proof: System Account (max_values: None, max_size: Some(128), added: 2603, mode: MaxEncodedLen)
+	/// Storage: Balances Holds (r:1 w:1)
+	/// Proof: Balances Holds (max_values: None, max_size: Some(134), added: 2609, mode: MaxEncodedLen)
+	/// Storage: TrustlessFileServer HeldBalance (r:1 w:1)
+	/// Proof: TrustlessFileServer HeldBalance (max_values: Some(1), max_size: Some(16), added: 511, mode: MaxEncodedLen)
+	fn report_file() -> Weight {
+		// Proof Size summary in bytes:
+		//  Measured:  `412`
+		//  Estimated: `5613`
+		// Minimum execution time: 24_000_000 picoseconds.
+		Weight::from_parts(25_000_000, 5613)
+			.saturating_add(RocksDbWeight::get().reads(6_u64))
+			.saturating_add(RocksDbWeight::get().writes(4_u64))
+	}
+	/// Storage: TrustlessFileServer Paused (r:1 w:0)
+	/// Proof: TrustlessFileServer Paused (max_values: Some(1), max_size: Some(1), added: 496, mode: MaxEncodedLen)
+	/// Storage: TrustlessFileServer Reports (r:1 w:1)
+	/// Proof: TrustlessFileServer Reports (max_values: None, max_size: Some(369), added: 2844, mode: MaxEncodedLen)
+	/// Storage: TrustlessFileServer FileOwners (r:1 w:1)
+	/// Proof: TrustlessFileServer FileOwners (max_values: None, max_size: Some(80), added: 2555, mode: MaxEncodedLen)
+	/// Storage: TrustlessFileServer FileTrees (r:1 w:1)
+	/// Proof: TrustlessFileServer FileTrees (max_values: None, max_size: Some(2116), added: 4591, mode: MaxEncodedLen)
+	/// Storage: TrustlessFileServer FileMeta (r:0 w:1)
+	/// Proof: TrustlessFileServer FileMeta (max_values: None, max_size: Some(65), added: 2540, mode: MaxEncodedLen)
+	/// Storage: TrustlessFileServer Registrants (r:3 w:3)
+	/// Proof: TrustlessFileServer Registrants (max_values: None, max_size: Some(114), added: 2589, mode: MaxEncodedLen)
+	/// Storage: TrustlessFileServer Sponsors (r:3 w:3)
+	/// Proof: TrustlessFileServer Sponsors (max_values: None, max_size: Some(104), added: 2579, mode: MaxEncodedLen)
+	/// Storage: TrustlessFileServer Reputation (r:1 w:1)
+	/// Proof: TrustlessFileServer Reputation (max_values: None, max_size: Some(68), added: 2543, mode: MaxEncodedLen)
+	/// Storage: System Account (r:4 w:4)
+	/// Proof: System Account (max_values: None, max_size: Some(128), added: 2603, mode: MaxEncodedLen)
+	/// Storage: TrustlessFileServer AccountUsage (r:2 w:2)
+	/// Proof: TrustlessFileServer AccountUsage (max_values: None, max_size: Some(76), added: 2551, mode: MaxEncodedLen)
+	/// Storage: TrustlessFileServer TotalBytesStored (r:1 w:1)
+	/// Proof: TrustlessFileServer TotalBytesStored (max_values: Some(1), max_size: Some(8), added: 503, mode: MaxEncodedLen)
+	/// Storage: TrustlessFileServer Tombstones (r:0 w:1)
+	/// Proof: TrustlessFileServer Tombstones (max_values: None, max_size: Some(1125), added: 3600, mode: MaxEncodedLen)
+	/// Storage: TrustlessFileServer OrphanedChunks (r:0 w:1)
+	/// Proof: TrustlessFileServer OrphanedChunks (max_values: None, max_size: Some(48), added: 2523, mode: MaxEncodedLen)
+	/// Storage: Balances Holds (r:4 w:4)
+	/// Proof: Balances Holds (max_values: None, max_size: Some(134), added: 2609, mode: MaxEncodedLen)
+	/// Storage: TrustlessFileServer HeldBalance (r:1 w:1)
+	/// Proof: TrustlessFileServer HeldBalance (max_values: Some(1), max_size: Some(16), added: 511, mode: MaxEncodedLen)
+	/// Storage: TrustlessFileServer UploadedAt (r:0 w:1)
+	/// Proof: TrustlessFileServer UploadedAt (max_values: None, max_size: Some(60), added: 2535, mode: MaxEncodedLen)
+	/// Storage: TrustlessFileServer FrozenFiles (r:0 w:1)
+	/// Proof: TrustlessFileServer FrozenFiles (max_values: None, max_size: Some(48), added: 2523, mode: MaxEncodedLen)
+	/// Storage: TrustlessFileServer ManagementThresholds (r:0 w:1)
+	/// Proof: TrustlessFileServer ManagementThresholds (max_values: None, max_size: Some(50), added: 2525, mode: MaxEncodedLen)
+	/// The range of component `r` is `[1, 3]`.
+	/// The range of component `n` is `[1, 4]`.
+	fn resolve_report(r: u32, n: u32, ) -> Weight {
+		// Proof Size summary in bytes:
+		//  Measured:  `634 + r * (96 ±0) + n * (80 ±0)`
+		//  Estimated: `5613 + r * (2603 ±0) + n * (2603 ±0)`
+		// Minimum execution time: 41_000_000 picoseconds.
+		Weight::from_parts(38_412_000, 5613)
+			// Standard Error: 12_000
+			.saturating_add(Weight::from_parts(9_870_000, 0).saturating_mul(r.into()))
+			// Standard Error: 9_000
+			.saturating_add(Weight::from_parts(7_215_000, 0).saturating_mul(n.into()))
+			.saturating_add(RocksDbWeight::get().reads(21_u64))
+			.saturating_add(RocksDbWeight::get().reads((3_u64).saturating_mul(r.into())))
+			.saturating_add(RocksDbWeight::get().reads((3_u64).saturating_mul(n.into())))
+			.saturating_add(RocksDbWeight::get().writes(26_u64))
+			.saturating_add(RocksDbWeight::get().writes((3_u64).saturating_mul(r.into())))
+			.saturating_add(RocksDbWeight::get().writes((3_u64).saturating_mul(n.into())))
+			.saturating_add(Weight::from_parts(0, 2603).saturating_mul(r.into()))
+			.saturating_add(Weight::from_parts(0, 2603).saturating_mul(n.into()))
+	}
+	/// Storage: TrustlessFileServer Paused (r:1 w:0)
+	/// Proof: TrustlessFileServer Paused (max_values: Some(1), max_size: Some(1), added: 496, mode: MaxEncodedLen)
+	/// Storage: TrustlessFileServer Registrants (r:1 w:0)
+	/// Proof: TrustlessFileServer Registrants (max_values: None, max_size: Some(114), added: 2589, mode: MaxEncodedLen)
+	/// Storage: TrustlessFileServer ScheduledDeletions (r:1 w:1)
+	/// Proof: TrustlessFileServer ScheduledDeletions (max_values: None, max_size: Some(104), added: 2579, mode: MaxEncodedLen)
+	/// Storage: Scheduler Lookup (r:1 w:1)
+	/// Proof: Scheduler Lookup (max_values: None, max_size: Some(48), added: 2523, mode: MaxEncodedLen)
+	/// Storage: Scheduler Agenda (r:1 w:1)
+	/// Proof: Scheduler Agenda (max_values: None, max_size: Some(38963), added: 41438, mode: MaxEncodedLen)
+	fn schedule_deletion() -> Weight {
+		// Proof Size summary in bytes:
+		//  Measured:  `478`
+		//  Estimated: `42428`
+		// Minimum execution time: 31_000_000 picoseconds.
+		Weight::from_parts(32_000_000, 42428)
+			.saturating_add(RocksDbWeight::get().reads(5_u64))
+			.saturating_add(RocksDbWeight::get().writes(3_u64))
+	}
+	/// Storage: TrustlessFileServer Paused (r:1 w:0)
+	/// Proof: TrustlessFileServer Paused (max_values: Some(1), max_size: Some(1), added: 496, mode: MaxEncodedLen)
+	/// Storage: TrustlessFileServer ScheduledDeletions (r:1 w:1)
+	/// Proof: TrustlessFileServer ScheduledDeletions (max_values: None, max_size: Some(104), added: 2579, mode: MaxEncodedLen)
+	/// Storage: Scheduler Lookup (r:1 w:1)
+	/// Proof: Scheduler Lookup (max_values: None, max_size: Some(48), added: 2523, mode: MaxEncodedLen)
+	/// Storage: Scheduler Agenda (r:1 w:1)
+	/// Proof: Scheduler Agenda (max_values: None, max_size: Some(38963), added: 41438, mode: MaxEncodedLen)
+	fn cancel_deletion() -> Weight {
+		// Proof Size summary in bytes:
+		//  Measured:  `612`
+		//  Estimated: `42428`
+		// Minimum execution time: 27_000_000 picoseconds.
+		Weight::from_parts(28_000_000, 42428)
+			.saturating_add(RocksDbWeight::get().reads(4_u64))
+			.saturating_add(RocksDbWeight::get().writes(3_u64))
+	}
+	/// Storage: TrustlessFileServer Paused (r:1 w:0)
+	/// Proof: TrustlessFileServer Paused (max_values: Some(1), max_size: Some(1), added: 496, mode: MaxEncodedLen)
+	/// Storage: TrustlessFileServer PendingUploads (r:1 w:1)
+	/// Proof: TrustlessFileServer PendingUploads (max_values: None, max_size: Some(2379), added: 4854, mode: MaxEncodedLen)
+	/// Storage: TrustlessFileServer BlockUploads (r:1 w:1)
+	/// Proof: TrustlessFileServer BlockUploads (max_values: Some(1), max_size: Some(129), added: 624, mode: MaxEncodedLen)
+	/// Storage: TrustlessFileServer Reputation (r:1 w:1)
+	/// Proof: TrustlessFileServer Reputation (max_values: None, max_size: Some(68), added: 2543, mode: MaxEncodedLen)
+	/// Storage: TrustlessFileServer FileOwners (r:0 w:1)
+	/// Proof: TrustlessFileServer FileOwners (max_values: None, max_size: Some(80), added: 2555, mode: MaxEncodedLen)
+	/// Storage: TrustlessFileServer FileTrees (r:0 w:1)
+	/// Proof: TrustlessFileServer FileTrees (max_values: None, max_size: Some(2116), added: 4591, mode: MaxEncodedLen)
+	/// Storage: TrustlessFileServer FileMeta (r:0 w:1)
+	/// Proof: TrustlessFileServer FileMeta (max_values: None, max_size: Some(65), added: 2540, mode: MaxEncodedLen)
+	/// Storage: TrustlessFileServer Registrants (r:0 w:1)
+	/// Proof: TrustlessFileServer Registrants (max_values: None, max_size: Some(114), added: 2589, mode: MaxEncodedLen)
+	/// Storage: TrustlessFileServer RegistrantCount (r:0 w:1)
+	/// Proof: TrustlessFileServer RegistrantCount (max_values: None, max_size: Some(52), added: 2527, mode: MaxEncodedLen)
+	/// Storage: TrustlessFileServer Checksums (r:0 w:1)
+	/// Proof: TrustlessFileServer Checksums (max_values: None, max_size: Some(80), added: 2555, mode: MaxEncodedLen)
+	/// Storage: TrustlessFileServer AccountUsage (r:2 w:2)
+	/// Proof: TrustlessFileServer AccountUsage (max_values: None, max_size: Some(76), added: 2551, mode: MaxEncodedLen)
+	/// Storage: TrustlessFileServer TotalBytesStored (r:1 w:1)
+	/// Proof: TrustlessFileServer TotalBytesStored (max_values: Some(1), max_size: Some(8), added: 503, mode: MaxEncodedLen)
+	/// Storage: Timestamp Now (r:1 w:0)
+	/// Proof: Timestamp Now (max_values: Some(1), max_size: Some(8), added: 503, mode: MaxEncodedLen)
+	/// Storage: TrustlessFileServer UploadedAt (r:0 w:1)
+	/// Proof: TrustlessFileServer UploadedAt (max_values: None, max_size: Some(60), added: 2535, mode: MaxEncodedLen)
+	fn approve_large_upload() -> Weight {
+		// Proof Size summary in bytes:
+		//  Measured:  `1384`
+		//  Estimated: `5844`
+		// Minimum execution time: 34_000_000 picoseconds.
+		Weight::from_parts(36_000_000, 5844)
+			.saturating_add(RocksDbWeight::get().reads(8_u64))
+			.saturating_add(RocksDbWeight::get().writes(14_u64))
+	}
+	/// Storage: TrustlessFileServer Paused (r:1 w:0)
+	/// Proof: TrustlessFileServer Paused (max_values: Some(1), max_size: Some(1), added: 496, mode: MaxEncodedLen)
+	/// Storage: TrustlessFileServer PendingUploads (r:1 w:1)
+	/// Proof: TrustlessFileServer PendingUploads (max_values: None, max_size: Some(2379), added: 4854, mode: MaxEncodedLen)
+	/// Storage: System Account (r:1 w:1)
+	/// Proof: System Account (max_values: None, max_size: Some(128), added: 2603, mode: MaxEncodedLen)
+	/// Storage: Balances Holds (r:1 w:1)
+	/// Proof: Balances Holds (max_values: None, max_size: Some(134), added: 2609, mode: MaxEncodedLen)
+	/// Storage: TrustlessFileServer HeldBalance (r:1 w:1)
+	/// Proof: TrustlessFileServer HeldBalance (max_values: Some(1), max_size: Some(16), added: 511, mode: MaxEncodedLen)
+	fn reject_large_upload() -> Weight {
+		// Proof Size summary in bytes:
+		//  Measured:  `1384`
+		//  Estimated: `5844`
+		// Minimum execution time: 26_000_000 picoseconds.
+		Weight::from_parts(27_000_000, 5844)
+			.saturating_add(RocksDbWeight::get().reads(5_u64))
+			.saturating_add(RocksDbWeight::get().writes(4_u64))
+	}
+	/// Storage: TrustlessFileServer Paused (r:1 w:0)
+	/// Proof: TrustlessFileServer Paused (max_values: Some(1), max_size: Some(1), added: 496, mode: MaxEncodedLen)
+	/// Storage: TrustlessFileServer FileOwners (r:1 w:0)
+	/// Proof: TrustlessFileServer FileOwners (max_values: None, max_size: Some(80), added: 2555, mode: MaxEncodedLen)
+	/// Storage: TrustlessFileServer FileMeta (r:1 w:0)
+	/// Proof: TrustlessFileServer FileMeta (max_values: None, max_size: Some(65), added: 2540, mode: MaxEncodedLen)
+	/// Storage: TrustlessFileServer Registrants (r:1 w:0)
+	/// Proof: TrustlessFileServer Registrants (max_values: None, max_size: Some(114), added: 2589, mode: MaxEncodedLen)
+	/// Storage: ParachainSystem HostConfiguration (r:1 w:0)
+	/// Proof Skipped: ParachainSystem HostConfiguration (max_values: Some(1), max_size: None, mode: Measured)
+	/// Storage: XcmpQueue OutboundXcmpStatus (r:1 w:1)
+	/// Proof Skipped: XcmpQueue OutboundXcmpStatus (max_values: Some(1), max_size: None, mode: Measured)
+	/// Storage: XcmpQueue OutboundXcmpMessages (r:0 w:1)
+	/// Proof Skipped: XcmpQueue OutboundXcmpMessages (max_values: None, max_size: None, mode: Measured)
+	fn attest_file() -> Weight {
+		// Proof Size summary in bytes:
+		//  Measured:  `1187`
+		//  Estimated: `5613`
+		// Minimum execution time: 38_000_000 picoseconds.
+		Weight::from_parts(40_000_000, 5613)
+			.saturating_add(RocksDbWeight::get().reads(6_u64))
+			.saturating_add(RocksDbWeight::get().writes(2_u64))
+	}
+	/// Storage: TrustlessFileServer ExternalFiles (r:0 w:1)
+	/// Proof: TrustlessFileServer ExternalFiles (max_values: None, max_size: Some(100), added: 2575, mode: MaxEncodedLen)
+	fn register_external_file() -> Weight {
+		// Proof Size summary in bytes:
+		//  Measured:  `0`
+		//  Estimated: `0`
+		// Minimum execution time: 14_000_000 picoseconds.
+		Weight::from_parts(15_000_000, 0)
+			.saturating_add(RocksDbWeight::get().writes(1_u64))
+	}
+	/// Storage: TrustlessFileServer FileOwners (r:1 w:0)
+	/// Proof: TrustlessFileServer FileOwners (max_values: None, max_size: Some(80), added: 2555, mode: MaxEncodedLen)
+	/// Storage: TrustlessFileServer ServedBandwidth (r:1 w:1)
+	/// Proof: TrustlessFileServer ServedBandwidth (max_values: None, max_size: Some(64), added: 2539, mode: MaxEncodedLen)
+	fn report_bandwidth() -> Weight {
+		// Proof Size summary in bytes:
+		//  Measured:  `2198`
+		//  Estimated: `5613`
+		// Minimum execution time: 15_000_000 picoseconds.
+		Weight::from_parts(16_000_000, 5613)
+			.saturating_add(RocksDbWeight::get().reads(2_u64))
+			.saturating_add(RocksDbWeight::get().writes(1_u64))
+	}
+	/// Storage: TrustlessFileServer FileMeta (r:1 w:0)
+	/// Proof: TrustlessFileServer FileMeta (max_values: None, max_size: Some(65), added: 2540, mode: MaxEncodedLen)
+	/// Storage: TrustlessFileServer AuditSample (r:1 w:1)
+	/// Proof: TrustlessFileServer AuditSample (max_values: Some(1), max_size: Some(641), added: 1136, mode: MaxEncodedLen)
+	/// Storage: TrustlessFileServer PieceAudits (r:0 w:1)
+	/// Proof: TrustlessFileServer PieceAudits (max_values: None, max_size: Some(61), added: 2536, mode: MaxEncodedLen)
+	fn report_audit() -> Weight {
+		// Proof Size summary in bytes:
+		//  Measured:  `837`
+		//  Estimated: `5666`
+		// Minimum execution time: 15_000_000 picoseconds.
+		Weight::from_parts(16_000_000, 5666)
+			.saturating_add(RocksDbWeight::get().reads(2_u64))
+			.saturating_add(RocksDbWeight::get().writes(2_u64))
+	}
+	/// Storage: TrustlessFileServer OcwAuthorities (r:0 w:1)
+	/// Proof: TrustlessFileServer OcwAuthorities (max_values: Some(1), max_size: Some(129), added: 624, mode: MaxEncodedLen)
+	/// The range of component `n` is `[1, 4]`.
+	fn set_ocw_authorities(n: u32, ) -> Weight {
+		// Proof Size summary in bytes:
+		//  Measured:  `6`
+		//  Estimated: `0`
+		// Minimum execution time: 10_000_000 picoseconds.
+		Weight::from_parts(10_412_000, 0)
+			// Standard Error: 3_000
+			.saturating_add(Weight::from_parts(96_000, 0).saturating_mul(n.into()))
+			.saturating_add(RocksDbWeight::get().writes(1_u64))
+	}
+	/// Storage: TrustlessFileServer Paused (r:1 w:0)
+	/// Proof: TrustlessFileServer Paused (max_values: Some(1), max_size: Some(1), added: 496, mode: MaxEncodedLen)
+	/// Storage: TrustlessFileServer FileOwners (r:1 w:0)
+	/// Proof: TrustlessFileServer FileOwners (max_values: None, max_size: Some(80), added: 2555, mode: MaxEncodedLen)
+	/// Storage: TrustlessFileServer Metadata (r:1 w:1)
+	/// Proof: TrustlessFileServer Metadata (max_values: None, max_size: Some(761), added: 3236, mode: MaxEncodedLen)
+	/// Storage: TrustlessFileServer FileHistory (r:1 w:1)
+	/// Proof: TrustlessFileServer FileHistory (max_values: None, max_size: Some(3266), added: 5741, mode: MaxEncodedLen)
+	fn set_retrieval_price() -> Weight {
+		// Proof Size summary in bytes:
+		//  Measured:  `2214`
+		//  Estimated: `6731`
+		// Minimum execution time: 18_000_000 picoseconds.
+		Weight::from_parts(19_000_000, 6731)
+			.saturating_add(RocksDbWeight::get().reads(4_u64))
+			.saturating_add(RocksDbWeight::get().writes(2_u64))
+	}
+	/// Storage: TrustlessFileServer Paused (r:1 w:0)
+	/// Proof: TrustlessFileServer Paused (max_values: Some(1), max_size: Some(1), added: 496, mode: MaxEncodedLen)
+	/// Storage: System Account (r:1 w:1)
+	/// Proof: System Account (max_values: None, max_size: Some(128), added: 2603, mode: MaxEncodedLen)
+	/// Storage: TrustlessFileServer NextCollectionId (r:1 w:1)
+	/// Proof: TrustlessFileServer NextCollectionId (max_values: Some(1), max_size: Some(8), added: 503, mode: MaxEncodedLen)
+	/// Storage: TrustlessFileServer Collections (r:0 w:1)
+	/// Proof: TrustlessFileServer Collections (max_values: None, max_size: Some(2106), added: 4581, mode: MaxEncodedLen)
+	/// Storage: Balances Holds (r:1 w:1)
+	/// Proof: Balances Holds (max_values: None, max_size: Some(134), added: 2609, mode: MaxEncodedLen)
+	/// Storage: TrustlessFileServer HeldBalance (r:1 w:1)
+	/// Proof: TrustlessFileServer HeldBalance (max_values: Some(1), max_size: Some(16), added: 511, mode: MaxEncodedLen)
+	fn create_collection() -> Weight {
+		// Proof Size summary in bytes:
+		//  Measured:  `172`
+		//  Estimated: `3593`
+		// Minimum execution time: 22_000_000 picoseconds.
+		Weight::from_parts(23_000_000, 3593)
+			.saturating_add(RocksDbWeight::get().reads(5_u64))
+			.saturating_add(RocksDbWeight::get().writes(5_u64))
+	}
+	/// Storage: TrustlessFileServer Paused (r:1 w:0)
+	/// Proof: TrustlessFileServer Paused (max_values: Some(1), max_size: Some(1), added: 496, mode: MaxEncodedLen)
+	/// Storage: TrustlessFileServer Collections (r:1 w:1)
+	/// Proof: TrustlessFileServer Collections (max_values: None, max_size: Some(2106), added: 4581, mode: MaxEncodedLen)
+	/// Storage: TrustlessFileServer FileOwners (r:1 w:0)
+	/// Proof: TrustlessFileServer FileOwners (max_values: None, max_size: Some(80), added: 2555, mode: MaxEncodedLen)
+	fn add_to_collection() -> Weight {
+		// Proof Size summary in bytes:
+		//  Measured:  `2371`
+		//  Estimated: `5571`
+		// Minimum execution time: 19_000_000 picoseconds.
+		Weight::from_parts(20_000_000, 5571)
+			.saturating_add(RocksDbWeight::get().reads(3_u64))
+			.saturating_add(RocksDbWeight::get().writes(1_u64))
+	}
+	/// Storage: TrustlessFileServer Paused (r:1 w:0)
+	/// Proof: TrustlessFileServer Paused (max_values: Some(1), max_size: Some(1), added: 496, mode: MaxEncodedLen)
+	/// Storage: TrustlessFileServer Collections (r:1 w:1)
+	/// Proof: TrustlessFileServer Collections (max_values: None, max_size: Some(2106), added: 4581, mode: MaxEncodedLen)
+	fn remove_from_collection() -> Weight {
+		// Proof Size summary in bytes:
+		//  Measured:  `2218`
+		//  Estimated: `5571`
+		// Minimum execution time: 14_000_000 picoseconds.
+		Weight::from_parts(15_000_000, 5571)
+			.saturating_add(RocksDbWeight::get().reads(2_u64))
+			.saturating_add(RocksDbWeight::get().writes(1_u64))
+	}
+	/// Storage: TrustlessFileServer Paused (r:1 w:0)
+	/// Proof: TrustlessFileServer Paused (max_values: Some(1), max_size: Some(1), added: 496, mode: MaxEncodedLen)
+	/// Storage: TrustlessFileServer Collections (r:1 w:0)
+	/// Proof: TrustlessFileServer Collections (max_values: None, max_size: Some(2106), added: 4581, mode: MaxEncodedLen)
+	/// Storage: TrustlessFileServer FileOwners (r:64 w:0)
+	/// Proof: TrustlessFileServer FileOwners (max_values: None, max_size: Some(80), added: 2555, mode: MaxEncodedLen)
+	/// Storage: TrustlessFileServer Metadata (r:64 w:64)
+	/// Proof: TrustlessFileServer Metadata (max_values: None, max_size: Some(761), added: 3236, mode: MaxEncodedLen)
+	/// Storage: TrustlessFileServer FileHistory (r:64 w:64)
+	/// Proof: TrustlessFileServer FileHistory (max_values: None, max_size: Some(3266), added: 5741, mode: MaxEncodedLen)
+	/// The range of component `n` is `[1, 64]`.
+	fn set_collection_price(n: u32, ) -> Weight {
+		// Proof Size summary in bytes:
+		//  Measured:  `2371 + n * (2214 ±0)`
+		//  Estimated: `5571 + n * (5741 ±0)`
+		// Minimum execution time: 29_000_000 picoseconds.
+		Weight::from_parts(12_104_000, 5571)
+			// Standard Error: 11_000
+			.saturating_add(Weight::from_parts(16_870_000, 0).saturating_mul(n.into()))
+			.saturating_add(RocksDbWeight::get().reads(2_u64))
+			.saturating_add(RocksDbWeight::get().reads((3_u64).saturating_mul(n.into())))
+			.saturating_add(RocksDbWeight::get().writes((2_u64).saturating_mul(n.into())))
+			.saturating_add(Weight::from_parts(0, 5741).saturating_mul(n.into()))
+	}
+	/// Storage: TrustlessFileServer Paused (r:1 w:0)
+	/// Proof: TrustlessFileServer Paused (max_values: Some(1), max_size: Some(1), added: 496, mode: MaxEncodedLen)
+	/// Storage: TrustlessFileServer Collections (r:1 w:1)
+	/// Proof: TrustlessFileServer Collections (max_values: None, max_size: Some(2106), added: 4581, mode: MaxEncodedLen)
+	/// Storage: TrustlessFileServer FileOwners (r:64 w:64)
+	/// Proof: TrustlessFileServer FileOwners (max_values: None, max_size: Some(80), added: 2555, mode: MaxEncodedLen)
+	/// Storage: TrustlessFileServer Registrants (r:64 w:0)
+	/// Proof: TrustlessFileServer Registrants (max_values: None, max_size: Some(114), added: 2589, mode: MaxEncodedLen)
+	/// Storage: System Account (r:2 w:2)
+	/// Proof: System Account (max_values: None, max_size: Some(128), added: 2603, mode: MaxEncodedLen)
+	/// Storage: TrustlessFileServer FileHistory (r:64 w:64)
+	/// Proof: TrustlessFileServer FileHistory (max_values: None, max_size: Some(3266), added: 5741, mode: MaxEncodedLen)
+	/// Storage: Balances Holds (r:2 w:2)
+	/// Proof: Balances Holds (max_values: None, max_size: Some(134), added: 2609, mode: MaxEncodedLen)
+	/// Storage: TrustlessFileServer ManagementThresholds (r:0 w:64)
+	/// Proof: TrustlessFileServer ManagementThresholds (max_values: None, max_size: Some(50), added: 2525, mode: MaxEncodedLen)
+	/// The range of component `n` is `[1, 64]`.
+	fn transfer_collection(n: u32, ) -> Weight {
+		// Proof Size summary in bytes:
+		//  Measured:  `2580 + n * (2310 ±0)`
+		//  Estimated: `6196 + n * (5741 ±0)`
+		// Minimum execution time: 47_000_000 picoseconds.
+		Weight::from_parts(30_511_000, 6196)
+			// Standard Error: 14_000
+			.saturating_add(Weight::from_parts(19_432_000, 0).saturating_mul(n.into()))
+			.saturating_add(RocksDbWeight::get().reads(6_u64))
+			.saturating_add(RocksDbWeight::get().reads((3_u64).saturating_mul(n.into())))
+			.saturating_add(RocksDbWeight::get().writes(5_u64))
+			.saturating_add(RocksDbWeight::get().writes((3_u64).saturating_mul(n.into())))
+			.saturating_add(Weight::from_parts(0, 5741).saturating_mul(n.into()))
+	}
+	/// Storage: TrustlessFileServer Paused (r:1 w:0)
+	/// Proof: TrustlessFileServer Paused (max_values: Some(1), max_size: Some(1), added: 496, mode: MaxEncodedLen)
+	/// Storage: TrustlessFileServer Collections (r:1 w:1)
+	/// Proof: TrustlessFileServer Collections (max_values: None, max_size: Some(2106), added: 4581, mode: MaxEncodedLen)
+	/// Storage: System Account (r:1 w:1)
+	/// Proof: System Account (max_values: None, max_size: Some(128), added: 2603, mode: MaxEncodedLen)
+	/// Storage: Balances Holds (r:1 w:1)
+	/// Proof: Balances Holds (max_values: None, max_size: Some(134), added: 2609, mode: MaxEncodedLen)
+	/// Storage: TrustlessFileServer HeldBalance (r:1 w:1)
+	/// Proof: TrustlessFileServer HeldBalance (max_values: Some(1), max_size: Some(16), added: 511, mode: MaxEncodedLen)
+	fn remove_collection() -> Weight {
+		// Proof Size summary in bytes:
+		//  Measured:  `2372`
+		//  Estimated: `5571`
+		// Minimum execution time: 21_000_000 picoseconds.
+		Weight::from_parts(22_000_000, 5571)
+			.saturating_add(RocksDbWeight::get().reads(5_u64))
+			.saturating_add(RocksDbWeight::get().writes(4_u64))
+	}
+	/// Storage: TrustlessFileServer OrphanedChunks (r:2 w:1)
+	/// Proof: TrustlessFileServer OrphanedChunks (max_values: None, max_size: Some(48), added: 2523, mode: MaxEncodedLen)
+	/// Storage: TrustlessFileServer FileOwners (r:1 w:0)
+	/// Proof: TrustlessFileServer FileOwners (max_values: None, max_size: Some(80), added: 2555, mode: MaxEncodedLen)
+	/// Storage: TrustlessFileServer ChunkAllocations (r:1000 w:1000)
+	/// Proof: TrustlessFileServer ChunkAllocations (max_values: None, max_size: Some(249), added: 2724, mode: MaxEncodedLen)
+	/// The range of component `n` is `[0, 1000]`.
+	fn reclaim_chunks(n: u32, ) -> Weight {
+		// Proof Size summary in bytes:
+		//  Measured:  `212 + n * (70 ±0)`
+		//  Estimated: `5036 + n * (2724 ±0)`
+		// Minimum execution time: 11_000_000 picoseconds.
+		Weight::from_parts(11_482_000, 5036)
+			// Standard Error: 2_000
+			.saturating_add(Weight::from_parts(4_215_000, 0).saturating_mul(n.into()))
+			.saturating_add(RocksDbWeight::get().reads(3_u64))
+			.saturating_add(RocksDbWeight::get().reads((1_u64).saturating_mul(n.into())))
+			.saturating_add(RocksDbWeight::get().writes(1_u64))
+			.saturating_add(RocksDbWeight::get().writes((1_u64).saturating_mul(n.into())))
+			.saturating_add(Weight::from_parts(0, 2724).saturating_mul(n.into()))
+	}
+	/// Storage: TrustlessFileServer Paused (r:1 w:0)
+	/// Proof: TrustlessFileServer Paused (max_values: Some(1), max_size: Some(1), added: 496, mode: MaxEncodedLen)
+	/// Storage: TrustlessFileServer PendingReview (r:1 w:1)
+	/// Proof: TrustlessFileServer PendingReview (max_values: None, max_size: Some(2379), added: 4854, mode: MaxEncodedLen)
+	/// Storage: TrustlessFileServer BlockUploads (r:1 w:1)
+	/// Proof: TrustlessFileServer BlockUploads (max_values: Some(1), max_size: Some(129), added: 624, mode: MaxEncodedLen)
+	/// Storage: TrustlessFileServer Reputation (r:1 w:1)
+	/// Proof: TrustlessFileServer Reputation (max_values: None, max_size: Some(68), added: 2543, mode: MaxEncodedLen)
+	/// Storage: TrustlessFileServer FileOwners (r:0 w:1)
+	/// Proof: TrustlessFileServer FileOwners (max_values: None, max_size: Some(80), added: 2555, mode: MaxEncodedLen)
+	/// Storage: TrustlessFileServer FileTrees (r:0 w:1)
+	/// Proof: TrustlessFileServer FileTrees (max_values: None, max_size: Some(2116), added: 4591, mode: MaxEncodedLen)
+	/// Storage: TrustlessFileServer FileMeta (r:0 w:1)
+	/// Proof: TrustlessFileServer FileMeta (max_values: None, max_size: Some(65), added: 2540, mode: MaxEncodedLen)
+	/// Storage: TrustlessFileServer Registrants (r:0 w:1)
+	/// Proof: TrustlessFileServer Registrants (max_values: None, max_size: Some(114), added: 2589, mode: MaxEncodedLen)
+	/// Storage: TrustlessFileServer RegistrantCount (r:0 w:1)
+	/// Proof: TrustlessFileServer RegistrantCount (max_values: None, max_size: Some(52), added: 2527, mode: MaxEncodedLen)
+	/// Storage: TrustlessFileServer Checksums (r:0 w:1)
+	/// Proof: TrustlessFileServer Checksums (max_values: None, max_size: Some(80), added: 2555, mode: MaxEncodedLen)
+	/// Storage: TrustlessFileServer AccountUsage (r:2 w:2)
+	/// Proof: TrustlessFileServer AccountUsage (max_values: None, max_size: Some(76), added: 2551, mode: MaxEncodedLen)
+	/// Storage: TrustlessFileServer TotalBytesStored (r:1 w:1)
+	/// Proof: TrustlessFileServer TotalBytesStored (max_values: Some(1), max_size: Some(8), added: 503, mode: MaxEncodedLen)
+	/// Storage: Timestamp Now (r:1 w:0)
+	/// Proof: Timestamp Now (max_values: Some(1), max_size: Some(8), added: 503, mode: MaxEncodedLen)
+	/// Storage: TrustlessFileServer UploadedAt (r:0 w:1)
+	/// Proof: TrustlessFileServer UploadedAt (max_values: None, max_size: Some(60), added: 2535, mode: MaxEncodedLen)
+	fn approve_review() -> Weight {
+		// Proof Size summary in bytes:
+		//  Measured:  `1384`
+		//  Estimated: `5844`
+		// Minimum execution time: 34_000_000 picoseconds.
+		Weight::from_parts(36_000_000, 5844)
+			.saturating_add(RocksDbWeight::get().reads(8_u64))
+			.saturating_add(RocksDbWeight::get().writes(14_u64))
+	}
+	/// Storage: TrustlessFileServer Paused (r:1 w:0)
+	/// Proof: TrustlessFileServer Paused (max_values: Some(1), max_size: Some(1), added: 496, mode: MaxEncodedLen)
+	/// Storage: TrustlessFileServer PendingReview (r:1 w:1)
+	/// Proof: TrustlessFileServer PendingReview (max_values: None, max_size: Some(2379), added: 4854, mode: MaxEncodedLen)
+	/// Storage: System Account (r:1 w:1)
+	/// Proof: System Account (max_values: None, max_size: Some(128), added: 2603, mode: MaxEncodedLen)
+	/// Storage: Balances Holds (r:1 w:1)
+	/// Proof: Balances Holds (max_values: None, max_size: Some(134), added: 2609, mode: MaxEncodedLen)
+	/// Storage: TrustlessFileServer HeldBalance (r:1 w:1)
+	/// Proof: TrustlessFileServer HeldBalance (max_values: Some(1), max_size: Some(16), added: 511, mode: MaxEncodedLen)
+	fn reject_review() -> Weight {
+		// Proof Size summary in bytes:
+		//  Measured:  `1384`
+		//  Estimated: `5844`
+		// Minimum execution time: 26_000_000 picoseconds.
+		Weight::from_parts(27_000_000, 5844)
+			.saturating_add(RocksDbWeight::get().reads(5_u64))
+			.saturating_add(RocksDbWeight::get().writes(4_u64))
+	}
+	/// Storage: TrustlessFileServer Paused (r:1 w:0)
+	/// Proof: TrustlessFileServer Paused (max_values: Some(1), max_size: Some(1), added: 496, mode: MaxEncodedLen)
+	/// Storage: TrustlessFileServer FileOwners (r:1 w:0)
+	/// Proof: TrustlessFileServer FileOwners (max_values: None, max_size: Some(80), added: 2555, mode: MaxEncodedLen)
+	/// Storage: TrustlessFileServer FrozenFiles (r:1 w:1)
+	/// Proof: TrustlessFileServer FrozenFiles (max_values: None, max_size: Some(48), added: 2523, mode: MaxEncodedLen)
+	fn freeze_file() -> Weight {
+		// Proof Size summary in bytes:
+		//  Measured:  `316`
+		//  Estimated: `3545`
+		// Minimum execution time: 13_000_000 picoseconds.
+		Weight::from_parts(14_000_000, 3545)
+			.saturating_add(RocksDbWeight::get().reads(3_u64))
+			.saturating_add(RocksDbWeight::get().writes(1_u64))
+	}
+	/// Storage: TrustlessFileServer Paused (r:1 w:0)
+	/// Proof: TrustlessFileServer Paused (max_values: Some(1), max_size: Some(1), added: 496, mode: MaxEncodedLen)
+	/// Storage: TrustlessFileServer FileOwners (r:1 w:0)
+	/// Proof: TrustlessFileServer FileOwners (max_values: None, max_size: Some(80), added: 2555, mode: MaxEncodedLen)
+	/// Storage: TrustlessFileServer FrozenFiles (r:1 w:1)
+	/// Proof: TrustlessFileServer FrozenFiles (max_values: None, max_size: Some(48), added: 2523, mode: MaxEncodedLen)
+	fn unfreeze_file() -> Weight {
+		// Proof Size summary in bytes:
+		//  Measured:  `316`
+		//  Estimated: `3545`
+		// Minimum execution time: 13_000_000 picoseconds.
+		Weight::from_parts(14_000_000, 3545)
+			.saturating_add(RocksDbWeight::get().reads(3_u64))
+			.saturating_add(RocksDbWeight::get().writes(1_u64))
+	}
+	/// Storage: TrustlessFileServer Paused (r:1 w:0)
+	/// Proof: TrustlessFileServer Paused (max_values: Some(1), max_size: Some(1), added: 496, mode: MaxEncodedLen)
+	/// Storage: TrustlessFileServer FileOwners (r:1 w:1)
+	/// Proof: TrustlessFileServer FileOwners (max_values: None, max_size: Some(80), added: 2555, mode: MaxEncodedLen)
+	/// Storage: TrustlessFileServer Registrants (r:2 w:2)
+	/// Proof: TrustlessFileServer Registrants (max_values: None, max_size: Some(114), added: 2589, mode: MaxEncodedLen)
+	/// Storage: TrustlessFileServer FileMeta (r:1 w:0)
+	/// Proof: TrustlessFileServer FileMeta (max_values: None, max_size: Some(65), added: 2540, mode: MaxEncodedLen)
+	/// Storage: TrustlessFileServer Sponsors (r:1 w:2)
+	/// Proof: TrustlessFileServer Sponsors (max_values: None, max_size: Some(128), added: 2603, mode: MaxEncodedLen)
+	/// Storage: TrustlessFileServer ScheduledDeletions (r:1 w:0)
+	/// Proof: TrustlessFileServer ScheduledDeletions (max_values: None, max_size: Some(104), added: 2579, mode: MaxEncodedLen)
+	/// Storage: TrustlessFileServer AccountUsage (r:2 w:2)
+	/// Proof: TrustlessFileServer AccountUsage (max_values: None, max_size: Some(76), added: 2551, mode: MaxEncodedLen)
+	/// Storage: TrustlessFileServer FileHistory (r:1 w:1)
+	/// Proof: TrustlessFileServer FileHistory (max_values: None, max_size: Some(3266), added: 5741, mode: MaxEncodedLen)
+	/// Storage: TrustlessFileServer ManagementThresholds (r:0 w:1)
+	/// Proof: TrustlessFileServer ManagementThresholds (max_values: None, max_size: Some(50), added: 2525, mode: MaxEncodedLen)
+	fn transfer_file() -> Weight {
+		// Proof Size summary in bytes:
+		//  Measured:  `2532`
+		//  Estimated: `7139`
+		// Minimum execution time: 41_000_000 picoseconds.
+		Weight::from_parts(43_000_000, 7139)
+			.saturating_add(RocksDbWeight::get().reads(11_u64))
+			.saturating_add(RocksDbWeight::get().writes(9_u64))
+	}
+	/// Storage: TrustlessFileServer Paused (r:1 w:0)
+	/// Proof: TrustlessFileServer Paused (max_values: Some(1), max_size: Some(1), added: 496, mode: MaxEncodedLen)
+	/// Storage: TrustlessFileServer FileOwners (r:1 w:0)
+	/// Proof: TrustlessFileServer FileOwners (max_values: None, max_size: Some(80), added: 2555, mode: MaxEncodedLen)
+	/// Storage: TrustlessFileServer ManagementThresholds (r:0 w:1)
+	/// Proof: TrustlessFileServer ManagementThresholds (max_values: None, max_size: Some(50), added: 2525, mode: MaxEncodedLen)
+	fn set_management_threshold() -> Weight {
+		// Proof Size summary in bytes:
+		//  Measured:  `316`
+		//  Estimated: `3545`
+		// Minimum execution time: 12_000_000 picoseconds.
+		Weight::from_parts(13_000_000, 3545)
+			.saturating_add(RocksDbWeight::get().reads(2_u64))
+			.saturating_add(RocksDbWeight::get().writes(1_u64))
+	}
+	/// Storage: TrustlessFileServer Paused (r:1 w:0)
+	/// Proof: TrustlessFileServer Paused (max_values: Some(1), max_size: Some(1), added: 496, mode: MaxEncodedLen)
+	/// Storage: TrustlessFileServer Names (r:1 w:1)
+	/// Proof: TrustlessFileServer Names (max_values: None, max_size: Some(358), added: 2833, mode: MaxEncodedLen)
+	/// Storage: System Account (r:1 w:1)
+	/// Proof: System Account (max_values: None, max_size: Some(128), added: 2603, mode: MaxEncodedLen)
+	/// Storage: Balances Holds (r:1 w:1)
+	/// Proof: Balances Holds (max_values: None, max_size: Some(134), added: 2609, mode: MaxEncodedLen)
+	/// Storage: TrustlessFileServer HeldBalance (r:1 w:1)
+	/// Proof: TrustlessFileServer HeldBalance (max_values: Some(1), max_size: Some(16), added: 511, mode: MaxEncodedLen)
+	fn renew_alias() -> Weight {
+		// Proof Size summary in bytes:
+		//  Measured:  `252`
+		//  Estimated: `3823`
+		// Minimum execution time: 18_000_000 picoseconds.
+		Weight::from_parts(19_000_000, 3823)
+			.saturating_add(RocksDbWeight::get().reads(5_u64))
+			.saturating_add(RocksDbWeight::get().writes(4_u64))
+	}
+	/// Storage: TrustlessFileServer Paused (r:1 w:0)
+	/// Proof: TrustlessFileServer Paused (max_values: Some(1), max_size: Some(1), added: 496, mode: MaxEncodedLen)
+	/// Storage: TrustlessFileServer Names (r:1 w:1)
+	/// Proof: TrustlessFileServer Names (max_values: None, max_size: Some(358), added: 2833, mode: MaxEncodedLen)
+	/// Storage: System Account (r:1 w:1)
+	/// Proof: System Account (max_values: None, max_size: Some(128), added: 2603, mode: MaxEncodedLen)
+	/// Storage: Balances Holds (r:1 w:1)
+	/// Proof: Balances Holds (max_values: None, max_size: Some(134), added: 2609, mode: MaxEncodedLen)
+	/// Storage: Balances TotalIssuance (r:1 w:1)
+	/// Proof: Balances TotalIssuance (max_values: Some(1), max_size: Some(16), added: 511, mode: MaxEncodedLen)
+	/// Storage: TrustlessFileServer HeldBalance (r:1 w:1)
+	/// Proof: TrustlessFileServer HeldBalance (max_values: Some(1), max_size: Some(16), added: 511, mode: MaxEncodedLen)
+	fn reassign_alias() -> Weight {
+		// Proof Size summary in bytes:
+		//  Measured:  `252`
+		//  Estimated: `3823`
+		// Minimum execution time: 20_000_000 picoseconds.
+		Weight::from_parts(21_000_000, 3823)
+			.saturating_add(RocksDbWeight::get().reads(6_u64))
+			.saturating_add(RocksDbWeight::get().writes(5_u64))
+	}
+	/// Storage: TrustlessFileServer Paused (r:1 w:0)
+	/// Proof: TrustlessFileServer Paused (max_values: Some(1), max_size: Some(1), added: 496, mode: MaxEncodedLen)
+	/// Storage: TrustlessFileServer RootClaims (r:1 w:1)
+	/// Proof: TrustlessFileServer RootClaims (max_values: None, max_size: Some(68), added: 2543, mode: MaxEncodedLen)
+	/// Storage: System Account (r:1 w:1)
+	/// Proof: System Account (max_values: None, max_size: Some(128), added: 2603, mode: MaxEncodedLen)
+	/// Storage: Balances Holds (r:1 w:1)
+	/// Proof: Balances Holds (max_values: None, max_size: Some(134), added: 2609, mode: MaxEncodedLen)
+	/// Storage: TrustlessFileServer HeldBalance (r:1 w:1)
+	/// Proof: TrustlessFileServer HeldBalance (max_values: Some(1), max_size: Some(16), added: 511, mode: MaxEncodedLen)
+	fn cancel_root_claim() -> Weight {
+		// Proof Size summary in bytes:
+		//  Measured:  `301`
+		//  Estimated: `3599`
+		// Minimum execution time: 19_000_000 picoseconds.
+		Weight::from_parts(20_000_000, 3599)
+			.saturating_add(RocksDbWeight::get().reads(5_u64))
+			.saturating_add(RocksDbWeight::get().writes(4_u64))
+	}
+	/// Storage: TrustlessFileServer OcwAuthorities (r:1 w:0)
+	/// Proof: TrustlessFileServer OcwAuthorities (max_values: Some(1), max_size: Some(129), added: 624, mode: MaxEncodedLen)
+	/// Storage: TrustlessFileServer PendingRechunks (r:1 w:1)
+	/// Proof: TrustlessFileServer PendingRechunks (max_values: None, max_size: Some(48), added: 2523, mode: MaxEncodedLen)
+	fn reject_rechunk() -> Weight {
+		// Proof Size summary in bytes:
+		//  Measured:  `214`
+		//  Estimated: `3513`
+		// Minimum execution time: 31_000_000 picoseconds.
+		Weight::from_parts(32_000_000, 3513)
+			.saturating_add(RocksDbWeight::get().reads(2_u64))
 			.saturating_add(RocksDbWeight::get().writes(1_u64))
 	}
 }